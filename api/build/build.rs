@@ -0,0 +1,31 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    build.rs
+
+Abstract:
+
+    File contains the entry point for the build time script that generates
+    the mailbox response structs and their host-side validators from a single
+    protocol description.
+
+--*/
+
+#[cfg(feature = "generate_protocol")]
+mod code_gen;
+#[cfg(feature = "generate_protocol")]
+mod protocol;
+
+// Main Entry point
+fn main() {
+    #[cfg(feature = "generate_protocol")]
+    {
+        let out_dir_os_str = std::env::var_os("OUT_DIR").unwrap();
+        let out_dir = out_dir_os_str.to_str().unwrap();
+
+        code_gen::CodeGen::gen_code(protocol::RESPONSES, "mailbox_resp.rs", out_dir);
+    }
+}
@@ -0,0 +1,164 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    code_gen.rs
+
+Abstract:
+
+    File contains code generation routines for mailbox responses
+
+--*/
+
+use crate::protocol::{Check, Field, Message, Ty};
+use quote::{__private::TokenStream, format_ident, quote};
+use std::{path::Path, process::Command};
+
+const HEADER: &str = "// Licensed under the Apache-2.0 license.
+//
+// Generated from build/protocol.rs. Regenerate with:
+//   cargo build -p caliptra-api --features generate_protocol
+// and copy the output into build/mailbox_resp.rs.
+";
+
+// Code Generator
+pub struct CodeGen {}
+
+impl CodeGen {
+    /// Generate code
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - Protocol description
+    /// * `file_name` - Output file name
+    /// * `out_path` - Output Path
+    pub fn gen_code(messages: &[Message], file_name: &str, out_path: &str) {
+        let file_path = Path::new(out_path).join(file_name);
+        std::fs::write(&file_path, Self::code(messages)).unwrap();
+        if Command::new("rustfmt")
+            .arg("--emit=files")
+            .arg("--edition=2021")
+            .arg(file_path)
+            .spawn()
+            .is_ok()
+        {}
+    }
+
+    fn code(messages: &[Message]) -> String {
+        let messages = messages.iter().map(Self::message);
+        let tokens = quote! {
+            #(#messages)*
+        };
+        format!("{HEADER}\n{tokens}")
+    }
+
+    fn message(message: &Message) -> TokenStream {
+        let name = format_ident!("{}", message.name);
+
+        let fields = message.fields.iter().map(|f| {
+            let field_name = format_ident!("{}", f.name);
+            let ty = Self::field_type(message.name, f);
+            quote! {
+                pub #field_name: #ty,
+            }
+        });
+
+        let consts = message.fields.iter().filter_map(|f| match f.ty {
+            Ty::StructArray { len_const, len, .. } => {
+                let len_const = format_ident!("{}", len_const);
+                Some(quote! {
+                    pub const #len_const: usize = #len;
+                })
+            }
+            _ => None,
+        });
+        let consts: Vec<TokenStream> = consts.collect();
+        let consts_impl = if consts.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                impl #name {
+                    #(#consts)*
+                }
+            }
+        };
+
+        let checks = message
+            .fields
+            .iter()
+            .flat_map(|f| f.checks.iter().map(|c| Self::check(message, f, c)));
+
+        quote! {
+            #[repr(C)]
+            #[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+            #[cfg_attr(feature = "serde", derive(serde_derive::Serialize, serde_derive::Deserialize))]
+            pub struct #name {
+                pub hdr: MailboxRespHeader,
+                #(#fields)*
+            }
+            #consts_impl
+            impl Response for #name {
+                fn validate(&self) -> Result<(), CaliptraApiError> {
+                    #(#checks)*
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn field_type(msg_name: &str, field: &Field) -> TokenStream {
+        match field.ty {
+            Ty::U32 => quote! { u32 },
+            Ty::U32Array(len) => quote! { [u32; #len] },
+            Ty::StructArray {
+                elem, len_const, ..
+            } => {
+                let msg_name = format_ident!("{}", msg_name);
+                let elem = format_ident!("{}", elem);
+                let len_const = format_ident!("{}", len_const);
+                quote! { [#elem; #msg_name::#len_const] }
+            }
+        }
+    }
+
+    fn check(message: &Message, field: &Field, check: &Check) -> TokenStream {
+        let field_name = format_ident!("{}", field.name);
+        let err_name = format!("{}.{}", message.name, field.name);
+        match check {
+            Check::Flags(flags) => {
+                let flags = format_ident!("{}", flags);
+                quote! {
+                    if #flags::from_bits(self.#field_name).is_none() {
+                        return Err(CaliptraApiError::MailboxRespInvalidField(#err_name));
+                    }
+                }
+            }
+            Check::CountOf(array) => {
+                let len_const = message
+                    .fields
+                    .iter()
+                    .find_map(|f| match f.ty {
+                        Ty::StructArray { len_const, .. } if f.name == *array => Some(len_const),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| panic!("{err_name}: no array field named {array}"));
+                let len_const = format_ident!("{}", len_const);
+                quote! {
+                    if self.#field_name as usize > Self::#len_const {
+                        return Err(CaliptraApiError::MailboxRespInvalidField(#err_name));
+                    }
+                }
+            }
+            Check::DisjointFrom(other) => {
+                let other = format_ident!("{}", other);
+                quote! {
+                    if self.#field_name & self.#other != 0 {
+                        return Err(CaliptraApiError::MailboxRespInvalidField(#err_name));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Licensed under the Apache-2.0 license.
+//
+// Generated from build/protocol.rs. Regenerate with:
+//   cargo build -p caliptra-api --features generate_protocol
+// and copy the output into build/mailbox_resp.rs.
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct SelfTestGetResultsResp {
+    pub hdr: MailboxRespHeader,
+    pub passed: u32,
+    pub failed: u32,
+}
+impl Response for SelfTestGetResultsResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        if SelfTestAlgorithms::from_bits(self.passed).is_none() {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "SelfTestGetResultsResp.passed",
+            ));
+        }
+        if self.passed & self.failed != 0 {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "SelfTestGetResultsResp.passed",
+            ));
+        }
+        if SelfTestAlgorithms::from_bits(self.failed).is_none() {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "SelfTestGetResultsResp.failed",
+            ));
+        }
+        Ok(())
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct ZeroizeResp {
+    pub hdr: MailboxRespHeader,
+    pub status: u32,
+    pub kv_locked_slots: u32,
+}
+impl Response for ZeroizeResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        if ZeroizeStatus::from_bits(self.status).is_none() {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "ZeroizeResp.status",
+            ));
+        }
+        Ok(())
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct GetUpdateHistoryResp {
+    pub hdr: MailboxRespHeader,
+    pub total_updates: u32,
+    pub entry_count: u32,
+    pub entries: [FwUpdateHistoryEntry; GetUpdateHistoryResp::MAX_ENTRIES],
+}
+impl GetUpdateHistoryResp {
+    pub const MAX_ENTRIES: usize = 8usize;
+}
+impl Response for GetUpdateHistoryResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        if self.entry_count as usize > Self::MAX_ENTRIES {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "GetUpdateHistoryResp.entry_count",
+            ));
+        }
+        Ok(())
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct IncrementFuseSvnResp {
+    pub hdr: MailboxRespHeader,
+    pub fuse_svn: u32,
+    pub new_fuse_svn: u32,
+    pub fuse_runtime_svn: [u32; 4usize],
+}
+impl Response for IncrementFuseSvnResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        Ok(())
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct GetDpeStatsResp {
+    pub hdr: MailboxRespHeader,
+    pub used_contexts: u32,
+    pub free_contexts: u32,
+    pub pl0_used_contexts: u32,
+    pub pl1_used_contexts: u32,
+    pub tagged_contexts: u32,
+    pub exported_cdi_handles: u32,
+}
+impl Response for GetDpeStatsResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        Ok(())
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct GetKvStatusResp {
+    pub hdr: MailboxRespHeader,
+    pub occupied_slots: u32,
+    pub use_locked_slots: u32,
+    pub write_locked_slots: u32,
+    pub slots: [KvSlotInfo; GetKvStatusResp::SLOT_COUNT],
+}
+impl GetKvStatusResp {
+    pub const SLOT_COUNT: usize = 32usize;
+}
+impl Response for GetKvStatusResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    protocol.rs
+
+Abstract:
+
+    File contains the mailbox protocol description. Every response listed here
+    is generated, together with its validator, into build/mailbox_resp.rs.
+
+--*/
+
+/// Field layout
+pub enum Ty {
+    /// Little endian u32
+    U32,
+
+    /// Fixed length array of u32
+    U32Array(usize),
+
+    /// Array of a hand written struct, with its length exposed as an
+    /// associated constant of the response
+    StructArray {
+        elem: &'static str,
+        len_const: &'static str,
+        len: usize,
+    },
+}
+
+/// Constraint checked by the host before the response is handed out
+pub enum Check {
+    /// Only bits defined by the named bitflags type may be set
+    Flags(&'static str),
+
+    /// Number of valid elements in an array; may not exceed its length
+    CountOf(&'static str),
+
+    /// No bit may be set in both this field and the named field
+    DisjointFrom(&'static str),
+}
+
+pub struct Field {
+    pub name: &'static str,
+    pub ty: Ty,
+    pub checks: &'static [Check],
+}
+
+pub struct Message {
+    pub name: &'static str,
+    pub fields: &'static [Field],
+}
+
+const fn field(name: &'static str, ty: Ty, checks: &'static [Check]) -> Field {
+    Field { name, ty, checks }
+}
+
+/// Responses generated from this description. The `hdr` field is implied.
+pub const RESPONSES: &[Message] = &[
+    // FIPS_SELF_TEST_GET_RESULTS
+    Message {
+        name: "SelfTestGetResultsResp",
+        fields: &[
+            field(
+                "passed",
+                Ty::U32,
+                &[
+                    Check::Flags("SelfTestAlgorithms"),
+                    Check::DisjointFrom("failed"),
+                ],
+            ),
+            field("failed", Ty::U32, &[Check::Flags("SelfTestAlgorithms")]),
+        ],
+    },
+    // FIPS_ZEROIZE
+    Message {
+        name: "ZeroizeResp",
+        fields: &[
+            field("status", Ty::U32, &[Check::Flags("ZeroizeStatus")]),
+            field("kv_locked_slots", Ty::U32, &[]),
+        ],
+    },
+    // GET_UPDATE_HISTORY
+    Message {
+        name: "GetUpdateHistoryResp",
+        fields: &[
+            field("total_updates", Ty::U32, &[]),
+            field("entry_count", Ty::U32, &[Check::CountOf("entries")]),
+            field(
+                "entries",
+                Ty::StructArray {
+                    elem: "FwUpdateHistoryEntry",
+                    len_const: "MAX_ENTRIES",
+                    len: 8,
+                },
+                &[],
+            ),
+        ],
+    },
+    // INCREMENT_FUSE_SVN
+    Message {
+        name: "IncrementFuseSvnResp",
+        fields: &[
+            field("fuse_svn", Ty::U32, &[]),
+            field("new_fuse_svn", Ty::U32, &[]),
+            field("fuse_runtime_svn", Ty::U32Array(4), &[]),
+        ],
+    },
+    // GET_DPE_STATS
+    Message {
+        name: "GetDpeStatsResp",
+        fields: &[
+            field("used_contexts", Ty::U32, &[]),
+            field("free_contexts", Ty::U32, &[]),
+            field("pl0_used_contexts", Ty::U32, &[]),
+            field("pl1_used_contexts", Ty::U32, &[]),
+            field("tagged_contexts", Ty::U32, &[]),
+            field("exported_cdi_handles", Ty::U32, &[]),
+        ],
+    },
+    // GET_KV_STATUS
+    Message {
+        name: "GetKvStatusResp",
+        fields: &[
+            field("occupied_slots", Ty::U32, &[]),
+            field("use_locked_slots", Ty::U32, &[]),
+            field("write_locked_slots", Ty::U32, &[]),
+            field(
+                "slots",
+                Ty::StructArray {
+                    elem: "KvSlotInfo",
+                    len_const: "SLOT_COUNT",
+                    len: 32,
+                },
+                &[],
+            ),
+        ],
+    },
+];
@@ -20,6 +20,12 @@ bitflags::bitflags! {
         const ROM_BASE = 0b1;
         // Represents base capabilities present in Caliptra Runtime v1.0
         const RT_BASE = 0b1 << 64;
+        // Runtime accepts versioned request headers and reports per-command
+        // versions through CAPABILITIES version 2
+        const RT_CMD_VERSIONING = 0b10 << 64;
+        // Runtime reports every supported command and its request versions
+        // through CAPABILITIES version 3
+        const RT_CMD_NEGOTIATION = 0b100 << 64;
     }
 }
 
@@ -0,0 +1,355 @@
+// Licensed under the Apache-2.0 license
+
+//! Collection of the attestation evidence a verifier needs from Caliptra in
+//! a single call.
+//!
+//! [`collect_evidence`] sends, in order, VERSION, FW_INFO, the certificates
+//! of the Caliptra-owned keys, a DPE GetProfile command, a DPE CertifyKey
+//! command whose label is the verifier's nonce, and QUOTE_PCRS. The certified
+//! key is derived from the label, so every nonce yields a fresh leaf key and
+//! certificate.
+//!
+//! The CertifyKey command is built for the profile GetProfile reports. The
+//! P-256 profile takes a 32-byte label, so only the first 32 bytes of the
+//! nonce are used there.
+
+use crate::{
+    mailbox::{
+        FipsVersionReq, FwInfoResp, FwInfoVersionedReq, GetFmcAliasCertReq, GetLdevCertReq,
+        GetRtAliasCertReq, InvokeDpeReq, InvokeDpeResp, MailboxReqHeader,
+        MailboxReqVersionedHeader, QuotePcrsReq, ResponseVarSize,
+    },
+    CaliptraApiError, SocManager,
+};
+use alloc::{vec, vec::Vec};
+use core::mem::size_of;
+use serde_derive::{Deserialize, Serialize};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+/// Size of the DPE label, and of the nonce passed to `collect_evidence`
+pub const NONCE_SIZE: usize = 48;
+
+const DPE_COMMAND_MAGIC: u32 = 0x4450_4543; // "DPEC"
+const DPE_RESPONSE_MAGIC: u32 = 0x4450_4552; // "DPER"
+const DPE_PROFILE_P256_SHA256: u32 = 3;
+const DPE_PROFILE_P384_SHA384: u32 = 4;
+const DPE_GET_PROFILE: u32 = 0x1;
+const DPE_CERTIFY_KEY: u32 = 0x9;
+const DPE_FORMAT_X509: u32 = 0;
+const DPE_HANDLE_SIZE: usize = 16;
+const DPE_MAX_CERT_SIZE: usize = 6144;
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout)]
+struct DpeCmdHdr {
+    magic: u32,
+    cmd_id: u32,
+    profile: u32,
+}
+
+/// CertifyKey command, up to the label whose size depends on the profile
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout)]
+struct DpeCertifyKeyCmdHdr {
+    hdr: DpeCmdHdr,
+    handle: [u8; DPE_HANDLE_SIZE],
+    flags: u32,
+    format: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout)]
+struct DpeRespHdr {
+    magic: u32,
+    status: u32,
+    profile: u32,
+}
+
+/// CertifyKey response, up to the public key whose size depends on the
+/// profile
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout)]
+struct DpeCertifyKeyRespHdr {
+    hdr: DpeRespHdr,
+    new_context_handle: [u8; DPE_HANDLE_SIZE],
+}
+
+/// Firmware identity reported by FW_INFO
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FwIdentity {
+    pub pl0_pauser: u32,
+    pub runtime_svn: u32,
+    pub min_runtime_svn: u32,
+    pub fmc_manifest_svn: u32,
+    pub rom_revision: [u8; 20],
+    pub fmc_revision: [u8; 20],
+    pub runtime_revision: [u8; 20],
+    pub rom_sha256_digest: [u32; 8],
+    pub fmc_sha384_digest: [u32; 12],
+    pub runtime_sha384_digest: [u32; 12],
+    pub owner_pub_key_hash: [u32; 12],
+}
+
+impl From<&FwInfoResp> for FwIdentity {
+    fn from(info: &FwInfoResp) -> Self {
+        Self {
+            pl0_pauser: info.pl0_pauser,
+            runtime_svn: info.runtime_svn,
+            min_runtime_svn: info.min_runtime_svn,
+            fmc_manifest_svn: info.fmc_manifest_svn,
+            rom_revision: info.rom_revision,
+            fmc_revision: info.fmc_revision,
+            runtime_revision: info.runtime_revision,
+            rom_sha256_digest: info.rom_sha256_digest,
+            fmc_sha384_digest: info.fmc_sha384_digest,
+            runtime_sha384_digest: info.runtime_sha384_digest,
+            owner_pub_key_hash: info.owner_pub_key_hash,
+        }
+    }
+}
+
+/// PCR quote returned by QUOTE_PCRS
+///
+/// `digest` is the SHA-384 hash of the PCRs followed by the nonce, signed by
+/// the FMC Alias key.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcrQuote {
+    pub pcrs: Vec<Vec<u8>>,
+    pub nonce: Vec<u8>,
+    pub digest: Vec<u8>,
+    pub reset_ctrs: Vec<u32>,
+    pub signature_r: Vec<u8>,
+    pub signature_s: Vec<u8>,
+}
+
+/// Attestation evidence returned by `collect_evidence`
+///
+/// Certificates are DER encoded. The chain runs from the LDevID certificate,
+/// issued by the IDevID key, to the DPE leaf certificate.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    /// FIPS mode, from VERSION
+    pub fips_mode: u32,
+
+    /// Hardware, ROM and firmware revisions, from VERSION
+    pub fips_rev: [u32; 3],
+
+    /// Module name, from VERSION
+    pub fips_name: [u8; 12],
+
+    pub fw: FwIdentity,
+    pub ldev_cert: Vec<u8>,
+    pub fmc_alias_cert: Vec<u8>,
+    pub rt_alias_cert: Vec<u8>,
+
+    /// Label the DPE leaf key was derived from
+    pub nonce: Vec<u8>,
+
+    /// DPE profile reported by GetProfile
+    pub dpe_profile: u32,
+
+    /// Uncompressed point of the DPE leaf key, on the curve of `dpe_profile`
+    pub dpe_pub_key_x: Vec<u8>,
+    pub dpe_pub_key_y: Vec<u8>,
+
+    /// DPE leaf certificate, issued by the RT Alias key
+    pub dpe_cert: Vec<u8>,
+
+    /// PCR quote over the first 32 bytes of the nonce
+    pub pcr_quote: PcrQuote,
+}
+
+/// Collect the evidence for a verifier that sent `nonce`
+///
+/// Stops at the first command that fails. Returns
+/// `CaliptraApiError::AttestationDisabled` if FW_INFO reports that
+/// attestation was disabled, since the DPE certificate would not be issued,
+/// and `CaliptraApiError::MailboxRespInvalidField("dpe_profile")` if DPE
+/// reports a profile other than P-256 or P-384.
+pub fn collect_evidence<S: SocManager + ?Sized>(
+    soc: &mut S,
+    nonce: &[u8; NONCE_SIZE],
+) -> Result<Evidence, CaliptraApiError> {
+    let mut resp_bytes = vec![0u8; size_of::<InvokeDpeResp>()];
+
+    let version = soc.mailbox_exec_req(FipsVersionReq::default(), &mut resp_bytes)?;
+
+    let fw_info = soc.mailbox_exec_req(
+        FwInfoVersionedReq {
+            hdr: MailboxReqVersionedHeader {
+                version: FwInfoVersionedReq::VERSION,
+                ..Default::default()
+            },
+        },
+        &mut resp_bytes,
+    )?;
+    if fw_info.attestation_disabled != 0 {
+        return Err(CaliptraApiError::AttestationDisabled);
+    }
+
+    let ldev_cert = cert_data(&soc.mailbox_exec_req(GetLdevCertReq::default(), &mut resp_bytes)?)?;
+    let fmc_alias_cert =
+        cert_data(&soc.mailbox_exec_req(GetFmcAliasCertReq::default(), &mut resp_bytes)?)?;
+    let rt_alias_cert =
+        cert_data(&soc.mailbox_exec_req(GetRtAliasCertReq::default(), &mut resp_bytes)?)?;
+
+    // The profile in the GetProfile command is ignored, and the one DPE
+    // implements is returned in the response header.
+    let get_profile = DpeCmdHdr {
+        magic: DPE_COMMAND_MAGIC,
+        cmd_id: DPE_GET_PROFILE,
+        profile: DPE_PROFILE_P384_SHA384,
+    };
+    let resp = invoke_dpe(soc, &[get_profile.as_bytes()], &mut resp_bytes)?;
+    let (dpe_hdr, _) = DpeRespHdr::read_from_prefix(dpe_data(&resp)?)
+        .map_err(|_| CaliptraApiError::MailboxRespInvalidField("data_size"))?;
+    let dpe_profile = dpe_hdr.profile;
+    let key_size = dpe_key_size(dpe_profile)?;
+
+    let certify_key = DpeCertifyKeyCmdHdr {
+        hdr: DpeCmdHdr {
+            magic: DPE_COMMAND_MAGIC,
+            cmd_id: DPE_CERTIFY_KEY,
+            profile: dpe_profile,
+        },
+        handle: [0; DPE_HANDLE_SIZE],
+        flags: 0,
+        format: DPE_FORMAT_X509,
+    };
+    let resp = invoke_dpe(
+        soc,
+        &[certify_key.as_bytes(), &nonce[..key_size]],
+        &mut resp_bytes,
+    )?;
+    let (_, rest) = DpeCertifyKeyRespHdr::read_from_prefix(dpe_data(&resp)?)
+        .map_err(|_| CaliptraApiError::MailboxRespInvalidField("data_size"))?;
+    let (dpe_pub_key_x, rest) = split(rest, key_size)?;
+    let (dpe_pub_key_y, rest) = split(rest, key_size)?;
+    let (cert_size, cert) = split(rest, size_of::<u32>())?;
+    let cert_size = u32::from_le_bytes(cert_size.try_into().unwrap()) as usize;
+    let dpe_cert = cert
+        .get(..cert_size)
+        .filter(|cert| cert.len() <= DPE_MAX_CERT_SIZE)
+        .ok_or(CaliptraApiError::MailboxRespInvalidField("cert_size"))?;
+    let dpe_pub_key_x = dpe_pub_key_x.to_vec();
+    let dpe_pub_key_y = dpe_pub_key_y.to_vec();
+    let dpe_cert = dpe_cert.to_vec();
+
+    let mut quote_req = QuotePcrsReq {
+        hdr: MailboxReqHeader::default(),
+        nonce: [0; 32],
+    };
+    quote_req.nonce.copy_from_slice(&nonce[..32]);
+    let quote = soc.mailbox_exec_req(quote_req, &mut resp_bytes)?;
+
+    Ok(Evidence {
+        fips_mode: version.mode,
+        fips_rev: version.fips_rev,
+        fips_name: version.name,
+        fw: FwIdentity::from(&fw_info),
+        ldev_cert,
+        fmc_alias_cert,
+        rt_alias_cert,
+        nonce: nonce.to_vec(),
+        dpe_profile,
+        dpe_pub_key_x,
+        dpe_pub_key_y,
+        dpe_cert,
+        pcr_quote: PcrQuote {
+            pcrs: quote.pcrs.iter().map(|pcr| pcr.to_vec()).collect(),
+            nonce: quote.nonce.to_vec(),
+            digest: quote.digest.to_vec(),
+            reset_ctrs: quote.reset_ctrs.to_vec(),
+            signature_r: quote.signature_r.to_vec(),
+            signature_s: quote.signature_s.to_vec(),
+        },
+    })
+}
+
+/// Send the concatenation of `cmd` to DPE with INVOKE_DPE
+fn invoke_dpe<S: SocManager + ?Sized>(
+    soc: &mut S,
+    cmd: &[&[u8]],
+    resp_bytes: &mut [u8],
+) -> Result<InvokeDpeResp, CaliptraApiError> {
+    let mut req = InvokeDpeReq {
+        hdr: MailboxReqHeader::default(),
+        ..Default::default()
+    };
+    let mut len = 0;
+    for part in cmd {
+        req.data[len..len + part.len()].copy_from_slice(part);
+        len += part.len();
+    }
+    req.data_size = len as u32;
+    soc.mailbox_exec_req(req, resp_bytes)
+}
+
+/// DPE response of `resp`, once its header shows that the command passed
+fn dpe_data(resp: &InvokeDpeResp) -> Result<&[u8], CaliptraApiError> {
+    let dpe_resp = resp
+        .data()
+        .map_err(|_| CaliptraApiError::MailboxRespInvalidField("data_size"))?;
+    let (dpe_hdr, _) = DpeRespHdr::read_from_prefix(dpe_resp)
+        .map_err(|_| CaliptraApiError::MailboxRespInvalidField("data_size"))?;
+    if dpe_hdr.magic != DPE_RESPONSE_MAGIC {
+        return Err(CaliptraApiError::MailboxRespInvalidField("dpe_magic"));
+    }
+    if dpe_hdr.status != 0 {
+        return Err(CaliptraApiError::DpeCmdFailed(dpe_hdr.status));
+    }
+    Ok(dpe_resp)
+}
+
+/// Size of the labels, coordinates and digests of a DPE profile
+fn dpe_key_size(profile: u32) -> Result<usize, CaliptraApiError> {
+    match profile {
+        DPE_PROFILE_P256_SHA256 => Ok(32),
+        DPE_PROFILE_P384_SHA384 => Ok(48),
+        _ => Err(CaliptraApiError::MailboxRespInvalidField("dpe_profile")),
+    }
+}
+
+fn split(data: &[u8], mid: usize) -> Result<(&[u8], &[u8]), CaliptraApiError> {
+    if data.len() < mid {
+        return Err(CaliptraApiError::MailboxRespInvalidField("data_size"));
+    }
+    Ok(data.split_at(mid))
+}
+
+fn cert_data<R: ResponseVarSize>(resp: &R) -> Result<Vec<u8>, CaliptraApiError> {
+    resp.data()
+        .map(|data| data.to_vec())
+        .map_err(|_| CaliptraApiError::MailboxRespInvalidField("data_size"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dpe_layout() {
+        // Sizes from the DPE profile for P-384
+        let key_size = dpe_key_size(DPE_PROFILE_P384_SHA384).unwrap();
+        assert_eq!(size_of::<DpeCertifyKeyCmdHdr>() + key_size, 12 + 72);
+        assert_eq!(
+            size_of::<DpeCertifyKeyRespHdr>() + 2 * key_size + size_of::<u32>(),
+            12 + 116
+        );
+        assert!(size_of::<DpeCertifyKeyCmdHdr>() + key_size <= InvokeDpeReq::DATA_MAX_SIZE);
+        assert!(
+            size_of::<DpeCertifyKeyRespHdr>() + 2 * key_size + size_of::<u32>() + DPE_MAX_CERT_SIZE
+                <= InvokeDpeResp::DATA_MAX_SIZE
+        );
+    }
+
+    #[test]
+    fn test_dpe_key_size() {
+        assert_eq!(dpe_key_size(DPE_PROFILE_P256_SHA256), Ok(32));
+        assert_eq!(dpe_key_size(DPE_PROFILE_P384_SHA384), Ok(48));
+        assert_eq!(
+            dpe_key_size(0),
+            Err(CaliptraApiError::MailboxRespInvalidField("dpe_profile"))
+        );
+    }
+}
@@ -1,15 +1,23 @@
 // Licensed under the Apache-2.0 license
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "evidence")]
+extern crate alloc;
+
 mod capabilities;
 mod checksum;
+#[cfg(feature = "evidence")]
+pub mod evidence;
 pub mod mailbox;
 pub mod soc_mgr;
+pub mod transport;
+pub mod wire;
 
 pub use caliptra_error as error;
 pub use capabilities::Capabilities;
 pub use checksum::{calc_checksum, verify_checksum};
 pub use soc_mgr::SocManager;
+pub use transport::AsyncMailboxTransport;
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum CaliptraApiError {
@@ -25,6 +33,7 @@ pub enum CaliptraApiError {
         actual: u32,
     },
     MailboxRespInvalidFipsStatus(u32),
+    MailboxRespInvalidField(&'static str),
     MailboxRespInvalidChecksum {
         expected: u32,
         actual: u32,
@@ -43,4 +52,6 @@ pub enum CaliptraApiError {
     FusesAlreadyIniitalized,
     FuseDoneNotSet,
     StashMeasurementFailed,
+    DpeCmdFailed(u32),
+    AttestationDisabled,
 }
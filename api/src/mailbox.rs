@@ -42,6 +42,8 @@ impl CommandId {
     pub const SELF_TEST_GET_RESULTS: Self = Self(0x4650_4C67); // "FPGR"
     /// The shutdown command.
     pub const SHUTDOWN: Self = Self(0x4650_5344); // "FPSD"
+    /// The zeroize command.
+    pub const ZEROIZE: Self = Self(0x4650_5A52); // "FPZR"
 
     // The capabilities command.
     pub const CAPABILITIES: Self = Self(0x4341_5053); // "CAPS"
@@ -58,8 +60,194 @@ impl CommandId {
     // The get FMC Alias CSR command.
     pub const GET_FMC_ALIAS_CSR: Self = Self(0x464D_4352); // "FMCR"
 
+    // The get RT Alias CSR command.
+    pub const GET_RT_ALIAS_CSR: Self = Self(0x5254_4352); // "RTCR"
+
     // The sign with exported ecdsa command.
     pub const SIGN_WITH_EXPORTED_ECDSA: Self = Self(0x5357_4545); // "SWEE"
+
+    // The revoke locality command.
+    pub const REVOKE_LOCALITY: Self = Self(0x5256_4C43); // "RVLC"
+
+    // The error injection command.
+    pub const INJECT_ERROR: Self = Self(0x4945_5252); // "IERR"
+
+    // The get firmware update history command.
+    pub const GET_UPDATE_HISTORY: Self = Self(0x4755_5048); // "GUPH"
+
+    // The increment runtime SVN fuse command.
+    pub const INCREMENT_FUSE_SVN: Self = Self(0x4946_5356); // "IFSV"
+
+    // The get DPE stats command (test builds only).
+    pub const GET_DPE_STATS: Self = Self(0x4744_5053); // "GDPS"
+
+    // The commit owner key rotation command.
+    pub const COMMIT_OWNER_KEY_ROTATION: Self = Self(0x434F_4B52); // "COKR"
+
+    // The set certificate issuance policy command.
+    pub const SET_CERT_POLICY: Self = Self(0x5343_504C); // "SCPL"
+
+    // The set certificate distribution points command.
+    pub const SET_CERT_DIST_POINTS: Self = Self(0x4344_5054); // "CDPT"
+
+    // The get key vault status command.
+    pub const GET_KV_STATUS: Self = Self(0x474B_5653); // "GKVS"
+
+    // The raw entropy capture commands (manufacturing only).
+    pub const CAPTURE_RAW_ENTROPY: Self = Self(0x4352_4157); // "CRAW"
+    pub const GET_RAW_ENTROPY: Self = Self(0x4752_4157); // "GRAW"
+
+    // The asynchronous job commands.
+    pub const START_JOB: Self = Self(0x4A53_5452); // "JSTR"
+    pub const GET_JOB_STATUS: Self = Self(0x4A53_5453); // "JSTS"
+
+    // The command batching command.
+    pub const BATCH: Self = Self(0x4241_5443); // "BATC"
+
+    // The SoC power-state transition commands.
+    pub const SUSPEND_PREPARE: Self = Self(0x5355_5350); // "SUSP"
+    pub const RESUME_NOTIFY: Self = Self(0x5245_534D); // "RESM"
+    pub const PREPARE_FOR_SLEEP: Self = Self(0x5046_534C); // "PFSL"
+
+    // The watchdog configuration command.
+    pub const CONFIGURE_WDT: Self = Self(0x4357_4454); // "CWDT"
+
+    // The owner-defined object verification commands.
+    pub const SET_OBJECT_SCHEMAS: Self = Self(0x534F_5343); // "SOSC"
+    pub const VERIFY_OBJECT: Self = Self(0x564F_424A); // "VOBJ"
+
+    // The get crash dump command.
+    pub const GET_CRASH_DUMP: Self = Self(0x4743_5244); // "GCRD"
+
+    // The log level command.
+    pub const SET_LOG_LEVEL: Self = Self(0x534C_4C56); // "SLLV"
+
+    // The boot profile command.
+    pub const GET_BOOT_PROFILE: Self = Self(0x4742_5046); // "GBPF"
+
+    // The get provisioning status command.
+    pub const GET_PROVISIONING_STATUS: Self = Self(0x4750_5253); // "GPRS"
+
+    // The field entropy re-seed command.
+    pub const RESEED_FIELD_ENTROPY: Self = Self(0x5253_4645); // "RSFE"
+
+    // The entropy source health command.
+    pub const GET_ENTROPY_HEALTH: Self = Self(0x4745_4E48); // "GENH"
+
+    // The get firmware handoff table command (debug only).
+    pub const GET_FHT: Self = Self(0x4746_4854); // "GFHT"
+
+    // The SPDM message transport command.
+    pub const SPDM: Self = Self(0x5350_444D); // "SPDM"
+
+    // The MCTP message transport command.
+    pub const MCTP: Self = Self(0x4D43_5450); // "MCTP"
+
+    // The owner-signed measurement manifest command.
+    pub const SET_MEASUREMENT_MANIFEST: Self = Self(0x534D_4D46); // "SMMF"
+
+    // The SoC image stream verification commands.
+    pub const START_STREAM_VERIFY: Self = Self(0x5354_5356); // "STSV"
+    pub const STREAM_DATA: Self = Self(0x5354_4441); // "STDA"
+    pub const FINISH_STREAM_VERIFY: Self = Self(0x464E_5356); // "FNSV"
+
+    // The sealed key commands.
+    pub const SEAL_KEY: Self = Self(0x5345_414C); // "SEAL"
+    pub const UNSEAL_KEY: Self = Self(0x554E_534C); // "UNSL"
+
+    // The monotonic counter commands.
+    pub const CREATE_COUNTER: Self = Self(0x4343_5452); // "CCTR"
+    pub const INCREMENT_COUNTER: Self = Self(0x4943_5452); // "ICTR"
+    pub const READ_COUNTER: Self = Self(0x5243_5452); // "RCTR"
+
+    // The signed counter timestamp command.
+    pub const TIMESTAMP: Self = Self(0x5453_5450); // "TSTP"
+
+    // The authenticated mailbox pairing command.
+    pub const PAIR_MAILBOX: Self = Self(0x5041_4952); // "PAIR"
+
+    /// Bit assigned to each command in the supported command bitmap reported
+    /// by CAPABILITIES version 3. Bits are never reassigned; new commands are
+    /// appended.
+    pub const CAPABILITY_BITS: [CommandId; 72] = [
+        CommandId::FIRMWARE_LOAD,
+        CommandId::GET_IDEV_CERT,
+        CommandId::GET_IDEV_INFO,
+        CommandId::POPULATE_IDEV_CERT,
+        CommandId::GET_LDEV_CERT,
+        CommandId::GET_FMC_ALIAS_CERT,
+        CommandId::GET_RT_ALIAS_CERT,
+        CommandId::ECDSA384_VERIFY,
+        CommandId::LMS_VERIFY,
+        CommandId::STASH_MEASUREMENT,
+        CommandId::INVOKE_DPE,
+        CommandId::DISABLE_ATTESTATION,
+        CommandId::FW_INFO,
+        CommandId::DPE_TAG_TCI,
+        CommandId::DPE_GET_TAGGED_TCI,
+        CommandId::INCREMENT_PCR_RESET_COUNTER,
+        CommandId::QUOTE_PCRS,
+        CommandId::EXTEND_PCR,
+        CommandId::ADD_SUBJECT_ALT_NAME,
+        CommandId::CERTIFY_KEY_EXTENDED,
+        CommandId::VERSION,
+        CommandId::SELF_TEST_START,
+        CommandId::SELF_TEST_GET_RESULTS,
+        CommandId::SHUTDOWN,
+        CommandId::ZEROIZE,
+        CommandId::CAPABILITIES,
+        CommandId::SET_AUTH_MANIFEST,
+        CommandId::AUTHORIZE_AND_STASH,
+        CommandId::GET_IDEV_CSR,
+        CommandId::GET_FMC_ALIAS_CSR,
+        CommandId::GET_RT_ALIAS_CSR,
+        CommandId::SIGN_WITH_EXPORTED_ECDSA,
+        CommandId::REVOKE_LOCALITY,
+        CommandId::INJECT_ERROR,
+        CommandId::GET_UPDATE_HISTORY,
+        CommandId::INCREMENT_FUSE_SVN,
+        CommandId::GET_DPE_STATS,
+        CommandId::COMMIT_OWNER_KEY_ROTATION,
+        CommandId::SET_CERT_POLICY,
+        CommandId::SET_CERT_DIST_POINTS,
+        CommandId::GET_KV_STATUS,
+        CommandId::CAPTURE_RAW_ENTROPY,
+        CommandId::GET_RAW_ENTROPY,
+        CommandId::START_JOB,
+        CommandId::GET_JOB_STATUS,
+        CommandId::BATCH,
+        CommandId::SUSPEND_PREPARE,
+        CommandId::RESUME_NOTIFY,
+        CommandId::PREPARE_FOR_SLEEP,
+        CommandId::CONFIGURE_WDT,
+        CommandId::SET_OBJECT_SCHEMAS,
+        CommandId::VERIFY_OBJECT,
+        CommandId::GET_CRASH_DUMP,
+        CommandId::SET_LOG_LEVEL,
+        CommandId::GET_BOOT_PROFILE,
+        CommandId::GET_PROVISIONING_STATUS,
+        CommandId::RESEED_FIELD_ENTROPY,
+        CommandId::GET_ENTROPY_HEALTH,
+        CommandId::GET_FHT,
+        CommandId::SPDM,
+        CommandId::MCTP,
+        CommandId::SET_MEASUREMENT_MANIFEST,
+        CommandId::START_STREAM_VERIFY,
+        CommandId::STREAM_DATA,
+        CommandId::FINISH_STREAM_VERIFY,
+        CommandId::SEAL_KEY,
+        CommandId::UNSEAL_KEY,
+        CommandId::CREATE_COUNTER,
+        CommandId::INCREMENT_COUNTER,
+        CommandId::READ_COUNTER,
+        CommandId::TIMESTAMP,
+        CommandId::PAIR_MAILBOX,
+    ];
+
+    /// Returns the bit assigned to this command in `CAPABILITY_BITS`
+    pub fn capability_bit(&self) -> Option<usize> {
+        Self::CAPABILITY_BITS.iter().position(|cmd| cmd == self)
+    }
 }
 
 impl From<u32> for CommandId {
@@ -94,6 +282,12 @@ where
         // Note: This will panic if sizeof::<Self>() < 4
         populate_checksum(self.as_mut_bytes());
     }
+
+    /// Check the response fields against the constraints in the protocol
+    /// description. Called by the host once the checksum has been verified.
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        Ok(())
+    }
 }
 
 #[repr(C)]
@@ -160,7 +354,39 @@ pub enum MailboxResp {
     AuthorizeAndStash(AuthorizeAndStashResp),
     GetIdevCsr(GetIdevCsrResp),
     GetFmcAliasCsr(GetFmcAliasCsrResp),
+    GetRtAliasCsr(GetRtAliasCsrResp),
     SignWithExportedEcdsa(SignWithExportedEcdsaResp),
+    SelfTestGetResults(SelfTestGetResultsResp),
+    Zeroize(ZeroizeResp),
+    GetUpdateHistory(GetUpdateHistoryResp),
+    IncrementFuseSvn(IncrementFuseSvnResp),
+    GetDpeStats(GetDpeStatsResp),
+    GetKvStatus(GetKvStatusResp),
+    GetRawEntropy(GetRawEntropyResp),
+    CapabilitiesCmdVersion(CapabilitiesCmdVersionResp),
+    CapabilitiesNegotiate(CapabilitiesNegotiateResp),
+    FwInfoV1(FwInfoV1Resp),
+    QuotePcrsCwt(QuotePcrsCwtResp),
+    StartJob(StartJobResp),
+    GetJobStatus(GetJobStatusResp),
+    Batch(BatchResp),
+    VerifyObject(VerifyObjectResp),
+    GetCrashDump(GetCrashDumpResp),
+    GetBootProfile(GetBootProfileResp),
+    GetProvisioningStatus(GetProvisioningStatusResp),
+    GetEntropyHealth(GetEntropyHealthResp),
+    GetFht(GetFhtResp),
+    Spdm(SpdmResp),
+    Mctp(MctpResp),
+    SetMeasurementManifest(SetMeasurementManifestResp),
+    FinishStreamVerify(FinishStreamVerifyResp),
+    SealKey(SealKeyResp),
+    UnsealKey(UnsealKeyResp),
+    CreateCounter(CreateCounterResp),
+    IncrementCounter(IncrementCounterResp),
+    ReadCounter(ReadCounterResp),
+    Timestamp(TimestampResp),
+    PairMailbox(PairMailboxResp),
 }
 
 impl MailboxResp {
@@ -183,7 +409,39 @@ impl MailboxResp {
             MailboxResp::AuthorizeAndStash(resp) => Ok(resp.as_bytes()),
             MailboxResp::GetIdevCsr(resp) => Ok(resp.as_bytes()),
             MailboxResp::GetFmcAliasCsr(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetRtAliasCsr(resp) => Ok(resp.as_bytes()),
             MailboxResp::SignWithExportedEcdsa(resp) => Ok(resp.as_bytes()),
+            MailboxResp::SelfTestGetResults(resp) => Ok(resp.as_bytes()),
+            MailboxResp::Zeroize(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetUpdateHistory(resp) => Ok(resp.as_bytes()),
+            MailboxResp::IncrementFuseSvn(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetDpeStats(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetKvStatus(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetRawEntropy(resp) => Ok(resp.as_bytes()),
+            MailboxResp::CapabilitiesCmdVersion(resp) => Ok(resp.as_bytes()),
+            MailboxResp::CapabilitiesNegotiate(resp) => Ok(resp.as_bytes()),
+            MailboxResp::FwInfoV1(resp) => Ok(resp.as_bytes()),
+            MailboxResp::QuotePcrsCwt(resp) => resp.as_bytes_partial(),
+            MailboxResp::StartJob(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetJobStatus(resp) => Ok(resp.as_bytes()),
+            MailboxResp::Batch(resp) => resp.as_bytes_partial(),
+            MailboxResp::VerifyObject(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetCrashDump(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetBootProfile(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetProvisioningStatus(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetEntropyHealth(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetFht(resp) => Ok(resp.as_bytes()),
+            MailboxResp::Spdm(resp) => resp.as_bytes_partial(),
+            MailboxResp::Mctp(resp) => resp.as_bytes_partial(),
+            MailboxResp::SetMeasurementManifest(resp) => Ok(resp.as_bytes()),
+            MailboxResp::FinishStreamVerify(resp) => Ok(resp.as_bytes()),
+            MailboxResp::SealKey(resp) => Ok(resp.as_bytes()),
+            MailboxResp::UnsealKey(resp) => Ok(resp.as_bytes()),
+            MailboxResp::CreateCounter(resp) => Ok(resp.as_bytes()),
+            MailboxResp::IncrementCounter(resp) => Ok(resp.as_bytes()),
+            MailboxResp::ReadCounter(resp) => Ok(resp.as_bytes()),
+            MailboxResp::Timestamp(resp) => resp.as_bytes_partial(),
+            MailboxResp::PairMailbox(resp) => Ok(resp.as_bytes()),
         }
     }
 
@@ -206,7 +464,39 @@ impl MailboxResp {
             MailboxResp::AuthorizeAndStash(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::GetIdevCsr(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::GetFmcAliasCsr(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetRtAliasCsr(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::SignWithExportedEcdsa(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::SelfTestGetResults(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::Zeroize(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetUpdateHistory(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::IncrementFuseSvn(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetDpeStats(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetKvStatus(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetRawEntropy(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::CapabilitiesCmdVersion(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::CapabilitiesNegotiate(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::FwInfoV1(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::QuotePcrsCwt(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::StartJob(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetJobStatus(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::Batch(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::VerifyObject(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetCrashDump(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetBootProfile(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetProvisioningStatus(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetEntropyHealth(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetFht(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::Spdm(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::Mctp(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::SetMeasurementManifest(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::FinishStreamVerify(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::SealKey(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::UnsealKey(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::CreateCounter(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::IncrementCounter(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::ReadCounter(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::Timestamp(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::PairMailbox(resp) => Ok(resp.as_mut_bytes()),
         }
     }
 
@@ -266,6 +556,35 @@ pub enum MailboxReq {
     SetAuthManifest(SetAuthManifestReq),
     AuthorizeAndStash(AuthorizeAndStashReq),
     SignWithExportedEcdsa(SignWithExportedEcdsaReq),
+    RevokeLocality(RevokeLocalityReq),
+    InjectError(InjectErrorReq),
+    CommitOwnerKeyRotation(CommitOwnerKeyRotationReq),
+    SetCertPolicy(SetCertPolicyReq),
+    SetCertDistPoints(SetCertDistPointsReq),
+    CapabilitiesCmdVersion(CapabilitiesCmdVersionReq),
+    CapabilitiesNegotiate(CapabilitiesNegotiateReq),
+    FwInfoVersioned(FwInfoVersionedReq),
+    QuotePcrsCwt(QuotePcrsCwtReq),
+    StartJob(StartJobReq),
+    GetJobStatus(GetJobStatusReq),
+    Batch(BatchReq),
+    ConfigureWdt(ConfigureWdtReq),
+    SetObjectSchemas(SetObjectSchemasReq),
+    VerifyObject(VerifyObjectReq),
+    SetLogLevel(SetLogLevelReq),
+    GetEntropyHealth(GetEntropyHealthReq),
+    Spdm(SpdmReq),
+    Mctp(MctpReq),
+    SetMeasurementManifest(SetMeasurementManifestReq),
+    StartStreamVerify(StartStreamVerifyReq),
+    StreamData(StreamDataReq),
+    FinishStreamVerify(FinishStreamVerifyReq),
+    SealKey(SealKeyReq),
+    UnsealKey(UnsealKeyReq),
+    IncrementCounter(IncrementCounterReq),
+    ReadCounter(ReadCounterReq),
+    Timestamp(TimestampReq),
+    PairMailbox(PairMailboxReq),
 }
 
 impl MailboxReq {
@@ -292,6 +611,35 @@ impl MailboxReq {
             MailboxReq::SetAuthManifest(req) => Ok(req.as_bytes()),
             MailboxReq::AuthorizeAndStash(req) => Ok(req.as_bytes()),
             MailboxReq::SignWithExportedEcdsa(req) => Ok(req.as_bytes()),
+            MailboxReq::RevokeLocality(req) => Ok(req.as_bytes()),
+            MailboxReq::InjectError(req) => Ok(req.as_bytes()),
+            MailboxReq::CommitOwnerKeyRotation(req) => Ok(req.as_bytes()),
+            MailboxReq::SetCertPolicy(req) => Ok(req.as_bytes()),
+            MailboxReq::SetCertDistPoints(req) => Ok(req.as_bytes()),
+            MailboxReq::CapabilitiesCmdVersion(req) => Ok(req.as_bytes()),
+            MailboxReq::CapabilitiesNegotiate(req) => Ok(req.as_bytes()),
+            MailboxReq::FwInfoVersioned(req) => Ok(req.as_bytes()),
+            MailboxReq::QuotePcrsCwt(req) => Ok(req.as_bytes()),
+            MailboxReq::StartJob(req) => req.as_bytes_partial(),
+            MailboxReq::GetJobStatus(req) => Ok(req.as_bytes()),
+            MailboxReq::Batch(req) => req.as_bytes_partial(),
+            MailboxReq::ConfigureWdt(req) => Ok(req.as_bytes()),
+            MailboxReq::SetObjectSchemas(req) => Ok(req.as_bytes()),
+            MailboxReq::VerifyObject(req) => req.as_bytes_partial(),
+            MailboxReq::SetLogLevel(req) => Ok(req.as_bytes()),
+            MailboxReq::GetEntropyHealth(req) => Ok(req.as_bytes()),
+            MailboxReq::Spdm(req) => req.as_bytes_partial(),
+            MailboxReq::Mctp(req) => req.as_bytes_partial(),
+            MailboxReq::SetMeasurementManifest(req) => Ok(req.as_bytes()),
+            MailboxReq::StartStreamVerify(req) => Ok(req.as_bytes()),
+            MailboxReq::StreamData(req) => req.as_bytes_partial(),
+            MailboxReq::FinishStreamVerify(req) => Ok(req.as_bytes()),
+            MailboxReq::SealKey(req) => Ok(req.as_bytes()),
+            MailboxReq::UnsealKey(req) => Ok(req.as_bytes()),
+            MailboxReq::IncrementCounter(req) => Ok(req.as_bytes()),
+            MailboxReq::ReadCounter(req) => Ok(req.as_bytes()),
+            MailboxReq::Timestamp(req) => Ok(req.as_bytes()),
+            MailboxReq::PairMailbox(req) => Ok(req.as_bytes()),
         }
     }
 
@@ -318,6 +666,35 @@ impl MailboxReq {
             MailboxReq::SetAuthManifest(req) => Ok(req.as_mut_bytes()),
             MailboxReq::AuthorizeAndStash(req) => Ok(req.as_mut_bytes()),
             MailboxReq::SignWithExportedEcdsa(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::RevokeLocality(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::InjectError(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::CommitOwnerKeyRotation(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::SetCertPolicy(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::SetCertDistPoints(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::CapabilitiesCmdVersion(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::CapabilitiesNegotiate(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::FwInfoVersioned(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::QuotePcrsCwt(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::StartJob(req) => req.as_bytes_partial_mut(),
+            MailboxReq::GetJobStatus(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::Batch(req) => req.as_bytes_partial_mut(),
+            MailboxReq::ConfigureWdt(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::SetObjectSchemas(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::VerifyObject(req) => req.as_bytes_partial_mut(),
+            MailboxReq::SetLogLevel(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetEntropyHealth(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::Spdm(req) => req.as_bytes_partial_mut(),
+            MailboxReq::Mctp(req) => req.as_bytes_partial_mut(),
+            MailboxReq::SetMeasurementManifest(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::StartStreamVerify(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::StreamData(req) => req.as_bytes_partial_mut(),
+            MailboxReq::FinishStreamVerify(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::SealKey(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::UnsealKey(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::IncrementCounter(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::ReadCounter(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::Timestamp(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::PairMailbox(req) => Ok(req.as_mut_bytes()),
         }
     }
 
@@ -344,6 +721,35 @@ impl MailboxReq {
             MailboxReq::SetAuthManifest(_) => CommandId::SET_AUTH_MANIFEST,
             MailboxReq::AuthorizeAndStash(_) => CommandId::AUTHORIZE_AND_STASH,
             MailboxReq::SignWithExportedEcdsa(_) => CommandId::SIGN_WITH_EXPORTED_ECDSA,
+            MailboxReq::RevokeLocality(_) => CommandId::REVOKE_LOCALITY,
+            MailboxReq::InjectError(_) => CommandId::INJECT_ERROR,
+            MailboxReq::CommitOwnerKeyRotation(_) => CommandId::COMMIT_OWNER_KEY_ROTATION,
+            MailboxReq::SetCertPolicy(_) => CommandId::SET_CERT_POLICY,
+            MailboxReq::SetCertDistPoints(_) => CommandId::SET_CERT_DIST_POINTS,
+            MailboxReq::CapabilitiesCmdVersion(_) => CommandId::CAPABILITIES,
+            MailboxReq::CapabilitiesNegotiate(_) => CommandId::CAPABILITIES,
+            MailboxReq::FwInfoVersioned(_) => CommandId::FW_INFO,
+            MailboxReq::QuotePcrsCwt(_) => CommandId::QUOTE_PCRS,
+            MailboxReq::StartJob(_) => CommandId::START_JOB,
+            MailboxReq::GetJobStatus(_) => CommandId::GET_JOB_STATUS,
+            MailboxReq::Batch(_) => CommandId::BATCH,
+            MailboxReq::ConfigureWdt(_) => CommandId::CONFIGURE_WDT,
+            MailboxReq::SetObjectSchemas(_) => CommandId::SET_OBJECT_SCHEMAS,
+            MailboxReq::VerifyObject(_) => CommandId::VERIFY_OBJECT,
+            MailboxReq::SetLogLevel(_) => CommandId::SET_LOG_LEVEL,
+            MailboxReq::GetEntropyHealth(_) => CommandId::GET_ENTROPY_HEALTH,
+            MailboxReq::Spdm(_) => CommandId::SPDM,
+            MailboxReq::Mctp(_) => CommandId::MCTP,
+            MailboxReq::SetMeasurementManifest(_) => CommandId::SET_MEASUREMENT_MANIFEST,
+            MailboxReq::StartStreamVerify(_) => CommandId::START_STREAM_VERIFY,
+            MailboxReq::StreamData(_) => CommandId::STREAM_DATA,
+            MailboxReq::FinishStreamVerify(_) => CommandId::FINISH_STREAM_VERIFY,
+            MailboxReq::SealKey(_) => CommandId::SEAL_KEY,
+            MailboxReq::UnsealKey(_) => CommandId::UNSEAL_KEY,
+            MailboxReq::IncrementCounter(_) => CommandId::INCREMENT_COUNTER,
+            MailboxReq::ReadCounter(_) => CommandId::READ_COUNTER,
+            MailboxReq::Timestamp(_) => CommandId::TIMESTAMP,
+            MailboxReq::PairMailbox(_) => CommandId::PAIR_MAILBOX,
         }
     }
 
@@ -374,8 +780,22 @@ pub struct MailboxReqHeader {
     pub chksum: u32,
 }
 
+/// Header of requests to command formats newer than version 1. Version 1 of
+/// every command uses `MailboxReqHeader`.
+#[repr(C)]
+#[derive(Default, Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct MailboxReqVersionedHeader {
+    pub chksum: u32,
+    pub version: u8,
+    pub reserved: [u8; 3],
+}
+
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
 pub struct MailboxRespHeader {
     pub chksum: u32,
     pub fips_status: u32,
@@ -767,8 +1187,55 @@ impl Default for GetFmcAliasCertResp {
 // No command-specific input args
 // No command-specific output args
 
+bitflags::bitflags! {
+    /// Algorithms and integrity checks exercised by the FIPS self test
+    #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct SelfTestAlgorithms : u32 {
+        const SHA1 = 1 << 0;
+        const SHA256 = 1 << 1;
+        const SHA384 = 1 << 2;
+        const SHA2_512_384_ACC = 1 << 3;
+        const ECC384 = 1 << 4;
+        const HMAC384_KDF = 1 << 5;
+        const LMS = 1 << 6;
+        const ROM_INTEGRITY = 1 << 7;
+        const FW_INTEGRITY = 1 << 8;
+        const SHA3 = 1 << 9;
+        const ECC256 = 1 << 10;
+    }
+}
+
+// FIPS_SELF_TEST_GET_RESULTS
+// No command-specific input args
+// Response generated from build/protocol.rs
+
+// FIPS_ZEROIZE
+// No command-specific input args
+bitflags::bitflags! {
+    /// Zeroization steps completed by the ZEROIZE command
+    #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct ZeroizeStatus : u32 {
+        const CRYPTO_ENGINES_CLEARED = 1 << 0;
+        const KEY_VAULT_ERASED = 1 << 1;
+        const PERSISTENT_DATA_CLEARED = 1 << 2;
+        const DATA_VAULT_LOCKED = 1 << 3;
+    }
+}
+
+// Response generated from build/protocol.rs
+
 // FIPS_GET_VERSION
 // No command-specific input args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct FipsVersionReq {
+    pub hdr: MailboxReqHeader,
+}
+impl Request for FipsVersionReq {
+    const ID: CommandId = CommandId::VERSION;
+    type Resp = FipsVersionResp;
+}
+
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
 pub struct FipsVersionResp {
@@ -780,7 +1247,8 @@ pub struct FipsVersionResp {
 impl Response for FipsVersionResp {}
 
 // FW_INFO
-// No command-specific input args
+// Callers that send a MailboxReqHeader receive FwInfoV1Resp. The fields added
+// since are only returned to callers that request version 2.
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
 pub struct FwInfoResp {
@@ -797,7 +1265,60 @@ pub struct FwInfoResp {
     pub fmc_sha384_digest: [u32; 12],
     pub runtime_sha384_digest: [u32; 12],
     pub owner_pub_key_hash: [u32; 12],
+    pub rom_sha_acc_lock_contention: u32,
+    pub fmc_wdt_budget_cycles: u32,
+    pub fmc_wdt_max_phase_cycles: u32,
+    pub idle_count: u32,
+    pub idle_cycles_low: u32,
+    pub idle_cycles_high: u32,
+    pub cmd_count: u32,
+    pub cmd_success_count: u32,
+    pub cmd_failure_count: u32,
+    pub uptime_cycles_low: u32,
+    pub uptime_cycles_high: u32,
+    pub iccm_ecc_cor_count: u32,
+    pub dccm_ecc_cor_count: u32,
+    pub mbox_ecc_cor_count: u32,
+    pub mbox_ecc_unc_count: u32,
+    pub ccm_ecc_unc: u32,
+    pub dccm_scrub_passes: u32,
+}
+impl Response for FwInfoResp {}
+
+// FW_INFO (version 2)
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct FwInfoVersionedReq {
+    pub hdr: MailboxReqVersionedHeader,
+}
+impl FwInfoVersionedReq {
+    pub const VERSION: u8 = 2;
+}
+impl Request for FwInfoVersionedReq {
+    const ID: CommandId = CommandId::FW_INFO;
+    type Resp = FwInfoResp;
+}
+
+// FW_INFO (version 1)
+// A prefix of FwInfoResp
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct FwInfoV1Resp {
+    pub hdr: MailboxRespHeader,
+    pub pl0_pauser: u32,
+    pub runtime_svn: u32,
+    pub min_runtime_svn: u32,
+    pub fmc_manifest_svn: u32,
+    pub attestation_disabled: u32,
+    pub rom_revision: [u8; 20],
+    pub fmc_revision: [u8; 20],
+    pub runtime_revision: [u8; 20],
+    pub rom_sha256_digest: [u32; 8],
+    pub fmc_sha384_digest: [u32; 12],
+    pub runtime_sha384_digest: [u32; 12],
+    pub owner_pub_key_hash: [u32; 12],
 }
+impl Response for FwInfoV1Resp {}
 
 // CAPABILITIES
 // No command-specific input args
@@ -809,6 +1330,87 @@ pub struct CapabilitiesResp {
 }
 impl Response for CapabilitiesResp {}
 
+// CAPABILITIES (version 2)
+// Also reports the request versions supported for `cmd`
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CapabilitiesCmdVersionReq {
+    pub hdr: MailboxReqVersionedHeader,
+    pub cmd: u32,
+}
+impl CapabilitiesCmdVersionReq {
+    pub const VERSION: u8 = 2;
+}
+impl Request for CapabilitiesCmdVersionReq {
+    const ID: CommandId = CommandId::CAPABILITIES;
+    type Resp = CapabilitiesCmdVersionResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CapabilitiesCmdVersionResp {
+    pub hdr: MailboxRespHeader,
+    pub capabilities: [u8; crate::capabilities::Capabilities::SIZE_IN_BYTES],
+    pub min_version: u8,
+    pub max_version: u8,
+    pub reserved: [u8; 2],
+}
+impl Response for CapabilitiesCmdVersionResp {}
+
+// CAPABILITIES (version 3)
+// Reports every supported command along with its newest request version
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CapabilitiesNegotiateReq {
+    pub hdr: MailboxReqVersionedHeader,
+}
+impl CapabilitiesNegotiateReq {
+    pub const VERSION: u8 = 3;
+}
+impl Request for CapabilitiesNegotiateReq {
+    const ID: CommandId = CommandId::CAPABILITIES;
+    type Resp = CapabilitiesNegotiateResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CapabilitiesNegotiateResp {
+    pub hdr: MailboxRespHeader,
+    pub capabilities: [u8; crate::capabilities::Capabilities::SIZE_IN_BYTES],
+    /// Bit `i` is set if `CommandId::CAPABILITY_BITS[i]` is supported
+    pub supported_cmds: [u32; CapabilitiesNegotiateResp::MAX_CMDS / 32],
+    /// Newest request version of `CommandId::CAPABILITY_BITS[i]`, or 0 if it
+    /// is not supported. The oldest supported version is always 1.
+    pub max_versions: [u8; CapabilitiesNegotiateResp::MAX_CMDS],
+}
+impl CapabilitiesNegotiateResp {
+    pub const MAX_CMDS: usize = 128;
+
+    /// Returns whether `cmd` is supported
+    pub fn is_supported(&self, cmd: &CommandId) -> bool {
+        cmd.capability_bit().map_or(false, |bit| {
+            self.supported_cmds[bit / 32] & (1 << (bit % 32)) != 0
+        })
+    }
+
+    /// Returns the newest request version of `cmd`, or 0 if it is not
+    /// supported
+    pub fn max_version(&self, cmd: &CommandId) -> u8 {
+        cmd.capability_bit().map_or(0, |bit| self.max_versions[bit])
+    }
+}
+impl Default for CapabilitiesNegotiateResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            capabilities: Default::default(),
+            supported_cmds: Default::default(),
+            max_versions: [0; CapabilitiesNegotiateResp::MAX_CMDS],
+        }
+    }
+}
+impl Response for CapabilitiesNegotiateResp {}
+
 // ADD_SUBJECT_ALT_NAME
 // No command-specific output args
 #[repr(C)]
@@ -955,6 +1557,52 @@ impl Request for QuotePcrsReq {
     type Resp = QuotePcrsResp;
 }
 
+// QUOTE_PCRS (version 2)
+// Returns the quote as a CBOR Web Token signed by the RT Alias key
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct QuotePcrsCwtReq {
+    pub hdr: MailboxReqVersionedHeader,
+    pub nonce: [u8; 32],
+}
+impl QuotePcrsCwtReq {
+    pub const VERSION: u8 = 2;
+}
+impl Request for QuotePcrsCwtReq {
+    const ID: CommandId = CommandId::QUOTE_PCRS;
+    type Resp = QuotePcrsCwtResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct QuotePcrsCwtResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; Self::DATA_MAX_SIZE],
+}
+impl QuotePcrsCwtResp {
+    pub const DATA_MAX_SIZE: usize = 2048;
+
+    /// EAT nonce claim (RFC 9711), holding the request nonce
+    pub const CLAIM_NONCE: i64 = 10;
+    /// EAT UEID claim (RFC 9711), holding the device UEID
+    pub const CLAIM_UEID: i64 = 256;
+    /// Private claim holding the array of PCR values
+    pub const CLAIM_PCRS: i64 = -70001;
+    /// Private claim holding the array of PCR reset counters
+    pub const CLAIM_PCR_RESET_COUNTERS: i64 = -70002;
+}
+impl Default for QuotePcrsCwtResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; Self::DATA_MAX_SIZE],
+        }
+    }
+}
+impl ResponseVarSize for QuotePcrsCwtResp {}
+
 // SET_AUTH_MANIFEST
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
@@ -1061,6 +1709,41 @@ impl GetFmcAliasCsrResp {
 }
 impl ResponseVarSize for GetFmcAliasCsrResp {}
 
+// GET_RT_ALIAS_CSR
+#[repr(C)]
+#[derive(Default, Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct GetRtAliasCsrReq {
+    pub hdr: MailboxReqHeader,
+}
+
+impl Request for GetRtAliasCsrReq {
+    const ID: CommandId = CommandId::GET_RT_ALIAS_CSR;
+    type Resp = GetRtAliasCsrResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct GetRtAliasCsrResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; Self::DATA_MAX_SIZE],
+}
+
+impl Default for GetRtAliasCsrResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; Self::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl GetRtAliasCsrResp {
+    pub const DATA_MAX_SIZE: usize = 512;
+}
+impl ResponseVarSize for GetRtAliasCsrResp {}
+
 // SIGN_WITH_EXPORTED_ECDSA
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
@@ -1121,122 +1804,1483 @@ impl Default for SignWithExportedEcdsaResp {
     }
 }
 
+// REVOKE_LOCALITY
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct RevokeLocalityReq {
+    pub hdr: MailboxReqHeader,
+    pub locality: u32,
+}
+
+impl Request for RevokeLocalityReq {
+    const ID: CommandId = CommandId::REVOKE_LOCALITY;
+    type Resp = MailboxRespHeader;
+}
+
 #[repr(u32)]
 #[derive(Debug, PartialEq, Eq)]
-pub enum ImageHashSource {
+pub enum InjectErrorType {
     Invalid = 0,
-    InRequest,
-    ShaAcc,
+    NonFatal,
+    Fatal,
+    Nmi,
+    Wdt,
 }
 
-impl From<u32> for ImageHashSource {
+impl From<u32> for InjectErrorType {
     fn from(val: u32) -> Self {
         match val {
-            1_u32 => ImageHashSource::InRequest,
-            2_u32 => ImageHashSource::ShaAcc,
-            _ => ImageHashSource::Invalid,
+            1_u32 => InjectErrorType::NonFatal,
+            2_u32 => InjectErrorType::Fatal,
+            3_u32 => InjectErrorType::Nmi,
+            4_u32 => InjectErrorType::Wdt,
+            _ => InjectErrorType::Invalid,
         }
     }
 }
 
-bitflags::bitflags! {
-    pub struct AuthAndStashFlags : u32 {
-        const SKIP_STASH = 0x1;
-    }
-}
-
-impl From<u32> for AuthAndStashFlags {
-    /// Converts to this type from the input type.
-    fn from(value: u32) -> Self {
-        AuthAndStashFlags::from_bits_truncate(value)
-    }
+// INJECT_ERROR
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct InjectErrorReq {
+    pub hdr: MailboxReqHeader,
+    pub error_type: u32,
 }
 
-impl AuthAndStashFlags {
-    pub fn set_skip_stash(&mut self, skip_stash: bool) {
-        self.set(AuthAndStashFlags::SKIP_STASH, skip_stash);
-    }
+impl Request for InjectErrorReq {
+    const ID: CommandId = CommandId::INJECT_ERROR;
+    type Resp = MailboxRespHeader;
 }
 
-// AUTHORIZE_AND_STASH
+// GET_UPDATE_HISTORY
 #[repr(C)]
-#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
-pub struct AuthorizeAndStashReq {
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetUpdateHistoryReq {
     pub hdr: MailboxReqHeader,
-    pub fw_id: [u8; 4],
-    pub measurement: [u8; 48], // Image digest.
-    pub context: [u8; 48],
-    pub svn: u32,
-    pub flags: u32,
-    pub source: u32,
-}
-impl Default for AuthorizeAndStashReq {
-    fn default() -> Self {
-        Self {
-            hdr: Default::default(),
-            fw_id: Default::default(),
-            measurement: [0u8; 48],
-            context: [0u8; 48],
-            svn: Default::default(),
-            flags: AuthAndStashFlags::SKIP_STASH.bits(),
-            source: ImageHashSource::InRequest as u32,
-        }
-    }
 }
-impl Request for AuthorizeAndStashReq {
-    const ID: CommandId = CommandId::AUTHORIZE_AND_STASH;
-    type Resp = StashMeasurementResp;
+
+impl Request for GetUpdateHistoryReq {
+    const ID: CommandId = CommandId::GET_UPDATE_HISTORY;
+    type Resp = GetUpdateHistoryResp;
 }
 
 #[repr(C)]
 #[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
-pub struct AuthorizeAndStashResp {
-    pub hdr: MailboxRespHeader,
-    pub auth_req_result: u32,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct FwUpdateHistoryEntry {
+    pub old_svn: u32,
+    pub new_svn: u32,
+    pub runtime_sha384_digest: [u32; 12],
+    pub timestamp_low: u32,
+    pub timestamp_high: u32,
 }
-impl Response for AuthorizeAndStashResp {}
-
-/// Retrieves dlen bytes  from the mailbox.
-pub fn mbox_read_response(
-    mbox: mbox::RegisterBlock<impl MmioMut>,
-    buf: &mut [u8],
-) -> Result<&[u8], CaliptraApiError> {
-    let dlen_bytes = mbox.dlen().read() as usize;
 
-    // Buffer must be big enough to store dlen bytes.
-    let buf = buf
-        .get_mut(..dlen_bytes)
-        .ok_or(CaliptraApiError::ReadBuffTooSmall)?;
+// Response generated from build/protocol.rs
 
-    mbox_read_fifo(mbox, buf)?;
+// INCREMENT_FUSE_SVN
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct IncrementFuseSvnReq {
+    pub hdr: MailboxReqHeader,
+}
 
-    Ok(buf)
+impl Request for IncrementFuseSvnReq {
+    const ID: CommandId = CommandId::INCREMENT_FUSE_SVN;
+    type Resp = IncrementFuseSvnResp;
 }
 
-pub fn mbox_read_fifo(
-    mbox: mbox::RegisterBlock<impl MmioMut>,
-    buf: &mut [u8],
-) -> core::result::Result<(), CaliptraApiError> {
-    use zerocopy::Unalign;
+// Response generated from build/protocol.rs
 
-    fn dequeue_words(mbox: &mbox::RegisterBlock<impl MmioMut>, buf: &mut [Unalign<u32>]) {
-        for word in buf.iter_mut() {
-            *word = Unalign::new(mbox.dataout().read());
-        }
-    }
+// GET_DPE_STATS
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetDpeStatsReq {
+    pub hdr: MailboxReqHeader,
+}
 
-    let dlen_bytes = mbox.dlen().read() as usize;
+impl Request for GetDpeStatsReq {
+    const ID: CommandId = CommandId::GET_DPE_STATS;
+    type Resp = GetDpeStatsResp;
+}
 
-    let buf = buf
-        .get_mut(..dlen_bytes)
-        .ok_or(CaliptraApiError::UnableToReadMailbox)?;
+// Response generated from build/protocol.rs
 
-    let len_words = buf.len() / size_of::<u32>();
-    let (mut buf_words, suffix) = Ref::from_prefix_with_elems(buf, len_words)
-        .map_err(|_| CaliptraApiError::ReadBuffTooSmall)?;
+// COMMIT_OWNER_KEY_ROTATION
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CommitOwnerKeyRotationReq {
+    pub hdr: MailboxReqHeader,
+    pub owner_pub_keys_digest: [u32; 12],
+}
 
-    dequeue_words(&mbox, &mut buf_words);
-    if !suffix.is_empty() {
+impl Request for CommitOwnerKeyRotationReq {
+    const ID: CommandId = CommandId::COMMIT_OWNER_KEY_ROTATION;
+    type Resp = MailboxRespHeader;
+}
+
+// RESEED_FIELD_ENTROPY
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct ReseedFieldEntropyReq {
+    pub hdr: MailboxReqHeader,
+}
+
+impl Request for ReseedFieldEntropyReq {
+    const ID: CommandId = CommandId::RESEED_FIELD_ENTROPY;
+    type Resp = MailboxRespHeader;
+}
+
+// SET_CERT_POLICY
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SetCertPolicyReq {
+    pub hdr: MailboxReqHeader,
+    pub flags: CertPolicyFlags,
+    pub max_not_after: [u8; 15],
+    pub reserved: u8,
+}
+
+impl Request for SetCertPolicyReq {
+    const ID: CommandId = CommandId::SET_CERT_POLICY;
+    type Resp = MailboxRespHeader;
+}
+
+#[repr(C)]
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, FromBytes, Immutable, KnownLayout, IntoBytes,
+)]
+pub struct CertPolicyFlags(pub u32);
+
+// SET_CERT_DIST_POINTS
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SetCertDistPointsReq {
+    pub hdr: MailboxReqHeader,
+    pub crl_uri_size: u32,
+    pub crl_uri: [u8; SetCertDistPointsReq::MAX_URI_SIZE],
+    pub aia_uri_size: u32,
+    pub aia_uri: [u8; SetCertDistPointsReq::MAX_URI_SIZE],
+}
+
+impl SetCertDistPointsReq {
+    pub const MAX_URI_SIZE: usize = 96;
+}
+
+impl Default for SetCertDistPointsReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            crl_uri_size: 0,
+            crl_uri: [0; Self::MAX_URI_SIZE],
+            aia_uri_size: 0,
+            aia_uri: [0; Self::MAX_URI_SIZE],
+        }
+    }
+}
+
+impl Request for SetCertDistPointsReq {
+    const ID: CommandId = CommandId::SET_CERT_DIST_POINTS;
+    type Resp = MailboxRespHeader;
+}
+
+bitflags! {
+    impl CertPolicyFlags: u32 {
+        const DENY_X509 = 1u32 << 0;
+        const DENY_CSR = 1u32 << 1;
+        const DENY_SUBJECT_ALT_NAME = 1u32 << 2;
+        const CAP_VALIDITY = 1u32 << 3;
+    }
+}
+
+// GET_KV_STATUS
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetKvStatusReq {
+    pub hdr: MailboxReqHeader,
+}
+
+impl Request for GetKvStatusReq {
+    const ID: CommandId = CommandId::GET_KV_STATUS;
+    type Resp = GetKvStatusResp;
+}
+
+#[repr(C)]
+#[derive(
+    Debug, Default, Clone, Copy, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct KvSlotInfo {
+    pub usage: u32,
+    pub kind: u32,
+}
+
+impl KvSlotInfo {
+    pub const KIND_EMPTY: u32 = 0;
+    pub const KIND_CDI: u32 = 1;
+    pub const KIND_PRIVATE_KEY: u32 = 2;
+    pub const KIND_EPHEMERAL: u32 = 3;
+}
+
+// Response generated from build/protocol.rs
+
+// CAPTURE_RAW_ENTROPY
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CaptureRawEntropyReq {
+    pub hdr: MailboxReqHeader,
+}
+
+impl Request for CaptureRawEntropyReq {
+    const ID: CommandId = CommandId::CAPTURE_RAW_ENTROPY;
+    type Resp = MailboxRespHeader;
+}
+
+// GET_RAW_ENTROPY
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetRawEntropyReq {
+    pub hdr: MailboxReqHeader,
+}
+
+impl Request for GetRawEntropyReq {
+    const ID: CommandId = CommandId::GET_RAW_ENTROPY;
+    type Resp = GetRawEntropyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetRawEntropyResp {
+    pub hdr: MailboxRespHeader,
+    pub seq_num: u32,
+    pub flags: u32,
+    pub sample_count: u32,
+    pub samples: [u32; Self::MAX_SAMPLES],
+}
+
+impl GetRawEntropyResp {
+    pub const MAX_SAMPLES: usize = 256;
+
+    /// Samples were dropped between the previous page and this one
+    pub const FLAG_OVERFLOW: u32 = 1 << 0;
+}
+
+impl Default for GetRawEntropyResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            seq_num: 0,
+            flags: 0,
+            sample_count: 0,
+            samples: [0u32; Self::MAX_SAMPLES],
+        }
+    }
+}
+
+impl Response for GetRawEntropyResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        if self.sample_count as usize > Self::MAX_SAMPLES {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "GetRawEntropyResp.sample_count",
+            ));
+        }
+        Ok(())
+    }
+}
+
+// START_JOB
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct StartJobReq {
+    pub hdr: MailboxReqHeader,
+    pub cmd: u32,
+    pub data_size: u32,
+    pub data: [u8; StartJobReq::DATA_MAX_SIZE], // variable length
+}
+
+impl StartJobReq {
+    /// Large enough for the request of any command that can run as a job
+    pub const DATA_MAX_SIZE: usize = size_of::<LmsVerifyReq>();
+
+    pub fn as_bytes_partial(&self) -> CaliptraResult<&[u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&self.as_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+
+    pub fn as_bytes_partial_mut(&mut self) -> CaliptraResult<&mut [u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&mut self.as_mut_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+}
+
+impl Default for StartJobReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            cmd: 0,
+            data_size: 0,
+            data: [0u8; StartJobReq::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for StartJobReq {
+    const ID: CommandId = CommandId::START_JOB;
+    type Resp = StartJobResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct StartJobResp {
+    pub hdr: MailboxRespHeader,
+    pub job_id: u32,
+}
+impl Response for StartJobResp {}
+
+// GET_JOB_STATUS
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetJobStatusReq {
+    pub hdr: MailboxReqHeader,
+    pub job_id: u32,
+}
+
+impl Request for GetJobStatusReq {
+    const ID: CommandId = CommandId::GET_JOB_STATUS;
+    type Resp = GetJobStatusResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetJobStatusResp {
+    pub hdr: MailboxRespHeader,
+    pub status: u32,
+    pub error: u32,
+    pub data_size: u32,
+    pub data: [u8; Self::DATA_MAX_SIZE],
+}
+
+impl GetJobStatusResp {
+    pub const DATA_MAX_SIZE: usize = 256;
+
+    /// The job has not run yet
+    pub const STATUS_IN_PROGRESS: u32 = 1;
+    /// The job succeeded; `data` holds the response of the command
+    pub const STATUS_COMPLETE: u32 = 2;
+    /// The job failed; `error` holds the error code of the command
+    pub const STATUS_FAILED: u32 = 3;
+}
+
+impl Default for GetJobStatusResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            status: 0,
+            error: 0,
+            data_size: 0,
+            data: [0u8; Self::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Response for GetJobStatusResp {
+    fn validate(&self) -> Result<(), CaliptraApiError> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraApiError::MailboxRespInvalidField(
+                "GetJobStatusResp.data_size",
+            ));
+        }
+        Ok(())
+    }
+}
+
+// BATCH
+/// Header of a command in a BATCH request. It is followed by the request of
+/// the command, padded to a multiple of 4 bytes.
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct BatchReqEntryHdr {
+    pub cmd: u32,
+    pub data_size: u32,
+}
+
+/// Header of a response in a BATCH response. It is followed by the response
+/// of the command, padded to a multiple of 4 bytes.
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct BatchRespEntryHdr {
+    pub cmd: u32,
+    pub error: u32,
+    pub data_size: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct BatchReq {
+    pub hdr: MailboxReqHeader,
+    pub data_size: u32,
+    pub data: [u8; BatchReq::DATA_MAX_SIZE], // variable length
+}
+
+impl BatchReq {
+    pub const DATA_MAX_SIZE: usize = 256;
+
+    /// Maximum number of commands in a batch
+    pub const MAX_CMDS: usize = 8;
+
+    /// Append a command to the batch
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - Command to append
+    /// * `req` - Request of the command, including its header
+    pub fn push(&mut self, cmd: CommandId, req: &[u8]) -> CaliptraResult<()> {
+        let entry_hdr = BatchReqEntryHdr {
+            cmd: cmd.into(),
+            data_size: req.len() as u32,
+        };
+        let start = self.data_size as usize;
+        let req_start = start + size_of::<BatchReqEntryHdr>();
+        let end = (req_start + req.len() + 3) & !3;
+        let entry = self
+            .data
+            .get_mut(start..end)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE)?;
+        entry.fill(0);
+        entry[..size_of::<BatchReqEntryHdr>()].copy_from_slice(entry_hdr.as_bytes());
+        entry[size_of::<BatchReqEntryHdr>()..][..req.len()].copy_from_slice(req);
+        self.data_size = end as u32;
+        Ok(())
+    }
+
+    pub fn as_bytes_partial(&self) -> CaliptraResult<&[u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&self.as_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+
+    pub fn as_bytes_partial_mut(&mut self) -> CaliptraResult<&mut [u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&mut self.as_mut_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+}
+
+impl Default for BatchReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            data_size: 0,
+            data: [0u8; BatchReq::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for BatchReq {
+    const ID: CommandId = CommandId::BATCH;
+    type Resp = BatchResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct BatchResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; BatchResp::DATA_MAX_SIZE], // variable length
+}
+
+impl BatchResp {
+    pub const DATA_MAX_SIZE: usize = 4096;
+}
+
+impl ResponseVarSize for BatchResp {}
+
+impl Default for BatchResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; BatchResp::DATA_MAX_SIZE],
+        }
+    }
+}
+
+// CONFIGURE_WDT
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct ConfigureWdtReq {
+    pub hdr: MailboxReqHeader,
+    pub timeout_cycles_low: u32,
+    pub timeout_cycles_high: u32,
+}
+
+impl ConfigureWdtReq {
+    /// Watchdog timeout requested by the SoC, or 0 to restore the default
+    pub fn timeout_cycles(&self) -> u64 {
+        (u64::from(self.timeout_cycles_high) << 32) | u64::from(self.timeout_cycles_low)
+    }
+}
+
+impl Request for ConfigureWdtReq {
+    const ID: CommandId = CommandId::CONFIGURE_WDT;
+    type Resp = MailboxRespHeader;
+}
+
+// SET_OBJECT_SCHEMAS
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SetObjectSchemasReq {
+    pub hdr: MailboxReqHeader,
+    pub schema_count: u32,
+    pub schema_digests: [[u8; 48]; SetObjectSchemasReq::MAX_SCHEMAS],
+}
+
+impl SetObjectSchemasReq {
+    pub const MAX_SCHEMAS: usize = 8;
+}
+
+impl Default for SetObjectSchemasReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            schema_count: 0,
+            schema_digests: [[0u8; 48]; SetObjectSchemasReq::MAX_SCHEMAS],
+        }
+    }
+}
+
+impl Request for SetObjectSchemasReq {
+    const ID: CommandId = CommandId::SET_OBJECT_SCHEMAS;
+    type Resp = MailboxRespHeader;
+}
+
+// VERIFY_OBJECT
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct VerifyObjectReq {
+    pub hdr: MailboxReqHeader,
+    pub schema_digest: [u8; 48],
+    pub signature_r: [u8; 48],
+    pub signature_s: [u8; 48],
+    pub data_size: u32,
+    pub data: [u8; VerifyObjectReq::DATA_MAX_SIZE], // variable length
+}
+
+impl VerifyObjectReq {
+    pub const DATA_MAX_SIZE: usize = 1024;
+
+    pub fn as_bytes_partial(&self) -> CaliptraResult<&[u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&self.as_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+
+    pub fn as_bytes_partial_mut(&mut self) -> CaliptraResult<&mut [u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&mut self.as_mut_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+}
+
+impl Default for VerifyObjectReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            schema_digest: [0u8; 48],
+            signature_r: [0u8; 48],
+            signature_s: [0u8; 48],
+            data_size: 0,
+            data: [0u8; VerifyObjectReq::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for VerifyObjectReq {
+    const ID: CommandId = CommandId::VERIFY_OBJECT;
+    type Resp = VerifyObjectResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct VerifyObjectResp {
+    pub hdr: MailboxRespHeader,
+    pub verdict: u32,
+}
+
+impl VerifyObjectResp {
+    /// The schema is approved and the object is signed by the owner
+    pub const OBJECT_AUTHORIZED: u32 = 0x4F42_4A41;
+    /// The schema digest is not among the approved ones
+    pub const OBJECT_SCHEMA_NOT_APPROVED: u32 = 0x4F42_4A53;
+    /// The object is not signed by the owner
+    pub const OBJECT_SIGNATURE_INVALID: u32 = 0x4F42_4A49;
+}
+
+impl Response for VerifyObjectResp {}
+
+// GET_CRASH_DUMP
+// No command-specific input args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetCrashDumpResp {
+    pub hdr: MailboxRespHeader,
+    pub valid: u32,
+    pub error_code: u32,
+    pub last_cmd: u32,
+    pub fht_validity: u32,
+    pub stack_high_water: u32,
+    pub has_trap: u32,
+    pub mcause: u32,
+    pub mscause: u32,
+    pub mepc: u32,
+    pub mtval: u32,
+    pub mstatus: u32,
+    pub ra: u32,
+    pub sp: u32,
+}
+
+impl Response for GetCrashDumpResp {}
+
+// SET_LOG_LEVEL
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SetLogLevelReq {
+    pub hdr: MailboxReqHeader,
+    pub log_level: u32,
+}
+
+impl SetLogLevelReq {
+    pub const LOG_LEVEL_DEFAULT: u32 = 0;
+    pub const LOG_LEVEL_ERROR: u32 = 1;
+    pub const LOG_LEVEL_WARN: u32 = 2;
+    pub const LOG_LEVEL_INFO: u32 = 3;
+    pub const LOG_LEVEL_TRACE: u32 = 4;
+}
+
+impl Request for SetLogLevelReq {
+    const ID: CommandId = CommandId::SET_LOG_LEVEL;
+    type Resp = MailboxRespHeader;
+}
+
+// GET_BOOT_PROFILE
+// No command-specific input args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetBootProfileResp {
+    pub hdr: MailboxRespHeader,
+    pub rom_entry: u32,
+    pub rom_kats_complete: u32,
+    pub rom_fw_received: u32,
+    pub rom_fw_verified: u32,
+    pub rom_exit: u32,
+    pub fmc_entry: u32,
+    pub fmc_exit: u32,
+    pub rt_entry: u32,
+    pub rt_ready: u32,
+}
+
+impl Response for GetBootProfileResp {}
+
+// GET_PROVISIONING_STATUS
+// No command-specific input args
+bitflags::bitflags! {
+    /// Manufacturing provisioning steps observed by ROM
+    #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct ProvisioningStatus : u32 {
+        /// The SoC completed writing the fuses
+        const FUSE_WRITE_DONE = 1 << 0;
+        /// The Deobfuscation Engine decrypted the UDS
+        const UDS_PROGRAMMED = 1 << 1;
+        /// The Deobfuscation Engine decrypted the field entropy
+        const FIELD_ENTROPY_PROGRAMMED = 1 << 2;
+        /// The obfuscated secrets were cleared from the Deobfuscation Engine
+        const DOE_SECRETS_CLEARED = 1 << 3;
+        /// ROM generated the IDevID CSR
+        const IDEVID_CSR_READY = 1 << 4;
+        /// ROM is ready for the firmware image
+        const READY_FOR_FW = 1 << 5;
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetProvisioningStatusResp {
+    pub hdr: MailboxRespHeader,
+    /// `ProvisioningStatus` flags
+    pub status: u32,
+}
+
+impl Response for GetProvisioningStatusResp {}
+
+// GET_ENTROPY_HEALTH
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetEntropyHealthReq {
+    pub hdr: MailboxReqHeader,
+    pub flags: u32,
+}
+
+impl GetEntropyHealthReq {
+    /// Reseed the CSRNG from entropy_src before reading the statistics
+    pub const RESEED: u32 = 1 << 0;
+}
+
+impl Request for GetEntropyHealthReq {
+    const ID: CommandId = CommandId::GET_ENTROPY_HEALTH;
+    type Resp = GetEntropyHealthResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetEntropyHealthResp {
+    pub hdr: MailboxRespHeader,
+    pub repcnt_threshold: u32,
+    pub adaptp_hi_threshold: u32,
+    pub adaptp_lo_threshold: u32,
+    pub repcnt_hi_watermark: u32,
+    pub adaptp_hi_watermark: u32,
+    pub adaptp_lo_watermark: u32,
+    pub repcnt_total_fails: u32,
+    pub adaptp_hi_total_fails: u32,
+    pub adaptp_lo_total_fails: u32,
+}
+
+impl Response for GetEntropyHealthResp {}
+
+// GET_FHT
+// No command-specific input args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetFhtResp {
+    pub hdr: MailboxRespHeader,
+    pub format_version: u32,
+    pub fht_validity: u32,
+    pub fht_marker: u32,
+    pub fht_major_ver: u32,
+    pub fht_minor_ver: u32,
+    pub manifest_load_addr: u32,
+    pub fips_fw_load_addr_hdl: u32,
+    pub rt_fw_entry_point_hdl: u32,
+    pub fmc_tci_dv_hdl: u32,
+    pub fmc_cdi_kv_hdl: u32,
+    pub fmc_priv_key_kv_hdl: u32,
+    pub fmc_pub_key_x_dv_hdl: u32,
+    pub fmc_pub_key_y_dv_hdl: u32,
+    pub fmc_cert_sig_r_dv_hdl: u32,
+    pub fmc_cert_sig_s_dv_hdl: u32,
+    pub fmc_svn_dv_hdl: u32,
+    pub rt_tci_dv_hdl: u32,
+    pub rt_cdi_kv_hdl: u32,
+    pub rt_priv_key_kv_hdl: u32,
+    pub rt_svn_dv_hdl: u32,
+    pub rt_min_svn_dv_hdl: u32,
+    pub ldevid_cert_sig_r_dv_hdl: u32,
+    pub ldevid_cert_sig_s_dv_hdl: u32,
+    pub rt_hash_chain_kv_hdl: u32,
+    pub rt_hash_chain_max_svn: u32,
+    pub ldevid_tbs_addr: u32,
+    pub ldevid_tbs_size: u32,
+    pub fmcalias_tbs_addr: u32,
+    pub fmcalias_tbs_size: u32,
+    pub rtalias_tbs_size: u32,
+    pub pcr_log_addr: u32,
+    pub pcr_log_index: u32,
+    pub meas_log_addr: u32,
+    pub meas_log_index: u32,
+    pub fuse_log_addr: u32,
+    pub hw_caps: u32,
+}
+
+impl GetFhtResp {
+    /// Version of the response layout. New fields are appended and bump the
+    /// version.
+    pub const FORMAT_VERSION: u32 = 1;
+}
+
+impl Response for GetFhtResp {}
+
+// SPDM
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SpdmReq {
+    pub hdr: MailboxReqHeader,
+    pub data_size: u32,
+    pub data: [u8; SpdmReq::DATA_MAX_SIZE], // variable length
+}
+
+impl SpdmReq {
+    pub const DATA_MAX_SIZE: usize = 256;
+
+    pub fn as_bytes_partial(&self) -> CaliptraResult<&[u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&self.as_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+
+    pub fn as_bytes_partial_mut(&mut self) -> CaliptraResult<&mut [u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&mut self.as_mut_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+}
+
+impl Default for SpdmReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            data_size: 0,
+            data: [0u8; SpdmReq::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for SpdmReq {
+    const ID: CommandId = CommandId::SPDM;
+    type Resp = SpdmResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SpdmResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; SpdmResp::DATA_MAX_SIZE], // variable length
+}
+
+impl SpdmResp {
+    pub const DATA_MAX_SIZE: usize = 2048;
+}
+
+impl ResponseVarSize for SpdmResp {}
+
+impl Default for SpdmResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; SpdmResp::DATA_MAX_SIZE],
+        }
+    }
+}
+
+// MCTP
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct MctpReq {
+    pub hdr: MailboxReqHeader,
+    pub data_size: u32,
+    pub data: [u8; MctpReq::DATA_MAX_SIZE], // variable length
+}
+
+impl MctpReq {
+    /// Transport header and message type followed by an SPDM request
+    pub const DATA_MAX_SIZE: usize = SpdmReq::DATA_MAX_SIZE + 8;
+
+    pub fn as_bytes_partial(&self) -> CaliptraResult<&[u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&self.as_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+
+    pub fn as_bytes_partial_mut(&mut self) -> CaliptraResult<&mut [u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&mut self.as_mut_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+}
+
+impl Default for MctpReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            data_size: 0,
+            data: [0u8; MctpReq::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for MctpReq {
+    const ID: CommandId = CommandId::MCTP;
+    type Resp = MctpResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct MctpResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; MctpResp::DATA_MAX_SIZE], // variable length
+}
+
+impl MctpResp {
+    /// Transport header and message type followed by an SPDM response
+    pub const DATA_MAX_SIZE: usize = SpdmResp::DATA_MAX_SIZE + 8;
+}
+
+impl ResponseVarSize for MctpResp {}
+
+impl Default for MctpResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; MctpResp::DATA_MAX_SIZE],
+        }
+    }
+}
+
+// SET_MEASUREMENT_MANIFEST
+#[repr(C)]
+#[derive(Debug, Clone, Copy, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct MeasurementManifestEntry {
+    pub metadata: [u8; 4],
+    pub measurement: [u8; 48],
+    pub svn: u32,
+}
+
+impl Default for MeasurementManifestEntry {
+    fn default() -> Self {
+        Self {
+            metadata: [0u8; 4],
+            measurement: [0u8; 48],
+            svn: 0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SetMeasurementManifestReq {
+    pub hdr: MailboxReqHeader,
+    pub manifest_id: u32,
+    pub signature_r: [u8; 48],
+    pub signature_s: [u8; 48],
+    pub entry_count: u32,
+    pub entries: [MeasurementManifestEntry; SetMeasurementManifestReq::MAX_ENTRIES],
+}
+
+impl SetMeasurementManifestReq {
+    pub const MAX_ENTRIES: usize = 8;
+}
+
+impl Default for SetMeasurementManifestReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            manifest_id: 0,
+            signature_r: [0u8; 48],
+            signature_s: [0u8; 48],
+            entry_count: 0,
+            entries: [MeasurementManifestEntry::default(); SetMeasurementManifestReq::MAX_ENTRIES],
+        }
+    }
+}
+
+impl Request for SetMeasurementManifestReq {
+    const ID: CommandId = CommandId::SET_MEASUREMENT_MANIFEST;
+    type Resp = SetMeasurementManifestResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SetMeasurementManifestResp {
+    pub hdr: MailboxRespHeader,
+    pub dpe_result: u32,
+    pub applied_count: u32,
+}
+impl Response for SetMeasurementManifestResp {}
+
+// START_STREAM_VERIFY
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct StartStreamVerifyReq {
+    pub hdr: MailboxReqHeader,
+    pub fw_id: [u8; 4],
+    pub image_size: u32,
+}
+
+impl Request for StartStreamVerifyReq {
+    const ID: CommandId = CommandId::START_STREAM_VERIFY;
+    type Resp = MailboxRespHeader;
+}
+
+// STREAM_DATA
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct StreamDataReq {
+    pub hdr: MailboxReqHeader,
+    pub data_size: u32,
+    pub data: [u8; StreamDataReq::DATA_MAX_SIZE], // variable length
+}
+
+impl StreamDataReq {
+    pub const DATA_MAX_SIZE: usize = 8192;
+
+    pub fn as_bytes_partial(&self) -> CaliptraResult<&[u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&self.as_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+
+    pub fn as_bytes_partial_mut(&mut self) -> CaliptraResult<&mut [u8]> {
+        if self.data_size as usize > Self::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE);
+        }
+        let unused_byte_count = Self::DATA_MAX_SIZE - self.data_size as usize;
+        Ok(&mut self.as_mut_bytes()[..size_of::<Self>() - unused_byte_count])
+    }
+}
+
+impl Default for StreamDataReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            data_size: 0,
+            data: [0u8; StreamDataReq::DATA_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for StreamDataReq {
+    const ID: CommandId = CommandId::STREAM_DATA;
+    type Resp = MailboxRespHeader;
+}
+
+// FINISH_STREAM_VERIFY
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct FinishStreamVerifyReq {
+    pub hdr: MailboxReqHeader,
+    pub flags: u32,
+}
+
+impl Request for FinishStreamVerifyReq {
+    const ID: CommandId = CommandId::FINISH_STREAM_VERIFY;
+    type Resp = FinishStreamVerifyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct FinishStreamVerifyResp {
+    pub hdr: MailboxRespHeader,
+    pub auth_req_result: u32,
+    pub digest: [u8; 48],
+}
+impl Response for FinishStreamVerifyResp {}
+
+impl Default for FinishStreamVerifyResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            auth_req_result: 0,
+            digest: [0u8; 48],
+        }
+    }
+}
+
+// SEAL_KEY
+#[repr(C)]
+#[derive(Debug, Clone, Copy, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SealedKeyBlob {
+    pub pcr_mask: u32,
+    pub pcr_digest: [u8; 48],
+    pub min_svn: u32,
+    pub nonce: [u8; 48],
+    pub key_size: u32,
+    pub ciphertext: [u8; SealedKeyBlob::KEY_MAX_SIZE],
+    pub tag: [u8; 48],
+}
+
+impl SealedKeyBlob {
+    pub const KEY_MAX_SIZE: usize = 64;
+}
+
+impl Default for SealedKeyBlob {
+    fn default() -> Self {
+        Self {
+            pcr_mask: 0,
+            pcr_digest: [0u8; 48],
+            min_svn: 0,
+            nonce: [0u8; 48],
+            key_size: 0,
+            ciphertext: [0u8; SealedKeyBlob::KEY_MAX_SIZE],
+            tag: [0u8; 48],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SealKeyReq {
+    pub hdr: MailboxReqHeader,
+    pub pcr_mask: u32,
+    pub pcr_digest: [u8; 48],
+    pub min_svn: u32,
+    pub key_size: u32,
+    pub key: [u8; SealedKeyBlob::KEY_MAX_SIZE],
+}
+
+impl Default for SealKeyReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            pcr_mask: 0,
+            pcr_digest: [0u8; 48],
+            min_svn: 0,
+            key_size: 0,
+            key: [0u8; SealedKeyBlob::KEY_MAX_SIZE],
+        }
+    }
+}
+
+impl Request for SealKeyReq {
+    const ID: CommandId = CommandId::SEAL_KEY;
+    type Resp = SealKeyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct SealKeyResp {
+    pub hdr: MailboxRespHeader,
+    pub sealed: SealedKeyBlob,
+}
+impl Response for SealKeyResp {}
+
+// UNSEAL_KEY
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct UnsealKeyReq {
+    pub hdr: MailboxReqHeader,
+    pub sealed: SealedKeyBlob,
+}
+
+impl Request for UnsealKeyReq {
+    const ID: CommandId = CommandId::UNSEAL_KEY;
+    type Resp = UnsealKeyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct UnsealKeyResp {
+    pub hdr: MailboxRespHeader,
+    pub key_size: u32,
+    pub key: [u8; SealedKeyBlob::KEY_MAX_SIZE],
+}
+impl Response for UnsealKeyResp {}
+
+impl Default for UnsealKeyResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            key_size: 0,
+            key: [0u8; SealedKeyBlob::KEY_MAX_SIZE],
+        }
+    }
+}
+
+// CREATE_COUNTER
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct CreateCounterResp {
+    pub hdr: MailboxRespHeader,
+    pub counter_id: u32,
+    pub value: u32,
+}
+impl Response for CreateCounterResp {}
+
+impl CreateCounterResp {
+    /// Number of counters that can be created
+    pub const MAX_COUNTERS: usize = 3;
+
+    /// Value at which a counter is exhausted. It then no longer increments.
+    pub const MAX_VALUE: u32 = u32::MAX - 1;
+}
+
+// INCREMENT_COUNTER
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct IncrementCounterReq {
+    pub hdr: MailboxReqHeader,
+    pub counter_id: u32,
+}
+
+impl Request for IncrementCounterReq {
+    const ID: CommandId = CommandId::INCREMENT_COUNTER;
+    type Resp = IncrementCounterResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct IncrementCounterResp {
+    pub hdr: MailboxRespHeader,
+    pub value: u32,
+}
+impl Response for IncrementCounterResp {}
+
+// READ_COUNTER
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct ReadCounterReq {
+    pub hdr: MailboxReqHeader,
+    pub counter_id: u32,
+}
+
+impl Request for ReadCounterReq {
+    const ID: CommandId = CommandId::READ_COUNTER;
+    type Resp = ReadCounterResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct ReadCounterResp {
+    pub hdr: MailboxRespHeader,
+    pub value: u32,
+}
+impl Response for ReadCounterResp {}
+
+// TIMESTAMP
+// Returns the timestamp as a CBOR Web Token signed by the RT Alias key
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct TimestampReq {
+    pub hdr: MailboxReqHeader,
+    pub counter_id: u32,
+    pub nonce: [u8; 32],
+}
+
+impl Request for TimestampReq {
+    const ID: CommandId = CommandId::TIMESTAMP;
+    type Resp = TimestampResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct TimestampResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; Self::DATA_MAX_SIZE],
+}
+impl TimestampResp {
+    pub const DATA_MAX_SIZE: usize = 256;
+
+    /// EAT nonce claim (RFC 9711), holding the request nonce
+    pub const CLAIM_NONCE: i64 = 10;
+    /// EAT UEID claim (RFC 9711), holding the device UEID
+    pub const CLAIM_UEID: i64 = 256;
+    /// Private claim holding the boot session ID
+    pub const CLAIM_BOOT_SESSION_ID: i64 = -70003;
+    /// Private claim holding the counter ID
+    pub const CLAIM_COUNTER_ID: i64 = -70004;
+    /// Private claim holding the counter value
+    pub const CLAIM_COUNTER_VALUE: i64 = -70005;
+}
+impl Default for TimestampResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; Self::DATA_MAX_SIZE],
+        }
+    }
+}
+impl ResponseVarSize for TimestampResp {}
+
+// PAIR_MAILBOX
+// Establishes the key of the authenticated mailbox with an ECDH exchange
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct PairMailboxReq {
+    pub hdr: MailboxReqHeader,
+    pub pub_key_x: [u8; 32],
+    pub pub_key_y: [u8; 32],
+}
+
+impl PairMailboxReq {
+    /// Start of the fixed info of the SP 800-56C one-step key derivation,
+    /// which continues with the public keys of the SoC and of Caliptra
+    pub const KDF_LABEL: &'static [u8] = b"caliptra_mbox_pairing";
+}
+
+impl Request for PairMailboxReq {
+    const ID: CommandId = CommandId::PAIR_MAILBOX;
+    type Resp = PairMailboxResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct PairMailboxResp {
+    pub hdr: MailboxRespHeader,
+    pub pub_key_x: [u8; 32],
+    pub pub_key_y: [u8; 32],
+}
+impl Response for PairMailboxResp {}
+
+/// Appended to the request of a privileged command once the mailbox is
+/// paired with PAIR_MAILBOX. The request checksum covers the trailer.
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct MailboxAuthTrailer {
+    /// Number of authenticated commands accepted since pairing
+    pub seq: u32,
+
+    /// HMAC-SHA-384 of SHA-384(command code || seq || request), keyed by the
+    /// paired key. The request is taken without its `chksum` field.
+    pub tag: [u8; 48],
+}
+
+#[repr(u32)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ImageHashSource {
+    Invalid = 0,
+    InRequest,
+    ShaAcc,
+}
+
+impl From<u32> for ImageHashSource {
+    fn from(val: u32) -> Self {
+        match val {
+            1_u32 => ImageHashSource::InRequest,
+            2_u32 => ImageHashSource::ShaAcc,
+            _ => ImageHashSource::Invalid,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    pub struct AuthAndStashFlags : u32 {
+        const SKIP_STASH = 0x1;
+    }
+}
+
+impl From<u32> for AuthAndStashFlags {
+    /// Converts to this type from the input type.
+    fn from(value: u32) -> Self {
+        AuthAndStashFlags::from_bits_truncate(value)
+    }
+}
+
+impl AuthAndStashFlags {
+    pub fn set_skip_stash(&mut self, skip_stash: bool) {
+        self.set(AuthAndStashFlags::SKIP_STASH, skip_stash);
+    }
+}
+
+// AUTHORIZE_AND_STASH
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct AuthorizeAndStashReq {
+    pub hdr: MailboxReqHeader,
+    pub fw_id: [u8; 4],
+    pub measurement: [u8; 48], // Image digest.
+    pub context: [u8; 48],
+    pub svn: u32,
+    pub flags: u32,
+    pub source: u32,
+}
+impl Default for AuthorizeAndStashReq {
+    fn default() -> Self {
+        Self {
+            hdr: Default::default(),
+            fw_id: Default::default(),
+            measurement: [0u8; 48],
+            context: [0u8; 48],
+            svn: Default::default(),
+            flags: AuthAndStashFlags::SKIP_STASH.bits(),
+            source: ImageHashSource::InRequest as u32,
+        }
+    }
+}
+impl Request for AuthorizeAndStashReq {
+    const ID: CommandId = CommandId::AUTHORIZE_AND_STASH;
+    type Resp = StashMeasurementResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct AuthorizeAndStashResp {
+    pub hdr: MailboxRespHeader,
+    pub auth_req_result: u32,
+}
+impl Response for AuthorizeAndStashResp {}
+
+/// Retrieves dlen bytes  from the mailbox.
+pub fn mbox_read_response(
+    mbox: mbox::RegisterBlock<impl MmioMut>,
+    buf: &mut [u8],
+) -> Result<&[u8], CaliptraApiError> {
+    let dlen_bytes = mbox.dlen().read() as usize;
+
+    // Buffer must be big enough to store dlen bytes.
+    let buf = buf
+        .get_mut(..dlen_bytes)
+        .ok_or(CaliptraApiError::ReadBuffTooSmall)?;
+
+    mbox_read_fifo(mbox, buf)?;
+
+    Ok(buf)
+}
+
+pub fn mbox_read_fifo(
+    mbox: mbox::RegisterBlock<impl MmioMut>,
+    buf: &mut [u8],
+) -> core::result::Result<(), CaliptraApiError> {
+    use zerocopy::Unalign;
+
+    fn dequeue_words(mbox: &mbox::RegisterBlock<impl MmioMut>, buf: &mut [Unalign<u32>]) {
+        for word in buf.iter_mut() {
+            *word = Unalign::new(mbox.dataout().read());
+        }
+    }
+
+    let dlen_bytes = mbox.dlen().read() as usize;
+
+    let buf = buf
+        .get_mut(..dlen_bytes)
+        .ok_or(CaliptraApiError::UnableToReadMailbox)?;
+
+    let len_words = buf.len() / size_of::<u32>();
+    let (mut buf_words, suffix) = Ref::from_prefix_with_elems(buf, len_words)
+        .map_err(|_| CaliptraApiError::ReadBuffTooSmall)?;
+
+    dequeue_words(&mbox, &mut buf_words);
+    if !suffix.is_empty() {
         let last_word = mbox.dataout().read();
         let suffix_len = suffix.len();
         suffix
@@ -1277,10 +3321,76 @@ pub fn mbox_write_fifo(
     Ok(())
 }
 
+// Responses generated from build/protocol.rs
+#[cfg(feature = "generate_protocol")]
+include!(concat!(env!("OUT_DIR"), "/mailbox_resp.rs"));
+#[cfg(not(feature = "generate_protocol"))]
+include! {"../build/mailbox_resp.rs"}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_self_test_results() {
+        let mut resp = SelfTestGetResultsResp {
+            passed: (SelfTestAlgorithms::SHA384 | SelfTestAlgorithms::ECC384).bits(),
+            failed: SelfTestAlgorithms::LMS.bits(),
+            ..Default::default()
+        };
+        assert_eq!(resp.validate(), Ok(()));
+
+        resp.failed = SelfTestAlgorithms::SHA384.bits();
+        assert_eq!(
+            resp.validate(),
+            Err(CaliptraApiError::MailboxRespInvalidField(
+                "SelfTestGetResultsResp.passed"
+            ))
+        );
+
+        resp.failed = 1 << 31;
+        assert_eq!(
+            resp.validate(),
+            Err(CaliptraApiError::MailboxRespInvalidField(
+                "SelfTestGetResultsResp.failed"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_zeroize() {
+        let mut resp = ZeroizeResp {
+            status: ZeroizeStatus::all().bits(),
+            ..Default::default()
+        };
+        assert_eq!(resp.validate(), Ok(()));
+
+        resp.status = 1 << 4;
+        assert_eq!(
+            resp.validate(),
+            Err(CaliptraApiError::MailboxRespInvalidField(
+                "ZeroizeResp.status"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_update_history_entry_count() {
+        let mut resp = GetUpdateHistoryResp {
+            entry_count: GetUpdateHistoryResp::MAX_ENTRIES as u32,
+            ..Default::default()
+        };
+        assert_eq!(resp.validate(), Ok(()));
+
+        resp.entry_count += 1;
+        assert_eq!(
+            resp.validate(),
+            Err(CaliptraApiError::MailboxRespInvalidField(
+                "GetUpdateHistoryResp.entry_count"
+            ))
+        );
+    }
+
     #[test]
     fn test_populate_checksum_resp_header() {
         let mut hdr = MailboxRespHeader {
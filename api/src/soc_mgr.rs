@@ -264,33 +264,7 @@ pub trait SocManager {
                 return Err(CaliptraApiError::MailboxTimeout);
             }
         }
-        let status = self.soc_mbox().status().read().status();
-        if status.cmd_failure() {
-            self.soc_mbox().execute().write(|w| w.execute(false));
-            let soc_ifc = self.soc_ifc();
-            return Err(CaliptraApiError::MailboxCmdFailed(
-                if soc_ifc.cptra_fw_error_fatal().read() != 0 {
-                    soc_ifc.cptra_fw_error_fatal().read()
-                } else {
-                    soc_ifc.cptra_fw_error_non_fatal().read()
-                },
-            ));
-        }
-        if status.cmd_complete() {
-            self.soc_mbox().execute().write(|w| w.execute(false));
-            return Ok(None);
-        }
-        if !status.data_ready() {
-            return Err(CaliptraApiError::UnknownCommandStatus(status as u32));
-        }
-
-        let res = mbox_read_response(self.soc_mbox(), resp_data);
-
-        self.soc_mbox().execute().write(|w| w.execute(false));
-
-        let buf = res?;
-
-        Ok(Some(buf))
+        read_mailbox_result(self, resp_data)
     }
 
     /// Executes a typed request and (if success), returns the typed response.
@@ -301,51 +275,13 @@ pub trait SocManager {
         mut req: R,
         resp_bytes: &mut [u8],
     ) -> core::result::Result<R::Resp, CaliptraApiError> {
-        if mem::size_of::<R>() < mem::size_of::<MailboxReqHeader>() {
-            return Err(CaliptraApiError::MailboxReqTypeTooSmall);
-        }
-        if mem::size_of::<R::Resp>() < mem::size_of::<MailboxRespHeader>() {
-            return Err(CaliptraApiError::MailboxRespTypeTooSmall);
-        }
-        if R::Resp::MIN_SIZE < mem::size_of::<MailboxRespHeader>() {
-            return Err(CaliptraApiError::MailboxRespTypeTooSmall);
-        }
-        let (header_bytes, payload_bytes) = req
-            .as_mut_bytes()
-            .split_at_mut(mem::size_of::<MailboxReqHeader>());
+        populate_req_checksum(&mut req)?;
 
-        let mut header = MailboxReqHeader::mut_from_bytes(header_bytes as &mut [u8]).unwrap();
-        header.chksum = calc_checksum(R::ID.into(), payload_bytes);
-
-        let Some(data) = SocManager::mailbox_exec(self, R::ID.into(), req.as_bytes(), resp_bytes)? else {
-                return Err(CaliptraApiError::MailboxNoResponseData);
+        let Some(data) = SocManager::mailbox_exec(self, R::ID.into(), req.as_bytes(), resp_bytes)?
+        else {
+            return Err(CaliptraApiError::MailboxNoResponseData);
         };
-
-        if data.len() < R::Resp::MIN_SIZE || data.len() > mem::size_of::<R::Resp>() {
-            return Err(CaliptraApiError::MailboxUnexpectedResponseLen {
-                expected_min: R::Resp::MIN_SIZE as u32,
-                expected_max: mem::size_of::<R::Resp>() as u32,
-                actual: data.len() as u32,
-            });
-        }
-
-        let mut response = R::Resp::new_zeroed();
-        response.as_mut_bytes()[..data.len()].copy_from_slice(data);
-
-        let (response_header, _) = MailboxRespHeader::read_from_prefix(data).unwrap();
-        let actual_checksum = calc_checksum(0, &data[4..]);
-        if actual_checksum != response_header.chksum {
-            return Err(CaliptraApiError::MailboxRespInvalidChecksum {
-                expected: response_header.chksum,
-                actual: actual_checksum,
-            });
-        }
-        if response_header.fips_status != MailboxRespHeader::FIPS_STATUS_APPROVED {
-            return Err(CaliptraApiError::MailboxRespInvalidFipsStatus(
-                response_header.fips_status,
-            ));
-        }
-        Ok(response)
+        parse_resp::<R>(data)
     }
 
     fn send_stash_measurement_req(
@@ -360,3 +296,93 @@ pub trait SocManager {
         Err(CaliptraApiError::StashMeasurementFailed)
     }
 }
+
+/// Read the outcome of a command that is no longer busy, along with its
+/// response, and hand the mailbox back to Caliptra.
+pub fn read_mailbox_result<'r, S: SocManager + ?Sized>(
+    soc: &mut S,
+    resp_data: &'r mut [u8],
+) -> core::result::Result<Option<&'r [u8]>, CaliptraApiError> {
+    let status = soc.soc_mbox().status().read().status();
+    if status.cmd_failure() {
+        soc.soc_mbox().execute().write(|w| w.execute(false));
+        let soc_ifc = soc.soc_ifc();
+        return Err(CaliptraApiError::MailboxCmdFailed(
+            if soc_ifc.cptra_fw_error_fatal().read() != 0 {
+                soc_ifc.cptra_fw_error_fatal().read()
+            } else {
+                soc_ifc.cptra_fw_error_non_fatal().read()
+            },
+        ));
+    }
+    if status.cmd_complete() {
+        soc.soc_mbox().execute().write(|w| w.execute(false));
+        return Ok(None);
+    }
+    if !status.data_ready() {
+        return Err(CaliptraApiError::UnknownCommandStatus(status as u32));
+    }
+
+    let res = mbox_read_response(soc.soc_mbox(), resp_data);
+
+    soc.soc_mbox().execute().write(|w| w.execute(false));
+
+    let buf = res?;
+
+    Ok(Some(buf))
+}
+
+/// Check the sizes of a typed request and its response, and calculate the
+/// checksum of the request.
+pub(crate) fn populate_req_checksum<R: Request>(
+    req: &mut R,
+) -> core::result::Result<(), CaliptraApiError> {
+    if mem::size_of::<R>() < mem::size_of::<MailboxReqHeader>() {
+        return Err(CaliptraApiError::MailboxReqTypeTooSmall);
+    }
+    if mem::size_of::<R::Resp>() < mem::size_of::<MailboxRespHeader>() {
+        return Err(CaliptraApiError::MailboxRespTypeTooSmall);
+    }
+    if R::Resp::MIN_SIZE < mem::size_of::<MailboxRespHeader>() {
+        return Err(CaliptraApiError::MailboxRespTypeTooSmall);
+    }
+    let (header_bytes, payload_bytes) = req
+        .as_mut_bytes()
+        .split_at_mut(mem::size_of::<MailboxReqHeader>());
+
+    let header = MailboxReqHeader::mut_from_bytes(header_bytes as &mut [u8]).unwrap();
+    header.chksum = calc_checksum(R::ID.into(), payload_bytes);
+    Ok(())
+}
+
+/// Validate the response to a typed request
+pub(crate) fn parse_resp<R: Request>(
+    data: &[u8],
+) -> core::result::Result<R::Resp, CaliptraApiError> {
+    if data.len() < R::Resp::MIN_SIZE || data.len() > mem::size_of::<R::Resp>() {
+        return Err(CaliptraApiError::MailboxUnexpectedResponseLen {
+            expected_min: R::Resp::MIN_SIZE as u32,
+            expected_max: mem::size_of::<R::Resp>() as u32,
+            actual: data.len() as u32,
+        });
+    }
+
+    let mut response = R::Resp::new_zeroed();
+    response.as_mut_bytes()[..data.len()].copy_from_slice(data);
+
+    let (response_header, _) = MailboxRespHeader::read_from_prefix(data).unwrap();
+    let actual_checksum = calc_checksum(0, &data[4..]);
+    if actual_checksum != response_header.chksum {
+        return Err(CaliptraApiError::MailboxRespInvalidChecksum {
+            expected: response_header.chksum,
+            actual: actual_checksum,
+        });
+    }
+    if response_header.fips_status != MailboxRespHeader::FIPS_STATUS_APPROVED {
+        return Err(CaliptraApiError::MailboxRespInvalidFipsStatus(
+            response_header.fips_status,
+        ));
+    }
+    response.validate()?;
+    Ok(response)
+}
@@ -0,0 +1,115 @@
+// Licensed under the Apache-2.0 license
+
+//! Mailbox transport for callers that cannot block a thread while Caliptra
+//! executes a command, such as SoC management daemons built on an async
+//! runtime. [`SocManager`] is the blocking counterpart.
+//!
+//! The futures returned here do not time out; bound them with the timer of
+//! the executor they run on.
+
+use crate::{
+    mailbox::Request,
+    soc_mgr::{parse_resp, populate_req_checksum, read_mailbox_result},
+    CaliptraApiError, SocManager,
+};
+use core::future::poll_fn;
+use core::task::{Context, Poll};
+use zerocopy::IntoBytes;
+
+/// Transport that executes mailbox commands without blocking
+pub trait AsyncMailboxTransport {
+    /// Lock the mailbox, write `cmd` and `req`, and ask Caliptra to execute
+    /// the command.
+    fn start_exec(&mut self, cmd: u32, req: &[u8]) -> Result<(), CaliptraApiError>;
+
+    /// Check whether the command passed to `start_exec` has finished.
+    ///
+    /// Returns the length of the response copied to `resp_data`, or `None`
+    /// if Caliptra indicated success without data. While the command is
+    /// still executing, returns `Poll::Pending` and arranges for `cx` to be
+    /// woken.
+    fn poll_finish_exec(
+        &mut self,
+        cx: &mut Context<'_>,
+        resp_data: &mut [u8],
+    ) -> Poll<Result<Option<usize>, CaliptraApiError>>;
+}
+
+/// Poll the mailbox of `soc` once
+///
+/// While the command is busy, wakes `cx` straight away so that the executor
+/// polls again after running its other tasks.
+pub fn poll_soc_mailbox<S: SocManager + ?Sized>(
+    soc: &mut S,
+    cx: &mut Context<'_>,
+    resp_data: &mut [u8],
+) -> Poll<Result<Option<usize>, CaliptraApiError>> {
+    if soc.soc_mbox().status().read().status().cmd_busy() {
+        cx.waker().wake_by_ref();
+        return Poll::Pending;
+    }
+    Poll::Ready(read_mailbox_result(soc, resp_data).map(|data| data.map(|data| data.len())))
+}
+
+/// Transport that talks to the Caliptra registers directly through a
+/// [`SocManager`]
+///
+/// The emulated and verilated models only make progress when stepped; use
+/// `caliptra_hw_model::AsyncModel` for those.
+pub struct MmioTransport<'a, S: SocManager> {
+    soc: &'a mut S,
+}
+
+impl<'a, S: SocManager> MmioTransport<'a, S> {
+    pub fn new(soc: &'a mut S) -> Self {
+        Self { soc }
+    }
+}
+
+impl<S: SocManager> AsyncMailboxTransport for MmioTransport<'_, S> {
+    fn start_exec(&mut self, cmd: u32, req: &[u8]) -> Result<(), CaliptraApiError> {
+        self.soc.start_mailbox_exec(cmd, req)
+    }
+
+    fn poll_finish_exec(
+        &mut self,
+        cx: &mut Context<'_>,
+        resp_data: &mut [u8],
+    ) -> Poll<Result<Option<usize>, CaliptraApiError>> {
+        poll_soc_mailbox(self.soc, cx, resp_data)
+    }
+}
+
+/// Executes `cmd` with request data `req`. Returns `Ok(Some(_))` if the uC
+/// responded with data, `Ok(None)` if the uC indicated success without data,
+/// Err(CaliptraApiError::MailboxCmdFailed) if the microcontroller responded
+/// with an error, or other errors if there was a problem communicating with
+/// the mailbox.
+pub async fn mailbox_exec<'r, T: AsyncMailboxTransport + ?Sized>(
+    transport: &mut T,
+    cmd: u32,
+    req: &[u8],
+    resp_data: &'r mut [u8],
+) -> Result<Option<&'r [u8]>, CaliptraApiError> {
+    transport.start_exec(cmd, req)?;
+    let len = poll_fn(|cx| transport.poll_finish_exec(cx, resp_data)).await?;
+    let resp_data: &'r [u8] = resp_data;
+    Ok(len.map(|len| &resp_data[..len]))
+}
+
+/// Executes a typed request and (if success), returns the typed response.
+/// The checksum field of the request is calculated, and the checksum of the
+/// response is validated.
+pub async fn mailbox_exec_req<R: Request, T: AsyncMailboxTransport + ?Sized>(
+    transport: &mut T,
+    mut req: R,
+    resp_bytes: &mut [u8],
+) -> Result<R::Resp, CaliptraApiError> {
+    populate_req_checksum(&mut req)?;
+
+    let Some(data) = mailbox_exec(transport, R::ID.into(), req.as_bytes(), resp_bytes).await?
+    else {
+        return Err(CaliptraApiError::MailboxNoResponseData);
+    };
+    parse_resp::<R>(data)
+}
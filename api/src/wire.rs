@@ -0,0 +1,181 @@
+// Licensed under the Apache-2.0 license
+
+//! Wire formats of the mailbox commands.
+//!
+//! Every request and response is a `#[repr(C)]` struct that is sent over the
+//! mailbox as its in-memory bytes, so all multi-byte fields are little endian:
+//! the byte order of Caliptra and of every host this crate supports. Each
+//! struct listed in [`WIRE_FORMATS`] is checked at compile time to implement
+//! the zerocopy traits the transport relies on.
+//!
+//! `FIRMWARE_LOAD` carries a raw firmware image and has no entry.
+
+use crate::mailbox::*;
+use core::mem::size_of;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+const _: () = assert!(
+    cfg!(target_endian = "little"),
+    "Mailbox structs are sent in native byte order, which must be little endian"
+);
+
+/// Wire format of one version of a command
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WireFormat {
+    /// Command code
+    pub cmd: u32,
+
+    /// Request version. Version 1 requests start with `MailboxReqHeader`,
+    /// later versions with `MailboxReqVersionedHeader`.
+    pub version: u8,
+
+    pub req_name: &'static str,
+
+    /// Size of the request struct. Requests with variable-length data may be
+    /// shorter.
+    pub req_size: usize,
+
+    pub resp_name: &'static str,
+
+    /// Size of the response struct. Responses with variable-length data may
+    /// be shorter.
+    pub resp_size: usize,
+}
+
+const fn assert_wire<T: IntoBytes + FromBytes + Immutable + KnownLayout>() {}
+
+macro_rules! wire_formats {
+    ($($cmd:ident $version:literal: $req:ty => $resp:ty,)*) => {
+        /// Request and response structs of every command, ordered by command
+        /// and then by version
+        pub const WIRE_FORMATS: &[WireFormat] = &[$(
+            WireFormat {
+                cmd: CommandId::$cmd.0,
+                version: $version,
+                req_name: stringify!($req),
+                req_size: size_of::<$req>(),
+                resp_name: stringify!($resp),
+                resp_size: size_of::<$resp>(),
+            },
+        )*];
+
+        const _: () = {
+            $(
+                assert_wire::<$req>();
+                assert_wire::<$resp>();
+            )*
+        };
+    };
+}
+
+wire_formats! {
+    GET_IDEV_CERT 1: GetIdevCertReq => GetIdevCertResp,
+    GET_IDEV_INFO 1: MailboxReqHeader => GetIdevInfoResp,
+    POPULATE_IDEV_CERT 1: PopulateIdevCertReq => MailboxRespHeader,
+    GET_LDEV_CERT 1: GetLdevCertReq => GetLdevCertResp,
+    GET_FMC_ALIAS_CERT 1: GetFmcAliasCertReq => GetFmcAliasCertResp,
+    GET_RT_ALIAS_CERT 1: GetRtAliasCertReq => GetRtAliasCertResp,
+    ECDSA384_VERIFY 1: EcdsaVerifyReq => MailboxRespHeader,
+    LMS_VERIFY 1: LmsVerifyReq => MailboxRespHeader,
+    STASH_MEASUREMENT 1: StashMeasurementReq => StashMeasurementResp,
+    INVOKE_DPE 1: InvokeDpeReq => InvokeDpeResp,
+    DISABLE_ATTESTATION 1: MailboxReqHeader => MailboxRespHeader,
+    FW_INFO 1: MailboxReqHeader => FwInfoV1Resp,
+    FW_INFO 2: FwInfoVersionedReq => FwInfoResp,
+    DPE_TAG_TCI 1: TagTciReq => MailboxRespHeader,
+    DPE_GET_TAGGED_TCI 1: GetTaggedTciReq => GetTaggedTciResp,
+    INCREMENT_PCR_RESET_COUNTER 1: IncrementPcrResetCounterReq => MailboxRespHeader,
+    QUOTE_PCRS 1: QuotePcrsReq => QuotePcrsResp,
+    QUOTE_PCRS 2: QuotePcrsCwtReq => QuotePcrsCwtResp,
+    EXTEND_PCR 1: ExtendPcrReq => MailboxRespHeader,
+    ADD_SUBJECT_ALT_NAME 1: AddSubjectAltNameReq => MailboxRespHeader,
+    CERTIFY_KEY_EXTENDED 1: CertifyKeyExtendedReq => CertifyKeyExtendedResp,
+    VERSION 1: MailboxReqHeader => FipsVersionResp,
+    SELF_TEST_START 1: MailboxReqHeader => MailboxRespHeader,
+    SELF_TEST_GET_RESULTS 1: MailboxReqHeader => SelfTestGetResultsResp,
+    SHUTDOWN 1: MailboxReqHeader => MailboxRespHeader,
+    ZEROIZE 1: MailboxReqHeader => ZeroizeResp,
+    CAPABILITIES 1: MailboxReqHeader => CapabilitiesResp,
+    CAPABILITIES 2: CapabilitiesCmdVersionReq => CapabilitiesCmdVersionResp,
+    CAPABILITIES 3: CapabilitiesNegotiateReq => CapabilitiesNegotiateResp,
+    SET_AUTH_MANIFEST 1: SetAuthManifestReq => MailboxRespHeader,
+    AUTHORIZE_AND_STASH 1: AuthorizeAndStashReq => AuthorizeAndStashResp,
+    GET_IDEV_CSR 1: GetIdevCsrReq => GetIdevCsrResp,
+    GET_FMC_ALIAS_CSR 1: GetFmcAliasCsrReq => GetFmcAliasCsrResp,
+    GET_RT_ALIAS_CSR 1: GetRtAliasCsrReq => GetRtAliasCsrResp,
+    SIGN_WITH_EXPORTED_ECDSA 1: SignWithExportedEcdsaReq => SignWithExportedEcdsaResp,
+    REVOKE_LOCALITY 1: RevokeLocalityReq => MailboxRespHeader,
+    INJECT_ERROR 1: InjectErrorReq => MailboxRespHeader,
+    GET_UPDATE_HISTORY 1: GetUpdateHistoryReq => GetUpdateHistoryResp,
+    INCREMENT_FUSE_SVN 1: IncrementFuseSvnReq => IncrementFuseSvnResp,
+    GET_DPE_STATS 1: GetDpeStatsReq => GetDpeStatsResp,
+    COMMIT_OWNER_KEY_ROTATION 1: CommitOwnerKeyRotationReq => MailboxRespHeader,
+    SET_CERT_POLICY 1: SetCertPolicyReq => MailboxRespHeader,
+    SET_CERT_DIST_POINTS 1: SetCertDistPointsReq => MailboxRespHeader,
+    GET_KV_STATUS 1: GetKvStatusReq => GetKvStatusResp,
+    CAPTURE_RAW_ENTROPY 1: CaptureRawEntropyReq => MailboxRespHeader,
+    GET_RAW_ENTROPY 1: GetRawEntropyReq => GetRawEntropyResp,
+    START_JOB 1: StartJobReq => StartJobResp,
+    GET_JOB_STATUS 1: GetJobStatusReq => GetJobStatusResp,
+    BATCH 1: BatchReq => BatchResp,
+    SUSPEND_PREPARE 1: MailboxReqHeader => MailboxRespHeader,
+    RESUME_NOTIFY 1: MailboxReqHeader => MailboxRespHeader,
+    PREPARE_FOR_SLEEP 1: MailboxReqHeader => MailboxRespHeader,
+    CONFIGURE_WDT 1: ConfigureWdtReq => MailboxRespHeader,
+    SET_OBJECT_SCHEMAS 1: SetObjectSchemasReq => MailboxRespHeader,
+    VERIFY_OBJECT 1: VerifyObjectReq => VerifyObjectResp,
+    GET_CRASH_DUMP 1: MailboxReqHeader => GetCrashDumpResp,
+    SET_LOG_LEVEL 1: SetLogLevelReq => MailboxRespHeader,
+    GET_BOOT_PROFILE 1: MailboxReqHeader => GetBootProfileResp,
+    GET_PROVISIONING_STATUS 1: MailboxReqHeader => GetProvisioningStatusResp,
+    RESEED_FIELD_ENTROPY 1: ReseedFieldEntropyReq => MailboxRespHeader,
+    GET_ENTROPY_HEALTH 1: GetEntropyHealthReq => GetEntropyHealthResp,
+    GET_FHT 1: MailboxReqHeader => GetFhtResp,
+    SPDM 1: SpdmReq => SpdmResp,
+    MCTP 1: MctpReq => MctpResp,
+    SET_MEASUREMENT_MANIFEST 1: SetMeasurementManifestReq => SetMeasurementManifestResp,
+    START_STREAM_VERIFY 1: StartStreamVerifyReq => MailboxRespHeader,
+    STREAM_DATA 1: StreamDataReq => MailboxRespHeader,
+    FINISH_STREAM_VERIFY 1: FinishStreamVerifyReq => FinishStreamVerifyResp,
+    SEAL_KEY 1: SealKeyReq => SealKeyResp,
+    UNSEAL_KEY 1: UnsealKeyReq => UnsealKeyResp,
+    CREATE_COUNTER 1: MailboxReqHeader => CreateCounterResp,
+    INCREMENT_COUNTER 1: IncrementCounterReq => IncrementCounterResp,
+    READ_COUNTER 1: ReadCounterReq => ReadCounterResp,
+    TIMESTAMP 1: TimestampReq => TimestampResp,
+    PAIR_MAILBOX 1: PairMailboxReq => PairMailboxResp,
+}
+
+/// Returns the wire formats of every version of `cmd`
+pub fn wire_formats(cmd: u32) -> impl Iterator<Item = &'static WireFormat> {
+    WIRE_FORMATS.iter().filter(move |format| format.cmd == cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_command_listed() {
+        for cmd in CommandId::CAPABILITY_BITS.iter() {
+            if *cmd == CommandId::FIRMWARE_LOAD {
+                continue;
+            }
+            let versions: Vec<u8> = wire_formats(cmd.0).map(|format| format.version).collect();
+            let expected: Vec<u8> = (1..=versions.len() as u8).collect();
+            assert_eq!(versions, expected, "Command 0x{:08x}", cmd.0);
+        }
+        assert_eq!(wire_formats(CommandId::FIRMWARE_LOAD.0).count(), 0);
+    }
+
+    #[test]
+    fn test_headers() {
+        for format in WIRE_FORMATS {
+            assert!(format.req_size >= size_of::<MailboxReqHeader>());
+            if format.version > 1 {
+                assert!(format.req_size >= size_of::<MailboxReqVersionedHeader>());
+            }
+            assert!(format.resp_size >= size_of::<MailboxRespHeader>());
+        }
+    }
+}
@@ -39,6 +39,7 @@ fn main() {
                 .value_parser(value_parser!(PathBuf)),
         )
         .arg(arg!(--"fake-rom" [FILE] "Fake ROM").value_parser(value_parser!(PathBuf)))
+        .arg(arg!(--"val-rom" [FILE] "Validation ROM").value_parser(value_parser!(PathBuf)))
         .arg(arg!(--"fake-fw" [FILE] "Fake FW bundle image").value_parser(value_parser!(PathBuf)))
         .arg(
             arg!(--"hashes" [FILE] "File path for output JSON file containing image bundle header hashes for external signing tools")
@@ -63,6 +64,11 @@ fn main() {
         std::fs::write(path, rom).unwrap();
     }
 
+    if let Some(path) = args.get_one::<PathBuf>("val-rom") {
+        let rom = caliptra_builder::build_firmware_rom(&firmware::ROM_VAL_WITH_UART).unwrap();
+        std::fs::write(path, rom).unwrap();
+    }
+
     let fmc_svn = if let Some(fmc) = args.get_one::<u32>("fmc-svn") {
         *fmc
     } else {
@@ -10,6 +10,7 @@ pub fn rom_from_env() -> &'static FwId<'static> {
         Ok("ROM") => &ROM,
         Ok("ROM_WITHOUT_UART") => &ROM,
         Ok("ROM_WITH_UART") => &ROM_WITH_UART,
+        Ok("ROM_VAL_WITH_UART") => &ROM_VAL_WITH_UART,
         Ok(s) => panic!("unexpected CPRTA_TEST_ROM env-var value: {s:?}"),
         Err(_) => &ROM_WITH_UART,
     }
@@ -33,6 +34,12 @@ pub const ROM_FAKE_WITH_UART: FwId = FwId {
     features: &["emu", "fake-rom"],
 };
 
+pub const ROM_VAL_WITH_UART: FwId = FwId {
+    crate_name: "caliptra-rom",
+    bin_name: "caliptra-rom",
+    features: &["emu", "val-rom"],
+};
+
 pub const ROM_WITH_FIPS_TEST_HOOKS: FwId = FwId {
     crate_name: "caliptra-rom",
     bin_name: "caliptra-rom",
@@ -51,28 +58,56 @@ pub const FMC_FAKE_WITH_UART: FwId = FwId {
     features: &["emu", "fake-fmc"],
 };
 
+pub const FMC_WITH_UART_RT_ALIAS_KEY_ROTATION: FwId = FwId {
+    crate_name: "caliptra-fmc",
+    bin_name: "caliptra-fmc",
+    features: &["emu", "rt-alias-key-rotation"],
+};
+
 pub const APP: FwId = FwId {
     crate_name: "caliptra-runtime",
     bin_name: "caliptra-runtime",
-    features: &["fips_self_test"],
+    features: &["fips_self_test", "dpe-profile-p384"],
 };
 
 pub const APP_WITH_UART: FwId = FwId {
     crate_name: "caliptra-runtime",
     bin_name: "caliptra-runtime",
-    features: &["emu", "fips_self_test"],
+    features: &["emu", "fips_self_test", "dpe-profile-p384"],
 };
 
 pub const APP_WITH_UART_FIPS_TEST_HOOKS: FwId = FwId {
     crate_name: "caliptra-runtime",
     bin_name: "caliptra-runtime",
-    features: &["emu", "fips_self_test", "fips-test-hooks"],
+    features: &[
+        "emu",
+        "fips_self_test",
+        "fips-test-hooks",
+        "dpe-profile-p384",
+    ],
+};
+
+pub const APP_WITH_UART_DPE_TEST_HOOKS: FwId = FwId {
+    crate_name: "caliptra-runtime",
+    bin_name: "caliptra-runtime",
+    features: &[
+        "emu",
+        "fips_self_test",
+        "dpe-test-hooks",
+        "dpe-profile-p384",
+    ],
 };
 
 pub const APP_WITH_UART_FPGA: FwId = FwId {
     crate_name: "caliptra-runtime",
     bin_name: "caliptra-runtime",
-    features: &["emu", "fips_self_test", "fpga_realtime"],
+    features: &["emu", "fips_self_test", "fpga_realtime", "dpe-profile-p384"],
+};
+
+pub const APP_WITH_UART_DPE_P256: FwId = FwId {
+    crate_name: "caliptra-runtime",
+    bin_name: "caliptra-runtime",
+    features: &["emu", "fips_self_test", "dpe-profile-p256"],
 };
 
 pub const APP_ZEROS: FwId = FwId {
@@ -307,6 +342,12 @@ pub mod driver_tests {
         ..BASE_FWID
     };
 
+    pub const TRNG_DRIVER_RESPONDER_DETERMINISTIC_DRBG: FwId = FwId {
+        bin_name: "trng_driver_responder",
+        features: &["emu", "deterministic-drbg"],
+        ..BASE_FWID
+    };
+
     pub const PERSISTENT: FwId = FwId {
         bin_name: "persistent",
         ..BASE_FWID
@@ -392,13 +433,17 @@ pub const REGISTERED_FW: &[&FwId] = &[
     &ROM,
     &ROM_WITH_UART,
     &ROM_FAKE_WITH_UART,
+    &ROM_VAL_WITH_UART,
     &ROM_WITH_FIPS_TEST_HOOKS,
     &FMC_WITH_UART,
     &FMC_FAKE_WITH_UART,
+    &FMC_WITH_UART_RT_ALIAS_KEY_ROTATION,
     &APP,
     &APP_WITH_UART,
     &APP_WITH_UART_FIPS_TEST_HOOKS,
+    &APP_WITH_UART_DPE_TEST_HOOKS,
     &APP_WITH_UART_FPGA,
+    &APP_WITH_UART_DPE_P256,
     &APP_ZEROS,
     &FMC_ZEROS,
     &caliptra_builder_tests::FWID,
@@ -440,6 +485,7 @@ pub const REGISTERED_FW: &[&FwId] = &[
     &driver_tests::CSRNG_FAIL_REPCNT_TESTS,
     &driver_tests::CSRNG_FAIL_ADAPTP_TESTS,
     &driver_tests::TRNG_DRIVER_RESPONDER,
+    &driver_tests::TRNG_DRIVER_RESPONDER_DETERMINISTIC_DRBG,
     &driver_tests::PERSISTENT,
     &rom_tests::ASM_TESTS,
     &rom_tests::TEST_FMC_WITH_UART,
@@ -20,7 +20,9 @@ use caliptra_image_elf::ElfExecutable;
 use caliptra_image_gen::{
     ImageGenerator, ImageGeneratorConfig, ImageGeneratorOwnerConfig, ImageGeneratorVendorConfig,
 };
-use caliptra_image_types::{ImageBundle, ImageRevision, RomInfo};
+use caliptra_image_types::{
+    ImageBundle, ImageManifestFormat, ImageRevision, ImageTocEntryCompression, RomInfo,
+};
 use elf::endian::LittleEndian;
 use nix::fcntl::FlockArg;
 use zerocopy::IntoBytes;
@@ -508,6 +510,8 @@ pub struct ImageOptions {
     pub app_svn: u32,
     pub vendor_config: ImageGeneratorVendorConfig,
     pub owner_config: Option<ImageGeneratorOwnerConfig>,
+    pub app_compression: ImageTocEntryCompression,
+    pub manifest_format: ImageManifestFormat,
 }
 impl Default for ImageOptions {
     fn default() -> Self {
@@ -518,6 +522,8 @@ impl Default for ImageOptions {
             app_svn: Default::default(),
             vendor_config: caliptra_image_fake_keys::VENDOR_CONFIG_KEY_0,
             owner_config: Some(caliptra_image_fake_keys::OWNER_CONFIG),
+            app_compression: Default::default(),
+            manifest_format: Default::default(),
         }
     }
 }
@@ -537,9 +543,11 @@ pub fn build_and_sign_image(
             opts.fmc_svn,
             image_revision()?,
         )?,
-        runtime: ElfExecutable::new(&app_elf, opts.app_version, opts.app_svn, image_revision()?)?,
+        runtime: ElfExecutable::new(&app_elf, opts.app_version, opts.app_svn, image_revision()?)?
+            .with_compression(opts.app_compression),
         vendor_config: opts.vendor_config,
         owner_config: opts.owner_config,
+        manifest_format: opts.manifest_format,
     })?;
     Ok(image)
 }
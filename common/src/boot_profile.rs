@@ -0,0 +1,35 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    boot_profile.rs
+
+Abstract:
+
+    File contains the routine recording boot milestones in the boot profile.
+
+--*/
+
+use caliptra_drivers::BootCheckpoint;
+#[cfg(target_arch = "riscv32")]
+use caliptra_drivers::PersistentDataAccessor;
+
+/// Record the current cycle count as the time the boot reached
+/// `checkpoint`. `BootCheckpoint::RomEntry` clears the previous profile.
+#[cfg(target_arch = "riscv32")]
+pub fn boot_checkpoint(checkpoint: BootCheckpoint) {
+    // Only the low word is kept; a boot does not take 2^32 cycles.
+    let cycles: u32;
+    unsafe { core::arch::asm!("csrr {}, mcycle", out(reg) cycles) };
+
+    let mut persistent_data = unsafe { PersistentDataAccessor::new() };
+    persistent_data
+        .get_mut()
+        .boot_profile
+        .record(checkpoint, cycles);
+}
+
+#[cfg(not(target_arch = "riscv32"))]
+pub fn boot_checkpoint(_checkpoint: BootCheckpoint) {}
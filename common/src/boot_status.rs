@@ -75,6 +75,7 @@ pub enum RomBootStatus {
     UpdateResetLoadImageComplete = UPDATE_RESET_BOOT_STATUS_BASE + 5,
     UpdateResetOverwriteManifestComplete = UPDATE_RESET_BOOT_STATUS_BASE + 6,
     UpdateResetComplete = UPDATE_RESET_BOOT_STATUS_BASE + 7,
+    UpdateResetNoOpComplete = UPDATE_RESET_BOOT_STATUS_BASE + 8,
 
     // ROM Global Boot Statues
     CfiInitialized = ROM_GLOBAL_BOOT_STATUS_BASE,
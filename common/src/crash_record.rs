@@ -0,0 +1,52 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    crash_record.rs
+
+Abstract:
+
+    File contains routines for capturing the crash record on a fatal error.
+
+--*/
+
+use caliptra_cpu::TrapRecord;
+use caliptra_drivers::{CrashRecord, PersistentDataAccessor, CRASH_RECORD_MARKER};
+use caliptra_registers::mbox::MboxCsr;
+
+use crate::stack_guard::stack_high_water;
+
+/// Save the state of the firmware in the crash record of the persistent
+/// data, replacing any previous record
+///
+/// # Arguments
+///
+/// * `code` - Fatal error code
+/// * `trap_record` - Trap record, if the error was raised by a trap handler
+pub fn record_crash(code: u32, trap_record: Option<&TrapRecord>) {
+    let mut persistent_data = unsafe { PersistentDataAccessor::new() };
+    let pdata = persistent_data.get_mut();
+    let mbox = unsafe { MboxCsr::new() };
+
+    let mut record = CrashRecord {
+        marker: CRASH_RECORD_MARKER,
+        error_code: code,
+        last_cmd: mbox.regs().cmd().read(),
+        fht_validity: CrashRecord::fht_validity(&pdata.fht),
+        stack_high_water: stack_high_water(),
+        ..Default::default()
+    };
+    if let Some(trap_record) = trap_record {
+        record.has_trap = 1;
+        record.mcause = trap_record.mcause;
+        record.mscause = trap_record.mscause;
+        record.mepc = trap_record.mepc;
+        record.mtval = trap_record.mtval;
+        record.mstatus = trap_record.mstatus;
+        record.ra = trap_record.ra;
+        record.sp = trap_record.sp;
+    }
+    pdata.crash_record = record;
+}
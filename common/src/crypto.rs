@@ -11,7 +11,18 @@ Abstract:
     Crypto helper routines
 
 --*/
-use caliptra_drivers::{Ecc384PubKey, KeyId};
+use caliptra_drivers::{
+    Array4x12, CaliptraResult, Ecc384PubKey, HashValue, Hmac384, Kdf, KeyId, KeyReadArgs,
+    LmotsAlgorithmType, Lms, LmsAlgorithmType, LmsIdentifier, LmsPublicKey, Sha256, Trng,
+};
+use zeroize::Zeroize;
+
+/// LMS parameter set of DICE alias keys
+pub const LMS_ALIAS_KEY_TREE_TYPE: LmsAlgorithmType = LmsAlgorithmType::LmsSha256N24H5;
+
+/// LM-OTS parameter set of DICE alias keys
+pub const LMS_ALIAS_KEY_OTS_TYPE: LmotsAlgorithmType = LmotsAlgorithmType::LmotsSha256N24W8;
+
 /// DICE  Layer Key Pair
 #[derive(Debug)]
 pub struct Ecc384KeyPair {
@@ -21,3 +32,58 @@ pub struct Ecc384KeyPair {
     /// Public Key
     pub pub_key: Ecc384PubKey,
 }
+
+/// Generate an LMS DICE alias key from a CDI
+///
+/// A single KDF output provides the 24-byte private key seed followed by the
+/// 16-byte LMS identifier. The seed is erased once the public key has been
+/// computed, so only the public key is available to the caller.
+///
+/// # Arguments
+///
+/// * `hmac` - HMAC384 driver
+/// * `trng` - TRNG driver
+/// * `sha256` - SHA256 driver
+/// * `lms` - LMS driver
+/// * `cdi` - Key slot holding the CDI
+/// * `label` - Diversification label
+///
+/// # Returns
+///
+/// * `LmsPublicKey` - LMS public key
+pub fn lms_alias_key_gen(
+    hmac: &mut Hmac384,
+    trng: &mut Trng,
+    sha256: &mut Sha256,
+    lms: &Lms,
+    cdi: KeyId,
+    label: &[u8],
+) -> CaliptraResult<LmsPublicKey<6>> {
+    let mut kdf_out = Array4x12::default();
+    Kdf::new(hmac, trng).derive(
+        KeyReadArgs::new(cdi).into(),
+        label,
+        None,
+        (&mut kdf_out).into(),
+    )?;
+
+    let mut kdf_bytes = <[u8; 48]>::from(&kdf_out);
+    kdf_out.zeroize();
+    let mut seed_bytes = [0u8; 24];
+    seed_bytes.copy_from_slice(&kdf_bytes[..24]);
+    let mut seed = HashValue::<6>::from(seed_bytes);
+    let mut id = LmsIdentifier::default();
+    id.copy_from_slice(&kdf_bytes[24..40]);
+    kdf_bytes.zeroize();
+    seed_bytes.zeroize();
+
+    let result = lms.generate_public_key::<6, 26, 5>(
+        sha256,
+        LMS_ALIAS_KEY_TREE_TYPE,
+        LMS_ALIAS_KEY_OTS_TYPE,
+        &id,
+        &seed,
+    );
+    seed.0.zeroize();
+    result
+}
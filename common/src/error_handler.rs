@@ -1,12 +1,29 @@
 // Licensed under the Apache-2.0 license
+use crate::log_error;
+use caliptra_cpu::TrapRecord;
 use caliptra_drivers::{
-    cprintln, report_fw_error_fatal, report_fw_error_non_fatal, Ecc384, Hmac384, KeyVault, Mailbox,
-    Sha256, Sha2_512_384Acc, Sha384, SocIfc,
+    report_fw_error_fatal, report_fw_error_non_fatal, Ecc384, Hmac384, KeyVault, Mailbox, Sha256,
+    Sha2_512_384Acc, Sha384, SocIfc,
 };
 
-#[allow(clippy::empty_loop)]
 pub fn handle_fatal_error(code: u32) -> ! {
-    cprintln!("Fatal Error: 0x{:08X}", code);
+    #[cfg(feature = "runtime")]
+    crate::crash_record::record_crash(code, None);
+    halt(code)
+}
+
+/// Same as `handle_fatal_error`, for errors raised by the exception and NMI
+/// handlers
+#[cfg_attr(not(feature = "runtime"), allow(unused_variables))]
+pub fn handle_fatal_trap(code: u32, trap_record: &TrapRecord) -> ! {
+    #[cfg(feature = "runtime")]
+    crate::crash_record::record_crash(code, Some(trap_record));
+    halt(code)
+}
+
+#[allow(clippy::empty_loop)]
+fn halt(code: u32) -> ! {
+    log_error!("Fatal Error: 0x{:08X}", code);
     report_fw_error_fatal(code);
     // Populate the non-fatal error code too; if there was a
     // non-fatal error stored here before we don't want somebody
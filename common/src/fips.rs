@@ -1,6 +1,6 @@
 // Licensed under the Apache-2.0 license
 
-use crate::cprintln;
+use crate::log_info;
 use crate::mailbox_api::{FipsVersionResp, MailboxRespHeader};
 use caliptra_drivers::CaliptraResult;
 use caliptra_drivers::SocIfc;
@@ -12,7 +12,7 @@ impl FipsVersionCmd {
 
     #[cfg_attr(feature = "runtime", inline(never))]
     pub fn execute(soc_ifc: &SocIfc) -> CaliptraResult<FipsVersionResp> {
-        cprintln!("[rt] FIPS Version");
+        log_info!("[rt] FIPS Version");
 
         Ok(FipsVersionResp {
             hdr: MailboxRespHeader::default(),
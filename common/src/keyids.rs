@@ -29,6 +29,12 @@ pub const KEY_ID_FMC_PRIV_KEY: KeyId = KeyId::KeyId7;
 pub const KEY_ID_RT_CDI: KeyId = KeyId::KeyId4;
 #[cfg(feature = "fmc")]
 pub const KEY_ID_RT_PRIV_KEY: KeyId = KeyId::KeyId5;
+#[cfg(feature = "fmc")]
+pub const KEY_ID_RT_CDI_ALT: KeyId = KeyId::KeyId11;
+#[cfg(feature = "fmc")]
+pub const KEY_ID_RT_PRIV_KEY_ALT: KeyId = KeyId::KeyId12;
+#[cfg(any(feature = "fmc", feature = "runtime"))]
+pub const KEY_ID_FHT_MAC: KeyId = KeyId::KeyId13;
 #[cfg(feature = "runtime")]
 pub const KEY_ID_DPE_CDI: KeyId = KeyId::KeyId8;
 #[cfg(feature = "runtime")]
@@ -2,6 +2,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod boot_profile;
 pub mod boot_status;
 pub mod capabilities {
     pub use caliptra_api::Capabilities;
@@ -9,11 +10,15 @@ pub mod capabilities {
 pub mod checksum {
     pub use caliptra_api::{calc_checksum, verify_checksum};
 }
+pub mod crash_record;
 pub mod crypto;
 pub mod dice;
 pub mod error_handler;
 pub mod fips;
 pub mod keyids;
+pub mod log;
+pub mod panic_site;
+pub mod stack_guard;
 pub mod verifier;
 pub mod wdt;
 
@@ -33,8 +38,9 @@ pub use caliptra_drivers::memory_layout;
 pub use caliptra_drivers::pcr_log as pcr;
 pub use caliptra_drivers::printer::HexBytes;
 pub use caliptra_drivers::printer::Printer;
-pub use error_handler::handle_fatal_error;
+pub use error_handler::{handle_fatal_error, handle_fatal_trap};
 pub use fuse::{FuseLogEntry, FuseLogEntryId};
+pub use panic_site::PanicSite;
 pub use pcr::{PcrLogEntry, PcrLogEntryId, RT_FW_CURRENT_PCR, RT_FW_JOURNEY_PCR};
 
 pub const FMC_ORG: u32 = 0x40000000;
@@ -0,0 +1,118 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    log.rs
+
+Abstract:
+
+    File contains the leveled logging macros used by the ROM, FMC and
+    Runtime Firmware.
+
+--*/
+
+#[cfg(all(feature = "runtime", not(feature = "std")))]
+use caliptra_drivers::PersistentDataAccessor;
+
+/// Severity of a log message
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Nothing is printed
+    Off = 0,
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Trace = 4,
+}
+
+impl TryFrom<u32> for LogLevel {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, ()> {
+        match value {
+            0 => Ok(LogLevel::Off),
+            1 => Ok(LogLevel::Error),
+            2 => Ok(LogLevel::Warn),
+            3 => Ok(LogLevel::Info),
+            4 => Ok(LogLevel::Trace),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Most verbose level built into the firmware, selected with the `log-*`
+/// features. Messages above it are compiled out.
+pub const MAX_LOG_LEVEL: LogLevel = if cfg!(feature = "log-off") {
+    LogLevel::Off
+} else if cfg!(feature = "log-error") {
+    LogLevel::Error
+} else if cfg!(feature = "log-warn") {
+    LogLevel::Warn
+} else if cfg!(feature = "log-info") {
+    LogLevel::Info
+} else {
+    LogLevel::Trace
+};
+
+/// Level messages are currently printed at
+///
+/// The Runtime Firmware uses the level set by SET_LOG_LEVEL, capped to
+/// `MAX_LOG_LEVEL`. Everything else uses `MAX_LOG_LEVEL`.
+pub fn log_level() -> LogLevel {
+    #[cfg(all(feature = "runtime", not(feature = "std")))]
+    {
+        let persistent_data = unsafe { PersistentDataAccessor::new() };
+        match LogLevel::try_from(persistent_data.get().log_level) {
+            Ok(LogLevel::Off) | Err(_) => {}
+            Ok(level) => return level.min(MAX_LOG_LEVEL),
+        }
+    }
+    MAX_LOG_LEVEL
+}
+
+/// Returns true if messages of `level` are printed
+#[inline(always)]
+pub fn log_enabled(level: LogLevel) -> bool {
+    // The first check is evaluated at compile time, which removes the
+    // message from images built without its level.
+    level <= MAX_LOG_LEVEL && level <= log_level()
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($($tt:tt)*) => {{
+        if $crate::log::log_enabled($crate::log::LogLevel::Error) {
+            $crate::cprintln!($($tt)*);
+        }
+    }}
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($tt:tt)*) => {{
+        if $crate::log::log_enabled($crate::log::LogLevel::Warn) {
+            $crate::cprintln!($($tt)*);
+        }
+    }}
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($tt:tt)*) => {{
+        if $crate::log::log_enabled($crate::log::LogLevel::Info) {
+            $crate::cprintln!($($tt)*);
+        }
+    }}
+}
+
+#[macro_export]
+macro_rules! log_trace {
+    ($($tt:tt)*) => {{
+        if $crate::log::log_enabled($crate::log::LogLevel::Trace) {
+            $crate::cprintln!($($tt)*);
+        }
+    }}
+}
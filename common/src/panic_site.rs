@@ -0,0 +1,101 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    panic_site.rs
+
+Abstract:
+
+    File contains routines for capturing the source location of a panic
+    without pulling in the core::fmt panic message machinery.
+
+--*/
+
+use caliptra_registers::soc_ifc::SocIfcReg;
+use core::panic::PanicInfo;
+
+/// Compact identifier of the source location of a panic.
+///
+/// The file name is reduced to a 32-bit FNV-1a hash so that it can be
+/// reported through the extended error info registers. The hash can be
+/// computed for every source file at build time with [`file_hash`] to map
+/// a reported panic site back to its location.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PanicSite {
+    pub file_hash: u32,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl PanicSite {
+    /// Extended error info word holding the file name hash
+    pub const EXT_INFO_FILE_HASH_IDX: usize = 0;
+
+    /// Extended error info word holding the line number
+    pub const EXT_INFO_LINE_IDX: usize = 1;
+
+    /// Extended error info word holding the column number
+    pub const EXT_INFO_COLUMN_IDX: usize = 2;
+
+    /// Capture the panic site from the panic info.
+    ///
+    /// Only the location is used; the panic message is never formatted.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - Panic info
+    ///
+    /// # Returns
+    ///
+    /// * `PanicSite` - Panic site, or all zeros if the location is unknown
+    pub fn from_panic_info(info: &PanicInfo) -> Self {
+        match info.location() {
+            Some(location) => Self {
+                file_hash: file_hash(location.file()),
+                line: location.line(),
+                column: location.column(),
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Record the panic site in the CPTRA_FW_EXTENDED_ERROR_INFO registers
+    pub fn log(&self) {
+        let mut soc_ifc = unsafe { SocIfcReg::new() };
+        let soc_ifc = soc_ifc.regs_mut();
+        let ext_info = soc_ifc.cptra_fw_extended_error_info();
+        ext_info
+            .at(Self::EXT_INFO_FILE_HASH_IDX)
+            .write(|_| self.file_hash);
+        ext_info.at(Self::EXT_INFO_LINE_IDX).write(|_| self.line);
+        ext_info
+            .at(Self::EXT_INFO_COLUMN_IDX)
+            .write(|_| self.column);
+    }
+}
+
+/// Compute the FNV-1a hash of a source file name
+///
+/// # Arguments
+///
+/// * `file` - File name, as returned by `file!()` or `Location::file()`
+///
+/// # Returns
+///
+/// * `u32` - File name hash
+pub const fn file_hash(file: &str) -> u32 {
+    const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const FNV_PRIME: u32 = 0x0100_0193;
+
+    let bytes = file.as_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
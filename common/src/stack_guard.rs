@@ -0,0 +1,56 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    stack_guard.rs
+
+Abstract:
+
+    File contains routines for measuring the stack high-water mark and
+    checking the stack guard region painted by the startup code.
+
+--*/
+
+#[cfg(target_arch = "riscv32")]
+use caliptra_drivers::memory_layout::{STACK_ORG, STACK_SIZE};
+
+/// Pattern written over the whole stack by the startup code (start.S)
+pub const STACK_PAINT: u32 = 0x5354_4B50; // "STKP"
+
+/// Size in bytes of the region at the bottom of the stack that must never
+/// be written. A write to it means the stack overflowed or came within
+/// this many bytes of doing so.
+pub const STACK_GUARD_SIZE: u32 = 256;
+
+/// Peak stack usage in bytes since the stack was painted
+#[cfg(target_arch = "riscv32")]
+pub fn stack_high_water() -> u32 {
+    let mut addr = STACK_ORG;
+    while addr < STACK_ORG + STACK_SIZE
+        && unsafe { core::ptr::read_volatile(addr as *const u32) } == STACK_PAINT
+    {
+        addr += 4;
+    }
+    STACK_ORG + STACK_SIZE - addr
+}
+
+#[cfg(not(target_arch = "riscv32"))]
+pub fn stack_high_water() -> u32 {
+    0
+}
+
+/// Returns true if the guard region at the bottom of the stack still holds
+/// the paint pattern
+#[cfg(target_arch = "riscv32")]
+pub fn stack_guard_intact() -> bool {
+    (STACK_ORG..STACK_ORG + STACK_GUARD_SIZE)
+        .step_by(4)
+        .all(|addr| unsafe { core::ptr::read_volatile(addr as *const u32) } == STACK_PAINT)
+}
+
+#[cfg(not(target_arch = "riscv32"))]
+pub fn stack_guard_intact() -> bool {
+    true
+}
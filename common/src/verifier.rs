@@ -19,6 +19,15 @@ use core::ops::Range;
 
 use caliptra_drivers::memory_layout::ICCM_RANGE;
 
+/// Bytes copied to the load address per accelerator stream update
+const LOAD_CHUNK_SIZE: usize = 256;
+
+/// Polls of the SHA accelerator lock before giving up on the SoC releasing it
+const SHA_ACC_LOCK_TIMEOUT_POLLS: u32 = 100_000;
+
+/// Winternitz chains computed between watchdog restarts during LMS verification
+const LMS_CHAINS_PER_STEP: usize = 8;
+
 /// ROM Verification Environemnt
 pub struct FirmwareImageVerificationEnv<'a, 'b> {
     pub sha256: &'a mut Sha256,
@@ -28,6 +37,14 @@ pub struct FirmwareImageVerificationEnv<'a, 'b> {
     pub data_vault: &'a mut DataVault,
     pub pcr_bank: &'a mut PcrBank,
     pub image: &'b [u8],
+
+    /// SHA accelerator used to hash the FMC and runtime while loading them.
+    /// When `None` they are hashed in place and loaded after verification.
+    pub load_sha_acc: Option<&'a mut Sha2_512_384Acc>,
+
+    /// Called between batches of LMS verification to restart the watchdog.
+    /// The runtime obtains it from its privileged interface.
+    pub wdt_pet: Option<fn()>,
 }
 
 impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b> {
@@ -43,6 +60,51 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         Ok(self.sha384.digest(data)?.0)
     }
 
+    /// Calculate Digest using the SHA accelerator stream while copying the
+    /// image from the mailbox SRAM to its load address
+    fn sha384_digest_and_load<'c>(
+        &mut self,
+        offset: u32,
+        len: u32,
+        segments: impl Iterator<Item = &'c ImageTocEntry>,
+    ) -> CaliptraResult<ImageDigest> {
+        let Some(sha_acc) = self.load_sha_acc.as_deref_mut() else {
+            return self.sha384_digest(offset, len);
+        };
+
+        let mut op = sha_acc.acquire_with_timeout(SHA_ACC_LOCK_TIMEOUT_POLLS)?;
+        let mut stream = op.stream_384(len)?;
+
+        for segment in segments {
+            let range = segment.image_range()?;
+            let src = self
+                .image
+                .get(range.start as usize..range.end as usize)
+                .ok_or(CaliptraError::IMAGE_VERIFIER_ERR_DIGEST_OUT_OF_BOUNDS)?;
+            let dest = unsafe {
+                let addr = segment.load_addr as *mut u8;
+                core::slice::from_raw_parts_mut(addr, segment.load_size() as usize)
+            };
+
+            if segment.is_compressed() {
+                stream.update(src)?;
+                lzss::decompress(src, dest)?;
+            } else {
+                for (src, dest) in src
+                    .chunks(LOAD_CHUNK_SIZE)
+                    .zip(dest.chunks_mut(LOAD_CHUNK_SIZE))
+                {
+                    dest.copy_from_slice(src);
+                    stream.update(src)?;
+                }
+            }
+        }
+
+        let mut digest = Array4x12::default();
+        stream.finalize(&mut digest)?;
+        Ok(digest.0)
+    }
+
     /// ECC-384 Verification routine
     fn ecc384_verify(
         &mut self,
@@ -75,7 +137,18 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         for i in 0..digest.len() {
             message[i * 4..][..4].copy_from_slice(&digest[i].to_be_bytes());
         }
-        Lms::default().verify_lms_signature_cfi(self.sha256, &message, pub_key, sig)
+        let lms = Lms::default();
+        let mut op = lms.verify_lms_signature_cfi_start(self.sha256, &message, pub_key, sig)?;
+        loop {
+            if let Some(root) = op.step_cfi(self.sha256, LMS_CHAINS_PER_STEP)? {
+                return Ok(root);
+            }
+            // Verification runs with interrupts disabled; keep the watchdog
+            // serviced between batches of chains.
+            if let Some(wdt_pet) = self.wdt_pet {
+                wdt_pet();
+            }
+        }
     }
 
     /// Retrieve Vendor Public Key Digest
@@ -123,6 +196,16 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         self.data_vault.owner_pk_hash().into()
     }
 
+    /// Get the owner public key digest committed by a key rotation in the dv
+    fn committed_owner_pub_key_digest_dv(&self) -> ImageDigest {
+        self.data_vault.committed_owner_pk_hash().into()
+    }
+
+    /// Get the next owner public key digest saved in the dv by the running image
+    fn next_owner_pub_key_digest_dv(&self) -> ImageDigest {
+        self.data_vault.next_owner_pk_hash().into()
+    }
+
     // Get the fmc digest from the data vault on cold boot
     fn get_fmc_digest_dv(&self) -> ImageDigest {
         self.data_vault.fmc_tci().into()
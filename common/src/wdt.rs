@@ -26,6 +26,8 @@ impl Default for WdtTimeout {
 impl WdtTimeout {
     pub const ROM_WDT1_TIMEOUT_IN_CYCLES: WdtTimeout =
         WdtTimeout::new_const(10 * EXPECTED_CALIPTRA_BOOT_TIME_IN_CYCLES);
+    pub const FMC_WDT1_TIMEOUT_IN_CYCLES: WdtTimeout =
+        WdtTimeout::new_const(5 * EXPECTED_FMC_PHASE_TIME_IN_CYCLES);
     pub const fn new_const(timeout_cycles: u64) -> Self {
         match core::num::NonZeroU64::new(timeout_cycles) {
             Some(val) => Self(val),
@@ -51,6 +53,7 @@ impl From<WdtTimeout> for u64 {
 }
 
 const EXPECTED_CALIPTRA_BOOT_TIME_IN_CYCLES: u64 = 20_000_000; // 20 million cycles
+const EXPECTED_FMC_PHASE_TIME_IN_CYCLES: u64 = 2_000_000; // 2 million cycles
 const WDT2_TIMEOUT_CYCLES: u64 = 1; // Fire immediately after WDT1 expiry
 
 const WDT1_MIN_TIMEOUT_IN_CYCLES: u64 = EXPECTED_CALIPTRA_BOOT_TIME_IN_CYCLES;
@@ -16,7 +16,7 @@ use caliptra_coverage::invoke_objdump;
 use caliptra_coverage::uncovered_functions;
 use caliptra_drivers::memory_layout::ICCM_ORG;
 use caliptra_drivers::memory_layout::ROM_ORG;
-use caliptra_image_types::IMAGE_MANIFEST_BYTE_SIZE;
+use caliptra_image_types::ImageManifestFormat;
 
 pub fn highlight_covered_instructions_in_objdump_output(
     base_address: usize,
@@ -109,7 +109,7 @@ fn main() -> std::io::Result<()> {
         .unwrap();
 
         let image = image.to_bytes().unwrap();
-        let iccm_image = &image.as_slice()[IMAGE_MANIFEST_BYTE_SIZE..];
+        let iccm_image = &image.as_slice()[ImageManifestFormat::V1.size()..];
 
         caliptra_coverage::get_tag_from_image(iccm_image)
     };
@@ -20,3 +20,29 @@ pub fn mpmc_halt_and_enable_interrupts() {
         core::arch::asm!("csrrs zero, 0x7c6, {r}", r = in(reg) HALT | HALTIE);
     }
 }
+
+// VeeR EL2 PRM 6.12.2 External Interrupt Vector Table Register
+// External interrupts taken while meivt points outside of DCCM raise an NMI
+#[cfg(feature = "riscv")]
+pub fn meivt_write(addr: u32) {
+    unsafe {
+        core::arch::asm!("csrw 0xbc8, {r}", r = in(reg) addr);
+    }
+}
+
+// Standard RISC-V MCYCLE/MCYCLEH CSRs
+// The high word is read twice to detect a carry out of the low word
+#[cfg(feature = "riscv")]
+pub fn mcycle() -> u64 {
+    loop {
+        let (hi, lo, hi2): (u32, u32, u32);
+        unsafe {
+            core::arch::asm!("csrr {r}, mcycleh", r = out(reg) hi);
+            core::arch::asm!("csrr {r}, mcycle", r = out(reg) lo);
+            core::arch::asm!("csrr {r}, mcycleh", r = out(reg) hi2);
+        }
+        if hi == hi2 {
+            return ((hi as u64) << 32) | lo as u64;
+        }
+    }
+}
@@ -38,6 +38,10 @@ impl Sha256Alg for Sha256SoftwareDriver {
             digest: sha2::Sha256::new(),
         })
     }
+
+    fn wntz_supported(&self) -> bool {
+        false
+    }
 }
 
 impl Sha256SoftwareDriver {
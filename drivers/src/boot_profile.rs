@@ -0,0 +1,68 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    boot_profile.rs
+
+Abstract:
+
+    Cycle counts at the boot milestones of the ROM, FMC and Runtime
+    Firmware, kept in persistent data for GET_BOOT_PROFILE.
+
+--*/
+
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+/// Boot milestone recorded in the `BootProfile`
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BootCheckpoint {
+    /// ROM started; begins a new profile
+    RomEntry = 0,
+    /// ROM finished its known answer tests
+    RomKatsComplete = 1,
+    /// ROM received the firmware image
+    RomFwReceived = 2,
+    /// ROM verified the firmware image
+    RomFwVerified = 3,
+    /// ROM jumped to the FMC
+    RomExit = 4,
+    /// FMC started
+    FmcEntry = 5,
+    /// FMC jumped to the Runtime Firmware
+    FmcExit = 6,
+    /// Runtime Firmware started
+    RtEntry = 7,
+    /// Runtime Firmware is ready for mailbox commands
+    RtReady = 8,
+}
+
+pub const BOOT_PROFILE_CHECKPOINT_COUNT: usize = 9;
+
+/// Value of the `mcycle` CSR, truncated to 32 bits, when the current boot
+/// reached each `BootCheckpoint`, or 0 if it has not reached it
+///
+/// `mcycle` restarts from 0 on every reset of the core, so the values of
+/// one profile are relative to the start of that boot.
+#[repr(C)]
+#[derive(Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct BootProfile {
+    pub cycles: [u32; BOOT_PROFILE_CHECKPOINT_COUNT],
+}
+
+impl BootProfile {
+    /// Cycle count of `checkpoint`
+    pub fn get(&self, checkpoint: BootCheckpoint) -> u32 {
+        self.cycles[checkpoint as usize]
+    }
+
+    /// Record that the boot reached `checkpoint` at `cycles`
+    pub fn record(&mut self, checkpoint: BootCheckpoint, cycles: u32) {
+        if checkpoint == BootCheckpoint::RomEntry {
+            *self = Self::default();
+        }
+        self.cycles[checkpoint as usize] = cycles;
+    }
+}
@@ -23,6 +23,27 @@ impl MemBounds for RomBounds {
 
 pub type RomAddr<T> = BoundedAddr<T, RomBounds>;
 
+pub struct IccmBounds {}
+impl MemBounds for IccmBounds {
+    const ORG: usize = memory_layout::ICCM_ORG as usize;
+    const SIZE: usize = memory_layout::ICCM_SIZE as usize;
+    const ERROR: CaliptraError = CaliptraError::ADDRESS_NOT_IN_ICCM;
+}
+
+pub type IccmAddr<T> = BoundedAddr<T, IccmBounds>;
+pub type IccmRange = BoundedRange<IccmBounds>;
+pub type IccmSlice<T> = BoundedSlice<T, IccmBounds>;
+
+pub struct DccmBounds {}
+impl MemBounds for DccmBounds {
+    const ORG: usize = memory_layout::DCCM_ORG as usize;
+    const SIZE: usize = memory_layout::DCCM_SIZE as usize;
+    const ERROR: CaliptraError = CaliptraError::ADDRESS_NOT_IN_DCCM;
+}
+
+pub type DccmRange = BoundedRange<DccmBounds>;
+pub type DccmSlice<T> = BoundedSlice<T, DccmBounds>;
+
 #[repr(C)]
 #[derive(TryFromBytes, IntoBytes, Immutable)]
 pub struct BoundedAddr<T: IntoBytes + FromBytes, B: MemBounds> {
@@ -91,10 +112,120 @@ impl<T: IntoBytes + FromBytes, B: MemBounds> From<&'static T> for BoundedAddr<T,
     }
 }
 
+/// A range of `len` bytes starting at `start`, validated to lie entirely
+/// within the memory region `B`.
+pub struct BoundedRange<B: MemBounds> {
+    start: u32,
+    len: u32,
+    _phantom: PhantomData<B>,
+}
+
+impl<B: MemBounds> BoundedRange<B> {
+    pub fn new(start: u32, len: u32) -> Result<Self, CaliptraError> {
+        Self::validate_range(start, len)?;
+        Ok(Self {
+            start,
+            len,
+            _phantom: Default::default(),
+        })
+    }
+    pub fn validate_range(start: u32, len: u32) -> Result<(), CaliptraError> {
+        let start = start as usize;
+        let len = len as usize;
+
+        if start < B::ORG || len > B::SIZE || start - B::ORG > B::SIZE - len {
+            return Err(B::ERROR);
+        }
+        Ok(())
+    }
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+impl<B: MemBounds> Clone for BoundedRange<B> {
+    fn clone(&self) -> Self {
+        Self {
+            start: self.start,
+            len: self.len,
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<B: MemBounds> Debug for BoundedRange<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BoundedRange")
+            .field("start", &self.start)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// `len` elements of `T` starting at `addr`, validated to be aligned and to
+/// lie entirely within the memory region `B`.
+pub struct BoundedSlice<T: IntoBytes + FromBytes, B: MemBounds> {
+    range: BoundedRange<B>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: IntoBytes + FromBytes, B: MemBounds> BoundedSlice<T, B> {
+    pub fn new(addr: u32, len: usize) -> Result<Self, CaliptraError> {
+        if addr as usize % core::mem::align_of::<T>() != 0 {
+            return Err(CaliptraError::ADDRESS_MISALIGNED);
+        }
+        let byte_len = len
+            .checked_mul(core::mem::size_of::<T>())
+            .and_then(|byte_len| u32::try_from(byte_len).ok())
+            .ok_or(B::ERROR)?;
+        Ok(Self {
+            range: BoundedRange::new(addr, byte_len)?,
+            _phantom: Default::default(),
+        })
+    }
+    pub fn range(&self) -> &BoundedRange<B> {
+        &self.range
+    }
+    pub fn len(&self) -> usize {
+        self.range.len as usize / core::mem::size_of::<T>()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// # Safety
+    ///
+    /// The memory must not be written through any other reference for the
+    /// lifetime of the returned slice.
+    pub unsafe fn as_slice<'a>(&self) -> &'a [T] {
+        core::slice::from_raw_parts(self.range.start as *const T, self.len())
+    }
+
+    /// # Safety
+    ///
+    /// The memory must not be accessed through any other reference for the
+    /// lifetime of the returned slice.
+    pub unsafe fn as_mut_slice<'a>(&self) -> &'a mut [T] {
+        core::slice::from_raw_parts_mut(self.range.start as *mut T, self.len())
+    }
+}
+impl<T: IntoBytes + FromBytes, B: MemBounds> Debug for BoundedSlice<T, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BoundedSlice")
+            .field("start", &self.range.start)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory_layout::{ROM_ORG, ROM_SIZE};
+    use crate::memory_layout::{DCCM_ORG, DCCM_SIZE, ICCM_ORG, ICCM_SIZE, ROM_ORG, ROM_SIZE};
 
     #[derive(IntoBytes, FromBytes)]
     #[repr(C)]
@@ -135,4 +266,55 @@ mod tests {
             Err(CaliptraError::ADDRESS_NOT_IN_ROM)
         );
     }
+
+    #[test]
+    fn test_iccm_range_validate() {
+        IccmRange::validate_range(ICCM_ORG, ICCM_SIZE).unwrap();
+        IccmRange::validate_range(ICCM_ORG + ICCM_SIZE - 4, 4).unwrap();
+        IccmRange::validate_range(ICCM_ORG + ICCM_SIZE, 0).unwrap();
+
+        assert_eq!(
+            IccmRange::validate_range(ICCM_ORG - 4, 8),
+            Err(CaliptraError::ADDRESS_NOT_IN_ICCM)
+        );
+        assert_eq!(
+            IccmRange::validate_range(ICCM_ORG + 4, ICCM_SIZE),
+            Err(CaliptraError::ADDRESS_NOT_IN_ICCM)
+        );
+        assert_eq!(
+            IccmRange::validate_range(ICCM_ORG + ICCM_SIZE, 1),
+            Err(CaliptraError::ADDRESS_NOT_IN_ICCM)
+        );
+        assert_eq!(
+            IccmRange::validate_range(u32::MAX, u32::MAX),
+            Err(CaliptraError::ADDRESS_NOT_IN_ICCM)
+        );
+        assert_eq!(
+            DccmRange::validate_range(ICCM_ORG, 4),
+            Err(CaliptraError::ADDRESS_NOT_IN_DCCM)
+        );
+    }
+
+    #[test]
+    fn test_dccm_slice_new() {
+        let slice = DccmSlice::<MyStruct>::new(DCCM_ORG + 8, 4).unwrap();
+        assert_eq!(slice.len(), 4);
+        assert_eq!(slice.range().start(), DCCM_ORG + 8);
+        assert_eq!(slice.range().len(), 32);
+
+        DccmSlice::<u8>::new(DCCM_ORG + DCCM_SIZE - 1, 1).unwrap();
+
+        assert_eq!(
+            DccmSlice::<MyStruct>::new(DCCM_ORG + 2, 1).unwrap_err(),
+            CaliptraError::ADDRESS_MISALIGNED
+        );
+        assert_eq!(
+            DccmSlice::<MyStruct>::new(DCCM_ORG + DCCM_SIZE - 4, 1).unwrap_err(),
+            CaliptraError::ADDRESS_NOT_IN_DCCM
+        );
+        assert_eq!(
+            DccmSlice::<MyStruct>::new(DCCM_ORG, usize::MAX).unwrap_err(),
+            CaliptraError::ADDRESS_NOT_IN_DCCM
+        );
+    }
 }
@@ -0,0 +1,131 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cert_dist_points.rs
+
+Abstract:
+
+    Owner-provisioned certificate distribution point URIs.
+
+--*/
+
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+pub const CERT_DIST_POINT_URI_MAX_SIZE: usize = 96;
+
+/// CRL distribution point and CA issuers URIs to add to the RT Alias
+/// certificate.
+///
+/// The URIs are set by the runtime and read by the FMC when it generates
+/// the RT Alias certificate, so they take effect on the next update reset.
+#[repr(C)]
+#[derive(IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct CertDistPoints {
+    /// Size of the CRL distribution point URI, 0 if unset
+    crl_uri_size: u32,
+
+    /// CRL distribution point URI
+    crl_uri: [u8; CERT_DIST_POINT_URI_MAX_SIZE],
+
+    /// Size of the authority information access CA issuers URI, 0 if unset
+    aia_uri_size: u32,
+
+    /// Authority information access CA issuers URI
+    aia_uri: [u8; CERT_DIST_POINT_URI_MAX_SIZE],
+}
+
+impl Default for CertDistPoints {
+    fn default() -> Self {
+        Self {
+            crl_uri_size: 0,
+            crl_uri: [0; CERT_DIST_POINT_URI_MAX_SIZE],
+            aia_uri_size: 0,
+            aia_uri: [0; CERT_DIST_POINT_URI_MAX_SIZE],
+        }
+    }
+}
+
+impl CertDistPoints {
+    /// Set both URIs. An empty URI leaves the corresponding extension out
+    /// of the certificate.
+    ///
+    /// # Arguments
+    ///
+    /// * `crl_uri` - CRL distribution point URI
+    /// * `aia_uri` - Authority information access CA issuers URI
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `false` if either URI is too long or is not printable
+    ///   ASCII without spaces. The URIs are left unchanged in that case.
+    pub fn set(&mut self, crl_uri: &[u8], aia_uri: &[u8]) -> bool {
+        if !Self::is_valid_uri(crl_uri) || !Self::is_valid_uri(aia_uri) {
+            return false;
+        }
+
+        self.zeroize();
+        self.crl_uri[..crl_uri.len()].copy_from_slice(crl_uri);
+        self.crl_uri_size = crl_uri.len() as u32;
+        self.aia_uri[..aia_uri.len()].copy_from_slice(aia_uri);
+        self.aia_uri_size = aia_uri.len() as u32;
+        true
+    }
+
+    /// Get the CRL distribution point URI, or `None` if unset or corrupted
+    pub fn crl_uri(&self) -> Option<&[u8]> {
+        Self::uri(&self.crl_uri, self.crl_uri_size)
+    }
+
+    /// Get the CA issuers URI, or `None` if unset or corrupted
+    pub fn aia_uri(&self) -> Option<&[u8]> {
+        Self::uri(&self.aia_uri, self.aia_uri_size)
+    }
+
+    fn uri(uri: &[u8], size: u32) -> Option<&[u8]> {
+        uri.get(..size as usize).filter(|uri| !uri.is_empty())
+    }
+
+    fn is_valid_uri(uri: &[u8]) -> bool {
+        uri.len() <= CERT_DIST_POINT_URI_MAX_SIZE && uri.iter().all(|c| matches!(c, 0x21..=0x7e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset() {
+        let points = CertDistPoints::default();
+        assert_eq!(points.crl_uri(), None);
+        assert_eq!(points.aia_uri(), None);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut points = CertDistPoints::default();
+        assert!(points.set(b"http://example.com/ca.crl", b""));
+        assert_eq!(points.crl_uri(), Some(&b"http://example.com/ca.crl"[..]));
+        assert_eq!(points.aia_uri(), None);
+
+        assert!(points.set(b"", b"http://example.com/ca.der"));
+        assert_eq!(points.crl_uri(), None);
+        assert_eq!(points.aia_uri(), Some(&b"http://example.com/ca.der"[..]));
+    }
+
+    #[test]
+    fn test_set_invalid() {
+        let mut points = CertDistPoints::default();
+        assert!(points.set(b"http://example.com/ca.crl", b""));
+
+        assert!(!points.set(&[b'a'; CERT_DIST_POINT_URI_MAX_SIZE + 1], b""));
+        assert!(!points.set(b"", b"http://example.com/a b"));
+        assert!(!points.set(b"http://example.com/\n", b""));
+        assert_eq!(points.crl_uri(), Some(&b"http://example.com/ca.crl"[..]));
+
+        assert!(points.set(&[b'a'; CERT_DIST_POINT_URI_MAX_SIZE], b""));
+    }
+}
@@ -0,0 +1,91 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    crash_record.rs
+
+Abstract:
+
+    Record of the last fatal error, kept in persistent data so that it can
+    be retrieved after the next boot.
+
+--*/
+
+use crate::hand_off::{FHT_INVALID_HANDLE, FHT_MARKER};
+use crate::FirmwareHandoffTable;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+/// Value of `CrashRecord::marker` when the record holds a crash
+pub const CRASH_RECORD_MARKER: u32 = 0x4853_5243; // "CRSH"
+
+/// State of the firmware when it hit a fatal error
+///
+/// Written by the fatal error handler and left in place across warm and
+/// update resets until retrieved by GET_CRASH_DUMP.
+#[repr(C)]
+#[derive(Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct CrashRecord {
+    /// `CRASH_RECORD_MARKER` if the record holds a crash
+    pub marker: u32,
+
+    /// Fatal error code
+    pub error_code: u32,
+
+    /// Last command written to the mailbox
+    pub last_cmd: u32,
+
+    /// Validity of the firmware handoff table, see the `FHT_*` constants
+    pub fht_validity: u32,
+
+    /// Peak stack usage in bytes
+    pub stack_high_water: u32,
+
+    /// Non-zero if the error was raised by an exception or NMI, in which
+    /// case the fields below hold the trap record
+    pub has_trap: u32,
+
+    pub mcause: u32,
+    pub mscause: u32,
+    pub mepc: u32,
+    pub mtval: u32,
+    pub mstatus: u32,
+    pub ra: u32,
+    pub sp: u32,
+}
+
+impl CrashRecord {
+    /// The FHT marker is present
+    pub const FHT_MARKER_VALID: u32 = 1 << 0;
+    /// The FHT passed its validity check
+    pub const FHT_VALID: u32 = 1 << 1;
+    /// The FHT points to the RT CDI
+    pub const FHT_RT_CDI_VALID: u32 = 1 << 2;
+    /// The FHT points to the RT alias private key
+    pub const FHT_RT_PRIV_KEY_VALID: u32 = 1 << 3;
+
+    /// Returns true if the record holds a crash
+    pub fn is_valid(&self) -> bool {
+        self.marker == CRASH_RECORD_MARKER
+    }
+
+    /// Summarize the validity of `fht` as a combination of the `FHT_*`
+    /// constants
+    pub fn fht_validity(fht: &FirmwareHandoffTable) -> u32 {
+        let mut validity = 0;
+        if fht.fht_marker == FHT_MARKER {
+            validity |= Self::FHT_MARKER_VALID;
+        }
+        if fht.is_valid() {
+            validity |= Self::FHT_VALID;
+        }
+        if fht.rt_cdi_kv_hdl != FHT_INVALID_HANDLE {
+            validity |= Self::FHT_RT_CDI_VALID;
+        }
+        if fht.rt_priv_key_kv_hdl != FHT_INVALID_HANDLE {
+            validity |= Self::FHT_RT_PRIV_KEY_VALID;
+        }
+        validity
+    }
+}
@@ -181,6 +181,89 @@ impl Csrng {
         }
     }
 
+    /// Returns the health test statistics gathered by entropy_src since it
+    /// was last enabled.
+    pub fn health_test_stats(&self) -> HealthTestStats {
+        let e = self.entropy_src.regs();
+
+        HealthTestStats {
+            repcnt_threshold: e.repcnt_thresholds().read().fips_thresh(),
+            adaptp_hi_threshold: e.adaptp_hi_thresholds().read().fips_thresh(),
+            adaptp_lo_threshold: e.adaptp_lo_thresholds().read().fips_thresh(),
+            repcnt_hi_watermark: e.repcnt_hi_watermarks().read().fips_watermark(),
+            adaptp_hi_watermark: e.adaptp_hi_watermarks().read().fips_watermark(),
+            adaptp_lo_watermark: e.adaptp_lo_watermarks().read().fips_watermark(),
+            repcnt_total_fails: e.repcnt_total_fails().read(),
+            adaptp_hi_total_fails: e.adaptp_hi_total_fails().read(),
+            adaptp_lo_total_fails: e.adaptp_lo_total_fails().read(),
+        }
+    }
+
+    /// Reseed the CSRNG with fresh output from entropy_src.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reseed command fails or entropy_src fails its
+    /// health checks.
+    pub fn reseed_from_entropy_src(&mut self) -> CaliptraResult<()> {
+        check_for_alert_state(self.entropy_src.regs())?;
+        self.reseed(Seed::EntropySrc)?;
+        check_for_alert_state(self.entropy_src.regs())
+    }
+
+    /// Put entropy_src into firmware override mode so that raw noise source
+    /// samples are copied into the observe FIFO before conditioning.
+    ///
+    /// Samples continue to flow to the conditioner, so the CSRNG remains
+    /// usable while a capture is in progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if entropy_src fails its health checks once re-enabled.
+    pub fn start_raw_entropy_capture(&mut self) -> CaliptraResult<()> {
+        const FALSE: u32 = MultiBitBool::False as u32;
+        const TRUE: u32 = MultiBitBool::True as u32;
+
+        let e = self.entropy_src.regs_mut();
+        if e.fw_ov_control().read().fw_ov_mode() == TRUE {
+            return Ok(());
+        }
+
+        // The override controls may only be changed while the module is disabled.
+        e.module_enable().write(|w| w.module_enable(FALSE));
+        e.fw_ov_control()
+            .write(|w| w.fw_ov_mode(TRUE).fw_ov_entropy_insert(FALSE));
+        e.module_enable().write(|w| w.module_enable(TRUE));
+        check_for_alert_state(self.entropy_src.regs())
+    }
+
+    /// Fill `dest` with raw noise source samples from the observe FIFO.
+    ///
+    /// Returns true if the observe FIFO overflowed since the previous read,
+    /// meaning samples were dropped between the two reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`start_raw_entropy_capture`] has not been called.
+    ///
+    /// [`start_raw_entropy_capture`]: Csrng::start_raw_entropy_capture
+    pub fn read_raw_entropy(&mut self, dest: &mut [u32]) -> CaliptraResult<bool> {
+        const TRUE: u32 = MultiBitBool::True as u32;
+
+        let e = self.entropy_src.regs();
+        if e.fw_ov_control().read().fw_ov_mode() != TRUE {
+            return Err(CaliptraError::DRIVER_CSRNG_RAW_ENTROPY_NOT_CAPTURING);
+        }
+
+        let overflow = e.fw_ov_rd_fifo_overflow().read().fw_ov_rd_fifo_overflow();
+        for word in dest.iter_mut() {
+            wait::until(|| e.observe_fifo_depth().read().observe_fifo_depth() > 0);
+            *word = e.fw_ov_rd_data().read();
+        }
+
+        Ok(overflow)
+    }
+
     pub fn uninstantiate(mut self) {
         let _ = send_command(&mut self.csrng, Command::Uninstantiate);
     }
@@ -259,6 +342,40 @@ pub struct HealthFailCounts {
     pub specific: AlertFailCountsReadVal,
 }
 
+/// Health test statistics gathered by entropy_src in FIPS mode.
+///
+/// This struct is returned by the [`health_test_stats`] function on [`Csrng`].
+///
+/// [`health_test_stats`]: Csrng::health_test_stats
+pub struct HealthTestStats {
+    /// Repetition Count test threshold.
+    pub repcnt_threshold: u32,
+
+    /// Adaptive Proportion test high threshold.
+    pub adaptp_hi_threshold: u32,
+
+    /// Adaptive Proportion test low threshold.
+    pub adaptp_lo_threshold: u32,
+
+    /// Longest run of a repeated bit seen by the Repetition Count test.
+    pub repcnt_hi_watermark: u32,
+
+    /// Highest count of ones in an Adaptive Proportion test window.
+    pub adaptp_hi_watermark: u32,
+
+    /// Lowest count of ones in an Adaptive Proportion test window.
+    pub adaptp_lo_watermark: u32,
+
+    /// Total number of Repetition Count test failures.
+    pub repcnt_total_fails: u32,
+
+    /// Total number of Adaptive Proportion test high threshold failures.
+    pub adaptp_hi_total_fails: u32,
+
+    /// Total number of Adaptive Proportion test low threshold failures.
+    pub adaptp_lo_total_fails: u32,
+}
+
 fn send_command(csrng: &mut CsrngReg, command: Command) -> CaliptraResult<()> {
     // https://opentitan.org/book/hw/ip/csrng/doc/theory_of_operation.html#general-command-format
     let acmd: u32;
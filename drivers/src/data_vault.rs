@@ -14,172 +14,302 @@ Abstract:
 
 use caliptra_registers::dv::DvReg;
 
+use crate::hand_off::DataStore;
 use crate::{Array4x12, Ecc384PubKey, Ecc384Signature};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ColdResetEntry48 {
-    LDevDiceSigR = 0,
-    LDevDiceSigS = 1,
-    LDevDicePubKeyX = 2,
-    LDevDicePubKeyY = 3,
-    FmcDiceSigR = 4,
-    FmcDiceSigS = 5,
-    FmcPubKeyX = 6,
-    FmcPubKeyY = 7,
-    FmcTci = 8,
-    OwnerPubKeyHash = 9,
+/// A bank of data vault registers. Every entry of a bank has the same width.
+pub trait DvBank {
+    /// Value held by an entry of the bank
+    type Value;
+
+    /// Identifier of an entry of the bank, as encoded in FHT handles
+    type Id: Copy;
+
+    /// Number of entries in the bank
+    const COUNT: usize;
+
+    fn read(dv: &DvReg, index: usize) -> Self::Value;
+    fn write(dv: &mut DvReg, index: usize, value: &Self::Value);
+    fn lock(dv: &mut DvReg, index: usize);
+    fn is_locked(dv: &DvReg, index: usize) -> bool;
+    fn data_store(id: Self::Id) -> DataStore;
 }
 
-impl TryFrom<u8> for ColdResetEntry48 {
-    type Error = ();
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(ColdResetEntry48::LDevDiceSigR),
-            1 => Ok(ColdResetEntry48::LDevDiceSigS),
-            2 => Ok(ColdResetEntry48::LDevDicePubKeyX),
-            3 => Ok(ColdResetEntry48::LDevDicePubKeyY),
-            4 => Ok(ColdResetEntry48::FmcDiceSigR),
-            5 => Ok(ColdResetEntry48::FmcDiceSigS),
-            6 => Ok(ColdResetEntry48::FmcPubKeyX),
-            7 => Ok(ColdResetEntry48::FmcPubKeyY),
-            8 => Ok(ColdResetEntry48::FmcTci),
-            9 => Ok(ColdResetEntry48::OwnerPubKeyHash),
-            _ => Err(()),
-        }
+/// 48-byte entries cleared on cold reset
+pub struct Sticky48;
+
+/// 4-byte entries cleared on cold reset
+pub struct Sticky4;
+
+/// 48-byte entries cleared on warm reset
+pub struct NonSticky48;
+
+/// 4-byte entries cleared on warm reset
+pub struct NonSticky4;
+
+impl DvBank for Sticky48 {
+    type Value = Array4x12;
+    type Id = ColdResetEntry48;
+    const COUNT: usize = 10;
+
+    fn read(dv: &DvReg, index: usize) -> Array4x12 {
+        Array4x12::read_from_reg(dv.regs().sticky_data_vault_entry().at(index))
     }
-}
 
-impl From<ColdResetEntry48> for u8 {
-    fn from(value: ColdResetEntry48) -> Self {
-        value as Self
+    fn write(dv: &mut DvReg, index: usize, value: &Array4x12) {
+        value.write_to_reg(dv.regs_mut().sticky_data_vault_entry().at(index));
     }
-}
 
-impl From<ColdResetEntry48> for u32 {
-    fn from(value: ColdResetEntry48) -> Self {
-        value as Self
+    fn lock(dv: &mut DvReg, index: usize) {
+        dv.regs_mut()
+            .sticky_data_vault_ctrl()
+            .at(index)
+            .write(|w| w.lock_entry(true));
     }
-}
 
-impl From<ColdResetEntry48> for usize {
-    fn from(value: ColdResetEntry48) -> Self {
-        value as Self
+    fn is_locked(dv: &DvReg, index: usize) -> bool {
+        dv.regs()
+            .sticky_data_vault_ctrl()
+            .at(index)
+            .read()
+            .lock_entry()
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ColdResetEntry4 {
-    FmcSvn = 0,
-    RomColdBootStatus = 1,
-    FmcEntryPoint = 2,
-    EccVendorPubKeyIndex = 3,
-    LmsVendorPubKeyIndex = 4,
+    fn data_store(id: ColdResetEntry48) -> DataStore {
+        DataStore::DataVaultSticky48(id)
+    }
 }
 
-impl TryFrom<u8> for ColdResetEntry4 {
-    type Error = ();
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(Self::FmcSvn),
-            2 => Ok(Self::FmcEntryPoint),
-            3 => Ok(Self::EccVendorPubKeyIndex),
-            4 => Ok(Self::LmsVendorPubKeyIndex),
-            _ => Err(()),
-        }
+impl DvBank for Sticky4 {
+    type Value = u32;
+    type Id = ColdResetEntry4;
+    const COUNT: usize = 8;
+
+    fn read(dv: &DvReg, index: usize) -> u32 {
+        dv.regs().sticky_lockable_scratch_reg().at(index).read()
     }
-}
 
-impl From<ColdResetEntry4> for u8 {
-    fn from(value: ColdResetEntry4) -> Self {
-        value as Self
+    fn write(dv: &mut DvReg, index: usize, value: &u32) {
+        dv.regs_mut()
+            .sticky_lockable_scratch_reg()
+            .at(index)
+            .write(|_| *value);
     }
-}
 
-impl From<ColdResetEntry4> for u32 {
-    fn from(value: ColdResetEntry4) -> Self {
-        value as Self
+    fn lock(dv: &mut DvReg, index: usize) {
+        dv.regs_mut()
+            .sticky_lockable_scratch_reg_ctrl()
+            .at(index)
+            .write(|w| w.lock_entry(true));
     }
-}
 
-impl From<ColdResetEntry4> for usize {
-    fn from(value: ColdResetEntry4) -> Self {
-        value as Self
+    fn is_locked(dv: &DvReg, index: usize) -> bool {
+        dv.regs()
+            .sticky_lockable_scratch_reg_ctrl()
+            .at(index)
+            .read()
+            .lock_entry()
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum WarmResetEntry48 {
-    RtTci = 0,
+    fn data_store(id: ColdResetEntry4) -> DataStore {
+        DataStore::DataVaultSticky4(id)
+    }
 }
 
-impl From<WarmResetEntry48> for u8 {
-    fn from(value: WarmResetEntry48) -> Self {
-        value as Self
+impl DvBank for NonSticky48 {
+    type Value = Array4x12;
+    type Id = WarmResetEntry48;
+    const COUNT: usize = 10;
+
+    fn read(dv: &DvReg, index: usize) -> Array4x12 {
+        Array4x12::read_from_reg(dv.regs().data_vault_entry().at(index))
     }
-}
 
-impl From<WarmResetEntry48> for u32 {
-    fn from(value: WarmResetEntry48) -> Self {
-        value as Self
+    fn write(dv: &mut DvReg, index: usize, value: &Array4x12) {
+        value.write_to_reg(dv.regs_mut().data_vault_entry().at(index));
     }
-}
 
-impl From<WarmResetEntry48> for usize {
-    fn from(value: WarmResetEntry48) -> Self {
-        value as Self
+    fn lock(dv: &mut DvReg, index: usize) {
+        dv.regs_mut()
+            .data_vault_ctrl()
+            .at(index)
+            .write(|w| w.lock_entry(true));
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum WarmResetEntry4 {
-    RtSvn = 0,
-    RtEntryPoint = 1,
-    ManifestAddr = 2,
-    RtMinSvn = 3,
-    RomUpdateResetStatus = 4,
-}
+    fn is_locked(dv: &DvReg, index: usize) -> bool {
+        dv.regs().data_vault_ctrl().at(index).read().lock_entry()
+    }
 
-impl From<WarmResetEntry4> for u8 {
-    fn from(value: WarmResetEntry4) -> Self {
-        value as Self
+    fn data_store(id: WarmResetEntry48) -> DataStore {
+        DataStore::DataVaultNonSticky48(id)
     }
 }
 
-impl From<WarmResetEntry4> for u32 {
-    fn from(value: WarmResetEntry4) -> Self {
-        value as Self
+impl DvBank for NonSticky4 {
+    type Value = u32;
+    type Id = WarmResetEntry4;
+    const COUNT: usize = 10;
+
+    fn read(dv: &DvReg, index: usize) -> u32 {
+        dv.regs().lockable_scratch_reg().at(index).read()
+    }
+
+    fn write(dv: &mut DvReg, index: usize, value: &u32) {
+        dv.regs_mut()
+            .lockable_scratch_reg()
+            .at(index)
+            .write(|_| *value);
+    }
+
+    fn lock(dv: &mut DvReg, index: usize) {
+        dv.regs_mut()
+            .lockable_scratch_reg_ctrl()
+            .at(index)
+            .write(|w| w.lock_entry(true));
+    }
+
+    fn is_locked(dv: &DvReg, index: usize) -> bool {
+        dv.regs()
+            .lockable_scratch_reg_ctrl()
+            .at(index)
+            .read()
+            .lock_entry()
     }
-}
 
-impl From<WarmResetEntry4> for usize {
-    fn from(value: WarmResetEntry4) -> Self {
-        value as Self
+    fn data_store(id: WarmResetEntry4) -> DataStore {
+        DataStore::DataVaultNonSticky4(id)
     }
 }
 
-impl TryFrom<u8> for WarmResetEntry4 {
-    type Error = ();
-    fn try_from(original: u8) -> Result<Self, Self::Error> {
-        match original {
-            0 => Ok(Self::RtSvn),
-            1 => Ok(Self::RtEntryPoint),
-            2 => Ok(Self::ManifestAddr),
-            3 => Ok(Self::RtMinSvn),
-            _ => Err(()),
-        }
+/// A data vault entry. Entry types are generated from the layout below and
+/// bind an entry to its bank, and so to the width of its value.
+pub trait DvEntry {
+    type Bank: DvBank;
+
+    /// Identifier of the entry within its bank
+    const ID: <Self::Bank as DvBank>::Id;
+
+    /// Index of the entry within its bank
+    const INDEX: usize;
+
+    /// Data store naming the entry in FHT handles
+    fn data_store() -> DataStore {
+        <Self::Bank as DvBank>::data_store(Self::ID)
     }
 }
 
-impl TryFrom<u8> for WarmResetEntry48 {
-    type Error = ();
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(Self::RtTci),
-            _ => Err(()),
+/// Value held by the data vault entry `E`
+pub type DvValue<E> = <<E as DvEntry>::Bank as DvBank>::Value;
+
+/// Generates, for each bank, an enum identifying its entries and, in the `dv`
+/// module, one type per entry implementing `DvEntry`. Entry indices are
+/// checked against the size of their bank at compile time.
+macro_rules! data_vault_layout {
+    ($($enum:ident in $bank:ident { $($entry:ident = $index:literal,)* })*) => {
+        $(
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub enum $enum {
+                $($entry = $index,)*
+            }
+
+            impl TryFrom<u8> for $enum {
+                type Error = ();
+                fn try_from(value: u8) -> Result<Self, Self::Error> {
+                    match value {
+                        $($index => Ok(Self::$entry),)*
+                        _ => Err(()),
+                    }
+                }
+            }
+
+            impl From<$enum> for u8 {
+                fn from(value: $enum) -> Self {
+                    value as Self
+                }
+            }
+
+            impl From<$enum> for u32 {
+                fn from(value: $enum) -> Self {
+                    value as Self
+                }
+            }
+
+            impl From<$enum> for usize {
+                fn from(value: $enum) -> Self {
+                    value as Self
+                }
+            }
+        )*
+
+        /// Typed data vault entries
+        pub mod dv {
+            use super::*;
+
+            $($(
+                pub struct $entry;
+
+                impl DvEntry for $entry {
+                    type Bank = $bank;
+                    const ID: $enum = $enum::$entry;
+                    const INDEX: usize = $index;
+                }
+
+                const _: () = assert!($index < <$bank as DvBank>::COUNT);
+            )*)*
         }
+    };
+}
+
+data_vault_layout! {
+    ColdResetEntry48 in Sticky48 {
+        LDevDiceSigR = 0,
+        LDevDiceSigS = 1,
+        LDevDicePubKeyX = 2,
+        LDevDicePubKeyY = 3,
+        FmcDiceSigR = 4,
+        FmcDiceSigS = 5,
+        FmcPubKeyX = 6,
+        FmcPubKeyY = 7,
+        FmcTci = 8,
+        OwnerPubKeyHash = 9,
+    }
+
+    ColdResetEntry4 in Sticky4 {
+        FmcSvn = 0,
+        RomColdBootStatus = 1,
+        FmcEntryPoint = 2,
+        EccVendorPubKeyIndex = 3,
+        LmsVendorPubKeyIndex = 4,
+        MonotonicCounter0 = 5,
+        MonotonicCounter1 = 6,
+        MonotonicCounter2 = 7,
+    }
+
+    WarmResetEntry48 in NonSticky48 {
+        RtTci = 0,
+        NextOwnerPubKeyHash = 1,
+        CommittedOwnerPubKeyHash = 2,
+    }
+
+    WarmResetEntry4 in NonSticky4 {
+        RtSvn = 0,
+        RtEntryPoint = 1,
+        ManifestAddr = 2,
+        RtMinSvn = 3,
+        RomUpdateResetStatus = 4,
+        BootSessionId0 = 5,
+        BootSessionId1 = 6,
     }
 }
 
+/// Entries holding the monotonic counters of the runtime. They are kept
+/// across warm resets and firmware updates.
+pub const MONOTONIC_COUNTER_ENTRIES: [ColdResetEntry4; 3] = [
+    ColdResetEntry4::MonotonicCounter0,
+    ColdResetEntry4::MonotonicCounter1,
+    ColdResetEntry4::MonotonicCounter2,
+];
+
 pub struct DataVault {
     dv: DvReg,
 }
@@ -194,8 +324,8 @@ impl DataVault {
     /// * `sig` - ldev dice signature
     ///
     pub fn set_ldev_dice_signature(&mut self, sig: &Ecc384Signature) {
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::LDevDiceSigR, &sig.r);
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::LDevDiceSigS, &sig.s);
+        self.write_lock::<dv::LDevDiceSigR>(&sig.r);
+        self.write_lock::<dv::LDevDiceSigS>(&sig.s);
     }
 
     /// Get the ldev dice signature.
@@ -208,8 +338,8 @@ impl DataVault {
     ///
     pub fn ldev_dice_signature(&self) -> Ecc384Signature {
         Ecc384Signature {
-            r: self.read_cold_reset_entry48(ColdResetEntry48::LDevDiceSigR),
-            s: self.read_cold_reset_entry48(ColdResetEntry48::LDevDiceSigS),
+            r: self.read::<dv::LDevDiceSigR>(),
+            s: self.read::<dv::LDevDiceSigS>(),
         }
     }
 
@@ -219,8 +349,8 @@ impl DataVault {
     /// * `pub_key` - ldev dice public key
     ///
     pub fn set_ldev_dice_pub_key(&mut self, pub_key: &Ecc384PubKey) {
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::LDevDicePubKeyX, &pub_key.x);
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::LDevDicePubKeyY, &pub_key.y);
+        self.write_lock::<dv::LDevDicePubKeyX>(&pub_key.x);
+        self.write_lock::<dv::LDevDicePubKeyY>(&pub_key.y);
     }
 
     /// Get the ldev dice public key.
@@ -230,8 +360,8 @@ impl DataVault {
     ///
     pub fn ldev_dice_pub_key(&self) -> Ecc384PubKey {
         Ecc384PubKey {
-            x: self.read_cold_reset_entry48(ColdResetEntry48::LDevDicePubKeyX),
-            y: self.read_cold_reset_entry48(ColdResetEntry48::LDevDicePubKeyY),
+            x: self.read::<dv::LDevDicePubKeyX>(),
+            y: self.read::<dv::LDevDicePubKeyY>(),
         }
     }
 
@@ -241,8 +371,8 @@ impl DataVault {
     /// * `sig` - fmc dice signature
     ///
     pub fn set_fmc_dice_signature(&mut self, sig: &Ecc384Signature) {
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::FmcDiceSigR, &sig.r);
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::FmcDiceSigS, &sig.s);
+        self.write_lock::<dv::FmcDiceSigR>(&sig.r);
+        self.write_lock::<dv::FmcDiceSigS>(&sig.s);
     }
 
     /// Get the fmc dice signature.
@@ -252,8 +382,8 @@ impl DataVault {
     ///
     pub fn fmc_dice_signature(&self) -> Ecc384Signature {
         Ecc384Signature {
-            r: self.read_cold_reset_entry48(ColdResetEntry48::FmcDiceSigR),
-            s: self.read_cold_reset_entry48(ColdResetEntry48::FmcDiceSigS),
+            r: self.read::<dv::FmcDiceSigR>(),
+            s: self.read::<dv::FmcDiceSigS>(),
         }
     }
 
@@ -263,8 +393,8 @@ impl DataVault {
     /// * `pub_key` - fmc public key
     ///
     pub fn set_fmc_pub_key(&mut self, pub_key: &Ecc384PubKey) {
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::FmcPubKeyX, &pub_key.x);
-        self.write_lock_cold_reset_entry48(ColdResetEntry48::FmcPubKeyY, &pub_key.y);
+        self.write_lock::<dv::FmcPubKeyX>(&pub_key.x);
+        self.write_lock::<dv::FmcPubKeyY>(&pub_key.y);
     }
 
     /// Get the fmc public key.
@@ -274,8 +404,8 @@ impl DataVault {
     ///
     pub fn fmc_pub_key(&self) -> Ecc384PubKey {
         Ecc384PubKey {
-            x: self.read_cold_reset_entry48(ColdResetEntry48::FmcPubKeyX),
-            y: self.read_cold_reset_entry48(ColdResetEntry48::FmcPubKeyY),
+            x: self.read::<dv::FmcPubKeyX>(),
+            y: self.read::<dv::FmcPubKeyY>(),
         }
     }
 
@@ -285,7 +415,7 @@ impl DataVault {
     /// * fmc tcb component identifier
     ///
     pub fn fmc_tci(&self) -> Array4x12 {
-        self.read_cold_reset_entry48(ColdResetEntry48::FmcTci)
+        self.read::<dv::FmcTci>()
     }
 
     /// Get the owner public key hash
@@ -295,7 +425,7 @@ impl DataVault {
     /// * `Array4x12` - Owner public key hash
     ///
     pub fn owner_pk_hash(&self) -> Array4x12 {
-        self.read_cold_reset_entry48(ColdResetEntry48::OwnerPubKeyHash)
+        self.read::<dv::OwnerPubKeyHash>()
     }
 
     /// Get the fmc security version number.
@@ -304,7 +434,7 @@ impl DataVault {
     /// * fmc security version number
     ///
     pub fn fmc_svn(&self) -> u32 {
-        self.read_cold_reset_entry4(ColdResetEntry4::FmcSvn)
+        self.read::<dv::FmcSvn>()
     }
 
     /// Get the fmc entry.
@@ -313,7 +443,7 @@ impl DataVault {
     ///
     /// * fmc entry point
     pub fn fmc_entry_point(&self) -> u32 {
-        self.read_cold_reset_entry4(ColdResetEntry4::FmcEntryPoint)
+        self.read::<dv::FmcEntryPoint>()
     }
 
     /// Get the Ecc vendor public key index used for image verification.
@@ -322,7 +452,7 @@ impl DataVault {
     ///
     /// * `u32` - Vendor public key index
     pub fn ecc_vendor_pk_index(&self) -> u32 {
-        self.read_cold_reset_entry4(ColdResetEntry4::EccVendorPubKeyIndex)
+        self.read::<dv::EccVendorPubKeyIndex>()
     }
 
     /// Get the Lms vendor public key index used for image verification.
@@ -331,7 +461,7 @@ impl DataVault {
     ///
     /// * `u32` - Vendor public key index
     pub fn lms_vendor_pk_index(&self) -> u32 {
-        self.read_cold_reset_entry4(ColdResetEntry4::LmsVendorPubKeyIndex)
+        self.read::<dv::LmsVendorPubKeyIndex>()
     }
 
     /// Get the rom cold boot status.
@@ -340,7 +470,7 @@ impl DataVault {
     ///
     /// * `u32` - Rom Cold Boot Status
     pub fn rom_cold_boot_status(&self) -> u32 {
-        self.read_cold_reset_entry4(ColdResetEntry4::RomColdBootStatus)
+        self.read::<dv::RomColdBootStatus>()
     }
 
     /// Get the rom update reset status.
@@ -349,7 +479,7 @@ impl DataVault {
     ///
     /// * `u32` - Rom Update Reset Status
     pub fn rom_update_reset_status(&self) -> u32 {
-        self.read_warm_reset_entry4(WarmResetEntry4::RomUpdateResetStatus)
+        self.read::<dv::RomUpdateResetStatus>()
     }
 
     /// Get the rt tcb component identifier.
@@ -358,7 +488,38 @@ impl DataVault {
     /// * rt tcb component identifier
     ///
     pub fn rt_tci(&self) -> Array4x12 {
-        self.read_warm_reset_entry48(WarmResetEntry48::RtTci)
+        self.read::<dv::RtTci>()
+    }
+
+    /// Get the digest of the owner public keys the running image authorizes
+    /// rotating to.
+    ///
+    /// # Returns
+    ///
+    /// * `Array4x12` - Next owner public key hash, zero if no rotation is pending
+    ///
+    pub fn next_owner_pk_hash(&self) -> Array4x12 {
+        self.read::<dv::NextOwnerPubKeyHash>()
+    }
+
+    /// Get the owner public key hash committed by an owner key rotation.
+    ///
+    /// # Returns
+    ///
+    /// * `Array4x12` - Committed owner public key hash, zero if no rotation was committed
+    ///
+    pub fn committed_owner_pk_hash(&self) -> Array4x12 {
+        self.read::<dv::CommittedOwnerPubKeyHash>()
+    }
+
+    /// Commit an owner key rotation and lock the entry.
+    ///
+    /// # Arguments
+    /// * `value` - Owner public key hash to commit
+    ///
+    pub fn commit_owner_pk_hash(&mut self, value: &Array4x12) {
+        self.write::<dv::CommittedOwnerPubKeyHash>(value);
+        self.lock::<dv::CommittedOwnerPubKeyHash>();
     }
 
     /// Get the rt security version number.
@@ -367,7 +528,7 @@ impl DataVault {
     /// * rt security version number
     ///
     pub fn rt_svn(&self) -> u32 {
-        self.read_warm_reset_entry4(WarmResetEntry4::RtSvn)
+        self.read::<dv::RtSvn>()
     }
 
     /// Get the rt minimum security version number.
@@ -376,7 +537,7 @@ impl DataVault {
     /// * rt minimum security version number
     ///
     pub fn rt_min_svn(&self) -> u32 {
-        self.read_warm_reset_entry4(WarmResetEntry4::RtMinSvn)
+        self.read::<dv::RtMinSvn>()
     }
 
     /// Get the rt entry.
@@ -385,7 +546,7 @@ impl DataVault {
     ///
     /// * rt entry point
     pub fn rt_entry_point(&self) -> u32 {
-        self.read_warm_reset_entry4(WarmResetEntry4::RtEntryPoint)
+        self.read::<dv::RtEntryPoint>()
     }
 
     /// Get the manifest address.
@@ -394,182 +555,147 @@ impl DataVault {
     ///
     /// * manifest address
     pub fn manifest_addr(&self) -> u32 {
-        self.read_warm_reset_entry4(WarmResetEntry4::ManifestAddr)
+        self.read::<dv::ManifestAddr>()
     }
 
-    /// Read the cold reset entry.
-    ///
-    /// # Arguments
-    /// * `entry` - cold reset entry
+    /// Read a data vault entry.
     ///
     /// # Returns
-    ///    cold reset entry value  
+    ///    entry value
     ///
-    pub fn read_cold_reset_entry48(&self, entry: ColdResetEntry48) -> Array4x12 {
-        let dv = self.dv.regs();
-        Array4x12::read_from_reg(dv.sticky_data_vault_entry().at(entry.into()))
+    pub fn read<E: DvEntry>(&self) -> DvValue<E> {
+        E::Bank::read(&self.dv, E::INDEX)
     }
 
-    /// Write and lock the cold reset entry.
+    /// Write a data vault entry.
     ///
     /// # Arguments
-    /// * `entry` - cold reset entry
-    /// * `value` - cold reset entry value
+    /// * `value` - entry value
     ///
-    fn write_lock_cold_reset_entry48(&mut self, entry: ColdResetEntry48, value: &Array4x12) {
-        self.write_cold_reset_entry48(entry, value);
-        self.lock_cold_reset_entry48(entry);
+    pub fn write<E: DvEntry>(&mut self, value: &DvValue<E>) {
+        E::Bank::write(&mut self.dv, E::INDEX, value);
     }
 
-    /// Write the cold reset entry.
-    ///
-    /// # Arguments
-    /// * `entry` - cold reset entry
-    /// * `value` - cold reset entry value
-    ///
-    pub fn write_cold_reset_entry48(&mut self, entry: ColdResetEntry48, value: &Array4x12) {
-        let dv = self.dv.regs_mut();
-        value.write_to_reg(dv.sticky_data_vault_entry().at(entry.into()));
+    /// Lock a data vault entry.
+    pub fn lock<E: DvEntry>(&mut self) {
+        E::Bank::lock(&mut self.dv, E::INDEX);
     }
 
-    /// Lock the cold reset entry.
+    /// Write and lock a data vault entry.
     ///
     /// # Arguments
-    /// * `entry` - cold reset entry
+    /// * `value` - entry value
     ///
-    pub fn lock_cold_reset_entry48(&mut self, entry: ColdResetEntry48) {
-        let dv = self.dv.regs_mut();
-        dv.sticky_data_vault_ctrl()
-            .at(entry.into())
-            .write(|w| w.lock_entry(true));
+    pub fn write_lock<E: DvEntry>(&mut self, value: &DvValue<E>) {
+        self.write::<E>(value);
+        self.lock::<E>();
+    }
+
+    /// Check if a data vault entry is locked.
+    pub fn is_locked<E: DvEntry>(&self) -> bool {
+        E::Bank::is_locked(&self.dv, E::INDEX)
     }
 
-    /// Read the warm reset entry.
+    /// Read the cold reset entry named by an FHT handle.
     ///
     /// # Arguments
-    /// * `entry` - warm reset entry
+    /// * `entry` - cold reset entry
     ///
     /// # Returns
-    ///    warm reset entry value  
+    ///    cold reset entry value
     ///
-    pub fn read_warm_reset_entry48(&self, entry: WarmResetEntry48) -> Array4x12 {
-        let dv = self.dv.regs();
-        Array4x12::read_from_reg(dv.data_vault_entry().at(entry.into()))
+    pub fn read_cold_reset_entry48(&self, entry: ColdResetEntry48) -> Array4x12 {
+        Sticky48::read(&self.dv, entry.into())
     }
 
-    /// Write the warm reset entry.
+    /// Check if the cold reset entry is locked.
     ///
     /// # Arguments
-    /// * `entry` - warm reset entry
-    /// * `value` - warm reset entry value
+    /// * `entry` - cold reset entry
     ///
-    pub fn write_warm_reset_entry48(&mut self, entry: WarmResetEntry48, value: &Array4x12) {
-        let dv = self.dv.regs_mut();
-        value.write_to_reg(dv.data_vault_entry().at(entry.into()));
+    pub fn is_cold_reset_entry48_locked(&self, entry: ColdResetEntry48) -> bool {
+        Sticky48::is_locked(&self.dv, entry.into())
     }
 
-    /// Lock the warm reset entry.
+    /// Read the warm reset entry named by an FHT handle.
     ///
     /// # Arguments
     /// * `entry` - warm reset entry
     ///
-    pub fn lock_warm_reset_entry48(&mut self, entry: WarmResetEntry48) {
-        let dv = self.dv.regs_mut();
-        dv.data_vault_ctrl()
-            .at(entry.into())
-            .write(|w| w.lock_entry(true));
+    /// # Returns
+    ///    warm reset entry value
+    ///
+    pub fn read_warm_reset_entry48(&self, entry: WarmResetEntry48) -> Array4x12 {
+        NonSticky48::read(&self.dv, entry.into())
     }
 
-    /// Read the cold reset entry.
+    /// Read the cold reset entry named by an FHT handle.
     ///
     /// # Arguments
     /// * `entry` - cold reset entry
     ///
     /// # Returns
-    ///    cold reset entry value  
+    ///    cold reset entry value
     ///
     pub fn read_cold_reset_entry4(&self, entry: ColdResetEntry4) -> u32 {
-        let dv = self.dv.regs();
-        dv.sticky_lockable_scratch_reg().at(entry.into()).read()
+        Sticky4::read(&self.dv, entry.into())
     }
 
-    /// Write and lock the cold reset entry.
+    /// Check if the cold reset entry is locked.
     ///
     /// # Arguments
     /// * `entry` - cold reset entry
-    /// * `value` - cold reset entry value
     ///
-    pub fn write_lock_cold_reset_entry4(&mut self, entry: ColdResetEntry4, value: u32) {
-        self.write_cold_reset_entry4(entry, value);
-        self.lock_cold_reset_entry4(entry);
+    pub fn is_cold_reset_entry4_locked(&self, entry: ColdResetEntry4) -> bool {
+        Sticky4::is_locked(&self.dv, entry.into())
     }
 
-    /// Write the cold reset entry.
+    /// Write a cold reset entry. Locked entries ignore writes.
     ///
     /// # Arguments
     /// * `entry` - cold reset entry
-    /// * `value` - cold reset entry value
+    /// * `value` - entry value
     ///
     pub fn write_cold_reset_entry4(&mut self, entry: ColdResetEntry4, value: u32) {
-        let dv = self.dv.regs_mut();
-        dv.sticky_lockable_scratch_reg()
-            .at(entry.into())
-            .write(|_| value);
+        Sticky4::write(&mut self.dv, entry.into(), &value);
     }
 
-    /// Lock the cold reset entry.
+    /// Lock a cold reset entry until the next cold reset.
     ///
     /// # Arguments
     /// * `entry` - cold reset entry
     ///
     pub fn lock_cold_reset_entry4(&mut self, entry: ColdResetEntry4) {
-        let dv = self.dv.regs_mut();
-        dv.sticky_lockable_scratch_reg_ctrl()
-            .at(entry.into())
-            .write(|w| w.lock_entry(true));
+        Sticky4::lock(&mut self.dv, entry.into());
     }
 
-    /// Read the warm reset entry.
+    /// Read the warm reset entry named by an FHT handle.
     ///
     /// # Arguments
     /// * `entry` - warm reset entry
     ///
     /// # Returns
-    ///    warm reset entry value  
+    ///    warm reset entry value
     ///
     pub fn read_warm_reset_entry4(&self, entry: WarmResetEntry4) -> u32 {
-        let dv = self.dv.regs();
-        dv.lockable_scratch_reg().at(entry.into()).read()
-    }
-
-    /// Write and lock the warm reset entry.
-    ///
-    /// # Arguments
-    /// * `entry` - warm reset entry
-    /// * `value` - warm reset entry value
-    pub fn write_lock_warm_reset_entry4(&mut self, entry: WarmResetEntry4, value: u32) {
-        self.write_warm_reset_entry4(entry, value);
-        self.lock_warm_reset_entry4(entry);
+        NonSticky4::read(&self.dv, entry.into())
     }
 
-    /// Write the warm reset entry.
+    /// Lock every data vault and lockable scratch register entry.
     ///
-    /// # Arguments
-    /// * `entry` - warm reset entry
-    /// * `value` - warm reset entry value
-    pub fn write_warm_reset_entry4(&mut self, entry: WarmResetEntry4, value: u32) {
-        let dv = self.dv.regs_mut();
-        dv.lockable_scratch_reg().at(entry.into()).write(|_| value);
-    }
-
-    /// Lock the warm reset entry.
-    ///
-    /// # Arguments
-    /// * `entry` - warm reset entry
-    pub fn lock_warm_reset_entry4(&mut self, entry: WarmResetEntry4) {
-        let dv = self.dv.regs_mut();
-        dv.lockable_scratch_reg_ctrl()
-            .at(entry.into())
-            .write(|w| w.lock_entry(true));
+    /// Locked entries can no longer be written until the next reset.
+    pub fn lock_all(&mut self) {
+        for i in 0..Sticky48::COUNT {
+            Sticky48::lock(&mut self.dv, i);
+        }
+        for i in 0..NonSticky48::COUNT {
+            NonSticky48::lock(&mut self.dv, i);
+        }
+        for i in 0..Sticky4::COUNT {
+            Sticky4::lock(&mut self.dv, i);
+        }
+        for i in 0..NonSticky4::COUNT {
+            NonSticky4::lock(&mut self.dv, i);
+        }
     }
 }
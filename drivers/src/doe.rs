@@ -93,4 +93,19 @@ impl DeobfuscationEngine {
 
         Ok(())
     }
+
+    /// Returns true if the UDS was decrypted since cold reset
+    pub fn uds_flow_done(&self) -> bool {
+        self.doe.regs().status().read().uds_flow_done()
+    }
+
+    /// Returns true if the field entropy was decrypted since cold reset
+    pub fn fe_flow_done(&self) -> bool {
+        self.doe.regs().status().read().fe_flow_done()
+    }
+
+    /// Returns true if the obfuscated secrets were cleared
+    pub fn secrets_cleared(&self) -> bool {
+        self.doe.regs().status().read().deobf_secrets_cleared()
+    }
 }
@@ -0,0 +1,693 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    ecc256_sw.rs
+
+Abstract:
+
+    File contains a software implementation of ECDSA over the NIST P-256
+    curve (FIPS 186-5) for DPE profiles that require it, as the ECC engine
+    only supports P-384. Also provides ECDH over the same curve, which the
+    ECC engine does not support at all.
+
+--*/
+
+use crate::{Array4x12, Array4x8, CaliptraError, CaliptraResult, Trng};
+use zeroize::Zeroize;
+
+/// ECC-256 coordinate or scalar
+pub type Ecc256Scalar = Array4x8;
+
+/// ECC-256 result
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Ecc256Result {
+    Success = 0,
+    SigVerifyFailed = 1,
+}
+
+/// ECC-256 public key
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Zeroize)]
+pub struct Ecc256PubKey {
+    /// X coordinate
+    pub x: Ecc256Scalar,
+
+    /// Y coordinate
+    pub y: Ecc256Scalar,
+}
+
+/// ECC-256 signature
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Zeroize)]
+pub struct Ecc256Signature {
+    /// Random point
+    pub r: Ecc256Scalar,
+
+    /// Proof
+    pub s: Ecc256Scalar,
+}
+
+/// ECC-256 private key, which is zeroized when dropped
+pub struct Ecc256PrivKey(Limbs);
+
+impl Drop for Ecc256PrivKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+const LIMBS: usize = 8;
+
+/// 256-bit integer, least significant word first
+type Limbs = [u32; LIMBS];
+
+const ZERO: Limbs = [0; LIMBS];
+const ONE: Limbs = [1, 0, 0, 0, 0, 0, 0, 0];
+const TWO: Limbs = [2, 0, 0, 0, 0, 0, 0, 0];
+
+/// Odd modulus `m` of Montgomery arithmetic, with R = 2^256
+struct Modulus {
+    m: Limbs,
+
+    /// -m^-1 mod 2^32
+    m_inv: u32,
+
+    /// R^2 mod m
+    r2: Limbs,
+}
+
+/// Field prime p
+const FIELD: Modulus = Modulus {
+    m: [
+        0xffffffff, 0xffffffff, 0xffffffff, 0x00000000, 0x00000000, 0x00000000, 0x00000001,
+        0xffffffff,
+    ],
+    m_inv: 0x00000001,
+    r2: [
+        0x00000003, 0x00000000, 0xffffffff, 0xfffffffb, 0xfffffffe, 0xffffffff, 0xfffffffd,
+        0x00000004,
+    ],
+};
+
+/// Group order n
+const ORDER: Modulus = Modulus {
+    m: [
+        0xfc632551, 0xf3b9cac2, 0xa7179e84, 0xbce6faad, 0xffffffff, 0xffffffff, 0x00000000,
+        0xffffffff,
+    ],
+    m_inv: 0xee00bc4f,
+    r2: [
+        0xbe79eea2, 0x83244c95, 0x49bd6fa6, 0x4699799c, 0x2b6bec59, 0x2845b239, 0xf3d95620,
+        0x66e12d94,
+    ],
+};
+
+/// Curve coefficient b, in Montgomery form
+const B_MONT: Limbs = [
+    0x29c4bddf, 0xd89cdf62, 0x78843090, 0xacf005cd, 0xf7212ed6, 0xe5a220ab, 0x04874834, 0xdc30061d,
+];
+
+/// Base point G
+const G_X: Limbs = [
+    0xd898c296, 0xf4a13945, 0x2deb33a0, 0x77037d81, 0x63a440f2, 0xf8bce6e5, 0xe12c4247, 0x6b17d1f2,
+];
+const G_Y: Limbs = [
+    0x37bf51f5, 0xcbb64068, 0x6b315ece, 0x2bce3357, 0x7c0f9e16, 0x8ee7eb4a, 0xfe1a7f9b, 0x4fe342e2,
+];
+
+/// a + b, with the carry out
+fn add(a: &Limbs, b: &Limbs) -> (Limbs, u32) {
+    let mut out = ZERO;
+    let mut carry = 0u64;
+    for i in 0..LIMBS {
+        let sum = a[i] as u64 + b[i] as u64 + carry;
+        out[i] = sum as u32;
+        carry = sum >> 32;
+    }
+    (out, carry as u32)
+}
+
+/// a - b, with the borrow out
+fn sub(a: &Limbs, b: &Limbs) -> (Limbs, u32) {
+    let mut out = ZERO;
+    let mut borrow = 0u64;
+    for i in 0..LIMBS {
+        let diff = (a[i] as u64).wrapping_sub(b[i] as u64).wrapping_sub(borrow);
+        out[i] = diff as u32;
+        borrow = diff >> 63;
+    }
+    (out, borrow as u32)
+}
+
+/// `a` if `choice` is 1 and `b` if it is 0, in constant time
+fn select(choice: u32, a: &Limbs, b: &Limbs) -> Limbs {
+    let mask = 0u32.wrapping_sub(choice);
+    let mut out = ZERO;
+    for i in 0..LIMBS {
+        out[i] = (a[i] & mask) | (b[i] & !mask);
+    }
+    out
+}
+
+/// 1 if `a` is zero and 0 otherwise, in constant time
+fn is_zero(a: &Limbs) -> u32 {
+    let acc = a.iter().fold(0, |acc, word| acc | word);
+    ((acc as u64).wrapping_sub(1) >> 63) as u32
+}
+
+fn bit(a: &Limbs, i: usize) -> u32 {
+    (a[i / 32] >> (i % 32)) & 1
+}
+
+fn from_scalar(a: &Ecc256Scalar) -> Limbs {
+    let mut out = ZERO;
+    for i in 0..LIMBS {
+        out[i] = a.0[LIMBS - 1 - i];
+    }
+    out
+}
+
+fn to_scalar(a: &Limbs) -> Ecc256Scalar {
+    let mut out = Ecc256Scalar::default();
+    for i in 0..LIMBS {
+        out.0[LIMBS - 1 - i] = a[i];
+    }
+    out
+}
+
+impl Modulus {
+    /// a + b mod m, for a, b < m
+    fn add(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (sum, carry) = add(a, b);
+        let (reduced, borrow) = sub(&sum, &self.m);
+        select(carry | (borrow ^ 1), &reduced, &sum)
+    }
+
+    /// a - b mod m, for a, b < m
+    fn sub(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (diff, borrow) = sub(a, b);
+        let (fixed, _) = add(&diff, &self.m);
+        select(borrow, &fixed, &diff)
+    }
+
+    /// a * b * R^-1 mod m, for a * b < m * R
+    fn mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut t = [0u32; LIMBS + 2];
+        for i in 0..LIMBS {
+            let mut carry = 0u64;
+            for j in 0..LIMBS {
+                let v = t[j] as u64 + a[j] as u64 * b[i] as u64 + carry;
+                t[j] = v as u32;
+                carry = v >> 32;
+            }
+            let v = t[LIMBS] as u64 + carry;
+            t[LIMBS] = v as u32;
+            t[LIMBS + 1] = (v >> 32) as u32;
+
+            let q = t[0].wrapping_mul(self.m_inv);
+            let v = t[0] as u64 + q as u64 * self.m[0] as u64;
+            let mut carry = v >> 32;
+            for j in 1..LIMBS {
+                let v = t[j] as u64 + q as u64 * self.m[j] as u64 + carry;
+                t[j - 1] = v as u32;
+                carry = v >> 32;
+            }
+            let v = t[LIMBS] as u64 + carry;
+            t[LIMBS - 1] = v as u32;
+            t[LIMBS] = t[LIMBS + 1] + (v >> 32) as u32;
+        }
+
+        let mut res = ZERO;
+        res.copy_from_slice(&t[..LIMBS]);
+        let (reduced, borrow) = sub(&res, &self.m);
+        let out = select(t[LIMBS] | (borrow ^ 1), &reduced, &res);
+        t.zeroize();
+        out
+    }
+
+    fn to_mont(&self, a: &Limbs) -> Limbs {
+        self.mul(a, &self.r2)
+    }
+
+    fn from_mont(&self, a: &Limbs) -> Limbs {
+        self.mul(a, &ONE)
+    }
+
+    /// a^-1 mod m in Montgomery form, for a in Montgomery form. The
+    /// exponent m - 2 is public, so the square-and-multiply branches do not
+    /// leak `a`.
+    fn inv(&self, a: &Limbs) -> Limbs {
+        let (e, _) = sub(&self.m, &TWO);
+        let mut out = self.to_mont(&ONE);
+        for i in (0..LIMBS * 32).rev() {
+            out = self.mul(&out, &out);
+            if bit(&e, i) == 1 {
+                out = self.mul(&out, a);
+            }
+        }
+        out
+    }
+
+    /// a mod m, for a < 2^256 < 2m
+    fn reduce(&self, a: &Limbs) -> Limbs {
+        let (reduced, borrow) = sub(a, &self.m);
+        select(borrow, a, &reduced)
+    }
+
+    /// Reduce the 384-bit big-endian `seed` modulo m. The extra 128 bits
+    /// make the bias of the result negligible (FIPS 186-5 A.2.1).
+    fn reduce_seed(&self, seed: &Array4x12) -> Limbs {
+        let mut lo = ZERO;
+        let mut hi = ZERO;
+        for i in 0..LIMBS {
+            lo[i] = seed.0[11 - i];
+        }
+        for i in 0..4 {
+            hi[i] = seed.0[3 - i];
+        }
+        // hi * R^2 * R^-1 = hi * 2^256 mod m
+        let hi = self.mul(&hi, &self.r2);
+        let out = self.add(&self.reduce(&lo), &hi);
+        lo.zeroize();
+        out
+    }
+}
+
+/// Point in homogeneous projective coordinates, in Montgomery form
+#[derive(Clone, Copy)]
+struct Point {
+    x: Limbs,
+    y: Limbs,
+    z: Limbs,
+}
+
+impl Point {
+    fn identity() -> Self {
+        Self {
+            x: ZERO,
+            y: FIELD.to_mont(&ONE),
+            z: ZERO,
+        }
+    }
+
+    /// Point of the affine coordinates `x` and `y`, checking that it is
+    /// on the curve
+    fn from_affine(x: &Limbs, y: &Limbs) -> Option<Self> {
+        let (_, x_borrow) = sub(x, &FIELD.m);
+        let (_, y_borrow) = sub(y, &FIELD.m);
+        if x_borrow & y_borrow == 0 {
+            return None;
+        }
+        let x = FIELD.to_mont(x);
+        let y = FIELD.to_mont(y);
+
+        // y^2 = x^3 - 3x + b
+        let lhs = FIELD.mul(&y, &y);
+        let x3 = FIELD.mul(&FIELD.mul(&x, &x), &x);
+        let three_x = FIELD.add(&FIELD.add(&x, &x), &x);
+        let rhs = FIELD.add(&FIELD.sub(&x3, &three_x), &B_MONT);
+        if lhs != rhs {
+            return None;
+        }
+        Some(Self {
+            x,
+            y,
+            z: FIELD.to_mont(&ONE),
+        })
+    }
+
+    fn generator() -> Self {
+        Self {
+            x: FIELD.to_mont(&G_X),
+            y: FIELD.to_mont(&G_Y),
+            z: FIELD.to_mont(&ONE),
+        }
+    }
+
+    /// Affine coordinates of the point, or None for the identity
+    fn to_affine(&self) -> Option<(Limbs, Limbs)> {
+        if is_zero(&self.z) == 1 {
+            return None;
+        }
+        let z_inv = FIELD.inv(&self.z);
+        let x = FIELD.from_mont(&FIELD.mul(&self.x, &z_inv));
+        let y = FIELD.from_mont(&FIELD.mul(&self.y, &z_inv));
+        Some((x, y))
+    }
+
+    /// Complete addition for a = -3 (Renes, Costello and Batina 2015,
+    /// algorithm 4), which also doubles points and handles the identity
+    fn add(&self, other: &Self) -> Self {
+        let f = &FIELD;
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+
+        let mut t0 = f.mul(x1, x2);
+        let mut t1 = f.mul(y1, y2);
+        let mut t2 = f.mul(z1, z2);
+        let mut t3 = f.add(x1, y1);
+        let mut t4 = f.add(x2, y2);
+        t3 = f.mul(&t3, &t4);
+        t4 = f.add(&t0, &t1);
+        t3 = f.sub(&t3, &t4);
+        t4 = f.add(y1, z1);
+        let mut x3 = f.add(y2, z2);
+        t4 = f.mul(&t4, &x3);
+        x3 = f.add(&t1, &t2);
+        t4 = f.sub(&t4, &x3);
+        x3 = f.add(x1, z1);
+        let mut y3 = f.add(x2, z2);
+        x3 = f.mul(&x3, &y3);
+        y3 = f.add(&t0, &t2);
+        y3 = f.sub(&x3, &y3);
+        let mut z3 = f.mul(&B_MONT, &t2);
+        x3 = f.sub(&y3, &z3);
+        z3 = f.add(&x3, &x3);
+        x3 = f.add(&x3, &z3);
+        z3 = f.sub(&t1, &x3);
+        x3 = f.add(&t1, &x3);
+        y3 = f.mul(&B_MONT, &y3);
+        t1 = f.add(&t2, &t2);
+        t2 = f.add(&t1, &t2);
+        y3 = f.sub(&y3, &t2);
+        y3 = f.sub(&y3, &t0);
+        t1 = f.add(&y3, &y3);
+        y3 = f.add(&t1, &y3);
+        t1 = f.add(&t0, &t0);
+        t0 = f.add(&t1, &t0);
+        t0 = f.sub(&t0, &t2);
+        t1 = f.mul(&t4, &y3);
+        t2 = f.mul(&t0, &y3);
+        y3 = f.mul(&x3, &z3);
+        y3 = f.add(&y3, &t2);
+        x3 = f.mul(&t3, &x3);
+        x3 = f.sub(&x3, &t1);
+        z3 = f.mul(&t4, &z3);
+        t1 = f.mul(&t3, &t0);
+        z3 = f.add(&z3, &t1);
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// k * self, in constant time with respect to `k`
+    fn mul(&self, k: &Limbs) -> Self {
+        let mut out = Self::identity();
+        for i in (0..LIMBS * 32).rev() {
+            out = out.add(&out);
+            let sum = out.add(self);
+            let choice = bit(k, i);
+            out = Self {
+                x: select(choice, &sum.x, &out.x),
+                y: select(choice, &sum.y, &out.y),
+                z: select(choice, &sum.z, &out.z),
+            };
+        }
+        out
+    }
+}
+
+/// Software ECDSA and ECDH over the NIST P-256 curve. Scalar multiplications
+/// and operations on private values run in constant time.
+pub struct Ecc256Sw;
+
+impl Ecc256Sw {
+    /// Generate a key pair from a 384-bit seed. The private key is the seed
+    /// reduced modulo the group order.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed, such as the output of a KDF
+    ///
+    /// # Returns
+    ///
+    /// * `(Ecc256PrivKey, Ecc256PubKey)` - Generated key pair
+    pub fn key_pair(seed: &Array4x12) -> CaliptraResult<(Ecc256PrivKey, Ecc256PubKey)> {
+        let priv_key = Ecc256PrivKey(ORDER.reduce_seed(seed));
+        if is_zero(&priv_key.0) == 1 {
+            return Err(CaliptraError::DRIVER_ECC256_SW_KEY_GEN_FAILURE);
+        }
+        let (x, y) = Point::generator()
+            .mul(&priv_key.0)
+            .to_affine()
+            .ok_or(CaliptraError::DRIVER_ECC256_SW_KEY_GEN_FAILURE)?;
+        let pub_key = Ecc256PubKey {
+            x: to_scalar(&x),
+            y: to_scalar(&y),
+        };
+        Ok((priv_key, pub_key))
+    }
+
+    /// Sign a digest, and verify the signature with the public key
+    ///
+    /// # Arguments
+    ///
+    /// * `priv_key` - Private key
+    /// * `pub_key` - Public key to verify with
+    /// * `digest` - Digest to sign
+    /// * `trng` - TRNG driver instance, for the signature nonce
+    ///
+    /// # Returns
+    ///
+    /// * `Ecc256Signature` - Generated signature
+    pub fn sign(
+        priv_key: &Ecc256PrivKey,
+        pub_key: &Ecc256PubKey,
+        digest: &Ecc256Scalar,
+        trng: &mut Trng,
+    ) -> CaliptraResult<Ecc256Signature> {
+        let mut nonce_seed = trng.generate()?;
+        let sig = Self::sign_with_nonce(priv_key, digest, &nonce_seed);
+        nonce_seed.0.zeroize();
+        let sig = sig?;
+
+        if Self::verify(pub_key, digest, &sig)? != Ecc256Result::Success {
+            return Err(CaliptraError::DRIVER_ECC256_SW_SIGNATURE_VERIFY_FAILURE);
+        }
+        Ok(sig)
+    }
+
+    fn sign_with_nonce(
+        priv_key: &Ecc256PrivKey,
+        digest: &Ecc256Scalar,
+        nonce_seed: &Array4x12,
+    ) -> CaliptraResult<Ecc256Signature> {
+        let mut k = ORDER.reduce_seed(nonce_seed);
+        if is_zero(&k) == 1 {
+            return Err(CaliptraError::DRIVER_ECC256_SW_SIGN_FAILURE);
+        }
+        let point = Point::generator().mul(&k).to_affine();
+        let Some((x, _)) = point else {
+            k.zeroize();
+            return Err(CaliptraError::DRIVER_ECC256_SW_SIGN_FAILURE);
+        };
+        let r = ORDER.reduce(&x);
+        let e = ORDER.reduce(&from_scalar(digest));
+
+        // s = k^-1 * (e + r * d) mod n
+        let k_mont = ORDER.to_mont(&k);
+        let mut d_mont = ORDER.to_mont(&priv_key.0);
+        let rd = ORDER.mul(&ORDER.to_mont(&r), &d_mont);
+        let sum = ORDER.add(&ORDER.to_mont(&e), &rd);
+        let s = ORDER.from_mont(&ORDER.mul(&ORDER.inv(&k_mont), &sum));
+        k.zeroize();
+        d_mont.zeroize();
+
+        if is_zero(&r) | is_zero(&s) == 1 {
+            return Err(CaliptraError::DRIVER_ECC256_SW_SIGN_FAILURE);
+        }
+        Ok(Ecc256Signature {
+            r: to_scalar(&r),
+            s: to_scalar(&s),
+        })
+    }
+
+    /// Verify a signature with a public key
+    ///
+    /// # Arguments
+    ///
+    /// * `pub_key` - Public key
+    /// * `digest` - Digest to verify
+    /// * `signature` - Signature to verify
+    ///
+    /// # Returns
+    ///
+    /// * `Ecc256Result` - Ecc256Result::Success if the signature verification passed
+    pub fn verify(
+        pub_key: &Ecc256PubKey,
+        digest: &Ecc256Scalar,
+        signature: &Ecc256Signature,
+    ) -> CaliptraResult<Ecc256Result> {
+        let r = from_scalar(&signature.r);
+        let s = from_scalar(&signature.s);
+        let (_, r_borrow) = sub(&r, &ORDER.m);
+        let (_, s_borrow) = sub(&s, &ORDER.m);
+        if is_zero(&r) | is_zero(&s) == 1 || r_borrow & s_borrow == 0 {
+            return Ok(Ecc256Result::SigVerifyFailed);
+        }
+        let Some(q) = Point::from_affine(&from_scalar(&pub_key.x), &from_scalar(&pub_key.y)) else {
+            return Ok(Ecc256Result::SigVerifyFailed);
+        };
+        let e = ORDER.reduce(&from_scalar(digest));
+
+        // R = (e * w) * G + (r * w) * Q, with w = s^-1 mod n
+        let w = ORDER.inv(&ORDER.to_mont(&s));
+        let u1 = ORDER.from_mont(&ORDER.mul(&ORDER.to_mont(&e), &w));
+        let u2 = ORDER.from_mont(&ORDER.mul(&ORDER.to_mont(&r), &w));
+        let point = Point::generator().mul(&u1).add(&q.mul(&u2));
+        let Some((x, _)) = point.to_affine() else {
+            return Ok(Ecc256Result::SigVerifyFailed);
+        };
+
+        if ORDER.reduce(&x) == r {
+            Ok(Ecc256Result::Success)
+        } else {
+            Ok(Ecc256Result::SigVerifyFailed)
+        }
+    }
+
+    /// Compute the ECDH shared secret of a private key and a peer public key
+    /// (SP 800-56A, section 5.7.1.2). The peer public key is checked to be
+    /// on the curve; P-256 has a cofactor of 1, so no further validation is
+    /// needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `priv_key` - Private key
+    /// * `peer_pub_key` - Public key of the peer
+    ///
+    /// # Returns
+    ///
+    /// * `Ecc256Scalar` - X coordinate of the shared point
+    pub fn ecdh(
+        priv_key: &Ecc256PrivKey,
+        peer_pub_key: &Ecc256PubKey,
+    ) -> CaliptraResult<Ecc256Scalar> {
+        let q = Point::from_affine(&from_scalar(&peer_pub_key.x), &from_scalar(&peer_pub_key.y))
+            .ok_or(CaliptraError::DRIVER_ECC256_SW_ECDH_FAILURE)?;
+        let Some((mut x, mut y)) = q.mul(&priv_key.0).to_affine() else {
+            return Err(CaliptraError::DRIVER_ECC256_SW_ECDH_FAILURE);
+        };
+        let shared_secret = to_scalar(&x);
+        x.zeroize();
+        y.zeroize();
+        Ok(shared_secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6979 A.2.5, with SHA-256 and the message "sample"
+    const SEED: Array4x12 = Array4x12::new([
+        0, 0, 0, 0, 0xc9afa9d8, 0x45ba7516, 0x6b5c2157, 0x67b1d693, 0x4e50c3db, 0x36e89b12,
+        0x7b8a622b, 0x120f6721,
+    ]);
+    const NONCE_SEED: Array4x12 = Array4x12::new([
+        0, 0, 0, 0, 0xa6e3c57d, 0xd01abe90, 0x08653839, 0x8355dd4c, 0x3b17aa87, 0x3382b0f2,
+        0x4d612949, 0x3d8aad60,
+    ]);
+    const PUB_KEY: Ecc256PubKey = Ecc256PubKey {
+        x: Array4x8::new([
+            0x60fed4ba, 0x255a9d31, 0xc961eb74, 0xc6356d68, 0xc049b892, 0x3b61fa6c, 0xe669622e,
+            0x60f29fb6,
+        ]),
+        y: Array4x8::new([
+            0x7903fe10, 0x08b8bc99, 0xa41ae9e9, 0x5628bc64, 0xf2f1b20c, 0x2d7e9f51, 0x77a3c294,
+            0xd4462299,
+        ]),
+    };
+    const DIGEST: Array4x8 = Array4x8::new([
+        0xaf2bdbe1, 0xaa9b6ec1, 0xe2ade1d6, 0x94f41fc7, 0x1a831d02, 0x68e98915, 0x62113d8a,
+        0x62add1bf,
+    ]);
+    const SIGNATURE: Ecc256Signature = Ecc256Signature {
+        r: Array4x8::new([
+            0xefd48b2a, 0xacb6a8fd, 0x1140dd9c, 0xd45e81d6, 0x9d2c877b, 0x56aaf991, 0xc34d0ea8,
+            0x4eaf3716,
+        ]),
+        s: Array4x8::new([
+            0xf7cb1c94, 0x2d657c41, 0xd436c7a1, 0xb6e29f65, 0xf3e900db, 0xb9aff406, 0x4dc4ab2f,
+            0x843acda8,
+        ]),
+    };
+
+    // x(SEED * NONCE_SEED * G)
+    const SHARED_SECRET: Array4x8 = Array4x8::new([
+        0x3ffbbd4f, 0xe496a30e, 0xa456822f, 0x31c21e76, 0x482462fc, 0xa119bece, 0x403abf00,
+        0xbed50fbb,
+    ]);
+
+    #[test]
+    fn test_key_pair() {
+        let (_, pub_key) = Ecc256Sw::key_pair(&SEED).unwrap();
+        assert_eq!(pub_key, PUB_KEY);
+    }
+
+    #[test]
+    fn test_key_pair_zero() {
+        assert_eq!(
+            Ecc256Sw::key_pair(&Array4x12::default()).err(),
+            Some(CaliptraError::DRIVER_ECC256_SW_KEY_GEN_FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_sign_with_nonce() {
+        let (priv_key, _) = Ecc256Sw::key_pair(&SEED).unwrap();
+        let sig = Ecc256Sw::sign_with_nonce(&priv_key, &DIGEST, &NONCE_SEED).unwrap();
+        assert_eq!(sig, SIGNATURE);
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(
+            Ecc256Sw::verify(&PUB_KEY, &DIGEST, &SIGNATURE).unwrap(),
+            Ecc256Result::Success
+        );
+
+        let mut digest = DIGEST;
+        digest.0[0] ^= 1;
+        assert_eq!(
+            Ecc256Sw::verify(&PUB_KEY, &digest, &SIGNATURE).unwrap(),
+            Ecc256Result::SigVerifyFailed
+        );
+
+        let mut pub_key = PUB_KEY;
+        pub_key.y.0[7] ^= 1;
+        assert_eq!(
+            Ecc256Sw::verify(&pub_key, &DIGEST, &SIGNATURE).unwrap(),
+            Ecc256Result::SigVerifyFailed
+        );
+    }
+
+    #[test]
+    fn test_ecdh() {
+        let (priv_key, pub_key) = Ecc256Sw::key_pair(&SEED).unwrap();
+        let (peer_priv_key, peer_pub_key) = Ecc256Sw::key_pair(&NONCE_SEED).unwrap();
+        assert_eq!(
+            Ecc256Sw::ecdh(&priv_key, &peer_pub_key).unwrap(),
+            SHARED_SECRET
+        );
+        assert_eq!(
+            Ecc256Sw::ecdh(&peer_priv_key, &pub_key).unwrap(),
+            SHARED_SECRET
+        );
+    }
+
+    #[test]
+    fn test_ecdh_invalid_peer_key() {
+        let (priv_key, _) = Ecc256Sw::key_pair(&SEED).unwrap();
+        let mut pub_key = PUB_KEY;
+        pub_key.y.0[7] ^= 1;
+        assert_eq!(
+            Ecc256Sw::ecdh(&priv_key, &pub_key).err(),
+            Some(CaliptraError::DRIVER_ECC256_SW_ECDH_FAILURE)
+        );
+    }
+}
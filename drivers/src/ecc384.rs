@@ -14,8 +14,8 @@ Abstract:
 
 use crate::kv_access::{KvAccess, KvAccessErr};
 use crate::{
-    array_concat3, okmutref, wait, Array4x12, Array4xN, CaliptraError, CaliptraResult, KeyReadArgs,
-    KeyWriteArgs, Trng,
+    array_concat3, okmutref, wait, Array4x12, Array4xN, CaliptraError, CaliptraResult, KeyId,
+    KeyReadArgs, KeyUsage, KeyWriteArgs, Trng,
 };
 #[cfg(not(feature = "no-cfi"))]
 use caliptra_cfi_derive::cfi_impl_fn;
@@ -255,6 +255,38 @@ impl Ecc384 {
         self.key_pair_base(seed, nonce, trng, priv_key, None)
     }
 
+    /// Generate ECC-384 Key Pair from a key vault seed, storing the private
+    /// key in the key vault. The key pair passes a pairwise consistency test
+    /// before the public key is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Key slot holding the seed for deterministic key generation
+    /// * `trng` - TRNG driver instance
+    /// * `priv_key` - Key slot to store the ECC-384 private key
+    ///
+    /// # Returns
+    ///
+    /// * `Ecc384PubKey` - Generated ECC-384 Public Key
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn keygen_to_kv(
+        &mut self,
+        seed: KeyId,
+        trng: &mut Trng,
+        priv_key: KeyId,
+    ) -> CaliptraResult<Ecc384PubKey> {
+        self.key_pair_base(
+            &Ecc384Seed::Key(KeyReadArgs::new(seed)),
+            &Array4x12::default(),
+            trng,
+            Ecc384PrivKeyOut::Key(KeyWriteArgs::new(
+                priv_key,
+                KeyUsage::default().set_ecc_private_key_en(),
+            )),
+            None,
+        )
+    }
+
     /// Generate ECC-384 Key Pair for FIPS KAT testing
     /// ONLY to be used for KAT testing
     ///
@@ -461,7 +493,7 @@ impl Ecc384 {
         // Copy digest
         KvAccess::copy_from_arr(data, ecc.msg())?;
 
-        // Generate an IV.
+        // Generate an IV. It masks the nonce derivation but not its result.
         let iv = trng.generate()?;
         KvAccess::copy_from_arr(&iv, ecc.iv())?;
 
@@ -486,6 +518,11 @@ impl Ecc384 {
     /// attacks that could expose the private key, this function also verifies
     /// the generated signature.
     ///
+    /// The engine derives the per-signature nonce from the private key and the
+    /// digest as specified by RFC 6979, so the signature is always the same for
+    /// a given key and digest. The IV drawn from `trng` only randomizes the
+    /// engine's side-channel countermeasures and does not affect the result.
+    ///
     /// # Arguments
     ///
     /// * `priv_key` - Private key
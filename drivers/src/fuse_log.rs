@@ -19,16 +19,17 @@ use zeroize::Zeroize;
 
 pub enum FuseLogEntryId {
     Invalid = 0,
-    VendorEccPubKeyIndex = 1,       // 4 bytes  (From Manifest)
-    VendorEccPubKeyRevocation = 2,  // 4 bytes  (From Fuse)
-    ManifestFmcSvn = 3,             // 4 bytes
-    ManifestReserved0 = 4,          // 4 bytes
-    FuseFmcSvn = 5,                 // 4 bytes
-    ManifestRtSvn = 6,              // 4 bytes
-    ManifestReserved1 = 7,          // 4 bytes
-    FuseRtSvn = 8,                  // 4 bytes
-    VendorLmsPubKeyIndex = 9,       // 4 bytes  (From Manifest)
-    VendorLmsPubKeyRevocation = 10, // 4 bytes  (From Fuse)
+    VendorEccPubKeyIndex = 1,           // 4 bytes  (From Manifest)
+    VendorEccPubKeyRevocation = 2,      // 4 bytes  (From Fuse)
+    ManifestFmcSvn = 3,                 // 4 bytes
+    ManifestReserved0 = 4,              // 4 bytes
+    FuseFmcSvn = 5,                     // 4 bytes
+    ManifestRtSvn = 6,                  // 4 bytes
+    ManifestReserved1 = 7,              // 4 bytes
+    FuseRtSvn = 8,                      // 4 bytes
+    VendorLmsPubKeyIndex = 9,           // 4 bytes  (From Manifest)
+    VendorLmsPubKeyRevocation = 10,     // 4 bytes  (From Fuse)
+    VendorIntermediateEccKeyIndex = 11, // 4 bytes  (From Manifest)
 }
 
 impl From<u32> for FuseLogEntryId {
@@ -44,6 +45,7 @@ impl From<u32> for FuseLogEntryId {
             8 => FuseLogEntryId::FuseRtSvn,
             9 => FuseLogEntryId::VendorLmsPubKeyIndex,
             10 => FuseLogEntryId::VendorLmsPubKeyRevocation,
+            11 => FuseLogEntryId::VendorIntermediateEccKeyIndex,
             _ => FuseLogEntryId::Invalid,
         }
     }
@@ -0,0 +1,201 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    fw_update_journal.rs
+
+Abstract:
+
+    Journal of impactless runtime firmware updates.
+
+--*/
+
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+pub const FW_UPDATE_JOURNAL_MAX_COUNT: usize = 8;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct FwUpdateJournalEntry {
+    /// SVN of the runtime firmware that requested the update
+    pub old_svn: u32,
+
+    /// SVN of the runtime firmware that was loaded
+    pub new_svn: u32,
+
+    /// Digest of the runtime firmware that was loaded
+    pub digest: [u32; 12],
+
+    /// SoC counter value sampled when the update was requested
+    pub timestamp: u64,
+}
+
+/// Ring buffer of the most recent firmware updates since cold reset.
+///
+/// An entry is started by the runtime before it triggers the update reset
+/// and is completed by the new runtime once the update reset succeeds.
+#[repr(C)]
+#[derive(Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct FwUpdateJournal {
+    /// Number of updates recorded since cold reset
+    total: u32,
+
+    /// Non-zero while an update is in flight
+    pending: u32,
+
+    pending_entry: FwUpdateJournalEntry,
+
+    entries: [FwUpdateJournalEntry; FW_UPDATE_JOURNAL_MAX_COUNT],
+
+    /// Mailbox command executing when the runtime staged the image of the
+    /// pending update in the mailbox, or 0 if the image came with
+    /// FIRMWARE_LOAD
+    staged_cmd: u32,
+}
+
+impl FwUpdateJournal {
+    /// Record the start of an update.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_svn` - SVN of the running firmware
+    /// * `timestamp` - SoC counter value
+    pub fn begin(&mut self, old_svn: u32, timestamp: u64) {
+        self.pending_entry = FwUpdateJournalEntry {
+            old_svn,
+            timestamp,
+            ..Default::default()
+        };
+        self.pending = 1;
+        self.staged_cmd = 0;
+    }
+
+    /// Record that the runtime staged the image of the pending update in
+    /// the mailbox while executing command `cmd`, so that ROM accepts it in
+    /// place of FIRMWARE_LOAD.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - Mailbox command left executing across the update reset
+    pub fn set_staged_cmd(&mut self, cmd: u32) {
+        if self.pending != 0 {
+            self.staged_cmd = cmd;
+        }
+    }
+
+    /// Mailbox command that carries the staged image of the pending update,
+    /// if any.
+    pub fn staged_cmd(&self) -> Option<u32> {
+        (self.pending != 0 && self.staged_cmd != 0).then_some(self.staged_cmd)
+    }
+
+    /// Complete the pending update, if any, and append it to the journal.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_svn` - SVN of the loaded firmware
+    /// * `digest` - Digest of the loaded firmware
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `false` if no update was pending
+    pub fn complete(&mut self, new_svn: u32, digest: [u32; 12]) -> bool {
+        if self.pending == 0 {
+            return false;
+        }
+
+        let idx = self.total as usize % FW_UPDATE_JOURNAL_MAX_COUNT;
+        self.entries[idx] = FwUpdateJournalEntry {
+            new_svn,
+            digest,
+            ..self.pending_entry
+        };
+        self.total = self.total.wrapping_add(1);
+        self.discard_pending();
+        true
+    }
+
+    /// Drop a pending update that never completed.
+    pub fn discard_pending(&mut self) {
+        self.pending = 0;
+        self.pending_entry = FwUpdateJournalEntry::default();
+        self.staged_cmd = 0;
+    }
+
+    /// Number of updates recorded since cold reset, including those that
+    /// have since been overwritten.
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Iterate over the retained entries, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &FwUpdateJournalEntry> {
+        let count = (self.total as usize).min(FW_UPDATE_JOURNAL_MAX_COUNT);
+        let start = (self.total as usize).wrapping_sub(count);
+        (start..start.wrapping_add(count)).map(|i| &self.entries[i % FW_UPDATE_JOURNAL_MAX_COUNT])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_without_begin() {
+        let mut journal = FwUpdateJournal::default();
+        assert!(!journal.complete(1, [0; 12]));
+        assert_eq!(journal.total(), 0);
+        assert_eq!(journal.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_begin_complete() {
+        let mut journal = FwUpdateJournal::default();
+        journal.begin(1, 0x1_0000_0002);
+        assert!(journal.complete(2, [3; 12]));
+        assert!(!journal.complete(2, [3; 12]));
+
+        let mut entries = journal.iter();
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.old_svn, 1);
+        assert_eq!(entry.new_svn, 2);
+        assert_eq!(entry.digest, [3; 12]);
+        assert_eq!(entry.timestamp, 0x1_0000_0002);
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn test_staged_cmd() {
+        let mut journal = FwUpdateJournal::default();
+        journal.set_staged_cmd(0x4D43_5450);
+        assert_eq!(journal.staged_cmd(), None);
+
+        journal.begin(1, 0);
+        assert_eq!(journal.staged_cmd(), None);
+        journal.set_staged_cmd(0x4D43_5450);
+        assert_eq!(journal.staged_cmd(), Some(0x4D43_5450));
+        assert!(journal.complete(2, [0; 12]));
+        assert_eq!(journal.staged_cmd(), None);
+
+        // A new update does not inherit the command
+        journal.begin(2, 0);
+        assert_eq!(journal.staged_cmd(), None);
+    }
+
+    #[test]
+    fn test_wraparound() {
+        let mut journal = FwUpdateJournal::default();
+        for i in 0..FW_UPDATE_JOURNAL_MAX_COUNT as u32 + 3 {
+            journal.begin(i, i.into());
+            journal.complete(i + 1, [i; 12]);
+        }
+        assert_eq!(journal.total(), FW_UPDATE_JOURNAL_MAX_COUNT as u32 + 3);
+
+        assert!(journal
+            .iter()
+            .map(|e| e.old_svn)
+            .eq(3..FW_UPDATE_JOURNAL_MAX_COUNT as u32 + 3));
+    }
+}
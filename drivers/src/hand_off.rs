@@ -3,8 +3,9 @@
 use crate::bounded_address::RomAddr;
 use crate::soc_ifc;
 use crate::{
-    ColdResetEntry4, ColdResetEntry48, Ecc384PubKey, Ecc384Signature, KeyId, ResetReason,
-    WarmResetEntry4, WarmResetEntry48,
+    Array4x12, CaliptraResult, ColdResetEntry4, ColdResetEntry48, Ecc384PubKey, Ecc384Signature,
+    Hmac384, HwCapabilities, KeyId, KeyReadArgs, LmsPublicKey, ResetReason, Trng, WarmResetEntry4,
+    WarmResetEntry48,
 };
 use bitfield::{bitfield_bitrange, bitfield_fields};
 use caliptra_error::CaliptraError;
@@ -16,6 +17,9 @@ use zeroize::Zeroize;
 pub const FHT_MARKER: u32 = 0x54484643;
 pub const FHT_INVALID_ADDRESS: u32 = u32::MAX;
 
+/// First FHT minor version that carries the `hw_caps` field.
+pub const FHT_HW_CAPS_MINOR_VERSION: u16 = 1;
+
 #[repr(C)]
 #[derive(IntoBytes, Immutable, KnownLayout, Copy, Clone, Debug, FromBytes, PartialEq, Zeroize)]
 pub struct HandOffDataHandle(pub u32);
@@ -296,12 +300,34 @@ pub struct FirmwareHandoffTable {
     #[cfg(any(feature = "fmc", feature = "runtime"))]
     pub rt_hash_chain_kv_hdl: HandOffDataHandle,
 
-    /// Reserved for future use.
-    #[cfg(any(feature = "fmc", feature = "runtime"))]
-    pub reserved: [u8; 1636],
-
+    /// Space for the RT hash chain fields, which are not visible to ROM.
     #[cfg(not(any(feature = "fmc", feature = "runtime")))]
-    pub reserved: [u8; 1642],
+    pub rt_hash_chain_rsvd: [u8; 6],
+
+    /// Hardware capabilities probed by ROM. Valid from FHT version 1.1.
+    pub hw_caps: u32,
+
+    /// Number of SHA accelerator lock polls that found the lock held by the
+    /// SoC while ROM loaded the firmware.
+    pub rom_sha_acc_lock_contention: u32,
+
+    /// WDT1 period FMC armed for each of its boot phases, in cycles.
+    pub fmc_wdt_budget_cycles: u32,
+
+    /// Longest FMC boot phase observed against `fmc_wdt_budget_cycles`, in
+    /// cycles.
+    pub fmc_wdt_max_phase_cycles: u32,
+
+    /// RtAlias LMS public key. All zeroes if FMC did not generate one.
+    #[zeroize(skip)]
+    pub rt_dice_lms_pub_key: LmsPublicKey<6>,
+
+    /// HMAC384 over every other field of the table, keyed by the FHT MAC key
+    /// derived by FMC.
+    pub fht_mac: [u32; 12],
+
+    /// Reserved for future use.
+    pub reserved: [u8; 1524],
 }
 
 impl Default for FirmwareHandoffTable {
@@ -347,11 +373,17 @@ impl Default for FirmwareHandoffTable {
             rt_hash_chain_max_svn: 0,
             #[cfg(any(feature = "fmc", feature = "runtime"))]
             rt_hash_chain_kv_hdl: HandOffDataHandle(0),
-            #[cfg(any(feature = "fmc", feature = "runtime"))]
-            reserved: [0u8; 1636],
 
             #[cfg(not(any(feature = "fmc", feature = "runtime")))]
-            reserved: [0u8; 1642],
+            rt_hash_chain_rsvd: [0u8; 6],
+
+            hw_caps: 0,
+            rom_sha_acc_lock_contention: 0,
+            fmc_wdt_budget_cycles: 0,
+            fmc_wdt_max_phase_cycles: 0,
+            rt_dice_lms_pub_key: LmsPublicKey::default(),
+            fht_mac: [0u32; 12],
+            reserved: [0u8; 1524],
         }
     }
 }
@@ -422,9 +454,55 @@ pub fn print_fht(fht: &FirmwareHandoffTable) {
     crate::cprintln!("Measurement log Address: {}", fht.meas_log_addr);
     crate::cprintln!("Measurement log Index: {}", fht.meas_log_index);
     crate::cprintln!("Fuse log Address: 0x{:08x}", fht.fuse_log_addr);
+    crate::cprintln!("HW Capabilities: 0x{:08x}", fht.hw_caps);
+    crate::cprintln!(
+        "ROM SHA Acc Lock Contention: {}",
+        fht.rom_sha_acc_lock_contention
+    );
+    crate::cprintln!(
+        "FMC WDT Max Phase: {} / {} cycles",
+        fht.fmc_wdt_max_phase_cycles,
+        fht.fmc_wdt_budget_cycles
+    );
 }
 
 impl FirmwareHandoffTable {
+    /// Hardware capabilities recorded by ROM, or `None` if the table was
+    /// populated by a ROM that predates the `hw_caps` field.
+    pub fn hw_capabilities(&self) -> Option<HwCapabilities> {
+        if self.fht_minor_ver >= FHT_HW_CAPS_MINOR_VERSION {
+            Some(HwCapabilities::from_bits_truncate(self.hw_caps))
+        } else {
+            None
+        }
+    }
+
+    /// Compute the HMAC384 of every field of the table except `fht_mac`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hmac` - HMAC384 driver
+    /// * `trng` - TRNG driver
+    /// * `key` - Key vault slot holding the FHT MAC key
+    pub fn compute_mac(
+        &self,
+        hmac: &mut Hmac384,
+        trng: &mut Trng,
+        key: KeyId,
+    ) -> CaliptraResult<[u32; 12]> {
+        let bytes = self.as_bytes();
+        let head = &bytes[..bytes.len()
+            - core::mem::size_of_val(&self.fht_mac)
+            - core::mem::size_of_val(&self.reserved)];
+
+        let mut mac = Array4x12::default();
+        let mut hmac_op = hmac.hmac_init(&KeyReadArgs::new(key).into(), trng, (&mut mac).into())?;
+        hmac_op.update(head)?;
+        hmac_op.update(&self.reserved)?;
+        hmac_op.finalize()?;
+        Ok(mac.into())
+    }
+
     /// Perform validity check of the table's data.
     /// The fields below should have been populated by ROM with
     /// valid data before it transfers control to mutable code.
@@ -516,6 +594,19 @@ mod tests {
         assert_eq!(FHT_SIZE, mem::size_of::<FirmwareHandoffTable>());
     }
 
+    #[test]
+    fn test_fht_hw_capabilities() {
+        let mut fht = crate::hand_off::FirmwareHandoffTable {
+            fht_major_ver: 1,
+            hw_caps: HwCapabilities::SHA256_WNTZ.bits(),
+            ..Default::default()
+        };
+        assert_eq!(fht.hw_capabilities(), None);
+
+        fht.fht_minor_ver = FHT_HW_CAPS_MINOR_VERSION;
+        assert_eq!(fht.hw_capabilities(), Some(HwCapabilities::SHA256_WNTZ));
+    }
+
     #[test]
     fn test_dv_nonsticky_384bit_set() {
         let fht = crate::hand_off::FirmwareHandoffTable {
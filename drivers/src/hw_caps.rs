@@ -0,0 +1,65 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    hw_caps.rs
+
+Abstract:
+
+    Optional hardware features that differ between Caliptra core revisions.
+
+--*/
+
+bitflags::bitflags! {
+    /// Hardware capabilities
+    #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct HwCapabilities : u32 {
+        /// SHA256 engine can accelerate LMS Winternitz hash chains
+        const SHA256_WNTZ = 0x01;
+        /// Internal TRNG is enabled
+        const INTERNAL_TRNG = 0x02;
+    }
+}
+
+impl HwCapabilities {
+    /// Derive the capabilities from the hardware revision and configuration
+    /// registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `cptra_generation` - `CPTRA_HW_REV_ID.CPTRA_GENERATION`; the low
+    ///   nibble holds the major revision and the next nibble the minor revision
+    /// * `internal_trng` - `CPTRA_HW_CONFIG.iTRNG_en`
+    pub fn from_hw_rev(cptra_generation: u32, internal_trng: bool) -> Self {
+        let major = cptra_generation & 0xf;
+        let minor = (cptra_generation >> 4) & 0xf;
+
+        let mut caps = Self::empty();
+        // Winternitz acceleration was added in RTL 1.1.
+        caps.set(Self::SHA256_WNTZ, (major, minor) >= (1, 1));
+        caps.set(Self::INTERNAL_TRNG, internal_trng);
+        caps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_hw_rev() {
+        assert_eq!(
+            HwCapabilities::from_hw_rev(0x01, false),
+            HwCapabilities::empty()
+        );
+        assert_eq!(
+            HwCapabilities::from_hw_rev(0x11, false),
+            HwCapabilities::SHA256_WNTZ
+        );
+        assert_eq!(
+            HwCapabilities::from_hw_rev(0x02, true),
+            HwCapabilities::SHA256_WNTZ | HwCapabilities::INTERNAL_TRNG
+        );
+    }
+}
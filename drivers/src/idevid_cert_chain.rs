@@ -0,0 +1,153 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    idevid_cert_chain.rs
+
+Abstract:
+
+    Externally-provided IDevID certificate chain.
+
+--*/
+
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+pub const IDEVID_CERT_CHAIN_MAX_COUNT: usize = 4;
+pub const IDEVID_CERT_CHAIN_MAX_SIZE: usize = 1520;
+
+/// Certificates provided by the SoC that chain the LDevID certificate up to
+/// a CA root, e.g. the IDevID certificate, an intermediate and the root.
+///
+/// Certificates are added closest-to-LDevID first and stored in chain
+/// order, so the most recently added certificate is at the start of the
+/// chain.
+#[repr(C)]
+#[derive(IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct IdevIdCertChain {
+    /// Number of certificates in the chain
+    count: u32,
+
+    /// Size of each certificate, in chain order
+    sizes: [u16; IDEVID_CERT_CHAIN_MAX_COUNT],
+
+    /// DER-encoded certificates, in chain order
+    data: [u8; IDEVID_CERT_CHAIN_MAX_SIZE],
+}
+
+impl Default for IdevIdCertChain {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sizes: [0; IDEVID_CERT_CHAIN_MAX_COUNT],
+            data: [0; IDEVID_CERT_CHAIN_MAX_SIZE],
+        }
+    }
+}
+
+impl IdevIdCertChain {
+    /// Number of certificates in the chain
+    pub fn count(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Get the DER-encoded chain.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&[u8]>` - The chain, or `None` if the chain is corrupted
+    pub fn as_slice(&self) -> Option<&[u8]> {
+        let sizes = self.sizes.get(..self.count())?;
+        let len = sizes.iter().map(|&size| size as usize).sum();
+        self.data.get(..len)
+    }
+
+    /// Get the first certificate of the chain, which is the most recently
+    /// added one.
+    pub fn first(&self) -> Option<&[u8]> {
+        if self.count == 0 {
+            return None;
+        }
+        self.data.get(..self.sizes[0] as usize)
+    }
+
+    /// Add a certificate to the start of the chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `cert` - DER-encoded certificate
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `false` if the certificate does not fit in the chain
+    pub fn prepend(&mut self, cert: &[u8]) -> bool {
+        let count = self.count();
+        if count >= IDEVID_CERT_CHAIN_MAX_COUNT {
+            return false;
+        }
+        let Some(len) = self.as_slice().map(|chain| chain.len()) else {
+            return false;
+        };
+        if cert.len() > IDEVID_CERT_CHAIN_MAX_SIZE - len {
+            return false;
+        }
+
+        self.data.copy_within(..len, cert.len());
+        self.data[..cert.len()].copy_from_slice(cert);
+        self.sizes.copy_within(..count, 1);
+        self.sizes[0] = cert.len() as u16;
+        self.count += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let chain = IdevIdCertChain::default();
+        assert_eq!(chain.count(), 0);
+        assert_eq!(chain.as_slice(), Some(&[][..]));
+        assert_eq!(chain.first(), None);
+    }
+
+    #[test]
+    fn test_prepend() {
+        let mut chain = IdevIdCertChain::default();
+        assert!(chain.prepend(&[1, 1, 1]));
+        assert!(chain.prepend(&[2, 2]));
+        assert!(chain.prepend(&[3]));
+
+        assert_eq!(chain.count(), 3);
+        assert_eq!(chain.first(), Some(&[3][..]));
+        assert_eq!(chain.as_slice(), Some(&[3, 2, 2, 1, 1, 1][..]));
+    }
+
+    #[test]
+    fn test_prepend_limits() {
+        let mut chain = IdevIdCertChain::default();
+        assert!(!chain.prepend(&[0; IDEVID_CERT_CHAIN_MAX_SIZE + 1]));
+        assert!(chain.prepend(&[0; IDEVID_CERT_CHAIN_MAX_SIZE - 3]));
+        assert!(!chain.prepend(&[0; 4]));
+        for _ in 1..IDEVID_CERT_CHAIN_MAX_COUNT {
+            assert!(chain.prepend(&[1]));
+        }
+        assert!(!chain.prepend(&[1]));
+        assert_eq!(chain.count(), IDEVID_CERT_CHAIN_MAX_COUNT);
+        assert_eq!(
+            chain.as_slice().map(|c| c.len()),
+            Some(IDEVID_CERT_CHAIN_MAX_SIZE)
+        );
+    }
+
+    #[test]
+    fn test_corrupted() {
+        let mut chain = IdevIdCertChain::default();
+        chain.count = IDEVID_CERT_CHAIN_MAX_COUNT as u32 + 1;
+        assert_eq!(chain.as_slice(), None);
+        assert!(!chain.prepend(&[1]));
+    }
+}
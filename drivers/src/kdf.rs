@@ -0,0 +1,143 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    kdf.rs
+
+Abstract:
+
+    File contains the key derivation function used for DICE derivations.
+
+--*/
+
+use crate::{Hmac384, Hmac384Key, Hmac384Tag, KeyId, KeyReadArgs, KeyUsage, KeyWriteArgs, Trng};
+#[cfg(not(feature = "no-cfi"))]
+use caliptra_cfi_derive::cfi_impl_fn;
+use caliptra_error::CaliptraResult;
+
+/// Label for deriving the IDevID CDI from the UDS
+pub const KDF_LABEL_IDEVID_CDI: &[u8] = b"idevid_cdi";
+
+/// Label for deriving the IDevID key generation seed
+pub const KDF_LABEL_IDEVID_KEYGEN: &[u8] = b"idevid_keygen";
+
+/// Label for deriving the LDevID key generation seed
+pub const KDF_LABEL_LDEVID_KEYGEN: &[u8] = b"ldevid_keygen";
+
+/// Label for deriving the FMC alias CDI
+pub const KDF_LABEL_FMC_ALIAS_CDI: &[u8] = b"fmc_alias_cdi";
+
+/// Label for deriving the FMC alias key generation seed
+pub const KDF_LABEL_FMC_ALIAS_KEYGEN: &[u8] = b"fmc_alias_keygen";
+
+/// Label for deriving the RT alias CDI
+pub const KDF_LABEL_RT_ALIAS_CDI: &[u8] = b"rt_alias_cdi";
+
+/// Label for deriving the RT alias key generation seed
+pub const KDF_LABEL_RT_ALIAS_KEYGEN: &[u8] = b"rt_alias_keygen";
+
+/// Label for deriving the RT alias LMS key seed and identifier
+pub const KDF_LABEL_RT_ALIAS_LMS_KEYGEN: &[u8] = b"rt_alias_lms_keygen";
+
+/// Label for re-keying the RT alias CDI when FMC rotates the RT alias keys
+pub const KDF_LABEL_RT_ALIAS_REKEY: &[u8] = b"rt_alias_rekey";
+
+/// Label for deriving DPE CDIs from the RT alias CDI
+pub const KDF_LABEL_DPE_CDI: &[u8] = b"derive_cdi";
+
+/// Label for deriving the FMC alias CDI of a break-glass image, which replaces
+/// the LDevID CDI with a known value
+pub const KDF_LABEL_ZERO_FMC_CDI: &[u8] = b"zero_fmc_cdi";
+
+/// Label for deriving the RT CDI that replaces the real one when attestation
+/// is disabled
+pub const KDF_LABEL_ZERO_RT_CDI: &[u8] = b"zero_rt_cdi";
+
+/// Label for deriving the key FMC uses to MAC the firmware handoff table
+pub const KDF_LABEL_FHT_MAC: &[u8] = b"fht_mac";
+
+/// SP 800-108 KDF in counter mode with HMAC-SHA384 as the PRF.
+///
+/// Every derivation produces a single 384-bit PRF block, so the counter is
+/// always 1. The fixed input data is the label, followed by a NULL byte and
+/// the context if one is provided.
+pub struct Kdf<'a> {
+    hmac: &'a mut Hmac384,
+    trng: &'a mut Trng,
+}
+
+impl<'a> Kdf<'a> {
+    pub fn new(hmac: &'a mut Hmac384, trng: &'a mut Trng) -> Self {
+        Self { hmac, trng }
+    }
+
+    /// Derive a key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - HMAC384 key
+    /// * `label` - Label for the KDF. If `context` is omitted, this is considered
+    ///             the fixed input data.
+    /// * `context` - Context for KDF. If present, a NULL byte is included between
+    ///               the label and context.
+    /// * `output` - Location to store the output
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn derive(
+        &mut self,
+        key: Hmac384Key,
+        label: &[u8],
+        context: Option<&[u8]>,
+        output: Hmac384Tag,
+    ) -> CaliptraResult<()> {
+        #[cfg(feature = "fips-test-hooks")]
+        unsafe {
+            crate::FipsTestHook::error_if_hook_set(crate::FipsTestHook::HMAC384_FAILURE)?
+        }
+
+        let mut hmac_op = self.hmac.hmac_init(&key, self.trng, output)?;
+
+        hmac_op.update(&1_u32.to_be_bytes())?;
+        hmac_op.update(label)?;
+
+        if let Some(context) = context {
+            hmac_op.update(&[0x00])?;
+            hmac_op.update(context)?;
+        }
+
+        hmac_op.finalize()
+    }
+
+    /// Derive a CDI or key generation seed from a key vault slot into a key
+    /// vault slot. The output slot is marked as a valid HMAC key and ECC key
+    /// generation seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key slot holding the HMAC384 key
+    /// * `label` - Label for the KDF
+    /// * `context` - Context for the KDF
+    /// * `output` - Key slot to store the output
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn derive_cdi(
+        &mut self,
+        key: KeyId,
+        label: &[u8],
+        context: Option<&[u8]>,
+        output: KeyId,
+    ) -> CaliptraResult<()> {
+        self.derive(
+            KeyReadArgs::new(key).into(),
+            label,
+            context,
+            KeyWriteArgs::new(
+                output,
+                KeyUsage::default()
+                    .set_hmac_key_en()
+                    .set_ecc_key_gen_seed_en(),
+            )
+            .into(),
+        )
+    }
+}
@@ -14,7 +14,7 @@ Abstract:
 
 use bitfield::bitfield;
 
-use crate::{CaliptraError, CaliptraResult};
+use crate::{CaliptraError, CaliptraResult, Kdf};
 use caliptra_registers::kv::KvReg;
 
 /// Key Identifier
@@ -168,6 +168,94 @@ impl KeyUsage {
     }
 }
 
+/// Number of slots in the key vault
+pub const KEY_VAULT_SLOTS: usize = 32;
+
+const KEY_IDS: [KeyId; KEY_VAULT_SLOTS] = [
+    KeyId::KeyId0,
+    KeyId::KeyId1,
+    KeyId::KeyId2,
+    KeyId::KeyId3,
+    KeyId::KeyId4,
+    KeyId::KeyId5,
+    KeyId::KeyId6,
+    KeyId::KeyId7,
+    KeyId::KeyId8,
+    KeyId::KeyId9,
+    KeyId::KeyId10,
+    KeyId::KeyId11,
+    KeyId::KeyId12,
+    KeyId::KeyId13,
+    KeyId::KeyId14,
+    KeyId::KeyId15,
+    KeyId::KeyId16,
+    KeyId::KeyId17,
+    KeyId::KeyId18,
+    KeyId::KeyId19,
+    KeyId::KeyId20,
+    KeyId::KeyId21,
+    KeyId::KeyId22,
+    KeyId::KeyId23,
+    KeyId::KeyId24,
+    KeyId::KeyId25,
+    KeyId::KeyId26,
+    KeyId::KeyId27,
+    KeyId::KeyId28,
+    KeyId::KeyId29,
+    KeyId::KeyId30,
+    KeyId::KeyId31,
+];
+
+/// Kind of material held by a key vault slot, inferred from its usage bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum KeySlotKind {
+    /// Slot holds no key
+    Empty = 0,
+
+    /// HMAC key or ECC key generation seed, such as a CDI
+    Cdi = 1,
+
+    /// ECC private key
+    PrivateKey = 2,
+
+    /// Intermediate data usable only as HMAC, SHA or ECC input
+    Ephemeral = 3,
+}
+
+/// Occupancy and lock state of a key vault slot
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeySlotStatus {
+    /// Usage bits of the key held by the slot
+    pub usage: KeyUsage,
+
+    /// Flag indicating if the slot is use locked
+    pub use_lock: bool,
+
+    /// Flag indicating if the slot is write locked
+    pub write_lock: bool,
+}
+
+impl KeySlotStatus {
+    /// Returns true if the slot holds a key
+    pub fn occupied(&self) -> bool {
+        self.usage.0 != 0
+    }
+
+    /// Kind of material held by the slot
+    pub fn kind(&self) -> KeySlotKind {
+        if !self.occupied() {
+            KeySlotKind::Empty
+        } else if self.usage.ecc_private_key() {
+            KeySlotKind::PrivateKey
+        } else if self.usage.hmac_key() || self.usage.ecc_key_gen_seed() {
+            KeySlotKind::Cdi
+        } else {
+            KeySlotKind::Ephemeral
+        }
+    }
+}
+
 /// Caliptra Key Vault
 pub struct KeyVault {
     kv: KvReg,
@@ -180,48 +268,21 @@ impl KeyVault {
     /// Erase all the keys in the key vault
     ///
     /// Note: The keys that have "use" or "write" lock set will not be erased
-    pub fn erase_all_keys(&mut self) {
-        const KEY_IDS: [KeyId; 32] = [
-            KeyId::KeyId0,
-            KeyId::KeyId1,
-            KeyId::KeyId2,
-            KeyId::KeyId3,
-            KeyId::KeyId4,
-            KeyId::KeyId5,
-            KeyId::KeyId6,
-            KeyId::KeyId7,
-            KeyId::KeyId8,
-            KeyId::KeyId9,
-            KeyId::KeyId10,
-            KeyId::KeyId11,
-            KeyId::KeyId12,
-            KeyId::KeyId13,
-            KeyId::KeyId14,
-            KeyId::KeyId15,
-            KeyId::KeyId16,
-            KeyId::KeyId17,
-            KeyId::KeyId18,
-            KeyId::KeyId19,
-            KeyId::KeyId20,
-            KeyId::KeyId21,
-            KeyId::KeyId22,
-            KeyId::KeyId23,
-            KeyId::KeyId24,
-            KeyId::KeyId25,
-            KeyId::KeyId26,
-            KeyId::KeyId27,
-            KeyId::KeyId28,
-            KeyId::KeyId29,
-            KeyId::KeyId30,
-            KeyId::KeyId31,
-        ];
-
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - Bitmap of the key slots that were skipped because they are locked
+    pub fn erase_all_keys(&mut self) -> u32 {
+        let mut locked_slots = 0;
         for id in KEY_IDS {
             if !self.key_use_lock(id) && !self.key_write_lock(id) {
                 let kv = self.kv.regs_mut();
                 kv.key_ctrl().at(id.into()).write(|w| w.clear(true));
+            } else {
+                locked_slots |= 1 << u32::from(id);
             }
         }
+        locked_slots
     }
 
     /// Erase specified key
@@ -243,6 +304,50 @@ impl KeyVault {
         Ok(())
     }
 
+    /// Retire a key by erasing it and locking the slot against further use
+    /// and writes. The locks remain set until they are cleared by a reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Key ID to retire
+    pub fn retire_key(&mut self, id: KeyId) -> CaliptraResult<()> {
+        self.erase_key(id)?;
+        self.set_key_use_lock(id);
+        self.set_key_write_lock(id);
+        Ok(())
+    }
+
+    /// Re-derive a CDI into a new slot and retire the slot it was derived
+    /// from. The old slot is only retired once the derivation succeeded, so a
+    /// failure leaves the original CDI in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `kdf` - KDF used for the derivation
+    /// * `key` - Key slot holding the CDI to re-key
+    /// * `label` - Label for the KDF
+    /// * `context` - Context for the KDF
+    /// * `output` - Key slot to store the new CDI
+    pub fn rekey_cdi(
+        &mut self,
+        kdf: &mut Kdf,
+        key: KeyId,
+        label: &[u8],
+        context: Option<&[u8]>,
+        output: KeyId,
+    ) -> CaliptraResult<()> {
+        if key == output {
+            return Err(CaliptraError::DRIVER_KV_REKEY_SAME_SLOT);
+        }
+
+        if self.key_use_lock(output) || self.key_write_lock(output) {
+            return Err(CaliptraError::DRIVER_KV_REKEY_OUTPUT_LOCKED);
+        }
+
+        kdf.derive_cdi(key, label, context, output)?;
+        self.retire_key(key)
+    }
+
     /// Retrieve the write lock status for a key
     ///
     /// # Arguments
@@ -325,6 +430,34 @@ impl KeyVault {
         KeyUsage(val.dest_valid())
     }
 
+    /// Retrieve the occupancy and lock state of a key slot
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Key ID
+    ///
+    /// # Returns
+    /// * `KeySlotStatus` - Slot status
+    pub fn slot_status(&mut self, id: KeyId) -> KeySlotStatus {
+        KeySlotStatus {
+            usage: self.key_usage(id),
+            use_lock: self.key_use_lock(id),
+            write_lock: self.key_write_lock(id),
+        }
+    }
+
+    /// Retrieve the occupancy and lock state of every key slot
+    ///
+    /// # Returns
+    /// * `[KeySlotStatus; KEY_VAULT_SLOTS]` - Slot status, indexed by Key ID
+    pub fn slot_map(&mut self) -> [KeySlotStatus; KEY_VAULT_SLOTS] {
+        let mut map = [KeySlotStatus::default(); KEY_VAULT_SLOTS];
+        for (slot, id) in map.iter_mut().zip(KEY_IDS) {
+            *slot = self.slot_status(id);
+        }
+        map
+    }
+
     /// Erase the key vault
     /// This is useful to call from a fatal-error-handling routine.
     ///
@@ -335,6 +468,6 @@ impl KeyVault {
     ///
     /// This function is safe to call from a trap handler.
     pub unsafe fn zeroize() {
-        KeyVault::new(unsafe { KvReg::new() }).erase_all_keys()
+        KeyVault::new(unsafe { KvReg::new() }).erase_all_keys();
     }
 }
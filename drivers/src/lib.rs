@@ -18,10 +18,14 @@ mod array;
 mod array_concat;
 mod wait;
 
+pub mod boot_profile;
 mod bounded_address;
+pub mod cert_dist_points;
+pub mod crash_record;
 mod csrng;
 mod data_vault;
 mod doe;
+mod ecc256_sw;
 mod ecc384;
 mod error_reporter;
 mod exit_ctrl;
@@ -29,13 +33,17 @@ mod exit_ctrl;
 pub mod fips_test_hooks;
 mod fuse_bank;
 pub mod fuse_log;
+pub mod fw_update_journal;
 pub mod hand_off;
 mod hmac384;
-mod hmac384_kdf;
+mod hw_caps;
+pub mod idevid_cert_chain;
+mod kdf;
 mod key_vault;
 mod kv_access;
 mod lms;
 mod mailbox;
+mod mem_ecc;
 pub mod memory_layout;
 mod okref;
 mod pcr_bank;
@@ -44,23 +52,41 @@ pub mod pcr_reset;
 mod persistent;
 pub mod pic;
 pub mod printer;
+pub mod revoked_localities;
 mod sha1;
 mod sha256;
 mod sha2_512_384acc;
+mod sha3;
 mod sha384;
+mod sha384_sw;
 mod soc_ifc;
+pub mod suspend_checkpoint;
 mod trng;
 mod trng_ext;
 
 pub use array::{Array4x12, Array4x16, Array4x4, Array4x5, Array4x8, Array4xN};
 pub use array_concat::array_concat3;
-pub use bounded_address::{BoundedAddr, MemBounds, RomAddr};
+pub use boot_profile::{BootCheckpoint, BootProfile};
+pub use bounded_address::{
+    BoundedAddr, BoundedRange, BoundedSlice, DccmBounds, DccmRange, DccmSlice, IccmAddr,
+    IccmBounds, IccmRange, IccmSlice, MemBounds, RomAddr,
+};
 pub use caliptra_error::{CaliptraError, CaliptraResult};
-pub use csrng::{Csrng, HealthFailCounts as CsrngHealthFailCounts, Seed as CsrngSeed};
+pub use caliptra_lms_types::{LmotsAlgorithmType, LmsAlgorithmType, LmsIdentifier, LmsPublicKey};
+pub use cert_dist_points::{CertDistPoints, CERT_DIST_POINT_URI_MAX_SIZE};
+pub use crash_record::{CrashRecord, CRASH_RECORD_MARKER};
+pub use csrng::{
+    Csrng, HealthFailCounts as CsrngHealthFailCounts, HealthTestStats as CsrngHealthTestStats,
+    Seed as CsrngSeed,
+};
 pub use data_vault::{
-    ColdResetEntry4, ColdResetEntry48, DataVault, WarmResetEntry4, WarmResetEntry48,
+    dv, ColdResetEntry4, ColdResetEntry48, DataVault, DvBank, DvEntry, DvValue, WarmResetEntry4,
+    WarmResetEntry48, MONOTONIC_COUNTER_ENTRIES,
 };
 pub use doe::DeobfuscationEngine;
+pub use ecc256_sw::{
+    Ecc256PrivKey, Ecc256PubKey, Ecc256Result, Ecc256Scalar, Ecc256Signature, Ecc256Sw,
+};
 pub use ecc384::{
     Ecc384, Ecc384PrivKeyIn, Ecc384PrivKeyOut, Ecc384PubKey, Ecc384Result, Ecc384Scalar,
     Ecc384Seed, Ecc384Signature,
@@ -72,16 +98,27 @@ pub use fips_test_hooks::FipsTestHook;
 pub use fuse_bank::{
     FuseBank, IdevidCertAttr, RomVerifyConfig, VendorPubKeyRevocation, X509KeyIdAlgo,
 };
+pub use fw_update_journal::{FwUpdateJournal, FwUpdateJournalEntry, FW_UPDATE_JOURNAL_MAX_COUNT};
 pub use hand_off::FirmwareHandoffTable;
 pub use hmac384::{Hmac384, Hmac384Data, Hmac384Key, Hmac384Op, Hmac384Tag};
-pub use hmac384_kdf::hmac384_kdf;
-pub use key_vault::{KeyId, KeyUsage, KeyVault};
+pub use hw_caps::HwCapabilities;
+pub use idevid_cert_chain::{
+    IdevIdCertChain, IDEVID_CERT_CHAIN_MAX_COUNT, IDEVID_CERT_CHAIN_MAX_SIZE,
+};
+pub use kdf::{
+    Kdf, KDF_LABEL_DPE_CDI, KDF_LABEL_FHT_MAC, KDF_LABEL_FMC_ALIAS_CDI, KDF_LABEL_FMC_ALIAS_KEYGEN,
+    KDF_LABEL_IDEVID_CDI, KDF_LABEL_IDEVID_KEYGEN, KDF_LABEL_LDEVID_KEYGEN, KDF_LABEL_RT_ALIAS_CDI,
+    KDF_LABEL_RT_ALIAS_KEYGEN, KDF_LABEL_RT_ALIAS_LMS_KEYGEN, KDF_LABEL_RT_ALIAS_REKEY,
+    KDF_LABEL_ZERO_FMC_CDI, KDF_LABEL_ZERO_RT_CDI,
+};
+pub use key_vault::{KeyId, KeySlotKind, KeySlotStatus, KeyUsage, KeyVault, KEY_VAULT_SLOTS};
 pub use kv_access::{KeyReadArgs, KeyWriteArgs};
 pub use lms::{
-    get_lmots_parameters, get_lms_parameters, HashValue, Lms, LmsResult, Sha192Digest,
+    get_lmots_parameters, get_lms_parameters, HashValue, Lms, LmsResult, LmsVerifyOp, Sha192Digest,
     Sha256Digest, D_INTR, D_LEAF, D_MESG, D_PBLC,
 };
 pub use mailbox::{Mailbox, MailboxRecvTxn, MailboxSendTxn};
+pub use mem_ecc::{MemEcc, MemEccCounts, MEM_ECC_SCRUB_CHUNK_SIZE};
 pub use okref::okmutref;
 pub use okref::okref;
 pub use pcr_bank::{PcrBank, PcrId};
@@ -92,15 +129,27 @@ pub use persistent::AuthManifestImageMetadataList;
 
 pub use persistent::{
     FuseLogArray, IdevIdCsr, PcrLogArray, PersistentData, PersistentDataAccessor,
-    StashMeasurementArray, FUSE_LOG_MAX_COUNT, MAX_CSR_SIZE, MEASUREMENT_MAX_COUNT,
-    PCR_LOG_MAX_COUNT,
+    PersistentDataHeader, PersistentDataSection, StashMeasurementArray, FUSE_LOG_MAX_COUNT,
+    MAX_CSR_SIZE, MEASUREMENT_MAX_COUNT, PCR_LOG_MAX_COUNT, PERSISTENT_DATA_VERSION,
+    RTALIAS_TBS_SIZE,
 };
 pub use pic::{IntSource, Pic};
+pub use revoked_localities::{RevokedLocalities, MAX_REVOKED_LOCALITIES};
 pub use sha1::{Sha1, Sha1Digest, Sha1DigestOp};
 pub use sha256::{Sha256, Sha256Alg, Sha256DigestOp};
-pub use sha2_512_384acc::{Sha2_512_384Acc, Sha2_512_384AccOp, ShaAccLockState};
+pub use sha2_512_384acc::{
+    Sha2_512_384Acc, Sha2_512_384AccDetachedStream, Sha2_512_384AccOp, Sha2_512_384AccStream,
+    ShaAccLockState,
+};
+pub use sha3::{Sha3, Sha3Mode, SHA3_384_DIGEST_SIZE};
 pub use sha384::{Sha384, Sha384Digest, Sha384DigestOp};
-pub use soc_ifc::{report_boot_status, Lifecycle, MfgFlags, ResetReason, SocIfc};
+pub use sha384_sw::{Sha384Sw, SHA384_SW_DIGEST_SIZE};
+pub use soc_ifc::{
+    report_boot_status, restart_wdt1, Lifecycle, MfgFlags, ResetReason, SocIfc,
+    FIELD_ENTROPY_RESEED_REQUEST, FIELD_ENTROPY_RESEED_REQUEST_WIRE, MBOX_CANCEL_REQUEST,
+    MBOX_CANCEL_REQUEST_REG, RUNTIME_SVN_FUSE_REQUEST_VALID, RUNTIME_SVN_FUSE_REQUEST_WIRE,
+};
+pub use suspend_checkpoint::SuspendCheckpoint;
 pub use trng::Trng;
 
 #[allow(unused_imports)]
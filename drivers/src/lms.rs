@@ -29,6 +29,10 @@ pub const D_MESG: u16 = 0x8181;
 pub const D_LEAF: u16 = 0x8282;
 pub const D_INTR: u16 = 0x8383;
 
+/// Winternitz chains computed between watchdog restarts by
+/// [`Lms::verify_lms_signature_with_wdt_pet`]
+const LMS_CHAINS_PER_WDT_PET: usize = 8;
+
 #[derive(Default, Debug)]
 pub struct Lms {
     kat_complete: bool,
@@ -309,9 +313,24 @@ impl Lms {
         Ok(HashValue::from(digest))
     }
 
+    fn hash_chain<const N: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        wnt_prefix: &mut [u8; Self::WNT_PREFIX_SIZE],
+        coeff: u8,
+        params: &LmotsParameter,
+        tmp: &mut HashValue<N>,
+    ) -> CaliptraResult<HashValue<N>> {
+        #[cfg(not(feature = "hw-1.0"))]
+        if sha256_driver.wntz_supported() {
+            return self.hash_chain_wntz(sha256_driver, wnt_prefix, coeff, params, tmp);
+        }
+        self.hash_chain_sw(sha256_driver, wnt_prefix, coeff, params, tmp)
+    }
+
     // This operation is accelerated in hardware by RTL1.1 and later.
     #[cfg(not(feature = "hw-1.0"))]
-    fn hash_chain<const N: usize>(
+    fn hash_chain_wntz<const N: usize>(
         &self,
         sha256_driver: &mut impl Sha256Alg,
         wnt_prefix: &mut [u8; Self::WNT_PREFIX_SIZE],
@@ -342,8 +361,7 @@ impl Lms {
         Ok(*tmp)
     }
 
-    #[cfg(feature = "hw-1.0")]
-    fn hash_chain<const N: usize>(
+    fn hash_chain_sw<const N: usize>(
         &self,
         sha256_driver: &mut impl Sha256Alg,
         wnt_prefix: &mut [u8; Self::WNT_PREFIX_SIZE],
@@ -378,6 +396,28 @@ impl Lms {
         y: &[[U32<LittleEndian>; N]; P],
         message_digest: &HashValue<N>,
     ) -> CaliptraResult<HashValue<N>> {
+        let params = self.ots_parameters::<N, P>(algo_type)?;
+        let message_hash_with_checksum = self.ots_coefficients(algo_type, message_digest)?;
+        let mut hash_block = Self::ots_hash_block(lms_identifier, q);
+
+        let mut z = [HashValue::<N>::default(); P];
+        for (i, val) in z.iter_mut().enumerate() {
+            *val = self.ots_chain(
+                sha256_driver,
+                &mut hash_block,
+                &message_hash_with_checksum,
+                params,
+                i,
+                &y[i],
+            )?;
+        }
+        self.ots_public_key(sha256_driver, lms_identifier, q, &z)
+    }
+
+    fn ots_parameters<const N: usize, const P: usize>(
+        &self,
+        algo_type: LmotsAlgorithmType,
+    ) -> CaliptraResult<&'static LmotsParameter> {
         let params: &LmotsParameter = get_lmots_parameters(algo_type)?;
 
         if params.p as usize != P {
@@ -389,8 +429,16 @@ impl Lms {
         if params.n as usize != N * 4 {
             return Err(CaliptraError::DRIVER_LMS_INVALID_HASH_WIDTH);
         }
-        let mut z = [HashValue::<N>::default(); P];
+        Ok(params)
+    }
 
+    /// Message digest followed by its checksum, from which the Winternitz
+    /// chain coefficients are taken.
+    fn ots_coefficients<const N: usize>(
+        &self,
+        algo_type: LmotsAlgorithmType,
+        message_digest: &HashValue<N>,
+    ) -> CaliptraResult<[u8; 34]> {
         let mut message_hash_with_checksum = [0u8; 34]; // 2 extra bytes for the checksum. needs to be N+2
 
         let mut i = 0;
@@ -404,21 +452,43 @@ impl Lms {
         let checksum_offset = N * 4;
         message_hash_with_checksum[checksum_offset] = be_checksum[0];
         message_hash_with_checksum[checksum_offset + 1] = be_checksum[1];
+        Ok(message_hash_with_checksum)
+    }
 
-        // In order to reduce the number of copies allocate a single block of memory
-        // and update only the portions that update between iterations
-        let mut hash_block = [0u8; 55];
+    // In order to reduce the number of copies allocate a single block of memory
+    // and update only the portions that update between iterations
+    fn ots_hash_block(lms_identifier: &LmsIdentifier, q: &[u8; 4]) -> [u8; Self::WNT_PREFIX_SIZE] {
+        let mut hash_block = [0u8; Self::WNT_PREFIX_SIZE];
         hash_block[0..16].clone_from_slice(lms_identifier);
         hash_block[16..20].clone_from_slice(q);
+        hash_block
+    }
 
-        for (i, val) in z.iter_mut().enumerate() {
-            let a = self.coefficient(&message_hash_with_checksum, i, params.w as usize)?;
-            let mut tmp = HashValue::<N>::from(y[i]);
+    /// Compute the end of Winternitz chain `i`
+    fn ots_chain<const N: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        hash_block: &mut [u8; Self::WNT_PREFIX_SIZE],
+        message_hash_with_checksum: &[u8; 34],
+        params: &LmotsParameter,
+        i: usize,
+        y: &[U32<LittleEndian>; N],
+    ) -> CaliptraResult<HashValue<N>> {
+        let a = self.coefficient(message_hash_with_checksum, i, params.w as usize)?;
+        let mut tmp = HashValue::<N>::from(*y);
 
-            hash_block[20..22].clone_from_slice(&(i as u16).to_be_bytes());
+        hash_block[20..22].clone_from_slice(&(i as u16).to_be_bytes());
 
-            *val = self.hash_chain(sha256_driver, &mut hash_block, a, params, &mut tmp)?;
-        }
+        self.hash_chain(sha256_driver, hash_block, a, params, &mut tmp)
+    }
+
+    fn ots_public_key<const N: usize, const P: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        lms_identifier: &LmsIdentifier,
+        q: &[u8; 4],
+        z: &[HashValue<N>; P],
+    ) -> CaliptraResult<HashValue<N>> {
         let mut digest = Array4x8::default();
         let mut hasher = sha256_driver.digest_init()?;
         hasher.update(lms_identifier)?;
@@ -464,6 +534,47 @@ impl Lms {
         result
     }
 
+    /// Like `verify_lms_signature`, but computes the Winternitz chains in
+    /// batches and calls `wdt_pet` between them so that the verification
+    /// does not trip the watchdog.
+    pub fn verify_lms_signature_with_wdt_pet(
+        &self,
+        sha256_driver: &mut Sha256,
+        input_string: &[u8],
+        lms_public_key: &LmsPublicKey<6>,
+        lms_sig: &LmsSignature<6, 51, 15>,
+        wdt_pet: fn(),
+    ) -> CaliptraResult<LmsResult> {
+        #[cfg(feature = "fips-test-hooks")]
+        let input_string = unsafe {
+            crate::FipsTestHook::corrupt_data_if_hook_set(
+                crate::FipsTestHook::LMS_CORRUPT_INPUT,
+                &input_string,
+            )
+        };
+
+        let mut op = self.verify_lms_signature_cfi_start(
+            sha256_driver,
+            input_string,
+            lms_public_key,
+            lms_sig,
+        )?;
+        let mut candidate_key = loop {
+            if let Some(root) = op.step_cfi(sha256_driver, LMS_CHAINS_PER_WDT_PET)? {
+                break root;
+            }
+            wdt_pet();
+        };
+
+        let result = if candidate_key != HashValue::from(lms_public_key.digest) {
+            Ok(LmsResult::SigVerifyFailed)
+        } else {
+            Ok(LmsResult::Success)
+        };
+        candidate_key.0.zeroize();
+        result
+    }
+
     ///  Note: Use this function only if glitch protection is not needed.
     ///        If glitch protection is needed, use `verify_lms_signature_cfi_generic` instead.
     pub fn verify_lms_signature_generic<const N: usize, const P: usize, const H: usize>(
@@ -503,7 +614,31 @@ impl Lms {
         lms_public_key: &LmsPublicKey<6>,
         lms_sig: &LmsSignature<6, 51, 15>,
     ) -> CaliptraResult<HashValue<6>> {
-        self.verify_lms_signature_cfi_generic(sha256_driver, input_string, lms_public_key, lms_sig)
+        let mut op = self.verify_lms_signature_cfi_start(
+            sha256_driver,
+            input_string,
+            lms_public_key,
+            lms_sig,
+        )?;
+        loop {
+            if let Some(root) = op.step_cfi(sha256_driver, usize::MAX)? {
+                return Ok(root);
+            }
+        }
+    }
+
+    /// Start a resumable verification with the production parameter set.
+    /// Like `verify_lms_signature_cfi`, this is non-generic so the KAT and
+    /// production firmware share the same machine code.
+    #[inline(never)]
+    pub fn verify_lms_signature_cfi_start<'a>(
+        &'a self,
+        sha256_driver: &mut Sha256,
+        input_string: &[u8],
+        lms_public_key: &'a LmsPublicKey<6>,
+        lms_sig: &'a LmsSignature<6, 51, 15>,
+    ) -> CaliptraResult<LmsVerifyOp<'a, 6, 51, 15>> {
+        self.verify_lms_signature_start(sha256_driver, input_string, lms_public_key, lms_sig)
     }
 
     #[inline(always)]
@@ -514,6 +649,28 @@ impl Lms {
         lms_public_key: &LmsPublicKey<N>,
         lms_sig: &LmsSignature<N, P, H>,
     ) -> CaliptraResult<HashValue<N>> {
+        let mut op =
+            self.verify_lms_signature_start(sha256_driver, input_string, lms_public_key, lms_sig)?;
+        loop {
+            if let Some(root) = op.step(sha256_driver, usize::MAX)? {
+                return Ok(root);
+            }
+        }
+    }
+
+    /// Start a resumable LMS verification.
+    ///
+    /// Validates the signature parameters and hashes the message. The
+    /// Winternitz chains and the tree path are computed by
+    /// [`LmsVerifyOp::step`].
+    #[inline(always)]
+    pub fn verify_lms_signature_start<'a, const N: usize, const P: usize, const H: usize>(
+        &'a self,
+        sha256_driver: &mut impl Sha256Alg,
+        input_string: &[u8],
+        lms_public_key: &'a LmsPublicKey<N>,
+        lms_sig: &'a LmsSignature<N, P, H>,
+    ) -> CaliptraResult<LmsVerifyOp<'a, N, P, H>> {
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
             crate::FipsTestHook::error_if_hook_set(crate::FipsTestHook::LMS_VERIFY_FAILURE)?
@@ -533,26 +690,10 @@ impl Lms {
         if lms_sig.q.get() >= 1 << H {
             return Err(CaliptraError::DRIVER_LMS_INVALID_Q_VALUE);
         }
-        let mut node_num: u32 = (1 << tree_height) + lms_sig.q.get();
+        let node_num: u32 = (1 << tree_height) + lms_sig.q.get();
         if node_num >= 2 << tree_height {
             return Err(CaliptraError::DRIVER_LMS_INVALID_Q_VALUE);
         }
-        let message_digest = self.hash_message(
-            sha256_driver,
-            input_string,
-            &lms_public_key.id,
-            &q_str,
-            &lms_sig.ots.nonce,
-        )?;
-        let candidate_key = self.candidate_ots_signature(
-            sha256_driver,
-            &lms_public_key.id,
-            lms_sig.ots.ots_type,
-            &q_str,
-            &lms_sig.ots.y,
-            &message_digest,
-        )?;
-
         match tree_height {
             5 => (),
             10 => (),
@@ -562,6 +703,39 @@ impl Lms {
             _ => return Err(CaliptraError::DRIVER_LMS_INVALID_TREE_HEIGHT),
         }
 
+        let message_digest = self.hash_message(
+            sha256_driver,
+            input_string,
+            &lms_public_key.id,
+            &q_str,
+            &lms_sig.ots.nonce,
+        )?;
+        let params = self.ots_parameters::<N, P>(lms_sig.ots.ots_type)?;
+        let message_hash_with_checksum =
+            self.ots_coefficients(lms_sig.ots.ots_type, &message_digest)?;
+
+        Ok(LmsVerifyOp {
+            lms: self,
+            lms_public_key,
+            lms_sig,
+            params,
+            q_str,
+            node_num,
+            message_hash_with_checksum,
+            hash_block: Self::ots_hash_block(&lms_public_key.id, &q_str),
+            z: [HashValue::<N>::default(); P],
+            next_chain: 0,
+        })
+    }
+
+    fn lms_root<const N: usize, const P: usize, const H: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        lms_public_key: &LmsPublicKey<N>,
+        lms_sig: &LmsSignature<N, P, H>,
+        mut node_num: u32,
+        candidate_key: &HashValue<N>,
+    ) -> CaliptraResult<HashValue<N>> {
         let mut digest = Array4x8::default();
         let mut hasher = sha256_driver.digest_init()?;
         hasher.update(&lms_public_key.id)?;
@@ -610,6 +784,138 @@ impl Lms {
         Ok(temp)
     }
 
+    /// Generate an LMS public key from a seed.
+    ///
+    /// The one-time private keys are derived from `seed` using the
+    /// pseudorandom key generation method of RFC 8554 Appendix A, so the same
+    /// seed and identifier always produce the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `sha256_driver` - SHA-256 driver
+    /// * `tree_type` - LMS parameter set
+    /// * `ots_type` - LM-OTS parameter set
+    /// * `lms_identifier` - Key pair identifier `I`
+    /// * `seed` - Secret seed the private key is derived from
+    ///
+    /// # Returns
+    ///
+    /// * `LmsPublicKey` - Public key whose digest is the root of the tree
+    pub fn generate_public_key<const N: usize, const P: usize, const H: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        tree_type: LmsAlgorithmType,
+        ots_type: LmotsAlgorithmType,
+        lms_identifier: &LmsIdentifier,
+        seed: &HashValue<N>,
+    ) -> CaliptraResult<LmsPublicKey<N>> {
+        let (_, tree_height) = get_lms_parameters(tree_type)?;
+        if tree_height as usize != H {
+            return Err(CaliptraError::DRIVER_LMS_INVALID_TREE_HEIGHT);
+        }
+        let params = self.ots_parameters::<N, P>(ots_type)?;
+
+        // Nodes waiting for their right sibling, one per level at most
+        let mut stack = [HashValue::<N>::default(); H];
+        let mut depth = 0;
+
+        for q in 0..(1u32 << H) {
+            let q_str = q.to_be_bytes();
+            let mut hash_block = Self::ots_hash_block(lms_identifier, &q_str);
+
+            let mut y = [HashValue::<N>::default(); P];
+            for (i, val) in y.iter_mut().enumerate() {
+                let mut x = self.ots_private_key(sha256_driver, lms_identifier, &q_str, i, seed)?;
+                hash_block[20..22].clone_from_slice(&(i as u16).to_be_bytes());
+                *val = self.hash_chain(sha256_driver, &mut hash_block, 0, params, &mut x)?;
+                x.0.zeroize();
+            }
+            let ots_public_key = self.ots_public_key(sha256_driver, lms_identifier, &q_str, &y)?;
+
+            let mut node_num = (1u32 << H) + q;
+            let mut node = self.tree_node(
+                sha256_driver,
+                lms_identifier,
+                node_num,
+                D_LEAF,
+                &[&ots_public_key],
+            )?;
+
+            // Fold completed subtrees into their parents
+            while node_num > 1 && node_num % 2 == 1 {
+                depth -= 1;
+                node_num /= 2;
+                node = self.tree_node(
+                    sha256_driver,
+                    lms_identifier,
+                    node_num,
+                    D_INTR,
+                    &[&stack[depth], &node],
+                )?;
+            }
+            if node_num == 1 {
+                return Ok(LmsPublicKey {
+                    tree_type,
+                    otstype: ots_type,
+                    id: *lms_identifier,
+                    digest: node.0.map(|word| U32::new(word.swap_bytes())),
+                });
+            }
+            stack[depth] = node;
+            depth += 1;
+        }
+
+        Err(CaliptraError::DRIVER_LMS_INVALID_TREE_HEIGHT)
+    }
+
+    /// Derive element `i` of the one-time private key `q` from the seed:
+    /// H(I || u32str(q) || u16str(i) || u8str(0xff) || SEED)
+    fn ots_private_key<const N: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        lms_identifier: &LmsIdentifier,
+        q: &[u8; 4],
+        i: usize,
+        seed: &HashValue<N>,
+    ) -> CaliptraResult<HashValue<N>> {
+        let mut digest = Array4x8::default();
+        let mut hasher = sha256_driver.digest_init()?;
+        hasher.update(lms_identifier)?;
+        hasher.update(q)?;
+        hasher.update(&(i as u16).to_be_bytes())?;
+        hasher.update(&[0xff])?;
+        for val in seed.0.iter() {
+            hasher.update(&val.to_be_bytes())?;
+        }
+        hasher.finalize(&mut digest)?;
+        let result = HashValue::<N>::from(digest);
+        digest.0.zeroize();
+        Ok(result)
+    }
+
+    /// Hash a node of the Merkle tree: H(I || u32str(r) || u16str(d) || children)
+    fn tree_node<const N: usize>(
+        &self,
+        sha256_driver: &mut impl Sha256Alg,
+        lms_identifier: &LmsIdentifier,
+        node_num: u32,
+        domain: u16,
+        children: &[&HashValue<N>],
+    ) -> CaliptraResult<HashValue<N>> {
+        let mut digest = Array4x8::default();
+        let mut hasher = sha256_driver.digest_init()?;
+        hasher.update(lms_identifier)?;
+        hasher.update(&node_num.to_be_bytes())?;
+        hasher.update(&domain.to_be_bytes())?;
+        for child in children {
+            for val in child.0.iter() {
+                hasher.update(&val.to_be_bytes())?;
+            }
+        }
+        hasher.finalize(&mut digest)?;
+        Ok(HashValue::<N>::from(digest))
+    }
+
     // Return the kat_complete state
     pub fn kat_is_complete(&self) -> bool {
         self.kat_complete
@@ -620,3 +926,83 @@ impl Lms {
         self.kat_complete = true;
     }
 }
+
+/// Resumable LMS signature verification.
+///
+/// Long running verifications are split into batches of Winternitz chains
+/// so that the caller can service the watchdog between them.
+pub struct LmsVerifyOp<'a, const N: usize, const P: usize, const H: usize> {
+    lms: &'a Lms,
+    lms_public_key: &'a LmsPublicKey<N>,
+    lms_sig: &'a LmsSignature<N, P, H>,
+    params: &'static LmotsParameter,
+    q_str: [u8; 4],
+    node_num: u32,
+    message_hash_with_checksum: [u8; 34],
+    hash_block: [u8; Lms::WNT_PREFIX_SIZE],
+    z: [HashValue<N>; P],
+    next_chain: usize,
+}
+
+impl<const N: usize, const P: usize, const H: usize> LmsVerifyOp<'_, N, P, H> {
+    /// Compute up to `max_chains` Winternitz chains.
+    ///
+    /// # Arguments
+    ///
+    /// * `sha256_driver` - SHA-256 driver
+    /// * `max_chains` - Number of chains to compute before yielding
+    ///
+    /// # Returns
+    ///
+    /// * `None` - More chains remain; call `step` again
+    /// * `Some(root)` - Candidate LMS root, to be compared against the public key digest
+    #[inline(always)]
+    pub fn step(
+        &mut self,
+        sha256_driver: &mut impl Sha256Alg,
+        max_chains: usize,
+    ) -> CaliptraResult<Option<HashValue<N>>> {
+        let end = P.min(self.next_chain.saturating_add(max_chains));
+        for i in self.next_chain..end {
+            self.z[i] = self.lms.ots_chain(
+                sha256_driver,
+                &mut self.hash_block,
+                &self.message_hash_with_checksum,
+                self.params,
+                i,
+                &self.lms_sig.ots.y[i],
+            )?;
+        }
+        self.next_chain = end;
+        if self.next_chain < P {
+            return Ok(None);
+        }
+
+        let candidate_key = self.lms.ots_public_key(
+            sha256_driver,
+            &self.lms_public_key.id,
+            &self.q_str,
+            &self.z,
+        )?;
+        let root = self.lms.lms_root(
+            sha256_driver,
+            self.lms_public_key,
+            self.lms_sig,
+            self.node_num,
+            &candidate_key,
+        )?;
+        Ok(Some(root))
+    }
+}
+
+impl LmsVerifyOp<'_, 6, 51, 15> {
+    /// Non-generic `step` for the production parameter set.
+    #[inline(never)]
+    pub fn step_cfi(
+        &mut self,
+        sha256_driver: &mut Sha256,
+        max_chains: usize,
+    ) -> CaliptraResult<Option<HashValue<6>>> {
+        self.step(sha256_driver, max_chains)
+    }
+}
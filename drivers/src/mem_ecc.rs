@@ -0,0 +1,129 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    mem_ecc.rs
+
+Abstract:
+
+    File contains the ICCM/DCCM ECC error accounting and the DCCM scrubber.
+
+--*/
+
+use crate::{memory_layout, SocIfc};
+
+/// Number of bytes of DCCM read by each call to `MemEcc::scrub`
+pub const MEM_ECC_SCRUB_CHUNK_SIZE: u32 = 1024;
+
+/// ECC error counts for the Caliptra memories
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemEccCounts {
+    /// Correctable ICCM errors since the core last left reset
+    pub iccm_correctable: u32,
+
+    /// Correctable DCCM errors since the core last left reset
+    pub dccm_correctable: u32,
+
+    /// An uncorrectable ICCM error was reported since Caliptra last left
+    /// cold reset
+    pub iccm_uncorrectable: bool,
+
+    /// An uncorrectable DCCM error was reported since Caliptra last left
+    /// cold reset
+    pub dccm_uncorrectable: bool,
+
+    /// Correctable mailbox SRAM errors
+    pub mbox_correctable: u32,
+
+    /// Uncorrectable mailbox SRAM errors
+    pub mbox_uncorrectable: u32,
+}
+
+/// ICCM/DCCM ECC error accounting
+///
+/// The core corrects single-bit errors when a word is read and counts them
+/// in its correctable error counters. Instructions are corrected as they are
+/// fetched, but data that is not read may accumulate a second error and
+/// become uncorrectable, so `scrub` reads DCCM a chunk at a time.
+#[derive(Default)]
+pub struct MemEcc {
+    /// Offset in DCCM of the next chunk to scrub
+    scrub_offset: u32,
+
+    /// Number of complete passes over DCCM
+    scrub_passes: u32,
+}
+
+impl MemEcc {
+    /// Read the next `MEM_ECC_SCRUB_CHUNK_SIZE` bytes of DCCM
+    pub fn scrub(&mut self) {
+        let start = memory_layout::DCCM_ORG + self.scrub_offset;
+        read_words(start, MEM_ECC_SCRUB_CHUNK_SIZE);
+
+        self.scrub_offset += MEM_ECC_SCRUB_CHUNK_SIZE;
+        if self.scrub_offset >= memory_layout::DCCM_SIZE {
+            self.scrub_offset = 0;
+            self.scrub_passes = self.scrub_passes.wrapping_add(1);
+        }
+    }
+
+    /// Number of complete scrub passes over DCCM
+    pub fn scrub_passes(&self) -> u32 {
+        self.scrub_passes
+    }
+
+    /// Current ECC error counts
+    ///
+    /// # Arguments
+    ///
+    /// * `soc_ifc` - SOC Interface
+    pub fn counts(&self, soc_ifc: &SocIfc) -> MemEccCounts {
+        MemEccCounts {
+            iccm_correctable: correctable_count(Csr::Miccmect),
+            dccm_correctable: correctable_count(Csr::Mdccmect),
+            iccm_uncorrectable: soc_ifc.iccm_ecc_unc(),
+            dccm_uncorrectable: soc_ifc.dccm_ecc_unc(),
+            mbox_correctable: soc_ifc.mbox_ecc_cor_count(),
+            mbox_uncorrectable: soc_ifc.mbox_ecc_unc_count(),
+        }
+    }
+}
+
+// VeeR EL2 PRM 3.5 Correctable Error Counter Registers
+enum Csr {
+    Miccmect,
+    Mdccmect,
+}
+
+/// Errors counted by a correctable error counter, held in bits 26:0. Bits
+/// 31:27 hold the threshold that raises the correctable error interrupt,
+/// which is not enabled.
+#[cfg(target_arch = "riscv32")]
+fn correctable_count(csr: Csr) -> u32 {
+    const COUNT_MASK: u32 = (1 << 27) - 1;
+    let val: u32;
+    unsafe {
+        match csr {
+            Csr::Miccmect => core::arch::asm!("csrr {r}, 0x7f1", r = out(reg) val),
+            Csr::Mdccmect => core::arch::asm!("csrr {r}, 0x7f2", r = out(reg) val),
+        }
+    }
+    val & COUNT_MASK
+}
+
+#[cfg(not(target_arch = "riscv32"))]
+fn correctable_count(_csr: Csr) -> u32 {
+    0
+}
+
+#[cfg(target_arch = "riscv32")]
+fn read_words(start: u32, len: u32) {
+    for addr in (start..start + len).step_by(4) {
+        unsafe { core::ptr::read_volatile(addr as *const u32) };
+    }
+}
+
+#[cfg(not(target_arch = "riscv32"))]
+fn read_words(_start: u32, _len: u32) {}
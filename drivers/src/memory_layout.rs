@@ -24,9 +24,9 @@ pub const CFI_STATE_ORG: u32 = 0x500003E4; // size = 6 words
 pub const BOOT_STATUS_ORG: u32 = 0x500003FC;
 pub const PERSISTENT_DATA_ORG: u32 = 0x50000400;
 
-pub const DATA_ORG: u32 = 0x50009C00;
+pub const DATA_ORG: u32 = 0x5000A400;
 
-pub const STACK_ORG: u32 = 0x5000A400;
+pub const STACK_ORG: u32 = 0x5000AC00;
 pub const ROM_STACK_ORG: u32 = 0x5001C000;
 
 pub const ESTACK_ORG: u32 = 0x5001F800;
@@ -45,7 +45,11 @@ pub const ROM_NSTACK_ORG: u32 = 0x5001FC00;
 // reserved for future use and then allocating the rest of the DCCM.
 //
 // The `DATA_SIZE` variable reflects the leftover space.
-pub const PERSISTENT_DATA_SIZE: u32 = 38 * 1024;
+//
+// The state added after `fmc_alias_csr` outgrew the original 3K reserve, so
+// the size was raised by 2K, taken from the stack. 1K of it is kept as the
+// reserve for future objects.
+pub const PERSISTENT_DATA_SIZE: u32 = 40 * 1024;
 
 pub const ROM_RELAXATION_PADDING: u32 = 4 * 1024;
 pub const ROM_SIZE: u32 = 48 * 1024;
@@ -54,7 +58,7 @@ pub const ICCM_SIZE: u32 = 128 * 1024;
 pub const DCCM_SIZE: u32 = 128 * 1024;
 pub const ROM_DATA_SIZE: u32 = 996;
 pub const DATA_SIZE: u32 = 2 * 1024;
-pub const STACK_SIZE: u32 = 85 * 1024;
+pub const STACK_SIZE: u32 = 83 * 1024;
 pub const ROM_STACK_SIZE: u32 = 14 * 1024;
 pub const ESTACK_SIZE: u32 = 1024;
 pub const ROM_ESTACK_SIZE: u32 = 1024;
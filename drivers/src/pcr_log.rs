@@ -19,19 +19,24 @@ pub const PCR_ID_FMC_CURRENT: PcrId = PcrId::PcrId0;
 pub const PCR_ID_FMC_JOURNEY: PcrId = PcrId::PcrId1;
 pub const PCR_ID_STASH_MEASUREMENT: PcrId = PcrId::PcrId31;
 
+/// Measurement extended into the FMC PCRs when booting a break-glass image
+pub const PCR_BREAK_GLASS_MEASUREMENT: [u8; 16] = *b"CPTRA_BREAKGLASS";
+
 // PcrLogEntryId is used to identify the PCR entry and
 // the size of the data in PcrLogEntry::pcr_data.
 #[repr(u16)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PcrLogEntryId {
     Invalid = 0,
-    DeviceStatus = 1,     // data size = 9 bytes
-    VendorPubKeyHash = 2, // data size = 48 bytes
-    OwnerPubKeyHash = 3,  // data size = 48 bytes
-    FmcTci = 4,           // data size = 48 bytes
-    StashMeasurement = 5, // data size = 48 bytes
-    RtTci = 6,            // data size = 48 bytes
-    FwImageManifest = 7,  // data size = 48 bytes
+    DeviceStatus = 1,        // data size = 9 bytes
+    VendorPubKeyHash = 2,    // data size = 48 bytes
+    OwnerPubKeyHash = 3,     // data size = 48 bytes
+    FmcTci = 4,              // data size = 48 bytes
+    StashMeasurement = 5,    // data size = 48 bytes
+    RtTci = 6,               // data size = 48 bytes
+    FwImageManifest = 7,     // data size = 48 bytes
+    BreakGlass = 8,          // data size = 16 bytes
+    MeasurementManifest = 9, // data size = 48 bytes
 }
 
 impl From<u16> for PcrLogEntryId {
@@ -45,6 +50,8 @@ impl From<u16> for PcrLogEntryId {
             5 => PcrLogEntryId::StashMeasurement,
             6 => PcrLogEntryId::RtTci,
             7 => PcrLogEntryId::FwImageManifest,
+            8 => PcrLogEntryId::BreakGlass,
+            9 => PcrLogEntryId::MeasurementManifest,
             _ => PcrLogEntryId::Invalid,
         }
     }
@@ -77,6 +84,8 @@ impl PcrLogEntry {
             PcrLogEntryId::StashMeasurement => 48,
             PcrLogEntryId::RtTci => 48,
             PcrLogEntryId::FwImageManifest => 48,
+            PcrLogEntryId::BreakGlass => PCR_BREAK_GLASS_MEASUREMENT.len(),
+            PcrLogEntryId::MeasurementManifest => 48,
         };
 
         &self.pcr_data.as_bytes()[..data_len]
@@ -91,7 +100,9 @@ pub struct MeasurementLogEntry {
     pub metadata: [u8; 4],
     pub context: [u32; 12],
     pub svn: u32,
-    pub reserved0: [u8; 4],
+
+    /// ID of the measurement manifest that carried the measurement, or 0
+    pub manifest_id: u32,
 }
 
 pub const RT_FW_CURRENT_PCR: PcrId = PcrId::PcrId2;
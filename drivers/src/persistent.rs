@@ -8,10 +8,10 @@ use caliptra_auth_man_types::{
     AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT,
 };
 use caliptra_error::{CaliptraError, CaliptraResult};
-use caliptra_image_types::ImageManifest;
+use caliptra_image_types::{ImageDigest, ImageManifest, ImageVendorIntermediateKeyTable};
 #[cfg(feature = "runtime")]
 use dpe::{DpeInstance, U8Bool, MAX_HANDLES};
-use zerocopy::{IntoBytes, KnownLayout, TryFromBytes};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, TryFromBytes};
 use zeroize::Zeroize;
 
 use crate::{
@@ -21,8 +21,15 @@ use crate::{
     FirmwareHandoffTable,
 };
 
-use crate::FmcAliasCsr;
+use crate::{BootProfile, CertDistPoints, CrashRecord, FmcAliasCsr, SuspendCheckpoint};
 
+#[cfg(feature = "runtime")]
+use crate::revoked_localities::RevokedLocalities;
+
+#[cfg(feature = "runtime")]
+use crate::fw_update_journal::FwUpdateJournal;
+#[cfg(feature = "runtime")]
+use crate::idevid_cert_chain::IdevIdCertChain;
 #[cfg(feature = "runtime")]
 use crate::pcr_reset::PcrResetCounter;
 
@@ -41,7 +48,17 @@ pub const PCR_RESET_COUNTER_SIZE: u32 = 1024;
 pub const AUTH_MAN_IMAGE_METADATA_MAX_SIZE: u32 = 7 * 1024;
 pub const IDEVID_CSR_SIZE: u32 = 1024;
 pub const FMC_ALIAS_CSR_SIZE: u32 = 1024;
-pub const RESERVED_MEMORY_SIZE: u32 = 3 * 1024;
+pub const PERSISTENT_DATA_HEADER_SIZE: u32 = 128;
+pub const FW_UPDATE_JOURNAL_SIZE: u32 = 1024;
+pub const IDEVID_CERT_CHAIN_SIZE: u32 = 1536;
+pub const CERT_DIST_POINTS_SIZE: u32 = 256;
+pub const SUSPEND_CHECKPOINT_SIZE: u32 = 128;
+pub const CRASH_RECORD_SIZE: u32 = 64;
+pub const LOG_LEVEL_SIZE: u32 = 4;
+pub const BOOT_PROFILE_SIZE: u32 = 60;
+pub const VENDOR_INTERMEDIATE_KEYS_SIZE: u32 = 896;
+pub const OWNER_KEY_COMMIT_REQUEST_SIZE: u32 = 48;
+pub const RESERVED_MEMORY_SIZE: u32 = 976;
 
 pub const PCR_LOG_MAX_COUNT: usize = 17;
 pub const FUSE_LOG_MAX_COUNT: usize = 62;
@@ -51,7 +68,8 @@ pub const MEASUREMENT_MAX_COUNT: usize = 8;
 const DPE_DCCM_STORAGE: usize = size_of::<DpeInstance>()
     + size_of::<u32>() * MAX_HANDLES
     + size_of::<U8Bool>() * MAX_HANDLES
-    + size_of::<U8Bool>();
+    + size_of::<U8Bool>()
+    + size_of::<RevokedLocalities>();
 
 #[cfg(feature = "runtime")]
 const _: () = assert!(DPE_DCCM_STORAGE < DPE_SIZE as usize);
@@ -186,6 +204,90 @@ impl IdevIdCsr {
     }
 }
 
+/// Layout version of the Runtime Firmware sections of `PersistentData`.
+///
+/// Bump this whenever one of the `PersistentDataSection`s changes layout, and
+/// teach the runtime to migrate data written with the previous version.
+pub const PERSISTENT_DATA_VERSION: u16 = 1;
+
+/// Runtime Firmware sections of `PersistentData` that are carried across an
+/// update reset
+#[repr(usize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PersistentDataSection {
+    /// DPE instance, context tags and the attestation disabled flag
+    Dpe = 0,
+    PcrReset = 1,
+    AuthManifestImageMetadata = 2,
+    FwUpdateJournal = 3,
+    IdevIdCertChain = 4,
+    CertDistPoints = 5,
+}
+
+impl PersistentDataSection {
+    pub const COUNT: usize = 6;
+
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::Dpe,
+        Self::PcrReset,
+        Self::AuthManifestImageMetadata,
+        Self::FwUpdateJournal,
+        Self::IdevIdCertChain,
+        Self::CertDistPoints,
+    ];
+}
+
+/// Identifies the layout of `PersistentData` and, while an update reset is in
+/// flight, the checksums of each `PersistentDataSection`
+#[derive(Clone, Copy, Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+#[repr(C)]
+pub struct PersistentDataHeader {
+    /// `PERSISTENT_DATA_VERSION` of the firmware that last owned the data, or
+    /// 0 if that firmware predates versioning
+    pub version: u16,
+
+    /// Non-zero if `checksums` were recorded before an update reset
+    sealed: u16,
+
+    /// Fletcher-16 checksum of each section, indexed by `PersistentDataSection`
+    checksums: [u16; PersistentDataSection::COUNT],
+}
+
+impl PersistentDataHeader {
+    /// Marks the data as laid out for `PERSISTENT_DATA_VERSION`, with no
+    /// checksums recorded
+    pub fn reset(&mut self) {
+        *self = Self {
+            version: PERSISTENT_DATA_VERSION,
+            ..Default::default()
+        };
+    }
+
+    /// Returns true if checksums were recorded before an update reset
+    pub fn is_sealed(&self) -> bool {
+        self.sealed != 0
+    }
+
+    /// Returns the checksum recorded for `section`
+    pub fn checksum(&self, section: PersistentDataSection) -> u16 {
+        self.checksums
+            .get(section as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Fletcher-16 checksum over the concatenation of `chunks`
+pub fn fletcher16(chunks: &[&[u8]]) -> u16 {
+    let mut sum1 = 0u16;
+    let mut sum2 = 0u16;
+    for byte in chunks.iter().flat_map(|chunk| chunk.iter()) {
+        sum1 = (sum1 + u16::from(*byte)) % 255;
+        sum2 = (sum2 + sum1) % 255;
+    }
+    (sum2 << 8) | sum1
+}
+
 const _: () = assert!(size_of::<PersistentData>() == memory_layout::PERSISTENT_DATA_SIZE as usize);
 const _: () = assert!(size_of::<ImageManifest>() <= MAN1_SIZE as usize);
 const _: () = assert!(size_of::<ImageManifest>() <= MAN2_SIZE as usize);
@@ -200,6 +302,19 @@ const _: () = assert!(
     size_of::<AuthManifestImageMetadataCollection>() <= AUTH_MAN_IMAGE_METADATA_MAX_SIZE as usize
 );
 const _: () = assert!(size_of::<IdevIdCsr>() <= IDEVID_CSR_SIZE as usize);
+#[cfg(feature = "runtime")]
+const _: () = assert!(size_of::<FwUpdateJournal>() <= FW_UPDATE_JOURNAL_SIZE as usize);
+#[cfg(feature = "runtime")]
+const _: () = assert!(size_of::<IdevIdCertChain>() <= IDEVID_CERT_CHAIN_SIZE as usize);
+const _: () = assert!(size_of::<CertDistPoints>() <= CERT_DIST_POINTS_SIZE as usize);
+const _: () = assert!(size_of::<SuspendCheckpoint>() <= SUSPEND_CHECKPOINT_SIZE as usize);
+const _: () = assert!(size_of::<CrashRecord>() <= CRASH_RECORD_SIZE as usize);
+const _: () = assert!(size_of::<u32>() == LOG_LEVEL_SIZE as usize);
+const _: () = assert!(size_of::<BootProfile>() <= BOOT_PROFILE_SIZE as usize);
+const _: () =
+    assert!(size_of::<ImageVendorIntermediateKeyTable>() <= VENDOR_INTERMEDIATE_KEYS_SIZE as usize);
+const _: () = assert!(size_of::<ImageDigest>() == OWNER_KEY_COMMIT_REQUEST_SIZE as usize);
+const _: () = assert!(size_of::<PersistentDataHeader>() <= PERSISTENT_DATA_HEADER_SIZE as usize);
 
 #[derive(TryFromBytes, IntoBytes, KnownLayout, Zeroize)]
 #[repr(C)]
@@ -237,6 +352,8 @@ pub struct PersistentData {
     #[cfg(feature = "runtime")]
     pub attestation_disabled: U8Bool,
     #[cfg(feature = "runtime")]
+    pub revoked_localities: RevokedLocalities,
+    #[cfg(feature = "runtime")]
     reserved6: [u8; DPE_SIZE as usize - DPE_DCCM_STORAGE],
     #[cfg(not(feature = "runtime"))]
     dpe: [u8; DPE_SIZE as usize],
@@ -264,6 +381,52 @@ pub struct PersistentData {
 
     reserved11: [u8; FMC_ALIAS_CSR_SIZE as usize - size_of::<FmcAliasCsr>()],
 
+    // Must stay at this offset, so that any firmware can find the layout
+    // version no matter how the fields that follow change.
+    pub header: PersistentDataHeader,
+    reserved12: [u8; PERSISTENT_DATA_HEADER_SIZE as usize - size_of::<PersistentDataHeader>()],
+
+    #[cfg(feature = "runtime")]
+    pub fw_update_journal: FwUpdateJournal,
+    #[cfg(feature = "runtime")]
+    reserved13: [u8; FW_UPDATE_JOURNAL_SIZE as usize - size_of::<FwUpdateJournal>()],
+
+    #[cfg(not(feature = "runtime"))]
+    fw_update_journal: [u8; FW_UPDATE_JOURNAL_SIZE as usize],
+
+    #[cfg(feature = "runtime")]
+    pub idevid_cert_chain: IdevIdCertChain,
+    #[cfg(feature = "runtime")]
+    reserved14: [u8; IDEVID_CERT_CHAIN_SIZE as usize - size_of::<IdevIdCertChain>()],
+
+    #[cfg(not(feature = "runtime"))]
+    idevid_cert_chain: [u8; IDEVID_CERT_CHAIN_SIZE as usize],
+
+    pub cert_dist_points: CertDistPoints,
+    reserved15: [u8; CERT_DIST_POINTS_SIZE as usize - size_of::<CertDistPoints>()],
+
+    pub suspend_checkpoint: SuspendCheckpoint,
+    reserved16: [u8; SUSPEND_CHECKPOINT_SIZE as usize - size_of::<SuspendCheckpoint>()],
+
+    pub crash_record: CrashRecord,
+    reserved17: [u8; CRASH_RECORD_SIZE as usize - size_of::<CrashRecord>()],
+
+    /// Log level set by SET_LOG_LEVEL, or 0 to use the level the firmware
+    /// was built with
+    pub log_level: u32,
+
+    pub boot_profile: BootProfile,
+    reserved18: [u8; BOOT_PROFILE_SIZE as usize - size_of::<BootProfile>()],
+
+    /// Manufacturer intermediate key table of the image in `manifest1`
+    pub vendor_intermediate_keys: ImageVendorIntermediateKeyTable,
+    reserved19:
+        [u8; VENDOR_INTERMEDIATE_KEYS_SIZE as usize - size_of::<ImageVendorIntermediateKeyTable>()],
+
+    /// Owner public key digest the runtime asked ROM to commit on the next
+    /// update reset, or zero if no commit is requested
+    pub owner_key_commit_request: ImageDigest,
+
     // Reserved memory for future objects.
     // New objects should always source memory from this range.
     // Taking memory from this reserve does NOT break hitless updates.
@@ -359,6 +522,66 @@ impl PersistentData {
             );
 
             persistent_data_offset += FMC_ALIAS_CSR_SIZE;
+            assert_eq!(
+                addr_of!((*P).header) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += PERSISTENT_DATA_HEADER_SIZE;
+            assert_eq!(
+                addr_of!((*P).fw_update_journal) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += FW_UPDATE_JOURNAL_SIZE;
+            assert_eq!(
+                addr_of!((*P).idevid_cert_chain) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += IDEVID_CERT_CHAIN_SIZE;
+            assert_eq!(
+                addr_of!((*P).cert_dist_points) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += CERT_DIST_POINTS_SIZE;
+            assert_eq!(
+                addr_of!((*P).suspend_checkpoint) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += SUSPEND_CHECKPOINT_SIZE;
+            assert_eq!(
+                addr_of!((*P).crash_record) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += CRASH_RECORD_SIZE;
+            assert_eq!(
+                addr_of!((*P).log_level) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += LOG_LEVEL_SIZE;
+            assert_eq!(
+                addr_of!((*P).boot_profile) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += BOOT_PROFILE_SIZE;
+            assert_eq!(
+                addr_of!((*P).vendor_intermediate_keys) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += VENDOR_INTERMEDIATE_KEYS_SIZE;
+            assert_eq!(
+                addr_of!((*P).owner_key_commit_request) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += OWNER_KEY_COMMIT_REQUEST_SIZE;
             assert_eq!(
                 addr_of!((*P).reserved_memory) as u32,
                 memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
@@ -373,6 +596,50 @@ impl PersistentData {
     }
 }
 
+#[cfg(feature = "runtime")]
+impl PersistentData {
+    /// Returns the checksum of `section` as laid out by this firmware
+    pub fn section_checksum(&self, section: PersistentDataSection) -> u16 {
+        match section {
+            PersistentDataSection::Dpe => fletcher16(&[
+                self.dpe.as_bytes(),
+                self.context_tags.as_bytes(),
+                self.context_has_tag.as_bytes(),
+                self.attestation_disabled.as_bytes(),
+                self.revoked_localities.as_bytes(),
+            ]),
+            PersistentDataSection::PcrReset => fletcher16(&[self.pcr_reset.as_bytes()]),
+            PersistentDataSection::AuthManifestImageMetadata => {
+                fletcher16(&[self.auth_manifest_image_metadata_col.as_bytes()])
+            }
+            PersistentDataSection::FwUpdateJournal => {
+                fletcher16(&[self.fw_update_journal.as_bytes()])
+            }
+            PersistentDataSection::IdevIdCertChain => {
+                fletcher16(&[self.idevid_cert_chain.as_bytes()])
+            }
+            PersistentDataSection::CertDistPoints => {
+                fletcher16(&[self.cert_dist_points.as_bytes()])
+            }
+        }
+    }
+
+    /// Records the layout version and the checksum of every section, so the
+    /// firmware started by the next update or warm reset can tell whether it
+    /// may interpret them.
+    pub fn seal(&mut self) {
+        let mut checksums = [0u16; PersistentDataSection::COUNT];
+        for (section, checksum) in PersistentDataSection::ALL.iter().zip(checksums.iter_mut()) {
+            *checksum = self.section_checksum(*section);
+        }
+        self.header = PersistentDataHeader {
+            version: PERSISTENT_DATA_VERSION,
+            sealed: 1,
+            checksums,
+        };
+    }
+}
+
 pub struct PersistentDataAccessor {
     // This field is here to ensure that Self::new() is the only way
     // to create this type.
@@ -444,4 +711,59 @@ mod tests {
         // to call assert_matches_layout() in a risc-v test.
         PersistentData::assert_matches_layout();
     }
+
+    #[test]
+    fn test_layout_offsets_are_stable() {
+        // These offsets are shared with firmware already in the field, which
+        // reads them across an update reset. They must never move.
+        const P: *const PersistentData =
+            memory_layout::PERSISTENT_DATA_ORG as *const PersistentData;
+        macro_rules! offset {
+            ($field:ident) => {
+                unsafe { addr_of!((*P).$field) as u32 - memory_layout::PERSISTENT_DATA_ORG }
+            };
+        }
+        assert_eq!(offset!(manifest1), 0);
+        assert_eq!(offset!(manifest2), 0x1800);
+        assert_eq!(offset!(fht), 0x3000);
+        assert_eq!(offset!(ldevid_tbs), 0x3800);
+        assert_eq!(offset!(fmcalias_tbs), 0x3c00);
+        assert_eq!(offset!(rtalias_tbs), 0x4000);
+        assert_eq!(offset!(pcr_log), 0x4400);
+        assert_eq!(offset!(measurement_log), 0x4800);
+        assert_eq!(offset!(fuse_log), 0x4c00);
+        assert_eq!(offset!(dpe), 0x5000);
+        assert_eq!(offset!(pcr_reset), 0x6400);
+        assert_eq!(offset!(auth_manifest_image_metadata_col), 0x6800);
+        assert_eq!(offset!(idevid_csr), 0x8400);
+        assert_eq!(offset!(fmc_alias_csr), 0x8800);
+        assert_eq!(offset!(header), 0x8c00);
+    }
+
+    #[test]
+    fn test_fletcher16() {
+        assert_eq!(fletcher16(&[]), 0);
+        assert_eq!(fletcher16(&[b"abcde"]), 0xc8f0);
+        assert_eq!(fletcher16(&[b"abcdef"]), 0x2057);
+        assert_eq!(fletcher16(&[b"ab", b"", b"cdef"]), 0x2057);
+    }
+
+    #[test]
+    fn test_header_reset() {
+        let mut header = PersistentDataHeader {
+            version: 0,
+            sealed: 1,
+            checksums: [0xffff; PersistentDataSection::COUNT],
+        };
+        assert!(header.is_sealed());
+        assert_eq!(
+            header.checksum(PersistentDataSection::CertDistPoints),
+            0xffff
+        );
+
+        header.reset();
+        assert_eq!(header.version, PERSISTENT_DATA_VERSION);
+        assert!(!header.is_sealed());
+        assert_eq!(header.checksum(PersistentDataSection::CertDistPoints), 0);
+    }
 }
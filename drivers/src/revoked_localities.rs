@@ -0,0 +1,115 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    revoked_localities.rs
+
+Abstract:
+
+    DPE localities revoked with REVOKE_LOCALITY.
+
+--*/
+
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+pub const MAX_REVOKED_LOCALITIES: usize = 8;
+
+/// Localities whose DPE contexts were torn down by REVOKE_LOCALITY.
+///
+/// The list lives next to the DPE contexts in persistent data so that it
+/// survives update and warm resets along with them, and is only cleared by a
+/// cold reset. Localities are stored as little-endian bytes to keep the
+/// structure free of alignment requirements.
+#[repr(C)]
+#[derive(Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct RevokedLocalities {
+    /// Number of valid entries in `localities`
+    count: u8,
+
+    localities: [[u8; 4]; MAX_REVOKED_LOCALITIES],
+}
+
+impl RevokedLocalities {
+    /// Returns the number of revoked localities
+    pub fn len(&self) -> usize {
+        (self.count as usize).min(MAX_REVOKED_LOCALITIES)
+    }
+
+    /// Returns true if no locality is revoked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the revoked localities
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.localities[..self.len()]
+            .iter()
+            .map(|locality| u32::from_le_bytes(*locality))
+    }
+
+    /// Returns true if `locality` is revoked
+    pub fn contains(&self, locality: u32) -> bool {
+        self.iter().any(|revoked| revoked == locality)
+    }
+
+    /// Revokes `locality`. Revoking a locality twice is a no-op.
+    ///
+    /// Returns an error if the list is full.
+    pub fn insert(&mut self, locality: u32) -> Result<(), ()> {
+        if self.contains(locality) {
+            return Ok(());
+        }
+        let len = self.len();
+        let slot = self.localities.get_mut(len).ok_or(())?;
+        *slot = locality.to_le_bytes();
+        self.count = (len + 1) as u8;
+        Ok(())
+    }
+
+    /// Un-revokes every locality
+    pub fn clear(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut revoked = RevokedLocalities::default();
+        assert!(revoked.is_empty());
+        assert!(!revoked.contains(0));
+
+        revoked.insert(0x1234_5678).unwrap();
+        revoked.insert(0x1234_5678).unwrap();
+        assert_eq!(revoked.len(), 1);
+        assert!(revoked.contains(0x1234_5678));
+        assert!(!revoked.contains(0));
+
+        revoked.clear();
+        assert!(revoked.is_empty());
+        assert!(!revoked.contains(0x1234_5678));
+    }
+
+    #[test]
+    fn test_full() {
+        let mut revoked = RevokedLocalities::default();
+        for i in 0..MAX_REVOKED_LOCALITIES as u32 {
+            revoked.insert(i).unwrap();
+        }
+        assert_eq!(revoked.insert(MAX_REVOKED_LOCALITIES as u32), Err(()));
+        assert!(revoked.iter().eq(0..MAX_REVOKED_LOCALITIES as u32));
+    }
+
+    #[test]
+    fn test_corrupt_count() {
+        let mut revoked = RevokedLocalities::default();
+        revoked.as_mut_bytes()[0] = 0xff;
+        assert_eq!(revoked.len(), MAX_REVOKED_LOCALITIES);
+        assert_eq!(revoked.insert(1), Err(()));
+    }
+}
@@ -14,7 +14,7 @@ Abstract:
 
 use core::usize;
 
-use crate::{array::Array4x16, wait, Array4x8, CaliptraError, CaliptraResult};
+use crate::{array::Array4x16, wait, Array4x8, CaliptraError, CaliptraResult, HwCapabilities};
 use caliptra_registers::sha256::Sha256Reg;
 
 const SHA256_BLOCK_BYTE_SIZE: usize = 64;
@@ -45,15 +45,33 @@ pub trait Sha256Alg {
 
     fn digest_init(&mut self) -> CaliptraResult<Self::DigestOp<'_>>;
     fn digest(&mut self, buf: &[u8]) -> CaliptraResult<Array4x8>;
+
+    /// Returns true if `update_wntz` and `finalize_wntz` are accelerated by
+    /// the hardware.
+    fn wntz_supported(&self) -> bool;
 }
 
 pub struct Sha256 {
     sha256: Sha256Reg,
+    wntz_supported: bool,
 }
 
 impl Sha256 {
     pub fn new(sha256: Sha256Reg) -> Self {
-        Self { sha256 }
+        Self {
+            sha256,
+            wntz_supported: cfg!(not(feature = "hw-1.0")),
+        }
+    }
+
+    /// Restrict the driver to the features present in the hardware.
+    ///
+    /// # Arguments
+    ///
+    /// * `caps` - Capabilities probed from the hardware
+    pub fn set_hw_capabilities(&mut self, caps: HwCapabilities) {
+        self.wntz_supported =
+            cfg!(not(feature = "hw-1.0")) && caps.contains(HwCapabilities::SHA256_WNTZ);
     }
 }
 
@@ -140,6 +158,10 @@ impl Sha256Alg for Sha256 {
 
         Ok(digest)
     }
+
+    fn wntz_supported(&self) -> bool {
+        self.wntz_supported
+    }
 }
 impl Sha256 {
     /// Take a raw sha256 digest of 0 or more 64-byte blocks of memory. Unlike
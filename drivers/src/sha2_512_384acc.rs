@@ -35,12 +35,57 @@ pub enum ShaAccLockState {
 
 pub struct Sha2_512_384Acc {
     sha512_acc: Sha512AccCsr,
+
+    /// Number of lock polls that found the accelerator held by the SoC
+    lock_contention_count: u32,
 }
 
 impl Sha2_512_384Acc {
     pub fn new(sha512_acc: Sha512AccCsr) -> Self {
-        Self { sha512_acc }
+        Self {
+            sha512_acc,
+            lock_contention_count: 0,
+        }
     }
+
+    /// Acquire the SHA384 Accelerator lock, polling while the SoC holds it.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_polls` - Number of times to poll the lock before giving up
+    ///
+    /// # Returns
+    ///
+    /// * On success, an object representing the SHA384 accelerator operation.
+    /// * `DRIVER_SHA2_512_384ACC_LOCK_TIMEOUT` if the lock was not released
+    ///   within `max_polls` polls.
+    pub fn acquire_with_timeout(&mut self, max_polls: u32) -> CaliptraResult<Sha2_512_384AccOp> {
+        #[cfg(feature = "fips-test-hooks")]
+        if unsafe {
+            crate::FipsTestHook::hook_cmd_is_set(
+                crate::FipsTestHook::SHA2_512_384_ACC_START_OP_FAILURE,
+            )
+        } {
+            return Err(CaliptraError::DRIVER_SHA2_512_384ACC_LOCK_TIMEOUT);
+        }
+
+        for _ in 0..max_polls {
+            // Reading the lock register acquires it if it is free.
+            if !self.sha512_acc.regs().lock().read().lock() {
+                return Ok(Sha2_512_384AccOp {
+                    sha512_acc: &mut self.sha512_acc,
+                });
+            }
+            self.lock_contention_count = self.lock_contention_count.saturating_add(1);
+        }
+        Err(CaliptraError::DRIVER_SHA2_512_384ACC_LOCK_TIMEOUT)
+    }
+
+    /// Number of lock polls that found the accelerator held by the SoC.
+    pub fn lock_contention_count(&self) -> u32 {
+        self.lock_contention_count
+    }
+
     /// Acquire the SHA384 Accelerator lock.
     ///
     /// # Arguments
@@ -292,4 +337,185 @@ impl Sha2_512_384AccOp<'_> {
 
         Ok(())
     }
+
+    /// Start a SHA 384 digest of data streamed through the accelerator
+    /// instead of read from the mailbox
+    ///
+    /// # Arguments
+    ///
+    /// * `dlen` - total length of the data that will be streamed
+    ///
+    /// # Returns
+    ///
+    /// * `Sha2_512_384AccStream` - stream to supply the data through
+    pub fn stream_384(&mut self, dlen: u32) -> CaliptraResult<Sha2_512_384AccStream<'_>> {
+        let sha_acc = self.sha512_acc.regs_mut();
+
+        // Selecting the mode restarts the stream.
+        sha_acc.mode().write(|w| w.mode(|_| ShaCmdE::ShaStream384));
+        sha_acc.dlen().write(|_| dlen);
+
+        Ok(Sha2_512_384AccStream {
+            sha512_acc: self.sha512_acc,
+            dlen,
+            written: 0,
+        })
+    }
+
+    /// Start a SHA 384 digest streamed through the accelerator across
+    /// several operations. The accelerator lock is not released with this
+    /// operation, but once the stream is finalized or aborted.
+    ///
+    /// # Arguments
+    ///
+    /// * `dlen` - total length of the data that will be streamed
+    ///
+    /// # Returns
+    ///
+    /// * `Sha2_512_384AccDetachedStream` - stream to supply the data through
+    pub fn detach_stream_384(mut self, dlen: u32) -> CaliptraResult<Sha2_512_384AccDetachedStream> {
+        self.stream_384(dlen)?;
+
+        // Keep the lock held.
+        core::mem::forget(self);
+
+        Ok(Sha2_512_384AccDetachedStream { dlen, written: 0 })
+    }
+}
+
+/// SHA 384 digest fed incrementally through the accelerator `datain` register
+pub struct Sha2_512_384AccStream<'a> {
+    sha512_acc: &'a mut Sha512AccCsr,
+    dlen: u32,
+    written: u32,
+}
+
+impl Sha2_512_384AccStream<'_> {
+    /// Stream more data into the digest
+    ///
+    /// Every update but the last must be a multiple of 4 bytes long.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - next part of the data
+    pub fn update(&mut self, data: &[u8]) -> CaliptraResult<()> {
+        let err = CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH;
+        if self.written % 4 != 0 || data.len() as u32 > self.dlen - self.written {
+            return Err(err);
+        }
+
+        let sha_acc = self.sha512_acc.regs_mut();
+        let mut words = data.chunks_exact(4);
+        for word in words.by_ref() {
+            sha_acc
+                .datain()
+                .write(|_| u32::from_be_bytes(word.try_into().unwrap()));
+        }
+
+        // The accelerator ignores the padding past `dlen`.
+        let rem = words.remainder();
+        if !rem.is_empty() {
+            let mut word = [0u8; 4];
+            word[..rem.len()].copy_from_slice(rem);
+            sha_acc.datain().write(|_| u32::from_be_bytes(word));
+        }
+
+        self.written += data.len() as u32;
+        Ok(())
+    }
+
+    /// Finish the digest once all `dlen` bytes have been streamed
+    ///
+    /// # Arguments
+    ///
+    /// * `digest` - buffer to populate with resulting digest
+    pub fn finalize(self, digest: Sha384Digest) -> CaliptraResult<()> {
+        if self.written != self.dlen {
+            return Err(CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH);
+        }
+
+        let sha_acc = self.sha512_acc.regs_mut();
+        sha_acc.execute().write(|_| ExecuteWriteVal::from(1));
+        wait::until(|| sha_acc.status().read().valid());
+
+        *digest = Array4x12::read_from_reg(sha_acc.digest().truncate::<12>());
+
+        // Zeroize the hardware registers.
+        self.sha512_acc
+            .regs_mut()
+            .control()
+            .write(|w| w.zeroize(true));
+
+        Ok(())
+    }
+}
+
+/// SHA 384 digest streamed through the accelerator across several
+/// operations, such as mailbox commands. The uC holds the accelerator lock
+/// until the stream is finalized or aborted.
+pub struct Sha2_512_384AccDetachedStream {
+    dlen: u32,
+    written: u32,
+}
+
+impl Sha2_512_384AccDetachedStream {
+    /// Number of bytes streamed so far
+    pub fn written(&self) -> u32 {
+        self.written
+    }
+
+    /// Stream more data into the digest
+    ///
+    /// Every update but the last must be a multiple of 4 bytes long.
+    ///
+    /// # Arguments
+    ///
+    /// * `sha_acc` - accelerator the stream was started on
+    /// * `data` - next part of the data
+    pub fn update(&mut self, sha_acc: &mut Sha2_512_384Acc, data: &[u8]) -> CaliptraResult<()> {
+        let mut stream = self.attach(sha_acc);
+        stream.update(data)?;
+        self.written = stream.written;
+        Ok(())
+    }
+
+    /// Finish the digest once all `dlen` bytes have been streamed, and
+    /// release the accelerator lock
+    ///
+    /// # Arguments
+    ///
+    /// * `sha_acc` - accelerator the stream was started on
+    /// * `digest` - buffer to populate with resulting digest
+    pub fn finalize(
+        self,
+        sha_acc: &mut Sha2_512_384Acc,
+        digest: Sha384Digest,
+    ) -> CaliptraResult<()> {
+        if self.written != self.dlen {
+            self.abort(sha_acc);
+            return Err(CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH);
+        }
+        self.attach(sha_acc).finalize(digest)?;
+        sha_acc.sha512_acc.regs_mut().lock().write(|w| w.lock(true));
+        Ok(())
+    }
+
+    /// Discard the digest and release the accelerator lock
+    ///
+    /// # Arguments
+    ///
+    /// * `sha_acc` - accelerator the stream was started on
+    pub fn abort(self, sha_acc: &mut Sha2_512_384Acc) {
+        let regs = sha_acc.sha512_acc.regs_mut();
+        regs.control().write(|w| w.zeroize(true));
+        regs.lock().write(|w| w.lock(true));
+    }
+
+    fn attach<'a>(&self, sha_acc: &'a mut Sha2_512_384Acc) -> Sha2_512_384AccStream<'a> {
+        Sha2_512_384AccStream {
+            sha512_acc: &mut sha_acc.sha512_acc,
+            dlen: self.dlen,
+            written: self.written,
+        }
+    }
 }
@@ -0,0 +1,274 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    sha3.rs
+
+Abstract:
+
+    File contains a software implementation of SHA3-384 and SHAKE256
+    (FIPS 202) for use where no SHA3 accelerator is available.
+
+--*/
+
+use zeroize::Zeroize;
+
+const KECCAK_ROUNDS: usize = 24;
+const KECCAK_STATE_BYTES: usize = 200;
+
+const KECCAK_RC: [u64; KECCAK_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+const KECCAK_RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const KECCAK_PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// SHA3-384 digest size in bytes
+pub const SHA3_384_DIGEST_SIZE: usize = 48;
+
+/// SHA3 family function
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sha3Mode {
+    /// SHA3-384 with a fixed 48 byte digest
+    Sha3_384,
+
+    /// SHAKE256 extendable output function
+    Shake256,
+}
+
+impl Sha3Mode {
+    /// Sponge rate in bytes
+    const fn rate(self) -> usize {
+        match self {
+            Self::Sha3_384 => KECCAK_STATE_BYTES - 2 * SHA3_384_DIGEST_SIZE,
+            Self::Shake256 => KECCAK_STATE_BYTES - 2 * 32,
+        }
+    }
+
+    /// Domain separation bits, including the first bit of the padding
+    const fn domain(self) -> u8 {
+        match self {
+            Self::Sha3_384 => 0x06,
+            Self::Shake256 => 0x1f,
+        }
+    }
+}
+
+/// Software SHA3 sponge
+#[derive(Zeroize)]
+pub struct Sha3 {
+    state: [u64; 25],
+
+    /// Offset of the next byte to absorb within the current block
+    pos: usize,
+
+    #[zeroize(skip)]
+    mode: Sha3Mode,
+}
+
+impl Sha3 {
+    /// Create a new SHA3 operation
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - SHA3 function to compute
+    pub fn new(mode: Sha3Mode) -> Self {
+        Self {
+            state: [0; 25],
+            pos: 0,
+            mode,
+        }
+    }
+
+    /// Compute the SHA3-384 digest of `data`
+    pub fn sha3_384(data: &[u8]) -> [u8; SHA3_384_DIGEST_SIZE] {
+        let mut digest = [0; SHA3_384_DIGEST_SIZE];
+        let mut op = Self::new(Sha3Mode::Sha3_384);
+        op.update(data);
+        op.finalize(&mut digest);
+        digest
+    }
+
+    /// Fill `out` with the SHAKE256 output for `data`
+    pub fn shake256(data: &[u8], out: &mut [u8]) {
+        let mut op = Self::new(Sha3Mode::Shake256);
+        op.update(data);
+        op.finalize(out);
+    }
+
+    /// Absorb data into the sponge
+    pub fn update(&mut self, data: &[u8]) {
+        let rate = self.mode.rate();
+        for &byte in data {
+            self.xor_byte(self.pos, byte);
+            self.pos += 1;
+            if self.pos == rate {
+                self.permute();
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Pad the message and squeeze the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Output buffer. Only the first 48 bytes are written for
+    ///           SHA3-384; SHAKE256 fills the whole buffer.
+    pub fn finalize(mut self, out: &mut [u8]) {
+        let rate = self.mode.rate();
+        self.xor_byte(self.pos, self.mode.domain());
+        self.xor_byte(rate - 1, 0x80);
+        self.permute();
+
+        let len = match self.mode {
+            Sha3Mode::Sha3_384 => out.len().min(SHA3_384_DIGEST_SIZE),
+            Sha3Mode::Shake256 => out.len(),
+        };
+        for (i, chunk) in out[..len].chunks_mut(rate).enumerate() {
+            if i != 0 {
+                self.permute();
+            }
+            for (j, byte) in chunk.iter_mut().enumerate() {
+                *byte = (self.state[j / 8] >> (8 * (j % 8))) as u8;
+            }
+        }
+        self.zeroize();
+    }
+
+    fn xor_byte(&mut self, offset: usize, byte: u8) {
+        self.state[offset / 8] ^= u64::from(byte) << (8 * (offset % 8));
+    }
+
+    /// Keccak-f[1600] permutation
+    fn permute(&mut self) {
+        let a = &mut self.state;
+        for rc in KECCAK_RC {
+            // Theta
+            let mut c = [0u64; 5];
+            for x in 0..5 {
+                c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            }
+            for x in 0..5 {
+                let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+                for y in 0..5 {
+                    a[5 * y + x] ^= d;
+                }
+            }
+
+            // Rho and Pi
+            let mut last = a[1];
+            for (&pi, &rho) in KECCAK_PI.iter().zip(KECCAK_RHO.iter()) {
+                let tmp = a[pi];
+                a[pi] = last.rotate_left(rho);
+                last = tmp;
+            }
+
+            // Chi
+            for y in 0..5 {
+                let mut row = [0u64; 5];
+                row.copy_from_slice(&a[5 * y..5 * y + 5]);
+                for x in 0..5 {
+                    a[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+                }
+            }
+
+            // Iota
+            a[0] ^= rc;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA3_384_EMPTY: [u8; 48] = [
+        0x0c, 0x63, 0xa7, 0x5b, 0x84, 0x5e, 0x4f, 0x7d, 0x01, 0x10, 0x7d, 0x85, 0x2e, 0x4c, 0x24,
+        0x85, 0xc5, 0x1a, 0x50, 0xaa, 0xaa, 0x94, 0xfc, 0x61, 0x99, 0x5e, 0x71, 0xbb, 0xee, 0x98,
+        0x3a, 0x2a, 0xc3, 0x71, 0x38, 0x31, 0x26, 0x4a, 0xdb, 0x47, 0xfb, 0x6b, 0xd1, 0xe0, 0x58,
+        0xd5, 0xf0, 0x04,
+    ];
+
+    #[test]
+    fn test_sha3_384_empty() {
+        assert_eq!(Sha3::sha3_384(&[]), SHA3_384_EMPTY);
+    }
+
+    #[test]
+    fn test_sha3_384_abc() {
+        let expected = [
+            0xec, 0x01, 0x49, 0x82, 0x88, 0x51, 0x6f, 0xc9, 0x26, 0x45, 0x9f, 0x58, 0xe2, 0xc6,
+            0xad, 0x8d, 0xf9, 0xb4, 0x73, 0xcb, 0x0f, 0xc0, 0x8c, 0x25, 0x96, 0xda, 0x7c, 0xf0,
+            0xe4, 0x9b, 0xe4, 0xb2, 0x98, 0xd8, 0x8c, 0xea, 0x92, 0x7a, 0xc7, 0xf5, 0x39, 0xf1,
+            0xed, 0xf2, 0x28, 0x37, 0x6d, 0x25,
+        ];
+        assert_eq!(Sha3::sha3_384(b"abc"), expected);
+    }
+
+    #[test]
+    fn test_sha3_384_incremental() {
+        // Spans several blocks, with updates that straddle block boundaries
+        let data: [u8; 500] = core::array::from_fn(|i| i as u8);
+        let mut op = Sha3::new(Sha3Mode::Sha3_384);
+        for chunk in data.chunks(37) {
+            op.update(chunk);
+        }
+        let mut digest = [0; 48];
+        op.finalize(&mut digest);
+        assert_eq!(digest, Sha3::sha3_384(&data));
+    }
+
+    #[test]
+    fn test_shake256_empty() {
+        let expected = [
+            0x46, 0xb9, 0xdd, 0x2b, 0x0b, 0xa8, 0x8d, 0x13, 0x23, 0x3b, 0x3f, 0xeb, 0x74, 0x3e,
+            0xeb, 0x24, 0x3f, 0xcd, 0x52, 0xea, 0x62, 0xb8, 0x1b, 0x82, 0xb5, 0x0c, 0x27, 0x64,
+            0x6e, 0xd5, 0x76, 0x2f,
+        ];
+        let mut out = [0; 32];
+        Sha3::shake256(&[], &mut out);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_shake256_long_output() {
+        // Output longer than one block must match a prefix-consistent squeeze
+        let mut long = [0; 300];
+        Sha3::shake256(b"abc", &mut long);
+        let mut short = [0; 32];
+        Sha3::shake256(b"abc", &mut short);
+        assert_eq!(long[..32], short);
+        assert_ne!(long[136..168], short);
+    }
+}
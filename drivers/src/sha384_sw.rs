@@ -0,0 +1,291 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    sha384_sw.rs
+
+Abstract:
+
+    File contains a software implementation of SHA-384 (FIPS 180-4) for
+    hash operations that must outlive a single use of the SHA-384 engine,
+    such as a transcript that spans several mailbox commands.
+
+--*/
+
+use zeroize::Zeroize;
+
+const SHA384_BLOCK_SIZE: usize = 128;
+
+/// SHA-384 digest size in bytes
+pub const SHA384_SW_DIGEST_SIZE: usize = 48;
+
+const SHA384_IV: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+const SHA512_K: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+/// Software SHA-384 operation
+///
+/// Unlike a digest operation of the SHA-384 engine, this operation does not
+/// borrow a driver, so it can be stored and cloned.
+#[derive(Clone, Zeroize)]
+pub struct Sha384Sw {
+    state: [u64; 8],
+    block: [u8; SHA384_BLOCK_SIZE],
+
+    /// Number of bytes in `block`
+    pos: usize,
+
+    /// Number of bytes hashed so far
+    len: u64,
+}
+
+impl Default for Sha384Sw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha384Sw {
+    /// Create a new SHA-384 operation
+    pub fn new() -> Self {
+        Self {
+            state: SHA384_IV,
+            block: [0; SHA384_BLOCK_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Compute the SHA-384 digest of `data`
+    pub fn digest(data: &[u8]) -> [u8; SHA384_SW_DIGEST_SIZE] {
+        let mut op = Self::new();
+        op.update(data);
+        op.finalize()
+    }
+
+    /// Add data to the hash
+    pub fn update(&mut self, data: &[u8]) {
+        self.len = self.len.wrapping_add(data.len() as u64);
+        for &byte in data {
+            self.block[self.pos] = byte;
+            self.pos += 1;
+            if self.pos == SHA384_BLOCK_SIZE {
+                self.compress();
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Digest of the data added so far, leaving the operation unchanged
+    pub fn peek(&self) -> [u8; SHA384_SW_DIGEST_SIZE] {
+        self.clone().finalize()
+    }
+
+    /// Pad the message and return the digest
+    pub fn finalize(mut self) -> [u8; SHA384_SW_DIGEST_SIZE] {
+        let bit_len = u128::from(self.len) * 8;
+        self.block[self.pos] = 0x80;
+        self.block[self.pos + 1..].fill(0);
+        if self.pos + 1 > SHA384_BLOCK_SIZE - 16 {
+            self.compress();
+            self.block.fill(0);
+        }
+        self.block[SHA384_BLOCK_SIZE - 16..].copy_from_slice(&bit_len.to_be_bytes());
+        self.compress();
+
+        let mut digest = [0; SHA384_SW_DIGEST_SIZE];
+        for (chunk, word) in digest.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        self.zeroize();
+        digest
+    }
+
+    fn compress(&mut self) {
+        let mut w = [0u64; 80];
+        for (word, chunk) in w.iter_mut().zip(self.block.chunks_exact(8)) {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(chunk);
+            *word = u64::from_be_bytes(bytes);
+        }
+        for t in 16..80 {
+            let s0 = w[t - 15].rotate_right(1) ^ w[t - 15].rotate_right(8) ^ (w[t - 15] >> 7);
+            let s1 = w[t - 2].rotate_right(19) ^ w[t - 2].rotate_right(61) ^ (w[t - 2] >> 6);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for (k, w) in SHA512_K.iter().zip(w.iter()) {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(*w);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (state, v) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *state = state.wrapping_add(v);
+        }
+        w.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha384_sw_empty() {
+        let expected = [
+            0x38, 0xb0, 0x60, 0xa7, 0x51, 0xac, 0x96, 0x38, 0x4c, 0xd9, 0x32, 0x7e, 0xb1, 0xb1,
+            0xe3, 0x6a, 0x21, 0xfd, 0xb7, 0x11, 0x14, 0xbe, 0x07, 0x43, 0x4c, 0x0c, 0xc7, 0xbf,
+            0x63, 0xf6, 0xe1, 0xda, 0x27, 0x4e, 0xde, 0xbf, 0xe7, 0x6f, 0x65, 0xfb, 0xd5, 0x1a,
+            0xd2, 0xf1, 0x48, 0x98, 0xb9, 0x5b,
+        ];
+        assert_eq!(Sha384Sw::digest(&[]), expected);
+    }
+
+    #[test]
+    fn test_sha384_sw_abc() {
+        let expected = [
+            0xcb, 0x00, 0x75, 0x3f, 0x45, 0xa3, 0x5e, 0x8b, 0xb5, 0xa0, 0x3d, 0x69, 0x9a, 0xc6,
+            0x50, 0x07, 0x27, 0x2c, 0x32, 0xab, 0x0e, 0xde, 0xd1, 0x63, 0x1a, 0x8b, 0x60, 0x5a,
+            0x43, 0xff, 0x5b, 0xed, 0x80, 0x86, 0x07, 0x2b, 0xa1, 0xe7, 0xcc, 0x23, 0x58, 0xba,
+            0xec, 0xa1, 0x34, 0xc8, 0x25, 0xa7,
+        ];
+        assert_eq!(Sha384Sw::digest(b"abc"), expected);
+    }
+
+    #[test]
+    fn test_sha384_sw_two_blocks() {
+        // FIPS 180-4 example whose padding spills into a second block
+        let expected = [
+            0x09, 0x33, 0x0c, 0x33, 0xf7, 0x11, 0x47, 0xe8, 0x3d, 0x19, 0x2f, 0xc7, 0x82, 0xcd,
+            0x1b, 0x47, 0x53, 0x11, 0x1b, 0x17, 0x3b, 0x3b, 0x05, 0xd2, 0x2f, 0xa0, 0x80, 0x86,
+            0xe3, 0xb0, 0xf7, 0x12, 0xfc, 0xc7, 0xc7, 0x1a, 0x55, 0x7e, 0x2d, 0xb9, 0x66, 0xc3,
+            0xe9, 0xfa, 0x91, 0x74, 0x60, 0x39,
+        ];
+        let data = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        assert_eq!(Sha384Sw::digest(data), expected);
+    }
+
+    #[test]
+    fn test_sha384_sw_incremental() {
+        // Updates that straddle block boundaries
+        let data: [u8; 500] = core::array::from_fn(|i| i as u8);
+        let mut op = Sha384Sw::new();
+        for chunk in data.chunks(37) {
+            op.update(chunk);
+        }
+        assert_eq!(op.peek(), Sha384Sw::digest(&data));
+        assert_eq!(op.finalize(), Sha384Sw::digest(&data));
+    }
+}
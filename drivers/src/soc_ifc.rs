@@ -17,10 +17,31 @@ use caliptra_error::{CaliptraError, CaliptraResult};
 use caliptra_registers::soc_ifc::enums::DeviceLifecycleE;
 use caliptra_registers::soc_ifc::{self, SocIfcReg};
 
-use crate::{memory_layout, FuseBank};
+use crate::{memory_layout, FuseBank, HwCapabilities};
 
 pub type Lifecycle = DeviceLifecycleE;
 
+/// Generic output wire used to request a runtime SVN fuse update from the SoC
+pub const RUNTIME_SVN_FUSE_REQUEST_WIRE: usize = 1;
+
+/// Set in [`RUNTIME_SVN_FUSE_REQUEST_WIRE`] when a fuse update is requested;
+/// the low byte holds the requested SVN.
+pub const RUNTIME_SVN_FUSE_REQUEST_VALID: u32 = 1 << 31;
+
+/// Generic output wire used to request new field entropy from the SoC. It is
+/// shared with [`RUNTIME_SVN_FUSE_REQUEST_WIRE`].
+pub const FIELD_ENTROPY_RESEED_REQUEST_WIRE: usize = RUNTIME_SVN_FUSE_REQUEST_WIRE;
+
+/// Set in [`FIELD_ENTROPY_RESEED_REQUEST_WIRE`] when new field entropy is
+/// requested.
+pub const FIELD_ENTROPY_RESEED_REQUEST: u32 = 1 << 30;
+
+/// Reserved register the SoC writes to cancel the in-flight mailbox command
+pub const MBOX_CANCEL_REQUEST_REG: usize = 0;
+
+/// Value written to [`MBOX_CANCEL_REQUEST_REG`] to request a cancel ("CNCL")
+pub const MBOX_CANCEL_REQUEST: u32 = 0x434E_434C;
+
 pub fn report_boot_status(val: u32) {
     let mut soc_ifc = unsafe { soc_ifc::SocIfcReg::new() };
 
@@ -36,6 +57,18 @@ pub fn report_boot_status(val: u32) {
     }
 }
 
+/// Restart WDT1.
+///
+/// Long-running operations that hold borrows of the drivers take this as a
+/// callback and call it between steps so that the watchdog does not expire.
+pub fn restart_wdt1() {
+    let mut soc_ifc = unsafe { SocIfcReg::new() };
+    soc_ifc
+        .regs_mut()
+        .cptra_wdt_timer1_ctrl()
+        .write(|w| w.timer1_restart(true));
+}
+
 pub fn reset_reason() -> ResetReason {
     let soc_ifc = unsafe { SocIfcReg::new() };
 
@@ -134,11 +167,16 @@ impl SocIfc {
     /// # Arguments
     ///
     /// * None
-    pub fn flow_status_ready_for_firmware(&mut self) -> bool {
-        let soc_ifc = self.soc_ifc.regs_mut();
+    pub fn flow_status_ready_for_firmware(&self) -> bool {
+        let soc_ifc = self.soc_ifc.regs();
         soc_ifc.cptra_flow_status().read().ready_for_fw()
     }
 
+    /// Returns true if the SoC completed writing the fuses
+    pub fn fuse_write_done(&self) -> bool {
+        self.soc_ifc.regs().cptra_fuse_wr_done().read().done()
+    }
+
     pub fn fuse_bank(&self) -> FuseBank {
         FuseBank {
             soc_ifc: &self.soc_ifc,
@@ -180,6 +218,16 @@ impl SocIfc {
         ((val >> FAKE_ROM_PROD_EN_BIT) & 1) != 0
     }
 
+    /// Check if SHA384 digesting is mocked for fake-rom
+    pub fn sha_mock_in_fake_mode(&self) -> bool {
+        // Bit 29 indicates to use the digests declared in the manifest
+        // instead of computing them in fake ROM
+        const FAKE_ROM_SHA_MOCK_EN_BIT: u32 = 29;
+        let soc_ifc_regs = self.soc_ifc.regs();
+        let val = soc_ifc_regs.cptra_dbg_manuf_service_reg().read();
+        ((val >> FAKE_ROM_SHA_MOCK_EN_BIT) & 1) != 0
+    }
+
     #[inline(always)]
     pub fn hw_config_internal_trng(&mut self) -> bool {
         self.soc_ifc.regs().cptra_hw_config().read().i_trng_en()
@@ -278,6 +326,50 @@ impl SocIfc {
             | ((soc_ifc_regs.cptra_wdt_cfg().at(1).read() as u64) << 32)
     }
 
+    /// Read the generic input wires driven by the SoC as a 64-bit value
+    pub fn generic_input_wires(&self) -> u64 {
+        let soc_ifc_regs = self.soc_ifc.regs();
+        soc_ifc_regs.cptra_generic_input_wires().at(0).read() as u64
+            | ((soc_ifc_regs.cptra_generic_input_wires().at(1).read() as u64) << 32)
+    }
+
+    /// Signal the SoC to burn the runtime SVN fuse up to `svn`
+    pub fn request_runtime_svn_fuse_update(&mut self, svn: u32) {
+        self.soc_ifc
+            .regs_mut()
+            .cptra_generic_output_wires()
+            .at(RUNTIME_SVN_FUSE_REQUEST_WIRE)
+            .modify(|w| (w & FIELD_ENTROPY_RESEED_REQUEST) | RUNTIME_SVN_FUSE_REQUEST_VALID | svn);
+    }
+
+    /// Signal the SoC to program new field entropy before the next cold reset
+    pub fn request_field_entropy_reseed(&mut self) {
+        self.soc_ifc
+            .regs_mut()
+            .cptra_generic_output_wires()
+            .at(FIELD_ENTROPY_RESEED_REQUEST_WIRE)
+            .modify(|w| w | FIELD_ENTROPY_RESEED_REQUEST);
+    }
+
+    /// Returns true if the SoC has asked to cancel the in-flight mailbox command
+    pub fn mbox_cancel_requested(&self) -> bool {
+        self.soc_ifc
+            .regs()
+            .cptra_rsvd_reg()
+            .at(MBOX_CANCEL_REQUEST_REG)
+            .read()
+            == MBOX_CANCEL_REQUEST
+    }
+
+    /// Acknowledge a mailbox cancel request
+    pub fn clear_mbox_cancel_request(&mut self) {
+        self.soc_ifc
+            .regs_mut()
+            .cptra_rsvd_reg()
+            .at(MBOX_CANCEL_REQUEST_REG)
+            .write(|_| 0);
+    }
+
     pub fn internal_fw_update_reset_wait_cycles(&self) -> u32 {
         self.soc_ifc
             .regs()
@@ -323,6 +415,15 @@ impl SocIfc {
         soc_ifc_regs.cptra_fw_rev_id().at(1).write(|_| rt_version);
     }
 
+    /// Probe the optional features of the Caliptra core.
+    pub fn hw_capabilities(&self) -> HwCapabilities {
+        let soc_ifc_regs = self.soc_ifc.regs();
+        HwCapabilities::from_hw_rev(
+            soc_ifc_regs.cptra_hw_rev_id().read().cptra_generation(),
+            soc_ifc_regs.cptra_hw_config().read().i_trng_en(),
+        )
+    }
+
     pub fn get_version(&self) -> [u32; 3] {
         [
             u32::from(self.soc_ifc.regs().cptra_hw_rev_id().read()),
@@ -347,6 +448,16 @@ impl SocIfc {
         intr_block.global_intr_en_r().write(|w| w.notif_en(true));
     }
 
+    pub fn disable_mbox_notif_interrupts(&mut self) {
+        let soc_ifc_regs = self.soc_ifc.regs_mut();
+        let intr_block = soc_ifc_regs.intr_block_rf();
+
+        intr_block
+            .notif_intr_en_r()
+            .write(|w| w.notif_cmd_avail_en(false));
+        intr_block.global_intr_en_r().write(|w| w.notif_en(false));
+    }
+
     pub fn has_mbox_notif_status(&self) -> bool {
         let soc_ifc = self.soc_ifc.regs();
         soc_ifc
@@ -363,6 +474,50 @@ impl SocIfc {
             .notif_internal_intr_r()
             .write(|w| w.notif_cmd_avail_sts(true));
     }
+
+    /// Returns true if an uncorrectable ICCM ECC error was reported
+    pub fn iccm_ecc_unc(&self) -> bool {
+        self.soc_ifc
+            .regs()
+            .cptra_hw_error_fatal()
+            .read()
+            .iccm_ecc_unc()
+    }
+
+    /// Returns true if an uncorrectable DCCM ECC error was reported
+    pub fn dccm_ecc_unc(&self) -> bool {
+        self.soc_ifc
+            .regs()
+            .cptra_hw_error_fatal()
+            .read()
+            .dccm_ecc_unc()
+    }
+
+    /// Number of correctable mailbox SRAM ECC errors
+    pub fn mbox_ecc_cor_count(&self) -> u32 {
+        self.soc_ifc
+            .regs()
+            .intr_block_rf()
+            .notif_mbox_ecc_cor_intr_count_r()
+            .read()
+    }
+
+    /// Number of uncorrectable mailbox SRAM ECC errors
+    pub fn mbox_ecc_unc_count(&self) -> u32 {
+        self.soc_ifc
+            .regs()
+            .intr_block_rf()
+            .error_mbox_ecc_unc_intr_count_r()
+            .read()
+    }
+
+    pub fn trigger_mbox_notif_interrupt(&mut self) {
+        let soc_ifc = self.soc_ifc.regs_mut();
+        soc_ifc
+            .intr_block_rf()
+            .notif_intr_trig_r()
+            .write(|w| w.notif_cmd_avail_trig(true));
+    }
 }
 
 bitflags::bitflags! {
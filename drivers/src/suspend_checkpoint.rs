@@ -0,0 +1,64 @@
+/*++
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    suspend_checkpoint.rs
+
+Abstract:
+
+    Checkpoint of the volatile runtime attestation state across SoC
+    low-power states.
+
+--*/
+
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zeroize::Zeroize;
+
+/// Volatile runtime state saved by SUSPEND_PREPARE or PREPARE_FOR_SLEEP, and
+/// restored by RESUME_NOTIFY or the next warm reset respectively.
+///
+/// The runtime MACs the checkpoint together with the attestation state kept
+/// in persistent data, so that a resume detects any change made while the SoC
+/// was in a low-power state.
+#[repr(C)]
+#[derive(Default, IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct SuspendCheckpoint {
+    /// `SUSPENDED` between SUSPEND_PREPARE and RESUME_NOTIFY, `SLEEPING`
+    /// between PREPARE_FOR_SLEEP and the next warm reset
+    pub suspended: u32,
+
+    /// Non-zero if a certificate policy was set
+    pub has_cert_policy: u32,
+
+    /// Flags of the certificate policy
+    pub cert_policy_flags: u32,
+
+    /// Latest notAfter time of the certificate policy, padded to a multiple
+    /// of four bytes
+    pub cert_policy_max_not_after: [u8; 16],
+
+    /// MAC over the fields above and the persistent attestation state
+    pub mac: [u32; 12],
+}
+
+impl SuspendCheckpoint {
+    pub const SUSPENDED: u32 = 1;
+    pub const SLEEPING: u32 = 2;
+
+    /// Returns true if a checkpoint is pending
+    pub fn is_suspended(&self) -> bool {
+        self.suspended != 0
+    }
+
+    /// Returns true if the checkpoint was saved by PREPARE_FOR_SLEEP
+    pub fn is_sleeping(&self) -> bool {
+        self.suspended == Self::SLEEPING
+    }
+
+    /// Bytes of the checkpoint covered by the MAC
+    pub fn mac_data(&self) -> &[u8] {
+        let bytes = self.as_bytes();
+        &bytes[..bytes.len() - core::mem::size_of_val(&self.mac)]
+    }
+}
@@ -7,7 +7,9 @@ use caliptra_registers::{
     csrng::CsrngReg, entropy_src::EntropySrcReg, soc_ifc::SocIfcReg, soc_ifc_trng::SocIfcTrngReg,
 };
 
-use crate::{trng_ext::TrngExt, Array4x12, Csrng, MfgFlags};
+#[cfg(feature = "deterministic-drbg")]
+use crate::CsrngSeed;
+use crate::{trng_ext::TrngExt, Array4x12, Csrng, CsrngHealthTestStats, MfgFlags};
 
 #[repr(u32)]
 pub enum Trng {
@@ -36,7 +38,19 @@ impl Trng {
         {
             Ok(Self::MfgMode())
         } else if soc_ifc.regs().cptra_hw_config().read().i_trng_en() {
-            Ok(Self::Internal(Csrng::new(csrng, entropy_src, soc_ifc)?))
+            // Test builds replace the entropy_src seed with a fixed one so
+            // that emulator runs are reproducible.
+            #[cfg(feature = "deterministic-drbg")]
+            let csrng = Csrng::with_seed(
+                csrng,
+                entropy_src,
+                soc_ifc,
+                CsrngSeed::Constant(&emu_drbg_seed()),
+            )?;
+            #[cfg(not(feature = "deterministic-drbg"))]
+            let csrng = Csrng::new(csrng, entropy_src, soc_ifc)?;
+
+            Ok(Self::Internal(csrng))
         } else {
             Ok(Self::External(TrngExt::new(soc_ifc_trng)))
         }
@@ -60,6 +74,43 @@ impl Trng {
         }
     }
 
+    /// Start copying raw noise source samples into the entropy_src observe
+    /// FIFO. Only available with the internal TRNG.
+    pub fn start_raw_entropy_capture(&mut self) -> CaliptraResult<()> {
+        match self {
+            Self::Internal(csrng) => csrng.start_raw_entropy_capture(),
+            _ => Err(CaliptraError::DRIVER_TRNG_RAW_ENTROPY_UNAVAILABLE),
+        }
+    }
+
+    /// Fill `dest` with raw noise source samples. Returns true if samples
+    /// were dropped since the previous read. Only available with the
+    /// internal TRNG.
+    pub fn read_raw_entropy(&mut self, dest: &mut [u32]) -> CaliptraResult<bool> {
+        match self {
+            Self::Internal(csrng) => csrng.read_raw_entropy(dest),
+            _ => Err(CaliptraError::DRIVER_TRNG_RAW_ENTROPY_UNAVAILABLE),
+        }
+    }
+
+    /// Returns the entropy_src health test statistics. Only available with
+    /// the internal TRNG.
+    pub fn health_test_stats(&self) -> CaliptraResult<CsrngHealthTestStats> {
+        match self {
+            Self::Internal(csrng) => Ok(csrng.health_test_stats()),
+            _ => Err(CaliptraError::DRIVER_TRNG_HEALTH_TEST_STATS_UNAVAILABLE),
+        }
+    }
+
+    /// Reseed the CSRNG with fresh output from entropy_src. Only available
+    /// with the internal TRNG.
+    pub fn reseed(&mut self) -> CaliptraResult<()> {
+        match self {
+            Self::Internal(csrng) => csrng.reseed_from_entropy_src(),
+            _ => Err(CaliptraError::DRIVER_TRNG_HEALTH_TEST_STATS_UNAVAILABLE),
+        }
+    }
+
     pub fn generate(&mut self) -> CaliptraResult<Array4x12> {
         extern "C" {
             fn cfi_panic_handler(code: u32) -> !;
@@ -85,3 +136,12 @@ impl Trng {
         }
     }
 }
+
+/// Returns the seed the emulator provides for the deterministic DRBG
+#[cfg(feature = "deterministic-drbg")]
+fn emu_drbg_seed() -> [u32; 12] {
+    // Emulator control seed registers, see caliptra-emu-periph EmuCtrl
+    const EMU_CTRL_DRBG_SEED: *const u32 = 0x2000_f010 as *const u32;
+
+    array::from_fn(|i| unsafe { core::ptr::read_volatile(EMU_CTRL_DRBG_SEED.add(i)) })
+}
@@ -32,6 +32,26 @@ fn test_boot_and_generate_pass() {
         .expect("CSRNG should pass continuous health tests (second generate)");
 }
 
+fn test_health_test_stats_and_reseed() {
+    let mut csrng = unsafe { Csrng::assume_initialized(CsrngReg::new(), EntropySrcReg::new()) };
+
+    csrng
+        .reseed_from_entropy_src()
+        .expect("CSRNG should pass health tests while reseeding");
+    let _ = csrng
+        .generate12()
+        .expect("CSRNG should pass continuous health tests after reseeding");
+
+    let stats = csrng.health_test_stats();
+    assert_eq!(stats.repcnt_total_fails, 0);
+    assert_eq!(stats.adaptp_hi_total_fails, 0);
+    assert_eq!(stats.adaptp_lo_total_fails, 0);
+    assert!(stats.repcnt_hi_watermark < stats.repcnt_threshold);
+    assert!(stats.adaptp_hi_watermark <= stats.adaptp_hi_threshold);
+    assert!(stats.adaptp_lo_watermark >= stats.adaptp_lo_threshold);
+}
+
 test_suite! {
     test_boot_and_generate_pass,
+    test_health_test_stats_and_reseed,
 }
@@ -453,6 +453,68 @@ fn test_kv_seed_from_kv_msg_from_input() {
     assert_eq!(result.unwrap(), Ecc384Result::Success);
 }
 
+fn test_keygen_to_kv() {
+    let mut ecc = unsafe { Ecc384::new(EccReg::new()) };
+    let mut trng = unsafe {
+        Trng::new(
+            CsrngReg::new(),
+            EntropySrcReg::new(),
+            SocIfcTrngReg::new(),
+            &SocIfcReg::new(),
+        )
+        .unwrap()
+    };
+    //
+    // Step 1: Generate a seed in kv slot 0.
+    //
+    let seed = [0u8; 48];
+    let key_out_seed = KeyWriteArgs {
+        id: KeyId::KeyId0,
+        usage: KeyUsage::default()
+            .set_ecc_key_gen_seed_en()
+            .set_ecc_private_key_en(),
+    };
+    let result = ecc.key_pair(
+        &Ecc384Seed::from(&Ecc384Scalar::from(seed)),
+        &Array4x12::default(),
+        &mut trng,
+        Ecc384PrivKeyOut::from(key_out_seed),
+    );
+    assert!(result.is_ok());
+
+    //
+    // Step 2: Generate a key pair from the seed into kv slot 1. This must
+    // match the key pair generated by key_pair() from the same seed.
+    //
+    let pub_key = ecc
+        .keygen_to_kv(KeyId::KeyId0, &mut trng, KeyId::KeyId1)
+        .unwrap();
+    let expected_pub_key = ecc
+        .key_pair(
+            &Ecc384Seed::from(KeyReadArgs::new(KeyId::KeyId0)),
+            &Array4x12::default(),
+            &mut trng,
+            Ecc384PrivKeyOut::from(KeyWriteArgs {
+                id: KeyId::KeyId2,
+                usage: KeyUsage::default().set_ecc_private_key_en(),
+            }),
+        )
+        .unwrap();
+    assert_eq!(pub_key, expected_pub_key);
+
+    //
+    // Step 3: The private key in kv slot 1 can be used for signing.
+    //
+    let digest = Array4x12::new([0u32; 12]);
+    let result = ecc.sign(
+        &KeyReadArgs::new(KeyId::KeyId1).into(),
+        &pub_key,
+        &digest,
+        &mut trng,
+    );
+    assert!(result.is_ok());
+}
+
 fn test_no_private_key_usage() {
     let mut ecc = unsafe { Ecc384::new(EccReg::new()) };
     let mut trng = unsafe {
@@ -511,5 +573,6 @@ test_suite! {
     test_verify_failure,
     test_kv_seed_from_input_msg_from_input,
     test_kv_seed_from_kv_msg_from_input,
+    test_keygen_to_kv,
     test_no_private_key_usage,
 }
@@ -17,14 +17,15 @@ Abstract:
 
 use caliptra_cfi_lib::CfiCounter;
 use caliptra_drivers::{
-    hmac384_kdf, Array4x12, Ecc384, Ecc384PrivKeyOut, Ecc384Scalar, Ecc384Seed, Hmac384, KeyId,
-    KeyReadArgs, KeyUsage, KeyWriteArgs, Trng,
+    Array4x12, CaliptraError, Ecc384, Ecc384PrivKeyOut, Ecc384Scalar, Ecc384Seed, Hmac384, Kdf,
+    KeyId, KeyReadArgs, KeyUsage, KeyVault, KeyWriteArgs, Trng,
 };
 use caliptra_kat::Hmac384KdfKat;
 use caliptra_registers::csrng::CsrngReg;
 use caliptra_registers::ecc::EccReg;
 use caliptra_registers::entropy_src::EntropySrcReg;
 use caliptra_registers::hmac::HmacReg;
+use caliptra_registers::kv::KvReg;
 use caliptra_registers::soc_ifc::SocIfcReg;
 use caliptra_registers::soc_ifc_trng::SocIfcTrngReg;
 
@@ -432,15 +433,9 @@ fn test_kdf(
 
     let kdf_out = KeyWriteArgs::new(KeyId::KeyId1, KeyUsage::default().set_ecc_key_gen_seed_en());
 
-    hmac384_kdf(
-        &mut hmac384,
-        kdf_key_in.into(),
-        label,
-        context,
-        &mut trng,
-        kdf_out.into(),
-    )
-    .unwrap();
+    Kdf::new(&mut hmac384, &mut trng)
+        .derive(kdf_key_in.into(), label, context, kdf_out.into())
+        .unwrap();
 
     let ecc_out = KeyWriteArgs::new(KeyId::KeyId2, KeyUsage::default().set_ecc_private_key_en());
 
@@ -565,19 +560,109 @@ fn test_kdf2() {
 
     let mut out_buf = Array4x12::default();
 
-    hmac384_kdf(
-        &mut hmac384,
-        (&Array4x12::from(&key)).into(),
-        &label,
-        None,
-        &mut trng,
-        (&mut out_buf).into(),
-    )
-    .unwrap();
+    Kdf::new(&mut hmac384, &mut trng)
+        .derive(
+            (&Array4x12::from(&key)).into(),
+            &label,
+            None,
+            (&mut out_buf).into(),
+        )
+        .unwrap();
 
     assert_eq!(<[u8; 48]>::from(out_buf)[..out.len()], out);
 }
 
+fn test_kdf_rekey_cdi() {
+    let mut hmac384 = unsafe { Hmac384::new(HmacReg::new()) };
+    let mut ecc = unsafe { Ecc384::new(EccReg::new()) };
+    let mut vault = unsafe { KeyVault::new(KvReg::new()) };
+    let mut trng = unsafe {
+        Trng::new(
+            CsrngReg::new(),
+            EntropySrcReg::new(),
+            SocIfcTrngReg::new(),
+            &SocIfcReg::new(),
+        )
+        .unwrap()
+    };
+
+    let cdi = KeyId::KeyId13;
+    let derived_cdi = KeyId::KeyId14;
+    let rekeyed_cdi = KeyId::KeyId15;
+    let label = b"rekey";
+    let context = [0x5a; 48];
+
+    hmac384
+        .hmac(
+            &(&Array4x12::from([0x0b; 48])).into(),
+            &(&[0xa5_u8; 8]).into(),
+            &mut trng,
+            KeyWriteArgs::new(cdi, KeyUsage::default().set_hmac_key_en()).into(),
+        )
+        .unwrap();
+
+    Kdf::new(&mut hmac384, &mut trng)
+        .derive_cdi(cdi, label, Some(&context), derived_cdi)
+        .unwrap();
+    vault
+        .rekey_cdi(
+            &mut Kdf::new(&mut hmac384, &mut trng),
+            cdi,
+            label,
+            Some(&context),
+            rekeyed_cdi,
+        )
+        .unwrap();
+
+    // The source slot is erased and locked
+    let status = vault.slot_status(cdi);
+    assert!(!status.occupied());
+    assert!(status.use_lock);
+    assert!(status.write_lock);
+
+    // The re-keyed CDI matches a plain derivation from the old CDI
+    let priv_key_out =
+        KeyWriteArgs::new(KeyId::KeyId2, KeyUsage::default().set_ecc_private_key_en());
+    let derived_pub_key = ecc
+        .key_pair(
+            &KeyReadArgs::new(derived_cdi).into(),
+            &Array4x12::default(),
+            &mut trng,
+            Ecc384PrivKeyOut::from(priv_key_out),
+        )
+        .unwrap();
+    let rekeyed_pub_key = ecc
+        .key_pair(
+            &KeyReadArgs::new(rekeyed_cdi).into(),
+            &Array4x12::default(),
+            &mut trng,
+            Ecc384PrivKeyOut::from(priv_key_out),
+        )
+        .unwrap();
+    assert_eq!(derived_pub_key, rekeyed_pub_key);
+
+    assert_eq!(
+        vault.rekey_cdi(
+            &mut Kdf::new(&mut hmac384, &mut trng),
+            rekeyed_cdi,
+            label,
+            None,
+            rekeyed_cdi,
+        ),
+        Err(CaliptraError::DRIVER_KV_REKEY_SAME_SLOT)
+    );
+    assert_eq!(
+        vault.rekey_cdi(
+            &mut Kdf::new(&mut hmac384, &mut trng),
+            rekeyed_cdi,
+            label,
+            None,
+            cdi,
+        ),
+        Err(CaliptraError::DRIVER_KV_REKEY_OUTPUT_LOCKED)
+    );
+}
+
 fn test_hmac_multi_block() {
     let mut hmac384 = unsafe { Hmac384::new(HmacReg::new()) };
     let mut trng = unsafe {
@@ -763,6 +848,7 @@ test_suite! {
     test_kdf0,
     test_kdf1,
     test_kdf2,
+    test_kdf_rekey_cdi,
     test_hmac_multi_block,
     test_hmac_exact_single_block,
     test_hmac_multi_block_two_step,
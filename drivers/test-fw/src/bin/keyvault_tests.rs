@@ -15,7 +15,7 @@ Abstract:
 #![no_std]
 #![no_main]
 
-use caliptra_drivers::{KeyId, KeyUsage, KeyVault};
+use caliptra_drivers::{KeyId, KeySlotKind, KeySlotStatus, KeyUsage, KeyVault};
 use caliptra_registers::kv::KvReg;
 use caliptra_test_harness::test_suite;
 
@@ -114,6 +114,44 @@ fn test_use_lock() {
     }
 }
 
+fn test_slot_map() {
+    let mut vault = unsafe { KeyVault::new(KvReg::new()) };
+
+    let map = vault.slot_map();
+    for key_id in KEY_IDS {
+        let status = map[usize::from(key_id)];
+        assert_eq!(status, vault.slot_status(key_id));
+        assert!(!status.occupied());
+        assert_eq!(status.kind(), KeySlotKind::Empty);
+        assert!(status.use_lock);
+        assert!(status.write_lock);
+    }
+
+    let kind = |usage: KeyUsage| {
+        KeySlotStatus {
+            usage,
+            ..Default::default()
+        }
+        .kind()
+    };
+    assert_eq!(
+        kind(
+            KeyUsage::default()
+                .set_hmac_key_en()
+                .set_ecc_key_gen_seed_en()
+        ),
+        KeySlotKind::Cdi
+    );
+    assert_eq!(
+        kind(KeyUsage::default().set_ecc_private_key_en()),
+        KeySlotKind::PrivateKey
+    );
+    assert_eq!(
+        kind(KeyUsage::default().set_hmac_data_en()),
+        KeySlotKind::Ephemeral
+    );
+}
+
 fn test_write_protection_stickiness() {
     let mut vault = unsafe { KeyVault::new(KvReg::new()) };
 
@@ -140,6 +178,7 @@ test_suite! {
     test_erase_all_keys,
     test_read_key_usage,
     test_use_lock,
+    test_slot_map,
     test_write_protection_stickiness,
     test_use_protection_stickiness,
 
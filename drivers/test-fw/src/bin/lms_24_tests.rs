@@ -416,6 +416,22 @@ fn test_lms_24_height_15() {
         .unwrap();
     assert_eq!(candidate_key, HashValue::from(LMS_PUBLIC_KEY.digest));
 
+    // Resumable verification yields between batches and reaches the same root
+    let lms = Lms::default();
+    let mut op = lms
+        .verify_lms_signature_cfi_start(&mut sha256, &MESSAGE, &LMS_PUBLIC_KEY, &LMS_SIG)
+        .unwrap();
+    let mut steps = 1;
+    let candidate_key = loop {
+        if let Some(root) = op.step_cfi(&mut sha256, 8).unwrap() {
+            break root;
+        }
+        steps += 1;
+    };
+    assert_eq!(candidate_key, HashValue::from(LMS_PUBLIC_KEY.digest));
+    // 51 chains in batches of 8
+    assert_eq!(steps, 7);
+
     // add a test that uses an invalid q value
     // in this case we are using the maximum value for q
     let invalid_q_sig = LmsSignature {
@@ -439,9 +455,50 @@ fn test_lms_24_height_15() {
     assert_eq!(result, Err(CaliptraError::DRIVER_LMS_INVALID_Q_VALUE));
 }
 
+fn test_generate_public_key_24() {
+    let mut sha256 = unsafe { Sha256::new(Sha256Reg::new()) };
+    let lms_identifier: LmsIdentifier = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    let seed = HashValue::from([0xa5u8; 24]);
+    let expected_root = HashValue::from([
+        0x84, 0xeb, 0x85, 0xc7, 0x35, 0x12, 0xc3, 0xbe, 0x49, 0x3e, 0xfe, 0x9a, 0x8f, 0xf6, 0xf0,
+        0xda, 0x8a, 0xdb, 0x17, 0xac, 0x47, 0xb1, 0xbd, 0x8d,
+    ]);
+
+    let public_key = Lms::default()
+        .generate_public_key::<6, 26, 5>(
+            &mut sha256,
+            LmsAlgorithmType::LmsSha256N24H5,
+            LmotsAlgorithmType::LmotsSha256N24W8,
+            &lms_identifier,
+            &seed,
+        )
+        .unwrap();
+    assert_eq!(public_key.tree_type, LmsAlgorithmType::LmsSha256N24H5);
+    assert_eq!(public_key.otstype, LmotsAlgorithmType::LmotsSha256N24W8);
+    assert_eq!(public_key.id, lms_identifier);
+    assert_eq!(HashValue::from(public_key.digest), expected_root);
+
+    // The tree height must match the parameter set
+    let result = Lms::default().generate_public_key::<6, 26, 10>(
+        &mut sha256,
+        LmsAlgorithmType::LmsSha256N24H5,
+        LmotsAlgorithmType::LmotsSha256N24W8,
+        &lms_identifier,
+        &seed,
+    );
+    assert_eq!(
+        result.err(),
+        Some(CaliptraError::DRIVER_LMS_INVALID_TREE_HEIGHT)
+    );
+}
+
 test_suite! {
     test_coefficient,
     test_get_lms_parameters,
     test_hash_message_24,
     test_lms_24_height_15,
+    test_generate_public_key_24,
 }
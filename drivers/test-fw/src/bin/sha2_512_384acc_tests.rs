@@ -16,7 +16,7 @@ Abstract:
 #![no_main]
 
 use caliptra_drivers::{
-    memory_layout, Array4x12, Array4x16, Mailbox, Sha2_512_384Acc, ShaAccLockState,
+    memory_layout, Array4x12, Array4x16, CaliptraError, Mailbox, Sha2_512_384Acc, ShaAccLockState,
 };
 use caliptra_kat::Sha2_512_384AccKat;
 use caliptra_registers::mbox::MboxCsr;
@@ -371,6 +371,99 @@ fn test_digest_max_mailbox_size() {
     };
 }
 
+fn test_stream_digest() {
+    let mut sha_acc = unsafe { Sha2_512_384Acc::new(Sha512AccCsr::new()) };
+
+    let data = "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu".as_bytes();
+    let expected: [u8; SHA384_HASH_SIZE] = [
+        0x09, 0x33, 0x0C, 0x33, 0xF7, 0x11, 0x47, 0xE8, 0x3D, 0x19, 0x2F, 0xC7, 0x82, 0xCD, 0x1B,
+        0x47, 0x53, 0x11, 0x1B, 0x17, 0x3B, 0x3B, 0x05, 0xD2, 0x2F, 0xA0, 0x80, 0x86, 0xE3, 0xB0,
+        0xF7, 0x12, 0xFC, 0xC7, 0xC7, 0x1A, 0x55, 0x7E, 0x2D, 0xB9, 0x66, 0xC3, 0xE9, 0xFA, 0x91,
+        0x74, 0x60, 0x39,
+    ];
+
+    if let Some(mut sha_acc_op) = sha_acc
+        .try_start_operation(ShaAccLockState::NotAcquired)
+        .unwrap()
+    {
+        let mut digest = Array4x12::default();
+        let mut stream = sha_acc_op.stream_384(data.len() as u32).unwrap();
+        stream.update(&data[..64]).unwrap();
+        stream.update(&data[64..]).unwrap();
+        stream.finalize((&mut digest).into()).unwrap();
+        assert_eq!(digest, Array4x12::from(expected));
+
+        // All `dlen` bytes must be streamed before finalizing
+        let mut stream = sha_acc_op.stream_384(data.len() as u32).unwrap();
+        stream.update(&data[..64]).unwrap();
+        assert_eq!(
+            stream.finalize((&mut digest).into()),
+            Err(CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH)
+        );
+
+        // and no more than `dlen` bytes may be streamed
+        let mut stream = sha_acc_op.stream_384(64).unwrap();
+        assert_eq!(
+            stream.update(data),
+            Err(CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH)
+        );
+
+        drop(sha_acc_op);
+    } else {
+        assert!(false);
+    };
+}
+
+fn test_detached_stream_digest() {
+    let mut sha_acc = unsafe { Sha2_512_384Acc::new(Sha512AccCsr::new()) };
+
+    let data = "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu".as_bytes();
+    let expected: [u8; SHA384_HASH_SIZE] = [
+        0x09, 0x33, 0x0C, 0x33, 0xF7, 0x11, 0x47, 0xE8, 0x3D, 0x19, 0x2F, 0xC7, 0x82, 0xCD, 0x1B,
+        0x47, 0x53, 0x11, 0x1B, 0x17, 0x3B, 0x3B, 0x05, 0xD2, 0x2F, 0xA0, 0x80, 0x86, 0xE3, 0xB0,
+        0xF7, 0x12, 0xFC, 0xC7, 0xC7, 0x1A, 0x55, 0x7E, 0x2D, 0xB9, 0x66, 0xC3, 0xE9, 0xFA, 0x91,
+        0x74, 0x60, 0x39,
+    ];
+
+    let mut digest = Array4x12::default();
+    let mut stream = sha_acc
+        .acquire_with_timeout(1)
+        .unwrap()
+        .detach_stream_384(data.len() as u32)
+        .unwrap();
+    stream.update(&mut sha_acc, &data[..64]).unwrap();
+
+    // The lock is held between updates
+    assert_eq!(
+        sha_acc.acquire_with_timeout(1).err(),
+        Some(CaliptraError::DRIVER_SHA2_512_384ACC_LOCK_TIMEOUT)
+    );
+
+    stream.update(&mut sha_acc, &data[64..]).unwrap();
+    assert_eq!(stream.written(), data.len() as u32);
+    stream.finalize(&mut sha_acc, &mut digest).unwrap();
+    assert_eq!(digest, Array4x12::from(expected));
+
+    // Finalizing an incomplete stream, or aborting it, releases the lock
+    let mut stream = sha_acc
+        .acquire_with_timeout(1)
+        .unwrap()
+        .detach_stream_384(data.len() as u32)
+        .unwrap();
+    stream.update(&mut sha_acc, &data[..64]).unwrap();
+    assert_eq!(
+        stream.finalize(&mut sha_acc, &mut digest),
+        Err(CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH)
+    );
+    let stream = sha_acc
+        .acquire_with_timeout(1)
+        .unwrap()
+        .detach_stream_384(data.len() as u32)
+        .unwrap();
+    stream.abort(&mut sha_acc);
+    drop(sha_acc.acquire_with_timeout(1).unwrap());
+}
+
 fn test_kat() {
     let mut sha_acc = unsafe { Sha2_512_384Acc::new(Sha512AccCsr::new()) };
     assert_eq!(
@@ -381,6 +474,28 @@ fn test_kat() {
     );
 }
 
+fn test_acquire_with_timeout() {
+    let mut sha_acc = unsafe { Sha2_512_384Acc::new(Sha512AccCsr::new()) };
+
+    // Uncontended acquisition succeeds on the first poll.
+    drop(sha_acc.acquire_with_timeout(1).unwrap());
+    assert_eq!(sha_acc.lock_contention_count(), 0);
+
+    // Hold the lock and verify the acquisition times out.
+    let op = sha_acc.acquire_with_timeout(1).unwrap();
+    let mut contender = unsafe { Sha2_512_384Acc::new(Sha512AccCsr::new()) };
+    assert_eq!(
+        contender.acquire_with_timeout(10).err(),
+        Some(CaliptraError::DRIVER_SHA2_512_384ACC_LOCK_TIMEOUT)
+    );
+    assert_eq!(contender.lock_contention_count(), 10);
+    drop(op);
+
+    // Once released, the contender can acquire it.
+    drop(contender.acquire_with_timeout(10).unwrap());
+    assert_eq!(contender.lock_contention_count(), 10);
+}
+
 test_suite! {
     test_kat,
     test_digest_max_mailbox_size,
@@ -389,4 +504,7 @@ test_suite! {
     test_digest1,
     test_digest2,
     test_digest_zero_size_buffer,
+    test_stream_digest,
+    test_detached_stream_digest,
+    test_acquire_with_timeout,
 }
@@ -709,7 +709,7 @@ fn test_uc_to_soc_error_state() {
     assert!(model.soc_mbox().status().read().mbox_fsm_ps().mbox_error());
 
     // Wait for the test-case to force unlock the mailbox
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     let _txn = model.wait_for_mailbox_receive().unwrap();
     model.soc_mbox().execute().write(|w| w.execute(true));
@@ -717,7 +717,7 @@ fn test_uc_to_soc_error_state() {
     assert!(model.soc_mbox().status().read().mbox_fsm_ps().mbox_error());
 
     // Wait for the test-case to force unlock the mailbox
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 }
 
 #[test]
@@ -1107,6 +1107,38 @@ fn test_trng_in_etrng_mode() {
     assert_eq!(trng_block, Some(block1.as_bytes().to_vec()));
 }
 
+#[test]
+#[cfg_attr(any(feature = "verilator", feature = "fpga_realtime"), ignore)]
+fn test_trng_deterministic_drbg() {
+    let rom = caliptra_builder::build_firmware_rom(
+        &firmware::driver_tests::TRNG_DRIVER_RESPONDER_DETERMINISTIC_DRBG,
+    )
+    .unwrap();
+
+    let trng_blocks = |drbg_seed: [u32; 12]| {
+        // The raw entropy differs on every run
+        let mut model = caliptra_hw_model::new(
+            InitParams {
+                rom: &rom,
+                trng_mode: Some(TrngMode::Internal),
+                drbg_seed,
+                ..default_init_params()
+            },
+            BootParams::default(),
+        )
+        .unwrap();
+        [
+            model.mailbox_execute(0, &[]).unwrap().unwrap(),
+            model.mailbox_execute(0, &[]).unwrap().unwrap(),
+        ]
+    };
+
+    let blocks = trng_blocks([0x1234_5678; 12]);
+    assert_ne!(blocks[0], blocks[1]);
+    assert_eq!(trng_blocks([0x1234_5678; 12]), blocks);
+    assert_ne!(trng_blocks([0x8765_4321; 12]), blocks);
+}
+
 #[test]
 fn test_persistent() {
     run_driver_test(&firmware::driver_tests::PERSISTENT);
@@ -119,6 +119,8 @@ impl CaliptraError {
         CaliptraError::new_const(0x00060001);
     pub const DRIVER_KV_ERASE_WRITE_LOCK_SET_FAILURE: CaliptraError =
         CaliptraError::new_const(0x00060002);
+    pub const DRIVER_KV_REKEY_SAME_SLOT: CaliptraError = CaliptraError::new_const(0x00060003);
+    pub const DRIVER_KV_REKEY_OUTPUT_LOCKED: CaliptraError = CaliptraError::new_const(0x00060004);
 
     pub const DRIVER_PCR_BANK_ERASE_WRITE_LOCK_SET_FAILURE: CaliptraError =
         CaliptraError::new_const(0x00070001);
@@ -133,6 +135,10 @@ impl CaliptraError {
     /// SHA2_512_384ACC Errors.
     pub const DRIVER_SHA2_512_384ACC_INDEX_OUT_OF_BOUNDS: CaliptraError =
         CaliptraError::new_const(0x00090003);
+    pub const DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x00090004);
+    pub const DRIVER_SHA2_512_384ACC_LOCK_TIMEOUT: CaliptraError =
+        CaliptraError::new_const(0x00090005);
     /// SHA1 Errors.
     pub const DRIVER_SHA1_INVALID_STATE: CaliptraError = CaliptraError::new_const(0x000a0001);
     pub const DRIVER_SHA1_MAX_DATA: CaliptraError = CaliptraError::new_const(0x000a0002);
@@ -262,6 +268,40 @@ impl CaliptraError {
         CaliptraError::new_const(0x000b0040);
     pub const IMAGE_VERIFIER_ERR_DIGEST_OUT_OF_BOUNDS: CaliptraError =
         CaliptraError::new_const(0x000b0041);
+    pub const IMAGE_VERIFIER_ERR_NEXT_OWNER_PUB_KEY_DIGEST_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000b0042);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_INDEX_OUT_OF_BOUNDS: CaliptraError =
+        CaliptraError::new_const(0x000b0043);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_REVOKED: CaliptraError =
+        CaliptraError::new_const(0x000b0044);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_DIGEST_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x000b0045);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID_ARG: CaliptraError =
+        CaliptraError::new_const(0x000b0046);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_VERIFY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x000b0047);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b0048);
+    pub const IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_OFFSET_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b0049);
+    pub const IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_LOAD_ADDR_OVERLAP: CaliptraError =
+        CaliptraError::new_const(0x000b004a);
+    pub const IMAGE_VERIFIER_ERR_TOC_ENTRY_COMPRESSION_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b004b);
+    pub const IMAGE_VERIFIER_ERR_BREAK_GLASS_OWNER_PUB_KEY_NOT_IN_FUSES: CaliptraError =
+        CaliptraError::new_const(0x000b004c);
+    pub const IMAGE_VERIFIER_ERR_UNPIN_VENDOR_KEY_OWNER_PUB_KEY_NOT_IN_FUSES: CaliptraError =
+        CaliptraError::new_const(0x000b004d);
+    pub const IMAGE_VERIFIER_ERR_MANIFEST_FORMAT_VERSION_UNSUPPORTED: CaliptraError =
+        CaliptraError::new_const(0x000b004e);
+    pub const IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b004f);
+    pub const IMAGE_VERIFIER_ERR_BREAK_GLASS_UPDATE_RESET: CaliptraError =
+        CaliptraError::new_const(0x000b0050);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SVN_GREATER_THAN_MAX_SUPPORTED:
+        CaliptraError = CaliptraError::new_const(0x000b0051);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SVN_LESS_THAN_FUSE: CaliptraError =
+        CaliptraError::new_const(0x000b0052);
 
     /// Driver Error: LMS
     pub const DRIVER_LMS_INVALID_LMS_ALGO_TYPE: CaliptraError =
@@ -304,6 +344,8 @@ impl CaliptraError {
         CaliptraError::new_const(0x000d0007);
     pub const DRIVER_CSRNG_ADAPTP_HEALTH_CHECK_FAILED: CaliptraError =
         CaliptraError::new_const(0x000d0008);
+    pub const DRIVER_CSRNG_RAW_ENTROPY_NOT_CAPTURING: CaliptraError =
+        CaliptraError::new_const(0x000d0009);
 
     pub const DRIVER_HANDOFF_INVALID_VAULT: CaliptraError = CaliptraError::new_const(0x000D100);
     pub const DRIVER_HANDOFF_INVALID_KEY_ID: CaliptraError = CaliptraError::new_const(0x000D101);
@@ -464,6 +506,97 @@ impl CaliptraError {
     pub const RUNTIME_GET_FMC_CSR_UNSUPPORTED_FMC: CaliptraError =
         CaliptraError::new_const(0x000E0055);
 
+    pub const RUNTIME_LOCALITY_REVOKED: CaliptraError = CaliptraError::new_const(0x000E0058);
+    pub const RUNTIME_REVOKED_LOCALITY_LIMIT_REACHED: CaliptraError =
+        CaliptraError::new_const(0x000E0059);
+    pub const RUNTIME_REVOKE_LOCALITY_FAILED: CaliptraError = CaliptraError::new_const(0x000E005A);
+    pub const RUNTIME_INJECT_ERROR_NOT_ALLOWED: CaliptraError =
+        CaliptraError::new_const(0x000E005B);
+    pub const RUNTIME_INJECT_ERROR_INVALID_TYPE: CaliptraError =
+        CaliptraError::new_const(0x000E005C);
+    pub const RUNTIME_INJECTED_NON_FATAL_ERROR: CaliptraError =
+        CaliptraError::new_const(0x000E005D);
+    pub const RUNTIME_INJECTED_FATAL_ERROR: CaliptraError = CaliptraError::new_const(0x000E005E);
+    pub const RUNTIME_FIPS_ERROR_STATE: CaliptraError = CaliptraError::new_const(0x000E005F);
+    pub const RUNTIME_PRIVILEGED_OP_NOT_ALLOWED: CaliptraError =
+        CaliptraError::new_const(0x000E0060);
+    pub const RUNTIME_FUSE_SVN_ANTI_ROLLBACK_DISABLED: CaliptraError =
+        CaliptraError::new_const(0x000E0061);
+    pub const RUNTIME_FUSE_SVN_NOT_INCREASED: CaliptraError = CaliptraError::new_const(0x000E0062);
+    pub const RUNTIME_DPE_CONTEXT_NOT_RECLAIMED: CaliptraError =
+        CaliptraError::new_const(0x000E0063);
+    pub const RUNTIME_OWNER_KEY_ROTATION_NOT_PENDING: CaliptraError =
+        CaliptraError::new_const(0x000E0064);
+    pub const RUNTIME_OWNER_KEY_ROTATION_DIGEST_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000E0065);
+    pub const RUNTIME_OWNER_KEY_ROTATION_KEY_NOT_IN_USE: CaliptraError =
+        CaliptraError::new_const(0x000E0066);
+    pub const RUNTIME_MAILBOX_CMD_CANCELED: CaliptraError = CaliptraError::new_const(0x000E0067);
+    pub const RUNTIME_CERT_POLICY_ALREADY_SET: CaliptraError = CaliptraError::new_const(0x000E0068);
+    pub const RUNTIME_CERT_POLICY_VIOLATION: CaliptraError = CaliptraError::new_const(0x000E0069);
+    pub const RUNTIME_RAW_ENTROPY_NOT_MANUFACTURING: CaliptraError =
+        CaliptraError::new_const(0x000E006A);
+    pub const RUNTIME_RAW_ENTROPY_CAPTURE_NOT_STARTED: CaliptraError =
+        CaliptraError::new_const(0x000E006B);
+    pub const RUNTIME_CMD_UNSUPPORTED_VERSION: CaliptraError = CaliptraError::new_const(0x000E006C);
+    pub const RUNTIME_GET_RT_ALIAS_CSR_FAILED: CaliptraError = CaliptraError::new_const(0x000E006D);
+    pub const RUNTIME_IDEV_CERT_CHAIN_TOO_LONG: CaliptraError =
+        CaliptraError::new_const(0x000E006E);
+    pub const RUNTIME_IDEV_CERT_CHAIN_INVALID_CERT: CaliptraError =
+        CaliptraError::new_const(0x000E006F);
+    pub const RUNTIME_IDEV_CERT_CHAIN_INVALID_ORDER: CaliptraError =
+        CaliptraError::new_const(0x000E0070);
+    pub const RUNTIME_CERT_DIST_POINT_INVALID_URI: CaliptraError =
+        CaliptraError::new_const(0x000E0071);
+    pub const RUNTIME_CMD_NOT_AUTHORIZED: CaliptraError = CaliptraError::new_const(0x000E0072);
+    pub const RUNTIME_CWT_ENCODING_FAILED: CaliptraError = CaliptraError::new_const(0x000E0073);
+    pub const RUNTIME_JOB_CMD_NOT_SUPPORTED: CaliptraError = CaliptraError::new_const(0x000E0074);
+    pub const RUNTIME_JOB_QUEUE_FULL: CaliptraError = CaliptraError::new_const(0x000E0075);
+    pub const RUNTIME_JOB_NOT_FOUND: CaliptraError = CaliptraError::new_const(0x000E0076);
+    pub const RUNTIME_BATCH_CMD_NOT_SUPPORTED: CaliptraError = CaliptraError::new_const(0x000E0077);
+    pub const RUNTIME_SUSPENDED: CaliptraError = CaliptraError::new_const(0x000E0078);
+    pub const RUNTIME_NOT_SUSPENDED: CaliptraError = CaliptraError::new_const(0x000E0079);
+    pub const RUNTIME_SUSPEND_CHECKPOINT_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000E007A);
+    pub const RUNTIME_WDT_TIMEOUT_INVALID: CaliptraError = CaliptraError::new_const(0x000E007B);
+    pub const RUNTIME_OBJECT_SCHEMAS_ALREADY_SET: CaliptraError =
+        CaliptraError::new_const(0x000E007C);
+    pub const RUNTIME_OBJECT_SCHEMAS_NOT_SET: CaliptraError = CaliptraError::new_const(0x000E007D);
+    pub const RUNTIME_STACK_OVERFLOW: CaliptraError = CaliptraError::new_const(0x000E007E);
+    pub const RUNTIME_LOG_LEVEL_INVALID: CaliptraError = CaliptraError::new_const(0x000E007F);
+    pub const RUNTIME_HANDOFF_FHT_MAC_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000E0080);
+    pub const RUNTIME_GET_FHT_NOT_ALLOWED: CaliptraError = CaliptraError::new_const(0x000E0081);
+    pub const RUNTIME_NMI_INTERNAL_ERROR: CaliptraError = CaliptraError::new_const(0x000E0082);
+    pub const RUNTIME_NMI_INVALID_DEVICE: CaliptraError = CaliptraError::new_const(0x000E0083);
+    pub const RUNTIME_NMI_MBOX_PROTOCOL_ERROR: CaliptraError = CaliptraError::new_const(0x000E0084);
+    pub const RUNTIME_NMI_BAD_FUSE: CaliptraError = CaliptraError::new_const(0x000E0085);
+    pub const RUNTIME_NMI_ICCM_BLOCKED: CaliptraError = CaliptraError::new_const(0x000E0086);
+    pub const RUNTIME_NMI_MBOX_ECC_UNC: CaliptraError = CaliptraError::new_const(0x000E0087);
+    pub const RUNTIME_MAILBOX_CMD_ABORTED: CaliptraError = CaliptraError::new_const(0x000E0088);
+    pub const RUNTIME_SLEEP_JOB_PENDING: CaliptraError = CaliptraError::new_const(0x000E0089);
+    pub const RUNTIME_MCTP_INVALID_PACKET: CaliptraError = CaliptraError::new_const(0x000E008A);
+    pub const RUNTIME_MCTP_UNSUPPORTED_MSG_TYPE: CaliptraError =
+        CaliptraError::new_const(0x000E008B);
+    pub const RUNTIME_MEASUREMENT_MANIFEST_SIGNATURE_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000E008C);
+    pub const RUNTIME_MEASUREMENT_MANIFEST_ALREADY_SET: CaliptraError =
+        CaliptraError::new_const(0x000E008D);
+    pub const RUNTIME_MEASUREMENT_LOG_FULL: CaliptraError = CaliptraError::new_const(0x000E008E);
+    pub const RUNTIME_STREAM_VERIFY_NOT_STARTED: CaliptraError =
+        CaliptraError::new_const(0x000E008F);
+    pub const RUNTIME_STREAM_VERIFY_IN_PROGRESS: CaliptraError =
+        CaliptraError::new_const(0x000E0090);
+    pub const RUNTIME_SEALED_KEY_INVALID: CaliptraError = CaliptraError::new_const(0x000E0091);
+    pub const RUNTIME_SEALED_KEY_POLICY_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000E0092);
+    pub const RUNTIME_SEALED_KEY_UNAVAILABLE: CaliptraError = CaliptraError::new_const(0x000E0093);
+    pub const RUNTIME_COUNTER_UNAVAILABLE: CaliptraError = CaliptraError::new_const(0x000E0094);
+    pub const RUNTIME_COUNTER_NOT_CREATED: CaliptraError = CaliptraError::new_const(0x000E0095);
+    pub const RUNTIME_COUNTER_EXHAUSTED: CaliptraError = CaliptraError::new_const(0x000E0096);
+    pub const RUNTIME_MAILBOX_PAIRING_FAILED: CaliptraError = CaliptraError::new_const(0x000E0097);
+    pub const RUNTIME_MAILBOX_AUTH_FAILED: CaliptraError = CaliptraError::new_const(0x000E0098);
+
     /// FMC Errors
     pub const FMC_GLOBAL_NMI: CaliptraError = CaliptraError::new_const(0x000F0001);
     pub const FMC_GLOBAL_EXCEPTION: CaliptraError = CaliptraError::new_const(0x000F0002);
@@ -489,9 +622,15 @@ impl CaliptraError {
     pub const FMC_ALIAS_CSR_VERIFICATION_FAILURE: CaliptraError =
         CaliptraError::new_const(0x000F0012);
     pub const FMC_ALIAS_CSR_OVERFLOW: CaliptraError = CaliptraError::new_const(0x000F0013);
+    pub const FMC_RT_ALIAS_ADD_EXTENSIONS_FAILED: CaliptraError =
+        CaliptraError::new_const(0x000F0014);
 
     /// TRNG_EXT Errors
     pub const DRIVER_TRNG_EXT_TIMEOUT: CaliptraError = CaliptraError::new_const(0x00100001);
+    pub const DRIVER_TRNG_RAW_ENTROPY_UNAVAILABLE: CaliptraError =
+        CaliptraError::new_const(0x00100002);
+    pub const DRIVER_TRNG_HEALTH_TEST_STATS_UNAVAILABLE: CaliptraError =
+        CaliptraError::new_const(0x00100003);
 
     /// SOC_IFC driver Errors
     pub const DRIVER_SOC_IFC_INVALID_TIMER_CONFIG: CaliptraError =
@@ -500,6 +639,15 @@ impl CaliptraError {
     /// Bounded address Errors
     pub const ADDRESS_MISALIGNED: CaliptraError = CaliptraError::new_const(0x00110000);
     pub const ADDRESS_NOT_IN_ROM: CaliptraError = CaliptraError::new_const(0x00110001);
+    pub const ADDRESS_NOT_IN_DCCM: CaliptraError = CaliptraError::new_const(0x00110002);
+
+    /// Software ECC-256 Errors
+    pub const DRIVER_ECC256_SW_KEY_GEN_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x00120001);
+    pub const DRIVER_ECC256_SW_SIGN_FAILURE: CaliptraError = CaliptraError::new_const(0x00120002);
+    pub const DRIVER_ECC256_SW_SIGNATURE_VERIFY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x00120003);
+    pub const DRIVER_ECC256_SW_ECDH_FAILURE: CaliptraError = CaliptraError::new_const(0x00120004);
 
     /// Initial Device ID Errors
     pub const ROM_IDEVID_CSR_BUILDER_INIT_FAILURE: CaliptraError =
@@ -531,6 +679,8 @@ impl CaliptraError {
     pub const FW_PROC_MAILBOX_RESERVED_PAUSER: CaliptraError = CaliptraError::new_const(0x01020009);
     pub const FW_PROC_MAILBOX_GET_IDEV_CSR_UNPROVISIONED_CSR: CaliptraError =
         CaliptraError::new_const(0x0102000A);
+    pub const FW_PROC_IMAGE_DECOMPRESSION_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x0102000B);
 
     /// FMC Alias Layer : Certificate Verification Failure.
     pub const FMC_ALIAS_CERT_VERIFY: CaliptraError = CaliptraError::new_const(0x01030001);
@@ -552,6 +702,27 @@ impl CaliptraError {
     pub const ROM_WARM_RESET_UNSUCCESSFUL_PREVIOUS_UPDATE_RESET: CaliptraError =
         CaliptraError::new_const(0x01040011);
 
+    pub const ROM_WARM_RESET_FMC_TCI_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040012);
+
+    pub const ROM_WARM_RESET_OWNER_PUB_KEY_HASH_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040013);
+
+    pub const ROM_WARM_RESET_FMC_SVN_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040014);
+
+    pub const ROM_WARM_RESET_FMC_ENTRY_POINT_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040015);
+
+    pub const ROM_WARM_RESET_ECC_VENDOR_PUB_KEY_INDEX_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040016);
+
+    pub const ROM_WARM_RESET_LMS_VENDOR_PUB_KEY_INDEX_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040017);
+
+    pub const ROM_WARM_RESET_COLD_BOOT_STATUS_NOT_LOCKED: CaliptraError =
+        CaliptraError::new_const(0x01040018);
+
     /// Unknown Reset Error
     pub const ROM_UNKNOWN_RESET_FLOW: CaliptraError = CaliptraError::new_const(0x01040020);
 
@@ -636,6 +807,16 @@ impl CaliptraError {
 
     pub const ROM_INTEGRITY_FAILURE: CaliptraError = CaliptraError::new_const(0x90080001);
 
+    pub const KAT_SHA3_384_DIGEST_MISMATCH: CaliptraError = CaliptraError::new_const(0x90090001);
+    pub const KAT_SHAKE256_DIGEST_MISMATCH: CaliptraError = CaliptraError::new_const(0x90090002);
+
+    pub const KAT_ECC256_SW_KEY_PAIR_GENERATE_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x900A0001);
+    pub const KAT_ECC256_SW_KEY_PAIR_VERIFY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x900A0002);
+    pub const KAT_ECC256_SW_SIGNATURE_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x900A0003);
+
     // TODO: What base value is right for this?
     // FIPS Hooks
     pub const FIPS_HOOKS_INJECTED_ERROR: CaliptraError = CaliptraError::new_const(0x90100000);
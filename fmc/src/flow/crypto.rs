@@ -12,12 +12,15 @@ use caliptra_drivers::okmutref;
 use zeroize::Zeroize;
 
 use caliptra_cfi_derive::cfi_impl_fn;
+#[cfg(feature = "hybrid-alias-key")]
+use caliptra_common::crypto::lms_alias_key_gen;
 use caliptra_common::{crypto::Ecc384KeyPair, keyids::KEY_ID_TMP};
 use caliptra_drivers::{
-    hmac384_kdf, okref, Array4x12, Array4x5, Array4x8, CaliptraResult, Ecc384PrivKeyIn,
-    Ecc384PrivKeyOut, Ecc384PubKey, Ecc384Result, Ecc384Signature, KeyId, KeyReadArgs, KeyUsage,
-    KeyWriteArgs, Sha256Alg,
+    okref, Array4x12, Array4x5, Array4x8, CaliptraResult, Ecc384PrivKeyIn, Ecc384PubKey,
+    Ecc384Result, Ecc384Signature, Kdf, KeyId, KeyReadArgs, Sha256Alg,
 };
+#[cfg(feature = "hybrid-alias-key")]
+use caliptra_drivers::{Lms, LmsPublicKey};
 
 pub trait Ecdsa384SignatureAdapter {
     /// Convert to ECDSA Signature
@@ -82,36 +85,49 @@ impl Crypto {
         env.sha384.digest(data)
     }
 
-    /// Calculate HMAC-384 KDF
+    /// Derive a CDI or key generation seed using the SP 800-108 KDF
     ///
     /// # Arguments
     ///
     /// * `env` - FMC Environment
     /// * `key` - HMAC384 key slot
-    /// * `label` - Input label
-    /// * `context` - Input context
+    /// * `label` - KDF label
+    /// * `context` - KDF context
     /// * `output` - Key slot to store the output
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
-    pub fn hmac384_kdf(
+    pub fn kdf(
         env: &mut FmcEnv,
         key: KeyId,
         label: &[u8],
         context: Option<&[u8]>,
         output: KeyId,
     ) -> CaliptraResult<()> {
-        hmac384_kdf(
-            &mut env.hmac384,
-            KeyReadArgs::new(key).into(),
+        Kdf::new(&mut env.hmac384, &mut env.trng).derive_cdi(key, label, context, output)
+    }
+
+    /// Re-key a CDI into a new key slot and retire the slot it was held in
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - FMC Environment
+    /// * `key` - Key slot holding the CDI to re-key
+    /// * `label` - KDF label
+    /// * `context` - KDF context
+    /// * `output` - Key slot to store the new CDI
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn rekey_cdi(
+        env: &mut FmcEnv,
+        key: KeyId,
+        label: &[u8],
+        context: Option<&[u8]>,
+        output: KeyId,
+    ) -> CaliptraResult<()> {
+        env.key_vault.rekey_cdi(
+            &mut Kdf::new(&mut env.hmac384, &mut env.trng),
+            key,
             label,
             context,
-            &mut env.trng,
-            KeyWriteArgs::new(
-                output,
-                KeyUsage::default()
-                    .set_hmac_key_en()
-                    .set_ecc_key_gen_seed_en(),
-            )
-            .into(),
+            output,
         )
     }
 
@@ -134,19 +150,9 @@ impl Crypto {
         label: &[u8],
         priv_key: KeyId,
     ) -> CaliptraResult<Ecc384KeyPair> {
-        Crypto::hmac384_kdf(env, cdi, label, None, KEY_ID_TMP)?;
-
-        let key_out = Ecc384PrivKeyOut::Key(KeyWriteArgs::new(
-            priv_key,
-            KeyUsage::default().set_ecc_private_key_en(),
-        ));
+        Crypto::kdf(env, cdi, label, None, KEY_ID_TMP)?;
 
-        let pub_key = env.ecc384.key_pair(
-            &KeyReadArgs::new(KEY_ID_TMP).into(),
-            &Array4x12::default(),
-            &mut env.trng,
-            key_out,
-        );
+        let pub_key = env.ecc384.keygen_to_kv(KEY_ID_TMP, &mut env.trng, priv_key);
         env.key_vault.erase_key(KEY_ID_TMP)?;
 
         Ok(Ecc384KeyPair {
@@ -155,6 +161,33 @@ impl Crypto {
         })
     }
 
+    /// Generate LMS Key Pair
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - FMC Environment
+    /// * `cdi` - Key slot to retrieve the CDI from
+    /// * `label` - Diversification label
+    ///
+    /// # Returns
+    ///
+    /// * `LmsPublicKey` - Public key. The private key is not retained.
+    #[cfg(feature = "hybrid-alias-key")]
+    pub fn lms_key_gen(
+        env: &mut FmcEnv,
+        cdi: KeyId,
+        label: &[u8],
+    ) -> CaliptraResult<LmsPublicKey<6>> {
+        lms_alias_key_gen(
+            &mut env.hmac384,
+            &mut env.trng,
+            &mut env.sha256,
+            &Lms::default(),
+            cdi,
+            label,
+        )
+    }
+
     /// Sign data using ECC Private Key
     ///
     /// This routine calculates the digest of the `data` and signs the hash
@@ -23,6 +23,7 @@ mod x509;
 use crate::flow::rt_alias::RtAliasLayer;
 
 use crate::fmc_env::FmcEnv;
+use crate::wdt::FmcWdt;
 use caliptra_drivers::CaliptraResult;
 
 /// Execute FMC Flows based on reset resason
@@ -31,6 +32,8 @@ use caliptra_drivers::CaliptraResult;
 ///
 /// * `env` - FMC Environment
 pub fn run(env: &mut FmcEnv) -> CaliptraResult<()> {
+    let mut wdt = FmcWdt::start(env);
+
     {
         use caliptra_cfi_lib::cfi_assert_eq;
         use caliptra_drivers::ResetReason;
@@ -41,8 +44,11 @@ pub fn run(env: &mut FmcEnv) -> CaliptraResult<()> {
             cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::ColdReset);
             // Generate the FMC Alias Certificate Signing Request (CSR)
             fmc_alias_csr::generate_csr(env)?;
+            wdt.end_phase(env);
         }
     }
 
-    RtAliasLayer::run(env)
+    RtAliasLayer::run(env)?;
+    wdt.end_phase(env);
+    Ok(())
 }
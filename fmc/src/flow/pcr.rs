@@ -38,9 +38,10 @@ use zerocopy::IntoBytes;
 /// # Arguments
 ///
 /// * `env` - FMC Environment
-/// * `pcr_id` - PCR slot to extend the data into
+/// * `extend_journey_tci` - Extend the RT TCI into the journey PCR as well as
+///   the current PCR
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
-pub fn extend_pcr_common(env: &mut FmcEnv) -> CaliptraResult<()> {
+pub fn extend_pcr_common(env: &mut FmcEnv, extend_journey_tci: bool) -> CaliptraResult<()> {
     // Calculate RT TCI (Hash over runtime code)
     let rt_tci: [u8; 48] = HandOff::rt_tci(env).into();
 
@@ -51,26 +52,31 @@ pub fn extend_pcr_common(env: &mut FmcEnv) -> CaliptraResult<()> {
     // Clear current PCR before extending it.
     env.pcr_bank.erase_pcr(RT_FW_CURRENT_PCR)?;
 
-    extend_and_log(env, PcrLogEntryId::RtTci, &rt_tci)?;
-    extend_and_log(env, PcrLogEntryId::FwImageManifest, &manifest_digest)?;
+    extend_and_log(env, PcrLogEntryId::RtTci, &rt_tci, extend_journey_tci)?;
+    extend_and_log(env, PcrLogEntryId::FwImageManifest, &manifest_digest, true)?;
 
     Ok(())
 }
 
-/// Extend `data` into both the current and journey PCRs, and updates the PCR log.
+/// Extend `data` into the current PCR, and optionally the journey PCR, and
+/// updates the PCR log.
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
-fn extend_and_log(env: &mut FmcEnv, entry_id: PcrLogEntryId, data: &[u8]) -> CaliptraResult<()> {
+fn extend_and_log(
+    env: &mut FmcEnv,
+    entry_id: PcrLogEntryId,
+    data: &[u8],
+    extend_journey: bool,
+) -> CaliptraResult<()> {
+    let mut pcr_ids = 1 << RT_FW_CURRENT_PCR as u8;
     env.pcr_bank
         .extend_pcr(RT_FW_CURRENT_PCR, &mut env.sha384, data)?;
-    env.pcr_bank
-        .extend_pcr(RT_FW_JOURNEY_PCR, &mut env.sha384, data)?;
-
-    log_pcr(
-        env.persistent_data.get_mut(),
-        entry_id,
-        (1 << RT_FW_CURRENT_PCR as u8) | (1 << RT_FW_JOURNEY_PCR as u8),
-        data,
-    )
+    if extend_journey {
+        env.pcr_bank
+            .extend_pcr(RT_FW_JOURNEY_PCR, &mut env.sha384, data)?;
+        pcr_ids |= 1 << RT_FW_JOURNEY_PCR as u8;
+    }
+
+    log_pcr(env.persistent_data.get_mut(), entry_id, pcr_ids, data)
 }
 
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
@@ -22,18 +22,32 @@ use crate::flow::x509::X509;
 use crate::fmc_env::FmcEnv;
 use crate::FmcBootStatus;
 use crate::HandOff;
-use caliptra_common::cprintln;
 use caliptra_common::crypto::Ecc384KeyPair;
-use caliptra_common::keyids::{KEY_ID_RT_CDI, KEY_ID_RT_PRIV_KEY, KEY_ID_TMP};
+use caliptra_common::keyids::{
+    KEY_ID_FHT_MAC, KEY_ID_RT_CDI, KEY_ID_RT_CDI_ALT, KEY_ID_RT_PRIV_KEY, KEY_ID_RT_PRIV_KEY_ALT,
+    KEY_ID_TMP,
+};
 use caliptra_common::HexBytes;
+use caliptra_common::RomBootStatus;
+use caliptra_common::{log_info, log_trace, log_warn};
+#[cfg(feature = "hybrid-alias-key")]
+use caliptra_drivers::KDF_LABEL_RT_ALIAS_LMS_KEYGEN;
 use caliptra_drivers::{
-    okref, report_boot_status, CaliptraError, CaliptraResult, Ecc384Result, KeyId, PersistentData,
-    ResetReason,
+    okref, report_boot_status, CaliptraError, CaliptraResult, CertDistPoints, Ecc384Result, KeyId,
+    PersistentData, ResetReason, CERT_DIST_POINT_URI_MAX_SIZE, KDF_LABEL_RT_ALIAS_CDI,
+    KDF_LABEL_RT_ALIAS_KEYGEN, KDF_LABEL_RT_ALIAS_REKEY, RTALIAS_TBS_SIZE,
+};
+use caliptra_x509::{
+    add_extensions, encode_authority_info_access, encode_crl_distribution_point, NotAfter,
+    NotBefore, RtAliasCertTbs, RtAliasCertTbsParams, X509Extension, AUTHORITY_INFO_ACCESS_OID,
+    CRL_DISTRIBUTION_POINTS_OID,
 };
-use caliptra_x509::{NotAfter, NotBefore, RtAliasCertTbs, RtAliasCertTbsParams};
 
 const SHA384_HASH_SIZE: usize = 48;
 
+/// Maximum size of an encoded distribution point extension value
+const DIST_POINT_EXT_MAX_SIZE: usize = CERT_DIST_POINT_URI_MAX_SIZE + 32;
+
 #[derive(Default)]
 pub struct RtAliasLayer {}
 
@@ -49,21 +63,42 @@ impl RtAliasLayer {
             return Err(CaliptraError::FMC_ALIAS_KV_COLLISION);
         }
 
-        cprintln!("[art] Derive CDI");
-        cprintln!("[art] Store in in slot 0x{:x}", KEY_ID_RT_CDI as u8);
+        let rotate = Self::rotate_keys(env);
+        let (rt_cdi, rt_priv_key) = if rotate {
+            Self::next_key_slots(env)?
+        } else {
+            (KEY_ID_RT_CDI, KEY_ID_RT_PRIV_KEY)
+        };
+
+        log_info!("[art] Derive CDI");
+        log_trace!("[art] Store in in slot 0x{:x}", rt_cdi as u8);
 
         // Derive CDI
-        Self::derive_cdi(env, input.cdi, KEY_ID_RT_CDI)?;
+        if rotate {
+            Self::rekey_cdi(env, rt_cdi)?;
+        } else {
+            Self::derive_cdi(env, input.cdi, rt_cdi)?;
+        }
         report_boot_status(FmcBootStatus::RtAliasDeriveCdiComplete as u32);
-        cprintln!("[art] Derive Key Pair");
-        cprintln!(
-            "[art] Store priv key in slot 0x{:x}",
-            KEY_ID_RT_PRIV_KEY as u8
-        );
+        log_info!("[art] Derive Key Pair");
+        log_trace!("[art] Store priv key in slot 0x{:x}", rt_priv_key as u8);
 
         // Derive DICE Key Pair from CDI
-        let key_pair = Self::derive_key_pair(env, KEY_ID_RT_CDI, KEY_ID_RT_PRIV_KEY)?;
-        cprintln!("[art] Derive Key Pair - Done");
+        let key_pair = Self::derive_key_pair(env, rt_cdi, rt_priv_key)?;
+        log_info!("[art] Derive Key Pair - Done");
+
+        if rotate {
+            let prev_priv_key = HandOff::rt_priv_key(env)?;
+            env.key_vault.retire_key(prev_priv_key)?;
+            log_info!("[art] Retired previous RT alias keys");
+        }
+
+        #[cfg(feature = "hybrid-alias-key")]
+        {
+            log_info!("[art] Derive LMS Key");
+            let lms_pub_key = Crypto::lms_key_gen(env, rt_cdi, KDF_LABEL_RT_ALIAS_LMS_KEYGEN)?;
+            HandOff::set_rt_dice_lms_pub_key(env, &lms_pub_key);
+        }
         report_boot_status(FmcBootStatus::RtAliasKeyPairDerivationComplete as u32);
 
         // Generate the Subject Serial Number and Subject Key Identifier.
@@ -78,7 +113,7 @@ impl RtAliasLayer {
 
         // Generate the output for next layer
         let output = DiceOutput {
-            cdi: KEY_ID_RT_CDI,
+            cdi: rt_cdi,
             subj_key_pair: key_pair,
             subj_sn,
             subj_key_id,
@@ -94,23 +129,55 @@ impl RtAliasLayer {
     }
 
     fn kv_slot_collides(slot: KeyId) -> bool {
-        slot == KEY_ID_RT_CDI || slot == KEY_ID_RT_PRIV_KEY || slot == KEY_ID_TMP
+        slot == KEY_ID_RT_CDI
+            || slot == KEY_ID_RT_PRIV_KEY
+            || slot == KEY_ID_RT_CDI_ALT
+            || slot == KEY_ID_RT_PRIV_KEY_ALT
+            || slot == KEY_ID_FHT_MAC
+            || slot == KEY_ID_TMP
+    }
+
+    /// Whether the RT alias keys are rotated on this boot instead of being
+    /// derived from the FMC CDI. Rotation happens on update reset when FMC is
+    /// built with the `rt-alias-key-rotation` feature. It is skipped when
+    /// attestation is disabled, as the CDI held by the previous runtime is
+    /// then derived from a known value.
+    fn rotate_keys(env: &mut FmcEnv) -> bool {
+        cfg!(feature = "rt-alias-key-rotation")
+            && env.soc_ifc.reset_reason() == ResetReason::UpdateReset
+            && !env.persistent_data.get().attestation_disabled.get()
+    }
+
+    /// Key slots for the rotated RT alias CDI and private key. The keys
+    /// alternate between two pairs of slots so the slots being retired are
+    /// never the ones being written.
+    fn next_key_slots(env: &FmcEnv) -> CaliptraResult<(KeyId, KeyId)> {
+        if HandOff::rt_cdi(env)? == KEY_ID_RT_CDI {
+            Ok((KEY_ID_RT_CDI_ALT, KEY_ID_RT_PRIV_KEY_ALT))
+        } else {
+            Ok((KEY_ID_RT_CDI, KEY_ID_RT_PRIV_KEY))
+        }
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     #[inline(never)]
     pub fn run(env: &mut FmcEnv) -> CaliptraResult<()> {
         Self::extend_pcrs(env)?;
-        cprintln!("[art] Extend RT PCRs Done");
+        log_info!("[art] Extend RT PCRs Done");
 
         env.pcr_bank
             .set_pcr_lock(caliptra_common::RT_FW_CURRENT_PCR);
         env.pcr_bank
             .set_pcr_lock(caliptra_common::RT_FW_JOURNEY_PCR);
-        cprintln!("[art] Lock RT PCRs Done");
+        log_info!("[art] Lock RT PCRs Done");
 
         Self::populate_dv(env)?;
-        cprintln!("[art] Populate DV Done");
+        log_info!("[art] Populate DV Done");
+
+        // Distribution points set by the runtime only last until cold reset
+        if env.soc_ifc.reset_reason() == ResetReason::ColdReset {
+            env.persistent_data.get_mut().cert_dist_points = CertDistPoints::default();
+        }
         report_boot_status(crate::FmcBootStatus::RtMeasurementComplete as u32);
 
         // Retrieve Dice Input Layer from Hand Off and Derive Key
@@ -162,15 +229,22 @@ impl RtAliasLayer {
         match reset_reason {
             ResetReason::ColdReset => {
                 cfi_assert_eq(reset_reason, ResetReason::ColdReset);
-                extend_pcr_common(env)
+                extend_pcr_common(env, true)
             }
             ResetReason::UpdateReset => {
                 cfi_assert_eq(reset_reason, ResetReason::UpdateReset);
-                extend_pcr_common(env)
+                // ROM kept the running runtime, so its TCI is already part
+                // of the journey.
+                let no_op = env.data_vault.rom_update_reset_status()
+                    == u32::from(RomBootStatus::UpdateResetNoOpComplete);
+                if no_op {
+                    log_info!("[alias rt] Runtime unchanged, skip journey TCI extension");
+                }
+                extend_pcr_common(env, !no_op)
             }
             ResetReason::WarmReset => {
                 cfi_assert_eq(reset_reason, ResetReason::WarmReset);
-                cprintln!("[aliasrt :skip pcr extension");
+                log_warn!("[aliasrt :skip pcr extension");
                 Ok(())
             }
             ResetReason::Unknown => {
@@ -238,7 +312,32 @@ impl RtAliasLayer {
     /// * `rt_cdi` - Key Slot to store the generated CDI
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn derive_cdi(env: &mut FmcEnv, fmc_cdi: KeyId, rt_cdi: KeyId) -> CaliptraResult<()> {
-        // Compose FMC TCI (1. RT TCI, 2. Image Manifest Digest)
+        let tci = Self::tci(env)?;
+
+        // Permute CDI from FMC TCI
+        Crypto::kdf(env, fmc_cdi, KDF_LABEL_RT_ALIAS_CDI, Some(&tci), rt_cdi)?;
+        report_boot_status(FmcBootStatus::RtAliasDeriveCdiComplete as u32);
+        Ok(())
+    }
+
+    /// Rotate the RT Alias CDI by re-keying the CDI held by the previous
+    /// runtime with the new Rt TCI and Image Manifest Digest. The slot holding
+    /// the previous CDI is retired.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - FMC Environment
+    /// * `rt_cdi` - Key Slot to store the generated CDI
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    fn rekey_cdi(env: &mut FmcEnv, rt_cdi: KeyId) -> CaliptraResult<()> {
+        let tci = Self::tci(env)?;
+        let prev_cdi = HandOff::rt_cdi(env)?;
+
+        Crypto::rekey_cdi(env, prev_cdi, KDF_LABEL_RT_ALIAS_REKEY, Some(&tci), rt_cdi)
+    }
+
+    /// Compose FMC TCI (1. RT TCI, 2. Image Manifest Digest)
+    fn tci(env: &mut FmcEnv) -> CaliptraResult<[u8; 2 * SHA384_HASH_SIZE]> {
         let mut tci = [0u8; 2 * SHA384_HASH_SIZE];
         let rt_tci: [u8; 48] = HandOff::rt_tci(env).into();
         tci[0..SHA384_HASH_SIZE].copy_from_slice(&rt_tci);
@@ -246,11 +345,7 @@ impl RtAliasLayer {
         let image_manifest_digest: Result<_, CaliptraError> = Tci::image_manifest_digest(env);
         let image_manifest_digest: [u8; 48] = okref(&image_manifest_digest)?.into();
         tci[SHA384_HASH_SIZE..2 * SHA384_HASH_SIZE].copy_from_slice(&image_manifest_digest);
-
-        // Permute CDI from FMC TCI
-        Crypto::hmac384_kdf(env, fmc_cdi, b"rt_alias_cdi", Some(&tci), rt_cdi)?;
-        report_boot_status(FmcBootStatus::RtAliasDeriveCdiComplete as u32);
-        Ok(())
+        Ok(tci)
     }
 
     /// Derive Dice Layer Key Pair
@@ -270,7 +365,7 @@ impl RtAliasLayer {
         cdi: KeyId,
         priv_key: KeyId,
     ) -> CaliptraResult<Ecc384KeyPair> {
-        let result = Crypto::ecc384_key_gen(env, cdi, b"rt_alias_keygen", priv_key);
+        let result = Crypto::ecc384_key_gen(env, cdi, KDF_LABEL_RT_ALIAS_KEYGEN, priv_key);
         if cfi_launder(result.is_ok()) {
             cfi_assert!(result.is_ok());
         } else {
@@ -323,9 +418,15 @@ impl RtAliasLayer {
 
         // Generate the `To Be Signed` portion of the CSR
         let tbs = RtAliasCertTbs::new(&params);
+        let mut tbs_buf = [0u8; RTALIAS_TBS_SIZE as usize];
+        let tbs = Self::add_dist_points(
+            tbs.tbs(),
+            &env.persistent_data.get().cert_dist_points,
+            &mut tbs_buf,
+        )?;
 
         // Sign the `To Be Signed` portion
-        cprintln!(
+        log_trace!(
             "[art] Signing Cert with AUTHO
             RITY.KEYID = {}",
             auth_priv_key as u8
@@ -334,40 +435,94 @@ impl RtAliasLayer {
         // Sign the AliasRt To Be Signed DER Blob with AliasFMC Private Key in Key Vault Slot 7
         // AliasRtTbsDigest = sha384_digest(AliasRtTbs) AliaRtTbsCertSig = ecc384_sign(KvSlot5, AliasFmcTbsDigest)
 
-        let sig = Crypto::ecdsa384_sign(env, auth_priv_key, auth_pub_key, tbs.tbs());
+        let sig = Crypto::ecdsa384_sign(env, auth_priv_key, auth_pub_key, tbs);
         let sig = okref(&sig)?;
         // Clear the authority private key
-        cprintln!("[art] Erasing AUTHORITY.KEYID = {}", auth_priv_key as u8);
+        log_trace!("[art] Erasing AUTHORITY.KEYID = {}", auth_priv_key as u8);
         // FMC ensures that CDIFMC and PrivateKeyFMC are locked to block further usage until the next boot.
         env.key_vault.set_key_use_lock(auth_priv_key);
         env.key_vault.set_key_use_lock(input.cdi);
 
         let _pub_x: [u8; 48] = (&pub_key.x).into();
         let _pub_y: [u8; 48] = (&pub_key.y).into();
-        cprintln!("[art] PUB.X = {}", HexBytes(&_pub_x));
-        cprintln!("[art] PUB.Y = {}", HexBytes(&_pub_y));
+        log_trace!("[art] PUB.X = {}", HexBytes(&_pub_x));
+        log_trace!("[art] PUB.Y = {}", HexBytes(&_pub_y));
 
         let _sig_r: [u8; 48] = (&sig.r).into();
         let _sig_s: [u8; 48] = (&sig.s).into();
-        cprintln!("[art] SIG.R = {}", HexBytes(&_sig_r));
-        cprintln!("[art] SIG.S = {}", HexBytes(&_sig_s));
+        log_trace!("[art] SIG.R = {}", HexBytes(&_sig_r));
+        log_trace!("[art] SIG.S = {}", HexBytes(&_sig_s));
 
         // Verify the signature of the `To Be Signed` portion
-        if Crypto::ecdsa384_verify(env, auth_pub_key, tbs.tbs(), sig)? != Ecc384Result::Success {
+        if Crypto::ecdsa384_verify(env, auth_pub_key, tbs, sig)? != Ecc384Result::Success {
             return Err(CaliptraError::FMC_RT_ALIAS_CERT_VERIFY);
         }
 
         HandOff::set_rt_dice_signature(env, sig);
 
         //  Copy TBS to DCCM and set size in FHT.
-        Self::copy_tbs(tbs.tbs(), env.persistent_data.get_mut())?;
-        HandOff::set_rtalias_tbs_size(env, tbs.tbs().len());
+        Self::copy_tbs(tbs, env.persistent_data.get_mut())?;
+        HandOff::set_rtalias_tbs_size(env, tbs.len());
 
         report_boot_status(FmcBootStatus::RtAliasCertSigGenerationComplete as u32);
 
         Ok(())
     }
 
+    /// Add the CRL distribution points and authority information access
+    /// extensions for the URIs provisioned through the runtime, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `tbs` - RT Alias TBS certificate
+    /// * `dist_points` - Provisioned distribution point URIs
+    /// * `buf` - Buffer to construct the extended TBS certificate in
+    ///
+    /// # Returns
+    ///
+    /// * `&[u8]` - TBS certificate to sign
+    fn add_dist_points<'a>(
+        tbs: &'a [u8],
+        dist_points: &CertDistPoints,
+        buf: &'a mut [u8],
+    ) -> CaliptraResult<&'a [u8]> {
+        const ERR: CaliptraError = CaliptraError::FMC_RT_ALIAS_ADD_EXTENSIONS_FAILED;
+
+        let mut crl_value = [0u8; DIST_POINT_EXT_MAX_SIZE];
+        let crl_len = match dist_points.crl_uri() {
+            Some(uri) => encode_crl_distribution_point(uri, &mut crl_value).ok_or(ERR)?,
+            None => 0,
+        };
+        let mut aia_value = [0u8; DIST_POINT_EXT_MAX_SIZE];
+        let aia_len = match dist_points.aia_uri() {
+            Some(uri) => encode_authority_info_access(uri, &mut aia_value).ok_or(ERR)?,
+            None => 0,
+        };
+
+        let exts = [
+            X509Extension {
+                oid: CRL_DISTRIBUTION_POINTS_OID,
+                critical: false,
+                value: crl_value.get(..crl_len).ok_or(ERR)?,
+            },
+            X509Extension {
+                oid: AUTHORITY_INFO_ACCESS_OID,
+                critical: false,
+                value: aia_value.get(..aia_len).ok_or(ERR)?,
+            },
+        ];
+        let exts = match (crl_len, aia_len) {
+            (0, 0) => return Ok(tbs),
+            (_, 0) => &exts[..1],
+            (0, _) => &exts[1..],
+            _ => &exts[..],
+        };
+
+        log_info!("[art] Adding distribution point extensions");
+        let len = add_extensions(tbs, exts, buf).ok_or(ERR)?;
+        buf.get(..len).ok_or(ERR)
+    }
+
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn copy_tbs(tbs: &[u8], persistent_data: &mut PersistentData) -> CaliptraResult<()> {
         let Some(dest) = persistent_data.rtalias_tbs.get_mut(..tbs.len()) else {
@@ -83,20 +83,32 @@ impl FmcEnv {
             &SocIfcReg::new(),
         )?;
 
+        // Prefer the capabilities recorded by ROM, probing the hardware only
+        // when running on a ROM that predates them.
+        let persistent_data = PersistentDataAccessor::new();
+        let soc_ifc = SocIfc::new(SocIfcReg::new());
+        let hw_caps = persistent_data
+            .get()
+            .fht
+            .hw_capabilities()
+            .unwrap_or_else(|| soc_ifc.hw_capabilities());
+        let mut sha256 = Sha256::new(Sha256Reg::new());
+        sha256.set_hw_capabilities(hw_caps);
+
         Ok(Self {
             sha1: Sha1::default(),
-            sha256: Sha256::new(Sha256Reg::new()),
+            sha256,
             sha384: Sha384::new(Sha512Reg::new()),
             sha2_512_384_acc: Sha2_512_384Acc::new(Sha512AccCsr::new()),
             hmac384: Hmac384::new(HmacReg::new()),
             ecc384: Ecc384::new(EccReg::new()),
             key_vault: KeyVault::new(KvReg::new()),
             data_vault: DataVault::new(DvReg::new()),
-            soc_ifc: SocIfc::new(SocIfcReg::new()),
+            soc_ifc,
             mbox: Mailbox::new(MboxCsr::new()),
             pcr_bank: PcrBank::new(PvReg::new()),
             trng,
-            persistent_data: PersistentDataAccessor::new(),
+            persistent_data,
         })
     }
 }
@@ -11,27 +11,25 @@ File Name:
         - Transfers control to the runtime firmware.
 ++*/
 
+use crate::flow::crypto::Crypto;
 use crate::flow::dice::DiceOutput;
 use crate::fmc_env::FmcEnv;
 use caliptra_cfi_derive::cfi_impl_fn;
+use caliptra_common::boot_profile::boot_checkpoint;
+use caliptra_common::keyids::KEY_ID_FHT_MAC;
 use caliptra_common::{handle_fatal_error, DataStore::*};
+use caliptra_common::{log_error, log_trace};
 use caliptra_common::{DataStore, FirmwareHandoffTable, HandOffDataHandle, Vault};
-use caliptra_drivers::{cprintln, memory_layout, Array4x12, Ecc384Signature, KeyId};
+#[cfg(feature = "hybrid-alias-key")]
+use caliptra_drivers::LmsPublicKey;
+use caliptra_drivers::{dv, Array4x12, DvEntry, DvValue, Ecc384Signature, KeyId};
+use caliptra_drivers::{BootCheckpoint, IccmAddr, KDF_LABEL_FHT_MAC};
 use caliptra_drivers::{Ecc384PubKey, Ecc384Scalar};
 use caliptra_error::{CaliptraError, CaliptraResult};
 
 #[cfg(feature = "riscv")]
 core::arch::global_asm!(include_str!("transfer_control.S"));
 
-pub struct IccmBounds {}
-impl caliptra_drivers::MemBounds for IccmBounds {
-    const ORG: usize = memory_layout::ICCM_ORG as usize;
-    const SIZE: usize = memory_layout::ICCM_SIZE as usize;
-    const ERROR: CaliptraError = CaliptraError::ADDRESS_NOT_IN_ICCM;
-}
-
-pub type IccmAddr<T> = caliptra_drivers::BoundedAddr<T, IccmBounds>;
-
 pub struct HandOff {}
 
 impl HandOff {
@@ -43,6 +41,21 @@ impl HandOff {
         &mut env.persistent_data.get_mut().fht
     }
 
+    /// Read data vault entry `E` through the FHT handle naming it
+    fn read_dv<E: DvEntry>(env: &FmcEnv, hdl: HandOffDataHandle) -> DvValue<E> {
+        Self::check_dv_hdl::<E>(hdl);
+        env.data_vault.read::<E>()
+    }
+
+    /// The FHT handle must name data vault entry `E`, so that FMC never reads
+    /// an entry of the wrong width or purpose.
+    fn check_dv_hdl<E: DvEntry>(hdl: HandOffDataHandle) {
+        if hdl != HandOffDataHandle::from(E::data_store()) {
+            log_error!("[fht] Invalid DV handle 0x{:08x}", hdl.0);
+            handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
+        }
+    }
+
     /// Retrieve FMC CDI
     pub fn fmc_cdi(env: &FmcEnv) -> KeyId {
         let ds: DataStore = Self::fht(env)
@@ -56,40 +69,30 @@ impl HandOff {
         }
     }
 
-    fn fmc_pub_key_x(env: &FmcEnv) -> Ecc384Scalar {
-        let ds: DataStore = Self::fht(env)
-            .fmc_pub_key_x_dv_hdl
-            .try_into()
-            .unwrap_or_else(|e: CaliptraError| {
-                cprintln!("[fht] Invalid FMC ALias Public Key X DV handle");
-                handle_fatal_error(e.into());
-            });
+    /// Retrieve the RT Alias CDI handed to the previous runtime
+    pub fn rt_cdi(env: &FmcEnv) -> CaliptraResult<KeyId> {
+        Self::kv_slot(Self::fht(env).rt_cdi_kv_hdl)
+    }
+
+    /// Retrieve the RT Alias private key handed to the previous runtime
+    pub fn rt_priv_key(env: &FmcEnv) -> CaliptraResult<KeyId> {
+        Self::kv_slot(Self::fht(env).rt_priv_key_kv_hdl)
+    }
 
-        // The data store is either a warm reset entry or a cold reset entry.
+    fn kv_slot(hdl: HandOffDataHandle) -> CaliptraResult<KeyId> {
+        let ds: CaliptraResult<DataStore> = hdl.try_into();
         match ds {
-            DataVaultNonSticky48(dv_entry) => env.data_vault.read_warm_reset_entry48(dv_entry),
-            DataVaultSticky48(dv_entry) => env.data_vault.read_cold_reset_entry48(dv_entry),
-            _ => handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into()),
+            Ok(KeyVaultSlot(key_id)) => Ok(key_id),
+            _ => Err(CaliptraError::FMC_HANDOFF_INVALID_PARAM),
         }
     }
 
-    fn fmc_pub_key_y(env: &FmcEnv) -> Ecc384Scalar {
-        let ds: DataStore = Self::fht(env)
-            .fmc_pub_key_y_dv_hdl
-            .try_into()
-            .unwrap_or_else(|e: CaliptraError| {
-                cprintln!("[fht] Invalid FMC ALias Public Key Y DV handle");
-                handle_fatal_error(e.into());
-            });
+    fn fmc_pub_key_x(env: &FmcEnv) -> Ecc384Scalar {
+        Self::read_dv::<dv::FmcPubKeyX>(env, Self::fht(env).fmc_pub_key_x_dv_hdl)
+    }
 
-        // The data store is either a warm reset entry or a cold reset entry.
-        match ds {
-            DataVaultNonSticky48(dv_entry) => env.data_vault.read_warm_reset_entry48(dv_entry),
-            DataVaultSticky48(dv_entry) => env.data_vault.read_cold_reset_entry48(dv_entry),
-            _ => {
-                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
-            }
-        }
+    fn fmc_pub_key_y(env: &FmcEnv) -> Ecc384Scalar {
+        Self::read_dv::<dv::FmcPubKeyY>(env, Self::fht(env).fmc_pub_key_y_dv_hdl)
     }
 
     /// Get the fmc public key.
@@ -110,24 +113,24 @@ impl HandOff {
             .fmc_priv_key_kv_hdl
             .try_into()
             .unwrap_or_else(|e: CaliptraError| {
-                cprintln!("[fht] Invalid FMC ALias Private Key KV handle");
+                log_error!("[fht] Invalid FMC ALias Private Key KV handle");
                 handle_fatal_error(e.into())
             });
 
         match ds {
             KeyVaultSlot(key_id) => {
-                cprintln!("[fht] FMC Alias Private Key: {:?}", u32::from(key_id));
+                log_trace!("[fht] FMC Alias Private Key: {:?}", u32::from(key_id));
                 key_id
             }
             _ => {
-                cprintln!("[fht] Invalid KeySlot DV Entry");
+                log_error!("[fht] Invalid KeySlot DV Entry");
                 handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into())
             }
         }
     }
 
     /// Transfer control to the runtime firmware.
-    pub fn to_rt(env: &FmcEnv) -> ! {
+    pub fn to_rt(env: &mut FmcEnv) -> ! {
         // Function is defined in start.S
         extern "C" {
             fn transfer_control(entry: u32) -> !;
@@ -136,98 +139,59 @@ impl HandOff {
         let rt_entry_point = Self::rt_entry_point(env);
 
         match IccmAddr::<u32>::validate_addr(rt_entry_point) {
-            Ok(_) => unsafe { transfer_control(rt_entry_point) },
+            Ok(_) => {
+                if let Err(e) = Self::set_fht_mac(env) {
+                    log_error!("[fht] Failed to MAC FHT");
+                    handle_fatal_error(e.into());
+                }
+                boot_checkpoint(BootCheckpoint::FmcExit);
+                unsafe { transfer_control(rt_entry_point) }
+            }
             Err(e) => {
-                cprintln!("[fht] Invalid RT Entry Point");
+                log_error!("[fht] Invalid RT Entry Point");
                 handle_fatal_error(e.into());
             }
         }
     }
 
+    /// MAC the handoff table with a key derived from the FMC CDI, so the
+    /// runtime can detect any modification made after FMC hands off.
+    ///
+    /// The key slot is write locked until the next reset.
+    fn set_fht_mac(env: &mut FmcEnv) -> CaliptraResult<()> {
+        let fmc_cdi = Self::fmc_cdi(env);
+        Crypto::kdf(env, fmc_cdi, KDF_LABEL_FHT_MAC, None, KEY_ID_FHT_MAC)?;
+        env.key_vault.set_key_write_lock(KEY_ID_FHT_MAC);
+
+        let mac = env.persistent_data.get().fht.compute_mac(
+            &mut env.hmac384,
+            &mut env.trng,
+            KEY_ID_FHT_MAC,
+        )?;
+        Self::fht_mut(env).fht_mac = mac;
+        Ok(())
+    }
+
     /// Retrieve runtime TCI (digest)
     pub fn rt_tci(env: &FmcEnv) -> Array4x12 {
-        let ds: DataStore =
-            Self::fht(env)
-                .rt_tci_dv_hdl
-                .try_into()
-                .unwrap_or_else(|e: CaliptraError| {
-                    cprintln!("[fht] Invalid TCI DV handle");
-                    handle_fatal_error(e.into())
-                });
-
-        // The data store is either a warm reset entry or a cold reset entry.
-        match ds {
-            DataVaultNonSticky48(dv_entry) => env.data_vault.read_warm_reset_entry48(dv_entry),
-            DataVaultSticky48(dv_entry) => env.data_vault.read_cold_reset_entry48(dv_entry),
-            _ => {
-                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
-            }
-        }
+        Self::read_dv::<dv::RtTci>(env, Self::fht(env).rt_tci_dv_hdl)
     }
 
     /// Retrieve runtime SVN.
     pub fn rt_svn(env: &FmcEnv) -> u32 {
-        let ds: DataStore =
-            Self::fht(env)
-                .rt_svn_dv_hdl
-                .try_into()
-                .unwrap_or_else(|e: CaliptraError| {
-                    cprintln!("[fht] Invalid RT SVN handle");
-                    handle_fatal_error(e.into())
-                });
-
-        // The data store is either a warm reset entry or a cold reset entry.
-        match ds {
-            DataVaultNonSticky4(dv_entry) => env.data_vault.read_warm_reset_entry4(dv_entry),
-            DataVaultSticky4(dv_entry) => env.data_vault.read_cold_reset_entry4(dv_entry),
-            _ => {
-                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
-            }
-        }
+        Self::read_dv::<dv::RtSvn>(env, Self::fht(env).rt_svn_dv_hdl)
     }
 
     /// Retrieve runtime minimum SVN.
     pub fn rt_min_svn(env: &FmcEnv) -> u32 {
-        let ds: DataStore =
-            Self::fht(env)
-                .rt_min_svn_dv_hdl
-                .try_into()
-                .unwrap_or_else(|e: CaliptraError| {
-                    cprintln!("[fht] Invalid RT Min SVN handle");
-                    handle_fatal_error(e.into())
-                });
-
-        // The data store must be a warm reset entry.
-        match ds {
-            DataVaultNonSticky4(dv_entry) => env.data_vault.read_warm_reset_entry4(dv_entry),
-            _ => {
-                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
-            }
-        }
+        Self::read_dv::<dv::RtMinSvn>(env, Self::fht(env).rt_min_svn_dv_hdl)
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn set_and_lock_rt_min_svn(env: &mut FmcEnv, min_svn: u32) -> CaliptraResult<()> {
-        let ds: DataStore =
-            Self::fht(env)
-                .rt_min_svn_dv_hdl
-                .try_into()
-                .unwrap_or_else(|e: CaliptraError| {
-                    cprintln!("[fht] Invalid RT Min SVN handle");
-                    handle_fatal_error(e.into())
-                });
-
-        // The data store must be a warm reset entry.
-        match ds {
-            DataVaultNonSticky4(dv_entry) => {
-                env.data_vault.write_warm_reset_entry4(dv_entry, min_svn);
-                env.data_vault.lock_warm_reset_entry4(dv_entry);
-                Ok(())
-            }
-            _ => {
-                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
-            }
-        }
+        Self::check_dv_hdl::<dv::RtMinSvn>(Self::fht(env).rt_min_svn_dv_hdl);
+        env.data_vault.write_lock::<dv::RtMinSvn>(&min_svn);
+        Ok(())
     }
 
     /// Store runtime Dice Signature
@@ -236,6 +200,12 @@ impl HandOff {
         Self::fht_mut(env).rt_dice_sign = *sig;
     }
 
+    /// Store runtime Dice LMS public key
+    #[cfg(feature = "hybrid-alias-key")]
+    pub fn set_rt_dice_lms_pub_key(env: &mut FmcEnv, pub_key: &LmsPublicKey<6>) {
+        Self::fht_mut(env).rt_dice_lms_pub_key = *pub_key;
+    }
+
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn set_rtalias_tbs_size(env: &mut FmcEnv, rtalias_tbs_size: usize) {
         Self::fht_mut(env).rtalias_tbs_size = rtalias_tbs_size as u16;
@@ -243,21 +213,7 @@ impl HandOff {
 
     /// Retrieve the entry point of the runtime firmware.
     fn rt_entry_point(env: &FmcEnv) -> u32 {
-        let ds: DataStore = Self::fht(env)
-            .rt_fw_entry_point_hdl
-            .try_into()
-            .unwrap_or_else(|e: CaliptraError| {
-                cprintln!("[fht] Invalid runtime entry point DV handle");
-                handle_fatal_error(e.into());
-            });
-        // The data store is either a warm reset entry or a cold reset entry.
-        match ds {
-            DataVaultNonSticky4(dv_entry) => env.data_vault.read_warm_reset_entry4(dv_entry),
-            DataVaultSticky4(dv_entry) => env.data_vault.read_cold_reset_entry4(dv_entry),
-            _ => {
-                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into());
-            }
-        }
+        Self::read_dv::<dv::RtEntryPoint>(env, Self::fht(env).rt_fw_entry_point_hdl)
     }
 
     #[allow(dead_code)]
@@ -13,12 +13,16 @@ Abstract:
 --*/
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(not(feature = "std"), no_main)]
+use caliptra_common::boot_profile::boot_checkpoint;
+use caliptra_common::{log_error, log_info};
+use caliptra_drivers::BootCheckpoint;
 use core::hint::black_box;
 
 use caliptra_cfi_lib::{cfi_assert_eq, CfiCounter};
 use caliptra_common::{
-    cprintln, handle_fatal_error,
+    handle_fatal_error,
     keyids::{KEY_ID_RT_CDI, KEY_ID_RT_PRIV_KEY},
+    PanicSite,
 };
 use caliptra_cpu::{log_trap_record, TrapRecord};
 
@@ -31,6 +35,7 @@ mod boot_status;
 mod flow;
 pub mod fmc_env;
 mod hand_off;
+mod wdt;
 
 pub use boot_status::FmcBootStatus;
 use caliptra_error::CaliptraError;
@@ -55,20 +60,21 @@ fn fix_fht(env: &mut fmc_env::FmcEnv) {
 
 #[no_mangle]
 pub extern "C" fn entry_point() -> ! {
-    cprintln!("{}", BANNER);
+    boot_checkpoint(BootCheckpoint::FmcEntry);
+    log_info!("{}", BANNER);
     let mut env = match unsafe { fmc_env::FmcEnv::new_from_registers() } {
         Ok(env) => env,
         Err(e) => handle_fatal_error(e.into()),
     };
 
     if !cfg!(feature = "no-cfi") {
-        cprintln!("[state] CFI Enabled");
+        log_info!("[state] CFI Enabled");
         let mut entropy_gen = || env.trng.generate().map(|a| a.0);
         CfiCounter::reset(&mut entropy_gen);
         CfiCounter::reset(&mut entropy_gen);
         CfiCounter::reset(&mut entropy_gen);
     } else {
-        cprintln!("[state] CFI Disabled");
+        log_info!("[state] CFI Disabled");
     }
 
     fix_fht(&mut env);
@@ -80,11 +86,11 @@ pub extern "C" fn entry_point() -> ! {
                 HandOffDataHandle::from(DataStore::KeyVaultSlot(KEY_ID_RT_CDI));
             env.persistent_data.get_mut().fht.rt_priv_key_kv_hdl =
                 HandOffDataHandle::from(DataStore::KeyVaultSlot(KEY_ID_RT_PRIV_KEY));
-            HandOff::to_rt(&env);
+            HandOff::to_rt(&mut env);
         }
         match flow::run(&mut env) {
             Ok(_) => match HandOff::is_ready_for_rt(&env) {
-                Ok(()) => HandOff::to_rt(&env),
+                Ok(()) => HandOff::to_rt(&mut env),
                 Err(e) => handle_fatal_error(e.into()),
             },
             Err(e) => handle_fatal_error(e.into()),
@@ -98,7 +104,7 @@ pub extern "C" fn entry_point() -> ! {
 #[inline(never)]
 #[allow(clippy::empty_loop)]
 extern "C" fn exception_handler(trap_record: &TrapRecord) {
-    cprintln!(
+    log_error!(
         "FMC EXCEPTION mcause=0x{:08X} mscause=0x{:08X} mepc=0x{:08X}",
         trap_record.mcause,
         trap_record.mscause,
@@ -125,7 +131,7 @@ extern "C" fn nmi_handler(trap_record: &TrapRecord) {
             .read(),
     );
     log_trap_record(trap_record, Some(err_interrupt_status));
-    cprintln!(
+    log_error!(
         "FMC NMI mcause=0x{:08X} mscause=0x{:08X} mepc=0x{:08X}error_internal_intr_r={:08X}",
         trap_record.mcause,
         trap_record.mscause,
@@ -136,7 +142,7 @@ extern "C" fn nmi_handler(trap_record: &TrapRecord) {
 
     let wdt_status = soc_ifc.regs().cptra_wdt_status().read();
     if wdt_status.t1_timeout() || wdt_status.t2_timeout() {
-        cprintln!("WDT Expired");
+        log_error!("WDT Expired");
         error = CaliptraError::FMC_GLOBAL_WDT_EXPIRED;
     }
 
@@ -145,7 +151,7 @@ extern "C" fn nmi_handler(trap_record: &TrapRecord) {
 
 #[no_mangle]
 extern "C" fn cfi_panic_handler(code: u32) -> ! {
-    cprintln!("[FMC] CFI Panic code=0x{:08X}", code);
+    log_error!("[FMC] CFI Panic code=0x{:08X}", code);
 
     handle_fatal_error(code);
 }
@@ -154,9 +160,16 @@ extern "C" fn cfi_panic_handler(code: u32) -> ! {
 #[inline(never)]
 #[cfg(not(feature = "std"))]
 #[allow(clippy::empty_loop)]
-fn fmc_panic(_: &core::panic::PanicInfo) -> ! {
-    cprintln!("FMC Panic!!");
+fn fmc_panic(info: &core::panic::PanicInfo) -> ! {
+    let site = PanicSite::from_panic_info(info);
+    log_error!(
+        "FMC Panic!! file_hash=0x{:08X} line={} column={}",
+        site.file_hash,
+        site.line,
+        site.column
+    );
     panic_is_possible();
+    site.log();
     handle_fatal_error(CaliptraError::FMC_GLOBAL_PANIC.into());
 }
 
@@ -0,0 +1,78 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    wdt.rs
+
+Abstract:
+
+    File contains the FMC watchdog budget and its boot phase telemetry.
+
+--*/
+
+use crate::fmc_env::FmcEnv;
+use caliptra_common::log_info;
+use caliptra_common::WdtTimeout;
+
+/// Watchdog of the FMC boot phases.
+///
+/// FMC re-arms WDT1 with its own budget instead of inheriting the ROM
+/// configuration, and restarts it at the end of every phase. The longest phase
+/// is recorded in the FHT so that the budget can be tuned across a fleet.
+pub struct FmcWdt {
+    phase_start: u64,
+}
+
+impl FmcWdt {
+    /// Arm WDT1 with the FMC phase budget and start the first phase
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - FMC Environment
+    pub fn start(env: &mut FmcEnv) -> Self {
+        let budget = u64::from(WdtTimeout::FMC_WDT1_TIMEOUT_IN_CYCLES);
+        log_info!("[fmc] Starting the WD Timer {} cycles", budget);
+        caliptra_common::wdt::start_wdt(&mut env.soc_ifc, WdtTimeout::FMC_WDT1_TIMEOUT_IN_CYCLES);
+
+        let fht = &mut env.persistent_data.get_mut().fht;
+        fht.fmc_wdt_budget_cycles = budget.try_into().unwrap_or(u32::MAX);
+        fht.fmc_wdt_max_phase_cycles = 0;
+
+        Self {
+            phase_start: Self::now(),
+        }
+    }
+
+    /// End the current phase: record its duration and restart WDT1 for the
+    /// next one
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - FMC Environment
+    pub fn end_phase(&mut self, env: &mut FmcEnv) {
+        let now = Self::now();
+        let elapsed: u32 = now
+            .saturating_sub(self.phase_start)
+            .try_into()
+            .unwrap_or(u32::MAX);
+        self.phase_start = now;
+
+        let fht = &mut env.persistent_data.get_mut().fht;
+        if elapsed > fht.fmc_wdt_max_phase_cycles {
+            fht.fmc_wdt_max_phase_cycles = elapsed;
+        }
+        caliptra_common::wdt::restart_wdt(&mut env.soc_ifc);
+    }
+
+    #[cfg(feature = "riscv")]
+    fn now() -> u64 {
+        caliptra_cpu::csr::mcycle()
+    }
+
+    #[cfg(not(feature = "riscv"))]
+    fn now() -> u64 {
+        0
+    }
+}
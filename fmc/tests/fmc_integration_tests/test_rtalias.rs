@@ -1,6 +1,9 @@
 // Licensed under the Apache-2.0 license
 use caliptra_builder::{
-    firmware::{self, runtime_tests::MOCK_RT_INTERACTIVE, FMC_WITH_UART},
+    firmware::{
+        self, runtime_tests::MOCK_RT_INTERACTIVE, FMC_WITH_UART,
+        FMC_WITH_UART_RT_ALIAS_KEY_ROTATION,
+    },
     get_ci_rom_version, CiRomVersion, ImageOptions,
 };
 use caliptra_common::RomBootStatus::*;
@@ -33,6 +36,7 @@ const PCR_COUNT: usize = 32;
 const PCR_ENTRY_SIZE: usize = core::mem::size_of::<PcrLogEntry>();
 
 const PCR2_AND_PCR3_EXTENDED_ID: u32 = (1 << PcrId::PcrId2 as u8) | (1 << PcrId::PcrId3 as u8);
+const PCR2_EXTENDED_ID: u32 = 1 << PcrId::PcrId2 as u8;
 
 #[test]
 fn test_boot_status_reporting() {
@@ -100,6 +104,9 @@ fn test_fht_info() {
     assert_eq!(fht.pcr_log_addr, 0x50004800);
     assert_eq!(fht.meas_log_addr, 0x50004C00);
     assert_eq!(fht.fuse_log_addr, 0x50005000);
+
+    // The runtime checked the MAC during its reset flow
+    assert_ne!(fht.fht_mac, [0; 12]);
 }
 
 #[test]
@@ -209,11 +216,17 @@ fn test_pcr_log() {
     let rt_tci2 = swap_word_bytes(&image2.manifest.runtime.digest);
     let manifest_digest2 = openssl::sha::sha384(image2.manifest.as_bytes());
 
+    // The runtime is unchanged, so ROM keeps it and its TCI is not extended
+    // into the journey again.
+    let rt_tci2_pcr_ids = match get_ci_rom_version() {
+        CiRomVersion::Rom1_0 => PCR2_AND_PCR3_EXTENDED_ID,
+        _ => PCR2_EXTENDED_ID,
+    };
     check_pcr_log_entry(
         &pcr_entry_arr,
         fht.pcr_log_index - 2,
         PcrLogEntryId::RtTci,
-        PCR2_AND_PCR3_EXTENDED_ID,
+        rt_tci2_pcr_ids,
         rt_tci2.as_bytes(),
     );
 
@@ -245,6 +258,57 @@ fn test_pcr_log() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_rt_alias_key_rotation() {
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    let image = caliptra_builder::build_and_sign_image(
+        &FMC_WITH_UART_RT_ALIAS_KEY_ROTATION,
+        &MOCK_RT_INTERACTIVE,
+        ImageOptions::default(),
+    )
+    .unwrap();
+    let image = image.to_bytes().unwrap();
+
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            ..Default::default()
+        },
+        BootParams {
+            fw_image: Some(&image),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // Cold reset derives the RT alias keys from the FMC CDI as usual
+    let fht = read_fht(&mut hw);
+    assert_eq!(fht.rt_cdi_kv_hdl.reg_num(), 4);
+    assert_eq!(fht.rt_priv_key_kv_hdl.reg_num(), 5);
+    let mut pub_keys = vec![fht.rt_dice_pub_key];
+
+    // Every update reset moves the keys to the other pair of slots and
+    // rotates the alias key, even when the firmware is unchanged.
+    for (cdi_slot, priv_key_slot) in [(11, 12), (4, 5)] {
+        hw.start_mailbox_execute(CommandId::FIRMWARE_LOAD.into(), &image)
+            .unwrap();
+        hw.step_until_boot_status(UpdateResetStarted.into(), false);
+        assert_eq!(hw.finish_mailbox_execute(), Ok(None));
+        hw.step_until_boot_status(RT_ALIAS_DERIVATION_COMPLETE, true);
+
+        let fht = read_fht(&mut hw);
+        assert_eq!(fht.rt_cdi_kv_hdl.reg_num(), cdi_slot);
+        assert_eq!(fht.rt_priv_key_kv_hdl.reg_num(), priv_key_slot);
+        assert!(!pub_keys.contains(&fht.rt_dice_pub_key));
+        pub_keys.push(fht.rt_dice_pub_key);
+    }
+}
+
+fn read_fht(hw: &mut impl HwModel) -> FirmwareHandoffTable {
+    let data = hw.mailbox_execute(TEST_CMD_READ_FHT, &[]).unwrap().unwrap();
+    FirmwareHandoffTable::read_from_bytes(data.as_bytes()).unwrap()
+}
+
 fn check_pcr_log_entry(
     pcr_entry_arr: &[u8],
     pcr_entry_index: u32,
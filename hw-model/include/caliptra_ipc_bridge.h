@@ -0,0 +1,45 @@
+// Licensed under the Apache-2.0 license
+
+// Wire protocol of the caliptra_ipc_bridge binary. An external simulator
+// connects to the bridge's Unix socket and sends a struct
+// caliptra_ipc_bridge_request per transaction; the bridge answers each with a
+// struct caliptra_ipc_bridge_response. All fields are native-endian.
+
+#ifndef HW_MODEL_CALIPTRA_IPC_BRIDGE_H
+#define HW_MODEL_CALIPTRA_IPC_BRIDGE_H
+
+#include <stdint.h>
+
+// Read the 32-bit APB register at addr. The value is returned in data.
+#define CALIPTRA_IPC_BRIDGE_OP_APB_READ 1
+// Write data to the 32-bit APB register at addr.
+#define CALIPTRA_IPC_BRIDGE_OP_APB_WRITE 2
+// Advance the model by count clock cycles.
+#define CALIPTRA_IPC_BRIDGE_OP_STEP 3
+// Returns 1 in data if the model is ready for firmware.
+#define CALIPTRA_IPC_BRIDGE_OP_READY_FOR_FW 4
+// Returns 1 in data if the firmware requested an exit.
+#define CALIPTRA_IPC_BRIDGE_OP_EXIT_REQUESTED 5
+// Take at most count bytes of UART output. data holds the number of bytes,
+// which immediately follow the response.
+#define CALIPTRA_IPC_BRIDGE_OP_OUTPUT_TAKE 6
+// Use data as the PAUSER for subsequent APB transactions.
+#define CALIPTRA_IPC_BRIDGE_OP_SET_APB_PAUSER 7
+
+#define CALIPTRA_IPC_BRIDGE_STATUS_OK 0
+#define CALIPTRA_IPC_BRIDGE_STATUS_BUS_ERROR 1
+#define CALIPTRA_IPC_BRIDGE_STATUS_INVALID_OP 2
+
+struct caliptra_ipc_bridge_request {
+    uint32_t op;
+    uint32_t addr;
+    uint32_t data;
+    uint32_t count;
+};
+
+struct caliptra_ipc_bridge_response {
+    uint32_t status;
+    uint32_t data;
+};
+
+#endif // HW_MODEL_CALIPTRA_IPC_BRIDGE_H
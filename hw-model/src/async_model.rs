@@ -0,0 +1,60 @@
+// Licensed under the Apache-2.0 license
+
+//! Async mailbox transport backed by a model.
+
+use crate::HwModel;
+use caliptra_api::{
+    transport::{poll_soc_mailbox, AsyncMailboxTransport},
+    CaliptraApiError, SocManager,
+};
+use std::task::{Context, Poll};
+
+/// Drives a model from an async caller
+///
+/// Models only make progress when stepped, so each poll steps the model for
+/// up to `steps_per_poll` cycles before checking on the command. Other tasks
+/// of the executor run between polls.
+pub struct AsyncModel<'a, M: HwModel> {
+    model: &'a mut M,
+    steps_per_poll: u32,
+}
+
+impl<'a, M: HwModel> AsyncModel<'a, M> {
+    pub const DEFAULT_STEPS_PER_POLL: u32 = 1000;
+
+    pub fn new(model: &'a mut M) -> Self {
+        Self {
+            model,
+            steps_per_poll: Self::DEFAULT_STEPS_PER_POLL,
+        }
+    }
+
+    pub fn with_steps_per_poll(mut self, steps_per_poll: u32) -> Self {
+        self.steps_per_poll = steps_per_poll;
+        self
+    }
+
+    pub fn model(&mut self) -> &mut M {
+        self.model
+    }
+}
+
+impl<M: HwModel> AsyncMailboxTransport for AsyncModel<'_, M> {
+    fn start_exec(&mut self, cmd: u32, req: &[u8]) -> Result<(), CaliptraApiError> {
+        self.model.start_mailbox_exec(cmd, req)
+    }
+
+    fn poll_finish_exec(
+        &mut self,
+        cx: &mut Context<'_>,
+        resp_data: &mut [u8],
+    ) -> Poll<Result<Option<usize>, CaliptraApiError>> {
+        for _ in 0..self.steps_per_poll {
+            if !self.model.soc_mbox().status().read().status().cmd_busy() {
+                break;
+            }
+            self.model.step();
+        }
+        poll_soc_mailbox(self.model, cx, resp_data)
+    }
+}
@@ -0,0 +1,52 @@
+// Licensed under the Apache-2.0 license
+
+//! Serves a Caliptra model to an external SoC simulator over a Unix socket.
+//!
+//! Usage: caliptra_ipc_bridge <socket-path> <rom-path> [security-state]
+//!
+//! See `caliptra_ipc_bridge.h` for the wire protocol.
+
+use caliptra_hw_model::{ipc_bridge::IpcBridge, InitParams, SecurityState};
+use std::os::unix::net::UnixListener;
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        eprintln!(
+            "Usage: {} <socket-path> <rom-path> [security-state]",
+            args[0]
+        );
+        exit(1);
+    }
+    let socket_path = &args[1];
+    let rom = std::fs::read(&args[2]).unwrap_or_else(|e| {
+        eprintln!("Unable to read ROM {}: {e}", args[2]);
+        exit(1);
+    });
+    let security_state = match args.get(3) {
+        Some(s) => SecurityState::from(s.parse::<u32>().unwrap_or_else(|_| {
+            eprintln!("Invalid security state {s}");
+            exit(1);
+        })),
+        None => SecurityState::default(),
+    };
+
+    let mut model = caliptra_hw_model::new_unbooted(InitParams {
+        rom: &rom,
+        security_state,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path).unwrap();
+    println!("Waiting for simulator on {socket_path}");
+    let (stream, _) = listener.accept().unwrap();
+    let result = IpcBridge::new(&mut model).serve(stream);
+    let _ = std::fs::remove_file(socket_path);
+    if let Err(e) = result {
+        eprintln!("Bridge failed: {e}");
+        exit(1);
+    }
+}
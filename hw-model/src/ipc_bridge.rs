@@ -0,0 +1,210 @@
+// Licensed under the Apache-2.0 license
+
+//! Bridge that lets an external SoC simulator drive a model over IPC.
+//!
+//! The simulator connects to a stream socket and sends fixed-size
+//! [`IpcBridgeRequest`] messages; each is answered with an
+//! [`IpcBridgeResponse`]. All fields are native-endian `u32`s, so the peer
+//! only needs `caliptra_ipc_bridge.h` and not the Rust model itself. The
+//! `caliptra_ipc_bridge` binary serves this protocol on a Unix socket.
+
+use std::io::{self, Read, Write};
+
+use caliptra_emu_bus::Bus;
+use caliptra_emu_types::RvSize;
+use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout};
+
+use crate::HwModel;
+
+pub const IPC_BRIDGE_OP_APB_READ: u32 = 1;
+pub const IPC_BRIDGE_OP_APB_WRITE: u32 = 2;
+pub const IPC_BRIDGE_OP_STEP: u32 = 3;
+pub const IPC_BRIDGE_OP_READY_FOR_FW: u32 = 4;
+pub const IPC_BRIDGE_OP_EXIT_REQUESTED: u32 = 5;
+pub const IPC_BRIDGE_OP_OUTPUT_TAKE: u32 = 6;
+pub const IPC_BRIDGE_OP_SET_APB_PAUSER: u32 = 7;
+
+pub const IPC_BRIDGE_STATUS_OK: u32 = 0;
+pub const IPC_BRIDGE_STATUS_BUS_ERROR: u32 = 1;
+pub const IPC_BRIDGE_STATUS_INVALID_OP: u32 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, FromBytes, IntoBytes, Immutable, KnownLayout)]
+pub struct IpcBridgeRequest {
+    /// One of the `IPC_BRIDGE_OP_*` values
+    pub op: u32,
+
+    /// APB address for reads and writes
+    pub addr: u32,
+
+    /// Write data, or the PAUSER for `IPC_BRIDGE_OP_SET_APB_PAUSER`
+    pub data: u32,
+
+    /// Number of cycles for `IPC_BRIDGE_OP_STEP`, or the maximum number of
+    /// bytes for `IPC_BRIDGE_OP_OUTPUT_TAKE`
+    pub count: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, FromBytes, IntoBytes, Immutable, KnownLayout)]
+pub struct IpcBridgeResponse {
+    /// One of the `IPC_BRIDGE_STATUS_*` values
+    pub status: u32,
+
+    /// Read data or boolean result. For `IPC_BRIDGE_OP_OUTPUT_TAKE`, the
+    /// number of UART output bytes that follow the response.
+    pub data: u32,
+}
+
+pub struct IpcBridge<'a, TModel: HwModel> {
+    model: &'a mut TModel,
+}
+
+impl<'a, TModel: HwModel> IpcBridge<'a, TModel> {
+    pub fn new(model: &'a mut TModel) -> Self {
+        Self { model }
+    }
+
+    /// Serve requests from `stream` until the peer closes it.
+    pub fn serve(&mut self, mut stream: impl Read + Write) -> io::Result<()> {
+        let mut req = IpcBridgeRequest::new_zeroed();
+        loop {
+            match stream.read_exact(req.as_mut_bytes()) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(e),
+            }
+            let (resp, payload) = self.handle(&req);
+            stream.write_all(resp.as_bytes())?;
+            stream.write_all(&payload)?;
+            stream.flush()?;
+        }
+    }
+
+    fn handle(&mut self, req: &IpcBridgeRequest) -> (IpcBridgeResponse, Vec<u8>) {
+        let mut resp = IpcBridgeResponse::default();
+        let mut payload = vec![];
+        match req.op {
+            IPC_BRIDGE_OP_APB_READ => match self.model.apb_bus().read(RvSize::Word, req.addr) {
+                Ok(data) => resp.data = data,
+                Err(_) => resp.status = IPC_BRIDGE_STATUS_BUS_ERROR,
+            },
+            IPC_BRIDGE_OP_APB_WRITE => {
+                if self
+                    .model
+                    .apb_bus()
+                    .write(RvSize::Word, req.addr, req.data)
+                    .is_err()
+                {
+                    resp.status = IPC_BRIDGE_STATUS_BUS_ERROR;
+                }
+            }
+            IPC_BRIDGE_OP_STEP => {
+                for _ in 0..req.count {
+                    self.model.step();
+                }
+            }
+            IPC_BRIDGE_OP_READY_FOR_FW => resp.data = self.model.ready_for_fw().into(),
+            IPC_BRIDGE_OP_EXIT_REQUESTED => resp.data = self.model.output().exit_requested().into(),
+            IPC_BRIDGE_OP_OUTPUT_TAKE => {
+                payload = self.model.output().take(req.count as usize).into_bytes();
+                resp.data = payload.len() as u32;
+            }
+            IPC_BRIDGE_OP_SET_APB_PAUSER => self.model.set_apb_pauser(req.data),
+            _ => resp.status = IPC_BRIDGE_STATUS_INVALID_OP,
+        }
+        (resp, payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mmio::Rv32GenMmio, InitParams};
+    use caliptra_api::SocManager;
+    use caliptra_registers::soc_ifc;
+    use std::net::Shutdown;
+    use std::os::unix::net::UnixStream;
+
+    const MBOX_ADDR_LOCK: u32 = 0x3002_0000;
+    const MBOX_ADDR_CMD: u32 = 0x3002_0008;
+
+    fn gen_image_hi() -> Vec<u8> {
+        let rv32_gen = Rv32GenMmio::new();
+        let soc_ifc =
+            unsafe { soc_ifc::RegisterBlock::new_with_mmio(0x3003_0000 as *mut u32, &rv32_gen) };
+        soc_ifc
+            .cptra_generic_output_wires()
+            .at(0)
+            .write(|_| b'h'.into());
+        soc_ifc
+            .cptra_generic_output_wires()
+            .at(0)
+            .write(|_| b'i'.into());
+        rv32_gen.into_inner().empty_loop().build()
+    }
+
+    fn request(stream: &mut UnixStream, op: u32, addr: u32, data: u32, count: u32) {
+        let req = IpcBridgeRequest {
+            op,
+            addr,
+            data,
+            count,
+        };
+        stream.write_all(req.as_bytes()).unwrap();
+    }
+
+    fn response(stream: &mut UnixStream) -> (u32, u32) {
+        let mut resp = IpcBridgeResponse::new_zeroed();
+        stream.read_exact(resp.as_mut_bytes()).unwrap();
+        (resp.status, resp.data)
+    }
+
+    #[test]
+    fn test_ipc_bridge() {
+        let mut model = crate::new_unbooted(InitParams {
+            rom: &gen_image_hi(),
+            ..Default::default()
+        })
+        .unwrap();
+        model.soc_ifc().cptra_fuse_wr_done().write(|w| w.done(true));
+        model.soc_ifc().cptra_bootfsm_go().write(|w| w.go(true));
+        model
+            .soc_ifc()
+            .cptra_mbox_valid_pauser()
+            .at(0)
+            .write(|_| 0x1);
+        model
+            .soc_ifc()
+            .cptra_mbox_pauser_lock()
+            .at(0)
+            .write(|w| w.lock(true));
+
+        // The model isn't Send, so queue up every request before serving them
+        // on this thread.
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        request(&mut client, IPC_BRIDGE_OP_SET_APB_PAUSER, 0, 0x1, 0);
+        request(&mut client, IPC_BRIDGE_OP_APB_READ, MBOX_ADDR_LOCK, 0, 0);
+        request(&mut client, IPC_BRIDGE_OP_APB_WRITE, MBOX_ADDR_CMD, 4242, 0);
+        request(&mut client, IPC_BRIDGE_OP_APB_READ, MBOX_ADDR_CMD, 0, 0);
+        request(&mut client, IPC_BRIDGE_OP_APB_READ, 0x1000_0000, 0, 0);
+        request(&mut client, 0xffff_ffff, 0, 0, 0);
+        request(&mut client, IPC_BRIDGE_OP_STEP, 0, 0, 1000);
+        request(&mut client, IPC_BRIDGE_OP_OUTPUT_TAKE, 0, 0, 64);
+        client.shutdown(Shutdown::Write).unwrap();
+
+        IpcBridge::new(&mut model).serve(&mut server).unwrap();
+
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_OK, 0));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_OK, 0));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_OK, 0));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_OK, 4242));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_BUS_ERROR, 0));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_INVALID_OP, 0));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_OK, 0));
+        assert_eq!(response(&mut client), (IPC_BRIDGE_STATUS_OK, 2));
+        let mut output = [0u8; 2];
+        client.read_exact(&mut output).unwrap();
+        assert_eq!(&output, b"hi");
+    }
+}
@@ -29,10 +29,13 @@ use caliptra_registers::soc_ifc::regs::{
 use rand::{rngs::StdRng, SeedableRng};
 use sha2::Digest;
 
+mod async_model;
+pub mod mbox_fuzz;
 pub mod mmio;
 mod model_emulated;
 
 mod bus_logger;
+pub mod ipc_bridge;
 #[cfg(feature = "verilator")]
 mod model_verilated;
 
@@ -44,8 +47,10 @@ mod rv32_builder;
 
 pub use api::mailbox::mbox_write_fifo;
 pub use api_types::{DeviceLifecycle, Fuses, SecurityState, U4};
+pub use async_model::AsyncModel;
 pub use caliptra_emu_bus::BusMmio;
 pub use caliptra_emu_cpu::{CodeRange, ImageInfo, StackInfo, StackRange};
+pub use caliptra_hw_model_types::{DataVaultRegion, VaultEvent};
 use output::ExitStatus;
 pub use output::Output;
 
@@ -141,6 +146,9 @@ impl TrngMode {
 
 const EXPECTED_CALIPTRA_BOOT_TIME_IN_CYCLES: u64 = 40_000_000; // 40 million cycles
 
+/// How long a mailbox command may keep the mailbox busy
+pub const MAILBOX_TIMEOUT_CYCLES: u64 = 40_000_000; // 100ms @400MHz
+
 pub struct InitParams<'a> {
     // The contents of the boot ROM
     pub rom: &'a [u8],
@@ -169,6 +177,10 @@ pub struct InitParams<'a> {
     // When None, use the itrng compile-time feature to decide which mode to use.
     pub trng_mode: Option<TrngMode>,
 
+    // Seed for the internal TRNG's CTR_DRBG when the firmware is built with
+    // the deterministic-drbg feature. Only supported by the emulator.
+    pub drbg_seed: [u32; 12],
+
     // If true (and the HwModel supports it), initialize the SRAM with random
     // data. This will likely result in a ECC double-bit error if the CPU
     // attempts to read uninitialized memory.
@@ -178,8 +190,8 @@ pub struct InitParams<'a> {
     // will be used
     pub trace_path: Option<PathBuf>,
 
-    // Information about the stack Caliptra is using. When set the emulator will check if the stack
-    // overflows.
+    // Information about the stack Caliptra is using. When set the emulator fails on stack overflows
+    // and reports the peak stack usage of each image.
     pub stack_info: Option<StackInfo>,
 }
 impl<'a> Default for InitParams<'a> {
@@ -213,6 +225,7 @@ impl<'a> Default for InitParams<'a> {
             } else {
                 TrngMode::External
             }),
+            drbg_seed: [0; 12],
             random_sram_puf: true,
             trace_path: None,
             stack_info: None,
@@ -310,11 +323,17 @@ pub enum ModelError {
         actual: u32,
     },
     MailboxRespInvalidFipsStatus(u32),
+    MailboxRespInvalidField(&'static str),
     MailboxTimeout,
     ReadBufferTooSmall,
     FuseDoneNotSet,
     FusesAlreadyInitialized,
     StashMeasurementFailed,
+    DpeCmdFailed(u32),
+    AttestationDisabled,
+    StepTimeout {
+        cycles: u64,
+    },
 }
 
 impl From<CaliptraApiError> for ModelError {
@@ -335,6 +354,9 @@ impl From<CaliptraApiError> for ModelError {
             CaliptraApiError::MailboxRespInvalidChecksum { expected, actual } => {
                 ModelError::MailboxRespInvalidChecksum { expected, actual }
             }
+            CaliptraApiError::MailboxRespInvalidField(field) => {
+                ModelError::MailboxRespInvalidField(field)
+            }
             CaliptraApiError::MailboxRespTypeTooSmall => ModelError::MailboxRespTypeTooSmall,
             CaliptraApiError::MailboxReqTypeTooSmall => ModelError::MailboxReqTypeTooSmall,
             CaliptraApiError::MailboxNoResponseData => ModelError::MailboxNoResponseData,
@@ -362,6 +384,10 @@ impl From<CaliptraApiError> for ModelError {
                 ModelError::StashMeasurementFailed
             }
             caliptra_api::CaliptraApiError::UnableToSetPauser => ModelError::UnableToSetPauser,
+            caliptra_api::CaliptraApiError::DpeCmdFailed(status) => {
+                ModelError::DpeCmdFailed(status)
+            }
+            caliptra_api::CaliptraApiError::AttestationDisabled => ModelError::AttestationDisabled,
         }
     }
 }
@@ -426,6 +452,9 @@ impl Display for ModelError {
                     "Mailbox response had non-success FIPS status: 0x{status:x}"
                 )
             }
+            ModelError::MailboxRespInvalidField(field) => {
+                write!(f, "Mailbox response field {field} has an invalid value")
+            }
             ModelError::MailboxTimeout => {
                 write!(f, "Mailbox timed out in busy state")
             }
@@ -447,6 +476,15 @@ impl Display for ModelError {
             ModelError::UnableToSetPauser => {
                 write!(f, "Valid PAUSER locked")
             }
+            ModelError::DpeCmdFailed(status) => {
+                write!(f, "DPE command failed. status=0x{status:x}")
+            }
+            ModelError::AttestationDisabled => {
+                write!(f, "Attestation is disabled")
+            }
+            ModelError::StepTimeout { cycles } => {
+                write!(f, "Condition not met after {cycles} cycles")
+            }
         }
     }
 }
@@ -647,6 +685,40 @@ pub trait HwModel: SocManager {
         }
     }
 
+    /// The number of clock cycles executed by the model.
+    ///
+    /// A single `step()` may cover any number of cycles (on fpga_realtime the
+    /// hardware runs freely between steps), so timeouts are measured with
+    /// this rather than by counting steps.
+    fn cycle_count(&mut self) -> u64;
+
+    /// Execute until the result of `predicate` becomes true, or fail with
+    /// `ModelError::StepTimeout` once `max_cycles` clock cycles have elapsed.
+    fn step_until_timeout(
+        &mut self,
+        max_cycles: u64,
+        mut predicate: impl FnMut(&mut Self) -> bool,
+    ) -> Result<(), ModelError> {
+        let start = self.cycle_count();
+        while !predicate(self) {
+            let cycles = self.cycle_count().wrapping_sub(start);
+            if cycles >= max_cycles {
+                return Err(ModelError::StepTimeout { cycles });
+            }
+            self.step();
+        }
+        Ok(())
+    }
+
+    /// Execute until the mailbox returns to idle, or fail with
+    /// `ModelError::MailboxTimeout` after `MAILBOX_TIMEOUT_CYCLES`.
+    fn step_until_mailbox_idle(&mut self) -> Result<(), ModelError> {
+        self.step_until_timeout(MAILBOX_TIMEOUT_CYCLES, |m| {
+            m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle()
+        })
+        .map_err(|_| ModelError::MailboxTimeout)
+    }
+
     /// Toggle reset pins and wait for ready_for_fuses
     fn warm_reset(&mut self) {
         // To be overridden by HwModel implementations that support this
@@ -753,8 +825,21 @@ pub trait HwModel: SocManager {
     // This function will not match any data in the output that was written
     // before this function was called.
     fn step_until_output_contains(&mut self, substr: &str) -> Result<(), Box<dyn Error>> {
+        self.step_until_output_contains_timeout(substr, u64::MAX)
+    }
+
+    /// Same as `step_until_output_contains`, but fails if `substr` has not
+    /// been written within `max_cycles` clock cycles.
+    fn step_until_output_contains_timeout(
+        &mut self,
+        substr: &str,
+        max_cycles: u64,
+    ) -> Result<(), Box<dyn Error>> {
         self.output().set_search_term(substr);
-        self.step_until(|m| m.output().search_matched());
+        self.step_until_timeout(max_cycles, |m| m.output().search_matched())
+            .map_err(|_| {
+                format!("expected output {substr:?} not seen within {max_cycles} cycles")
+            })?;
         Ok(())
     }
 
@@ -826,6 +911,15 @@ pub trait HwModel: SocManager {
 
     fn ecc_error_injection(&mut self, _mode: ErrorInjectionMode) {}
 
+    /// Drain the KeyVault, PCR vault, and DataVault mutations recorded since
+    /// the model was created or since the last call, oldest first.
+    ///
+    /// Models that can't observe the vault internals never record anything,
+    /// so this returns an empty list for them.
+    fn take_vault_events(&mut self) -> Vec<VaultEvent> {
+        Vec::new()
+    }
+
     fn set_apb_pauser(&mut self, pauser: u32);
 
     /// Executes a typed request and (if success), returns the typed response.
@@ -891,14 +985,10 @@ pub trait HwModel: SocManager {
     /// Wait for the response to a previous call to `start_mailbox_execute()`.
     fn finish_mailbox_execute(&mut self) -> std::result::Result<Option<Vec<u8>>, ModelError> {
         // Wait for the microcontroller to finish executing
-        let mut timeout_cycles = 40000000; // 100ms @400MHz
-        while self.soc_mbox().status().read().status().cmd_busy() {
-            self.step();
-            timeout_cycles -= 1;
-            if timeout_cycles == 0 {
-                return Err(ModelError::MailboxTimeout);
-            }
-        }
+        self.step_until_timeout(MAILBOX_TIMEOUT_CYCLES, |m| {
+            !m.soc_mbox().status().read().status().cmd_busy()
+        })
+        .map_err(|_| ModelError::MailboxTimeout)?;
         let status = self.soc_mbox().status().read().status();
         if status.cmd_failure() {
             writeln!(self.output().logger(), ">>> mbox cmd response: failed").unwrap();
@@ -1306,6 +1396,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_step_until_timeout() {
+        let mut model = caliptra_hw_model::new(
+            InitParams {
+                rom: &gen_image_hi(),
+                ..Default::default()
+            },
+            BootParams::default(),
+        )
+        .unwrap();
+        model.step_until_output("hii").unwrap();
+
+        // The ROM spins forever once it has written its output
+        let start = model.cycle_count();
+        let Err(ModelError::StepTimeout { cycles }) = model.step_until_timeout(10_000, |_| false)
+        else {
+            panic!("expected a step timeout");
+        };
+        assert!(cycles >= 10_000);
+        assert!(model.cycle_count() - start >= 10_000);
+
+        assert_eq!(
+            model
+                .step_until_output_contains_timeout("bye", 10_000)
+                .err()
+                .unwrap()
+                .to_string(),
+            "expected output \"bye\" not seen within 10000 cycles"
+        );
+        model.step_until_mailbox_idle().unwrap();
+    }
+
     #[test]
     pub fn test_mailbox_execute() {
         let message: [u8; 10] = [0x90, 0x5e, 0x1f, 0xad, 0x8b, 0x60, 0xb0, 0xbf, 0x1c, 0x7e];
@@ -1456,6 +1578,85 @@ mod tests {
         );
     }
 
+    #[test]
+    /// Test the async mailbox transport.
+    fn test_async_model_mbox() {
+        use caliptra_api::{transport::mailbox_exec, CaliptraApiError};
+        use std::future::Future;
+        use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn block_on<F: Future>(fut: F) -> F::Output {
+            const VTABLE: RawWakerVTable = RawWakerVTable::new(
+                |_| RawWaker::new(std::ptr::null(), &VTABLE),
+                |_| {},
+                |_| {},
+                |_| {},
+            );
+            let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+            let mut cx = Context::from_waker(&waker);
+            let mut fut = std::pin::pin!(fut);
+            loop {
+                if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+        }
+
+        let message: [u8; 10] = [0x90, 0x5e, 0x1f, 0xad, 0x8b, 0x60, 0xb0, 0xbf, 0x1c, 0x7e];
+
+        let rom =
+            caliptra_builder::build_firmware_rom(&firmware::hw_model_tests::MAILBOX_RESPONDER)
+                .unwrap();
+
+        let mut model = caliptra_hw_model::new(
+            InitParams {
+                rom: &rom,
+                ..Default::default()
+            },
+            BootParams::default(),
+        )
+        .unwrap();
+        let mut transport = caliptra_hw_model::AsyncModel::new(&mut model).with_steps_per_poll(10);
+
+        // Send command that echoes the command and input message
+        let mut resp_data = [0u8; 128];
+        assert_eq!(
+            block_on(mailbox_exec(
+                &mut transport,
+                0x1000_0000,
+                &message,
+                &mut resp_data
+            )),
+            Ok(Some(
+                [[0x00, 0x00, 0x00, 0x10].as_slice(), &message]
+                    .concat()
+                    .as_bytes()
+            )),
+        );
+
+        // Send command that returns success with no output
+        assert_eq!(
+            block_on(mailbox_exec(
+                &mut transport,
+                0x2000_0000,
+                &[],
+                &mut resp_data
+            )),
+            Ok(None)
+        );
+
+        // Send command that returns failure
+        assert_eq!(
+            block_on(mailbox_exec(
+                &mut transport,
+                0x4000_0000,
+                &message,
+                &mut resp_data
+            )),
+            Err(CaliptraApiError::MailboxCmdFailed(0))
+        );
+    }
+
     #[test]
     pub fn test_mailbox_receive() {
         let rom = caliptra_builder::build_firmware_rom(&firmware::hw_model_tests::MAILBOX_SENDER)
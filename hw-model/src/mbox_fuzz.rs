@@ -0,0 +1,200 @@
+// Licensed under the Apache-2.0 license
+
+//! Randomized stream of mailbox commands and device perturbations, used to
+//! check that firmware survives arbitrary input from the SoC.
+
+use caliptra_api::{calc_checksum, wire::wire_formats};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// Action generated by [`MboxFuzzer`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MboxFuzzAction {
+    /// Execute a mailbox command. The payload includes the checksum word,
+    /// which may or may not be valid.
+    Execute { cmd: u32, payload: Vec<u8> },
+
+    /// Access the mailbox with a different PAUSER
+    SetApbPauser(u32),
+
+    /// Warm reset the device and wait for the firmware to boot again
+    WarmReset,
+}
+
+/// Parameters of a [`MboxFuzzer`] run
+#[derive(Clone, Debug)]
+pub struct MboxFuzzParams {
+    /// Seed of the random stream; the same seed and parameters generate the
+    /// same actions.
+    pub seed: u64,
+
+    /// Commands that well-formed requests are generated for. Other requests
+    /// use random command codes.
+    pub commands: Vec<u32>,
+
+    /// PAUSER values to switch between. Empty if the model cannot change the
+    /// PAUSER.
+    pub pausers: Vec<u32>,
+
+    /// Generate warm resets
+    pub warm_reset: bool,
+
+    /// Largest generated payload, in bytes
+    pub max_payload_size: usize,
+}
+
+impl Default for MboxFuzzParams {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            commands: vec![],
+            pausers: vec![],
+            warm_reset: true,
+            max_payload_size: 2048,
+        }
+    }
+}
+
+/// Generator of random mailbox commands interleaved with perturbations of
+/// the device state.
+pub struct MboxFuzzer {
+    rng: StdRng,
+    params: MboxFuzzParams,
+}
+
+impl MboxFuzzer {
+    /// Percentage of actions that are warm resets, when enabled
+    const WARM_RESET_PERCENT: u32 = 1;
+
+    /// Percentage of actions that change the PAUSER, when enabled
+    const SET_PAUSER_PERCENT: u32 = 5;
+
+    /// Percentage of commands taken from `MboxFuzzParams::commands`
+    const KNOWN_CMD_PERCENT: u32 = 85;
+
+    /// Percentage of known commands sized like one of their requests
+    const WIRE_SIZE_PERCENT: u32 = 50;
+
+    /// Percentage of commands with a valid checksum
+    const VALID_CHECKSUM_PERCENT: u32 = 90;
+
+    pub fn new(params: MboxFuzzParams) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(params.seed),
+            params,
+        }
+    }
+
+    pub fn params(&self) -> &MboxFuzzParams {
+        &self.params
+    }
+
+    /// Generate the next action
+    pub fn next_action(&mut self) -> MboxFuzzAction {
+        let roll = self.rng.gen_range(0..100);
+        if self.params.warm_reset && roll < Self::WARM_RESET_PERCENT {
+            return MboxFuzzAction::WarmReset;
+        }
+        if roll < Self::WARM_RESET_PERCENT + Self::SET_PAUSER_PERCENT {
+            if let Some(&pauser) = self.params.pausers.choose(&mut self.rng) {
+                return MboxFuzzAction::SetApbPauser(pauser);
+            }
+        }
+        self.next_command()
+    }
+
+    fn next_command(&mut self) -> MboxFuzzAction {
+        let known_cmd = self.params.commands.choose(&mut self.rng).copied();
+        let cmd = match known_cmd {
+            Some(cmd) if self.percent(Self::KNOWN_CMD_PERCENT) => cmd,
+            _ => self.rng.gen(),
+        };
+
+        // Favor short payloads, which get past more of the length checks
+        let max_len = match self.rng.gen_range(0..4) {
+            0 => 8,
+            1 => 64,
+            2 => 512,
+            _ => self.params.max_payload_size,
+        };
+        let len = self
+            .rng
+            .gen_range(0..=max_len.min(self.params.max_payload_size));
+        let mut payload = vec![0u8; len];
+        self.rng.fill(&mut payload[..]);
+
+        // Requests of the expected size get past the length checks and reach
+        // the command handlers
+        if known_cmd == Some(cmd) && self.percent(Self::WIRE_SIZE_PERCENT) {
+            let formats: Vec<_> = wire_formats(cmd).collect();
+            if let Some(format) = formats.choose(&mut self.rng) {
+                payload.resize(format.req_size.min(self.params.max_payload_size), 0);
+                if format.version > 1 && payload.len() > 4 {
+                    payload[4] = format.version;
+                }
+            }
+        }
+
+        if payload.len() >= 4 && self.percent(Self::VALID_CHECKSUM_PERCENT) {
+            let chksum = calc_checksum(cmd, &payload[4..]);
+            payload[..4].copy_from_slice(&chksum.to_le_bytes());
+        }
+        MboxFuzzAction::Execute { cmd, payload }
+    }
+
+    fn percent(&mut self, percent: u32) -> bool {
+        self.rng.gen_range(0..100) < percent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actions(params: MboxFuzzParams, count: usize) -> Vec<MboxFuzzAction> {
+        let mut fuzzer = MboxFuzzer::new(params);
+        (0..count).map(|_| fuzzer.next_action()).collect()
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let params = MboxFuzzParams {
+            seed: 0x1234,
+            commands: vec![0x4650_5652],
+            pausers: vec![1, 2],
+            ..Default::default()
+        };
+        assert_eq!(actions(params.clone(), 500), actions(params, 500));
+    }
+
+    #[test]
+    fn test_params() {
+        let params = MboxFuzzParams {
+            seed: 0x5678,
+            commands: vec![0x4650_5652],
+            warm_reset: false,
+            max_payload_size: 16,
+            ..Default::default()
+        };
+        let actions = actions(params, 1000);
+        for action in actions.iter() {
+            match action {
+                MboxFuzzAction::Execute { payload, .. } => assert!(payload.len() <= 16),
+                action => panic!("Unexpected action {action:?}"),
+            }
+        }
+
+        // A good share of the commands are well-formed
+        let valid = actions
+            .iter()
+            .filter(|action| match action {
+                MboxFuzzAction::Execute { cmd, payload } => {
+                    *cmd == 0x4650_5652
+                        && payload.len() >= 4
+                        && payload[..4] == calc_checksum(*cmd, &payload[4..]).to_le_bytes()
+                }
+                _ => false,
+            })
+            .count();
+        assert!(valid > actions.len() / 3);
+    }
+}
@@ -11,13 +11,14 @@ use std::rc::Rc;
 use caliptra_emu_bus::Clock;
 #[cfg(feature = "coverage")]
 use caliptra_emu_cpu::CoverageBitmaps;
-use caliptra_emu_cpu::{Cpu, InstrTracer};
+use caliptra_emu_cpu::{Cpu, InstrTracer, StackInfo};
 use caliptra_emu_periph::ActionCb;
 use caliptra_emu_periph::ReadyForFwCb;
 use caliptra_emu_periph::{CaliptraRootBus, CaliptraRootBusArgs, SocToCaliptraBus, TbServicesCb};
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
-use caliptra_hw_model_types::ErrorInjectionMode;
-use caliptra_image_types::IMAGE_MANIFEST_BYTE_SIZE;
+use caliptra_hw_model_types::{ErrorInjectionMode, VaultEvent};
+use caliptra_image_types::{ImageManifest, ImageManifestFormat, ImageVendorIntermediateKeyTable};
+use zerocopy::FromZeros;
 
 use crate::bus_logger::BusLogger;
 use crate::bus_logger::LogFile;
@@ -102,6 +103,13 @@ impl ModelEmulated {
     }
 }
 
+impl ModelEmulated {
+    /// Peak stack usage of each image, if `InitParams::stack_info` was set
+    pub fn stack_info(&self) -> Option<&StackInfo> {
+        self.cpu.stack_info()
+    }
+}
+
 fn hash_slice(slice: &[u8]) -> u64 {
     let mut hasher = DefaultHasher::new();
     std::hash::Hash::hash_slice(slice, &mut hasher);
@@ -164,6 +172,7 @@ impl HwModel for ModelEmulated {
 
             itrng_nibbles: Some(params.itrng_nibbles),
             etrng_responses: params.etrng_responses,
+            drbg_seed: params.drbg_seed,
             ..CaliptraRootBusArgs::default()
         };
         let mut root_bus = CaliptraRootBus::new(&clock, bus_args);
@@ -238,6 +247,10 @@ impl HwModel for ModelEmulated {
         }
     }
 
+    fn cycle_count(&mut self) -> u64 {
+        self.cpu.clock.now()
+    }
+
     fn output(&mut self) -> &mut Output {
         // In case the caller wants to log something, make sure the log has the
         // correct time.env::
@@ -246,7 +259,12 @@ impl HwModel for ModelEmulated {
     }
 
     fn cover_fw_mage(&mut self, fw_image: &[u8]) {
-        let iccm_image = &fw_image[IMAGE_MANIFEST_BYTE_SIZE..];
+        let manifest_size = ImageManifest::new_zeroed()
+            .parse(&mut ImageVendorIntermediateKeyTable::new_zeroed(), fw_image)
+            .map_or(ImageManifestFormat::V1.size(), |layout| {
+                layout.size as usize
+            });
+        let iccm_image = &fw_image[manifest_size..];
         self.iccm_image_tag = Some(hash_slice(iccm_image));
     }
     fn tracing_hint(&mut self, enable: bool) {
@@ -288,6 +306,10 @@ impl HwModel for ModelEmulated {
         }
     }
 
+    fn take_vault_events(&mut self) -> Vec<VaultEvent> {
+        self.cpu.bus.bus.key_vault.take_events()
+    }
+
     fn set_apb_pauser(&mut self, _pauser: u32) {
         unimplemented!();
     }
@@ -114,6 +114,11 @@ pub struct ModelFpgaRealtime {
 
     trng_mode: TrngMode,
     openocd: Option<Child>,
+
+    // The wrapper cycle counter is 32 bits wide; it is extended to 64 bits
+    // each time it is read
+    last_wrapper_cycle: u32,
+    cycle_count: u64,
 }
 
 impl ModelFpgaRealtime {
@@ -202,6 +207,14 @@ impl ModelFpgaRealtime {
         }
     }
 
+    fn wrapper_cycle(&self) -> u32 {
+        unsafe {
+            self.wrapper
+                .offset(FPGA_WRAPPER_CYCLE_COUNT_OFFSET)
+                .read_volatile()
+        }
+    }
+
     fn is_ready_for_fuses(&self) -> bool {
         unsafe {
             GpioInput(
@@ -408,6 +421,9 @@ impl HwModel for ModelFpgaRealtime {
             trng_mode: desired_trng_mode,
 
             openocd: None,
+
+            last_wrapper_cycle: 0,
+            cycle_count: 0,
         };
 
         // Set pwrgood and rst_b to 0 to boot from scratch
@@ -477,12 +493,15 @@ impl HwModel for ModelFpgaRealtime {
         self.trng_mode
     }
 
+    fn cycle_count(&mut self) -> u64 {
+        let cycle = self.wrapper_cycle();
+        self.cycle_count += u64::from(cycle.wrapping_sub(self.last_wrapper_cycle));
+        self.last_wrapper_cycle = cycle;
+        self.cycle_count
+    }
+
     fn output(&mut self) -> &mut crate::Output {
-        let cycle = unsafe {
-            self.wrapper
-                .offset(FPGA_WRAPPER_CYCLE_COUNT_OFFSET)
-                .read_volatile()
-        };
+        let cycle = self.wrapper_cycle();
         self.output.sink().set_now(u64::from(cycle));
         &mut self.output
     }
@@ -144,6 +144,10 @@ impl HwModel for ModelVerilated {
         self.process_trng_end();
     }
 
+    fn cycle_count(&mut self) -> u64 {
+        self.v.total_cycles()
+    }
+
     fn new_unbooted(params: crate::InitParams) -> Result<Self, Box<dyn std::error::Error>>
     where
         Self: Sized,
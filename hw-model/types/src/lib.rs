@@ -216,6 +216,44 @@ pub enum ErrorInjectionMode {
     DccmDoubleBitEcc,
 }
 
+/// Lockable region of the DataVault peripheral
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DataVaultRegion {
+    StickyDataVault,
+    NonStickyDataVault,
+    NonStickyLockableScratch,
+    StickyLockableScratch,
+}
+
+/// KeyVault, PCR vault, or DataVault mutation observed by the model
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VaultEvent {
+    /// A crypto engine wrote a key into a KeyVault slot
+    KeyWrite { slot: u32, usage: u32 },
+
+    /// A KeyVault slot was erased through its control register
+    KeyClear { slot: u32 },
+
+    /// A lock was requested through a KeyVault slot control register
+    KeyLock {
+        slot: u32,
+        write_lock: bool,
+        use_lock: bool,
+    },
+
+    /// A PCR was erased through its control register
+    PcrClear { pcr: u32 },
+
+    /// A lock was requested through a PCR control register
+    PcrLock { pcr: u32 },
+
+    /// A lock was requested through a DataVault control register
+    DataVaultLock { region: DataVaultRegion, entry: u32 },
+
+    /// A warm or update reset released the non-sticky locks
+    LocksReleased,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -27,6 +27,24 @@ pub(crate) struct VendorKeyConfig {
     pub ecc_priv_keys: Option<[String; VENDOR_ECC_KEY_COUNT as usize]>,
 
     pub lms_priv_keys: Option<[String; VENDOR_LMS_KEY_COUNT as usize]>,
+
+    pub intermediate: Option<VendorIntermediateKeyConfig>,
+}
+
+/// Vendor Intermediate Key Configuration
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct VendorIntermediateKeyConfig {
+    pub ecc_pub_keys: Vec<String>,
+
+    pub ecc_priv_keys: Option<Vec<String>>,
+
+    /// Revoked intermediate keys; bit N revokes `ecc_pub_keys[N]`
+    #[serde(default)]
+    pub revocation: u32,
+
+    /// Security version of the intermediate key table
+    #[serde(default)]
+    pub svn: u32,
 }
 
 /// Owner Key Configuration
@@ -39,6 +57,12 @@ pub(crate) struct OwnerKeyConfig {
     pub lms_pub_key: String,
 
     pub lms_priv_key: Option<String>,
+
+    /// Owner ECC public key to rotate to
+    pub next_ecc_pub_key: Option<String>,
+
+    /// Owner LMS public key to rotate to
+    pub next_lms_pub_key: Option<String>,
 }
 
 //Key Configuration
@@ -49,6 +73,50 @@ pub(crate) struct KeyConfig {
     pub owner: Option<OwnerKeyConfig>,
 }
 
+/// Executable Configuration
+#[derive(Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) struct ExecutableConfig {
+    pub path: String,
+
+    pub version: u32,
+
+    pub svn: u32,
+
+    pub rev: String,
+}
+
+/// Configuration of a single bundle in a bundle family
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct BundleConfig {
+    pub out: String,
+
+    pub ecc_pk_idx: u32,
+
+    pub lms_pk_idx: u32,
+
+    pub pl0_pauser: Option<u32>,
+
+    pub intermediate_ecc_pk_idx: Option<u32>,
+
+    /// Overrides the family FMC
+    pub fmc: Option<ExecutableConfig>,
+
+    /// Overrides the family runtime
+    pub runtime: Option<ExecutableConfig>,
+}
+
+/// Bundle Family Configuration
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct BundlesConfig {
+    pub key_config: String,
+
+    pub fmc: ExecutableConfig,
+
+    pub runtime: ExecutableConfig,
+
+    pub bundle: Vec<BundleConfig>,
+}
+
 /// Load Key Configuration from file
 pub(crate) fn load_key_config(path: &PathBuf) -> anyhow::Result<KeyConfig> {
     let config_str = std::fs::read_to_string(path)
@@ -59,3 +127,14 @@ pub(crate) fn load_key_config(path: &PathBuf) -> anyhow::Result<KeyConfig> {
 
     Ok(config)
 }
+
+/// Load Bundle Family Configuration from file
+pub(crate) fn load_bundles_config(path: &PathBuf) -> anyhow::Result<BundlesConfig> {
+    let config_str = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the config file {}", path.display()))?;
+
+    let config: BundlesConfig = toml::from_str(&config_str)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+
+    Ok(config)
+}
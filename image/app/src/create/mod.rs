@@ -26,11 +26,12 @@ use caliptra_image_gen::*;
 use caliptra_image_serde::ImageBundleWriter;
 use caliptra_image_types::*;
 use clap::ArgMatches;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
 use caliptra_image_elf::ElfExecutable;
-use config::{OwnerKeyConfig, VendorKeyConfig};
+use config::{ExecutableConfig, OwnerKeyConfig, VendorKeyConfig};
 
 use chrono::NaiveDate;
 
@@ -118,6 +119,14 @@ pub(crate) fn run_cmd(args: &ArgMatches) -> anyhow::Result<()> {
         .get_one::<u32>("lms-pk-idx")
         .with_context(|| "lms-pk-idx arg not specified")?;
 
+    let intermediate_ecc_key_idx = args.get_one::<u32>("intermediate-ecc-pk-idx");
+
+    let runtime_compression = if args.get_flag("rt-compress") {
+        ImageTocEntryCompression::Lzss
+    } else {
+        ImageTocEntryCompression::None
+    };
+
     let out_path: &PathBuf = args
         .get_one::<PathBuf>("out")
         .with_context(|| "out arg not specified")?;
@@ -164,22 +173,48 @@ pub(crate) fn run_cmd(args: &ArgMatches) -> anyhow::Result<()> {
         *runtime_version,
         *runtime_svn,
         runtime_rev[..IMAGE_REVISION_BYTE_SIZE].try_into()?,
-    )?;
+    )?
+    .with_compression(runtime_compression);
 
     let config_dir = config_path
         .parent()
         .with_context(|| "Invalid parent path")?;
 
+    let mut vendor_config = vendor_config(
+        config_dir,
+        &config.vendor,
+        *ecc_key_idx,
+        *lms_key_idx,
+        mfg_from_date,
+        mfg_to_date,
+    )?;
+    if let Some(idx) = intermediate_ecc_key_idx {
+        vendor_config.intermediate = Some(intermediate_config(config_dir, &config.vendor, *idx)?);
+    }
+
+    let mut owner_config = owner_config(config_dir, &config.owner, own_from_date, own_to_date)?;
+    if args.get_flag("break-glass") {
+        owner_config
+            .as_mut()
+            .with_context(|| "break-glass requires an owner key")?
+            .break_glass = true;
+    }
+    if args.get_flag("unpin-vendor-key") {
+        owner_config
+            .as_mut()
+            .with_context(|| "unpin-vendor-key requires an owner key")?
+            .unpin_vendor_key = true;
+    }
+    if let Some(pauser) = args.get_one::<u32>("owner-cmd-pauser") {
+        owner_config
+            .as_mut()
+            .with_context(|| "owner-cmd-pauser requires an owner key")?
+            .cmd_pauser = Some(*pauser);
+    }
+
     let gen_config = ImageGeneratorConfig::<ElfExecutable> {
-        vendor_config: vendor_config(
-            config_dir,
-            &config.vendor,
-            *ecc_key_idx,
-            *lms_key_idx,
-            mfg_from_date,
-            mfg_to_date,
-        )?,
-        owner_config: owner_config(config_dir, &config.owner, own_from_date, own_to_date)?,
+        vendor_config,
+        owner_config,
         fmc,
         runtime,
     };
@@ -200,6 +235,101 @@ pub(crate) fn run_cmd(args: &ArgMatches) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Run the create-bundles command
+pub(crate) fn run_bundles_cmd(args: &ArgMatches) -> anyhow::Result<()> {
+    let config_path: &PathBuf = args
+        .get_one::<PathBuf>("config")
+        .with_context(|| "config arg not specified")?;
+
+    let config = config::load_bundles_config(config_path)?;
+    let config_dir = config_path
+        .parent()
+        .with_context(|| "Invalid parent path")?;
+
+    let key_config_path = config_dir.join(&config.key_config);
+    let key_config = config::load_key_config(&key_config_path)?;
+    let key_config_dir = key_config_path
+        .parent()
+        .with_context(|| "Invalid parent path")?;
+
+    // Keys and executables are loaded once and shared by every bundle.
+    let vendor = vendor_config(
+        key_config_dir,
+        &key_config.vendor,
+        0,
+        0,
+        [0u8; 15],
+        [0u8; 15],
+    )?;
+    let owner = owner_config(key_config_dir, &key_config.owner, [0u8; 15], [0u8; 15])?;
+
+    let mut executables = HashMap::new();
+    let mut gen_configs = vec![];
+    for bundle in config.bundle.iter() {
+        let fmc = bundle.fmc.as_ref().unwrap_or(&config.fmc);
+        let runtime = bundle.runtime.as_ref().unwrap_or(&config.runtime);
+
+        let mut vendor_config = vendor.clone();
+        vendor_config.ecc_key_idx = bundle.ecc_pk_idx;
+        vendor_config.lms_key_idx = bundle.lms_pk_idx;
+        vendor_config.pl0_pauser = bundle.pl0_pauser;
+        if let Some(idx) = bundle.intermediate_ecc_pk_idx {
+            vendor_config.intermediate = Some(intermediate_config(
+                key_config_dir,
+                &key_config.vendor,
+                idx,
+            )?);
+        }
+
+        gen_configs.push(ImageGeneratorConfig::<ElfExecutable> {
+            vendor_config,
+            owner_config: owner.clone(),
+            fmc: open_executable(config_dir, fmc, &mut executables)?,
+            runtime: open_executable(config_dir, runtime, &mut executables)?,
+        });
+    }
+
+    let gen = ImageGenerator::new(Crypto::default());
+    let images = gen.generate_all(&gen_configs)?;
+
+    for (bundle, image) in config.bundle.iter().zip(images.iter()) {
+        let out_path = config_dir.join(&bundle.out);
+        let out_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&out_path)
+            .with_context(|| format!("Failed to create file {}", out_path.display()))?;
+
+        let mut writer = ImageBundleWriter::new(out_file);
+        writer.write(image)?;
+    }
+
+    Ok(())
+}
+
+/// Open an ELF executable, reusing a previously opened copy if available
+fn open_executable(
+    path: &Path,
+    config: &ExecutableConfig,
+    executables: &mut HashMap<ExecutableConfig, ElfExecutable>,
+) -> anyhow::Result<ElfExecutable> {
+    if let Some(executable) = executables.get(config) {
+        return Ok(executable.clone());
+    }
+
+    let rev = hex::decode(&config.rev)?;
+    let executable = ElfExecutable::open(
+        &path.join(&config.path),
+        config.version,
+        config.svn,
+        rev[..IMAGE_REVISION_BYTE_SIZE].try_into()?,
+    )?;
+    executables.insert(config.clone(), executable.clone());
+
+    Ok(executable)
+}
+
 /// Generate Vendor Config
 fn vendor_config(
     path: &Path,
@@ -265,6 +395,47 @@ fn vendor_config(
     Ok(gen_config)
 }
 
+/// Generate vendor intermediate key config
+fn intermediate_config(
+    path: &Path,
+    config: &VendorKeyConfig,
+    ecc_key_idx: u32,
+) -> anyhow::Result<ImageGeneratorVendorIntermediateConfig> {
+    let config = config
+        .intermediate
+        .as_ref()
+        .with_context(|| "No vendor intermediate keys in key config")?;
+    if config.ecc_pub_keys.len() > VENDOR_INTERMEDIATE_ECC_KEY_COUNT as usize {
+        return Err(anyhow!(
+            "At most {VENDOR_INTERMEDIATE_ECC_KEY_COUNT} vendor intermediate keys are supported"
+        ));
+    }
+    if ecc_key_idx as usize >= config.ecc_pub_keys.len() {
+        return Err(anyhow!(
+            "Invalid vendor intermediate ECC public key index {ecc_key_idx}"
+        ));
+    }
+
+    let mut gen_config = ImageGeneratorVendorIntermediateConfig::default();
+    for (i, pem_file) in config.ecc_pub_keys.iter().enumerate() {
+        let pub_key_path = path.join(pem_file);
+        gen_config.pub_keys.ecc_pub_keys[i] = Crypto::ecc_pub_key_from_pem(&pub_key_path)?;
+    }
+    gen_config.pub_keys.revocation = config.revocation;
+    gen_config.pub_keys.svn = config.svn;
+    gen_config.ecc_key_idx = ecc_key_idx;
+
+    if let Some(ecc_priv_keys) = &config.ecc_priv_keys {
+        let pem_file = ecc_priv_keys
+            .get(ecc_key_idx as usize)
+            .with_context(|| "Missing vendor intermediate ECC private key")?;
+        let priv_key_path = path.join(pem_file);
+        gen_config.ecc_priv_key = Some(Crypto::ecc_priv_key_from_pem(&priv_key_path)?);
+    }
+
+    Ok(gen_config)
+}
+
 /// Generate owner config
 fn owner_config(
     path: &Path,
@@ -296,6 +467,20 @@ fn owner_config(
             priv_keys.lms_priv_key = lms_priv_key_from_pem(&priv_key_path)?;
             gen_config.priv_keys = Some(priv_keys);
         }
+
+        let next_pub_keys = (&config.next_ecc_pub_key, &config.next_lms_pub_key);
+        gen_config.next_owner_pub_keys = match next_pub_keys {
+            (Some(ecc_pem_file), Some(lms_pem_file)) => Some(ImageOwnerPubKeys {
+                ecc_pub_key: Crypto::ecc_pub_key_from_pem(&path.join(ecc_pem_file))?,
+                lms_pub_key: lms_pub_key_from_pem(&path.join(lms_pem_file))?,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(anyhow!(
+                    "Both next_ecc_pub_key and next_lms_pub_key must be specified"
+                ))
+            }
+        };
         gen_config.not_before = from_date;
         gen_config.not_after = to_date;
 
@@ -36,6 +36,11 @@ fn main() {
                 .required(false)
                 .value_parser(value_parser!(u32)),
         )
+        .arg(
+            arg!(--"intermediate-ecc-pk-idx" <U32> "Vendor Intermediate ECC Public Key Index")
+                .required(false)
+                .value_parser(value_parser!(u32)),
+        )
         .arg(
             arg!(--"fmc" <FILE> "FMC ELF binary")
                 .required(true)
@@ -76,6 +81,23 @@ fn main() {
                 .required(true)
                 .value_parser(value_parser!(u32)),
         )
+        .arg(
+            arg!(--"rt-compress" "Compress the Runtime with LZSS")
+                .required(false),
+        )
+        .arg(
+            arg!(--"break-glass" "Mark the image as an owner-signed break-glass recovery image")
+                .required(false),
+        )
+        .arg(
+            arg!(--"unpin-vendor-key" "Allow an update to use vendor key indices other than the ones used at cold boot")
+                .required(false),
+        )
+        .arg(
+            arg!(--"owner-cmd-pauser" <U32> "Restrict the runtime owner management commands to this PAUSER")
+                .required(false)
+                .value_parser(value_parser!(u32)),
+        )
         .arg(
             arg!(--"out" <FILE> "Output file")
                 .required(true)
@@ -100,6 +122,13 @@ fn main() {
             arg!(--"mfg-to-date" <String> "Certificate Validity End Date By Manufacturer [YYYYMMDDHHMMSS - Zulu Time]")
                 .required(false)
                 .value_parser(value_parser!(String)),
+        ),
+        Command::new("create-bundles")
+        .about("Create a family of firmware image bundles from one configuration file")
+        .arg(
+            arg!(--"config" <FILE> "Bundle family configuration file")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
         )];
 
     let cmd = Command::new("caliptra-image-app")
@@ -110,6 +139,7 @@ fn main() {
 
     let result = match cmd.subcommand().unwrap() {
         ("create", args) => create::run_cmd(args),
+        ("create-bundles", args) => create::run_bundles_cmd(args),
         (_, _) => unreachable!(),
     };
 
@@ -14,14 +14,14 @@ Abstract:
 
 use anyhow::{bail, Context};
 use caliptra_image_gen::ImageGenratorExecutable;
-use caliptra_image_types::ImageRevision;
+use caliptra_image_types::{ImageRevision, ImageTocEntryCompression};
 use elf::abi::PT_LOAD;
 use elf::endian::AnyEndian;
 use elf::ElfBytes;
 use std::path::PathBuf;
 
 /// ELF Executable
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ElfExecutable {
     version: u32,
     svn: u32,
@@ -29,6 +29,7 @@ pub struct ElfExecutable {
     load_addr: u32,
     entry_point: u32,
     content: Vec<u8>,
+    compression: ImageTocEntryCompression,
 }
 
 fn load_into_image(
@@ -104,8 +105,15 @@ impl ElfExecutable {
             load_addr,
             entry_point,
             content,
+            compression: ImageTocEntryCompression::None,
         })
     }
+
+    /// Compress the executable with `compression` when generating the image.
+    pub fn with_compression(mut self, compression: ImageTocEntryCompression) -> Self {
+        self.compression = compression;
+        self
+    }
 }
 
 impl ImageGenratorExecutable for ElfExecutable {
@@ -143,6 +151,11 @@ impl ImageGenratorExecutable for ElfExecutable {
     fn size(&self) -> u32 {
         self.content.len() as u32
     }
+
+    /// Executable compression
+    fn compression(&self) -> ImageTocEntryCompression {
+        self.compression
+    }
 }
 
 #[cfg(test)]
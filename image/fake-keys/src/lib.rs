@@ -340,6 +340,7 @@ pub const VENDOR_CONFIG_KEY_0: ImageGeneratorVendorConfig = ImageGeneratorVendor
     not_before: [0u8; 15],
     not_after: [0u8; 15],
     pl0_pauser: Some(0x1),
+    intermediate: None,
 };
 
 pub const VENDOR_CONFIG_KEY_1: ImageGeneratorVendorConfig = ImageGeneratorVendorConfig {
@@ -372,6 +373,10 @@ pub const OWNER_CONFIG: ImageGeneratorOwnerConfig = ImageGeneratorOwnerConfig {
     not_before: [0u8; 15],
     not_after: [0u8; 15],
     epoch: [0u8; 2],
+    next_owner_pub_keys: None,
+    break_glass: false,
+    unpin_vendor_key: false,
+    cmd_pauser: None,
 };
 
 #[test]
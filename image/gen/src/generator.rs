@@ -14,6 +14,7 @@ Abstract:
 use anyhow::bail;
 use caliptra_image_types::*;
 use memoffset::offset_of;
+use std::collections::{hash_map::Entry, HashMap};
 use zerocopy::IntoBytes;
 
 use crate::*;
@@ -45,42 +46,98 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
     where
         E: ImageGenratorExecutable,
     {
-        let image_size =
-            IMAGE_MANIFEST_BYTE_SIZE as u32 + config.fmc.size() + config.runtime.size();
-        if image_size > IMAGE_BYTE_SIZE as u32 {
-            bail!(
-                "Image larger than {IMAGE_BYTE_SIZE} bytes; image size:{} bytes",
-                image_size
-            );
-        }
+        self.generate_with_digests(config, &mut HashMap::new())
+    }
 
+    /// Generate a family of images
+    ///
+    /// Executables shared between configurations (e.g. the same runtime
+    /// signed for several SKUs) are only hashed once.
+    ///
+    /// # Arguments
+    ///
+    /// * `configs` - Image generator configuration for each image
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<ImageBundle>` - Caliptra Image Bundle for each configuration
+    pub fn generate_all<E>(
+        &self,
+        configs: &[ImageGeneratorConfig<E>],
+    ) -> anyhow::Result<Vec<ImageBundle>>
+    where
+        E: ImageGenratorExecutable,
+    {
+        let mut digests = HashMap::new();
+        configs
+            .iter()
+            .map(|config| self.generate_with_digests(config, &mut digests))
+            .collect()
+    }
+
+    /// Generate image, reusing and recording executable digests in `digests`
+    fn generate_with_digests<'a, E>(
+        &self,
+        config: &'a ImageGeneratorConfig<E>,
+        digests: &mut HashMap<&'a [u8], ImageDigest>,
+    ) -> anyhow::Result<ImageBundle>
+    where
+        E: ImageGenratorExecutable,
+    {
         // Create FMC TOC & Content
         let id = ImageTocEntryId::Fmc;
-        let offset = IMAGE_MANIFEST_BYTE_SIZE as u32;
-        let (fmc_toc, fmc) = self.gen_image(&config.fmc, id, offset)?;
+        let offset = config.manifest_format.size() as u32;
+        let (fmc_tocs, fmc) = self.gen_image(&config.fmc, id, offset, digests)?;
+        let [fmc_toc] = fmc_tocs[..] else {
+            bail!(
+                "FMC must have a single segment; segments:{}",
+                fmc_tocs.len()
+            );
+        };
 
         // Create Runtime TOC & Content
         let id = ImageTocEntryId::Runtime;
         let offset = offset + fmc_toc.size;
-        let (runtime_toc, runtime) = self.gen_image(&config.runtime, id, offset)?;
+        let (runtime_tocs, runtime) = self.gen_image(&config.runtime, id, offset, digests)?;
 
-        // Check if fmc and runtime image load address ranges don't overlap.
-        if fmc_toc.overlaps(&runtime_toc) {
+        let image_size = config.manifest_format.size() + fmc.len() + runtime.len();
+        if image_size > IMAGE_BYTE_SIZE {
             bail!(
-                "FMC:[{:#x?}:{:#x?}] and Runtime:[{:#x?}:{:#x?}] load address ranges overlap",
-                fmc_toc.load_addr,
-                fmc_toc.load_addr + fmc_toc.size - 1,
-                runtime_toc.load_addr,
-                runtime_toc.load_addr + runtime_toc.size - 1
+                "Image larger than {IMAGE_BYTE_SIZE} bytes; image size:{} bytes",
+                image_size
             );
         }
 
+        // Check if fmc and runtime image load address ranges don't overlap.
+        for (i, toc) in runtime_tocs.iter().enumerate() {
+            if fmc_toc.overlaps(toc) {
+                bail!(
+                    "FMC:[{:#x?}:{:#x?}] and Runtime:[{:#x?}:{:#x?}] load address ranges overlap",
+                    fmc_toc.load_addr,
+                    fmc_toc.load_addr + fmc_toc.load_size() - 1,
+                    toc.load_addr,
+                    toc.load_addr + toc.load_size() - 1
+                );
+            }
+            if runtime_tocs[i + 1..]
+                .iter()
+                .any(|other| toc.overlaps(other))
+            {
+                bail!(
+                    "Runtime segment [{:#x?}:{:#x?}] overlaps another runtime segment",
+                    toc.load_addr,
+                    toc.load_addr + toc.load_size() - 1
+                );
+            }
+        }
+
         let ecc_key_idx = config.vendor_config.ecc_key_idx;
         let lms_key_idx = config.vendor_config.lms_key_idx;
 
         // Create Header
-        let toc_digest = self.toc_digest(&fmc_toc, &runtime_toc)?;
-        let header = self.gen_header(config, ecc_key_idx, lms_key_idx, toc_digest)?;
+        let toc_digest = self.segmented_toc_digest(&fmc_toc, &runtime_tocs)?;
+        let toc_len = 1 + runtime_tocs.len() as u32;
+        let header = self.gen_header(config, ecc_key_idx, lms_key_idx, toc_len, toc_digest)?;
 
         // Create Preamable
         let header_digest_vendor = self.header_digest_vendor(&header)?;
@@ -94,20 +151,27 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
         )?;
 
         // Create Manifest
-        let manifest = ImageManifest {
+        let mut manifest = ImageManifest {
             marker: MANIFEST_MARKER,
             size: core::mem::size_of::<ImageManifest>() as u32,
             preamble,
             header,
             fmc: fmc_toc,
-            runtime: runtime_toc,
+            runtime: runtime_tocs[0],
+            runtime_extra_segments: Default::default(),
         };
+        manifest.runtime_extra_segments[..runtime_tocs.len() - 1]
+            .copy_from_slice(&runtime_tocs[1..]);
+
+        let vendor_intermediate_keys = self.gen_vendor_intermediate_keys(config, ecc_key_idx)?;
 
         // Create Image Bundle
         let image = ImageBundle {
             manifest,
+            vendor_intermediate_keys,
             fmc,
             runtime,
+            manifest_format: config.manifest_format,
         };
 
         Ok(image)
@@ -128,13 +192,26 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
         let mut vendor_sigs = ImageSignatures::default();
         let mut owner_sigs = ImageSignatures::default();
 
+        if let Some(intermediate) = &config.vendor_config.intermediate {
+            if let Some(priv_key) = &intermediate.ecc_priv_key {
+                vendor_sigs.ecc_sig = self.crypto.ecdsa384_sign(
+                    digest_vendor,
+                    priv_key,
+                    &intermediate.pub_keys.ecc_pub_keys[intermediate.ecc_key_idx as usize],
+                )?;
+            }
+        }
+
         if let Some(priv_keys) = config.vendor_config.priv_keys {
-            let sig = self.crypto.ecdsa384_sign(
-                digest_vendor,
-                &priv_keys.ecc_priv_keys[ecc_vendor_key_idx as usize],
-                &config.vendor_config.pub_keys.ecc_pub_keys[ecc_vendor_key_idx as usize],
-            )?;
-            vendor_sigs.ecc_sig = sig;
+            let ecc_priv_key = &priv_keys.ecc_priv_keys[ecc_vendor_key_idx as usize];
+            let ecc_pub_key =
+                &config.vendor_config.pub_keys.ecc_pub_keys[ecc_vendor_key_idx as usize];
+            // With intermediate keys, the vendor key only signs the key table
+            if config.vendor_config.intermediate.is_none() {
+                vendor_sigs.ecc_sig =
+                    self.crypto
+                        .ecdsa384_sign(digest_vendor, ecc_priv_key, ecc_pub_key)?;
+            }
             let lms_sig = self.crypto.lms_sign(
                 digest_vendor,
                 &priv_keys.lms_priv_keys[lms_vendor_key_idx as usize],
@@ -166,19 +243,51 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             ..Default::default()
         };
 
+        if let Some(intermediate) = &config.vendor_config.intermediate {
+            preamble.vendor_intermediate_ecc_key_idx = intermediate.ecc_key_idx;
+        }
+
         if let Some(owner_config) = &config.owner_config {
             preamble.owner_pub_keys = owner_config.pub_keys;
+            preamble.next_owner_pub_keys_digest = self.next_owner_pubkey_digest(owner_config)?;
         }
 
         Ok(preamble)
     }
 
+    /// Create the vendor intermediate key table, signed by the vendor ECC key
+    pub fn gen_vendor_intermediate_keys<E>(
+        &self,
+        config: &ImageGeneratorConfig<E>,
+        ecc_vendor_key_idx: u32,
+    ) -> anyhow::Result<ImageVendorIntermediateKeyTable>
+    where
+        E: ImageGenratorExecutable,
+    {
+        let mut table = ImageVendorIntermediateKeyTable::default();
+        let Some(intermediate) = &config.vendor_config.intermediate else {
+            return Ok(table);
+        };
+        table.keys = intermediate.pub_keys;
+
+        if let Some(priv_keys) = config.vendor_config.priv_keys {
+            let digest = self.crypto.sha384_digest(table.keys.as_bytes())?;
+            table.sig = self.crypto.ecdsa384_sign(
+                &digest,
+                &priv_keys.ecc_priv_keys[ecc_vendor_key_idx as usize],
+                &config.vendor_config.pub_keys.ecc_pub_keys[ecc_vendor_key_idx as usize],
+            )?;
+        }
+        Ok(table)
+    }
+
     /// Generate header
     fn gen_header<E>(
         &self,
         config: &ImageGeneratorConfig<E>,
         ecc_key_idx: u32,
         lms_key_idx: u32,
+        toc_len: u32,
         digest: ImageDigest,
     ) -> anyhow::Result<ImageHeader>
     where
@@ -188,7 +297,7 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             vendor_ecc_pub_key_idx: ecc_key_idx,
             vendor_lms_pub_key_idx: lms_key_idx,
             flags: Self::DEFAULT_FLAGS,
-            toc_len: MAX_TOC_ENTRY_COUNT,
+            toc_len,
             toc_digest: digest,
             ..Default::default()
         };
@@ -201,10 +310,26 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             header.pl0_pauser = pauser;
         }
 
+        if config.vendor_config.intermediate.is_some() {
+            header.flags |= VENDOR_INTERMEDIATE_KEY_FLAG;
+        }
+
         if let Some(owner_config) = &config.owner_config {
             header.owner_data.owner_not_before = owner_config.not_before;
             header.owner_data.owner_not_after = owner_config.not_after;
             header.owner_data.epoch = owner_config.epoch;
+            if owner_config.break_glass {
+                header.owner_data.flags |= OWNER_BREAK_GLASS_FLAG;
+            }
+            if owner_config.unpin_vendor_key {
+                header.owner_data.flags |= OWNER_UNPIN_VENDOR_KEY_FLAG;
+            }
+            if let Some(cmd_pauser) = owner_config.cmd_pauser {
+                header.owner_data.flags |= OWNER_CMD_PAUSER_FLAG;
+                header.owner_data.cmd_pauser = cmd_pauser;
+            }
+            header.owner_data.next_owner_pub_keys_digest =
+                self.next_owner_pubkey_digest(owner_config)?;
         }
 
         Ok(header)
@@ -229,28 +354,90 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             .sha384_digest(preamble.owner_pub_keys.as_bytes())
     }
 
+    /// Calculate the digest of the owner public key(s) to rotate to, or zero
+    /// if no rotation is requested
+    fn next_owner_pubkey_digest(
+        &self,
+        owner_config: &ImageGeneratorOwnerConfig,
+    ) -> anyhow::Result<ImageDigest> {
+        match &owner_config.next_owner_pub_keys {
+            Some(pub_keys) => self.crypto.sha384_digest(pub_keys.as_bytes()),
+            None => Ok(ImageDigest::default()),
+        }
+    }
+
     /// Calculate vendor public key(s) digest
     pub fn vendor_pubkey_digest(&self, preamble: &ImagePreamble) -> anyhow::Result<ImageDigest> {
         self.crypto
             .sha384_digest(preamble.vendor_pub_keys.as_bytes())
     }
 
-    /// Generate image
-    fn gen_image<E>(
+    /// Generate the TOC entries and stored content of an executable, one
+    /// entry per segment.
+    ///
+    /// The first entry holds the digest of the whole stored content.
+    /// Additional segments only describe where their part of the content is
+    /// loaded. Compressed segments are compressed separately so each can be
+    /// decompressed to its own load address.
+    fn gen_image<'a, E>(
         &self,
-        image: &E,
+        image: &'a E,
         id: ImageTocEntryId,
         offset: u32,
-    ) -> anyhow::Result<(ImageTocEntry, Vec<u8>)>
+        digests: &mut HashMap<&'a [u8], ImageDigest>,
+    ) -> anyhow::Result<(Vec<ImageTocEntry>, Vec<u8>)>
     where
         E: ImageGenratorExecutable,
     {
-        let r#type = ImageTocEntryType::Executable;
-        let digest = self.crypto.sha384_digest(image.content())?;
+        let segments = image.segments();
+        if segments.is_empty() || segments.len() > MAX_RUNTIME_SEGMENT_COUNT as usize {
+            bail!(
+                "Executable must have between 1 and {MAX_RUNTIME_SEGMENT_COUNT} segments; segments:{}",
+                segments.len()
+            );
+        }
+        if segments.iter().any(|s| s.size == 0) {
+            bail!("Executable segments must not be empty");
+        }
+        if segments.iter().map(|s| s.size as u64).sum::<u64>() != image.content().len() as u64 {
+            bail!("Executable segment sizes do not add up to the executable size");
+        }
+
+        let compression = image.compression();
+        let (content, stored_sizes) = match compression {
+            ImageTocEntryCompression::None => (
+                image.content().clone(),
+                segments.iter().map(|s| s.size).collect::<Vec<_>>(),
+            ),
+            ImageTocEntryCompression::Lzss => {
+                let mut content = vec![];
+                let mut stored_sizes = vec![];
+                let mut remaining = image.content().as_slice();
+                for segment in segments.iter() {
+                    let (data, rest) = remaining.split_at(segment.size as usize);
+                    remaining = rest;
+
+                    // Padded to whole words, as the ROM loads words
+                    let mut compressed = lzss::compress(data);
+                    compressed.resize(compressed.len().next_multiple_of(4), 0);
+                    stored_sizes.push(compressed.len() as u32);
+                    content.extend_from_slice(&compressed);
+                }
+                (content, stored_sizes)
+            }
+        };
+
+        let digest = match compression {
+            ImageTocEntryCompression::None => match digests.entry(image.content().as_slice()) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => *entry.insert(self.crypto.sha384_digest(image.content())?),
+            },
+            ImageTocEntryCompression::Lzss => self.crypto.sha384_digest(&content)?,
+        };
 
-        let entry = ImageTocEntry {
+        let toc = ImageTocEntry {
             id: id.into(),
-            r#type: r#type.into(),
+            r#type: ImageTocEntryType::Executable.into(),
             revision: *image.rev(),
             version: image.version(),
             svn: image.svn(),
@@ -258,11 +445,38 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             load_addr: image.load_addr(),
             entry_point: image.entry_point(),
             offset,
-            size: image.content().len() as u32,
+            size: content.len() as u32,
+            compression: compression.into(),
+            uncompressed_size: 0,
             digest,
         };
 
-        Ok((entry, image.content().clone()))
+        let mut offset = offset;
+        let mut tocs = vec![];
+        for (i, (segment, stored_size)) in segments.iter().zip(stored_sizes).enumerate() {
+            let toc = if i == 0 {
+                toc
+            } else {
+                ImageTocEntry {
+                    entry_point: 0,
+                    digest: ImageDigest::default(),
+                    ..toc
+                }
+            };
+            tocs.push(ImageTocEntry {
+                load_addr: segment.load_addr,
+                offset,
+                size: stored_size,
+                uncompressed_size: match compression {
+                    ImageTocEntryCompression::None => 0,
+                    ImageTocEntryCompression::Lzss => segment.size,
+                },
+                ..toc
+            });
+            offset += stored_size;
+        }
+
+        Ok((tocs, content))
     }
 
     /// Calculate TOC digest
@@ -270,10 +484,21 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
         &self,
         fmc_toc: &ImageTocEntry,
         rt_toc: &ImageTocEntry,
+    ) -> anyhow::Result<ImageDigest> {
+        self.segmented_toc_digest(fmc_toc, core::slice::from_ref(rt_toc))
+    }
+
+    /// Calculate TOC digest for a runtime with one or more segments
+    pub fn segmented_toc_digest(
+        &self,
+        fmc_toc: &ImageTocEntry,
+        rt_tocs: &[ImageTocEntry],
     ) -> anyhow::Result<ImageDigest> {
         let mut toc_content: Vec<u8> = Vec::new();
         toc_content.extend_from_slice(fmc_toc.as_bytes());
-        toc_content.extend_from_slice(rt_toc.as_bytes());
+        for rt_toc in rt_tocs {
+            toc_content.extend_from_slice(rt_toc.as_bytes());
+        }
         self.crypto.sha384_digest(&toc_content)
     }
 }
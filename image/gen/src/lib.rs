@@ -41,6 +41,30 @@ pub trait ImageGenratorExecutable {
 
     /// Executable Size
     fn size(&self) -> u32;
+
+    /// Executable Segments. The content is the concatenation of the segments,
+    /// each loaded at its own address.
+    fn segments(&self) -> Vec<ImageExecutableSegment> {
+        vec![ImageExecutableSegment {
+            load_addr: self.load_addr(),
+            size: self.size(),
+        }]
+    }
+
+    /// Compression applied to the executable's segments in the image
+    fn compression(&self) -> ImageTocEntryCompression {
+        ImageTocEntryCompression::None
+    }
+}
+
+/// Contiguous part of an executable's content
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImageExecutableSegment {
+    /// Segment Load Address
+    pub load_addr: u32,
+
+    /// Segment Size
+    pub size: u32,
 }
 
 pub trait ImageGeneratorHasher {
@@ -105,6 +129,20 @@ pub struct ImageGeneratorVendorConfig {
     pub not_after: [u8; 15],
 
     pub pl0_pauser: Option<u32>,
+
+    /// Intermediate keys to sign the image with instead of the vendor ECC key
+    pub intermediate: Option<ImageGeneratorVendorIntermediateConfig>,
+}
+
+/// Image Generator Vendor Intermediate Key Configuration
+#[derive(Default, Clone)]
+pub struct ImageGeneratorVendorIntermediateConfig {
+    pub pub_keys: ImageVendorIntermediateKeys,
+
+    pub ecc_key_idx: u32,
+
+    /// Private key of the intermediate key at `ecc_key_idx`
+    pub ecc_priv_key: Option<ImageEccPrivKey>,
 }
 
 /// Image Generator Owner Configuration
@@ -119,6 +157,19 @@ pub struct ImageGeneratorOwnerConfig {
     pub not_after: [u8; 15],
 
     pub epoch: [u8; 2],
+
+    /// Owner public keys the image authorizes rotating to
+    pub next_owner_pub_keys: Option<ImageOwnerPubKeys>,
+
+    /// Mark the image as a break-glass recovery image
+    pub break_glass: bool,
+
+    /// Allow the image to use vendor key indices other than the ones pinned
+    /// at cold boot
+    pub unpin_vendor_key: bool,
+
+    /// Restrict the runtime owner management commands to this PAUSER
+    pub cmd_pauser: Option<u32>,
 }
 
 /// Image Generator Configuration
@@ -134,4 +185,6 @@ where
     pub fmc: T,
 
     pub runtime: T,
+
+    pub manifest_format: ImageManifestFormat,
 }
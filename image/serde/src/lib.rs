@@ -29,6 +29,8 @@ impl<W: Write> ImageBundleWriter<W> {
     /// Write Image Bundle
     pub fn write(&mut self, image: &ImageBundle) -> anyhow::Result<()> {
         self.writer.write_all(image.manifest.as_bytes())?;
+        self.writer
+            .write_all(image.vendor_intermediate_keys.as_bytes())?;
         self.writer.write_all(&image.fmc)?;
         self.writer.write_all(&image.runtime)?;
         Ok(())
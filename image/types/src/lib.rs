@@ -14,6 +14,9 @@ Abstract:
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod lzss;
+pub mod manifest_v2;
+
 use caliptra_error::{CaliptraError, CaliptraResult};
 use core::mem::size_of;
 use core::ops::Range;
@@ -28,7 +31,10 @@ use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 pub const MANIFEST_MARKER: u32 = 0x4E414D43;
 pub const VENDOR_ECC_KEY_COUNT: u32 = 4;
 pub const VENDOR_LMS_KEY_COUNT: u32 = 32;
-pub const MAX_TOC_ENTRY_COUNT: u32 = 2;
+pub const VENDOR_INTERMEDIATE_ECC_KEY_COUNT: u32 = 8;
+pub const MAX_RUNTIME_SEGMENT_COUNT: u32 = 2;
+pub const MIN_TOC_ENTRY_COUNT: u32 = 2;
+pub const MAX_TOC_ENTRY_COUNT: u32 = 1 + MAX_RUNTIME_SEGMENT_COUNT;
 pub const IMAGE_REVISION_BYTE_SIZE: usize = 20;
 pub const ECC384_SCALAR_WORD_SIZE: usize = 12;
 pub const ECC384_SCALAR_BYTE_SIZE: usize = 48;
@@ -44,8 +50,30 @@ pub const IMAGE_BYTE_SIZE: usize = 128 * 1024;
 pub const IMAGE_LMS_TREE_TYPE: LmsAlgorithmType = LmsAlgorithmType::LmsSha256N24H15;
 // LMOTS-SHA192-W4
 pub const IMAGE_LMS_OTS_TYPE: LmotsAlgorithmType = LmotsAlgorithmType::LmotsSha256N24W4;
+
+/// Header flag indicating the vendor ECC signature was produced by the
+/// intermediate key at `ImagePreamble::vendor_intermediate_ecc_key_idx`
+/// rather than directly by the vendor ECC public key.
+pub const VENDOR_INTERMEDIATE_KEY_FLAG: u32 = 1 << 1;
+
+/// Owner data flag marking a break-glass recovery image. Such an image is
+/// accepted regardless of the SVN fuses, but boots with attestation disabled
+/// and a distinct PCR measurement.
+pub const OWNER_BREAK_GLASS_FLAG: u32 = 1 << 0;
+
+/// Owner data flag allowing an update reset image to use vendor key indices
+/// other than the ones pinned at cold boot.
+pub const OWNER_UNPIN_VENDOR_KEY_FLAG: u32 = 1 << 1;
+
+/// Owner data flag restricting the runtime owner management commands to the
+/// `cmd_pauser` of the owner data.
+pub const OWNER_CMD_PAUSER_FLAG: u32 = 1 << 2;
+
 pub const IMAGE_MANIFEST_BYTE_SIZE: usize = core::mem::size_of::<ImageManifest>();
 
+/// Size of the table of contents, including unused runtime segment entries
+pub const IMAGE_TOC_BYTE_SIZE: usize = MAX_TOC_ENTRY_COUNT as usize * size_of::<ImageTocEntry>();
+
 pub type ImageScalar = [u32; ECC384_SCALAR_WORD_SIZE];
 pub type ImageDigest = [u32; SHA384_DIGEST_WORD_SIZE];
 pub type ImageRevision = [u8; IMAGE_REVISION_BYTE_SIZE];
@@ -111,11 +139,17 @@ pub struct ImageBundle {
     /// Manifest
     pub manifest: ImageManifest,
 
+    /// Vendor intermediate key table
+    pub vendor_intermediate_keys: ImageVendorIntermediateKeyTable,
+
     /// FMC
     pub fmc: Vec<u8>,
 
     /// Runtime
     pub runtime: Vec<u8>,
+
+    /// Format of the manifest in the image
+    pub manifest_format: ImageManifestFormat,
 }
 
 #[cfg(feature = "std")]
@@ -123,7 +157,15 @@ impl ImageBundle {
     pub fn to_bytes(&self) -> std::io::Result<Vec<u8>> {
         use std::io::ErrorKind;
         let mut result = vec![];
-        result.extend_from_slice(self.manifest.as_bytes());
+        match self.manifest_format {
+            ImageManifestFormat::V1 => {
+                result.extend_from_slice(self.manifest.as_bytes());
+                result.extend_from_slice(self.vendor_intermediate_keys.as_bytes());
+            }
+            ImageManifestFormat::V2 => {
+                result.extend_from_slice(&self.manifest.to_v2_bytes(&self.vendor_intermediate_keys))
+            }
+        }
         if self.manifest.fmc.offset as usize != result.len() {
             return Err(std::io::Error::new(
                 ErrorKind::Other,
@@ -143,7 +185,7 @@ impl ImageBundle {
                 "actual runtime offset does not match manifest",
             ));
         }
-        if self.manifest.runtime.size as usize != self.runtime.len() {
+        if self.manifest.runtime_size() as usize != self.runtime.len() {
             return Err(std::io::Error::new(
                 ErrorKind::Other,
                 "actual runtime size does not match manifest",
@@ -154,6 +196,103 @@ impl ImageBundle {
     }
 }
 
+/// Format of the manifest at the start of an image
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageManifestFormat {
+    /// `ImageManifest` as is
+    #[default]
+    V1,
+
+    /// Self-describing header followed by sections. See `manifest_v2`.
+    V2,
+}
+
+impl ImageManifestFormat {
+    /// Returns the size of the manifest in the image, including the vendor
+    /// intermediate key table
+    pub const fn size(self) -> usize {
+        match self {
+            ImageManifestFormat::V1 => {
+                size_of::<ImageManifest>() + size_of::<ImageVendorIntermediateKeyTable>()
+            }
+            ImageManifestFormat::V2 => manifest_v2::MANIFEST_V2_SIZE,
+        }
+    }
+}
+
+/// Location of the manifest structures in an image.
+///
+/// The signed parts of the manifest are hashed from the image itself, so their
+/// offsets depend on the format of the manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageManifestLayout {
+    /// Size of the manifest in the image
+    pub size: u32,
+
+    /// Offset of the preamble in the image
+    pub preamble_offset: u32,
+
+    /// Offset of the header in the image
+    pub header_offset: u32,
+
+    /// Offset of the table of contents in the image
+    pub toc_offset: u32,
+
+    /// Offset of the `ImageVendorIntermediateKeyTable` in the image
+    pub vendor_intermediate_keys_offset: u32,
+}
+
+impl Default for ImageManifestLayout {
+    fn default() -> Self {
+        Self::v1()
+    }
+}
+
+impl ImageManifestLayout {
+    /// Returns the layout of a version 1 manifest
+    pub fn v1() -> Self {
+        Self {
+            size: ImageManifestFormat::V1.size() as u32,
+            preamble_offset: offset_of!(ImageManifest, preamble) as u32,
+            header_offset: offset_of!(ImageManifest, header) as u32,
+            toc_offset: offset_of!(ImageManifest, fmc) as u32,
+            vendor_intermediate_keys_offset: size_of::<ImageManifest>() as u32,
+        }
+    }
+
+    fn preamble_range(&self, span: Range<usize>) -> Range<u32> {
+        span.start as u32 + self.preamble_offset..span.end as u32 + self.preamble_offset
+    }
+
+    /// Returns the `Range<u32>` containing the vendor public keys
+    pub fn vendor_pub_keys_range(&self) -> Range<u32> {
+        self.preamble_range(span_of!(ImagePreamble, vendor_pub_keys))
+    }
+
+    /// Returns `Range<u32>` containing the vendor intermediate key table
+    pub fn vendor_intermediate_keys_range(&self) -> Range<u32> {
+        let span = span_of!(ImageVendorIntermediateKeyTable, keys);
+        span.start as u32 + self.vendor_intermediate_keys_offset
+            ..span.end as u32 + self.vendor_intermediate_keys_offset
+    }
+
+    /// Returns `Range<u32>` containing the owner public key
+    pub fn owner_pub_key_range(&self) -> Range<u32> {
+        self.preamble_range(span_of!(ImagePreamble, owner_pub_keys))
+    }
+
+    /// Returns `Range<u32>` containing the header
+    pub fn header_range(&self) -> Range<u32> {
+        self.header_offset..self.header_offset + size_of::<ImageHeader>() as u32
+    }
+
+    /// Returns `Range<u32>` containing the table of contents, including
+    /// unused runtime segment entries
+    pub fn toc_range(&self) -> Range<u32> {
+        self.toc_offset..self.toc_offset + IMAGE_TOC_BYTE_SIZE as u32
+    }
+}
+
 /// Calipatra Image Manifest
 #[repr(C)]
 #[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Clone, Copy, Debug, Zeroize)]
@@ -176,6 +315,10 @@ pub struct ImageManifest {
 
     /// Runtime TOC Entry
     pub runtime: ImageTocEntry,
+
+    /// Additional Runtime Segment TOC Entries. Only the first
+    /// `header.toc_len - 2` entries are used.
+    pub runtime_extra_segments: [ImageTocEntry; MAX_RUNTIME_SEGMENT_COUNT as usize - 1],
 }
 
 impl Default for ImageManifest {
@@ -187,34 +330,117 @@ impl Default for ImageManifest {
             header: ImageHeader::default(),
             fmc: ImageTocEntry::default(),
             runtime: ImageTocEntry::default(),
+            runtime_extra_segments: Default::default(),
         }
     }
 }
 impl ImageManifest {
     /// Returns the `Range<u32>` containing the vendor public keys
     pub fn vendor_pub_keys_range() -> Range<u32> {
-        let offset = offset_of!(ImageManifest, preamble) as u32;
-        let span = span_of!(ImagePreamble, vendor_pub_keys);
-        span.start as u32 + offset..span.end as u32 + offset
+        ImageManifestLayout::v1().vendor_pub_keys_range()
+    }
+
+    /// Returns `Range<u32>` containing the vendor intermediate key table
+    pub fn vendor_intermediate_keys_range() -> Range<u32> {
+        ImageManifestLayout::v1().vendor_intermediate_keys_range()
     }
 
     /// Returns `Range<u32>` containing the owner public key
     pub fn owner_pub_key_range() -> Range<u32> {
-        let offset = offset_of!(ImageManifest, preamble) as u32;
-        let span = span_of!(ImagePreamble, owner_pub_keys);
-        span.start as u32 + offset..span.end as u32 + offset
+        ImageManifestLayout::v1().owner_pub_key_range()
     }
 
     /// Returns `Range<u32>` containing the header
     pub fn header_range() -> Range<u32> {
-        let span = span_of!(ImageManifest, header);
-        span.start as u32..span.end as u32
+        ImageManifestLayout::v1().header_range()
     }
 
-    /// Returns `Range<u32>` containing the table of contents
+    /// Returns `Range<u32>` containing the table of contents, including
+    /// unused runtime segment entries
     pub fn toc_range() -> Range<u32> {
-        let span = span_of!(ImageManifest, fmc..=runtime);
-        span.start as u32..span.end as u32
+        ImageManifestLayout::v1().toc_range()
+    }
+
+    /// Returns the table of contents as bytes, including unused runtime
+    /// segment entries
+    pub fn toc_bytes(&self) -> &[u8] {
+        &self.as_bytes()[span_of!(ImageManifest, fmc..=runtime_extra_segments)]
+    }
+
+    /// Returns the table of contents as mutable bytes, including unused
+    /// runtime segment entries
+    pub fn toc_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.as_mut_bytes()[span_of!(ImageManifest, fmc..=runtime_extra_segments)]
+    }
+
+    /// Parse the manifest at the start of `image` into `self`, and the vendor
+    /// intermediate key table into `vendor_intermediate_keys`, in either
+    /// format. A version 2 manifest is assembled from its sections into the
+    /// equivalent version 1 manifest.
+    ///
+    /// The manifest is not verified: a version 1 manifest is copied as is, and
+    /// a truncated one is zero-filled for the verifier to reject.
+    ///
+    /// # Arguments
+    ///
+    /// * `vendor_intermediate_keys` - Destination of the key table
+    /// * `image`                    - Image starting with the manifest
+    ///
+    /// # Returns
+    ///
+    /// * `ImageManifestLayout` - Location of the manifest structures in `image`
+    pub fn parse(
+        &mut self,
+        vendor_intermediate_keys: &mut ImageVendorIntermediateKeyTable,
+        image: &[u8],
+    ) -> CaliptraResult<ImageManifestLayout> {
+        if let Ok((manifest_v2::MANIFEST_MARKER_V2, _)) = u32::read_from_prefix(image) {
+            return manifest_v2::parse(self, vendor_intermediate_keys, image);
+        }
+
+        let layout = ImageManifestLayout::v1();
+        copy_zero_filled(self.as_mut_bytes(), image);
+        copy_zero_filled(
+            vendor_intermediate_keys.as_mut_bytes(),
+            image
+                .get(layout.vendor_intermediate_keys_offset as usize..)
+                .unwrap_or_default(),
+        );
+        Ok(layout)
+    }
+
+    /// Returns the number of runtime segments in the table of contents
+    pub fn runtime_segment_count(&self) -> usize {
+        let toc_len = self
+            .header
+            .toc_len
+            .clamp(MIN_TOC_ENTRY_COUNT, MAX_TOC_ENTRY_COUNT);
+        (toc_len - 1) as usize
+    }
+
+    /// Returns the runtime segment TOC entries, starting with `runtime`.
+    ///
+    /// The segments are stored back to back in the image and the `runtime`
+    /// digest covers all of them.
+    pub fn runtime_segments(&self) -> impl Iterator<Item = &ImageTocEntry> {
+        core::iter::once(&self.runtime)
+            .chain(self.runtime_extra_segments.iter())
+            .take(self.runtime_segment_count())
+    }
+
+    /// Returns the combined size of the runtime segments
+    pub fn runtime_size(&self) -> u32 {
+        self.runtime_segments()
+            .fold(0, |size, segment| size.saturating_add(segment.size))
+    }
+}
+
+/// Copy the start of `src` to `dest`, zero-filling what `src` is too short for
+fn copy_zero_filled(dest: &mut [u8], src: &[u8]) {
+    dest.fill(0);
+    let len = dest.len().min(src.len());
+    if let (Some(dest), Some(src)) = (dest.get_mut(..len), src.get(..len)) {
+        dest.copy_from_slice(src);
     }
 }
 
@@ -227,6 +453,37 @@ pub struct ImageVendorPubKeys {
     pub lms_pub_keys: [ImageLmsPublicKey; VENDOR_LMS_KEY_COUNT as usize],
 }
 
+/// Vendor intermediate ECC keys endorsed by a vendor ECC public key
+#[repr(C)]
+#[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy, Zeroize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ImageVendorIntermediateKeys {
+    /// Revocation bitmap. Bit N set revokes `ecc_pub_keys[N]`.
+    pub revocation: u32,
+
+    /// Security version of the table. It must not be lower than the FMC SVN
+    /// fuses, so that a table signed before a revocation cannot be replayed.
+    pub svn: u32,
+
+    pub ecc_pub_keys: [ImageEccPubKey; VENDOR_INTERMEDIATE_ECC_KEY_COUNT as usize],
+}
+
+/// Vendor intermediate key table. It follows the manifest in the image rather
+/// than being part of it, so that the manifest still fits the fixed-size slots
+/// it is persisted in.
+#[repr(C)]
+#[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy, Zeroize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ImageVendorIntermediateKeyTable {
+    /// Vendor Intermediate Public Keys. Only used when the header has
+    /// `VENDOR_INTERMEDIATE_KEY_FLAG` set.
+    pub keys: ImageVendorIntermediateKeys,
+
+    /// Signature of `keys` by the vendor ECC public key selected by
+    /// `ImagePreamble::vendor_ecc_pub_key_idx`
+    pub sig: ImageEccSignature,
+}
+
 #[repr(C)]
 #[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy, Zeroize)]
 pub struct ImageVendorPrivKeys {
@@ -284,7 +541,15 @@ pub struct ImagePreamble {
     /// Owner Signatures
     pub owner_sigs: ImageSignatures,
 
-    pub _rsvd: [u32; 2],
+    /// Digest of the owner public keys to rotate to. Zero if no rotation is
+    /// pending. Must match the owner-signed copy in the header.
+    pub next_owner_pub_keys_digest: ImageDigest,
+
+    /// Vendor Intermediate ECC Public Key Index, into the
+    /// `ImageVendorIntermediateKeyTable` following the manifest
+    pub vendor_intermediate_ecc_key_idx: u32,
+
+    pub _rsvd: u32,
 }
 
 #[repr(C)]
@@ -313,7 +578,19 @@ pub struct OwnerSignedData {
     /// Owner epoch, used to diversify stable SVN keys.
     pub epoch: [u8; 2],
 
-    reserved: [u8; 8],
+    /// Flags
+    /// Bit 0: Break-glass recovery image. See `OWNER_BREAK_GLASS_FLAG`.
+    /// Bit 1: Vendor key indices are not pinned. See
+    ///        `OWNER_UNPIN_VENDOR_KEY_FLAG`.
+    /// Bit 2: Interpret the cmd_pauser field. See `OWNER_CMD_PAUSER_FLAG`.
+    pub flags: u32,
+
+    /// The PAUSER allowed to invoke the runtime owner management commands.
+    pub cmd_pauser: u32,
+
+    /// Digest of the owner public keys to rotate to. Zero if no rotation is
+    /// pending.
+    pub next_owner_pub_keys_digest: ImageDigest,
 }
 
 /// Caliptra Image header
@@ -332,9 +609,12 @@ pub struct ImageHeader {
 
     /// Flags
     /// Bit 0: Interpret the pl0_pauser field. If not set, all PAUSERs are PL1.
+    /// Bit 1: The vendor ECC signature was produced by a vendor intermediate
+    ///        key. See `VENDOR_INTERMEDIATE_KEY_FLAG`.
     pub flags: u32,
 
-    /// TOC Entry Count
+    /// TOC Entry Count. One FMC entry followed by one or more runtime
+    /// segment entries.
     pub toc_len: u32,
 
     /// The PAUSER with PL0 privileges. The SoC integration must choose
@@ -351,6 +631,26 @@ pub struct ImageHeader {
     pub owner_data: OwnerSignedData,
 }
 
+impl OwnerSignedData {
+    pub fn is_break_glass(&self) -> bool {
+        self.flags & OWNER_BREAK_GLASS_FLAG != 0
+    }
+
+    pub fn is_vendor_key_unpinned(&self) -> bool {
+        self.flags & OWNER_UNPIN_VENDOR_KEY_FLAG != 0
+    }
+
+    /// The PAUSER the owner restricts the owner management commands to, if
+    /// any
+    pub fn cmd_pauser(&self) -> Option<u32> {
+        if self.flags & OWNER_CMD_PAUSER_FLAG != 0 {
+            Some(self.cmd_pauser)
+        } else {
+            None
+        }
+    }
+}
+
 /// Caliptra table contents entry id
 pub enum ImageTocEntryType {
     /// First mutable code
@@ -380,6 +680,24 @@ impl From<ImageTocEntryId> for u32 {
     }
 }
 
+/// Caliptra table contents entry compression
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageTocEntryCompression {
+    /// Image is stored as is
+    #[default]
+    None = 0,
+
+    /// Image is compressed with the LZSS format in [`lzss`]
+    Lzss = 1,
+}
+
+impl From<ImageTocEntryCompression> for u32 {
+    /// Converts to this type from the input type.
+    fn from(value: ImageTocEntryCompression) -> Self {
+        value as u32
+    }
+}
+
 /// Caliptra Table of contents entry
 #[repr(C)]
 #[derive(IntoBytes, Clone, Copy, FromBytes, Immutable, KnownLayout, Default, Debug, Zeroize)]
@@ -415,6 +733,12 @@ pub struct ImageTocEntry {
     /// Size
     pub size: u32,
 
+    /// Compression
+    pub compression: u32,
+
+    /// Size once decompressed. Only used if the image is compressed.
+    pub uncompressed_size: u32,
+
     /// Digest
     pub digest: ImageDigest,
 }
@@ -430,9 +754,27 @@ impl ImageTocEntry {
         self.size
     }
 
+    pub fn is_compressed(&self) -> bool {
+        self.compression != u32::from(ImageTocEntryCompression::None)
+    }
+
+    pub fn compression_valid(&self) -> bool {
+        self.compression == u32::from(ImageTocEntryCompression::None)
+            || self.compression == u32::from(ImageTocEntryCompression::Lzss)
+    }
+
+    /// Returns the size of the image once loaded
+    pub fn load_size(&self) -> u32 {
+        if self.is_compressed() {
+            self.uncompressed_size
+        } else {
+            self.size
+        }
+    }
+
     pub fn overlaps(&self, other: &ImageTocEntry) -> bool {
-        self.load_addr < (other.load_addr + other.image_size())
-            && (self.load_addr + self.image_size()) > other.load_addr
+        self.load_addr < (other.load_addr + other.load_size())
+            && (self.load_addr + self.load_size()) > other.load_addr
     }
 }
 
@@ -458,6 +800,176 @@ mod tests {
         assert_eq!(std::mem::size_of::<ImageManifest>() % 4, 0);
     }
 
+    fn test_manifest() -> ImageManifest {
+        let mut manifest = ImageManifest {
+            marker: MANIFEST_MARKER,
+            ..Default::default()
+        };
+        manifest.preamble.vendor_ecc_pub_key_idx = 1;
+        manifest.preamble.owner_pub_keys.ecc_pub_key.x[0] = 0xaaaa_aaaa;
+        manifest.header.toc_len = 2;
+        manifest.fmc.offset = 0x1000;
+        manifest.runtime_extra_segments[0].size = 0x200;
+        manifest
+    }
+
+    fn test_vendor_intermediate_keys() -> ImageVendorIntermediateKeyTable {
+        let mut keys = ImageVendorIntermediateKeyTable::default();
+        keys.keys.svn = 3;
+        keys.keys.ecc_pub_keys[2].y[0] = 0x5555_5555;
+        keys.sig.r[0] = 0xbbbb_bbbb;
+        keys
+    }
+
+    fn test_image_v1() -> Vec<u8> {
+        let mut image = test_manifest().as_bytes().to_vec();
+        image.extend_from_slice(test_vendor_intermediate_keys().as_bytes());
+        image
+    }
+
+    #[test]
+    fn test_manifest_parse_v1() {
+        let image = test_image_v1();
+        assert_eq!(image.len(), ImageManifestFormat::V1.size());
+
+        let mut parsed = ImageManifest::default();
+        let mut keys = ImageVendorIntermediateKeyTable::default();
+        let layout = parsed.parse(&mut keys, &image).unwrap();
+        assert_eq!(layout, ImageManifestLayout::v1());
+        assert_eq!(parsed.as_bytes(), test_manifest().as_bytes());
+        assert_eq!(keys.as_bytes(), test_vendor_intermediate_keys().as_bytes());
+
+        // A truncated key table is zero-filled
+        let len = ImageManifestFormat::V1.size() - 4;
+        parsed.parse(&mut keys, &image[..len]).unwrap();
+        assert_eq!(parsed.as_bytes(), test_manifest().as_bytes());
+        assert_eq!(keys.sig.s, [0; SHA384_DIGEST_WORD_SIZE]);
+    }
+
+    #[test]
+    fn test_manifest_parse_v2() {
+        let manifest = test_manifest();
+        let image = manifest.to_v2_bytes(&test_vendor_intermediate_keys());
+        assert_eq!(image.len(), ImageManifestFormat::V2.size());
+
+        let mut parsed = ImageManifest::default();
+        let mut keys = ImageVendorIntermediateKeyTable::default();
+        let layout = parsed.parse(&mut keys, &image).unwrap();
+        assert_eq!(layout.size as usize, image.len());
+        assert_eq!(parsed.as_bytes(), manifest.as_bytes());
+        assert_eq!(keys.as_bytes(), test_vendor_intermediate_keys().as_bytes());
+
+        // The ranges of the layout hold the same bytes as in version 1
+        let v1 = test_image_v1();
+        let v1_ranges = [
+            ImageManifest::vendor_pub_keys_range(),
+            ImageManifest::vendor_intermediate_keys_range(),
+            ImageManifest::owner_pub_key_range(),
+            ImageManifest::header_range(),
+            ImageManifest::toc_range(),
+        ];
+        let v2_ranges = [
+            layout.vendor_pub_keys_range(),
+            layout.vendor_intermediate_keys_range(),
+            layout.owner_pub_key_range(),
+            layout.header_range(),
+            layout.toc_range(),
+        ];
+        for (v1_range, v2_range) in v1_ranges.into_iter().zip(v2_ranges) {
+            assert_eq!(
+                &v1[v1_range.start as usize..v1_range.end as usize],
+                &image[v2_range.start as usize..v2_range.end as usize]
+            );
+        }
+    }
+
+    #[test]
+    fn test_manifest_parse_v2_unknown_section() {
+        use manifest_v2::{ImageManifestHeaderV2, ImageManifestSection};
+
+        let manifest = test_manifest();
+        let v2 = manifest.to_v2_bytes(&test_vendor_intermediate_keys());
+        let (hdr, rest) = ImageManifestHeaderV2::read_from_prefix(&v2[..]).unwrap();
+        let dir_len = 4 * size_of::<ImageManifestSection>();
+        let (mut sections, sections_data) =
+            <[ImageManifestSection; 4]>::read_from_prefix(rest).unwrap();
+
+        // Append a section from a later format, with its directory entry
+        let entry_size = size_of::<ImageManifestSection>() as u32;
+        for section in sections.iter_mut() {
+            section.offset += entry_size;
+        }
+        let extra_data = [0xa5u8; 8];
+        let extra = ImageManifestSection {
+            id: 0x10,
+            offset: v2.len() as u32 + entry_size,
+            size: extra_data.len() as u32,
+        };
+        let hdr = ImageManifestHeaderV2 {
+            size: hdr.size + entry_size + extra_data.len() as u32,
+            section_count: 5,
+            ..hdr
+        };
+        let mut image = hdr.as_bytes().to_vec();
+        image.extend_from_slice(sections.as_bytes());
+        image.extend_from_slice(extra.as_bytes());
+        image.extend_from_slice(sections_data);
+        image.extend_from_slice(&extra_data);
+        assert_eq!(rest.len() - dir_len, sections_data.len());
+
+        let mut parsed = ImageManifest::default();
+        let mut keys = ImageVendorIntermediateKeyTable::default();
+        let layout = parsed.parse(&mut keys, &image).unwrap();
+        assert_eq!(layout.size as usize, image.len());
+        assert_eq!(layout.header_offset, sections[1].offset);
+        assert_eq!(parsed.as_bytes(), manifest.as_bytes());
+        assert_eq!(keys.as_bytes(), test_vendor_intermediate_keys().as_bytes());
+    }
+
+    #[test]
+    fn test_manifest_parse_v2_invalid() {
+        use manifest_v2::ImageManifestHeaderV2;
+
+        let manifest = test_manifest();
+        let v2 = manifest.to_v2_bytes(&test_vendor_intermediate_keys());
+        let (hdr, rest) = ImageManifestHeaderV2::read_from_prefix(&v2[..]).unwrap();
+        let mut parsed = ImageManifest::default();
+        let mut keys = ImageVendorIntermediateKeyTable::default();
+
+        let mut image = ImageManifestHeaderV2 {
+            format_version: 3,
+            ..hdr
+        }
+        .as_bytes()
+        .to_vec();
+        image.extend_from_slice(rest);
+        assert_eq!(
+            parsed.parse(&mut keys, &image),
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_FORMAT_VERSION_UNSUPPORTED)
+        );
+
+        // The TOC and the key table sections are missing
+        for section_count in [2, 3] {
+            let mut image = ImageManifestHeaderV2 {
+                section_count,
+                ..hdr
+            }
+            .as_bytes()
+            .to_vec();
+            image.extend_from_slice(rest);
+            assert_eq!(
+                parsed.parse(&mut keys, &image),
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)
+            );
+        }
+
+        // The manifest is larger than the image
+        assert_eq!(
+            parsed.parse(&mut keys, &v2[..v2.len() - 4]),
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SIZE_MISMATCH)
+        );
+    }
+
     #[test]
     fn test_image_overlap() {
         let mut image1 = ImageTocEntry::default();
@@ -546,5 +1058,14 @@ mod tests {
         image2.load_addr = 500;
         image2.size = 100;
         assert!(!image1.overlaps(&image2));
+
+        // Case 13: compressed images overlap by their uncompressed size
+        image1.load_addr = 400;
+        image1.size = 50;
+        image1.compression = ImageTocEntryCompression::Lzss.into();
+        image1.uncompressed_size = 101;
+        image2.load_addr = 500;
+        image2.size = 100;
+        assert!(image1.overlaps(&image2));
     }
 }
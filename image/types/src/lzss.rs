@@ -0,0 +1,228 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+   lzss.rs
+
+Abstract:
+
+    File contains the LZSS format used for compressed firmware images.
+
+    The stream is a sequence of groups, each made of a flag byte followed by
+    up to eight items. Bit N of the flag byte (LSB first) describes item N:
+
+    * 1 - literal byte
+    * 0 - two byte back-reference into the decompressed output:
+          byte 0: (distance - 1) bits 7:0
+          byte 1: (distance - 1) bits 11:8 in bits 7:4, (length - 3) in bits 3:0
+
+    The stream carries no length; decompression stops once the destination is
+    full, so trailing padding is ignored.
+
+--*/
+
+use caliptra_error::{CaliptraError, CaliptraResult};
+
+/// Maximum back-reference distance
+pub const LZSS_WINDOW_SIZE: usize = 4096;
+
+/// Minimum back-reference length
+pub const LZSS_MIN_MATCH: usize = 3;
+
+/// Maximum back-reference length
+pub const LZSS_MAX_MATCH: usize = LZSS_MIN_MATCH + 0xf;
+
+/// Decompress `src` into `dst`, filling all of `dst`.
+///
+/// # Arguments
+///
+/// * `src` - Compressed stream
+/// * `dst` - Destination buffer, sized to the decompressed length
+///
+/// # Returns
+///
+/// * `FW_PROC_IMAGE_DECOMPRESSION_FAILURE` - The stream ended early or
+///   references data outside the decompressed output
+pub fn decompress(src: &[u8], dst: &mut [u8]) -> CaliptraResult<()> {
+    let err = CaliptraError::FW_PROC_IMAGE_DECOMPRESSION_FAILURE;
+    let mut src = src.iter().copied();
+    let mut out = 0;
+
+    while out < dst.len() {
+        let flags = src.next().ok_or(err)?;
+        for bit in 0..8 {
+            if out == dst.len() {
+                break;
+            }
+            if flags & (1 << bit) != 0 {
+                dst[out] = src.next().ok_or(err)?;
+                out += 1;
+                continue;
+            }
+
+            let lo = src.next().ok_or(err)? as usize;
+            let hi = src.next().ok_or(err)? as usize;
+            let distance = (lo | ((hi >> 4) << 8)) + 1;
+            let len = (hi & 0xf) + LZSS_MIN_MATCH;
+            if distance > out || len > dst.len() - out {
+                return Err(err);
+            }
+
+            // Byte by byte, as the match may overlap the bytes it produces
+            for _ in 0..len {
+                dst[out] = dst[out - distance];
+                out += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compress `src`.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - Compressed stream
+#[cfg(feature = "std")]
+pub fn compress(src: &[u8]) -> Vec<u8> {
+    use std::collections::HashMap;
+
+    // Bounds the search time for highly repetitive content
+    const MAX_CANDIDATES: usize = 64;
+
+    let mut out = vec![];
+    let mut positions: HashMap<[u8; LZSS_MIN_MATCH], Vec<usize>> = HashMap::new();
+    let mut flags_idx = 0;
+    let mut bit = 8;
+    let mut pos = 0;
+
+    while pos < src.len() {
+        if bit == 8 {
+            flags_idx = out.len();
+            out.push(0);
+            bit = 0;
+        }
+
+        let mut best_len = 0;
+        let mut best_distance = 0;
+        if let Some(key) = src.get(pos..pos + LZSS_MIN_MATCH) {
+            let max_len = LZSS_MAX_MATCH.min(src.len() - pos);
+            let candidates = positions.get(key).into_iter().flatten().rev();
+            for &candidate in candidates.take(MAX_CANDIDATES) {
+                let distance = pos - candidate;
+                if distance > LZSS_WINDOW_SIZE {
+                    break;
+                }
+                let len = (0..max_len)
+                    .take_while(|&i| src[candidate + i] == src[pos + i])
+                    .count();
+                if len > best_len {
+                    best_len = len;
+                    best_distance = distance;
+                }
+                if len == max_len {
+                    break;
+                }
+            }
+        }
+
+        let consumed = if best_len >= LZSS_MIN_MATCH {
+            let code = best_distance - 1;
+            out.push(code as u8);
+            out.push((((code >> 8) << 4) | (best_len - LZSS_MIN_MATCH)) as u8);
+            best_len
+        } else {
+            out[flags_idx] |= 1 << bit;
+            out.push(src[pos]);
+            1
+        };
+        bit += 1;
+
+        for p in pos..pos + consumed {
+            if let Some(key) = src.get(p..p + LZSS_MIN_MATCH) {
+                positions
+                    .entry(key.try_into().unwrap())
+                    .or_default()
+                    .push(p);
+            }
+        }
+        pos += consumed;
+    }
+    out
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &[u8]) {
+        let compressed = compress(data);
+        let mut decompressed = vec![0xa5; data.len()];
+        decompress(&compressed, &mut decompressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(&[]);
+        round_trip(b"a");
+        round_trip(b"abcabcabcabcabcabcabcabcabcabc");
+        round_trip(&[0; 10000]);
+
+        let mut state = 0x1234_5678u32;
+        let noise: Vec<u8> = (0..10000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        round_trip(&noise);
+        round_trip(&[noise.as_slice(), &noise[..5000], &noise[2000..]].concat());
+    }
+
+    #[test]
+    fn test_compresses() {
+        assert!(compress(&[0; 10000]).len() < 10000 / 8);
+    }
+
+    #[test]
+    fn test_padding_ignored() {
+        let mut compressed = compress(b"hello hello hello");
+        compressed.extend_from_slice(&[0, 0, 0]);
+        let mut decompressed = [0; 17];
+        decompress(&compressed, &mut decompressed).unwrap();
+        assert_eq!(&decompressed, b"hello hello hello");
+    }
+
+    #[test]
+    fn test_truncated() {
+        let compressed = compress(b"hello hello hello");
+        let mut decompressed = [0; 17];
+        assert_eq!(
+            decompress(&compressed[..compressed.len() - 1], &mut decompressed),
+            Err(CaliptraError::FW_PROC_IMAGE_DECOMPRESSION_FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_reference_before_start() {
+        let mut decompressed = [0; 4];
+        assert_eq!(
+            decompress(&[0x01, b'a', 0x01, 0x00], &mut decompressed),
+            Err(CaliptraError::FW_PROC_IMAGE_DECOMPRESSION_FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_reference_past_end() {
+        let mut decompressed = [0; 4];
+        assert_eq!(
+            decompress(&[0x01, b'a', 0x00, 0x0f], &mut decompressed),
+            Err(CaliptraError::FW_PROC_IMAGE_DECOMPRESSION_FAILURE)
+        );
+    }
+}
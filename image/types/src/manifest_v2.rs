@@ -0,0 +1,219 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+   manifest_v2.rs
+
+Abstract:
+
+    File contains the version 2 firmware manifest format.
+
+    A version 2 manifest starts with a self-describing header and a directory
+    of its sections. Each section holds one of the structures of the version 1
+    manifest. Parsers skip the sections they do not know, so that new sections
+    can be added without changing the layout of the existing ones.
+
+--*/
+
+use crate::{
+    ImageHeader, ImageManifest, ImageManifestLayout, ImagePreamble,
+    ImageVendorIntermediateKeyTable, IMAGE_TOC_BYTE_SIZE, MANIFEST_MARKER,
+};
+use caliptra_error::{CaliptraError, CaliptraResult};
+use core::mem::size_of;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+/// Marker of a version 2 manifest ("CMN2")
+pub const MANIFEST_MARKER_V2: u32 = 0x324E4D43;
+pub const MANIFEST_FORMAT_VERSION_2: u32 = 2;
+pub const MANIFEST_V2_MAX_SECTION_COUNT: u32 = 8;
+
+/// Section holding the `ImagePreamble`
+pub const MANIFEST_SECTION_PREAMBLE: u32 = 1;
+
+/// Section holding the `ImageHeader`
+pub const MANIFEST_SECTION_HEADER: u32 = 2;
+
+/// Section holding the table of contents, including unused runtime segment
+/// entries
+pub const MANIFEST_SECTION_TOC: u32 = 3;
+
+/// Section holding the `ImageVendorIntermediateKeyTable`
+pub const MANIFEST_SECTION_VENDOR_INTERMEDIATE_KEYS: u32 = 4;
+
+const REQUIRED_SECTIONS: u32 = (1 << MANIFEST_SECTION_PREAMBLE)
+    | (1 << MANIFEST_SECTION_HEADER)
+    | (1 << MANIFEST_SECTION_TOC)
+    | (1 << MANIFEST_SECTION_VENDOR_INTERMEDIATE_KEYS);
+
+const SECTION_COUNT: usize = 4;
+
+/// Size of a version 2 manifest holding the sections known to this crate
+pub const MANIFEST_V2_SIZE: usize = size_of::<ImageManifestHeaderV2>()
+    + SECTION_COUNT * size_of::<ImageManifestSection>()
+    + size_of::<ImagePreamble>()
+    + size_of::<ImageHeader>()
+    + IMAGE_TOC_BYTE_SIZE
+    + size_of::<ImageVendorIntermediateKeyTable>();
+
+/// Header of a version 2 manifest. It is followed by `section_count` section
+/// directory entries.
+#[repr(C)]
+#[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy)]
+pub struct ImageManifestHeaderV2 {
+    /// Marker, `MANIFEST_MARKER_V2`
+    pub marker: u32,
+
+    /// Size of the manifest, including this header and the sections
+    pub size: u32,
+
+    /// Format version, `MANIFEST_FORMAT_VERSION_2`
+    pub format_version: u32,
+
+    /// Number of section directory entries
+    pub section_count: u32,
+}
+
+/// Section directory entry of a version 2 manifest
+#[repr(C)]
+#[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy)]
+pub struct ImageManifestSection {
+    /// Section ID, one of `MANIFEST_SECTION_*`
+    pub id: u32,
+
+    /// Offset of the section from the start of the manifest
+    pub offset: u32,
+
+    /// Size of the section in bytes
+    pub size: u32,
+}
+
+/// Assemble the version 1 manifest and the vendor intermediate key table from
+/// the sections of the version 2 manifest at the start of `image`
+pub(crate) fn parse(
+    manifest: &mut ImageManifest,
+    vendor_intermediate_keys: &mut ImageVendorIntermediateKeyTable,
+    image: &[u8],
+) -> CaliptraResult<ImageManifestLayout> {
+    let (hdr, _) = ImageManifestHeaderV2::read_from_prefix(image)
+        .map_err(|_| CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SIZE_MISMATCH)?;
+    if hdr.format_version != MANIFEST_FORMAT_VERSION_2 {
+        Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_FORMAT_VERSION_UNSUPPORTED)?;
+    }
+    if hdr.size % 4 != 0 {
+        Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SIZE_MISMATCH)?;
+    }
+    let image = image
+        .get(..hdr.size as usize)
+        .ok_or(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SIZE_MISMATCH)?;
+
+    if hdr.section_count > MANIFEST_V2_MAX_SECTION_COUNT {
+        Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+    }
+    let dir_start = size_of::<ImageManifestHeaderV2>();
+    let dir_end = dir_start + hdr.section_count as usize * size_of::<ImageManifestSection>();
+    let dir = image
+        .get(dir_start..dir_end)
+        .ok_or(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+
+    manifest.as_mut_bytes().fill(0);
+    vendor_intermediate_keys.as_mut_bytes().fill(0);
+    let mut layout = ImageManifestLayout {
+        size: hdr.size,
+        preamble_offset: 0,
+        header_offset: 0,
+        toc_offset: 0,
+        vendor_intermediate_keys_offset: 0,
+    };
+    let mut found = 0u32;
+    for entry in dir.chunks_exact(size_of::<ImageManifestSection>()) {
+        let section = ImageManifestSection::read_from_bytes(entry)
+            .map_err(|_| CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+        let (dest, offset) = match section.id {
+            MANIFEST_SECTION_PREAMBLE => (
+                manifest.preamble.as_mut_bytes(),
+                &mut layout.preamble_offset,
+            ),
+            MANIFEST_SECTION_HEADER => (manifest.header.as_mut_bytes(), &mut layout.header_offset),
+            MANIFEST_SECTION_TOC => (manifest.toc_bytes_mut(), &mut layout.toc_offset),
+            MANIFEST_SECTION_VENDOR_INTERMEDIATE_KEYS => (
+                vendor_intermediate_keys.as_mut_bytes(),
+                &mut layout.vendor_intermediate_keys_offset,
+            ),
+            // Sections added by later formats are skipped
+            _ => continue,
+        };
+
+        let bit = 1 << section.id;
+        if found & bit != 0
+            || section.size as usize != dest.len()
+            || section.offset % 4 != 0
+            || (section.offset as usize) < dir_end
+        {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+        }
+        found |= bit;
+
+        let start = section.offset as usize;
+        let end = start
+            .checked_add(dest.len())
+            .ok_or(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+        let src = image
+            .get(start..end)
+            .ok_or(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+        dest.copy_from_slice(src);
+        *offset = section.offset;
+    }
+    if found != REQUIRED_SECTIONS {
+        Err(CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_SECTION_INVALID)?;
+    }
+
+    manifest.marker = MANIFEST_MARKER;
+    manifest.size = size_of::<ImageManifest>() as u32;
+    Ok(layout)
+}
+
+#[cfg(feature = "std")]
+impl ImageManifest {
+    /// Serialize the manifest and the vendor intermediate key table in the
+    /// version 2 format
+    pub fn to_v2_bytes(
+        &self,
+        vendor_intermediate_keys: &ImageVendorIntermediateKeyTable,
+    ) -> Vec<u8> {
+        let sections: [(u32, &[u8]); SECTION_COUNT] = [
+            (MANIFEST_SECTION_PREAMBLE, self.preamble.as_bytes()),
+            (MANIFEST_SECTION_HEADER, self.header.as_bytes()),
+            (MANIFEST_SECTION_TOC, self.toc_bytes()),
+            (
+                MANIFEST_SECTION_VENDOR_INTERMEDIATE_KEYS,
+                vendor_intermediate_keys.as_bytes(),
+            ),
+        ];
+
+        let hdr = ImageManifestHeaderV2 {
+            marker: MANIFEST_MARKER_V2,
+            size: MANIFEST_V2_SIZE as u32,
+            format_version: MANIFEST_FORMAT_VERSION_2,
+            section_count: SECTION_COUNT as u32,
+        };
+        let mut result = hdr.as_bytes().to_vec();
+        let mut offset =
+            size_of::<ImageManifestHeaderV2>() + SECTION_COUNT * size_of::<ImageManifestSection>();
+        for (id, data) in sections {
+            let section = ImageManifestSection {
+                id,
+                offset: offset as u32,
+                size: data.len() as u32,
+            };
+            result.extend_from_slice(section.as_bytes());
+            offset += data.len();
+        }
+        for (_, data) in sections {
+            result.extend_from_slice(data);
+        }
+        result
+    }
+}
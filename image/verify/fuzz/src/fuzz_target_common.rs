@@ -117,6 +117,14 @@ impl ImageVerificationEnv for TestEnv {
         self.owner_pub_key_digest
     }
 
+    fn committed_owner_pub_key_digest_dv(&self) -> ImageDigest {
+        ImageDigest::default()
+    }
+
+    fn next_owner_pub_key_digest_dv(&self) -> ImageDigest {
+        ImageDigest::default()
+    }
+
     fn get_fmc_digest_dv(&self) -> ImageDigest {
         self.fmc_digest
     }
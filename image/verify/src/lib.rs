@@ -68,6 +68,10 @@ pub struct ImageVerificationLogInfo {
     /// Vendor LMS Public Key Revocation Fuse
     pub fuse_vendor_lms_pub_key_revocation: Option<u32>,
 
+    /// Vendor intermediate ECC public key index, if the image was signed by
+    /// an intermediate key
+    pub vendor_intermediate_ecc_key_idx: Option<u32>,
+
     /// First Mutable code's logging information
     pub fmc_log_info: ImageSvnLogInfo,
 
@@ -90,6 +94,12 @@ pub struct ImageVerificationInfo {
     /// Whether `owner_pub_keys_digest` was in fuses
     pub owner_pub_keys_digest_in_fuses: bool,
 
+    /// Digest of the owner public keys the image authorizes rotating to
+    pub next_owner_pub_keys_digest: ImageDigest,
+
+    /// Whether the image is an owner-signed break-glass recovery image
+    pub break_glass: bool,
+
     /// First mutable code
     pub fmc: ImageVerificationExeInfo,
 
@@ -105,6 +115,21 @@ pub trait ImageVerificationEnv {
     /// Calculate SHA-384 Digest
     fn sha384_digest(&mut self, offset: u32, len: u32) -> CaliptraResult<ImageDigest>;
 
+    /// Calculate SHA-384 Digest of an executable stored in `segments`
+    ///
+    /// Environments that can load the image while hashing it copy each
+    /// segment to its load address in the same pass. The load addresses are
+    /// validated before this is called. By default the image is only hashed
+    /// and must be loaded after verification.
+    fn sha384_digest_and_load<'a>(
+        &mut self,
+        offset: u32,
+        len: u32,
+        _segments: impl Iterator<Item = &'a ImageTocEntry>,
+    ) -> CaliptraResult<ImageDigest> {
+        self.sha384_digest(offset, len)
+    }
+
     /// Perform ECC-384 Verification
     fn ecc384_verify(
         &mut self,
@@ -148,6 +173,12 @@ pub trait ImageVerificationEnv {
     // Get the owner key digest saved on cold boot in data vault
     fn owner_pub_key_digest_dv(&self) -> ImageDigest;
 
+    // Get the owner key digest committed by a key rotation in data vault
+    fn committed_owner_pub_key_digest_dv(&self) -> ImageDigest;
+
+    // Get the next owner key digest authorized by the running image in data vault
+    fn next_owner_pub_key_digest_dv(&self) -> ImageDigest;
+
     // Save the fmc digest in the data vault on cold boot
     fn get_fmc_digest_dv(&self) -> ImageDigest;
 
@@ -32,6 +32,7 @@ struct HeaderInfo<'a> {
     vendor_ecc_pub_key_idx: u32,
     vendor_lms_pub_key_idx: Option<u32>,
     vendor_ecc_pub_key_revocation: VendorPubKeyRevocation,
+    vendor_intermediate_ecc_key_idx: Option<u32>,
     vendor_ecc_info: (&'a ImageEccPubKey, &'a ImageEccSignature),
     vendor_lms_info: Option<(&'a ImageLmsPublicKey, &'a ImageLmsSignature)>,
     vendor_lms_pub_key_revocation: Option<u32>,
@@ -39,6 +40,7 @@ struct HeaderInfo<'a> {
     owner_lms_info: Option<(&'a ImageLmsPublicKey, &'a ImageLmsSignature)>,
     owner_pub_keys_digest: ImageDigest,
     owner_pub_keys_digest_in_fuses: bool,
+    next_owner_pub_keys_digest: &'a ImageDigest,
 }
 
 /// TOC Info
@@ -50,23 +52,58 @@ struct TocInfo<'a> {
 /// Image Info
 struct ImageInfo<'a> {
     fmc: &'a ImageTocEntry,
-    runtime: &'a ImageTocEntry,
 }
 
 /// Image Verifier
-pub struct ImageVerifier<Env: ImageVerificationEnv> {
+pub struct ImageVerifier<'a, Env: ImageVerificationEnv> {
     /// Verification Environment
     env: Env,
+
+    /// Location of the manifest structures in the image
+    manifest_layout: ImageManifestLayout,
+
+    /// Vendor intermediate key table of the image
+    vendor_intermediate_keys: Option<&'a ImageVendorIntermediateKeyTable>,
 }
 
-impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
+impl<'a, Env: ImageVerificationEnv> ImageVerifier<'a, Env> {
     /// Create a new instance `ImageVerifier`
     ///
     /// # Arguments
     ///
     /// * `env` - Environment
     pub fn new(env: Env) -> Self {
-        Self { env }
+        Self {
+            env,
+            manifest_layout: ImageManifestLayout::v1(),
+            vendor_intermediate_keys: None,
+        }
+    }
+
+    /// Set the vendor intermediate key table of the image, as parsed by
+    /// `ImageManifest::parse`. Without it, images signed by a vendor
+    /// intermediate key are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Vendor intermediate key table
+    pub fn with_vendor_intermediate_keys(
+        mut self,
+        keys: &'a ImageVendorIntermediateKeyTable,
+    ) -> Self {
+        self.vendor_intermediate_keys = Some(keys);
+        self
+    }
+
+    /// Set the location of the manifest structures in the image, as returned
+    /// by `ImageManifest::parse`. Defaults to the version 1 layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` - Manifest layout
+    pub fn with_manifest_layout(mut self, layout: ImageManifestLayout) -> Self {
+        self.manifest_layout = layout;
+        self
     }
 
     /// Verify Caliptra image
@@ -100,7 +137,12 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         // Verify the preamble
         let preamble = &manifest.preamble;
-        let header_info = self.verify_preamble(preamble, reason);
+        let header_info = self.verify_preamble(
+            preamble,
+            manifest.header.flags,
+            manifest.header.owner_data.flags,
+            reason,
+        );
         let header_info = okref(&header_info)?;
 
         // Verify Header
@@ -108,21 +150,43 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         let toc_info = self.verify_header(header, header_info);
         let toc_info = okref(&toc_info)?;
 
+        // A break-glass image bypasses the SVN policy, so the owner key that
+        // signed the flag must be locked in the fuses. ROM only keeps its keys
+        // out of the device chain when it derives the FMC alias CDI, so it
+        // cannot be booted by an update reset.
+        let break_glass = header.owner_data.is_break_glass();
+        if cfi_launder(break_glass) {
+            if !header_info.owner_pub_keys_digest_in_fuses {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_BREAK_GLASS_OWNER_PUB_KEY_NOT_IN_FUSES)?;
+            } else {
+                cfi_assert!(header_info.owner_pub_keys_digest_in_fuses);
+            }
+            if cfi_launder(reason) == ResetReason::UpdateReset {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_BREAK_GLASS_UPDATE_RESET)?;
+            } else {
+                cfi_assert_ne(reason, ResetReason::UpdateReset);
+            }
+        } else {
+            cfi_assert!(!break_glass);
+        }
+
         // Verify TOC
         let image_info = self.verify_toc(manifest, toc_info, img_bundle_sz);
         let image_info = okref(&image_info)?;
 
         // Verify FMC
-        let (fmc_info, fmc_log_info) = self.verify_fmc(image_info.fmc, reason)?;
+        let (fmc_info, fmc_log_info) = self.verify_fmc(image_info.fmc, reason, break_glass)?;
 
         // Verify Runtime
-        let (runtime_info, rt_log_info) = self.verify_runtime(image_info.runtime)?;
+        let (runtime_info, rt_log_info) = self.verify_runtime(manifest, break_glass)?;
 
         let info = ImageVerificationInfo {
             vendor_ecc_pub_key_idx: header_info.vendor_ecc_pub_key_idx,
             vendor_lms_pub_key_idx: header_info.vendor_lms_pub_key_idx,
             owner_pub_keys_digest: header_info.owner_pub_keys_digest,
             owner_pub_keys_digest_in_fuses: header_info.owner_pub_keys_digest_in_fuses,
+            next_owner_pub_keys_digest: *header_info.next_owner_pub_keys_digest,
+            break_glass,
             fmc: fmc_info,
             runtime: runtime_info,
             log_info: ImageVerificationLogInfo {
@@ -132,6 +196,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 rt_log_info,
                 fuse_vendor_lms_pub_key_revocation: header_info.vendor_lms_pub_key_revocation,
                 vendor_lms_pub_key_idx: header_info.vendor_lms_pub_key_idx,
+                vendor_intermediate_ecc_key_idx: header_info.vendor_intermediate_ecc_key_idx,
             },
         };
 
@@ -140,11 +205,16 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
     /// Verify Preamble
     #[cfg_attr(all(not(test), not(feature = "no-cfi")), cfi_impl_fn)]
-    fn verify_preamble<'a>(
+    fn verify_preamble<'b>(
         &mut self,
-        preamble: &'a ImagePreamble,
+        preamble: &'b ImagePreamble,
+        header_flags: u32,
+        owner_flags: u32,
         reason: ResetReason,
-    ) -> CaliptraResult<HeaderInfo<'a>> {
+    ) -> CaliptraResult<HeaderInfo<'b>>
+    where
+        'a: 'b,
+    {
         // Verify Vendor Public Key Digest
         self.verify_vendor_pk_digest()?;
 
@@ -152,13 +222,45 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         let (owner_pub_keys_digest, owner_pub_keys_digest_in_fuses) =
             self.verify_owner_pk_digest(reason)?;
 
+        // Unpinning the vendor key indices is owner policy, so the owner key
+        // that signed the flag must be locked in the fuses.
+        let unpin_vendor_key = owner_flags & OWNER_UNPIN_VENDOR_KEY_FLAG != 0;
+        if cfi_launder(unpin_vendor_key) {
+            if !owner_pub_keys_digest_in_fuses {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_UNPIN_VENDOR_KEY_OWNER_PUB_KEY_NOT_IN_FUSES)?;
+            } else {
+                cfi_assert!(owner_pub_keys_digest_in_fuses);
+            }
+        } else {
+            cfi_assert!(!unpin_vendor_key);
+        }
+
         // Verify ECC Vendor Key Index
         let (vendor_ecc_pub_key_idx, vendor_ecc_pub_key_revocation) =
-            self.verify_vendor_ecc_pk_idx(preamble, reason)?;
+            self.verify_vendor_ecc_pk_idx(preamble, reason, unpin_vendor_key)?;
+
+        let vendor_ecc_pub_key =
+            &preamble.vendor_pub_keys.ecc_pub_keys[vendor_ecc_pub_key_idx as usize];
+
+        // Verify the vendor intermediate keys if the header was signed by one.
+        // The header flags are covered by the vendor signature, so clearing or
+        // setting the flag only changes which key that signature must verify
+        // against.
+        let vendor_intermediate_key =
+            if cfi_launder(header_flags & VENDOR_INTERMEDIATE_KEY_FLAG) != 0 {
+                Some(self.verify_vendor_intermediate_keys(preamble, vendor_ecc_pub_key)?)
+            } else {
+                cfi_assert_eq(header_flags & VENDOR_INTERMEDIATE_KEY_FLAG, 0);
+                None
+            };
+        let vendor_intermediate_ecc_key_idx = vendor_intermediate_key.map(|(idx, _)| idx);
 
         // ECC Vendor Information
         let vendor_ecc_info = (
-            &preamble.vendor_pub_keys.ecc_pub_keys[vendor_ecc_pub_key_idx as usize],
+            match vendor_intermediate_key {
+                Some((_, key)) => key,
+                None => vendor_ecc_pub_key,
+            },
             &preamble.vendor_sigs.ecc_sig,
         );
 
@@ -169,7 +271,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         if cfi_launder(self.env.lms_verify_enabled()) {
             (vendor_lms_pub_key_idx, vendor_lms_pub_key_revocation) =
-                self.verify_vendor_lms_pk_idx(preamble, reason)?;
+                self.verify_vendor_lms_pk_idx(preamble, reason, unpin_vendor_key)?;
 
             if let Some(idx) = vendor_lms_pub_key_idx {
                 vendor_lms_info = Some((
@@ -205,8 +307,10 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             owner_lms_info,
             owner_pub_keys_digest,
             owner_pub_keys_digest_in_fuses,
+            next_owner_pub_keys_digest: &preamble.next_owner_pub_keys_digest,
             owner_ecc_info,
             vendor_ecc_pub_key_revocation,
+            vendor_intermediate_ecc_key_idx,
             vendor_lms_pub_key_revocation,
         };
 
@@ -218,6 +322,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         &mut self,
         preamble: &ImagePreamble,
         reason: ResetReason,
+        unpin_vendor_key: bool,
     ) -> CaliptraResult<(u32, VendorPubKeyRevocation)> {
         const SECOND_LAST_KEY_IDX: u32 = VENDOR_ECC_KEY_COUNT - 2;
         const LAST_KEY_IDX: u32 = SECOND_LAST_KEY_IDX + 1;
@@ -242,7 +347,9 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             _ => Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_ECC_PUB_KEY_INDEX_OUT_OF_BOUNDS)?,
         }
 
-        if cfi_launder(reason) == ResetReason::UpdateReset {
+        // The index used at cold boot stays pinned across update resets
+        // unless the owner has unpinned it.
+        if cfi_launder(reason) == ResetReason::UpdateReset && !cfi_launder(unpin_vendor_key) {
             let expected = self.env.vendor_ecc_pub_key_idx_dv();
             if cfi_launder(expected) != key_idx {
                 Err(
@@ -252,17 +359,96 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 cfi_assert_eq(self.env.vendor_ecc_pub_key_idx_dv(), key_idx);
             }
         } else {
-            cfi_assert_ne(reason, ResetReason::UpdateReset);
+            cfi_assert!(reason != ResetReason::UpdateReset || unpin_vendor_key);
         }
 
         Ok((key_idx, revocation))
     }
 
+    /// Verify the vendor intermediate key table is signed by the vendor ECC
+    /// public key and the selected intermediate key is not revoked
+    ///
+    /// # Returns
+    ///
+    /// * `(u32, &ImageEccPubKey)` - Index and value of the intermediate key
+    fn verify_vendor_intermediate_keys(
+        &mut self,
+        preamble: &ImagePreamble,
+        vendor_ecc_pub_key: &ImageEccPubKey,
+    ) -> CaliptraResult<(u32, &'a ImageEccPubKey)> {
+        // A missing table is rejected like an unsigned one
+        let table = self.vendor_intermediate_keys.ok_or(
+            CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID_ARG,
+        )?;
+        let keys = &table.keys;
+        let key_idx = preamble.vendor_intermediate_ecc_key_idx;
+
+        if cfi_launder(key_idx) >= VENDOR_INTERMEDIATE_ECC_KEY_COUNT {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_INDEX_OUT_OF_BOUNDS)?;
+        } else {
+            cfi_assert_le(key_idx, VENDOR_INTERMEDIATE_ECC_KEY_COUNT - 1);
+        }
+
+        if cfi_launder(keys.revocation) & (1 << key_idx) != 0 {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_REVOKED)?;
+        } else {
+            cfi_assert_eq(keys.revocation & (1 << key_idx), 0);
+        }
+
+        // The revocation bitmap is only as fresh as the table, so the table
+        // SVN is held to the FMC SVN fuses, which are advanced whenever a key
+        // is revoked. Older tables can then no longer be replayed.
+        if self.svn_check_required() {
+            if keys.svn > 32 {
+                Err(
+                    CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SVN_GREATER_THAN_MAX_SUPPORTED,
+                )?;
+            }
+            if cfi_launder(keys.svn) < self.env.fmc_fuse_svn() {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SVN_LESS_THAN_FUSE)?;
+            } else {
+                cfi_assert_ge(keys.svn, self.env.fmc_fuse_svn());
+            }
+        }
+
+        let sig = &table.sig;
+        if &sig.r == ZERO_DIGEST || &sig.s == ZERO_DIGEST {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID_ARG)?;
+        }
+
+        // The signature covers the revocation bitmap along with the keys.
+        let range = self.manifest_layout.vendor_intermediate_keys_range();
+        let digest = self
+            .env
+            .sha384_digest(range.start, range.len() as u32)
+            .map_err(|err| {
+                self.env.set_fw_extended_error(err.into());
+                CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_DIGEST_FAILURE
+            })?;
+
+        let verify_r = self
+            .env
+            .ecc384_verify(&digest, vendor_ecc_pub_key, sig)
+            .map_err(|err| {
+                self.env.set_fw_extended_error(err.into());
+                CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_VERIFY_FAILURE
+            })?;
+
+        if cfi_launder(verify_r) != caliptra_drivers::Array4xN(sig.r) {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID)?;
+        } else {
+            caliptra_cfi_lib::cfi_assert_eq_12_words(&verify_r.0, &sig.r);
+        }
+
+        Ok((key_idx, &keys.ecc_pub_keys[key_idx as usize]))
+    }
+
     /// Verify Vendor LMS Public Key Index
     fn verify_vendor_lms_pk_idx(
         &mut self,
         preamble: &ImagePreamble,
         reason: ResetReason,
+        unpin_vendor_key: bool,
     ) -> CaliptraResult<(Option<u32>, Option<u32>)> {
         const SECOND_LAST_KEY_IDX: u32 = VENDOR_LMS_KEY_COUNT - 2;
         const LAST_KEY_IDX: u32 = SECOND_LAST_KEY_IDX + 1;
@@ -286,7 +472,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             _ => Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_LMS_PUB_KEY_INDEX_OUT_OF_BOUNDS)?,
         }
 
-        if cfi_launder(reason) == ResetReason::UpdateReset {
+        if cfi_launder(reason) == ResetReason::UpdateReset && !cfi_launder(unpin_vendor_key) {
             let expected = self.env.vendor_lms_pub_key_idx_dv();
             if cfi_launder(expected) != key_idx {
                 Err(
@@ -296,7 +482,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 cfi_assert_eq(self.env.vendor_lms_pub_key_idx_dv(), key_idx);
             }
         } else {
-            cfi_assert_ne(reason, ResetReason::UpdateReset);
+            cfi_assert!(reason != ResetReason::UpdateReset || unpin_vendor_key);
         }
 
         Ok((Some(key_idx), Some(revocation)))
@@ -328,7 +514,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             cfi_assert_ne(expected, ZERO_DIGEST);
         }
 
-        let range = ImageManifest::vendor_pub_keys_range();
+        let range = self.manifest_layout.vendor_pub_keys_range();
 
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
@@ -361,7 +547,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         &mut self,
         reason: ResetReason,
     ) -> CaliptraResult<(ImageDigest, bool)> {
-        let range = ImageManifest::owner_pub_key_range();
+        let range = self.manifest_layout.owner_pub_key_range();
 
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
@@ -379,10 +565,25 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 CaliptraError::IMAGE_VERIFIER_ERR_OWNER_PUB_KEY_DIGEST_FAILURE
             })?;
 
+        // Once the running image authorizes a new owner key, images signed by
+        // that key are accepted on update reset in place of the fused key.
+        // After the rotation is committed only the new key is accepted.
+        let committed_digest = &self.env.committed_owner_pub_key_digest_dv();
+        let rotated = if cfi_launder(reason) == ResetReason::UpdateReset {
+            let next_digest = &self.env.next_owner_pub_key_digest_dv();
+            (committed_digest != ZERO_DIGEST && committed_digest == actual)
+                || (next_digest != ZERO_DIGEST && next_digest == actual)
+        } else {
+            cfi_assert_ne(reason, ResetReason::UpdateReset);
+            false
+        };
+
         let fuses_digest = &self.env.owner_pub_key_digest_fuses();
 
         if fuses_digest == ZERO_DIGEST {
             caliptra_cfi_lib::cfi_assert_eq_12_words(fuses_digest, ZERO_DIGEST);
+        } else if cfi_launder(rotated) {
+            cfi_assert!(rotated);
         } else if fuses_digest != actual {
             return Err(CaliptraError::IMAGE_VERIFIER_ERR_OWNER_PUB_KEY_DIGEST_MISMATCH);
         } else {
@@ -391,7 +592,9 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         if cfi_launder(reason) == ResetReason::UpdateReset {
             let cold_boot_digest = &self.env.owner_pub_key_digest_dv();
-            if cfi_launder(cold_boot_digest) != actual {
+            if cfi_launder(rotated) {
+                cfi_assert!(rotated);
+            } else if committed_digest != ZERO_DIGEST || cfi_launder(cold_boot_digest) != actual {
                 return Err(CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_OWNER_DIGEST_FAILURE);
             } else {
                 caliptra_cfi_lib::cfi_assert_eq_12_words(cold_boot_digest, actual);
@@ -400,7 +603,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             cfi_assert_ne(reason, ResetReason::UpdateReset);
         }
 
-        Ok((*actual, fuses_digest != ZERO_DIGEST))
+        Ok((*actual, fuses_digest != ZERO_DIGEST && !rotated))
     }
 
     /// Verify Header
@@ -411,7 +614,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         info: &HeaderInfo,
     ) -> CaliptraResult<TocInfo<'a>> {
         // Calculate the digest for the header
-        let range = ImageManifest::header_range();
+        let range = self.manifest_layout.header_range();
         let vendor_header_len = offset_of!(ImageHeader, owner_data);
 
         #[cfg(feature = "fips-test-hooks")]
@@ -477,6 +680,19 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             cfi_assert!(info.owner_lms_info.is_none());
         }
 
+        // Verify the next owner key digest in the preamble matches the one
+        // signed by the owner
+        if cfi_launder(&header.owner_data.next_owner_pub_keys_digest)
+            != info.next_owner_pub_keys_digest
+        {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_NEXT_OWNER_PUB_KEY_DIGEST_MISMATCH)?;
+        } else {
+            caliptra_cfi_lib::cfi_assert_eq_12_words(
+                &header.owner_data.next_owner_pub_keys_digest,
+                info.next_owner_pub_keys_digest,
+            );
+        }
+
         let verif_info = TocInfo {
             len: header.toc_len,
             digest: &header.toc_digest,
@@ -636,13 +852,18 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         verify_info: &TocInfo,
         img_bundle_sz: u32,
     ) -> CaliptraResult<ImageInfo<'a>> {
-        if cfi_launder(verify_info.len) != MAX_TOC_ENTRY_COUNT {
+        if cfi_launder(verify_info.len) < MIN_TOC_ENTRY_COUNT
+            || cfi_launder(verify_info.len) > MAX_TOC_ENTRY_COUNT
+        {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_TOC_ENTRY_COUNT_INVALID)?;
         } else {
-            cfi_assert_eq(verify_info.len, MAX_TOC_ENTRY_COUNT);
+            cfi_assert_ge(verify_info.len, MIN_TOC_ENTRY_COUNT);
+            cfi_assert_le(verify_info.len, MAX_TOC_ENTRY_COUNT);
         }
 
-        let range = ImageManifest::toc_range();
+        // Only the entries in use are covered by the digest
+        let range = self.manifest_layout.toc_range();
+        let toc_size = verify_info.len * core::mem::size_of::<ImageTocEntry>() as u32;
 
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
@@ -654,7 +875,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         let actual = self
             .env
-            .sha384_digest(range.start, range.len() as u32)
+            .sha384_digest(range.start, toc_size)
             .map_err(|err| {
                 self.env.set_fw_extended_error(err.into());
                 CaliptraError::IMAGE_VERIFIER_ERR_TOC_DIGEST_FAILURE
@@ -666,20 +887,37 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             caliptra_cfi_lib::cfi_assert_eq_12_words(verify_info.digest, &actual);
         }
 
+        // Verify the compression of each entry is supported.
+        if !manifest.fmc.compression_valid()
+            || manifest
+                .runtime_segments()
+                .any(|segment| !segment.compression_valid())
+        {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_TOC_ENTRY_COMPRESSION_INVALID)?;
+        }
+
         // Verify the FMC size is not zero.
-        if manifest.fmc.image_size() == 0 {
+        if manifest.fmc.image_size() == 0 || manifest.fmc.load_size() == 0 {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_SIZE_ZERO)?;
         }
 
-        // Verify the Runtime size is not zero.
-        if manifest.runtime.image_size() == 0 {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SIZE_ZERO)?;
+        // Verify the Runtime segment sizes are not zero and the segments are
+        // stored back to back, so the runtime digest covers one range.
+        let mut runtime_end = manifest.runtime.offset;
+        for segment in manifest.runtime_segments() {
+            if segment.image_size() == 0 || segment.load_size() == 0 {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SIZE_ZERO)?;
+            }
+            if segment.offset != runtime_end {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_OFFSET_INVALID)?;
+            }
+            runtime_end = segment.image_range()?.end;
         }
 
         // Image length does not exceed the Image Bundle size
-        let img_len: u64 = manifest.size as u64
+        let img_len: u64 = self.manifest_layout.size as u64
             + manifest.fmc.image_size() as u64
-            + manifest.runtime.image_size() as u64;
+            + (runtime_end - manifest.runtime.offset) as u64;
 
         if img_len > img_bundle_sz.into() {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_IMAGE_LEN_MORE_THAN_BUNDLE_SIZE)?;
@@ -687,7 +925,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         // Check if fmc and runtime sections overlap in the image.
         let fmc_range = manifest.fmc.image_range()?;
-        let runtime_range = manifest.runtime.image_range()?;
+        let runtime_range = manifest.runtime.offset..runtime_end;
         if fmc_range.start < runtime_range.end && fmc_range.end > runtime_range.start {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_RUNTIME_OVERLAP)?;
         }
@@ -700,28 +938,39 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         // Check if fmc and runtime images don't overlap on loading in the ICCM.
         let fmc_load_addr_start = manifest.fmc.load_addr;
         let (fmc_load_addr_end, overflow) =
-            fmc_load_addr_start.overflowing_add(manifest.fmc.image_size() - 1);
+            fmc_load_addr_start.overflowing_add(manifest.fmc.load_size() - 1);
         if overflow {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDRESS_IMAGE_SIZE_ARITHMETIC_OVERFLOW)?;
         }
 
-        let runtime_load_addr_start = manifest.runtime.load_addr;
-        let (runtime_load_addr_end, overflow) =
-            runtime_load_addr_start.overflowing_add(manifest.runtime.image_size() - 1);
-        if overflow {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDRESS_IMAGE_SIZE_ARITHMETIC_OVERFLOW)?;
+        for segment in manifest.runtime_segments() {
+            let runtime_load_addr_start = segment.load_addr;
+            let (runtime_load_addr_end, overflow) =
+                runtime_load_addr_start.overflowing_add(segment.load_size() - 1);
+            if overflow {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDRESS_IMAGE_SIZE_ARITHMETIC_OVERFLOW)?;
+            }
+
+            if fmc_load_addr_start <= runtime_load_addr_end
+                && fmc_load_addr_end >= runtime_load_addr_start
+            {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_RUNTIME_LOAD_ADDR_OVERLAP)?;
+            }
         }
 
-        if fmc_load_addr_start <= runtime_load_addr_end
-            && fmc_load_addr_end >= runtime_load_addr_start
-        {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_RUNTIME_LOAD_ADDR_OVERLAP)?;
+        // Check if the runtime segments don't overlap on loading in the ICCM.
+        // Sizes are non-zero and the end addresses don't overflow.
+        for (i, segment) in manifest.runtime_segments().enumerate() {
+            let end = segment.load_addr + (segment.load_size() - 1);
+            if manifest.runtime_segments().skip(i + 1).any(|other| {
+                segment.load_addr <= other.load_addr + (other.load_size() - 1)
+                    && other.load_addr <= end
+            }) {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_LOAD_ADDR_OVERLAP)?;
+            }
         }
 
-        let info = ImageInfo {
-            fmc: &manifest.fmc,
-            runtime: &manifest.runtime,
-        };
+        let info = ImageInfo { fmc: &manifest.fmc };
 
         Ok(info)
     }
@@ -750,9 +999,25 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         &mut self,
         verify_info: &ImageTocEntry,
         reason: ResetReason,
+        break_glass: bool,
     ) -> CaliptraResult<(ImageVerificationExeInfo, ImageSvnLogInfo)> {
         let range = verify_info.image_range()?;
 
+        // The environment may load the image while hashing it, so the load
+        // address is checked first.
+        // Overflow/underflow is checked in verify_toc
+        if !self.env.iccm_range().contains(&verify_info.load_addr)
+            || !self
+                .env
+                .iccm_range()
+                .contains(&(verify_info.load_addr + verify_info.load_size() - 1))
+        {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDR_INVALID)?;
+        }
+        if verify_info.load_addr % 4 != 0 {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDR_UNALIGNED)?;
+        }
+
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
             caliptra_drivers::FipsTestHook::update_hook_cmd_if_hook_set(
@@ -763,7 +1028,11 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         let actual = self
             .env
-            .sha384_digest(range.start, range.len() as u32)
+            .sha384_digest_and_load(
+                range.start,
+                range.len() as u32,
+                core::iter::once(verify_info),
+            )
             .map_err(|err| {
                 self.env.set_fw_extended_error(err.into());
                 CaliptraError::IMAGE_VERIFIER_ERR_FMC_DIGEST_FAILURE
@@ -775,19 +1044,6 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             caliptra_cfi_lib::cfi_assert_eq_12_words(&verify_info.digest, &actual);
         }
 
-        // Overflow/underflow is checked in verify_toc
-        if !self.env.iccm_range().contains(&verify_info.load_addr)
-            || !self
-                .env
-                .iccm_range()
-                .contains(&(verify_info.load_addr + verify_info.size - 1))
-        {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDR_INVALID)?;
-        }
-        if verify_info.load_addr % 4 != 0 {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDR_UNALIGNED)?;
-        }
-
         if !self.env.iccm_range().contains(&verify_info.entry_point) {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_ENTRY_POINT_INVALID)?;
         }
@@ -800,7 +1056,10 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_SVN_GREATER_THAN_MAX_SUPPORTED)?;
             }
 
-            if cfi_launder(verify_info.svn) < self.env.fmc_fuse_svn() {
+            // Break-glass images may roll back below the fuses
+            if cfi_launder(break_glass) {
+                cfi_assert!(break_glass);
+            } else if cfi_launder(verify_info.svn) < self.env.fmc_fuse_svn() {
                 Err(CaliptraError::IMAGE_VERIFIER_ERR_FMC_SVN_LESS_THAN_FUSE)?;
             } else {
                 cfi_assert_ge(verify_info.svn, self.env.fmc_fuse_svn());
@@ -842,9 +1101,30 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
     #[cfg_attr(all(not(test), not(feature = "no-cfi")), cfi_impl_fn)]
     fn verify_runtime(
         &mut self,
-        verify_info: &ImageTocEntry,
+        manifest: &ImageManifest,
+        break_glass: bool,
     ) -> CaliptraResult<(ImageVerificationExeInfo, ImageSvnLogInfo)> {
-        let range = verify_info.image_range()?;
+        let verify_info = &manifest.runtime;
+
+        // The digest covers all the runtime segments
+        let size = manifest.runtime_size();
+
+        // The environment may load the image while hashing it, so the load
+        // addresses are checked first.
+        // Overflow/underflow is checked in verify_toc
+        for segment in manifest.runtime_segments() {
+            if !self.env.iccm_range().contains(&segment.load_addr)
+                || !self
+                    .env
+                    .iccm_range()
+                    .contains(&(segment.load_addr + segment.load_size() - 1))
+            {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_INVALID)?;
+            }
+            if segment.load_addr % 4 != 0 {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_UNALIGNED)?;
+            }
+        }
 
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
@@ -856,7 +1136,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         let actual = self
             .env
-            .sha384_digest(range.start, range.len() as u32)
+            .sha384_digest_and_load(verify_info.offset, size, manifest.runtime_segments())
             .map_err(|err| {
                 self.env.set_fw_extended_error(err.into());
                 CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_DIGEST_FAILURE
@@ -867,19 +1147,6 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         } else {
             caliptra_cfi_lib::cfi_assert_eq_12_words(&verify_info.digest, &actual);
         }
-
-        // Overflow/underflow is checked in verify_toc
-        if !self.env.iccm_range().contains(&verify_info.load_addr)
-            || !self
-                .env
-                .iccm_range()
-                .contains(&(verify_info.load_addr + verify_info.size - 1))
-        {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_INVALID)?;
-        }
-        if verify_info.load_addr % 4 != 0 {
-            Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_UNALIGNED)?;
-        }
         if !self.env.iccm_range().contains(&verify_info.entry_point) {
             Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_ENTRY_POINT_INVALID)?;
         }
@@ -892,7 +1159,10 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SVN_GREATER_THAN_MAX_SUPPORTED)?;
             }
 
-            if cfi_launder(verify_info.svn) < self.env.runtime_fuse_svn() {
+            // Break-glass images may roll back below the fuses
+            if cfi_launder(break_glass) {
+                cfi_assert!(break_glass);
+            } else if cfi_launder(verify_info.svn) < self.env.runtime_fuse_svn() {
                 Err(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SVN_LESS_THAN_FUSE)?;
             } else {
                 cfi_assert_ge(verify_info.svn, self.env.runtime_fuse_svn());
@@ -910,7 +1180,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             svn: verify_info.svn,
             effective_fuse_svn,
             digest: verify_info.digest,
-            size: verify_info.size,
+            size,
         };
 
         let log_info: ImageSvnLogInfo = ImageSvnLogInfo {
@@ -978,7 +1248,7 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_vendor_ecc_pk_idx(&preamble, ResetReason::UpdateReset);
+        let result = verifier.verify_vendor_ecc_pk_idx(&preamble, ResetReason::UpdateReset, false);
         assert!(result.is_ok());
     }
 
@@ -995,13 +1265,86 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_vendor_ecc_pk_idx(&preamble, ResetReason::UpdateReset);
+        let result = verifier.verify_vendor_ecc_pk_idx(&preamble, ResetReason::UpdateReset, false);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_VENDOR_ECC_PUB_KEY_IDX_MISMATCH)
         );
     }
 
+    #[test]
+    fn test_vendor_ecc_pk_idx_unpinned_update_rst() {
+        let test_env = TestEnv {
+            verify_result: true,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+
+        let preamble = ImagePreamble {
+            vendor_ecc_pub_key_idx: 2,
+            ..Default::default()
+        };
+
+        let (key_idx, _) = verifier
+            .verify_vendor_ecc_pk_idx(&preamble, ResetReason::UpdateReset, true)
+            .unwrap();
+        assert_eq!(key_idx, 2);
+    }
+
+    #[test]
+    fn test_preamble_unpin_vendor_key_update_rst() {
+        let test_env = TestEnv {
+            lifecycle: Lifecycle::Production,
+            vendor_pub_key_digest: DUMMY_DATA,
+            owner_pub_key_digest: DUMMY_DATA,
+            digest: DUMMY_DATA,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+
+        let preamble = ImagePreamble {
+            vendor_ecc_pub_key_idx: 2,
+            ..Default::default()
+        };
+
+        let result = verifier.verify_preamble(&preamble, 0, 0, ResetReason::UpdateReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_VENDOR_ECC_PUB_KEY_IDX_MISMATCH)
+        );
+
+        let info = verifier
+            .verify_preamble(
+                &preamble,
+                0,
+                OWNER_UNPIN_VENDOR_KEY_FLAG,
+                ResetReason::UpdateReset,
+            )
+            .unwrap();
+        assert_eq!(info.vendor_ecc_pub_key_idx, 2);
+    }
+
+    #[test]
+    fn test_preamble_unpin_vendor_key_owner_pub_key_not_in_fuses() {
+        let test_env = TestEnv {
+            verify_result: true,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let preamble = ImagePreamble::default();
+
+        let result = verifier.verify_preamble(
+            &preamble,
+            0,
+            OWNER_UNPIN_VENDOR_KEY_FLAG,
+            ResetReason::ColdReset,
+        );
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_UNPIN_VENDOR_KEY_OWNER_PUB_KEY_NOT_IN_FUSES)
+        );
+    }
+
     #[test]
     fn test_owner_pk_digest_update_rst() {
         let test_env = TestEnv {
@@ -1015,10 +1358,80 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_preamble(&preamble, ResetReason::UpdateReset);
+        let result = verifier.verify_preamble(&preamble, 0, 0, ResetReason::UpdateReset);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_owner_pk_digest_rotation_update_rst() {
+        const NEXT_OWNER_DIGEST: ImageDigest = [0xabcd_ef01; 12];
+        let test_env = TestEnv {
+            lifecycle: Lifecycle::Production,
+            vendor_pub_key_digest: DUMMY_DATA,
+            owner_pub_key_digest: DUMMY_DATA,
+            next_owner_pub_key_digest: NEXT_OWNER_DIGEST,
+            digest: NEXT_OWNER_DIGEST,
+            ..Default::default()
+        };
+
+        let mut verifier = ImageVerifier::new(test_env);
+        let (digest, in_fuses) = verifier
+            .verify_owner_pk_digest(ResetReason::UpdateReset)
+            .unwrap();
+        assert_eq!(digest, NEXT_OWNER_DIGEST);
+        assert!(!in_fuses);
+
+        // The next owner key is not accepted on cold reset
+        let result = verifier.verify_owner_pk_digest(ResetReason::ColdReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_OWNER_PUB_KEY_DIGEST_MISMATCH)
+        );
+    }
+
+    #[test]
+    fn test_owner_pk_digest_committed_rotation_update_rst() {
+        const NEW_OWNER_DIGEST: ImageDigest = [0xabcd_ef01; 12];
+        let test_env = TestEnv {
+            lifecycle: Lifecycle::Production,
+            vendor_pub_key_digest: DUMMY_DATA,
+            owner_pub_key_digest: DUMMY_DATA,
+            committed_owner_pub_key_digest: NEW_OWNER_DIGEST,
+            digest: NEW_OWNER_DIGEST,
+            ..Default::default()
+        };
+
+        let mut verifier = ImageVerifier::new(test_env);
+        let result = verifier.verify_owner_pk_digest(ResetReason::UpdateReset);
+        assert!(result.is_ok());
+
+        // The previous owner key is no longer accepted
+        verifier.env.digest = DUMMY_DATA;
+        let result = verifier.verify_owner_pk_digest(ResetReason::UpdateReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_OWNER_DIGEST_FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_owner_pk_digest_no_rotation_update_rst() {
+        let test_env = TestEnv {
+            lifecycle: Lifecycle::Production,
+            vendor_pub_key_digest: DUMMY_DATA,
+            owner_pub_key_digest: DUMMY_DATA,
+            digest: [0xabcd_ef01; 12],
+            ..Default::default()
+        };
+
+        let mut verifier = ImageVerifier::new(test_env);
+        let result = verifier.verify_owner_pk_digest(ResetReason::UpdateReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_OWNER_PUB_KEY_DIGEST_MISMATCH)
+        );
+    }
+
     #[test]
     fn test_verify_fmc_update_rst() {
         let test_env = TestEnv {
@@ -1040,7 +1453,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_fmc(&verify_info, ResetReason::UpdateReset);
+        let result = verifier.verify_fmc(&verify_info, ResetReason::UpdateReset, false);
         assert!(result.is_ok());
     }
 
@@ -1063,7 +1476,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_fmc(&verify_info, ResetReason::UpdateReset);
+        let result = verifier.verify_fmc(&verify_info, ResetReason::UpdateReset, false);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_FMC_DIGEST_MISMATCH)
@@ -1084,7 +1497,7 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_preamble(&preamble, ResetReason::UpdateReset);
+        let result = verifier.verify_preamble(&preamble, 0, 0, ResetReason::UpdateReset);
         assert!(result.is_ok());
     }
 
@@ -1124,7 +1537,7 @@ mod tests {
             ..Default::default()
         };
         let mut verifier = ImageVerifier::new(test_env);
-        let result = verifier.verify_preamble(&preamble, ResetReason::ColdReset);
+        let result = verifier.verify_preamble(&preamble, 0, 0, ResetReason::ColdReset);
         assert!(result.is_err());
         assert_eq!(
             result.err(),
@@ -1144,7 +1557,7 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_preamble(&preamble, ResetReason::ColdReset);
+        let result = verifier.verify_preamble(&preamble, 0, 0, ResetReason::ColdReset);
         assert!(result.is_ok());
     }
 
@@ -1158,13 +1571,200 @@ mod tests {
         };
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
-        let result = verifier.verify_preamble(&preamble, ResetReason::ColdReset);
+        let result = verifier.verify_preamble(&preamble, 0, 0, ResetReason::ColdReset);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_PUB_KEY_DIGEST_MISMATCH)
         );
     }
 
+    fn intermediate_key_preamble(key_idx: u32) -> ImagePreamble {
+        ImagePreamble {
+            vendor_intermediate_ecc_key_idx: key_idx,
+            ..Default::default()
+        }
+    }
+
+    fn intermediate_key_table(revocation: u32, svn: u32) -> ImageVendorIntermediateKeyTable {
+        let mut table = ImageVendorIntermediateKeyTable {
+            sig: VENDOR_ECC_SIG,
+            ..Default::default()
+        };
+        table.keys.revocation = revocation;
+        table.keys.svn = svn;
+        table.keys.ecc_pub_keys[1] = VENDOR_ECC_PUBKEY;
+        table
+    }
+
+    fn intermediate_key_test_env(verify_result: bool) -> TestEnv {
+        TestEnv {
+            lifecycle: Lifecycle::Production,
+            vendor_pub_key_digest: DUMMY_DATA,
+            owner_pub_key_digest: DUMMY_DATA,
+            digest: DUMMY_DATA,
+            verify_result,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_preamble_vendor_intermediate_key() {
+        let table = intermediate_key_table(0, 0);
+        let mut verifier = ImageVerifier::new(intermediate_key_test_env(true))
+            .with_vendor_intermediate_keys(&table);
+        let preamble = intermediate_key_preamble(1);
+
+        let info = verifier
+            .verify_preamble(
+                &preamble,
+                VENDOR_INTERMEDIATE_KEY_FLAG,
+                0,
+                ResetReason::ColdReset,
+            )
+            .unwrap();
+        assert_eq!(info.vendor_intermediate_ecc_key_idx, Some(1));
+        assert_eq!(*info.vendor_ecc_info.0, VENDOR_ECC_PUBKEY);
+
+        // The intermediate keys are ignored unless the header flag is set
+        let info = verifier
+            .verify_preamble(&preamble, 0, 0, ResetReason::ColdReset)
+            .unwrap();
+        assert_eq!(info.vendor_intermediate_ecc_key_idx, None);
+        assert_eq!(*info.vendor_ecc_info.0, ImageEccPubKey::default());
+    }
+
+    #[test]
+    fn test_preamble_vendor_intermediate_key_index_out_of_bounds() {
+        let table = intermediate_key_table(0, 0);
+        let mut verifier = ImageVerifier::new(intermediate_key_test_env(true))
+            .with_vendor_intermediate_keys(&table);
+        let preamble = intermediate_key_preamble(VENDOR_INTERMEDIATE_ECC_KEY_COUNT);
+
+        let result = verifier.verify_preamble(
+            &preamble,
+            VENDOR_INTERMEDIATE_KEY_FLAG,
+            0,
+            ResetReason::ColdReset,
+        );
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_INDEX_OUT_OF_BOUNDS)
+        );
+    }
+
+    #[test]
+    fn test_preamble_vendor_intermediate_key_revoked() {
+        let table = intermediate_key_table(0b10, 0);
+        let mut verifier = ImageVerifier::new(intermediate_key_test_env(true))
+            .with_vendor_intermediate_keys(&table);
+        let preamble = intermediate_key_preamble(1);
+
+        let result = verifier.verify_preamble(
+            &preamble,
+            VENDOR_INTERMEDIATE_KEY_FLAG,
+            0,
+            ResetReason::ColdReset,
+        );
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_REVOKED)
+        );
+    }
+
+    #[test]
+    fn test_preamble_vendor_intermediate_keys_replayed() {
+        let preamble = intermediate_key_preamble(1);
+        let verify_table = |table: &ImageVendorIntermediateKeyTable, lifecycle| {
+            ImageVerifier::new(TestEnv {
+                fuse_svn: 2,
+                lifecycle,
+                ..intermediate_key_test_env(true)
+            })
+            .with_vendor_intermediate_keys(table)
+            .verify_preamble(
+                &preamble,
+                VENDOR_INTERMEDIATE_KEY_FLAG,
+                0,
+                ResetReason::ColdReset,
+            )
+            .map(|info| info.vendor_intermediate_ecc_key_idx)
+        };
+
+        // Key 1 was revoked by the table with SVN 2, and the fuses advanced
+        assert_eq!(
+            verify_table(&intermediate_key_table(0b10, 2), Lifecycle::Production),
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEY_REVOKED)
+        );
+
+        // The validly signed table from before the revocation is rejected
+        assert_eq!(
+            verify_table(&intermediate_key_table(0, 1), Lifecycle::Production),
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SVN_LESS_THAN_FUSE)
+        );
+
+        assert_eq!(
+            verify_table(&intermediate_key_table(0, 33), Lifecycle::Production),
+            Err(
+                CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SVN_GREATER_THAN_MAX_SUPPORTED
+            )
+        );
+
+        // Unprovisioned devices do not check the SVN
+        assert_eq!(
+            verify_table(&intermediate_key_table(0, 1), Lifecycle::Unprovisioned),
+            Ok(Some(1))
+        );
+    }
+
+    #[test]
+    fn test_preamble_vendor_intermediate_keys_signature_invalid() {
+        let table = intermediate_key_table(0, 0);
+        let mut verifier = ImageVerifier::new(intermediate_key_test_env(false))
+            .with_vendor_intermediate_keys(&table);
+        let preamble = intermediate_key_preamble(1);
+
+        let result = verifier.verify_preamble(
+            &preamble,
+            VENDOR_INTERMEDIATE_KEY_FLAG,
+            0,
+            ResetReason::ColdReset,
+        );
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID)
+        );
+
+        let table = ImageVendorIntermediateKeyTable {
+            sig: ImageEccSignature::default(),
+            ..intermediate_key_table(0, 0)
+        };
+        let mut verifier = ImageVerifier::new(intermediate_key_test_env(true))
+            .with_vendor_intermediate_keys(&table);
+        let result = verifier.verify_preamble(
+            &preamble,
+            VENDOR_INTERMEDIATE_KEY_FLAG,
+            0,
+            ResetReason::ColdReset,
+        );
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID_ARG)
+        );
+
+        // An image signed by an intermediate key needs the key table
+        let mut verifier = ImageVerifier::new(intermediate_key_test_env(true));
+        let result = verifier.verify_preamble(
+            &preamble,
+            VENDOR_INTERMEDIATE_KEY_FLAG,
+            0,
+            ResetReason::ColdReset,
+        );
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_INTERMEDIATE_KEYS_SIGNATURE_INVALID_ARG)
+        );
+    }
+
     #[test]
     fn test_header_vendor_pubkey_invalid_arg() {
         let test_env = TestEnv::default();
@@ -1185,7 +1785,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1215,7 +1817,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1251,7 +1855,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1282,12 +1888,14 @@ mod tests {
             vendor_ecc_pub_key_idx: 0,
             vendor_lms_pub_key_idx: Some(0),
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_ecc_info: (&VENDOR_ECC_PUBKEY, &VENDOR_ECC_SIG),
             vendor_lms_info: Some((&binding_vendor_lms_pubkey, &binding_vendor_lms_sig)),
             owner_ecc_info: (&owner_ecc_pubkey, &owner_ecc_sig),
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1321,7 +1929,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1355,7 +1965,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1389,7 +2001,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1422,7 +2036,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let result = verifier.verify_header(&header, &header_info);
@@ -1458,7 +2074,9 @@ mod tests {
             owner_lms_info: Some((&owner_lms_pubkey, &owner_lms_sig)),
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
+            next_owner_pub_keys_digest: ZERO_DIGEST,
             vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_intermediate_ecc_key_idx: None,
             vendor_lms_pub_key_revocation: Default::default(),
         };
         let toc_info = verifier.verify_header(&header, &header_info).unwrap();
@@ -1472,7 +2090,17 @@ mod tests {
         let test_env = TestEnv::default();
         let mut verifier = ImageVerifier::new(test_env);
         let toc_info = TocInfo {
-            len: MAX_TOC_ENTRY_COUNT / 2,
+            len: MIN_TOC_ENTRY_COUNT - 1,
+            digest: &ImageDigest::default(),
+        };
+        let result = verifier.verify_toc(&manifest, &toc_info, manifest.size);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_TOC_ENTRY_COUNT_INVALID)
+        );
+
+        let toc_info = TocInfo {
+            len: MAX_TOC_ENTRY_COUNT + 1,
             digest: &ImageDigest::default(),
         };
         let result = verifier.verify_toc(&manifest, &toc_info, manifest.size);
@@ -1778,6 +2406,123 @@ mod tests {
         );
     }
 
+    fn segmented_runtime_manifest() -> ImageManifest {
+        let mut manifest = ImageManifest::default();
+        manifest.header.toc_len = 3;
+        manifest.fmc.offset = 0;
+        manifest.fmc.size = 100;
+        manifest.fmc.load_addr = ICCM_ORG;
+        manifest.runtime.offset = 100;
+        manifest.runtime.size = 100;
+        manifest.runtime.load_addr = ICCM_ORG + 0x1000;
+        manifest.runtime.entry_point = ICCM_ORG + 0x1000;
+        manifest.runtime_extra_segments[0].offset = 200;
+        manifest.runtime_extra_segments[0].size = 60;
+        manifest.runtime_extra_segments[0].load_addr = ICCM_ORG + 0x2000;
+        manifest
+    }
+
+    #[test]
+    fn test_rt_segments() {
+        let manifest = segmented_runtime_manifest();
+        let mut verifier = ImageVerifier::new(TestEnv::default());
+        let toc_info = TocInfo {
+            len: manifest.header.toc_len,
+            digest: &ImageDigest::default(),
+        };
+        let img_bundle_sz = manifest.size + 260;
+
+        assert_eq!(manifest.runtime_segments().count(), 2);
+        assert!(verifier
+            .verify_toc(&manifest, &toc_info, img_bundle_sz)
+            .is_ok());
+        let (info, _log_info) = verifier.verify_runtime(&manifest, false).unwrap();
+        assert_eq!(info.size, 160);
+
+        // Segments must be stored back to back
+        let mut gap = segmented_runtime_manifest();
+        gap.runtime_extra_segments[0].offset = 204;
+        assert_eq!(
+            verifier.verify_toc(&gap, &toc_info, img_bundle_sz).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_OFFSET_INVALID)
+        );
+
+        let mut empty = segmented_runtime_manifest();
+        empty.runtime_extra_segments[0].size = 0;
+        assert_eq!(
+            verifier.verify_toc(&empty, &toc_info, img_bundle_sz).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SIZE_ZERO)
+        );
+
+        let mut overlap = segmented_runtime_manifest();
+        overlap.runtime_extra_segments[0].load_addr = ICCM_ORG + 0x1000 + 96;
+        assert_eq!(
+            verifier
+                .verify_toc(&overlap, &toc_info, img_bundle_sz)
+                .err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_LOAD_ADDR_OVERLAP)
+        );
+
+        let mut outside_iccm = segmented_runtime_manifest();
+        outside_iccm.runtime_extra_segments[0].load_addr = ICCM_ORG + ICCM_SIZE - 4;
+        assert_eq!(
+            verifier.verify_runtime(&outside_iccm, false).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_INVALID)
+        );
+    }
+
+    #[test]
+    fn test_rt_compressed_segments() {
+        let mut manifest = segmented_runtime_manifest();
+        manifest.runtime_extra_segments[0].compression = ImageTocEntryCompression::Lzss.into();
+        manifest.runtime_extra_segments[0].uncompressed_size = 0x1000;
+        let mut verifier = ImageVerifier::new(TestEnv::default());
+        let toc_info = TocInfo {
+            len: manifest.header.toc_len,
+            digest: &ImageDigest::default(),
+        };
+        let img_bundle_sz = manifest.size + 260;
+
+        assert!(verifier
+            .verify_toc(&manifest, &toc_info, img_bundle_sz)
+            .is_ok());
+        let (info, _log_info) = verifier.verify_runtime(&manifest, false).unwrap();
+        assert_eq!(info.size, 160);
+
+        let mut unknown = manifest;
+        unknown.runtime_extra_segments[0].compression = 2;
+        assert_eq!(
+            verifier
+                .verify_toc(&unknown, &toc_info, img_bundle_sz)
+                .err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_TOC_ENTRY_COMPRESSION_INVALID)
+        );
+
+        let mut empty = manifest;
+        empty.runtime_extra_segments[0].uncompressed_size = 0;
+        assert_eq!(
+            verifier.verify_toc(&empty, &toc_info, img_bundle_sz).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SIZE_ZERO)
+        );
+
+        // ICCM placement is checked against the decompressed size
+        let mut outside_iccm = manifest;
+        outside_iccm.runtime_extra_segments[0].load_addr = ICCM_ORG + ICCM_SIZE - 0x800;
+        assert_eq!(
+            verifier.verify_runtime(&outside_iccm, false).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_INVALID)
+        );
+
+        let mut overlap = manifest;
+        overlap.runtime_extra_segments[0].load_addr = ICCM_ORG + 0x1000 - 0x800;
+        assert_eq!(
+            verifier
+                .verify_toc(&overlap, &toc_info, img_bundle_sz)
+                .err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SEGMENT_LOAD_ADDR_OVERLAP)
+        );
+    }
+
     #[test]
     fn test_fmc_contained_in_iccm() {
         let test_env = TestEnv::default();
@@ -1789,7 +2534,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset);
+        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset, false);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDR_INVALID)
@@ -1802,7 +2547,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset);
+        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset, false);
         assert_eq!(result.err(), None);
     }
 
@@ -1811,16 +2556,37 @@ mod tests {
         let test_env = TestEnv::default();
         let mut verifier = ImageVerifier::new(test_env);
         let verify_info = ImageTocEntry {
+            load_addr: ICCM_ORG,
+            entry_point: ICCM_ORG,
+            size: 100,
             digest: DUMMY_DATA,
             ..Default::default()
         };
-        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset);
+        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset, false);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_FMC_DIGEST_MISMATCH)
         );
     }
 
+    #[test]
+    fn test_fmc_load_addr_checked_before_digest() {
+        let test_env = TestEnv::default();
+        let mut verifier = ImageVerifier::new(test_env);
+        let verify_info = ImageTocEntry {
+            load_addr: ICCM_ORG + ICCM_SIZE - 4,
+            entry_point: ICCM_ORG,
+            size: 100,
+            digest: DUMMY_DATA,
+            ..Default::default()
+        };
+        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset, false);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_FMC_LOAD_ADDR_INVALID)
+        );
+    }
+
     #[test]
     fn test_fmc_success() {
         let test_env = TestEnv::default();
@@ -1833,7 +2599,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset);
+        let result = verifier.verify_fmc(&verify_info, ResetReason::ColdReset, false);
         assert!(result.is_ok());
         let (info, _log_info) = result.unwrap();
         assert_eq!(info.load_addr, ICCM_ORG);
@@ -1842,15 +2608,25 @@ mod tests {
         assert_eq!(info.size, 100);
     }
 
+    fn runtime_manifest(runtime: ImageTocEntry) -> ImageManifest {
+        ImageManifest {
+            runtime,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_rt_digest_mismatch() {
         let test_env = TestEnv::default();
         let mut verifier = ImageVerifier::new(test_env);
         let verify_info = ImageTocEntry {
+            load_addr: ICCM_ORG,
+            entry_point: ICCM_ORG,
+            size: 100,
             digest: DUMMY_DATA,
             ..Default::default()
         };
-        let result = verifier.verify_runtime(&verify_info);
+        let result = verifier.verify_runtime(&runtime_manifest(verify_info), false);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_DIGEST_MISMATCH)
@@ -1868,7 +2644,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_runtime(&verify_info);
+        let result = verifier.verify_runtime(&runtime_manifest(verify_info), false);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_LOAD_ADDR_INVALID)
@@ -1881,7 +2657,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = verifier.verify_runtime(&verify_info);
+        let result = verifier.verify_runtime(&runtime_manifest(verify_info), false);
         assert_eq!(result.err(), None);
     }
 
@@ -1896,7 +2672,7 @@ mod tests {
             size: 100,
             ..Default::default()
         };
-        let result = verifier.verify_runtime(&verify_info);
+        let result = verifier.verify_runtime(&runtime_manifest(verify_info), false);
         assert!(result.is_ok());
         let (info, _log_info) = result.unwrap();
         assert_eq!(info.load_addr, ICCM_ORG);
@@ -1905,6 +2681,57 @@ mod tests {
         assert_eq!(info.size, 100);
     }
 
+    #[test]
+    fn test_break_glass_svn_rollback() {
+        let test_env = TestEnv {
+            lifecycle: Lifecycle::Production,
+            fuse_svn: 5,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let verify_info = ImageTocEntry {
+            load_addr: ICCM_ORG,
+            entry_point: ICCM_ORG,
+            svn: 1,
+            size: 100,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            verifier
+                .verify_fmc(&verify_info, ResetReason::ColdReset, false)
+                .err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_FMC_SVN_LESS_THAN_FUSE)
+        );
+        assert_eq!(
+            verifier
+                .verify_runtime(&runtime_manifest(verify_info), false)
+                .err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_RUNTIME_SVN_LESS_THAN_FUSE)
+        );
+
+        let (info, _log_info) = verifier
+            .verify_fmc(&verify_info, ResetReason::ColdReset, true)
+            .unwrap();
+        assert_eq!(info.svn, 1);
+        let (info, _log_info) = verifier
+            .verify_runtime(&runtime_manifest(verify_info), true)
+            .unwrap();
+        assert_eq!(info.svn, 1);
+
+        // The maximum SVN still applies
+        let verify_info = ImageTocEntry {
+            svn: 33,
+            ..verify_info
+        };
+        assert_eq!(
+            verifier
+                .verify_fmc(&verify_info, ResetReason::ColdReset, true)
+                .err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_FMC_SVN_GREATER_THAN_MAX_SUPPORTED)
+        );
+    }
+
     struct TestEnv {
         digest: ImageDigest,
         fmc_digest: ImageDigest,
@@ -1914,7 +2741,10 @@ mod tests {
         vendor_ecc_pub_key_revocation: VendorPubKeyRevocation,
         vendor_lms_pub_key_revocation: u32,
         owner_pub_key_digest: ImageDigest,
+        committed_owner_pub_key_digest: ImageDigest,
+        next_owner_pub_key_digest: ImageDigest,
         lifecycle: Lifecycle,
+        fuse_svn: u32,
     }
 
     impl Default for TestEnv {
@@ -1928,7 +2758,10 @@ mod tests {
                 vendor_ecc_pub_key_revocation: VendorPubKeyRevocation::default(),
                 vendor_lms_pub_key_revocation: 0,
                 owner_pub_key_digest: ImageDigest::default(),
+                committed_owner_pub_key_digest: ImageDigest::default(),
+                next_owner_pub_key_digest: ImageDigest::default(),
                 lifecycle: Lifecycle::Unprovisioned,
+                fuse_svn: 0,
             }
         }
     }
@@ -2000,16 +2833,24 @@ mod tests {
             self.owner_pub_key_digest
         }
 
+        fn committed_owner_pub_key_digest_dv(&self) -> ImageDigest {
+            self.committed_owner_pub_key_digest
+        }
+
+        fn next_owner_pub_key_digest_dv(&self) -> ImageDigest {
+            self.next_owner_pub_key_digest
+        }
+
         fn get_fmc_digest_dv(&self) -> ImageDigest {
             self.fmc_digest
         }
 
         fn fmc_fuse_svn(&self) -> u32 {
-            0
+            self.fuse_svn
         }
 
         fn runtime_fuse_svn(&self) -> u32 {
-            0
+            self.fuse_svn
         }
 
         fn iccm_range(&self) -> Range<u32> {
@@ -0,0 +1,97 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    ecc256_sw_kat.rs
+
+Abstract:
+
+    File contains the Known Answer Tests (KAT) for the software ECC-256
+    implementation.
+
+--*/
+
+use caliptra_drivers::{
+    Array4x12, Array4x8, Array4xN, CaliptraError, CaliptraResult, Ecc256PubKey, Ecc256Result,
+    Ecc256Signature, Ecc256Sw, Trng,
+};
+
+/// Seed whose reduction is the RFC 6979 A.2.5 private key
+const KEY_GEN_SEED: Array4x12 = Array4x12::new([
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0xc9afa9d8, 0x45ba7516, 0x6b5c2157, 0x67b1d693,
+    0x4e50c3db, 0x36e89b12, 0x7b8a622b, 0x120f6721,
+]);
+
+const KEY_GEN_PUB_KEY: Ecc256PubKey = Ecc256PubKey {
+    x: Array4xN([
+        0x60fed4ba, 0x255a9d31, 0xc961eb74, 0xc6356d68, 0xc049b892, 0x3b61fa6c, 0xe669622e,
+        0x60f29fb6,
+    ]),
+    y: Array4xN([
+        0x7903fe10, 0x08b8bc99, 0xa41ae9e9, 0x5628bc64, 0xf2f1b20c, 0x2d7e9f51, 0x77a3c294,
+        0xd4462299,
+    ]),
+};
+
+/// SHA-256 digest of "sample"
+const DIGEST: Array4x8 = Array4x8::new([
+    0xaf2bdbe1, 0xaa9b6ec1, 0xe2ade1d6, 0x94f41fc7, 0x1a831d02, 0x68e98915, 0x62113d8a, 0x62add1bf,
+]);
+
+const SIGNATURE: Ecc256Signature = Ecc256Signature {
+    r: Array4xN([
+        0xefd48b2a, 0xacb6a8fd, 0x1140dd9c, 0xd45e81d6, 0x9d2c877b, 0x56aaf991, 0xc34d0ea8,
+        0x4eaf3716,
+    ]),
+    s: Array4xN([
+        0xf7cb1c94, 0x2d657c41, 0xd436c7a1, 0xb6e29f65, 0xf3e900db, 0xb9aff406, 0x4dc4ab2f,
+        0x843acda8,
+    ]),
+};
+
+#[derive(Default, Debug)]
+pub struct Ecc256SwKat {}
+
+impl Ecc256SwKat {
+    /// This function executes the Known Answer Tests (aka KAT) for the
+    /// software ECC-256 implementation.
+    ///
+    /// Test vector source:
+    /// RFC 6979 A.2.5, with SHA-256 and the message "sample"
+    ///
+    /// # Arguments
+    ///
+    /// * `trng` - TRNG driver, for the nonce of the pairwise consistency test
+    ///
+    /// # Returns
+    ///
+    /// * `CaliptraResult` - Result denoting the KAT outcome.
+    pub fn execute(&self, trng: &mut Trng) -> CaliptraResult<()> {
+        self.kat_key_pair_gen_sign_and_verify(trng)?;
+        self.kat_signature_verify()
+    }
+
+    fn kat_key_pair_gen_sign_and_verify(&self, trng: &mut Trng) -> CaliptraResult<()> {
+        let (priv_key, pub_key) = Ecc256Sw::key_pair(&KEY_GEN_SEED)
+            .map_err(|_| CaliptraError::KAT_ECC256_SW_KEY_PAIR_GENERATE_FAILURE)?;
+        if pub_key != KEY_GEN_PUB_KEY {
+            Err(CaliptraError::KAT_ECC256_SW_KEY_PAIR_VERIFY_FAILURE)?;
+        }
+
+        // Pairwise consistency test: signing verifies the signature
+        Ecc256Sw::sign(&priv_key, &pub_key, &DIGEST, trng)
+            .map_err(|_| CaliptraError::KAT_ECC256_SW_KEY_PAIR_VERIFY_FAILURE)?;
+
+        Ok(())
+    }
+
+    fn kat_signature_verify(&self) -> CaliptraResult<()> {
+        if Ecc256Sw::verify(&KEY_GEN_PUB_KEY, &DIGEST, &SIGNATURE)? != Ecc256Result::Success {
+            Err(CaliptraError::KAT_ECC256_SW_SIGNATURE_MISMATCH)?;
+        }
+
+        Ok(())
+    }
+}
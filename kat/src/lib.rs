@@ -14,6 +14,7 @@ Abstract:
 
 #![no_std]
 
+mod ecc256_sw_kat;
 mod ecc384_kat;
 mod hmac384kdf_kat;
 mod kats_env;
@@ -22,8 +23,10 @@ mod sha1_kat;
 mod sha256_kat;
 mod sha2_512_384acc_kat;
 mod sha384_kat;
+mod sha3_kat;
 
 pub use caliptra_drivers::{CaliptraError, CaliptraResult};
+pub use ecc256_sw_kat::Ecc256SwKat;
 pub use ecc384_kat::Ecc384Kat;
 pub use hmac384kdf_kat::Hmac384KdfKat;
 pub use kats_env::KatsEnv;
@@ -32,9 +35,68 @@ pub use sha1_kat::Sha1Kat;
 pub use sha256_kat::Sha256Kat;
 pub use sha2_512_384acc_kat::Sha2_512_384AccKat;
 pub use sha384_kat::Sha384Kat;
+pub use sha3_kat::Sha3Kat;
 
 use caliptra_drivers::cprintln;
 
+/// Known Answer Tests for the cryptographic algorithms implemented in H/W,
+/// shared by ROM and the Runtime Firmware self test
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kat {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha2_512_384Acc,
+    Ecc384,
+    Hmac384Kdf,
+    Lms,
+}
+
+impl Kat {
+    /// Every KAT, in execution order
+    pub const ALL: [Kat; 7] = [
+        Kat::Sha1,
+        Kat::Sha256,
+        Kat::Sha384,
+        Kat::Sha2_512_384Acc,
+        Kat::Ecc384,
+        Kat::Hmac384Kdf,
+        Kat::Lms,
+    ];
+
+    /// Name of the KAT, as printed in the boot log
+    pub fn name(self) -> &'static str {
+        match self {
+            Kat::Sha1 => "sha1",
+            Kat::Sha256 => "SHA2-256",
+            Kat::Sha384 => "SHA2-384",
+            Kat::Sha2_512_384Acc => "SHA2-512-ACC",
+            Kat::Ecc384 => "ECC-384",
+            Kat::Hmac384Kdf => "HMAC-384Kdf",
+            Kat::Lms => "LMS",
+        }
+    }
+
+    /// Execute the KAT
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - KAT Environment
+    pub fn execute(self, env: &mut KatsEnv) -> CaliptraResult<()> {
+        match self {
+            Kat::Sha1 => Sha1Kat::default().execute(env.sha1),
+            Kat::Sha256 => Sha256Kat::default().execute(env.sha256),
+            Kat::Sha384 => Sha384Kat::default().execute(env.sha384),
+            Kat::Sha2_512_384Acc => {
+                Sha2_512_384AccKat::default().execute(env.sha2_512_384_acc, env.sha_acc_lock_state)
+            }
+            Kat::Ecc384 => Ecc384Kat::default().execute(env.ecc384, env.trng),
+            Kat::Hmac384Kdf => Hmac384KdfKat::default().execute(env.hmac384, env.trng),
+            Kat::Lms => LmsKat::default().execute(env.sha256, env.lms),
+        }
+    }
+}
+
 /// Execute Known Answer Tests
 ///
 /// # Arguments
@@ -43,26 +105,10 @@ use caliptra_drivers::cprintln;
 pub fn execute_kat(env: &mut KatsEnv) -> CaliptraResult<()> {
     cprintln!("[kat] ++");
 
-    cprintln!("[kat] sha1");
-    Sha1Kat::default().execute(env.sha1)?;
-
-    cprintln!("[kat] SHA2-256");
-    Sha256Kat::default().execute(env.sha256)?;
-
-    cprintln!("[kat] SHA2-384");
-    Sha384Kat::default().execute(env.sha384)?;
-
-    cprintln!("[kat] SHA2-512-ACC");
-    Sha2_512_384AccKat::default().execute(env.sha2_512_384_acc, env.sha_acc_lock_state)?;
-
-    cprintln!("[kat] ECC-384");
-    Ecc384Kat::default().execute(env.ecc384, env.trng)?;
-
-    cprintln!("[kat] HMAC-384Kdf");
-    Hmac384KdfKat::default().execute(env.hmac384, env.trng)?;
-
-    cprintln!("[kat] LMS");
-    LmsKat::default().execute(env.sha256, env.lms)?;
+    for kat in Kat::ALL {
+        cprintln!("[kat] {}", kat.name());
+        kat.execute(env)?;
+    }
 
     cprintln!("[kat] --");
 
@@ -49,6 +49,12 @@ struct caliptra_req_header {
     caliptra_checksum chksum;
 };
 
+struct caliptra_req_versioned_header {
+    caliptra_checksum chksum;
+    uint8_t version;
+    uint8_t reserved[3];
+};
+
 struct caliptra_resp_header {
     caliptra_checksum chksum;
     uint32_t fips_status;
@@ -131,6 +137,10 @@ struct caliptra_stash_measurement_resp {
     uint32_t dpe_result;
 };
 
+struct caliptra_fw_info_req {
+    struct caliptra_req_versioned_header hdr;
+};
+
 struct caliptra_fw_info_resp {
     struct caliptra_resp_header hdr;
     uint32_t pl0_pauser;
@@ -145,6 +155,23 @@ struct caliptra_fw_info_resp {
     uint32_t fmc_sha384_digest[12];
     uint32_t runtime_sha384_digest[12];
     uint32_t owner_pub_key_hash[12];
+    uint32_t rom_sha_acc_lock_contention;
+    uint32_t fmc_wdt_budget_cycles;
+    uint32_t fmc_wdt_max_phase_cycles;
+    uint32_t idle_count;
+    uint32_t idle_cycles_low;
+    uint32_t idle_cycles_high;
+    uint32_t cmd_count;
+    uint32_t cmd_success_count;
+    uint32_t cmd_failure_count;
+    uint32_t uptime_cycles_low;
+    uint32_t uptime_cycles_high;
+    uint32_t iccm_ecc_cor_count;
+    uint32_t dccm_ecc_cor_count;
+    uint32_t mbox_ecc_cor_count;
+    uint32_t mbox_ecc_unc_count;
+    uint32_t ccm_ecc_unc;
+    uint32_t dccm_scrub_passes;
 };
 
 struct caliptra_dpe_tag_tci_req {
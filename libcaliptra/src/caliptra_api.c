@@ -1079,9 +1079,10 @@ int caliptra_fw_info(struct caliptra_fw_info_resp *resp, bool async)
         return INVALID_PARAMS;
     }
 
-    caliptra_checksum checksum = 0;
+    // Version 2 returns every field of the response
+    struct caliptra_fw_info_req req = { .hdr.version = 2 };
 
-    CREATE_PARCEL(p, OP_FW_INFO, &checksum, resp);
+    CREATE_PARCEL(p, OP_FW_INFO, &req, resp);
 
     return pack_and_execute_command(&p, async);
 }
@@ -14,17 +14,19 @@ Abstract:
 
 use crate::{rom_env::RomEnv, CALIPTRA_ROM_INFO};
 use caliptra_cfi_derive::cfi_mod_fn;
+use caliptra_common::log_trace;
 use caliptra_common::{
     keyids::{KEY_ID_FMC_PRIV_KEY, KEY_ID_ROM_FMC_CDI},
     DataVaultRegister, FirmwareHandoffTable, HandOffDataHandle, Vault, FHT_INVALID_HANDLE,
     FHT_MARKER,
 };
 use caliptra_drivers::{
-    cprintln, ColdResetEntry4, ColdResetEntry48, RomAddr, WarmResetEntry4, WarmResetEntry48,
+    hand_off::FHT_HW_CAPS_MINOR_VERSION, ColdResetEntry4, ColdResetEntry48, RomAddr,
+    WarmResetEntry4, WarmResetEntry48,
 };
 
 const FHT_MAJOR_VERSION: u16 = 1;
-const FHT_MINOR_VERSION: u16 = 0;
+const FHT_MINOR_VERSION: u16 = FHT_HW_CAPS_MINOR_VERSION;
 
 #[derive(Debug, Default)]
 pub struct FhtDataStore {}
@@ -151,7 +153,7 @@ impl FhtDataStore {
 pub fn initialize_fht(env: &mut RomEnv) {
     let pdata = &env.persistent_data.get();
 
-    cprintln!("[fht] FHT @ 0x{:08X}", &pdata.fht as *const _ as usize);
+    log_trace!("[fht] FHT @ 0x{:08X}", &pdata.fht as *const _ as usize);
 
     env.persistent_data.get_mut().fht = FirmwareHandoffTable {
         fht_marker: FHT_MARKER,
@@ -181,6 +183,7 @@ pub fn initialize_fht(env: &mut RomEnv) {
         pcr_log_addr: &pdata.pcr_log as *const _ as u32,
         meas_log_addr: &pdata.measurement_log as *const _ as u32,
         fuse_log_addr: &pdata.fuse_log as *const _ as u32,
+        hw_caps: env.soc_ifc.hw_capabilities().bits(),
         ..Default::default()
     };
 }
@@ -124,37 +124,24 @@ impl Crypto {
         )
     }
 
-    /// Calculate HMAC-348 KDF
+    /// Derive a CDI or key generation seed using the SP 800-108 KDF
     ///
     /// # Arguments
     ///
     /// * `env` - ROM Environment
     /// * `key` - HMAC384 key slot
-    /// * `label` - Input label
-    /// * `context` - Input context
+    /// * `label` - KDF label
+    /// * `context` - KDF context
     /// * `output` - Key slot to store the output
     #[inline(always)]
-    pub fn hmac384_kdf(
+    pub fn kdf(
         env: &mut RomEnv,
         key: KeyId,
         label: &[u8],
         context: Option<&[u8]>,
         output: KeyId,
     ) -> CaliptraResult<()> {
-        hmac384_kdf(
-            &mut env.hmac384,
-            KeyReadArgs::new(key).into(),
-            label,
-            context,
-            &mut env.trng,
-            KeyWriteArgs::new(
-                output,
-                KeyUsage::default()
-                    .set_hmac_key_en()
-                    .set_ecc_key_gen_seed_en(),
-            )
-            .into(),
-        )
+        Kdf::new(&mut env.hmac384, &mut env.trng).derive_cdi(key, label, context, output)
     }
 
     /// Generate ECC Key Pair
@@ -175,19 +162,9 @@ impl Crypto {
         label: &[u8],
         priv_key: KeyId,
     ) -> CaliptraResult<Ecc384KeyPair> {
-        Crypto::hmac384_kdf(env, cdi, label, None, KEY_ID_TMP)?;
+        Crypto::kdf(env, cdi, label, None, KEY_ID_TMP)?;
 
-        let key_out = Ecc384PrivKeyOut::Key(KeyWriteArgs::new(
-            priv_key,
-            KeyUsage::default().set_ecc_private_key_en(),
-        ));
-
-        let pub_key = env.ecc384.key_pair(
-            &KeyReadArgs::new(KEY_ID_TMP).into(),
-            &Array4x12::default(),
-            &mut env.trng,
-            key_out,
-        );
+        let pub_key = env.ecc384.keygen_to_kv(KEY_ID_TMP, &mut env.trng, priv_key);
         env.key_vault.erase_key(KEY_ID_TMP)?;
 
         Ok(Ecc384KeyPair {
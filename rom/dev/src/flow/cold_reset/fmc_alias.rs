@@ -17,7 +17,6 @@ use super::crypto::{Crypto, Ecc384KeyPair};
 use super::dice::{DiceInput, DiceOutput};
 use super::fw_processor::FwProcInfo;
 use super::x509::X509;
-use crate::cprintln;
 use crate::flow::cold_reset::{copy_tbs, TbsType};
 use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
@@ -27,7 +26,12 @@ use caliptra_common::dice;
 use caliptra_common::keyids::{KEY_ID_FMC_PRIV_KEY, KEY_ID_ROM_FMC_CDI};
 use caliptra_common::pcr::PCR_ID_FMC_CURRENT;
 use caliptra_common::RomBootStatus::*;
-use caliptra_drivers::{okmutref, report_boot_status, Array4x12, CaliptraResult, KeyId, Lifecycle};
+use caliptra_common::{log_info, log_trace};
+use caliptra_drivers::{
+    okmutref, report_boot_status, Array4x12, CaliptraResult, Hmac384Key, Kdf, KeyId, KeyUsage,
+    KeyWriteArgs, Lifecycle, KDF_LABEL_FMC_ALIAS_CDI, KDF_LABEL_FMC_ALIAS_KEYGEN,
+    KDF_LABEL_ZERO_FMC_CDI,
+};
 use caliptra_x509::{FmcAliasCertTbs, FmcAliasCertTbsParams};
 use zeroize::Zeroize;
 
@@ -42,10 +46,10 @@ impl FmcAliasLayer {
         input: &DiceInput,
         fw_proc_info: &FwProcInfo,
     ) -> CaliptraResult<()> {
-        cprintln!("[afmc] ++");
-        cprintln!("[afmc] CDI.KEYID = {}", KEY_ID_ROM_FMC_CDI as u8);
-        cprintln!("[afmc] SUBJECT.KEYID = {}", KEY_ID_FMC_PRIV_KEY as u8);
-        cprintln!(
+        log_info!("[afmc] ++");
+        log_trace!("[afmc] CDI.KEYID = {}", KEY_ID_ROM_FMC_CDI as u8);
+        log_trace!("[afmc] SUBJECT.KEYID = {}", KEY_ID_FMC_PRIV_KEY as u8);
+        log_trace!(
             "[afmc] AUTHORITY.KEYID = {}",
             input.auth_key_pair.priv_key as u8
         );
@@ -53,8 +57,17 @@ impl FmcAliasLayer {
         // We use the value of PCR0 as the measurement for deriving the CDI.
         let mut measurement = env.pcr_bank.read_pcr(PCR_ID_FMC_CURRENT);
 
-        // Derive the DICE CDI from the measurement
-        let result = Self::derive_cdi(env, &measurement, KEY_ID_ROM_FMC_CDI);
+        // Derive the DICE CDI from the measurement. A break-glass image may
+        // carry firmware that predates attestation being disabled for it, so
+        // its CDI must not be derived from the LDevID CDI.
+        let break_glass = fw_proc_info.break_glass;
+        let result = if cfi_launder(break_glass) {
+            cfi_assert!(break_glass);
+            Self::derive_break_glass_cdi(env, &measurement, KEY_ID_ROM_FMC_CDI)
+        } else {
+            cfi_assert!(!break_glass);
+            Self::derive_cdi(env, &measurement, KEY_ID_ROM_FMC_CDI)
+        };
         measurement.0.zeroize();
         result?;
 
@@ -84,7 +97,7 @@ impl FmcAliasLayer {
         result?;
 
         report_boot_status(FmcAliasDerivationComplete.into());
-        cprintln!("[afmc] --");
+        log_info!("[afmc] --");
 
         Ok(())
     }
@@ -100,7 +113,44 @@ impl FmcAliasLayer {
     fn derive_cdi(env: &mut RomEnv, measurements: &Array4x12, cdi: KeyId) -> CaliptraResult<()> {
         let mut measurements: [u8; 48] = measurements.into();
 
-        let result = Crypto::hmac384_kdf(env, cdi, b"fmc_alias_cdi", Some(&measurements), cdi);
+        let result = Crypto::kdf(env, cdi, KDF_LABEL_FMC_ALIAS_CDI, Some(&measurements), cdi);
+        measurements.zeroize();
+        result?;
+        report_boot_status(FmcAliasDeriveCdiComplete.into());
+        Ok(())
+    }
+
+    /// Derive the Composite Device Identity (CDI) of a break-glass image from
+    /// FMC measurements, with an all-zero key in place of the LDevID CDI.
+    /// No key derived from it chains to the device identity.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - ROM Environment
+    /// * `measurements` - Array containing the FMC measurements
+    /// * `cdi` - Key Slot holding the LDevID CDI, and to store the generated CDI
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    fn derive_break_glass_cdi(
+        env: &mut RomEnv,
+        measurements: &Array4x12,
+        cdi: KeyId,
+    ) -> CaliptraResult<()> {
+        let mut measurements: [u8; 48] = measurements.into();
+
+        let result = env.key_vault.erase_key(cdi).and_then(|_| {
+            Kdf::new(&mut env.hmac384, &mut env.trng).derive(
+                Hmac384Key::Array4x12(&Array4x12::default()),
+                KDF_LABEL_ZERO_FMC_CDI,
+                Some(&measurements),
+                KeyWriteArgs::new(
+                    cdi,
+                    KeyUsage::default()
+                        .set_hmac_key_en()
+                        .set_ecc_key_gen_seed_en(),
+                )
+                .into(),
+            )
+        });
         measurements.zeroize();
         result?;
         report_boot_status(FmcAliasDeriveCdiComplete.into());
@@ -124,7 +174,7 @@ impl FmcAliasLayer {
         cdi: KeyId,
         priv_key: KeyId,
     ) -> CaliptraResult<Ecc384KeyPair> {
-        let result = Crypto::ecc384_key_gen(env, cdi, b"fmc_alias_keygen", priv_key);
+        let result = Crypto::ecc384_key_gen(env, cdi, KDF_LABEL_FMC_ALIAS_KEYGEN, priv_key);
         if cfi_launder(result.is_ok()) {
             cfi_assert!(result.is_ok());
             report_boot_status(FmcAliasKeyPairDerivationComplete.into());
@@ -187,23 +237,31 @@ impl FmcAliasLayer {
             tcb_info_flags: &flags,
             tcb_info_fmc_svn: &svn.to_be_bytes(),
             tcb_info_fmc_svn_fuses: &fuse_svn.to_be_bytes(),
-            not_before: &fw_proc_info.fmc_cert_valid_not_before.value,
-            not_after: &fw_proc_info.fmc_cert_valid_not_after.value,
+            not_before: &fw_proc_info.cert_valid_not_before.value,
+            not_after: &fw_proc_info.cert_valid_not_after.value,
         };
 
         // Generate the `To Be Signed` portion of the CSR
         let tbs = FmcAliasCertTbs::new(&params);
 
+        // The LDevID never certifies the FMC alias key of a break-glass
+        // image, which signs its own certificate instead
+        let (sign_priv_key, sign_pub_key) = if fw_proc_info.break_glass {
+            (output.subj_key_pair.priv_key, pub_key)
+        } else {
+            (auth_priv_key, auth_pub_key)
+        };
+
         // Sign the `To Be Signed` portion
-        cprintln!(
+        log_trace!(
             "[afmc] Signing Cert w/ AUTHORITY.KEYID = {}",
-            auth_priv_key as u8
+            sign_priv_key as u8
         );
-        let mut sig = Crypto::ecdsa384_sign_and_verify(env, auth_priv_key, auth_pub_key, tbs.tbs());
+        let mut sig = Crypto::ecdsa384_sign_and_verify(env, sign_priv_key, sign_pub_key, tbs.tbs());
         let sig = okmutref(&mut sig)?;
 
         // Clear the authority private key
-        cprintln!("[afmc] Erase AUTHORITY.KEYID = {}", auth_priv_key as u8);
+        log_trace!("[afmc] Erase AUTHORITY.KEYID = {}", auth_priv_key as u8);
         env.key_vault.erase_key(auth_priv_key).map_err(|err| {
             sig.zeroize();
             err
@@ -211,13 +269,13 @@ impl FmcAliasLayer {
 
         let _pub_x: [u8; 48] = (&pub_key.x).into();
         let _pub_y: [u8; 48] = (&pub_key.y).into();
-        cprintln!("[afmc] PUB.X = {}", HexBytes(&_pub_x));
-        cprintln!("[afmc] PUB.Y = {}", HexBytes(&_pub_y));
+        log_trace!("[afmc] PUB.X = {}", HexBytes(&_pub_x));
+        log_trace!("[afmc] PUB.Y = {}", HexBytes(&_pub_y));
 
         let _sig_r: [u8; 48] = (&sig.r).into();
         let _sig_s: [u8; 48] = (&sig.s).into();
-        cprintln!("[afmc] SIG.R = {}", HexBytes(&_sig_r));
-        cprintln!("[afmc] SIG.S = {}", HexBytes(&_sig_s));
+        log_trace!("[afmc] SIG.R = {}", HexBytes(&_sig_r));
+        log_trace!("[afmc] SIG.S = {}", HexBytes(&_sig_s));
 
         // Lock the FMC Certificate Signature in data vault until next boot
         env.data_vault.set_fmc_dice_signature(sig);
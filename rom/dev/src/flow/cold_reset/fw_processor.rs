@@ -13,26 +13,32 @@ Abstract:
 --*/
 #[cfg(feature = "fake-rom")]
 use crate::flow::fake::FakeRomImageVerificationEnv;
+use crate::flow::image_loader::load_toc_entry;
 use crate::fuse::log_fuse_data;
 use crate::pcr;
+use crate::personality::PERSONALITY;
 use crate::rom_env::RomEnv;
 use crate::run_fips_tests;
 use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_cfi_lib::CfiCounter;
+use caliptra_common::boot_profile::boot_checkpoint;
 use caliptra_common::capabilities::Capabilities;
 use caliptra_common::fips::FipsVersionCmd;
 use caliptra_common::mailbox_api::{
-    CapabilitiesResp, CommandId, GetIdevCsrResp, MailboxReqHeader, MailboxRespHeader, Response,
-    StashMeasurementReq, StashMeasurementResp,
+    CapabilitiesResp, CommandId, GetIdevCsrResp, GetProvisioningStatusResp, MailboxReqHeader,
+    MailboxRespHeader, ProvisioningStatus, Response, StashMeasurementReq, StashMeasurementResp,
 };
 use caliptra_common::pcr::PCR_ID_STASH_MEASUREMENT;
 use caliptra_common::verifier::FirmwareImageVerificationEnv;
 use caliptra_common::PcrLogEntry;
 use caliptra_common::PcrLogEntryId;
+use caliptra_common::{log_error, log_info, log_trace, log_warn};
 use caliptra_common::{FuseLogEntryId, RomBootStatus::*};
 use caliptra_drivers::pcr_log::MeasurementLogEntry;
 use caliptra_drivers::*;
-use caliptra_image_types::{ImageManifest, IMAGE_BYTE_SIZE};
+use caliptra_image_types::{
+    ImageManifest, ImageManifestLayout, ImageVendorIntermediateKeyTable, IMAGE_BYTE_SIZE,
+};
 use caliptra_image_verify::{ImageVerificationInfo, ImageVerificationLogInfo, ImageVerifier};
 use caliptra_kat::KatsEnv;
 use caliptra_x509::{NotAfter, NotBefore};
@@ -44,13 +50,15 @@ const RESERVED_PAUSER: u32 = 0xFFFFFFFF;
 
 #[derive(Debug, Default, Zeroize)]
 pub struct FwProcInfo {
-    pub fmc_cert_valid_not_before: NotBefore,
+    pub cert_valid_not_before: NotBefore,
 
-    pub fmc_cert_valid_not_after: NotAfter,
+    pub cert_valid_not_after: NotAfter,
 
     pub fmc_effective_fuse_svn: u32,
 
     pub owner_pub_keys_digest_in_fuses: bool,
+
+    pub break_glass: bool,
 }
 
 pub struct FirmwareProcessor {}
@@ -88,6 +96,7 @@ impl FirmwareProcessor {
         // Process mailbox commands.
         let mut txn = Self::process_mailbox_commands(
             &mut env.soc_ifc,
+            &env.doe,
             &mut env.mbox,
             &mut env.pcr_bank,
             &mut kats_env,
@@ -103,7 +112,7 @@ impl FirmwareProcessor {
 
         // Load the manifest
         let manifest = Self::load_manifest(&mut env.persistent_data, &mut txn);
-        let manifest = okref(&manifest)?;
+        let (manifest, manifest_layout) = okref(&manifest)?;
 
         let mut venv = FirmwareImageVerificationEnv {
             sha256: &mut env.sha256,
@@ -113,10 +122,25 @@ impl FirmwareProcessor {
             data_vault: &mut env.data_vault,
             pcr_bank: &mut env.pcr_bank,
             image: txn.raw_mailbox_contents(),
+            // Load the image while hashing it rather than re-walking the
+            // mailbox after verification.
+            load_sha_acc: if PERSONALITY.fake_flow {
+                None
+            } else {
+                Some(&mut env.sha2_512_384_acc)
+            },
+            wdt_pet: Some(restart_wdt1),
         };
+        let loaded_during_verify = venv.load_sha_acc.is_some();
 
         // Verify the image
-        let info = Self::verify_image(&mut venv, manifest, txn.dlen());
+        let info = Self::verify_image(
+            &mut venv,
+            manifest,
+            &env.persistent_data.get().vendor_intermediate_keys,
+            *manifest_layout,
+            txn.dlen(),
+        );
         let info = okref(&info)?;
 
         Self::update_fuse_log(&mut env.persistent_data.get_mut().fuse_log, &info.log_info)?;
@@ -124,12 +148,20 @@ impl FirmwareProcessor {
         // Populate data vault
         Self::populate_data_vault(venv.data_vault, info, &env.persistent_data);
 
+        // Like the rotation itself, a commit request does not survive a cold
+        // reset.
+        env.persistent_data.get_mut().owner_key_commit_request = Default::default();
+
         // Extend PCR0 and PCR1
         pcr::extend_pcrs(&mut venv, info, &mut env.persistent_data)?;
         report_boot_status(FwProcessorExtendPcrComplete.into());
 
         // Load the image
-        Self::load_image(manifest, &mut txn)?;
+        if loaded_during_verify {
+            Self::skip_image(manifest, &mut txn)?;
+        } else {
+            Self::load_image(manifest, &mut txn)?;
+        }
 
         // Complete the mailbox transaction indicating success.
         txn.complete(true)?;
@@ -140,15 +172,22 @@ impl FirmwareProcessor {
         env.soc_ifc.set_fmc_fw_rev_id(manifest.fmc.version as u16);
         env.soc_ifc.set_rt_fw_rev_id(manifest.runtime.version);
 
+        // Hand the SHA accelerator lock contention seen while loading to the runtime
+        env.persistent_data
+            .get_mut()
+            .fht
+            .rom_sha_acc_lock_contention = env.sha2_512_384_acc.lock_contention_count();
+
         // Get the certificate validity info
         let (nb, nf) = Self::get_cert_validity_info(manifest);
 
         report_boot_status(FwProcessorComplete.into());
         Ok(FwProcInfo {
-            fmc_cert_valid_not_before: nb,
-            fmc_cert_valid_not_after: nf,
+            cert_valid_not_before: nb,
+            cert_valid_not_after: nf,
             fmc_effective_fuse_svn: info.fmc.effective_fuse_svn,
             owner_pub_keys_digest_in_fuses: info.owner_pub_keys_digest_in_fuses,
+            break_glass: info.break_glass,
         })
     }
 
@@ -157,6 +196,7 @@ impl FirmwareProcessor {
     /// # Arguments
     ///
     /// * `soc_ifc` - SOC Interface
+    /// * `doe` - Deobfuscation Engine
     /// * `mbox` - Mailbox
     /// * `pcr_bank` - PCR Bank
     /// * `sha384` - SHA384
@@ -173,6 +213,7 @@ impl FirmwareProcessor {
     ///  but before caliptra has set the FW_ERROR_NON_FATAL register.
     fn process_mailbox_commands<'a>(
         soc_ifc: &mut SocIfc,
+        doe: &DeobfuscationEngine,
         mbox: &'a mut Mailbox,
         pcr_bank: &mut PcrBank,
         env: &mut KatsEnv,
@@ -180,7 +221,7 @@ impl FirmwareProcessor {
     ) -> CaliptraResult<ManuallyDrop<MailboxRecvTxn<'a>>> {
         let mut self_test_in_progress = false;
 
-        cprintln!("[fwproc] Wait for Commands...");
+        log_info!("[fwproc] Wait for Commands...");
         loop {
             // Random delay for CFI glitch protection.
             CfiCounter::delay();
@@ -193,7 +234,7 @@ impl FirmwareProcessor {
                     return Err(CaliptraError::FW_PROC_MAILBOX_RESERVED_PAUSER);
                 }
 
-                cprintln!("[fwproc] Recv command 0x{:08x}", txn.cmd());
+                log_trace!("[fwproc] Recv command 0x{:08x}", txn.cmd());
 
                 // Handle FW load as a separate case due to the re-borrow explained below
                 if txn.cmd() == CommandId::FIRMWARE_LOAD.into() {
@@ -207,12 +248,13 @@ impl FirmwareProcessor {
                     // failure) or by a manual complete call upon success.
                     let txn = ManuallyDrop::new(txn.start_txn());
                     if txn.dlen() == 0 || txn.dlen() > IMAGE_BYTE_SIZE as u32 {
-                        cprintln!("Invalid Img size: {} bytes" txn.dlen());
+                        log_error!("Invalid Img size: {} bytes" txn.dlen());
                         return Err(CaliptraError::FW_PROC_INVALID_IMAGE_SIZE);
                     }
 
-                    cprintln!("[fwproc] Recv'd Img size: {} bytes" txn.dlen());
+                    log_info!("[fwproc] Recv'd Img size: {} bytes" txn.dlen());
                     report_boot_status(FwProcessorDownloadImageComplete.into());
+                    boot_checkpoint(BootCheckpoint::RomFwReceived);
                     return Ok(txn);
                 }
 
@@ -285,7 +327,7 @@ impl FirmwareProcessor {
                     }
                     CommandId::STASH_MEASUREMENT => {
                         if persistent_data.fht.meas_log_index == MEASUREMENT_MAX_COUNT as u32 {
-                            cprintln!("[fwproc] Max # of measurements received.");
+                            log_warn!("[fwproc] Max # of measurements received.");
                             txn.complete(false)?;
 
                             // Raise a fatal error on hitting the max. limit.
@@ -329,8 +371,19 @@ impl FirmwareProcessor {
                         resp.populate_chksum();
                         txn.send_response(resp.as_bytes())?;
                     }
+                    CommandId::GET_PROVISIONING_STATUS => {
+                        let mut request = MailboxReqHeader::default();
+                        Self::copy_req_verify_chksum(&mut txn, request.as_mut_bytes())?;
+
+                        let mut resp = GetProvisioningStatusResp {
+                            hdr: MailboxRespHeader::default(),
+                            status: Self::provisioning_status(soc_ifc, doe, persistent_data).bits(),
+                        };
+                        resp.populate_chksum();
+                        txn.send_response(resp.as_bytes())?;
+                    }
                     _ => {
-                        cprintln!("[fwproc] Invalid command received");
+                        log_error!("[fwproc] Invalid command received");
                         // Don't complete the transaction here; let the fatal
                         // error handler do it to prevent a race condition
                         // setting the error code.
@@ -341,20 +394,70 @@ impl FirmwareProcessor {
         }
     }
 
-    /// Load the manifest
+    /// Collect the manufacturing provisioning steps observed so far
+    ///
+    /// ROM cannot read the UDS and field entropy fuses, so they are reported
+    /// as programmed once the Deobfuscation Engine has decrypted them.
+    ///
+    /// # Arguments
+    ///
+    /// * `soc_ifc` - SOC Interface
+    /// * `doe` - Deobfuscation Engine
+    /// * `persistent_data` - Persistent data
+    fn provisioning_status(
+        soc_ifc: &SocIfc,
+        doe: &DeobfuscationEngine,
+        persistent_data: &PersistentData,
+    ) -> ProvisioningStatus {
+        let mut status = ProvisioningStatus::empty();
+        status.set(
+            ProvisioningStatus::FUSE_WRITE_DONE,
+            soc_ifc.fuse_write_done(),
+        );
+        status.set(ProvisioningStatus::UDS_PROGRAMMED, doe.uds_flow_done());
+        status.set(
+            ProvisioningStatus::FIELD_ENTROPY_PROGRAMMED,
+            doe.fe_flow_done(),
+        );
+        status.set(
+            ProvisioningStatus::DOE_SECRETS_CLEARED,
+            doe.secrets_cleared(),
+        );
+        status.set(
+            ProvisioningStatus::IDEVID_CSR_READY,
+            !persistent_data.idevid_csr.is_unprovisioned(),
+        );
+        status.set(
+            ProvisioningStatus::READY_FOR_FW,
+            soc_ifc.flow_status_ready_for_firmware(),
+        );
+        status
+    }
+
+    /// Load the manifest and the vendor intermediate key table
     ///
     /// # Returns
     ///
     /// * `Manifest` - Caliptra Image Bundle Manifest
+    /// * `ImageManifestLayout` - Location of the manifest structures in the image
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn load_manifest(
         persistent_data: &mut PersistentDataAccessor,
         txn: &mut MailboxRecvTxn,
-    ) -> CaliptraResult<ImageManifest> {
-        let manifest = &mut persistent_data.get_mut().manifest1;
-        txn.copy_request(manifest.as_mut_bytes())?;
+    ) -> CaliptraResult<(ImageManifest, ImageManifestLayout)> {
+        let persistent_data = persistent_data.get_mut();
+        let image = txn
+            .raw_mailbox_contents()
+            .get(..txn.dlen() as usize)
+            .ok_or(CaliptraError::FW_PROC_INVALID_IMAGE_SIZE)?;
+        let layout = persistent_data
+            .manifest1
+            .parse(&mut persistent_data.vendor_intermediate_keys, image)?;
+
+        // The FMC follows the manifest in the mailbox FIFO
+        txn.drop_words(layout.size as usize / 4)?;
         report_boot_status(FwProcessorManifestLoadComplete.into());
-        Ok(*manifest)
+        Ok((persistent_data.manifest1, layout))
     }
 
     /// Verify the image
@@ -366,6 +469,8 @@ impl FirmwareProcessor {
     fn verify_image(
         venv: &mut FirmwareImageVerificationEnv,
         manifest: &ImageManifest,
+        vendor_intermediate_keys: &ImageVendorIntermediateKeyTable,
+        manifest_layout: ImageManifestLayout,
         img_bundle_sz: u32,
     ) -> CaliptraResult<ImageVerificationInfo> {
         #[cfg(feature = "fake-rom")]
@@ -376,6 +481,8 @@ impl FirmwareProcessor {
             data_vault: venv.data_vault,
             ecc384: venv.ecc384,
             image: venv.image,
+            manifest,
+            manifest_layout,
         };
 
         // Random delay for CFI glitch protection.
@@ -384,14 +491,20 @@ impl FirmwareProcessor {
         CfiCounter::delay();
         CfiCounter::delay();
 
-        let mut verifier = ImageVerifier::new(venv);
+        let mut verifier = ImageVerifier::new(venv)
+            .with_manifest_layout(manifest_layout)
+            .with_vendor_intermediate_keys(vendor_intermediate_keys);
         let info = verifier.verify(manifest, img_bundle_sz, ResetReason::ColdReset)?;
 
-        cprintln!(
+        log_info!(
             "[fwproc] Img verified w/ Vendor ECC Key Idx {}",
             info.vendor_ecc_pub_key_idx,
         );
+        if info.break_glass {
+            log_warn!("[fwproc] Break-glass image, attestation disabled");
+        }
         report_boot_status(FwProcessorImageVerificationComplete.into());
+        boot_checkpoint(BootCheckpoint::RomFwVerified);
         Ok(info)
     }
 
@@ -486,6 +599,15 @@ impl FirmwareProcessor {
             )?;
         }
 
+        // Log VendorIntermediateEccKeyIndex
+        if let Some(vendor_intermediate_ecc_key_idx) = log_info.vendor_intermediate_ecc_key_idx {
+            log_fuse_data(
+                log,
+                FuseLogEntryId::VendorIntermediateEccKeyIndex,
+                vendor_intermediate_ecc_key_idx.as_bytes(),
+            )?;
+        }
+
         Ok(())
     }
 
@@ -500,31 +622,39 @@ impl FirmwareProcessor {
     #[inline(always)]
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn load_image(manifest: &ImageManifest, txn: &mut MailboxRecvTxn) -> CaliptraResult<()> {
-        cprintln!(
+        log_info!(
             "[fwproc] Load FMC at address 0x{:08x} len {}",
             manifest.fmc.load_addr,
             manifest.fmc.size
         );
 
-        let fmc_dest = unsafe {
-            let addr = (manifest.fmc.load_addr) as *mut u32;
-            core::slice::from_raw_parts_mut(addr, manifest.fmc.size as usize / 4)
-        };
+        load_toc_entry(&manifest.fmc, txn)?;
 
-        txn.copy_request(fmc_dest.as_mut_bytes())?;
+        // Runtime segments are stored back to back following the FMC
+        for segment in manifest.runtime_segments() {
+            log_info!(
+                "[fwproc] Load Runtime at address 0x{:08x} len {}",
+                segment.load_addr,
+                segment.size
+            );
 
-        cprintln!(
-            "[fwproc] Load Runtime at address 0x{:08x} len {}",
-            manifest.runtime.load_addr,
-            manifest.runtime.size
-        );
+            load_toc_entry(segment, txn)?;
+        }
 
-        let runtime_dest = unsafe {
-            let addr = (manifest.runtime.load_addr) as *mut u32;
-            core::slice::from_raw_parts_mut(addr, manifest.runtime.size as usize / 4)
-        };
+        report_boot_status(FwProcessorLoadImageComplete.into());
+        Ok(())
+    }
 
-        txn.copy_request(runtime_dest.as_mut_bytes())?;
+    /// Drain the image, already loaded during verification, from the mailbox
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - Manifest
+    /// * `txn`      - Mailbox Receive Transaction
+    #[inline(always)]
+    fn skip_image(manifest: &ImageManifest, txn: &mut MailboxRecvTxn) -> CaliptraResult<()> {
+        let len = manifest.fmc.size + manifest.runtime_size();
+        txn.drop_words(len as usize / 4)?;
 
         report_boot_status(FwProcessorLoadImageComplete.into());
         Ok(())
@@ -542,39 +672,34 @@ impl FirmwareProcessor {
         info: &ImageVerificationInfo,
         persistent_data: &PersistentDataAccessor,
     ) {
-        data_vault.write_cold_reset_entry48(ColdResetEntry48::FmcTci, &info.fmc.digest.into());
+        data_vault.write::<dv::FmcTci>(&info.fmc.digest.into());
 
-        data_vault.write_cold_reset_entry4(ColdResetEntry4::FmcSvn, info.fmc.svn);
+        data_vault.write::<dv::FmcSvn>(&info.fmc.svn);
 
-        data_vault.write_cold_reset_entry4(ColdResetEntry4::FmcEntryPoint, info.fmc.entry_point);
+        data_vault.write::<dv::FmcEntryPoint>(&info.fmc.entry_point);
 
-        data_vault.write_cold_reset_entry48(
-            ColdResetEntry48::OwnerPubKeyHash,
-            &info.owner_pub_keys_digest.into(),
-        );
+        data_vault.write::<dv::OwnerPubKeyHash>(&info.owner_pub_keys_digest.into());
 
-        data_vault.write_cold_reset_entry4(
-            ColdResetEntry4::EccVendorPubKeyIndex,
-            info.vendor_ecc_pub_key_idx,
-        );
+        data_vault.write::<dv::EccVendorPubKeyIndex>(&info.vendor_ecc_pub_key_idx);
 
         // If LMS is not enabled, write the max value to the data vault
         // to indicate the index is invalid.
-        data_vault.write_cold_reset_entry4(
-            ColdResetEntry4::LmsVendorPubKeyIndex,
-            info.vendor_lms_pub_key_idx.unwrap_or(u32::MAX),
-        );
+        data_vault
+            .write::<dv::LmsVendorPubKeyIndex>(&info.vendor_lms_pub_key_idx.unwrap_or(u32::MAX));
 
-        data_vault.write_warm_reset_entry48(WarmResetEntry48::RtTci, &info.runtime.digest.into());
+        data_vault.write::<dv::RtTci>(&info.runtime.digest.into());
 
-        data_vault.write_warm_reset_entry4(WarmResetEntry4::RtSvn, info.runtime.svn);
+        data_vault.write::<dv::NextOwnerPubKeyHash>(&info.next_owner_pub_keys_digest.into());
 
-        data_vault.write_warm_reset_entry4(WarmResetEntry4::RtEntryPoint, info.runtime.entry_point);
+        // No owner key rotation has been committed since cold reset.
+        data_vault.write::<dv::CommittedOwnerPubKeyHash>(&Array4x12::default());
 
-        data_vault.write_warm_reset_entry4(
-            WarmResetEntry4::ManifestAddr,
-            &persistent_data.get().manifest1 as *const _ as u32,
-        );
+        data_vault.write::<dv::RtSvn>(&info.runtime.svn);
+
+        data_vault.write::<dv::RtEntryPoint>(&info.runtime.entry_point);
+
+        data_vault
+            .write::<dv::ManifestAddr>(&(&persistent_data.get().manifest1 as *const _ as u32));
         report_boot_status(FwProcessorPopulateDataVaultComplete.into());
     }
 
@@ -731,7 +856,7 @@ impl FirmwareProcessor {
             metadata: stash_measurement.metadata,
             context: zerocopy::transmute!(stash_measurement.context),
             svn: stash_measurement.svn,
-            reserved0: [0u8; 4],
+            manifest_id: 0,
         };
 
         fht.meas_log_index += 1;
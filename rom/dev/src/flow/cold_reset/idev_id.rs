@@ -16,13 +16,13 @@ Abstract:
 use super::crypto::*;
 use super::dice::*;
 use super::x509::*;
-use crate::cprintln;
 use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
 use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_cfi_lib::{cfi_assert, cfi_assert_bool, cfi_launder};
 use caliptra_common::keyids::{KEY_ID_FE, KEY_ID_IDEVID_PRIV_KEY, KEY_ID_ROM_FMC_CDI, KEY_ID_UDS};
 use caliptra_common::RomBootStatus::*;
+use caliptra_common::{log_info, log_trace};
 use caliptra_drivers::MAX_CSR_SIZE;
 use caliptra_drivers::*;
 use caliptra_x509::*;
@@ -46,10 +46,10 @@ impl InitDevIdLayer {
     /// * `DiceOutput` - DICE layer output
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn derive(env: &mut RomEnv) -> CaliptraResult<DiceOutput> {
-        cprintln!("[idev] ++");
-        cprintln!("[idev] CDI.KEYID = {}", KEY_ID_ROM_FMC_CDI as u8);
-        cprintln!("[idev] SUBJECT.KEYID = {}", KEY_ID_IDEVID_PRIV_KEY as u8);
-        cprintln!("[idev] UDS.KEYID = {}", KEY_ID_UDS as u8);
+        log_info!("[idev] ++");
+        log_trace!("[idev] CDI.KEYID = {}", KEY_ID_ROM_FMC_CDI as u8);
+        log_trace!("[idev] SUBJECT.KEYID = {}", KEY_ID_IDEVID_PRIV_KEY as u8);
+        log_trace!("[idev] UDS.KEYID = {}", KEY_ID_UDS as u8);
 
         // If CSR is not requested, indicate to the SOC that it can start
         // uploading the firmware image to the mailbox.
@@ -99,7 +99,7 @@ impl InitDevIdLayer {
         // Write IDevID public key to FHT
         env.persistent_data.get_mut().fht.idev_dice_pub_key = output.subj_key_pair.pub_key;
 
-        cprintln!("[idev] --");
+        log_info!("[idev] --");
         report_boot_status(IDevIdDerivationComplete.into());
 
         // Return the DICE Layer Output
@@ -157,9 +157,9 @@ impl InitDevIdLayer {
     /// * `cdi` - Key Slot to store the generated CDI
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn derive_cdi(env: &mut RomEnv, uds: KeyId, cdi: KeyId) -> CaliptraResult<()> {
-        Crypto::hmac384_kdf(env, uds, b"idevid_cdi", None, cdi)?;
+        Crypto::kdf(env, uds, KDF_LABEL_IDEVID_CDI, None, cdi)?;
 
-        cprintln!("[idev] Erasing UDS.KEYID = {}", uds as u8);
+        log_trace!("[idev] Erasing UDS.KEYID = {}", uds as u8);
         env.key_vault.erase_key(uds)?;
         report_boot_status(IDevIdCdiDerivationComplete.into());
         Ok(())
@@ -182,7 +182,7 @@ impl InitDevIdLayer {
         cdi: KeyId,
         priv_key: KeyId,
     ) -> CaliptraResult<Ecc384KeyPair> {
-        let result = Crypto::ecc384_key_gen(env, cdi, b"idevid_keygen", priv_key);
+        let result = Crypto::ecc384_key_gen(env, cdi, KDF_LABEL_IDEVID_KEYGEN, priv_key);
         if cfi_launder(result.is_ok()) {
             cfi_assert!(result.is_ok());
             report_boot_status(IDevIdKeyPairDerivationComplete.into());
@@ -211,7 +211,7 @@ impl InitDevIdLayer {
             return Ok(());
         }
 
-        cprintln!("[idev] CSR upload begun");
+        log_info!("[idev] CSR upload begun");
 
         // Generate the CSR
         Self::make_csr(env, output)
@@ -241,7 +241,7 @@ impl InitDevIdLayer {
         // Generate the `To Be Signed` portion of the CSR
         let tbs = InitDevIdCsrTbs::new(&params);
 
-        cprintln!(
+        log_trace!(
             "[idev] Sign CSR w/ SUBJECT.KEYID = {}",
             key_pair.priv_key as u8
         );
@@ -253,13 +253,13 @@ impl InitDevIdLayer {
 
         let _pub_x: [u8; 48] = key_pair.pub_key.x.into();
         let _pub_y: [u8; 48] = key_pair.pub_key.y.into();
-        cprintln!("[idev] PUB.X = {}", HexBytes(&_pub_x));
-        cprintln!("[idev] PUB.Y = {}", HexBytes(&_pub_y));
+        log_trace!("[idev] PUB.X = {}", HexBytes(&_pub_x));
+        log_trace!("[idev] PUB.Y = {}", HexBytes(&_pub_y));
 
         let _sig_r: [u8; 48] = (&sig.r).into();
         let _sig_s: [u8; 48] = (&sig.s).into();
-        cprintln!("[idev] SIG.R = {}", HexBytes(&_sig_r));
-        cprintln!("[idev] SIG.S = {}", HexBytes(&_sig_s));
+        log_trace!("[idev] SIG.R = {}", HexBytes(&_sig_r));
+        log_trace!("[idev] SIG.S = {}", HexBytes(&_sig_s));
 
         // Build the CSR with `To Be Signed` & `Signature`
         let mut csr_buf = [0; MAX_CSR_SIZE];
@@ -276,7 +276,7 @@ impl InitDevIdLayer {
             return Err(CaliptraError::ROM_IDEVID_CSR_OVERFLOW);
         }
 
-        cprintln!("[idev] CSR = {}", HexBytes(&csr_buf[..csr_len]));
+        log_trace!("[idev] CSR = {}", HexBytes(&csr_buf[..csr_len]));
         report_boot_status(IDevIdMakeCsrComplete.into());
 
         let dev_id_csr = IdevIdCsr::new(&csr_buf, csr_len)?;
@@ -320,7 +320,7 @@ impl InitDevIdLayer {
                 // Release access to the mailbox
                 txn.complete()?;
 
-                cprintln!("[idev] CSR uploaded");
+                log_info!("[idev] CSR uploaded");
                 report_boot_status(IDevIdSendCsrComplete.into());
 
                 // exit the loop
@@ -15,8 +15,8 @@ Abstract:
 
 use super::crypto::*;
 use super::dice::*;
+use super::fw_processor::FwProcInfo;
 use super::x509::*;
-use crate::cprintln;
 use crate::flow::cold_reset::{copy_tbs, TbsType};
 use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
@@ -24,6 +24,7 @@ use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_cfi_lib::{cfi_assert, cfi_assert_bool, cfi_launder};
 use caliptra_common::keyids::{KEY_ID_FE, KEY_ID_LDEVID_PRIV_KEY, KEY_ID_ROM_FMC_CDI};
 use caliptra_common::RomBootStatus::*;
+use caliptra_common::{log_info, log_trace};
 use caliptra_drivers::*;
 use caliptra_x509::*;
 use zeroize::Zeroize;
@@ -45,14 +46,14 @@ impl LocalDevIdLayer {
     /// * `DiceOutput` - key pair, subject identifier serial number, subject key identifier
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn derive(env: &mut RomEnv, input: &DiceInput) -> CaliptraResult<DiceOutput> {
-        cprintln!("[ldev] ++");
-        cprintln!("[ldev] CDI.KEYID = {}", KEY_ID_ROM_FMC_CDI as u8);
-        cprintln!("[ldev] SUBJECT.KEYID = {}", KEY_ID_LDEVID_PRIV_KEY as u8);
-        cprintln!(
+        log_info!("[ldev] ++");
+        log_trace!("[ldev] CDI.KEYID = {}", KEY_ID_ROM_FMC_CDI as u8);
+        log_trace!("[ldev] SUBJECT.KEYID = {}", KEY_ID_LDEVID_PRIV_KEY as u8);
+        log_trace!(
             "[ldev] AUTHORITY.KEYID = {}",
             input.auth_key_pair.priv_key as u8
         );
-        cprintln!("[ldev] FE.KEYID = {}", KEY_ID_FE as u8);
+        log_trace!("[ldev] FE.KEYID = {}", KEY_ID_FE as u8);
 
         // The measurement for this layer is generated by previous layer
         // (Initial Device ID DICE Layer).
@@ -80,10 +81,7 @@ impl LocalDevIdLayer {
             subj_key_id,
         };
 
-        // Generate Local Device ID Certificate
-        Self::generate_cert_sig(env, input, &output)?;
-
-        cprintln!("[ldev] --");
+        log_info!("[ldev] --");
         report_boot_status(LDevIdDerivationComplete.into());
 
         Ok(output)
@@ -101,7 +99,7 @@ impl LocalDevIdLayer {
         Crypto::hmac384_mac(env, cdi, &b"ldevid_cdi".into(), cdi)?;
         Crypto::hmac384_mac(env, cdi, &KeyReadArgs::new(fe).into(), cdi)?;
 
-        cprintln!("[ldev] Erasing FE.KEYID = {}", fe as u8);
+        log_trace!("[ldev] Erasing FE.KEYID = {}", fe as u8);
         env.key_vault.erase_key(fe)?;
         report_boot_status(LDevIdCdiDerivationComplete.into());
         Ok(())
@@ -124,7 +122,7 @@ impl LocalDevIdLayer {
         cdi: KeyId,
         priv_key: KeyId,
     ) -> CaliptraResult<Ecc384KeyPair> {
-        let result = Crypto::ecc384_key_gen(env, cdi, b"ldevid_keygen", priv_key);
+        let result = Crypto::ecc384_key_gen(env, cdi, KDF_LABEL_LDEVID_KEYGEN, priv_key);
         if cfi_launder(result.is_ok()) {
             cfi_assert!(result.is_ok());
             report_boot_status(LDevIdKeyPairDerivationComplete.into());
@@ -136,15 +134,21 @@ impl LocalDevIdLayer {
 
     /// Generate Local Device ID Certificate Signature
     ///
+    /// This is deferred until the firmware image has been validated so that
+    /// the certificate validity window from the image manifest can be used.
+    ///
     /// # Arguments
     ///
-    /// * `env`    - ROM Environment
-    /// * `input`  - DICE Input
-    /// * `output` - DICE Output
-    fn generate_cert_sig(
+    /// * `env`          - ROM Environment
+    /// * `input`        - DICE Input
+    /// * `output`       - DICE Output
+    /// * `fw_proc_info` - Firmware Processor Information
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn generate_cert_sig(
         env: &mut RomEnv,
         input: &DiceInput,
         output: &DiceOutput,
+        fw_proc_info: &FwProcInfo,
     ) -> CaliptraResult<()> {
         let auth_priv_key = input.auth_key_pair.priv_key;
         let auth_pub_key = &input.auth_key_pair.pub_key;
@@ -162,15 +166,15 @@ impl LocalDevIdLayer {
             authority_key_id: input.auth_key_id,
             serial_number,
             public_key: &pub_key.to_der(),
-            not_before: &NotBefore::default().value,
-            not_after: &NotAfter::default().value,
+            not_before: &fw_proc_info.cert_valid_not_before.value,
+            not_after: &fw_proc_info.cert_valid_not_after.value,
         };
 
         // Generate the `To Be Signed` portion of the CSR
         let tbs = LocalDevIdCertTbs::new(&params);
 
         // Sign the `To Be Signed` portion
-        cprintln!(
+        log_trace!(
             "[ldev] Signing Cert w/ AUTHORITY.KEYID = {}",
             auth_priv_key as u8
         );
@@ -185,13 +189,13 @@ impl LocalDevIdLayer {
 
         let _pub_x: [u8; 48] = (&pub_key.x).into();
         let _pub_y: [u8; 48] = (&pub_key.y).into();
-        cprintln!("[ldev] PUB.X = {}", HexBytes(&_pub_x));
-        cprintln!("[ldev] PUB.Y = {}", HexBytes(&_pub_y));
+        log_trace!("[ldev] PUB.X = {}", HexBytes(&_pub_x));
+        log_trace!("[ldev] PUB.Y = {}", HexBytes(&_pub_y));
 
         let _sig_r: [u8; 48] = (&sig.r).into();
         let _sig_s: [u8; 48] = (&sig.s).into();
-        cprintln!("[ldev] SIG.R = {}", HexBytes(&_sig_r));
-        cprintln!("[ldev] SIG.S = {}", HexBytes(&_sig_s));
+        log_trace!("[ldev] SIG.R = {}", HexBytes(&_sig_r));
+        log_trace!("[ldev] SIG.S = {}", HexBytes(&_sig_s));
 
         // Lock the Local Device ID cert signature in data vault until
         // cold reset
@@ -26,8 +26,9 @@ use crate::flow::cold_reset::fmc_alias::FmcAliasLayer;
 use crate::flow::cold_reset::fw_processor::FirmwareProcessor;
 use crate::flow::cold_reset::idev_id::InitDevIdLayer;
 use crate::flow::cold_reset::ldev_id::LocalDevIdLayer;
-use crate::{cprintln, rom_env::RomEnv};
+use crate::rom_env::RomEnv;
 use caliptra_cfi_derive::{cfi_impl_fn, cfi_mod_fn};
+use caliptra_common::log_info;
 use caliptra_common::RomBootStatus::*;
 use caliptra_drivers::*;
 use zeroize::Zeroize;
@@ -48,14 +49,14 @@ impl ColdResetFlow {
     #[inline(never)]
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn run(env: &mut RomEnv) -> CaliptraResult<()> {
-        cprintln!("[cold-reset] ++");
+        log_info!("[cold-reset] ++");
         report_boot_status(ColdResetStarted.into());
 
         // Indicate that Cold-Reset flow has started.
         // This is used by the next Warm-Reset flow to confirm that the Cold-Reset was successful.
         // Success status is set at the end of the flow.
         env.data_vault
-            .write_cold_reset_entry4(ColdResetEntry4::RomColdBootStatus, ColdResetStarted.into());
+            .write::<dv::RomColdBootStatus>(&ColdResetStarted.into());
 
         // Initialize FHT
         fht::initialize_fht(env);
@@ -65,14 +66,23 @@ impl ColdResetFlow {
         let ldevid_layer_input = dice_input_from_output(&idevid_layer_output);
 
         // Execute LDEVID layer
-        let result = LocalDevIdLayer::derive(env, &ldevid_layer_input);
-        idevid_layer_output.zeroize();
-        let mut ldevid_layer_output = result?;
+        let mut ldevid_layer_output = LocalDevIdLayer::derive(env, &ldevid_layer_input)?;
         let fmc_layer_input = dice_input_from_output(&ldevid_layer_output);
 
         // Download and validate firmware.
         let mut fw_proc_info = FirmwareProcessor::process(env)?;
 
+        // Generate the LDEVID certificate using the validity window from the
+        // firmware manifest.
+        let result = LocalDevIdLayer::generate_cert_sig(
+            env,
+            &ldevid_layer_input,
+            &ldevid_layer_output,
+            &fw_proc_info,
+        );
+        idevid_layer_output.zeroize();
+        result?;
+
         // Execute FMCALIAS layer
         let result = FmcAliasLayer::derive(env, &fmc_layer_input, &fw_proc_info);
         ldevid_layer_output.zeroize();
@@ -81,14 +91,12 @@ impl ColdResetFlow {
 
         // Indicate Cold-Reset successful completion.
         // This is used by the Warm-Reset flow to confirm that the Cold-Reset was successful.
-        env.data_vault.write_lock_cold_reset_entry4(
-            ColdResetEntry4::RomColdBootStatus,
-            ColdResetComplete.into(),
-        );
+        env.data_vault
+            .write_lock::<dv::RomColdBootStatus>(&ColdResetComplete.into());
 
         report_boot_status(ColdResetComplete.into());
 
-        cprintln!("[cold-reset] --");
+        log_info!("[cold-reset] --");
 
         Ok(())
     }
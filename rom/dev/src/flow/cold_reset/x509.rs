@@ -12,8 +12,8 @@ Abstract:
 
 --*/
 use super::crypto::Crypto;
-use crate::cprintln;
 use crate::rom_env::RomEnv;
+use caliptra_common::log_trace;
 use caliptra_drivers::*;
 
 /// X509 API
@@ -66,24 +66,24 @@ impl X509 {
 
         let digest: [u8; 20] = match env.soc_ifc.fuse_bank().idev_id_x509_key_id_algo() {
             X509KeyIdAlgo::Sha1 => {
-                cprintln!("[idev] Sha1 KeyId Algorithm");
+                log_trace!("[idev] Sha1 KeyId Algorithm");
                 let digest = Crypto::sha1_digest(env, &data);
                 okref(&digest)?.into()
             }
             X509KeyIdAlgo::Sha256 => {
-                cprintln!("[idev] Sha256 KeyId Algorithm");
+                log_trace!("[idev] Sha256 KeyId Algorithm");
                 let digest = Crypto::sha256_digest(env, &data);
                 let digest: [u8; 32] = okref(&digest)?.into();
                 digest[..20].try_into().unwrap()
             }
             X509KeyIdAlgo::Sha384 => {
-                cprintln!("[idev] Sha384 KeyId Algorithm");
+                log_trace!("[idev] Sha384 KeyId Algorithm");
                 let digest = Crypto::sha384_digest(env, &data);
                 let digest: [u8; 48] = okref(&digest)?.into();
                 digest[..20].try_into().unwrap()
             }
             X509KeyIdAlgo::Fuse => {
-                cprintln!("[idev] Fuse KeyId");
+                log_trace!("[idev] Fuse KeyId");
                 env.soc_ifc.fuse_bank().subject_key_id()
             }
         };
@@ -20,13 +20,14 @@ compile_error!("This file should NEVER be included except for the fake-rom featu
 mod fw_processor;
 
 use crate::fht;
+use crate::flow::reset_state::ResetState;
 use crate::flow::update_reset;
 use crate::flow::warm_reset;
 use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
+use caliptra_common::log_info;
 use caliptra_common::FirmwareHandoffTable;
 use caliptra_common::RomBootStatus::*;
-use caliptra_drivers::cprintln;
 use caliptra_drivers::Lifecycle;
 use caliptra_drivers::LmsResult;
 use caliptra_drivers::VendorPubKeyRevocation;
@@ -134,11 +135,10 @@ impl FakeRomFlow {
     /// * `env` - ROM Environment
     #[inline(never)]
     pub fn run(env: &mut RomEnv) -> CaliptraResult<()> {
-        let reset_reason = env.soc_ifc.reset_reason();
-        match reset_reason {
+        match ResetState::new(env)? {
             // Cold Reset Flow
-            ResetReason::ColdReset => {
-                cprintln!("[fake-rom-cold-reset] ++");
+            ResetState::Cold => {
+                log_info!("[fake-rom-cold-reset] ++");
                 report_boot_status(ColdResetStarted.into());
 
                 // Zeroize the key vault in the fake ROM flow
@@ -166,20 +166,17 @@ impl FakeRomFlow {
                 // FMC Alias Cert
                 copy_canned_fmc_alias_cert(env)?;
 
-                cprintln!("[fake-rom-cold-reset] --");
+                log_info!("[fake-rom-cold-reset] --");
                 report_boot_status(ColdResetComplete.into());
 
                 Ok(())
             }
 
             // Warm Reset Flow
-            ResetReason::WarmReset => warm_reset::WarmResetFlow::run(env),
+            ResetState::Warm => warm_reset::WarmResetFlow::run(env),
 
             // Update Reset Flow
-            ResetReason::UpdateReset => update_reset::UpdateResetFlow::run(env),
-
-            // Unknown/Spurious Reset Flow
-            ResetReason::Unknown => Err(CaliptraError::ROM_UNKNOWN_RESET_FLOW),
+            ResetState::Update => update_reset::UpdateResetFlow::run(env),
         }
     }
 }
@@ -194,7 +191,12 @@ pub fn copy_canned_ldev_cert(env: &mut RomEnv) -> CaliptraResult<()> {
     // Copy TBS to DCCM
     let tbs = &FAKE_LDEV_TBS;
     env.persistent_data.get_mut().fht.ldevid_tbs_size = u16::try_from(tbs.len()).unwrap();
-    let Some(dst) = env.persistent_data.get_mut().ldevid_tbs.get_mut(..tbs.len()) else {
+    let Some(dst) = env
+        .persistent_data
+        .get_mut()
+        .ldevid_tbs
+        .get_mut(..tbs.len())
+    else {
         return Err(CaliptraError::ROM_GLOBAL_UNSUPPORTED_LDEVID_TBS_SIZE);
     };
     dst.copy_from_slice(tbs);
@@ -212,7 +214,12 @@ pub fn copy_canned_fmc_alias_cert(env: &mut RomEnv) -> CaliptraResult<()> {
     // Copy TBS to DCCM
     let tbs = &FAKE_FMC_ALIAS_TBS;
     env.persistent_data.get_mut().fht.fmcalias_tbs_size = u16::try_from(tbs.len()).unwrap();
-    let Some(dst) = env.persistent_data.get_mut().fmcalias_tbs.get_mut(..tbs.len()) else {
+    let Some(dst) = env
+        .persistent_data
+        .get_mut()
+        .fmcalias_tbs
+        .get_mut(..tbs.len())
+    else {
         return Err(CaliptraError::ROM_GLOBAL_UNSUPPORTED_FMCALIAS_TBS_SIZE);
     };
     dst.copy_from_slice(tbs);
@@ -227,6 +234,32 @@ pub(crate) struct FakeRomImageVerificationEnv<'a, 'b> {
     pub(crate) data_vault: &'a mut DataVault,
     pub(crate) ecc384: &'a mut Ecc384,
     pub image: &'b [u8],
+    pub(crate) manifest: &'a ImageManifest,
+    pub(crate) manifest_layout: ImageManifestLayout,
+}
+
+impl<'a, 'b> FakeRomImageVerificationEnv<'a, 'b> {
+    /// Digest the manifest declares (or the fuses expect) for the region
+    /// starting at `offset`. Used in place of the SHA accelerator when
+    /// SHA mocking is enabled.
+    fn declared_digest(&self, offset: u32) -> ImageDigest {
+        let layout = &self.manifest_layout;
+        if offset == layout.vendor_pub_keys_range().start {
+            self.soc_ifc.fuse_bank().vendor_pub_key_hash().into()
+        } else if offset == layout.owner_pub_key_range().start {
+            self.soc_ifc.fuse_bank().owner_pub_key_hash().into()
+        } else if offset == layout.toc_offset {
+            self.manifest.header.toc_digest
+        } else if offset == self.manifest.fmc.offset {
+            self.manifest.fmc.digest
+        } else if offset == self.manifest.runtime.offset {
+            self.manifest.runtime.digest
+        } else {
+            // Only signed regions are left, and signature checks are mocked
+            // whenever SHA is.
+            ImageDigest::default()
+        }
+    }
 }
 
 impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
@@ -239,6 +272,9 @@ impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
             .ok_or(err)?
             .get(..len as usize)
             .ok_or(err)?;
+        if self.soc_ifc.sha_mock_in_fake_mode() && !self.soc_ifc.verify_in_fake_mode() {
+            return Ok(self.declared_digest(offset));
+        }
         Ok(self.sha384.digest(data)?.0)
     }
 
@@ -332,6 +368,16 @@ impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
         self.data_vault.owner_pk_hash().into()
     }
 
+    /// Get the owner public key digest committed by a key rotation in the dv
+    fn committed_owner_pub_key_digest_dv(&self) -> ImageDigest {
+        self.data_vault.committed_owner_pk_hash().into()
+    }
+
+    /// Get the next owner public key digest saved in the dv by the running image
+    fn next_owner_pub_key_digest_dv(&self) -> ImageDigest {
+        self.data_vault.next_owner_pk_hash().into()
+    }
+
     // Get the fmc digest from the data vault on cold boot
     fn get_fmc_digest_dv(&self) -> ImageDigest {
         self.data_vault.fmc_tci().into()
@@ -0,0 +1,56 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    image_loader.rs
+
+Abstract:
+
+    File contains the code to load firmware images from the mailbox to ICCM.
+
+--*/
+
+use caliptra_cfi_derive::cfi_mod_fn;
+use caliptra_common::log_info;
+use caliptra_drivers::{IccmSlice, MailboxRecvTxn};
+use caliptra_error::{CaliptraError, CaliptraResult};
+use caliptra_image_types::{lzss, ImageTocEntry};
+use zerocopy::IntoBytes;
+
+/// Load the image described by a TOC entry to its load address.
+///
+/// The image must be the next part of the mailbox contents. Compressed
+/// images are decompressed from the mailbox SRAM and skipped in the FIFO.
+/// The destination must lie entirely within ICCM.
+///
+/// # Arguments
+///
+/// * `entry` - TOC entry, verified by the image verifier
+/// * `txn`   - Mailbox Receive Transaction
+#[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
+pub fn load_toc_entry(entry: &ImageTocEntry, txn: &mut MailboxRecvTxn) -> CaliptraResult<()> {
+    if !entry.is_compressed() {
+        let dest = IccmSlice::<u32>::new(entry.load_addr, entry.size as usize / 4)?;
+        let dest = unsafe { dest.as_mut_slice() };
+        return txn.copy_request(dest.as_mut_bytes());
+    }
+
+    log_info!(
+        "[fwproc] Decompress {} bytes to {}",
+        entry.size,
+        entry.uncompressed_size
+    );
+
+    let range = entry.image_range()?;
+    let src = txn
+        .raw_mailbox_contents()
+        .get(range.start as usize..range.end as usize)
+        .ok_or(CaliptraError::FW_PROC_IMAGE_DECOMPRESSION_FAILURE)?;
+    let dest = IccmSlice::<u8>::new(entry.load_addr, entry.uncompressed_size as usize)?;
+    let dest = unsafe { dest.as_mut_slice() };
+    lzss::decompress(src, dest)?;
+
+    txn.drop_words(entry.size as usize / 4)
+}
@@ -15,15 +15,18 @@ Abstract:
 mod cold_reset;
 #[cfg(feature = "fake-rom")]
 mod fake;
+mod image_loader;
+mod reset_state;
 mod update_reset;
 mod warm_reset;
 
-use crate::cprintln;
-use crate::{handle_fatal_error, rom_env::RomEnv};
+use crate::{handle_fatal_error, personality::PERSONALITY, rom_env::RomEnv};
 use caliptra_cfi_derive::cfi_mod_fn;
 use caliptra_cfi_lib::cfi_assert_eq;
+use caliptra_common::log_error;
 use caliptra_drivers::{CaliptraResult, ResetReason};
 use caliptra_error::CaliptraError;
+use reset_state::ResetState;
 
 /// Execute ROM Flows based on reset reason
 ///
@@ -32,33 +35,25 @@ use caliptra_error::CaliptraError;
 /// * `env` - ROM Environment
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
 pub fn run(env: &mut RomEnv) -> CaliptraResult<()> {
-    let reset_reason = env.soc_ifc.reset_reason();
-
-    if cfg!(not(feature = "fake-rom")) {
-        match reset_reason {
+    if !PERSONALITY.fake_flow {
+        match ResetState::new(env)? {
             // Cold Reset Flow
-            ResetReason::ColdReset => {
+            ResetState::Cold => {
                 cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::ColdReset);
                 cold_reset::ColdResetFlow::run(env)
             }
 
             // Warm Reset Flow
-            ResetReason::WarmReset => {
+            ResetState::Warm => {
                 cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::WarmReset);
                 warm_reset::WarmResetFlow::run(env)
             }
 
             // Update Reset Flow
-            ResetReason::UpdateReset => {
+            ResetState::Update => {
                 cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::UpdateReset);
                 update_reset::UpdateResetFlow::run(env)
             }
-
-            // Unknown/Spurious Reset Flow
-            ResetReason::Unknown => {
-                cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::Unknown);
-                Err(CaliptraError::ROM_UNKNOWN_RESET_FLOW)
-            }
         }
     } else {
         let _result: CaliptraResult<()> = Err(CaliptraError::ROM_GLOBAL_PANIC);
@@ -66,7 +61,7 @@ pub fn run(env: &mut RomEnv) -> CaliptraResult<()> {
         if (env.soc_ifc.lifecycle() == caliptra_drivers::Lifecycle::Production)
             && !(env.soc_ifc.prod_en_in_fake_mode())
         {
-            cprintln!("Fake ROM in Prod disabled");
+            log_error!("Fake ROM in Prod disabled");
             handle_fatal_error(CaliptraError::ROM_GLOBAL_FAKE_ROM_IN_PRODUCTION.into());
         }
 
@@ -0,0 +1,155 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    reset_state.rs
+
+Abstract:
+
+    File contains the state machine that selects the ROM flow for a reset
+    and validates the data vault state the previous boot left behind.
+
+--*/
+
+use crate::rom_env::RomEnv;
+use caliptra_cfi_derive::cfi_impl_fn;
+use caliptra_cfi_lib::{cfi_assert, cfi_assert_bool, cfi_assert_eq, cfi_assert_ne, cfi_launder};
+use caliptra_common::log_warn;
+use caliptra_common::RomBootStatus::*;
+use caliptra_drivers::{ColdResetEntry4, ColdResetEntry48, ResetReason};
+use caliptra_error::{CaliptraError, CaliptraResult};
+
+/// Cold reset entries that the previous cold boot locked, and the error
+/// reported if one of them is found unlocked on a warm reset
+const STICKY_ENTRIES48: [(ColdResetEntry48, CaliptraError); 2] = [
+    (
+        ColdResetEntry48::FmcTci,
+        CaliptraError::ROM_WARM_RESET_FMC_TCI_NOT_LOCKED,
+    ),
+    (
+        ColdResetEntry48::OwnerPubKeyHash,
+        CaliptraError::ROM_WARM_RESET_OWNER_PUB_KEY_HASH_NOT_LOCKED,
+    ),
+];
+
+const STICKY_ENTRIES4: [(ColdResetEntry4, CaliptraError); 5] = [
+    (
+        ColdResetEntry4::FmcSvn,
+        CaliptraError::ROM_WARM_RESET_FMC_SVN_NOT_LOCKED,
+    ),
+    (
+        ColdResetEntry4::FmcEntryPoint,
+        CaliptraError::ROM_WARM_RESET_FMC_ENTRY_POINT_NOT_LOCKED,
+    ),
+    (
+        ColdResetEntry4::EccVendorPubKeyIndex,
+        CaliptraError::ROM_WARM_RESET_ECC_VENDOR_PUB_KEY_INDEX_NOT_LOCKED,
+    ),
+    (
+        ColdResetEntry4::LmsVendorPubKeyIndex,
+        CaliptraError::ROM_WARM_RESET_LMS_VENDOR_PUB_KEY_INDEX_NOT_LOCKED,
+    ),
+    (
+        ColdResetEntry4::RomColdBootStatus,
+        CaliptraError::ROM_WARM_RESET_COLD_BOOT_STATUS_NOT_LOCKED,
+    ),
+];
+
+/// ROM flow selected for a reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetState {
+    /// Power-on reset; the data vault is empty
+    Cold,
+
+    /// Warm reset after a completed cold boot; the cold reset entries are
+    /// still populated and locked
+    Warm,
+
+    /// Firmware update requested by the runtime
+    Update,
+}
+
+impl ResetState {
+    /// Select the flow for the current reset
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - ROM Environment
+    ///
+    /// # Returns
+    ///
+    /// * `ResetState` - Flow to run, or the reason the data vault left by
+    ///   the previous boot can not be trusted
+    #[inline(never)]
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn new(env: &RomEnv) -> CaliptraResult<Self> {
+        match env.soc_ifc.reset_reason() {
+            ResetReason::ColdReset => {
+                cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::ColdReset);
+                Ok(Self::Cold)
+            }
+            ResetReason::WarmReset => {
+                cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::WarmReset);
+                Self::validate_warm_reset(env)?;
+                Ok(Self::Warm)
+            }
+            ResetReason::UpdateReset => {
+                cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::UpdateReset);
+                Ok(Self::Update)
+            }
+            ResetReason::Unknown => {
+                cfi_assert_eq(env.soc_ifc.reset_reason(), ResetReason::Unknown);
+                Err(CaliptraError::ROM_UNKNOWN_RESET_FLOW)
+            }
+        }
+    }
+
+    /// Check that the previous boot completed and left every cold reset
+    /// entry locked
+    fn validate_warm_reset(env: &RomEnv) -> CaliptraResult<()> {
+        // Check if previous Cold-Reset was successful.
+        if cfi_launder(env.data_vault.rom_cold_boot_status()) != ColdResetComplete.into() {
+            log_warn!("[warm-reset] Prev Cold-Reset failed");
+            return Err(CaliptraError::ROM_WARM_RESET_UNSUCCESSFUL_PREVIOUS_COLD_RESET);
+        } else {
+            cfi_assert_eq(
+                env.data_vault.rom_cold_boot_status(),
+                ColdResetComplete.into(),
+            );
+        }
+
+        // Check if previous Update-Reset, if any,  was successful.
+        if cfi_launder(env.data_vault.rom_update_reset_status()) == UpdateResetStarted.into() {
+            log_warn!("[warm-reset] Prev Update Reset failed");
+            return Err(CaliptraError::ROM_WARM_RESET_UNSUCCESSFUL_PREVIOUS_UPDATE_RESET);
+        } else {
+            cfi_assert_ne(
+                env.data_vault.rom_update_reset_status(),
+                UpdateResetStarted.into(),
+            );
+        }
+
+        // A reset between reporting cold boot completion and locking the
+        // data vault leaves entries that could be rewritten by the firmware.
+        for (entry, err) in STICKY_ENTRIES48 {
+            if !cfi_launder(env.data_vault.is_cold_reset_entry48_locked(entry)) {
+                log_warn!("[warm-reset] DV entry48 {} not locked", entry as u32);
+                return Err(err);
+            } else {
+                cfi_assert!(env.data_vault.is_cold_reset_entry48_locked(entry));
+            }
+        }
+        for (entry, err) in STICKY_ENTRIES4 {
+            if !cfi_launder(env.data_vault.is_cold_reset_entry4_locked(entry)) {
+                log_warn!("[warm-reset] DV entry4 {} not locked", entry as u32);
+                return Err(err);
+            } else {
+                cfi_assert!(env.data_vault.is_cold_reset_entry4_locked(entry));
+            }
+        }
+
+        Ok(())
+    }
+}
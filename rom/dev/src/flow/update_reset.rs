@@ -13,19 +13,26 @@ Abstract:
 --*/
 #[cfg(feature = "fake-rom")]
 use crate::flow::fake::FakeRomImageVerificationEnv;
-use crate::{cprintln, pcr, rom_env::RomEnv};
+use crate::flow::image_loader::load_toc_entry;
+use crate::{pcr, rom_env::RomEnv};
+use caliptra_common::boot_profile::boot_checkpoint;
 use caliptra_common::verifier::FirmwareImageVerificationEnv;
+use caliptra_common::{log_error, log_info, log_warn};
+use caliptra_drivers::BootCheckpoint;
 
 use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_common::mailbox_api::CommandId;
 use caliptra_common::RomBootStatus::*;
+use caliptra_drivers::memory_layout::ICCM_RANGE;
 use caliptra_drivers::report_fw_error_non_fatal;
 use caliptra_drivers::{
-    okref, report_boot_status, MailboxRecvTxn, ResetReason, WarmResetEntry4, WarmResetEntry48,
+    dv, okref, report_boot_status, restart_wdt1, Array4x12, MailboxRecvTxn, ResetReason,
 };
 use caliptra_drivers::{DataVault, PersistentData};
 use caliptra_error::{CaliptraError, CaliptraResult};
-use caliptra_image_types::ImageManifest;
+use caliptra_image_types::{
+    ImageDigest, ImageManifest, ImageManifestLayout, ImageVendorIntermediateKeyTable,
+};
 use caliptra_image_verify::{ImageVerificationInfo, ImageVerifier};
 use zerocopy::IntoBytes;
 
@@ -40,29 +47,45 @@ impl UpdateResetFlow {
     /// * `env` - ROM Environment
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn run(env: &mut RomEnv) -> CaliptraResult<()> {
-        cprintln!("[update-reset] ++");
+        log_info!("[update-reset] ++");
         report_boot_status(UpdateResetStarted.into());
 
         // Indicate that Update-Reset flow has started.
         // This is used by the next Warm-Reset flow to confirm that the Update-Reset was successful.
         // Success status is set at the end of the flow.
-        env.data_vault.write_warm_reset_entry4(
-            WarmResetEntry4::RomUpdateResetStatus,
-            UpdateResetStarted.into(),
-        );
+        env.data_vault
+            .write::<dv::RomUpdateResetStatus>(&UpdateResetStarted.into());
+
+        // Committed before the new image is verified, so that it must be
+        // signed by the committed owner key.
+        Self::commit_owner_key_rotation(env)?;
 
         let Some(mut recv_txn) = env.mbox.try_start_recv_txn() else {
-            cprintln!("Failed To Get Mailbox Txn");
+            log_error!("Failed To Get Mailbox Txn");
             return Err(CaliptraError::ROM_UPDATE_RESET_FLOW_MAILBOX_ACCESS_FAILURE);
         };
 
-        let mut process_txn = || -> CaliptraResult<()> {
-            if recv_txn.cmd() != CommandId::FIRMWARE_LOAD.into() {
-                cprintln!("Invalid command 0x{:08x} recv", recv_txn.cmd());
+        // Like manifest2, the key table of the new image is staged until the
+        // image is verified.
+        let mut vendor_intermediate_keys = ImageVendorIntermediateKeyTable::default();
+
+        let mut process_txn = || -> CaliptraResult<bool> {
+            // The runtime may have staged the image in the mailbox while
+            // executing another command, e.g. a PLDM firmware update
+            let staged_cmd = env.persistent_data.get().fw_update_journal.staged_cmd();
+            if recv_txn.cmd() != CommandId::FIRMWARE_LOAD.into()
+                && Some(recv_txn.cmd()) != staged_cmd
+            {
+                log_error!("Invalid command 0x{:08x} recv", recv_txn.cmd());
                 return Err(CaliptraError::ROM_UPDATE_RESET_FLOW_INVALID_FIRMWARE_COMMAND);
             }
 
-            Self::load_manifest(env.persistent_data.get_mut(), &mut recv_txn)?;
+            boot_checkpoint(BootCheckpoint::RomFwReceived);
+            let manifest_layout = Self::load_manifest(
+                env.persistent_data.get_mut(),
+                &mut vendor_intermediate_keys,
+                &mut recv_txn,
+            )?;
             report_boot_status(UpdateResetLoadManifestComplete.into());
 
             let mut venv = FirmwareImageVerificationEnv {
@@ -73,14 +96,32 @@ impl UpdateResetFlow {
                 data_vault: &mut env.data_vault,
                 pcr_bank: &mut env.pcr_bank,
                 image: recv_txn.raw_mailbox_contents(),
+                // The running image must survive a failed update, so it is
+                // only overwritten after verification.
+                load_sha_acc: None,
+                wdt_pet: Some(restart_wdt1),
             };
 
             let info = {
                 let manifest = &env.persistent_data.get().manifest2;
-                Self::verify_image(&mut venv, manifest, recv_txn.dlen())
+                Self::verify_image(
+                    &mut venv,
+                    manifest,
+                    &vendor_intermediate_keys,
+                    manifest_layout,
+                    recv_txn.dlen(),
+                )
             };
             let info = okref(&info)?;
             report_boot_status(UpdateResetImageVerificationComplete.into());
+            boot_checkpoint(BootCheckpoint::RomFwVerified);
+
+            // Checked before the data vault is repopulated with the new TCI
+            let unchanged = Self::runtime_unchanged(
+                venv.data_vault,
+                &env.persistent_data.get().manifest1,
+                &env.persistent_data.get().manifest2,
+            );
 
             // Populate data vault
             Self::populate_data_vault(venv.data_vault, info);
@@ -89,44 +130,60 @@ impl UpdateResetFlow {
             pcr::extend_pcrs(&mut venv, info, &mut env.persistent_data)?;
             report_boot_status(UpdateResetExtendPcrComplete.into());
 
-            cprintln!(
+            log_info!(
                 "[update-reset] Img verified w/ Vendor ECC Key Index {}",
                 info.vendor_ecc_pub_key_idx
             );
 
-            let manifest = &env.persistent_data.get().manifest2;
-            Self::load_image(manifest, &mut recv_txn)?;
-            Ok(())
+            if unchanged {
+                log_info!("[update-reset] Runtime unchanged, skipping load");
+                recv_txn.complete(true)?;
+            } else {
+                let manifest = &env.persistent_data.get().manifest2;
+                Self::load_image(manifest, &mut recv_txn)?;
+            }
+            Ok(unchanged)
+        };
+        let unchanged = match process_txn() {
+            Ok(unchanged) => unchanged,
+            Err(e) => {
+                // To prevent a race condition where the SoC sees the mailbox
+                // transaction fail and reads the non-fatal error register before it
+                // gets populated, report the non-fatal error code now.
+                report_fw_error_non_fatal(e.into());
+                return Err(e);
+            }
         };
-        if let Err(e) = process_txn() {
-            // To prevent a race condition where the SoC sees the mailbox
-            // transaction fail and reads the non-fatal error register before it
-            // gets populated, report the non-fatal error code now.
-            report_fw_error_non_fatal(e.into());
-            return Err(e);
-        }
 
         // Drop the transaction and release the Mailbox lock after the image
         // has been successfully verified and loaded in memory
         drop(recv_txn);
-        report_boot_status(UpdateResetLoadImageComplete.into());
+        if !unchanged {
+            report_boot_status(UpdateResetLoadImageComplete.into());
+        }
 
         let persistent_data = env.persistent_data.get_mut();
-        cprintln!("[update-reset] Copying MAN_2 To MAN_1");
+        log_info!("[update-reset] Copying MAN_2 To MAN_1");
         persistent_data.manifest1 = persistent_data.manifest2;
+        persistent_data.vendor_intermediate_keys = vendor_intermediate_keys;
         report_boot_status(UpdateResetOverwriteManifestComplete.into());
 
         // Set RT version. FMC does not change.
         env.soc_ifc
             .set_rt_fw_rev_id(persistent_data.manifest1.runtime.version);
 
-        env.data_vault.write_lock_warm_reset_entry4(
-            WarmResetEntry4::RomUpdateResetStatus,
-            UpdateResetComplete.into(),
-        );
+        // A no-op update is reported separately so that the SoC, and FMC,
+        // can tell that the running firmware was kept.
+        let status = if unchanged {
+            UpdateResetNoOpComplete
+        } else {
+            UpdateResetComplete
+        };
+        env.data_vault
+            .write_lock::<dv::RomUpdateResetStatus>(&status.into());
 
-        cprintln!("[update-reset Success] --");
-        report_boot_status(UpdateResetComplete.into());
+        log_info!("[update-reset Success] --");
+        report_boot_status(status.into());
 
         Ok(())
     }
@@ -142,6 +199,8 @@ impl UpdateResetFlow {
     fn verify_image(
         env: &mut FirmwareImageVerificationEnv,
         manifest: &ImageManifest,
+        vendor_intermediate_keys: &ImageVendorIntermediateKeyTable,
+        manifest_layout: ImageManifestLayout,
         img_bundle_sz: u32,
     ) -> CaliptraResult<ImageVerificationInfo> {
         #[cfg(feature = "fake-rom")]
@@ -152,9 +211,13 @@ impl UpdateResetFlow {
             data_vault: env.data_vault,
             ecc384: env.ecc384,
             image: env.image,
+            manifest,
+            manifest_layout,
         };
 
-        let mut verifier = ImageVerifier::new(env);
+        let mut verifier = ImageVerifier::new(env)
+            .with_manifest_layout(manifest_layout)
+            .with_vendor_intermediate_keys(vendor_intermediate_keys);
 
         let info = verifier.verify(manifest, img_bundle_sz, ResetReason::UpdateReset)?;
 
@@ -170,21 +233,18 @@ impl UpdateResetFlow {
     /// * `txn`      - Mailbox Receive Transaction
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn load_image(manifest: &ImageManifest, txn: &mut MailboxRecvTxn) -> CaliptraResult<()> {
-        cprintln!(
-            "[update-reset] Loading Runtime at addr 0x{:08x} len {}",
-            manifest.runtime.load_addr,
-            manifest.runtime.size
-        );
-
         // Throw away the FMC portion of the image
         txn.drop_words(manifest.fmc.size as usize / 4)?;
 
-        let runtime_dest = unsafe {
-            let addr = (manifest.runtime.load_addr) as *mut u32;
-            core::slice::from_raw_parts_mut(addr, manifest.runtime.size as usize / 4)
-        };
+        for segment in manifest.runtime_segments() {
+            log_info!(
+                "[update-reset] Loading Runtime at addr 0x{:08x} len {}",
+                segment.load_addr,
+                segment.size
+            );
 
-        txn.copy_request(runtime_dest.as_mut_bytes())?;
+            load_toc_entry(segment, txn)?;
+        }
 
         //Call the complete here to reset the execute bit
         txn.complete(true)?;
@@ -192,17 +252,96 @@ impl UpdateResetFlow {
         Ok(())
     }
 
-    /// Load the manifest
+    /// Load the manifest and the vendor intermediate key table
     ///
     /// # Returns
     ///
-    /// * `Manifest` - Caliptra Image Bundle Manifest
+    /// * `ImageManifestLayout` - Location of the manifest structures in the image
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn load_manifest(
         persistent_data: &mut PersistentData,
+        vendor_intermediate_keys: &mut ImageVendorIntermediateKeyTable,
         txn: &mut MailboxRecvTxn,
-    ) -> CaliptraResult<()> {
-        txn.copy_request(persistent_data.manifest2.as_mut_bytes())?;
+    ) -> CaliptraResult<ImageManifestLayout> {
+        let image = txn
+            .raw_mailbox_contents()
+            .get(..txn.dlen() as usize)
+            .ok_or(CaliptraError::ROM_UPDATE_RESET_FLOW_MANIFEST_READ_FAILURE)?;
+        let layout = persistent_data
+            .manifest2
+            .parse(vendor_intermediate_keys, image)?;
+
+        // The FMC follows the manifest in the mailbox FIFO
+        txn.drop_words(layout.size as usize / 4)?;
+        Ok(layout)
+    }
+
+    /// Check if the runtime of the new image is already loaded
+    ///
+    /// # Arguments
+    ///
+    /// * `data_vault` - Data Vault holding the TCI of the running runtime
+    /// * `running`    - Manifest of the running image
+    /// * `new`        - Manifest of the verified new image
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the runtime digest and segment layout are unchanged
+    fn runtime_unchanged(
+        data_vault: &DataVault,
+        running: &ImageManifest,
+        new: &ImageManifest,
+    ) -> bool {
+        if data_vault.rt_tci() != Array4x12::from(new.runtime.digest) {
+            return false;
+        }
+
+        // Only code in the locked ICCM is known to be intact; anything loaded
+        // elsewhere may have been modified by the running firmware.
+        running.runtime_segment_count() == new.runtime_segment_count()
+            && running
+                .runtime_segments()
+                .zip(new.runtime_segments())
+                .all(|(running, new)| {
+                    running.load_addr == new.load_addr
+                        && running.compression == new.compression
+                        && running.load_size() == new.load_size()
+                        && ICCM_RANGE.contains(&new.load_addr)
+                        && new
+                            .load_addr
+                            .checked_add(new.load_size())
+                            .is_some_and(|end| end <= ICCM_RANGE.end)
+                })
+    }
+
+    /// Commit the owner key rotation requested by the runtime, if any
+    ///
+    /// The runtime requests the commit once the running image is signed by
+    /// the next owner key. The request is only held in persistent data, so
+    /// the checks are repeated before the data vault is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - ROM Environment
+    fn commit_owner_key_rotation(env: &mut RomEnv) -> CaliptraResult<()> {
+        let persistent_data = env.persistent_data.get_mut();
+        let request = Array4x12::from(persistent_data.owner_key_commit_request);
+        if request == Array4x12::default() {
+            return Ok(());
+        }
+        persistent_data.owner_key_commit_request = ImageDigest::default();
+
+        let next_digest = env.data_vault.next_owner_pk_hash();
+        let running_digest = env
+            .sha384
+            .digest(persistent_data.manifest1.preamble.owner_pub_keys.as_bytes())?;
+        if request != next_digest || running_digest != next_digest {
+            log_warn!("[update-reset] Ignoring invalid owner key rotation commit");
+            return Ok(());
+        }
+
+        log_info!("[update-reset] Committing owner key rotation");
+        env.data_vault.commit_owner_pk_hash(&request);
         Ok(())
     }
 
@@ -213,11 +352,17 @@ impl UpdateResetFlow {
     /// * `env`  - ROM Environment
     /// * `info` - Image Verification Info
     fn populate_data_vault(data_vault: &mut DataVault, info: &ImageVerificationInfo) {
-        data_vault.write_warm_reset_entry48(WarmResetEntry48::RtTci, &info.runtime.digest.into());
+        data_vault.write::<dv::RtTci>(&info.runtime.digest.into());
+
+        // Keep a pending owner key rotation alive across images that do not
+        // name a new owner key, so that it can still be committed.
+        if info.next_owner_pub_keys_digest != ImageDigest::default() {
+            data_vault.write::<dv::NextOwnerPubKeyHash>(&info.next_owner_pub_keys_digest.into());
+        }
 
-        data_vault.write_warm_reset_entry4(WarmResetEntry4::RtSvn, info.runtime.svn);
+        data_vault.write::<dv::RtSvn>(&info.runtime.svn);
 
-        data_vault.write_warm_reset_entry4(WarmResetEntry4::RtEntryPoint, info.runtime.entry_point);
+        data_vault.write::<dv::RtEntryPoint>(&info.runtime.entry_point);
 
         report_boot_status(UpdateResetPopulateDataVaultComplete.into());
     }
@@ -11,11 +11,10 @@ Abstract:
     File contains the implementation of warm reset flow.
 
 --*/
-use crate::{cprintln, rom_env::RomEnv};
+use crate::rom_env::RomEnv;
 use caliptra_cfi_derive::cfi_impl_fn;
-use caliptra_cfi_lib::{cfi_assert_eq, cfi_assert_ne, cfi_launder};
-use caliptra_common::RomBootStatus::*;
-use caliptra_error::{CaliptraError, CaliptraResult};
+use caliptra_common::log_info;
+use caliptra_error::CaliptraResult;
 
 /// Warm Reset Flow
 pub struct WarmResetFlow {}
@@ -28,32 +27,13 @@ impl WarmResetFlow {
     /// * `env` - ROM Environment
     #[inline(never)]
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
-    pub fn run(env: &mut RomEnv) -> CaliptraResult<()> {
-        cprintln!("[warm-reset] ++");
-
-        // Check if previous Cold-Reset was successful.
-        if cfi_launder(env.data_vault.rom_cold_boot_status()) != ColdResetComplete.into() {
-            cprintln!("[warm-reset] Prev Cold-Reset failed");
-            return Err(CaliptraError::ROM_WARM_RESET_UNSUCCESSFUL_PREVIOUS_COLD_RESET);
-        } else {
-            cfi_assert_eq(
-                env.data_vault.rom_cold_boot_status(),
-                ColdResetComplete.into(),
-            );
-        }
-
-        // Check if previous Update-Reset, if any,  was successful.
-        if cfi_launder(env.data_vault.rom_update_reset_status()) == UpdateResetStarted.into() {
-            cprintln!("[warm-reset] Prev Update Reset failed");
-            return Err(CaliptraError::ROM_WARM_RESET_UNSUCCESSFUL_PREVIOUS_UPDATE_RESET);
-        } else {
-            cfi_assert_ne(
-                env.data_vault.rom_update_reset_status(),
-                UpdateResetStarted.into(),
-            );
-        }
-
-        cprintln!("[warm-reset] --");
+    pub fn run(_env: &mut RomEnv) -> CaliptraResult<()> {
+        log_info!("[warm-reset] ++");
+
+        // The data vault left by the previous boot is validated when the
+        // reset state is selected, so there is nothing left to check.
+
+        log_info!("[warm-reset] --");
 
         Ok(())
     }
@@ -14,11 +14,11 @@ Abstract:
 
 use caliptra_cfi_derive::cfi_mod_fn;
 use caliptra_common::pcr::{PCR_ID_FMC_CURRENT, PCR_ID_FMC_JOURNEY, PCR_ID_STASH_MEASUREMENT};
-use caliptra_drivers::{
-    ColdResetEntry4, ColdResetEntry48, ResetReason, WarmResetEntry4, WarmResetEntry48,
-};
+use caliptra_common::{log_info, log_warn};
+use caliptra_drivers::{dv, ResetReason};
 
-use crate::{cprintln, rom_env::RomEnv};
+use crate::personality::PERSONALITY;
+use crate::rom_env::RomEnv;
 
 /// Lock registers
 ///
@@ -28,7 +28,7 @@ use crate::{cprintln, rom_env::RomEnv};
 /// * `reset_reason` - Reset reason
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
 pub fn lock_registers(env: &mut RomEnv, reset_reason: ResetReason) {
-    cprintln!("[state] Locking Datavault");
+    log_info!("[state] Locking Datavault");
     if reset_reason == ResetReason::ColdReset {
         lock_cold_reset_reg(env);
         lock_common_reg_set(env);
@@ -38,13 +38,17 @@ pub fn lock_registers(env: &mut RomEnv, reset_reason: ResetReason) {
         lock_common_reg_set(env);
     }
 
-    cprintln!("[state] Locking PCR0, PCR1 and PCR31");
+    log_info!("[state] Locking PCR0, PCR1 and PCR31");
     env.pcr_bank.set_pcr_lock(PCR_ID_FMC_CURRENT);
     env.pcr_bank.set_pcr_lock(PCR_ID_FMC_JOURNEY);
     env.pcr_bank.set_pcr_lock(PCR_ID_STASH_MEASUREMENT);
 
-    cprintln!("[state] Locking ICCM");
-    env.soc_ifc.set_iccm_lock(true);
+    if !PERSONALITY.iccm_unlocked {
+        log_info!("[state] Locking ICCM");
+        env.soc_ifc.set_iccm_lock(true);
+    } else {
+        log_warn!("[state] Leaving ICCM unlocked");
+    }
 }
 
 /// Lock registers on a cold reset
@@ -55,32 +59,25 @@ pub fn lock_registers(env: &mut RomEnv, reset_reason: ResetReason) {
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
 fn lock_cold_reset_reg(env: &mut RomEnv) {
     // Lock the FMC TCI in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry48(ColdResetEntry48::FmcTci);
+    env.data_vault.lock::<dv::FmcTci>();
 
     // Lock the FMC SVN  in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry4(ColdResetEntry4::FmcSvn);
+    env.data_vault.lock::<dv::FmcSvn>();
 
     // Lock the FMC entry point in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry4(ColdResetEntry4::FmcEntryPoint);
+    env.data_vault.lock::<dv::FmcEntryPoint>();
 
     // Lock the Owner Public Key Hash in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry48(ColdResetEntry48::OwnerPubKeyHash);
+    env.data_vault.lock::<dv::OwnerPubKeyHash>();
 
     // Lock the Ecc Vendor Public Key Index in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry4(ColdResetEntry4::EccVendorPubKeyIndex);
+    env.data_vault.lock::<dv::EccVendorPubKeyIndex>();
 
     // Lock the Lms Vendor Public Key Index in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry4(ColdResetEntry4::LmsVendorPubKeyIndex);
+    env.data_vault.lock::<dv::LmsVendorPubKeyIndex>();
 
     // Lock Cold Reset Status register in data vault until next cold reset
-    env.data_vault
-        .lock_cold_reset_entry4(ColdResetEntry4::RomColdBootStatus);
+    env.data_vault.lock::<dv::RomColdBootStatus>();
 }
 
 /// Lock all common registers across all reset types
@@ -91,24 +88,25 @@ fn lock_cold_reset_reg(env: &mut RomEnv) {
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
 fn lock_common_reg_set(env: &mut RomEnv) {
     // Lock the Runtime TCI in data vault until next reset
-    env.data_vault
-        .lock_warm_reset_entry48(WarmResetEntry48::RtTci);
+    env.data_vault.lock::<dv::RtTci>();
+
+    // Lock the Next Owner Public Key Hash in data vault until next reset
+    env.data_vault.lock::<dv::NextOwnerPubKeyHash>();
+
+    // Lock the Committed Owner Public Key Hash in data vault until next reset
+    env.data_vault.lock::<dv::CommittedOwnerPubKeyHash>();
 
     // Lock the Runtime SVN in data vault until next reset
-    env.data_vault
-        .lock_warm_reset_entry4(WarmResetEntry4::RtSvn);
+    env.data_vault.lock::<dv::RtSvn>();
 
     // Do not lock Runtime minimum SVN; FMC will manage this.
 
     // Lock the Runtime entry point in data vault until next reset
-    env.data_vault
-        .lock_warm_reset_entry4(WarmResetEntry4::RtEntryPoint);
+    env.data_vault.lock::<dv::RtEntryPoint>();
 
     // Lock the Manifest addr in data vault until next reset
-    env.data_vault
-        .lock_warm_reset_entry4(WarmResetEntry4::ManifestAddr);
+    env.data_vault.lock::<dv::ManifestAddr>();
 
     // Lock the Update Reset status in data vault until next reset
-    env.data_vault
-        .lock_warm_reset_entry4(WarmResetEntry4::RomUpdateResetStatus);
+    env.data_vault.lock::<dv::RomUpdateResetStatus>();
 }
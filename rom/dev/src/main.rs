@@ -18,20 +18,24 @@ Abstract:
 
 use crate::{lock::lock_registers, print::HexBytes};
 use caliptra_cfi_lib::{cfi_assert_eq, CfiCounter};
+use caliptra_common::boot_profile::boot_checkpoint;
 use caliptra_common::RomBootStatus;
 use caliptra_common::RomBootStatus::{KatComplete, KatStarted};
+use caliptra_common::{log_error, log_info, log_trace};
+use caliptra_drivers::BootCheckpoint;
 use caliptra_kat::*;
 use caliptra_registers::soc_ifc::SocIfcReg;
 use core::hint::black_box;
 
 use caliptra_drivers::{
-    cprintln, report_boot_status, report_fw_error_fatal, report_fw_error_non_fatal, CaliptraError,
-    Ecc384, Hmac384, KeyVault, Mailbox, ResetReason, Sha256, Sha2_512_384Acc, Sha384,
-    ShaAccLockState, SocIfc, Trng,
+    report_boot_status, report_fw_error_fatal, report_fw_error_non_fatal, CaliptraError, Ecc384,
+    Hmac384, KeyVault, Mailbox, ResetReason, Sha256, Sha2_512_384Acc, Sha384, ShaAccLockState,
+    SocIfc, Trng,
 };
 use caliptra_error::CaliptraResult;
 use caliptra_image_types::RomInfo;
 use caliptra_kat::KatsEnv;
+use personality::PERSONALITY;
 use rom_env::RomEnv;
 use zeroize::Zeroize;
 
@@ -47,6 +51,7 @@ mod flow;
 mod fuse;
 mod lock;
 mod pcr;
+mod personality;
 mod rom_env;
 mod wdt;
 
@@ -65,7 +70,11 @@ extern "C" {
 
 #[no_mangle]
 pub extern "C" fn rom_entry() -> ! {
-    cprintln!("{}", BANNER);
+    boot_checkpoint(BootCheckpoint::RomEntry);
+    if PERSONALITY.uart_prints {
+        log_info!("{}", BANNER);
+        log_info!("[state] Personality = {}", PERSONALITY.name);
+    }
 
     let mut env = match unsafe { rom_env::RomEnv::new_from_registers() } {
         Ok(env) => env,
@@ -73,13 +82,13 @@ pub extern "C" fn rom_entry() -> ! {
     };
 
     if !cfg!(feature = "no-cfi") {
-        cprintln!("[state] CFI Enabled");
+        log_info!("[state] CFI Enabled");
         let mut entropy_gen = || env.trng.generate().map(|a| a.0);
         CfiCounter::reset(&mut entropy_gen);
         CfiCounter::reset(&mut entropy_gen);
         CfiCounter::reset(&mut entropy_gen);
     } else {
-        cprintln!("[state] CFI Disabled");
+        log_info!("[state] CFI Disabled");
     }
 
     // Check if TRNG is correctly sourced as per hw config.
@@ -93,17 +102,17 @@ pub extern "C" fn rom_entry() -> ! {
         caliptra_drivers::Lifecycle::Production => "Production",
         caliptra_drivers::Lifecycle::Reserved2 => "Unknown",
     };
-    cprintln!("[state] LifecycleState = {}", _lifecyle);
+    log_info!("[state] LifecycleState = {}", _lifecyle);
 
-    if cfg!(feature = "fake-rom")
+    if !PERSONALITY.allowed_in_production
         && (env.soc_ifc.lifecycle() == caliptra_drivers::Lifecycle::Production)
         && !(env.soc_ifc.prod_en_in_fake_mode())
     {
-        cprintln!("Fake ROM in Prod lifecycle disabled");
+        log_error!("{} ROM in Prod lifecycle disabled", PERSONALITY.name);
         handle_fatal_error(CaliptraError::ROM_GLOBAL_FAKE_ROM_IN_PRODUCTION.into());
     }
 
-    cprintln!(
+    log_info!(
         "[state] DebugLocked = {}",
         if env.soc_ifc.debug_locked() {
             "Yes"
@@ -114,7 +123,7 @@ pub extern "C" fn rom_entry() -> ! {
 
     // Set the ROM version
     let rom_info = unsafe { &CALIPTRA_ROM_INFO };
-    if !cfg!(feature = "fake-rom") {
+    if !PERSONALITY.fake_flow {
         env.soc_ifc.set_rom_fw_rev_id(rom_info.version);
     } else {
         env.soc_ifc.set_rom_fw_rev_id(0xFFFF);
@@ -125,7 +134,7 @@ pub extern "C" fn rom_entry() -> ! {
 
     let reset_reason = env.soc_ifc.reset_reason();
 
-    if !cfg!(feature = "fake-rom") {
+    if !PERSONALITY.fake_flow {
         let mut kats_env = caliptra_kat::KatsEnv {
             // SHA1 Engine
             sha1: &mut env.sha1,
@@ -165,6 +174,7 @@ pub extern "C" fn rom_entry() -> ! {
         if let Err(err) = result {
             handle_fatal_error(err.into());
         }
+        boot_checkpoint(BootCheckpoint::RomKatsComplete);
     }
 
     if let Err(err) = flow::run(&mut env) {
@@ -202,7 +212,7 @@ pub extern "C" fn rom_entry() -> ! {
 fn run_fips_tests(env: &mut KatsEnv) -> CaliptraResult<()> {
     report_boot_status(KatStarted.into());
 
-    cprintln!("[kat] SHA2-256");
+    log_trace!("[kat] SHA2-256");
     Sha256Kat::default().execute(env.sha256)?;
 
     #[cfg(feature = "fips-test-hooks")]
@@ -233,10 +243,10 @@ fn rom_integrity_test(env: &mut KatsEnv, expected_digest: &[u32; 8]) -> Caliptra
 
     let n_blocks = unsafe { &CALIPTRA_ROM_INFO as *const RomInfo as usize / 64 };
     let mut digest = unsafe { env.sha256.digest_blocks_raw(rom_start, n_blocks)? };
-    cprintln!("ROM Digest: {}", HexBytes(&<[u8; 32]>::from(digest)));
+    log_info!("ROM Digest: {}", HexBytes(&<[u8; 32]>::from(digest)));
     if digest.0 != *expected_digest {
         digest.zeroize();
-        cprintln!("ROM integrity test failed");
+        log_error!("ROM integrity test failed");
         return Err(CaliptraError::ROM_INTEGRITY_FAILURE);
     }
     digest.zeroize();
@@ -252,7 +262,8 @@ fn launch_fmc(env: &mut RomEnv) -> ! {
     // Get the fmc entry point from data vault
     let entry = env.data_vault.fmc_entry_point();
 
-    cprintln!("[exit] Launching FMC @ 0x{:08X}", entry);
+    log_info!("[exit] Launching FMC @ 0x{:08X}", entry);
+    boot_checkpoint(BootCheckpoint::RomExit);
 
     // Exit ROM and jump to specified entry point
     unsafe { exit_rom(entry) }
@@ -261,7 +272,7 @@ fn launch_fmc(env: &mut RomEnv) -> ! {
 #[no_mangle]
 #[inline(never)]
 extern "C" fn exception_handler(exception: &exception::ExceptionRecord) {
-    cprintln!(
+    log_error!(
         "EXCEPTION mcause=0x{:08X} mscause=0x{:08X} mepc=0x{:08X} ra=0x{:08X}",
         exception.mcause,
         exception.mscause,
@@ -297,7 +308,7 @@ extern "C" fn nmi_handler(exception: &exception::ExceptionRecord) {
             .read(),
     );
 
-    cprintln!(
+    log_error!(
         "NMI mcause=0x{:08X} mscause=0x{:08X} mepc=0x{:08X} ra=0x{:08X} error_internal_intr_r={:08X}",
         exception.mcause,
         exception.mscause,
@@ -321,7 +332,7 @@ extern "C" fn nmi_handler(exception: &exception::ExceptionRecord) {
 
     let wdt_status = soc_ifc.regs().cptra_wdt_status().read();
     if wdt_status.t1_timeout() || wdt_status.t2_timeout() {
-        cprintln!("WDT Expired");
+        log_error!("WDT Expired");
         error = CaliptraError::ROM_GLOBAL_WDT_EXPIRED;
     }
 
@@ -332,27 +343,27 @@ extern "C" fn nmi_handler(exception: &exception::ExceptionRecord) {
 #[inline(never)]
 #[cfg(not(feature = "std"))]
 fn rom_panic(_: &core::panic::PanicInfo) -> ! {
-    cprintln!("Panic!!");
+    log_error!("Panic!!");
     panic_is_possible();
 
     handle_fatal_error(CaliptraError::ROM_GLOBAL_PANIC.into());
 }
 
 fn handle_non_fatal_error(code: u32) {
-    cprintln!("ROM Non-Fatal Error: 0x{:08X}", code);
+    log_error!("ROM Non-Fatal Error: 0x{:08X}", code);
     report_fw_error_non_fatal(code);
 }
 
 #[no_mangle]
 extern "C" fn cfi_panic_handler(code: u32) -> ! {
-    cprintln!("[ROM] CFI Panic code=0x{:08X}", code);
+    log_error!("[ROM] CFI Panic code=0x{:08X}", code);
 
     handle_fatal_error(code);
 }
 
 #[allow(clippy::empty_loop)]
 fn handle_fatal_error(code: u32) -> ! {
-    cprintln!("ROM Fatal Error: 0x{:08X}", code);
+    log_error!("ROM Fatal Error: 0x{:08X}", code);
     report_fw_error_fatal(code);
     // Populate the non-fatal error code too; if there was a
     // non-fatal error stored here before we don't want somebody
@@ -407,32 +418,36 @@ fn validate_trng_config(env: &mut RomEnv) {
     // explicit here, and necessary to prevent the compiler from inserting a ton
     // of glitch-susceptible jumps into the generated code.
 
+    // Personalities that accept the manufacturing-mode RNG select it
+    // regardless of the debug lock.
+    let debug_locked = env.soc_ifc.debug_locked() & !PERSONALITY.fake_trng;
+
     cfi_assert_eq(
         env.soc_ifc.hw_config_internal_trng()
-            & (!env.soc_ifc.mfg_flag_rng_unavailable() | env.soc_ifc.debug_locked()),
+            & (!env.soc_ifc.mfg_flag_rng_unavailable() | debug_locked),
         matches!(env.trng, Trng::Internal(_)),
     );
     cfi_assert_eq(
         !env.soc_ifc.hw_config_internal_trng()
-            & (!env.soc_ifc.mfg_flag_rng_unavailable() | env.soc_ifc.debug_locked()),
+            & (!env.soc_ifc.mfg_flag_rng_unavailable() | debug_locked),
         matches!(env.trng, Trng::External(_)),
     );
     cfi_assert_eq(
-        env.soc_ifc.mfg_flag_rng_unavailable() & !env.soc_ifc.debug_locked(),
+        env.soc_ifc.mfg_flag_rng_unavailable() & !debug_locked,
         matches!(env.trng, Trng::MfgMode()),
     );
     cfi_assert_eq(
         env.soc_ifc.hw_config_internal_trng()
-            & (!env.soc_ifc.mfg_flag_rng_unavailable() | env.soc_ifc.debug_locked()),
+            & (!env.soc_ifc.mfg_flag_rng_unavailable() | debug_locked),
         matches!(env.trng, Trng::Internal(_)),
     );
     cfi_assert_eq(
         !env.soc_ifc.hw_config_internal_trng()
-            & (!env.soc_ifc.mfg_flag_rng_unavailable() | env.soc_ifc.debug_locked()),
+            & (!env.soc_ifc.mfg_flag_rng_unavailable() | debug_locked),
         matches!(env.trng, Trng::External(_)),
     );
     cfi_assert_eq(
-        env.soc_ifc.mfg_flag_rng_unavailable() & !env.soc_ifc.debug_locked(),
+        env.soc_ifc.mfg_flag_rng_unavailable() & !debug_locked,
         matches!(env.trng, Trng::MfgMode()),
     );
 }
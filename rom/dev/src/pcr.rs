@@ -24,7 +24,7 @@ Note:
 use caliptra_cfi_derive::{cfi_impl_fn, cfi_mod_fn};
 use caliptra_common::verifier::FirmwareImageVerificationEnv;
 use caliptra_common::{
-    pcr::{PCR_ID_FMC_CURRENT, PCR_ID_FMC_JOURNEY},
+    pcr::{PCR_BREAK_GLASS_MEASUREMENT, PCR_ID_FMC_CURRENT, PCR_ID_FMC_JOURNEY},
     PcrLogEntry, PcrLogEntryId,
 };
 use caliptra_drivers::{
@@ -104,6 +104,11 @@ pub(crate) fn extend_pcrs(
         PcrLogEntryId::FmcTci,
     )?;
 
+    // Make a break-glass boot evident to verifiers
+    if info.break_glass {
+        pcr.extend(&PCR_BREAK_GLASS_MEASUREMENT, PcrLogEntryId::BreakGlass)?;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,80 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    personality.rs
+
+Abstract:
+
+    File contains the build-time personality of the ROM, which selects the
+    debug-only behaviors compiled into the image.
+
+--*/
+
+#[cfg(all(feature = "fake-rom", feature = "val-rom"))]
+compile_error!("The fake-rom and val-rom features are mutually exclusive");
+
+/// Debug-only behaviors of a ROM build
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Personality {
+    /// Name reported at boot
+    pub name: &'static str,
+
+    /// Status messages are printed to the UART
+    pub uart_prints: bool,
+
+    /// KATs and DICE derivation are skipped in favor of the fake ROM flow
+    pub fake_flow: bool,
+
+    /// ROM may run in the production lifecycle without the fake ROM
+    /// production enable bit being set
+    pub allowed_in_production: bool,
+
+    /// The manufacturing-mode RNG is accepted even when debug is locked
+    pub fake_trng: bool,
+
+    /// ICCM is left unlocked when handing off to FMC
+    pub iccm_unlocked: bool,
+}
+
+/// Personality of the ROM shipped in silicon
+pub const PRODUCTION: Personality = Personality {
+    name: "production",
+    uart_prints: cfg!(feature = "emu"),
+    fake_flow: false,
+    allowed_in_production: true,
+    fake_trng: false,
+    iccm_unlocked: false,
+};
+
+/// Personality of the fake ROM used to speed up pre-Si boot
+pub const FAKE: Personality = Personality {
+    name: "fake",
+    uart_prints: cfg!(feature = "emu"),
+    fake_flow: true,
+    allowed_in_production: false,
+    fake_trng: false,
+    iccm_unlocked: false,
+};
+
+/// Personality of the validation ROM. It runs the full boot flow but keeps
+/// the firmware inspectable and does not require a working TRNG.
+pub const VAL: Personality = Personality {
+    name: "val",
+    uart_prints: cfg!(feature = "emu"),
+    fake_flow: false,
+    allowed_in_production: false,
+    fake_trng: true,
+    iccm_unlocked: true,
+};
+
+/// Personality this ROM was built with
+pub const PERSONALITY: Personality = if cfg!(feature = "val-rom") {
+    VAL
+} else if cfg!(feature = "fake-rom") {
+    FAKE
+} else {
+    PRODUCTION
+};
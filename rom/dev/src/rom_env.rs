@@ -16,6 +16,7 @@ Abstract:
 --*/
 
 use crate::fht::FhtDataStore;
+use crate::personality::PERSONALITY;
 use caliptra_drivers::{
     DataVault, DeobfuscationEngine, Ecc384, Hmac384, KeyVault, Lms, Mailbox, PcrBank,
     PersistentDataAccessor, Sha1, Sha256, Sha2_512_384Acc, Sha384, SocIfc, Trng,
@@ -80,17 +81,25 @@ pub struct RomEnv {
 
 impl RomEnv {
     pub unsafe fn new_from_registers() -> CaliptraResult<Self> {
-        let trng = Trng::new(
-            CsrngReg::new(),
-            EntropySrcReg::new(),
-            SocIfcTrngReg::new(),
-            &SocIfcReg::new(),
-        )?;
+        let soc_ifc = SocIfc::new(SocIfcReg::new());
+
+        let trng = if PERSONALITY.fake_trng && soc_ifc.mfg_flag_rng_unavailable() {
+            Trng::MfgMode()
+        } else {
+            Trng::new(
+                CsrngReg::new(),
+                EntropySrcReg::new(),
+                SocIfcTrngReg::new(),
+                &SocIfcReg::new(),
+            )?
+        };
+        let mut sha256 = Sha256::new(Sha256Reg::new());
+        sha256.set_hw_capabilities(soc_ifc.hw_capabilities());
 
         Ok(Self {
             doe: DeobfuscationEngine::new(DoeReg::new()),
             sha1: Sha1::default(),
-            sha256: Sha256::new(Sha256Reg::new()),
+            sha256,
             sha384: Sha384::new(Sha512Reg::new()),
             sha2_512_384_acc: Sha2_512_384Acc::new(Sha512AccCsr::new()),
             hmac384: Hmac384::new(HmacReg::new()),
@@ -98,7 +107,7 @@ impl RomEnv {
             lms: Lms::default(),
             key_vault: KeyVault::new(KvReg::new()),
             data_vault: DataVault::new(DvReg::new()),
-            soc_ifc: SocIfc::new(SocIfcReg::new()),
+            soc_ifc,
             mbox: Mailbox::new(MboxCsr::new()),
             pcr_bank: PcrBank::new(PvReg::new()),
             fht_data_store: FhtDataStore::default(),
@@ -20,7 +20,7 @@ use caliptra_cfi_derive::cfi_mod_fn;
 use caliptra_common::WdtTimeout;
 use caliptra_drivers::SocIfc;
 
-use crate::cprintln;
+use caliptra_common::{log_info, log_warn};
 
 /// Start the Watchdog Timer
 /// Note: WDT is configured only if the device is in non-debug mode (i.e debug_locked = 1)
@@ -35,7 +35,7 @@ pub fn start_wdt(soc_ifc: &mut SocIfc) {
         if wdt_timeout_cycles == 0 {
             wdt_timeout_cycles = 1;
         }
-        cprintln!(
+        log_info!(
             "[state] Starting the WD Timer {} cycles",
             wdt_timeout_cycles
         );
@@ -44,6 +44,6 @@ pub fn start_wdt(soc_ifc: &mut SocIfc) {
             WdtTimeout::from(core::num::NonZeroU64::new(wdt_timeout_cycles).unwrap()),
         );
     } else {
-        cprintln!("[state] WD Timer not started. Device not locked for debugging");
+        log_warn!("[state] WD Timer not started. Device not locked for debugging");
     }
 }
@@ -15,6 +15,9 @@ use caliptra_hw_model::{
 use caliptra_hw_model::{DefaultHwModel, ModelError};
 use caliptra_image_types::ImageBundle;
 
+/// Bytes at the end of each stack that the firmware must never reach
+const STACK_GUARD_BYTES: u32 = 64;
+
 pub fn build_hw_model_and_image_bundle(
     fuses: Fuses,
     image_options: ImageOptions,
@@ -29,21 +32,24 @@ pub fn build_hw_model(fuses: Fuses) -> DefaultHwModel {
         ImageInfo::new(
             StackRange::new(ROM_STACK_ORG + ROM_STACK_SIZE, ROM_STACK_ORG),
             CodeRange::new(ROM_ORG, ROM_ORG + ROM_SIZE),
-        ),
+        )
+        .with_name("ROM"),
         ImageInfo::new(
             StackRange::new(STACK_ORG + STACK_SIZE, STACK_ORG),
             CodeRange::new(FMC_ORG, FMC_ORG + FMC_SIZE),
-        ),
+        )
+        .with_name("FMC"),
         ImageInfo::new(
             StackRange::new(STACK_ORG + STACK_SIZE, STACK_ORG),
             CodeRange::new(RUNTIME_ORG, RUNTIME_ORG + RUNTIME_SIZE),
-        ),
+        )
+        .with_name("RT"),
     ];
     caliptra_hw_model::new(
         InitParams {
             rom: &rom,
             security_state: SecurityState::from(fuses.life_cycle as u32),
-            stack_info: Some(StackInfo::new(image_info)),
+            stack_info: Some(StackInfo::new(image_info).with_guard_bytes(STACK_GUARD_BYTES)),
             ..Default::default()
         },
         BootParams {
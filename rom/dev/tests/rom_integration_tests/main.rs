@@ -14,9 +14,12 @@ mod test_idevid_derivation;
 mod test_image_validation;
 mod test_mailbox_errors;
 mod test_panic_missing;
+mod test_provisioning_status;
 mod test_rom_integrity;
 mod test_symbols;
 mod test_update_reset;
+mod test_val_rom;
+mod test_vault_events;
 mod test_version;
 mod test_warm_reset;
 mod test_wdt_activation_and_stoppage;
@@ -37,13 +37,7 @@ fn test_cold_reset_status_reporting() {
     hw.step_until_boot_status(LDevIdKeyPairDerivationComplete.into(), false);
     hw.step_until_boot_status(LDevIdSubjIdSnGenerationComplete.into(), false);
     hw.step_until_boot_status(LDevIdSubjKeyIdGenerationComplete.into(), false);
-    if cfg!(feature = "fpga_realtime") {
-        // Skip check for LDevIdCertSigGenerationComplete because it is set for too short of a time in nolog mode
-        hw.step_until_boot_status(LDevIdDerivationComplete.into(), true);
-    } else {
-        hw.step_until_boot_status(LDevIdCertSigGenerationComplete.into(), false);
-        hw.step_until_boot_status(LDevIdDerivationComplete.into(), false);
-    }
+    hw.step_until_boot_status(LDevIdDerivationComplete.into(), false);
 
     // Wait for uploading firmware.
     hw.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_fw());
@@ -80,7 +74,13 @@ fn test_cold_reset_status_reporting() {
     hw.step_until_boot_status(FwProcessorLoadImageComplete.into(), false);
     hw.step_until_boot_status(FwProcessorFirmwareDownloadTxComplete.into(), false);
     hw.step_until_boot_status(FwProcessorComplete.into(), false);
-    hw.step_until_boot_status(FmcAliasDeriveCdiComplete.into(), false);
+    if cfg!(feature = "fpga_realtime") {
+        // Skip check for LDevIdCertSigGenerationComplete because it is set for too short of a time in nolog mode
+        hw.step_until_boot_status(FmcAliasDeriveCdiComplete.into(), true);
+    } else {
+        hw.step_until_boot_status(LDevIdCertSigGenerationComplete.into(), false);
+        hw.step_until_boot_status(FmcAliasDeriveCdiComplete.into(), false);
+    }
     hw.step_until_boot_status(FmcAliasKeyPairDerivationComplete.into(), false);
     hw.step_until_boot_status(FmcAliasSubjIdSnGenerationComplete.into(), false);
     hw.step_until_boot_status(FmcAliasSubjKeyIdGenerationComplete.into(), false);
@@ -176,7 +176,7 @@ fn test_fake_rom_update_reset() {
     if cfg!(not(feature = "fpga_realtime")) {
         hw.step_until_boot_status(UpdateResetStarted.into(), true);
     }
-    hw.step_until_boot_status(UpdateResetComplete.into(), true);
+    hw.step_until_boot_status(UpdateResetNoOpComplete.into(), true);
 
     assert_eq!(hw.finish_mailbox_execute(), Ok(None));
 
@@ -237,6 +237,42 @@ fn test_image_verify() {
     );
 }
 
+#[test]
+fn test_sha_mock() {
+    const DBG_MANUF_FAKE_ROM_SHA_MOCK: u32 = 0x1 << 29; // BIT 29 turns on SHA mocking
+    let fuses = Fuses::default();
+    let rom = caliptra_builder::build_firmware_rom(&ROM_FAKE_WITH_UART).unwrap();
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            security_state: SecurityState::from(fuses.life_cycle as u32),
+            ..Default::default()
+        },
+        BootParams {
+            fuses,
+            initial_dbg_manuf_service_reg: DBG_MANUF_FAKE_ROM_SHA_MOCK,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let mut image_bundle = caliptra_builder::build_and_sign_image(
+        &FAKE_TEST_FMC_WITH_UART,
+        &APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+
+    // The declared digest is trusted, so a mismatch with the runtime is not
+    // detected.
+    image_bundle.manifest.runtime.digest = [0xdead_beef; 12];
+
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+
+    hw.step_until_boot_status(ColdResetComplete.into(), true);
+}
+
 #[test]
 fn test_fake_rom_version() {
     const FAKE_ROM_VERSION: u16 = 0xFFFF;
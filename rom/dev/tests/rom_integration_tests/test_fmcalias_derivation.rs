@@ -12,15 +12,17 @@ use caliptra_builder::{
 use caliptra_common::mailbox_api::{CommandId, MailboxReqHeader, StashMeasurementReq};
 use caliptra_common::RomBootStatus::ColdResetComplete;
 use caliptra_common::RomBootStatus::*;
+use caliptra_common::{pcr::PCR_BREAK_GLASS_MEASUREMENT, PcrLogEntry, PcrLogEntryId};
 use caliptra_common::{FirmwareHandoffTable, FuseLogEntry, FuseLogEntryId};
-use caliptra_common::{PcrLogEntry, PcrLogEntryId};
 use caliptra_drivers::pcr_log::MeasurementLogEntry;
 use caliptra_drivers::{ColdResetEntry4, PcrId, RomVerifyConfig};
 use caliptra_error::CaliptraError;
-use caliptra_hw_model::{BootParams, Fuses, HwModel, InitParams, ModelError, SecurityState};
+use caliptra_hw_model::{
+    BootParams, Fuses, HwModel, InitParams, ModelError, SecurityState, MAILBOX_TIMEOUT_CYCLES,
+};
 use caliptra_image_crypto::OsslCrypto as Crypto;
 use caliptra_image_fake_keys::{OWNER_CONFIG, VENDOR_CONFIG_KEY_1};
-use caliptra_image_gen::ImageGenerator;
+use caliptra_image_gen::{ImageGenerator, ImageGeneratorOwnerConfig};
 use caliptra_image_types::IMAGE_BYTE_SIZE;
 use caliptra_test::swap_word_bytes;
 use openssl::hash::{Hasher, MessageDigest};
@@ -69,9 +71,10 @@ fn test_firmware_gt_max_size() {
         hw.soc_mbox().datain().write(|_| i as u32);
     }
     hw.soc_mbox().execute().write(|w| w.execute(true));
-    while hw.soc_mbox().status().read().status().cmd_busy() {
-        hw.step();
-    }
+    hw.step_until_timeout(MAILBOX_TIMEOUT_CYCLES, |m| {
+        !m.soc_mbox().status().read().status().cmd_busy()
+    })
+    .unwrap();
     hw.soc_mbox().execute().write(|w| w.execute(false));
 
     assert_eq!(
@@ -402,6 +405,76 @@ fn test_pcr_log_fmc_fuse_svn() {
     );
 }
 
+#[test]
+fn test_pcr_log_break_glass() {
+    let gen = ImageGenerator::new(Crypto::default());
+    let image_bundle = helpers::build_image_bundle(ImageOptions::default());
+
+    let vendor_pubkey_digest = gen
+        .vendor_pubkey_digest(&image_bundle.manifest.preamble)
+        .unwrap();
+
+    let owner_pubkey_digest = gen
+        .owner_pubkey_digest(&image_bundle.manifest.preamble)
+        .unwrap();
+
+    let fuses = Fuses {
+        key_manifest_pk_hash: vendor_pubkey_digest,
+        owner_pk_hash: owner_pubkey_digest,
+        ..Default::default()
+    };
+    let rom = caliptra_builder::build_firmware_rom(firmware::rom_from_env()).unwrap();
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            security_state: SecurityState::from(fuses.life_cycle as u32),
+            ..Default::default()
+        },
+        BootParams {
+            fuses,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let image_options = ImageOptions {
+        owner_config: Some(ImageGeneratorOwnerConfig {
+            break_glass: true,
+            ..OWNER_CONFIG
+        }),
+        ..Default::default()
+    };
+    let image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &APP_WITH_UART,
+        image_options,
+    )
+    .unwrap();
+
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+
+    hw.step_until_boot_status(u32::from(ColdResetComplete), true);
+
+    let pcr_entry_arr = hw.mailbox_execute(0x1000_0000, &[]).unwrap().unwrap();
+
+    check_pcr_log_entry(
+        &pcr_entry_arr,
+        3,
+        PcrLogEntryId::FmcTci,
+        PCR0_AND_PCR1_EXTENDED_ID,
+        swap_word_bytes(&image_bundle.manifest.fmc.digest).as_bytes(),
+    );
+
+    check_pcr_log_entry(
+        &pcr_entry_arr,
+        4,
+        PcrLogEntryId::BreakGlass,
+        PCR0_AND_PCR1_EXTENDED_ID,
+        &PCR_BREAK_GLASS_MEASUREMENT,
+    );
+}
+
 fn hash_pcr_log_entry(entry: &PcrLogEntry, pcr: &mut [u8; 48]) {
     let mut hasher = Hasher::new(MessageDigest::sha384()).unwrap();
     hasher.update(pcr).unwrap();
@@ -537,10 +610,10 @@ fn test_pcr_log_across_update_reset() {
         assert_eq!(pcrs[offset..offset + 48], [0; 48]);
     }
 
-    // Trigger an update reset.
+    // Trigger an update reset. The runtime is unchanged, so it is kept.
     hw.upload_firmware(&image_bundle.to_bytes().unwrap())
         .unwrap();
-    hw.step_until_boot_status(UpdateResetComplete.into(), true);
+    hw.step_until_boot_status(UpdateResetNoOpComplete.into(), true);
 
     let pcr_entry_arr = hw.mailbox_execute(0x1000_0000, &[]).unwrap().unwrap();
 
@@ -603,6 +676,7 @@ fn test_fuse_log() {
         fmc_version: 0,
         app_svn: FMC_SVN,
         app_version: 0,
+        ..Default::default()
     };
     let image_bundle =
         caliptra_builder::build_and_sign_image(&TEST_FMC_WITH_UART, &APP_WITH_UART, image_options)
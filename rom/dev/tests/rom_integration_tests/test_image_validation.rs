@@ -20,11 +20,16 @@ use caliptra_hw_model::{
 use caliptra_image_crypto::OsslCrypto as Crypto;
 use caliptra_image_elf::ElfExecutable;
 use caliptra_image_fake_keys::{
-    VENDOR_CONFIG_KEY_0, VENDOR_CONFIG_KEY_1, VENDOR_CONFIG_KEY_2, VENDOR_CONFIG_KEY_3,
+    OWNER_CONFIG, VENDOR_CONFIG_KEY_0, VENDOR_CONFIG_KEY_1, VENDOR_CONFIG_KEY_2,
+    VENDOR_CONFIG_KEY_3,
+};
+use caliptra_image_gen::{
+    ImageGenerator, ImageGeneratorConfig, ImageGeneratorOwnerConfig, ImageGeneratorVendorConfig,
 };
-use caliptra_image_gen::{ImageGenerator, ImageGeneratorConfig, ImageGeneratorVendorConfig};
 use caliptra_image_types::{
-    ImageBundle, ImageManifest, VENDOR_ECC_KEY_COUNT, VENDOR_LMS_KEY_COUNT,
+    manifest_v2::{ImageManifestHeaderV2, MANIFEST_MARKER_V2},
+    ImageBundle, ImageManifest, ImageManifestFormat, ImageTocEntryCompression,
+    VENDOR_ECC_KEY_COUNT, VENDOR_LMS_KEY_COUNT,
 };
 use openssl::asn1::Asn1Integer;
 use openssl::asn1::Asn1Time;
@@ -35,7 +40,7 @@ use openssl::rsa::Rsa;
 use openssl::x509::X509Req;
 use openssl::x509::X509;
 use std::str;
-use zerocopy::IntoBytes;
+use zerocopy::{FromBytes, IntoBytes};
 
 use crate::helpers;
 
@@ -1570,6 +1575,64 @@ fn test_fmc_svn_less_than_fuse_svn() {
     );
 }
 
+fn break_glass_image_options() -> ImageOptions {
+    ImageOptions {
+        owner_config: Some(ImageGeneratorOwnerConfig {
+            break_glass: true,
+            ..OWNER_CONFIG
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_break_glass_fmc_svn_less_than_fuse_svn() {
+    let gen = ImageGenerator::new(Crypto::default());
+    let image_bundle = helpers::build_image_bundle(ImageOptions::default());
+    let vendor_pubkey_digest = gen
+        .vendor_pubkey_digest(&image_bundle.manifest.preamble)
+        .unwrap();
+    let owner_pubkey_digest = gen
+        .owner_pubkey_digest(&image_bundle.manifest.preamble)
+        .unwrap();
+
+    let fuses = caliptra_hw_model::Fuses {
+        life_cycle: DeviceLifecycle::Manufacturing,
+        anti_rollback_disable: false,
+        key_manifest_pk_hash: vendor_pubkey_digest,
+        owner_pk_hash: owner_pubkey_digest,
+        fmc_key_manifest_svn: 0b11, // fuse svn = 2
+        ..Default::default()
+    };
+
+    let image_options = ImageOptions {
+        fmc_svn: 1,
+        ..break_glass_image_options()
+    };
+
+    let (mut hw, image_bundle) = helpers::build_hw_model_and_image_bundle(fuses, image_options);
+    assert!(image_bundle.manifest.header.owner_data.is_break_glass());
+
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+
+    hw.step_until_boot_status(u32::from(ColdResetComplete), true);
+}
+
+#[test]
+fn test_break_glass_owner_pub_key_not_in_fuses() {
+    let (mut hw, image_bundle) =
+        helpers::build_hw_model_and_image_bundle(Fuses::default(), break_glass_image_options());
+
+    assert_eq!(
+        ModelError::MailboxCmdFailed(u32::from(
+            CaliptraError::IMAGE_VERIFIER_ERR_BREAK_GLASS_OWNER_PUB_KEY_NOT_IN_FUSES
+        )),
+        hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+            .unwrap_err()
+    );
+}
+
 #[test]
 fn test_toc_rt_size_zero() {
     let (mut hw, mut image_bundle) =
@@ -1618,6 +1681,88 @@ fn test_runtime_digest_mismatch() {
     );
 }
 
+#[test]
+fn test_runtime_compressed() {
+    let (mut hw, image_bundle) = helpers::build_hw_model_and_image_bundle(
+        Fuses::default(),
+        ImageOptions {
+            app_compression: ImageTocEntryCompression::Lzss,
+            ..Default::default()
+        },
+    );
+    assert!(image_bundle.manifest.runtime.is_compressed());
+
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+
+    hw.step_until_boot_status(u32::from(ColdResetComplete), true);
+}
+
+#[test]
+fn test_manifest_v2() {
+    let (mut hw, image_bundle) = helpers::build_hw_model_and_image_bundle(
+        Fuses::default(),
+        ImageOptions {
+            manifest_format: ImageManifestFormat::V2,
+            ..Default::default()
+        },
+    );
+    let image = image_bundle.to_bytes().unwrap();
+    assert_eq!(&image[..4], MANIFEST_MARKER_V2.as_bytes());
+
+    hw.upload_firmware(&image).unwrap();
+
+    hw.step_until_boot_status(u32::from(ColdResetComplete), true);
+}
+
+#[test]
+fn test_manifest_v2_format_version_unsupported() {
+    let (mut hw, image_bundle) = helpers::build_hw_model_and_image_bundle(
+        Fuses::default(),
+        ImageOptions {
+            manifest_format: ImageManifestFormat::V2,
+            ..Default::default()
+        },
+    );
+    let mut image = image_bundle.to_bytes().unwrap();
+    let mut hdr = ImageManifestHeaderV2::read_from_prefix(&image).unwrap().0;
+    hdr.format_version = 3;
+    image[..hdr.as_bytes().len()].copy_from_slice(hdr.as_bytes());
+
+    assert_eq!(
+        ModelError::MailboxCmdFailed(
+            CaliptraError::IMAGE_VERIFIER_ERR_MANIFEST_FORMAT_VERSION_UNSUPPORTED.into()
+        ),
+        hw.upload_firmware(&image).unwrap_err()
+    );
+}
+
+#[test]
+fn test_runtime_compression_invalid() {
+    let (mut hw, mut image_bundle) =
+        helpers::build_hw_model_and_image_bundle(Fuses::default(), ImageOptions::default());
+
+    image_bundle.manifest.runtime.compression = 0xDEADBEEF;
+
+    let gen = ImageGenerator::new(Crypto::default());
+
+    // Update TOC digest.
+    image_bundle.manifest.header.toc_digest = gen
+        .toc_digest(&image_bundle.manifest.fmc, &image_bundle.manifest.runtime)
+        .unwrap();
+
+    // Update Header.
+    update_header(&mut image_bundle);
+
+    assert_eq!(
+        ModelError::MailboxCmdFailed(u32::from(
+            CaliptraError::IMAGE_VERIFIER_ERR_TOC_ENTRY_COMPRESSION_INVALID
+        )),
+        hw.upload_firmware(&generate_image_bytes(&mut image_bundle))
+            .unwrap_err()
+    );
+}
+
 #[test]
 fn test_runtime_invalid_load_addr_before_iccm() {
     let (mut hw, mut image_bundle) =
@@ -1892,6 +2037,8 @@ fn cert_test_with_custom_dates() {
 
     let not_before: Asn1Time = Asn1Time::from_str("20270101000000Z").unwrap();
     let not_after: Asn1Time = Asn1Time::from_str("20280101000000Z").unwrap();
+    assert!(ldevid_cert.not_before() == not_before);
+    assert!(ldevid_cert.not_after() == not_after);
 
     // Get the fmclias cert.
     let cert = fmcalias_cert(&ldevid_cert, &output);
@@ -2020,6 +2167,7 @@ fn update_header(image_bundle: &mut ImageBundle) {
         runtime: ElfExecutable::default(),
         vendor_config: opts.vendor_config,
         owner_config: opts.owner_config,
+        manifest_format: opts.manifest_format,
     };
 
     let gen = ImageGenerator::new(Crypto::default());
@@ -0,0 +1,74 @@
+// Licensed under the Apache-2.0 license
+
+use caliptra_api::SocManager;
+use caliptra_builder::ImageOptions;
+use caliptra_common::mailbox_api::{
+    CommandId, GetProvisioningStatusResp, MailboxReqHeader, ProvisioningStatus,
+};
+use caliptra_drivers::MfgFlags;
+use caliptra_hw_model::{DefaultHwModel, Fuses, HwModel};
+use zerocopy::{FromBytes, IntoBytes};
+
+use crate::helpers;
+
+fn get_provisioning_status(hw: &mut DefaultHwModel) -> ProvisioningStatus {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::GET_PROVISIONING_STATUS),
+            &[],
+        ),
+    };
+
+    let response = hw
+        .mailbox_execute(
+            CommandId::GET_PROVISIONING_STATUS.into(),
+            payload.as_bytes(),
+        )
+        .unwrap()
+        .unwrap();
+
+    let resp = GetProvisioningStatusResp::ref_from_bytes(response.as_bytes()).unwrap();
+    assert!(caliptra_common::checksum::verify_checksum(
+        resp.hdr.chksum,
+        0x0,
+        &resp.as_bytes()[core::mem::size_of_val(&resp.hdr.chksum)..],
+    ));
+
+    ProvisioningStatus::from_bits_retain(resp.status)
+}
+
+#[test]
+fn test_get_provisioning_status() {
+    let (mut hw, _) =
+        helpers::build_hw_model_and_image_bundle(Fuses::default(), ImageOptions::default());
+    hw.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_fw());
+
+    assert_eq!(
+        get_provisioning_status(&mut hw),
+        ProvisioningStatus::FUSE_WRITE_DONE
+            | ProvisioningStatus::UDS_PROGRAMMED
+            | ProvisioningStatus::FIELD_ENTROPY_PROGRAMMED
+            | ProvisioningStatus::DOE_SECRETS_CLEARED
+            | ProvisioningStatus::READY_FOR_FW
+    );
+}
+
+#[test]
+fn test_get_provisioning_status_idevid_csr() {
+    let (mut hw, _) =
+        helpers::build_hw_model_and_image_bundle(Fuses::default(), ImageOptions::default());
+
+    let flags = MfgFlags::GENERATE_IDEVID_CSR;
+    hw.soc_ifc()
+        .cptra_dbg_manuf_service_reg()
+        .write(|_| flags.bits());
+    helpers::get_csr(&mut hw).unwrap();
+    hw.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_fw());
+
+    assert!(get_provisioning_status(&mut hw).contains(
+        ProvisioningStatus::UDS_PROGRAMMED
+            | ProvisioningStatus::FIELD_ENTROPY_PROGRAMMED
+            | ProvisioningStatus::IDEVID_CSR_READY
+            | ProvisioningStatus::READY_FOR_FW
+    ));
+}
@@ -17,8 +17,9 @@ use caliptra_common::RomBootStatus::*;
 use caliptra_drivers::WarmResetEntry4;
 use caliptra_error::CaliptraError;
 use caliptra_hw_model::{BootParams, HwModel, InitParams};
-use caliptra_image_fake_keys::VENDOR_CONFIG_KEY_0;
-use caliptra_image_gen::ImageGeneratorVendorConfig;
+use caliptra_image_crypto::OsslCrypto as Crypto;
+use caliptra_image_fake_keys::{OWNER_CONFIG, VENDOR_CONFIG_KEY_0};
+use caliptra_image_gen::{ImageGenerator, ImageGeneratorOwnerConfig, ImageGeneratorVendorConfig};
 use zerocopy::{FromBytes, IntoBytes};
 
 const TEST_FMC_CMD_RESET_FOR_UPDATE: u32 = 0x1000_0004;
@@ -48,10 +49,19 @@ fn test_update_reset_success() {
 
     hw.step_until_boot_status(ColdResetComplete.into(), true);
 
+    // Use a different runtime so that ROM loads it rather than keeping the
+    // running one
+    let updated_image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &TEST_RT_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+
     // Trigger an update reset with "new" firmware
     hw.start_mailbox_execute(
         CommandId::FIRMWARE_LOAD.into(),
-        &image_bundle.to_bytes().unwrap(),
+        &updated_image_bundle.to_bytes().unwrap(),
     )
     .unwrap();
 
@@ -238,10 +248,19 @@ fn test_update_reset_boot_status() {
 
     hw.step_until_boot_status(ColdResetComplete.into(), true);
 
+    // Use a different runtime so that ROM loads it rather than keeping the
+    // running one
+    let updated_image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &TEST_RT_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+
     // Start the firmware update process
     hw.start_mailbox_execute(
         CommandId::FIRMWARE_LOAD.into(),
-        &image_bundle.to_bytes().unwrap(),
+        &updated_image_bundle.to_bytes().unwrap(),
     )
     .unwrap();
 
@@ -341,6 +360,102 @@ fn test_update_reset_vendor_ecc_pub_key_idx_dv_mismatch() {
     );
 }
 
+#[test]
+fn test_update_reset_vendor_ecc_pub_key_idx_unpinned() {
+    let rom = caliptra_builder::build_firmware_rom(firmware::rom_from_env()).unwrap();
+    let image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+    let owner_pubkey_digest = ImageGenerator::new(Crypto::default())
+        .owner_pubkey_digest(&image_bundle.manifest.preamble)
+        .unwrap();
+
+    // Firmware with a different vendor ECC key index, unpinned by the owner
+    let image_options = ImageOptions {
+        vendor_config: ImageGeneratorVendorConfig {
+            ecc_key_idx: 2,
+            ..VENDOR_CONFIG_KEY_0
+        },
+        owner_config: Some(ImageGeneratorOwnerConfig {
+            unpin_vendor_key: true,
+            ..OWNER_CONFIG
+        }),
+        ..Default::default()
+    };
+    let image_bundle2 = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &TEST_RT_WITH_UART,
+        image_options,
+    )
+    .unwrap();
+    assert!(image_bundle2
+        .manifest
+        .header
+        .owner_data
+        .is_vendor_key_unpinned());
+
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            ..Default::default()
+        },
+        BootParams {
+            fuses: caliptra_hw_model::Fuses {
+                owner_pk_hash: owner_pubkey_digest,
+                ..Default::default()
+            },
+            fw_image: Some(&image_bundle.to_bytes().unwrap()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    hw.step_until_boot_status(ColdResetComplete.into(), true);
+
+    hw.start_mailbox_execute(
+        CommandId::FIRMWARE_LOAD.into(),
+        &image_bundle2.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    if cfg!(not(feature = "fpga_realtime")) {
+        hw.step_until_boot_status(UpdateResetStarted.into(), true);
+    }
+
+    assert_eq!(hw.finish_mailbox_execute(), Ok(None));
+
+    hw.step_until_boot_status(UpdateResetComplete.into(), true);
+
+    // The cold boot index stays pinned for images that don't unpin it
+    let image_options = ImageOptions {
+        vendor_config: ImageGeneratorVendorConfig {
+            ecc_key_idx: 2,
+            ..VENDOR_CONFIG_KEY_0
+        },
+        ..Default::default()
+    };
+    let image_bundle3 = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &APP_WITH_UART,
+        image_options,
+    )
+    .unwrap();
+    assert_eq!(
+        hw.upload_firmware(&image_bundle3.to_bytes().unwrap()),
+        Err(caliptra_hw_model::ModelError::MailboxCmdFailed(
+            CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_VENDOR_ECC_PUB_KEY_IDX_MISMATCH.into()
+        ))
+    );
+
+    // Exit test-fmc with success
+    hw.mailbox_execute(0x1000_000C, &[]).unwrap();
+
+    hw.step_until_exit_success().unwrap();
+}
+
 #[test]
 fn test_update_reset_vendor_lms_pub_key_idx_dv_mismatch() {
     let rom = caliptra_builder::build_firmware_rom(firmware::rom_from_env()).unwrap();
@@ -435,10 +550,19 @@ fn test_check_rom_update_reset_status_reg() {
 
     hw.step_until_boot_status(ColdResetComplete.into(), true);
 
+    // Use a different runtime so that ROM loads it rather than keeping the
+    // running one
+    let updated_image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &TEST_RT_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+
     // Trigger an update reset with "new" firmware
     hw.start_mailbox_execute(
         CommandId::FIRMWARE_LOAD.into(),
-        &image_bundle.to_bytes().unwrap(),
+        &updated_image_bundle.to_bytes().unwrap(),
     )
     .unwrap();
 
@@ -468,6 +592,137 @@ fn test_check_rom_update_reset_status_reg() {
     assert_eq!(*warmresetentry4_value, u32::from(UpdateResetComplete));
 }
 
+#[test]
+fn test_update_reset_no_op() {
+    let rom = caliptra_builder::build_firmware_rom(firmware::rom_from_env()).unwrap();
+    let image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            ..Default::default()
+        },
+        BootParams {
+            fw_image: Some(&image_bundle.to_bytes().unwrap()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    hw.step_until_boot_status(ColdResetComplete.into(), true);
+
+    // Update to the runtime that is already running
+    hw.start_mailbox_execute(
+        CommandId::FIRMWARE_LOAD.into(),
+        &image_bundle.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    if cfg!(not(feature = "fpga_realtime")) {
+        hw.step_until_boot_status(UpdateResetStarted.into(), true);
+        hw.step_until_boot_status(UpdateResetLoadManifestComplete.into(), false);
+        hw.step_until_boot_status(UpdateResetImageVerificationComplete.into(), false);
+        hw.step_until_boot_status(UpdateResetPopulateDataVaultComplete.into(), false);
+        hw.step_until_boot_status(UpdateResetExtendPcrComplete.into(), false);
+        // The runtime is not loaded again
+        hw.step_until_boot_status(UpdateResetOverwriteManifestComplete.into(), false);
+        hw.step_until_boot_status(UpdateResetNoOpComplete.into(), false);
+    }
+
+    assert_eq!(hw.finish_mailbox_execute(), Ok(None));
+
+    hw.step_until_boot_status(UpdateResetNoOpComplete.into(), true);
+
+    let warmresetentry4_array = hw.mailbox_execute(0x1000_000D, &[]).unwrap().unwrap();
+    let warmresetentry4_offset = core::mem::size_of::<u32>() * 9; // Skip first four entries and the id
+    let (warmresetentry4_value, _) =
+        u32::ref_from_prefix(warmresetentry4_array[warmresetentry4_offset..].as_bytes()).unwrap();
+    assert_eq!(*warmresetentry4_value, u32::from(UpdateResetNoOpComplete));
+
+    // Exit test-fmc with success
+    hw.mailbox_execute(0x1000_000C, &[]).unwrap();
+
+    hw.step_until_exit_success().unwrap();
+}
+
+#[test]
+fn test_update_reset_break_glass() {
+    let gen = ImageGenerator::new(Crypto::default());
+    let rom = caliptra_builder::build_firmware_rom(firmware::rom_from_env()).unwrap();
+    let image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+    let owner_pubkey_digest = gen
+        .owner_pubkey_digest(&image_bundle.manifest.preamble)
+        .unwrap();
+
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            ..Default::default()
+        },
+        BootParams {
+            fw_image: Some(&image_bundle.to_bytes().unwrap()),
+            fuses: caliptra_hw_model::Fuses {
+                owner_pk_hash: owner_pubkey_digest,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    hw.step_until_boot_status(ColdResetComplete.into(), true);
+
+    // The FMC alias CDI was derived from the LDevID CDI on cold reset
+    let image_options = ImageOptions {
+        owner_config: Some(ImageGeneratorOwnerConfig {
+            break_glass: true,
+            ..OWNER_CONFIG
+        }),
+        ..Default::default()
+    };
+    let image_bundle = caliptra_builder::build_and_sign_image(
+        &TEST_FMC_INTERACTIVE,
+        &APP_WITH_UART,
+        image_options,
+    )
+    .unwrap();
+
+    hw.start_mailbox_execute(
+        CommandId::FIRMWARE_LOAD.into(),
+        &image_bundle.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    hw.step_until_boot_status(UpdateResetStarted.into(), true);
+
+    assert_eq!(
+        hw.finish_mailbox_execute(),
+        Err(caliptra_hw_model::ModelError::MailboxCmdFailed(
+            CaliptraError::IMAGE_VERIFIER_ERR_BREAK_GLASS_UPDATE_RESET.into()
+        ))
+    );
+
+    // Exit test-fmc with success
+    hw.mailbox_execute(0x1000_000C, &[]).unwrap();
+
+    hw.step_until_exit_success().unwrap();
+
+    assert_eq!(
+        hw.soc_ifc().cptra_fw_error_non_fatal().read(),
+        u32::from(CaliptraError::IMAGE_VERIFIER_ERR_BREAK_GLASS_UPDATE_RESET)
+    );
+}
+
 #[test]
 fn test_fmc_is_16k() {
     struct Fmc<'a> {
@@ -0,0 +1,71 @@
+// Licensed under the Apache-2.0 license
+
+use caliptra_api::SocManager;
+use caliptra_builder::firmware::{APP_WITH_UART, FMC_WITH_UART, ROM_VAL_WITH_UART};
+use caliptra_builder::ImageOptions;
+use caliptra_common::RomBootStatus::*;
+use caliptra_drivers::CaliptraError;
+use caliptra_hw_model::{BootParams, DeviceLifecycle, HwModel, InitParams, SecurityState};
+
+const DBG_MANUF_RNG_UNAVAILABLE: u32 = 0x1 << 1;
+
+#[test]
+fn test_val_rom_boot() {
+    // The val ROM accepts the manufacturing-mode RNG even when debug is locked
+    let security_state = *SecurityState::default().set_debug_locked(true);
+
+    let rom = caliptra_builder::build_firmware_rom(&ROM_VAL_WITH_UART).unwrap();
+    let image_bundle = caliptra_builder::build_and_sign_image(
+        &FMC_WITH_UART,
+        &APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            security_state,
+            ..Default::default()
+        },
+        BootParams {
+            initial_dbg_manuf_service_reg: DBG_MANUF_RNG_UNAVAILABLE,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    hw.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_fw());
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+
+    hw.step_until_boot_status(ColdResetComplete.into(), true);
+    hw.step_until_output_contains("[state] Leaving ICCM unlocked")
+        .unwrap();
+
+    let output = hw.output().take(usize::MAX);
+    assert!(output.contains("[state] Personality = val"));
+}
+
+#[test]
+fn test_val_rom_production_error() {
+    let security_state =
+        *SecurityState::default().set_device_lifecycle(DeviceLifecycle::Production);
+
+    let rom = caliptra_builder::build_firmware_rom(&ROM_VAL_WITH_UART).unwrap();
+    let mut hw = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            security_state,
+            ..Default::default()
+        },
+        BootParams::default(),
+    )
+    .unwrap();
+
+    hw.step_until(|m| m.soc_ifc().cptra_fw_error_fatal().read() != 0);
+
+    assert_eq!(
+        hw.soc_ifc().cptra_fw_error_fatal().read(),
+        u32::from(CaliptraError::ROM_GLOBAL_FAKE_ROM_IN_PRODUCTION)
+    );
+}
@@ -0,0 +1,47 @@
+// Licensed under the Apache-2.0 license
+
+use caliptra_builder::ImageOptions;
+use caliptra_common::keyids::{KEY_ID_FE, KEY_ID_UDS};
+use caliptra_common::pcr::PCR_ID_FMC_JOURNEY;
+use caliptra_hw_model::{Fuses, HwModel, VaultEvent};
+
+use crate::helpers;
+
+#[test]
+fn test_vault_events_cold_boot() {
+    // Only the emulator can observe vault mutations
+    #![cfg_attr(any(feature = "verilator", feature = "fpga_realtime"), ignore)]
+
+    let (mut hw, image_bundle) =
+        helpers::build_hw_model_and_image_bundle(Fuses::default(), ImageOptions::default());
+
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+    hw.step_until_output_contains("[exit] Launching FMC")
+        .unwrap();
+    let rom_events = hw.take_vault_events();
+
+    hw.step_until_output_contains("[rt] Runtime listening for mailbox commands...")
+        .unwrap();
+    let post_rom_events = hw.take_vault_events();
+
+    // The journey PCR is locked exactly once per cold boot, by ROM.
+    let journey_pcr_lock = VaultEvent::PcrLock {
+        pcr: PCR_ID_FMC_JOURNEY as u32,
+    };
+    assert_eq!(
+        rom_events
+            .iter()
+            .filter(|e| **e == journey_pcr_lock)
+            .count(),
+        1
+    );
+    assert!(!post_rom_events.contains(&journey_pcr_lock));
+
+    // The UDS and field entropy slots are never written after ROM exits.
+    assert!(!post_rom_events.iter().any(|e| matches!(
+        e,
+        VaultEvent::KeyWrite { slot, .. }
+            if *slot == KEY_ID_UDS as u32 || *slot == KEY_ID_FE as u32
+    )));
+}
@@ -171,6 +171,35 @@ fn test_warm_reset_during_cold_boot_after_image_validation() {
     );
 }
 
+#[test]
+fn test_warm_reset_before_data_vault_lock() {
+    let fuses = Fuses {
+        life_cycle: DeviceLifecycle::Unprovisioned,
+        ..Default::default()
+    };
+
+    let (mut hw, image_bundle) =
+        helpers::build_hw_model_and_image_bundle(fuses, ImageOptions::default());
+
+    hw.upload_firmware(&image_bundle.to_bytes().unwrap())
+        .unwrap();
+
+    // Cold boot is reported complete before the data vault is locked
+    hw.step_until_boot_status(ColdResetComplete.into(), true);
+
+    // Perform a warm reset
+    hw.warm_reset_flow(&Fuses::default());
+
+    // Wait for error
+    while hw.soc_ifc().cptra_fw_error_fatal().read() == 0 {
+        hw.step();
+    }
+    assert_eq!(
+        hw.soc_ifc().cptra_fw_error_fatal().read(),
+        u32::from(CaliptraError::ROM_WARM_RESET_FMC_TCI_NOT_LOCKED)
+    );
+}
+
 #[test]
 fn test_warm_reset_during_update_reset() {
     let fuses = Fuses {
@@ -202,7 +231,7 @@ fn test_warm_reset_during_update_reset() {
     assert_eq!(hw.finish_mailbox_execute(), Ok(None));
 
     // Step till after last step in update reset is complete
-    hw.step_until_boot_status(UpdateResetLoadImageComplete.into(), true);
+    hw.step_until_boot_status(UpdateResetOverwriteManifestComplete.into(), true);
 
     // Perform a warm reset
     hw.warm_reset_flow(&Fuses::default());
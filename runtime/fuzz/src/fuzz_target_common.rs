@@ -0,0 +1,109 @@
+// Licensed under the Apache-2.0 license
+
+use caliptra_api::{calc_checksum, mailbox::CommandId, wire::WIRE_FORMATS, SocManager};
+use caliptra_builder::{
+    firmware::{APP_WITH_UART, FMC_WITH_UART},
+    ImageOptions,
+};
+use caliptra_hw_model::{BootParams, DefaultHwModel, HwModel, InitParams, ModelError};
+use std::cell::RefCell;
+
+/// Commands that end the runtime session by design
+const SESSION_ENDING_CMDS: [CommandId; 7] = [
+    CommandId::FIRMWARE_LOAD,
+    CommandId::SHUTDOWN,
+    CommandId::ZEROIZE,
+    CommandId::INJECT_ERROR,
+    CommandId::INCREMENT_FUSE_SVN,
+    CommandId::RESEED_FIELD_ENTROPY,
+    CommandId::PREPARE_FOR_SLEEP,
+];
+
+/// Flags in the second byte of the input
+const FLAG_WIRE_SIZE: u8 = 1 << 0;
+const FLAG_VERSION: u8 = 1 << 1;
+const FLAG_BAD_CHECKSUM: u8 = 1 << 2;
+
+thread_local! {
+    // Booting the runtime takes far longer than a command, so the model is
+    // shared by every input of a run. A crash found this way may depend on
+    // the inputs that came before it.
+    static MODEL: RefCell<Option<DefaultHwModel>> = RefCell::new(None);
+}
+
+fn boot() -> DefaultHwModel {
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    let image = caliptra_builder::build_and_sign_image(
+        &FMC_WITH_UART,
+        &APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+    let mut model = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            ..Default::default()
+        },
+        BootParams {
+            fw_image: Some(&image.to_bytes().unwrap()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+    model
+}
+
+/// Decode the input into a command code and request
+///
+/// The first byte selects one of `WIRE_FORMATS` and the second holds the
+/// `FLAG_*` bits. The rest of the input is the request, which is resized to
+/// the request struct and given the version of the format if asked to, and
+/// otherwise kept as is. The checksum is fixed up unless `FLAG_BAD_CHECKSUM`
+/// is set, so that most inputs reach the command handlers.
+fn decode(data: &[u8]) -> Option<(u32, Vec<u8>)> {
+    let (&selector, data) = data.split_first()?;
+    let (&flags, data) = data.split_first()?;
+
+    let format = &WIRE_FORMATS[usize::from(selector) % WIRE_FORMATS.len()];
+    if SESSION_ENDING_CMDS.iter().any(|cmd| cmd.0 == format.cmd) {
+        return None;
+    }
+
+    let mut req = data.to_vec();
+    if flags & FLAG_WIRE_SIZE != 0 {
+        req.resize(format.req_size, 0);
+    }
+    if flags & FLAG_VERSION != 0 && format.version > 1 && req.len() > 4 {
+        req[4] = format.version;
+    }
+    if flags & FLAG_BAD_CHECKSUM == 0 && req.len() >= 4 {
+        let chksum = calc_checksum(format.cmd, &req[4..]);
+        req[..4].copy_from_slice(&chksum.to_le_bytes());
+    }
+    Some((format.cmd, req))
+}
+
+/// Run one request through the runtime and check that it did not panic
+///
+/// A runtime panic is reported as a fatal error.
+pub fn harness(data: &[u8]) {
+    let Some((cmd, req)) = decode(data) else {
+        return;
+    };
+
+    MODEL.with(|model| {
+        let mut model = model.borrow_mut();
+        let model = model.get_or_insert_with(boot);
+
+        match model.mailbox_execute(cmd, &req) {
+            Ok(_) | Err(ModelError::MailboxCmdFailed(_)) => {}
+            Err(e) => panic!("Unexpected error {e} for command 0x{cmd:08x}"),
+        }
+        let fatal = model.soc_ifc().cptra_fw_error_fatal().read();
+        assert_eq!(
+            fatal, 0,
+            "Fatal error 0x{fatal:08x} after command 0x{cmd:08x}"
+        );
+    });
+}
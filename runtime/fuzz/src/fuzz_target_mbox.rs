@@ -0,0 +1,29 @@
+// Licensed under the Apache-2.0 license
+
+#![cfg_attr(feature = "libfuzzer-sys", no_main)]
+
+#[cfg(all(not(feature = "libfuzzer-sys"), not(feature = "afl")))]
+compile_error!("Either feature \"libfuzzer-sys\" or \"afl\" must be enabled!");
+
+#[cfg(feature = "libfuzzer-sys")]
+use libfuzzer_sys::fuzz_target;
+
+#[cfg(feature = "afl")]
+use afl::fuzz;
+
+mod fuzz_target_common;
+use fuzz_target_common::harness;
+
+// cargo-fuzz target
+#[cfg(feature = "libfuzzer-sys")]
+fuzz_target!(|data: &[u8]| {
+    harness(data);
+});
+
+// cargo-afl target
+#[cfg(feature = "afl")]
+fn main() {
+    fuzz!(|data: &[u8]| {
+        harness(data);
+    });
+}
@@ -59,50 +59,8 @@ impl AuthorizeAndStashCmd {
                 Err(CaliptraError::RUNTIME_AUTH_AND_STASH_UNSUPPORTED_IMAGE_SOURCE)?;
             }
 
-            // Check if firmware id is present in the image metadata entry collection.
-            let persistent_data = drivers.persistent_data.get();
-            let auth_manifest_image_metadata_col =
-                &persistent_data.auth_manifest_image_metadata_col;
-
-            let cmd_fw_id = u32::from_le_bytes(cmd.fw_id);
-            let auth_result = if let Some(metadata_entry) =
-                Self::find_metadata_entry(auth_manifest_image_metadata_col, cmd_fw_id)
-            {
-                // If 'ignore_auth_check' is set, then skip the image digest comparison and authorize the image.
-                let flags = ImageMetadataFlags(metadata_entry.flags);
-                if flags.ignore_auth_check() {
-                    cfi_assert!(cfi_launder(flags.ignore_auth_check()));
-                    IMAGE_AUTHORIZED
-                } else if cfi_launder(metadata_entry.digest) == cmd.measurement {
-                    caliptra_cfi_lib_git::cfi_assert_eq_12_words(
-                        &Array4x12::from(metadata_entry.digest).0,
-                        &Array4x12::from(cmd.measurement).0,
-                    );
-                    IMAGE_AUTHORIZED
-                } else {
-                    IMAGE_HASH_MISMATCH
-                }
-            } else {
-                IMAGE_NOT_AUTHORIZED
-            };
-
-            // Stash the measurement if the image is authorized.
-            if auth_result == IMAGE_AUTHORIZED {
-                let flags: AuthAndStashFlags = cmd.flags.into();
-                if !flags.contains(AuthAndStashFlags::SKIP_STASH) {
-                    let dpe_result = StashMeasurementCmd::stash_measurement(
-                        drivers,
-                        &cmd.fw_id,
-                        &cmd.measurement,
-                    )?;
-                    if dpe_result != DpeErrorCode::NoError {
-                        drivers
-                            .soc_ifc
-                            .set_fw_extended_error(dpe_result.get_error_code());
-                        Err(CaliptraError::RUNTIME_AUTH_AND_STASH_MEASUREMENT_DPE_ERROR)?;
-                    }
-                }
-            }
+            let auth_result =
+                Self::authorize_and_stash(drivers, &cmd.fw_id, &cmd.measurement, cmd.flags.into())?;
 
             Ok(MailboxResp::AuthorizeAndStash(AuthorizeAndStashResp {
                 hdr: MailboxRespHeader::default(),
@@ -113,10 +71,70 @@ impl AuthorizeAndStashCmd {
         }
     }
 
+    /// Authorize an image digest against the image metadata collection, and
+    /// stash it if the image is authorized.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `fw_id` - Firmware ID of the image, in little-endian format
+    /// * `measurement` - Digest of the image
+    /// * `flags` - Flags of the request
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - IMAGE_AUTHORIZED, IMAGE_NOT_AUTHORIZED or IMAGE_HASH_MISMATCH
+    pub(crate) fn authorize_and_stash(
+        drivers: &mut Drivers,
+        fw_id: &[u8; 4],
+        measurement: &[u8; 48],
+        flags: AuthAndStashFlags,
+    ) -> CaliptraResult<u32> {
+        // Check if firmware id is present in the image metadata entry collection.
+        let persistent_data = drivers.persistent_data.get();
+        let auth_manifest_image_metadata_col = &persistent_data.auth_manifest_image_metadata_col;
+
+        let cmd_fw_id = u32::from_le_bytes(*fw_id);
+        let auth_result = if let Some(metadata_entry) =
+            Self::find_metadata_entry(auth_manifest_image_metadata_col, cmd_fw_id)
+        {
+            // If 'ignore_auth_check' is set, then skip the image digest comparison and authorize the image.
+            let flags = ImageMetadataFlags(metadata_entry.flags);
+            if flags.ignore_auth_check() {
+                cfi_assert!(cfi_launder(flags.ignore_auth_check()));
+                IMAGE_AUTHORIZED
+            } else if cfi_launder(metadata_entry.digest) == *measurement {
+                caliptra_cfi_lib_git::cfi_assert_eq_12_words(
+                    &Array4x12::from(metadata_entry.digest).0,
+                    &Array4x12::from(*measurement).0,
+                );
+                IMAGE_AUTHORIZED
+            } else {
+                IMAGE_HASH_MISMATCH
+            }
+        } else {
+            IMAGE_NOT_AUTHORIZED
+        };
+
+        // Stash the measurement if the image is authorized.
+        if auth_result == IMAGE_AUTHORIZED && !flags.contains(AuthAndStashFlags::SKIP_STASH) {
+            let dpe_result = StashMeasurementCmd::stash_measurement(drivers, fw_id, measurement)?;
+            if dpe_result != DpeErrorCode::NoError {
+                drivers
+                    .soc_ifc
+                    .set_fw_extended_error(dpe_result.get_error_code());
+                Err(CaliptraError::RUNTIME_AUTH_AND_STASH_MEASUREMENT_DPE_ERROR)?;
+            }
+        }
+
+        Ok(auth_result)
+    }
+
     /// Search for a metadata entry in the sorted `AuthManifestImageMetadataCollection` that matches the firmware ID.
     ///
     /// This function performs a binary search on the `image_metadata_list` of the provided `AuthManifestImageMetadataCollection`.
     /// It compares the firmware ID (`fw_id`) of each metadata entry with the provided `cmd_fw_id`.
+    /// Only the first `entry_count` entries are sorted and signed, so the unused entries are never searched.
     ///
     /// # Arguments
     ///
@@ -133,10 +151,12 @@ impl AuthorizeAndStashCmd {
         auth_manifest_image_metadata_col: &AuthManifestImageMetadataCollection,
         cmd_fw_id: u32,
     ) -> Option<&AuthManifestImageMetadata> {
-        auth_manifest_image_metadata_col
+        let image_metadata_list = auth_manifest_image_metadata_col
             .image_metadata_list
+            .get(..auth_manifest_image_metadata_col.entry_count as usize)?;
+        image_metadata_list
             .binary_search_by(|metadata| metadata.fw_id.cmp(&cmd_fw_id))
             .ok()
-            .map(|index| &auth_manifest_image_metadata_col.image_metadata_list[index])
+            .map(|index| &image_metadata_list[index])
     }
 }
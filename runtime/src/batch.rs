@@ -0,0 +1,173 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    batch.rs
+
+Abstract:
+
+    File contains the Batch mailbox command.
+
+--*/
+
+use crate::{
+    cancel, capabilities::CapabilitiesCmd, dice::GetRtAliasCertCmd, CmdPolicy, CmdVersion, Drivers,
+    FipsVersionCmd, FwInfoCmd, GetFmcAliasCertCmd, GetLdevCertCmd, IDevIdInfoCmd,
+};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{
+    BatchReq, BatchReqEntryHdr, BatchResp, BatchRespEntryHdr, CommandId, MailboxResp,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::{FromBytes, IntoBytes};
+
+/// Round `len` up to a multiple of 4 bytes
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+pub struct BatchCmd;
+impl BatchCmd {
+    /// Commands that can run in a batch
+    fn is_batch_cmd(cmd: &CommandId) -> bool {
+        matches!(
+            *cmd,
+            CommandId::VERSION
+                | CommandId::FW_INFO
+                | CommandId::CAPABILITIES
+                | CommandId::GET_IDEV_INFO
+                | CommandId::GET_LDEV_CERT
+                | CommandId::GET_FMC_ALIAS_CERT
+                | CommandId::GET_RT_ALIAS_CERT
+        )
+    }
+
+    /// Split the next command off the batch
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Remaining commands of the batch
+    ///
+    /// # Returns
+    ///
+    /// * `(u32, &[u8], &[u8])` - Command, its request and the remaining commands
+    fn next_entry(data: &[u8]) -> CaliptraResult<(u32, &[u8], &[u8])> {
+        let (entry_hdr, rest) = BatchReqEntryHdr::ref_from_prefix(data)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let req_len = entry_hdr.data_size as usize;
+        if req_len > BatchReq::DATA_MAX_SIZE {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+        let req = rest
+            .get(..req_len)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let rest = rest
+            .get(padded_len(req_len)..)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        Ok((entry_hdr.cmd, req, rest))
+    }
+
+    /// Append `data` to the batch response at `offset`
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - Offset following `data`
+    fn append(resp: &mut BatchResp, offset: usize, data: &[u8]) -> CaliptraResult<usize> {
+        let end = offset + data.len();
+        resp.data
+            .get_mut(offset..end)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_API_RESPONSE_DATA_LEN_TOO_LARGE)?
+            .copy_from_slice(data);
+        Ok(end)
+    }
+
+    fn execute_entry(
+        drivers: &mut Drivers,
+        cmd: CommandId,
+        req: &[u8],
+    ) -> CaliptraResult<MailboxResp> {
+        let cmd_version = CmdVersion::check(&cmd, req)?;
+        match cmd {
+            CommandId::VERSION => {
+                FipsVersionCmd::execute(&drivers.soc_ifc).map(MailboxResp::FipsVersion)
+            }
+            CommandId::FW_INFO => FwInfoCmd::execute(drivers, cmd_version),
+            CommandId::CAPABILITIES => CapabilitiesCmd::execute(req, cmd_version),
+            CommandId::GET_IDEV_INFO => IDevIdInfoCmd::execute(drivers),
+            CommandId::GET_LDEV_CERT => GetLdevCertCmd::execute(drivers),
+            CommandId::GET_FMC_ALIAS_CERT => GetFmcAliasCertCmd::execute(drivers),
+            CommandId::GET_RT_ALIAS_CERT => GetRtAliasCertCmd::execute(drivers),
+            _ => Err(CaliptraError::RUNTIME_BATCH_CMD_NOT_SUPPORTED),
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        if cmd_args.len() > core::mem::size_of::<BatchReq>() {
+            return Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY);
+        }
+        let mut cmd = BatchReq::default();
+        cmd.as_mut_bytes()[..cmd_args.len()].copy_from_slice(cmd_args);
+        let data = cmd
+            .data
+            .get(..cmd.data_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        // Reject the whole batch before running any of its commands
+        let mut entries = data;
+        let mut num_cmds = 0;
+        while !entries.is_empty() {
+            let (entry_cmd, _, rest) = Self::next_entry(entries)?;
+            num_cmds += 1;
+            if num_cmds > BatchReq::MAX_CMDS {
+                return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+            }
+            if !Self::is_batch_cmd(&CommandId::from(entry_cmd)) {
+                return Err(CaliptraError::RUNTIME_BATCH_CMD_NOT_SUPPORTED);
+            }
+            CmdPolicy::authorize(drivers, CommandId::from(entry_cmd))?;
+            entries = rest;
+        }
+
+        // A failing command records its error and the batch carries on
+        let mut resp = BatchResp::default();
+        let mut offset = 0;
+        let mut entries = data;
+        while !entries.is_empty() {
+            cancel::checkpoint(&mut drivers.soc_ifc, &drivers.mbox)?;
+            let (entry_cmd, req, rest) = Self::next_entry(entries)?;
+            entries = rest;
+
+            let mut entry_hdr = BatchRespEntryHdr {
+                cmd: entry_cmd,
+                ..Default::default()
+            };
+            let entry_resp = Self::execute_entry(drivers, CommandId::from(entry_cmd), req)
+                .and_then(|mut entry_resp| {
+                    entry_resp.populate_chksum()?;
+                    Ok(entry_resp)
+                });
+            let entry_resp_bytes = match &entry_resp {
+                Ok(entry_resp) => entry_resp.as_bytes()?,
+                Err(e) => {
+                    entry_hdr.error = (*e).into();
+                    &[]
+                }
+            };
+            entry_hdr.data_size = entry_resp_bytes.len() as u32;
+
+            offset = Self::append(&mut resp, offset, entry_hdr.as_bytes())?;
+            offset = Self::append(&mut resp, offset, entry_resp_bytes)?;
+            offset = padded_len(offset);
+            if offset > BatchResp::DATA_MAX_SIZE {
+                return Err(CaliptraError::RUNTIME_MAILBOX_API_RESPONSE_DATA_LEN_TOO_LARGE);
+            }
+        }
+        resp.data_size = offset as u32;
+
+        Ok(MailboxResp::Batch(resp))
+    }
+}
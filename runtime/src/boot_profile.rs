@@ -0,0 +1,38 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    boot_profile.rs
+
+Abstract:
+
+    File contains GetBootProfile mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{GetBootProfileResp, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::{BootCheckpoint, CaliptraResult};
+
+pub struct GetBootProfileCmd;
+impl GetBootProfileCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let profile = &drivers.persistent_data.get().boot_profile;
+
+        Ok(MailboxResp::GetBootProfile(GetBootProfileResp {
+            hdr: MailboxRespHeader::default(),
+            rom_entry: profile.get(BootCheckpoint::RomEntry),
+            rom_kats_complete: profile.get(BootCheckpoint::RomKatsComplete),
+            rom_fw_received: profile.get(BootCheckpoint::RomFwReceived),
+            rom_fw_verified: profile.get(BootCheckpoint::RomFwVerified),
+            rom_exit: profile.get(BootCheckpoint::RomExit),
+            fmc_entry: profile.get(BootCheckpoint::FmcEntry),
+            fmc_exit: profile.get(BootCheckpoint::FmcExit),
+            rt_entry: profile.get(BootCheckpoint::RtEntry),
+            rt_ready: profile.get(BootCheckpoint::RtReady),
+        }))
+    }
+}
@@ -0,0 +1,55 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cancel.rs
+
+Abstract:
+
+    File contains support for canceling in-flight mailbox commands.
+
+--*/
+
+use crate::mailbox::Mailbox;
+use caliptra_drivers::{CaliptraError, CaliptraResult, Ecc384, Hmac384, Sha256, Sha384, SocIfc};
+
+/// Operation checkpoint for long-running commands.
+///
+/// Handlers must only call this before they modify any state that outlives
+/// the command, so that a canceled command has no effect.
+///
+/// # Returns
+///
+/// * `RUNTIME_MAILBOX_CMD_CANCELED` - The SoC requested the in-flight command
+///   be canceled
+/// * `RUNTIME_MAILBOX_CMD_ABORTED` - The SoC released the mailbox
+pub(crate) fn checkpoint(soc_ifc: &mut SocIfc, mbox: &Mailbox) -> CaliptraResult<()> {
+    if soc_ifc.mbox_cancel_requested() {
+        soc_ifc.clear_mbox_cancel_request();
+        return Err(CaliptraError::RUNTIME_MAILBOX_CMD_CANCELED);
+    }
+    if mbox.is_aborted() {
+        return Err(CaliptraError::RUNTIME_MAILBOX_CMD_ABORTED);
+    }
+    Ok(())
+}
+
+/// Returns true if `err` was returned by a command that was canceled or
+/// aborted
+pub(crate) fn is_canceled(err: CaliptraError) -> bool {
+    err == CaliptraError::RUNTIME_MAILBOX_CMD_CANCELED
+        || err == CaliptraError::RUNTIME_MAILBOX_CMD_ABORTED
+}
+
+/// Clear the intermediate results a canceled or aborted command left in the
+/// crypto engines.
+pub(crate) fn scrub_canceled_command() {
+    unsafe {
+        Ecc384::zeroize();
+        Hmac384::zeroize();
+        Sha256::zeroize();
+        Sha384::zeroize();
+    }
+}
@@ -12,22 +12,69 @@ Abstract:
 
 --*/
 
+use crate::CmdVersion;
 use caliptra_common::{
     capabilities::Capabilities,
-    mailbox_api::{CapabilitiesResp, MailboxResp, MailboxRespHeader},
+    mailbox_api::{
+        CapabilitiesCmdVersionReq, CapabilitiesCmdVersionResp, CapabilitiesNegotiateReq,
+        CapabilitiesNegotiateResp, CapabilitiesResp, CommandId, MailboxResp, MailboxRespHeader,
+    },
 };
-use caliptra_error::CaliptraResult;
+use caliptra_error::{CaliptraError, CaliptraResult};
+use zerocopy::FromBytes;
+
+const _: () = assert!(CommandId::CAPABILITY_BITS.len() <= CapabilitiesNegotiateResp::MAX_CMDS);
 
 pub struct CapabilitiesCmd;
 impl CapabilitiesCmd {
     #[inline(never)]
-    pub(crate) fn execute() -> CaliptraResult<MailboxResp> {
+    pub(crate) fn execute(cmd_args: &[u8], version: u8) -> CaliptraResult<MailboxResp> {
         let mut capabilities = Capabilities::default();
         capabilities |= Capabilities::RT_BASE;
+        capabilities |= Capabilities::RT_CMD_VERSIONING;
+        capabilities |= Capabilities::RT_CMD_NEGOTIATION;
+
+        if version < CapabilitiesCmdVersionReq::VERSION {
+            return Ok(MailboxResp::Capabilities(CapabilitiesResp {
+                hdr: MailboxRespHeader::default(),
+                capabilities: capabilities.to_bytes(),
+            }));
+        }
+
+        if version == CapabilitiesNegotiateReq::VERSION {
+            CapabilitiesNegotiateReq::ref_from_bytes(cmd_args)
+                .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+            return Ok(MailboxResp::CapabilitiesNegotiate(Self::negotiate(
+                capabilities,
+            )));
+        }
+
+        let cmd = CapabilitiesCmdVersionReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let (min_version, max_version) = CmdVersion::supported(&CommandId::from(cmd.cmd));
+
+        Ok(MailboxResp::CapabilitiesCmdVersion(
+            CapabilitiesCmdVersionResp {
+                hdr: MailboxRespHeader::default(),
+                capabilities: capabilities.to_bytes(),
+                min_version,
+                max_version,
+                reserved: [0; 2],
+            },
+        ))
+    }
 
-        Ok(MailboxResp::Capabilities(CapabilitiesResp {
-            hdr: MailboxRespHeader::default(),
+    fn negotiate(capabilities: Capabilities) -> CapabilitiesNegotiateResp {
+        let mut resp = CapabilitiesNegotiateResp {
             capabilities: capabilities.to_bytes(),
-        }))
+            ..Default::default()
+        };
+        for (bit, cmd) in CommandId::CAPABILITY_BITS.iter().enumerate() {
+            if CmdVersion::is_supported(cmd) {
+                resp.supported_cmds[bit / 32] |= 1 << (bit % 32);
+                resp.max_versions[bit] = CmdVersion::supported(cmd).1;
+            }
+        }
+        resp
     }
 }
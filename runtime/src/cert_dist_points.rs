@@ -0,0 +1,51 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cert_dist_points.rs
+
+Abstract:
+
+    File contains SetCertDistPoints mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{MailboxResp, SetCertDistPointsReq};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::FromBytes;
+
+pub struct SetCertDistPointsCmd;
+impl SetCertDistPointsCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SetCertDistPointsReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        let crl_uri = cmd
+            .crl_uri
+            .get(..cmd.crl_uri_size as usize)
+            .ok_or(CaliptraError::RUNTIME_CERT_DIST_POINT_INVALID_URI)?;
+        let aia_uri = cmd
+            .aia_uri
+            .get(..cmd.aia_uri_size as usize)
+            .ok_or(CaliptraError::RUNTIME_CERT_DIST_POINT_INVALID_URI)?;
+
+        // The FMC adds the URIs to the RT Alias certificate the next time it
+        // runs, i.e. on the next update reset.
+        if !drivers
+            .persistent_data
+            .get_mut()
+            .cert_dist_points
+            .set(crl_uri, aia_uri)
+        {
+            return Err(CaliptraError::RUNTIME_CERT_DIST_POINT_INVALID_URI);
+        }
+
+        Ok(MailboxResp::default())
+    }
+}
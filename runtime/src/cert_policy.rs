@@ -0,0 +1,125 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cert_policy.rs
+
+Abstract:
+
+    File contains SetCertPolicy mailbox command and the certificate issuance
+    policy enforced on DPE CertifyKey.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{CertPolicyFlags, MailboxResp, SetCertPolicyReq};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use dpe::commands::CertifyKeyCmd;
+use zerocopy::FromBytes;
+
+/// Owner constraints on certificates issued by DPE CertifyKey
+#[derive(Debug, Clone, Copy)]
+pub struct CertPolicy {
+    flags: CertPolicyFlags,
+    max_not_after: [u8; 15],
+}
+
+impl CertPolicy {
+    /// Check that a CertifyKey request is allowed by the policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - CertifyKey command about to be executed
+    pub fn check_certify_key(&self, cmd: &CertifyKeyCmd) -> CaliptraResult<()> {
+        let denied = match cmd.format {
+            CertifyKeyCmd::FORMAT_X509 => self.flags.contains(CertPolicyFlags::DENY_X509),
+            CertifyKeyCmd::FORMAT_CSR => self.flags.contains(CertPolicyFlags::DENY_CSR),
+            _ => false,
+        };
+        if denied {
+            return Err(CaliptraError::RUNTIME_CERT_POLICY_VIOLATION);
+        }
+        Ok(())
+    }
+
+    /// Rebuild a policy saved with `flags` and `raw_max_not_after`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<CertPolicy>` - None if the saved policy is not valid
+    pub fn from_raw(flags: u32, max_not_after: [u8; 15]) -> Option<Self> {
+        let flags = CertPolicyFlags::from_bits(flags)?;
+        if flags.contains(CertPolicyFlags::CAP_VALIDITY)
+            && !Self::is_generalized_time(&max_not_after)
+        {
+            return None;
+        }
+        Some(Self {
+            flags,
+            max_not_after,
+        })
+    }
+
+    /// Raw flags of the policy
+    pub fn flags(&self) -> u32 {
+        self.flags.bits()
+    }
+
+    /// Latest notAfter time of the policy, whether or not validity is capped
+    pub fn raw_max_not_after(&self) -> &[u8; 15] {
+        &self.max_not_after
+    }
+
+    /// Returns true if the subject alternative name extension must be omitted
+    pub fn denies_subject_alt_name(&self) -> bool {
+        self.flags.contains(CertPolicyFlags::DENY_SUBJECT_ALT_NAME)
+    }
+
+    /// Latest notAfter time allowed in issued certificates, as a
+    /// GeneralizedTime string, if validity is capped
+    pub fn max_not_after(&self) -> Option<&[u8; 15]> {
+        if self.flags.contains(CertPolicyFlags::CAP_VALIDITY) {
+            Some(&self.max_not_after)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `time` is a GeneralizedTime of the form YYYYMMDDHHMMSSZ
+    fn is_generalized_time(time: &[u8; 15]) -> bool {
+        time[..14].iter().all(u8::is_ascii_digit) && time[14] == b'Z'
+    }
+}
+
+pub struct SetCertPolicyCmd;
+impl SetCertPolicyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SetCertPolicyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // The policy is latched until the next reset so it cannot be relaxed
+        if drivers.cert_policy.is_some() {
+            return Err(CaliptraError::RUNTIME_CERT_POLICY_ALREADY_SET);
+        }
+
+        let flags = CertPolicyFlags::from_bits(cmd.flags.0)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        if flags.contains(CertPolicyFlags::CAP_VALIDITY)
+            && !CertPolicy::is_generalized_time(&cmd.max_not_after)
+        {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+
+        drivers.cert_policy = Some(CertPolicy {
+            flags,
+            max_not_after: cmd.max_not_after,
+        });
+
+        Ok(MailboxResp::default())
+    }
+}
@@ -25,8 +25,8 @@ use dpe::{
 use zerocopy::{FromBytes, IntoBytes};
 
 use crate::{
-    CptraDpeTypes, DpeCrypto, DpeEnv, DpePlatform, Drivers, PauserPrivileges, MAX_CERT_CHAIN_SIZE,
-    PL0_PAUSER_FLAG,
+    CptraDpeTypes, DpeCrypto, DpeEnv, DpePlatform, Drivers, PauserPrivileges, PrivilegedSocIfc,
+    MAX_CERT_CHAIN_SIZE, PL0_PAUSER_FLAG,
 };
 
 pub struct CertifyKeyExtendedCmd;
@@ -47,8 +47,10 @@ impl CertifyKeyExtendedCmd {
         let hashed_rt_pub_key = drivers.compute_rt_alias_sn()?;
         let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
         let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+        let wdt_pet = PrivilegedSocIfc::wdt_pet(drivers);
         let pdata = drivers.persistent_data.get_mut();
         let crypto = DpeCrypto::new(
+            &mut drivers.sha256,
             &mut drivers.sha384,
             &mut drivers.trng,
             &mut drivers.ecc384,
@@ -58,7 +60,8 @@ impl CertifyKeyExtendedCmd {
             key_id_rt_cdi,
             key_id_rt_priv_key,
             &mut drivers.exported_cdi_slots,
-        );
+        )
+        .with_wdt_pet(wdt_pet);
         let pl0_pauser = pdata.manifest1.header.pl0_pauser;
         let (nb, nf) = Drivers::get_cert_validity_info(&pdata.manifest1);
         // Populate the otherName only if requested and provided by ADD_SUBJECT_ALT_NAME
@@ -80,12 +83,16 @@ impl CertifyKeyExtendedCmd {
                 &nf,
                 dmtf_device_info,
                 None,
+                drivers.cert_policy,
             ),
         };
 
         let mut dpe = &mut pdata.dpe;
         let certify_key_cmd = CertifyKeyCmd::ref_from_bytes(&cmd.certify_key_req[..])
             .map_err(|_| CaliptraError::RUNTIME_DPE_COMMAND_DESERIALIZATION_FAILED)?;
+        if let Some(cert_policy) = &drivers.cert_policy {
+            cert_policy.check_certify_key(certify_key_cmd)?;
+        }
         let locality = drivers.mbox.user();
         let resp = certify_key_cmd.execute(dpe, &mut env, locality);
 
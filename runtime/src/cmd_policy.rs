@@ -0,0 +1,111 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cmd_policy.rs
+
+Abstract:
+
+    File contains the authorization policy of the mailbox commands.
+
+--*/
+
+use crate::{Drivers, PauserPrivileges};
+use caliptra_common::mailbox_api::CommandId;
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+
+/// Callers allowed to invoke a mailbox command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmdAccess {
+    /// Any PAUSER. The command may still restrict PL1 callers itself.
+    Any,
+
+    /// Only the PL0 PAUSER
+    Pl0,
+
+    /// Only the command PAUSER designated in the owner data of the image
+    /// header, or the PL0 PAUSER if the owner does not designate one
+    Owner,
+}
+
+pub struct CmdPolicy;
+impl CmdPolicy {
+    /// Get the callers allowed to invoke `cmd`
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - Mailbox command
+    pub fn access(cmd: CommandId) -> CmdAccess {
+        match cmd {
+            CommandId::REVOKE_LOCALITY
+            | CommandId::INCREMENT_FUSE_SVN
+            | CommandId::SUSPEND_PREPARE
+            | CommandId::RESUME_NOTIFY
+            | CommandId::PREPARE_FOR_SLEEP
+            | CommandId::CONFIGURE_WDT
+            | CommandId::GET_CRASH_DUMP
+            | CommandId::SET_LOG_LEVEL
+            | CommandId::SET_MEASUREMENT_MANIFEST
+            | CommandId::UNSEAL_KEY
+            | CommandId::CREATE_COUNTER
+            | CommandId::INCREMENT_COUNTER
+            | CommandId::TIMESTAMP
+            | CommandId::INJECT_ERROR
+            | CommandId::PAIR_MAILBOX => CmdAccess::Pl0,
+            CommandId::COMMIT_OWNER_KEY_ROTATION
+            | CommandId::SET_CERT_POLICY
+            | CommandId::SET_CERT_DIST_POINTS
+            | CommandId::SET_OBJECT_SCHEMAS
+            | CommandId::RESEED_FIELD_ENTROPY
+            | CommandId::SEAL_KEY => CmdAccess::Owner,
+            _ => CmdAccess::Any,
+        }
+    }
+
+    /// Whether the request or response of `cmd` carries secrets, so that the
+    /// mailbox SRAM must be scrubbed once the SoC has collected the response
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - Mailbox command
+    pub fn confidential(cmd: &CommandId) -> bool {
+        matches!(
+            *cmd,
+            CommandId::INVOKE_DPE
+                | CommandId::SIGN_WITH_EXPORTED_ECDSA
+                | CommandId::GET_RAW_ENTROPY
+                | CommandId::SEAL_KEY
+                | CommandId::UNSEAL_KEY
+        )
+    }
+
+    /// Check that the caller of the pending mailbox command may invoke it
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `cmd` - Mailbox command
+    pub(crate) fn authorize(drivers: &Drivers, cmd: CommandId) -> CaliptraResult<()> {
+        match Self::access(cmd) {
+            CmdAccess::Any => Ok(()),
+            CmdAccess::Pl0 => Self::authorize_pl0(drivers),
+            CmdAccess::Owner => {
+                let owner_data = &drivers.persistent_data.get().manifest1.header.owner_data;
+                match owner_data.cmd_pauser() {
+                    Some(cmd_pauser) if drivers.mbox.user() == cmd_pauser => Ok(()),
+                    Some(_) => Err(CaliptraError::RUNTIME_CMD_NOT_AUTHORIZED),
+                    None => Self::authorize_pl0(drivers),
+                }
+            }
+        }
+    }
+
+    fn authorize_pl0(drivers: &Drivers) -> CaliptraResult<()> {
+        if drivers.caller_privilege_level() != PauserPrivileges::PL0 {
+            return Err(CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL);
+        }
+        Ok(())
+    }
+}
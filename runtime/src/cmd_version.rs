@@ -0,0 +1,102 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cmd_version.rs
+
+Abstract:
+
+    File contains the table of commands with more than one request format.
+
+--*/
+
+use caliptra_common::mailbox_api::{
+    CapabilitiesNegotiateReq, CommandId, FwInfoVersionedReq, MailboxReqHeader,
+    MailboxReqVersionedHeader, QuotePcrsCwtReq, QuotePcrsReq,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use core::mem::size_of;
+use zerocopy::FromBytes;
+
+struct CmdVersions {
+    cmd: CommandId,
+
+    /// Length of a version 1 request. Requests of any other length carry a
+    /// `MailboxReqVersionedHeader`.
+    v1_len: usize,
+
+    /// Newest supported request version
+    max_version: u8,
+}
+
+/// Commands not listed here only have a version 1 request format.
+const CMD_VERSIONS: [CmdVersions; 3] = [
+    CmdVersions {
+        cmd: CommandId::CAPABILITIES,
+        v1_len: size_of::<MailboxReqHeader>(),
+        max_version: CapabilitiesNegotiateReq::VERSION,
+    },
+    CmdVersions {
+        cmd: CommandId::FW_INFO,
+        v1_len: size_of::<MailboxReqHeader>(),
+        max_version: FwInfoVersionedReq::VERSION,
+    },
+    CmdVersions {
+        cmd: CommandId::QUOTE_PCRS,
+        v1_len: size_of::<QuotePcrsReq>(),
+        max_version: QuotePcrsCwtReq::VERSION,
+    },
+];
+
+pub struct CmdVersion;
+impl CmdVersion {
+    /// Returns whether this build of Runtime executes `cmd`
+    pub fn is_supported(cmd: &CommandId) -> bool {
+        match *cmd {
+            #[cfg(not(feature = "fips_self_test"))]
+            CommandId::SELF_TEST_START | CommandId::SELF_TEST_GET_RESULTS => false,
+            #[cfg(not(feature = "dpe-test-hooks"))]
+            CommandId::GET_DPE_STATS => false,
+            // Only handled by ROM
+            CommandId::GET_PROVISIONING_STATUS => false,
+            _ => cmd.capability_bit().is_some(),
+        }
+    }
+
+    /// Returns the oldest and newest request versions supported for `cmd`
+    pub fn supported(cmd: &CommandId) -> (u8, u8) {
+        let max_version = CMD_VERSIONS
+            .iter()
+            .find(|v| v.cmd == *cmd)
+            .map_or(1, |v| v.max_version);
+        (1, max_version)
+    }
+
+    /// Determine the version of a request and check that it is supported
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - Command ID
+    /// * `cmd_args` - Request, including the header
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - Request version
+    pub fn check(cmd: &CommandId, cmd_args: &[u8]) -> CaliptraResult<u8> {
+        let Some(versions) = CMD_VERSIONS.iter().find(|v| v.cmd == *cmd) else {
+            return Ok(1);
+        };
+        if cmd_args.len() == versions.v1_len {
+            return Ok(1);
+        }
+
+        let (hdr, _) = MailboxReqVersionedHeader::ref_from_prefix(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        if hdr.version < 2 || hdr.version > versions.max_version {
+            return Err(CaliptraError::RUNTIME_CMD_UNSUPPORTED_VERSION);
+        }
+        Ok(hdr.version)
+    }
+}
@@ -0,0 +1,56 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    commit_owner_key_rotation.rs
+
+Abstract:
+
+    File contains CommitOwnerKeyRotation mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{CommitOwnerKeyRotationReq, MailboxResp};
+use caliptra_drivers::{Array4x12, CaliptraError, CaliptraResult};
+use zerocopy::{FromBytes, IntoBytes};
+
+pub struct CommitOwnerKeyRotationCmd;
+impl CommitOwnerKeyRotationCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = CommitOwnerKeyRotationReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        let next_digest = drivers.data_vault.next_owner_pk_hash();
+        if next_digest == Array4x12::default() {
+            return Err(CaliptraError::RUNTIME_OWNER_KEY_ROTATION_NOT_PENDING);
+        }
+        if Array4x12::from(cmd.owner_pub_keys_digest) != next_digest {
+            return Err(CaliptraError::RUNTIME_OWNER_KEY_ROTATION_DIGEST_MISMATCH);
+        }
+
+        // The rotation can only be committed once an image signed by the new
+        // owner key has been loaded, proving the new key is usable.
+        let owner_pub_keys = &drivers
+            .persistent_data
+            .get()
+            .manifest1
+            .preamble
+            .owner_pub_keys;
+        let running_digest = drivers.sha384.digest(owner_pub_keys.as_bytes())?;
+        if running_digest != next_digest {
+            return Err(CaliptraError::RUNTIME_OWNER_KEY_ROTATION_KEY_NOT_IN_USE);
+        }
+
+        // The committed digest is locked in the data vault, so ROM records it
+        // on the next update reset.
+        drivers.persistent_data.get_mut().owner_key_commit_request = next_digest.into();
+
+        Ok(MailboxResp::default())
+    }
+}
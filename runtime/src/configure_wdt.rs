@@ -0,0 +1,44 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    configure_wdt.rs
+
+Abstract:
+
+    File contains the ConfigureWdt mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{ConfigureWdtReq, MailboxResp};
+use caliptra_common::WdtTimeout;
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use core::num::NonZeroU64;
+use zerocopy::FromBytes;
+
+pub struct ConfigureWdtCmd;
+impl ConfigureWdtCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = ConfigureWdtReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // The default timeout is the shortest one every command is known to
+        // complete in.
+        let min_timeout = u64::from(WdtTimeout::default());
+        drivers.wdt_timeout = match NonZeroU64::new(cmd.timeout_cycles()) {
+            None => WdtTimeout::default(),
+            Some(timeout) if timeout.get() >= min_timeout => WdtTimeout(timeout),
+            Some(_) => return Err(CaliptraError::RUNTIME_WDT_TIMEOUT_INVALID),
+        };
+
+        // The new timeout is armed from the next command on, as the watchdog
+        // is owned by the mailbox loop.
+        Ok(MailboxResp::default())
+    }
+}
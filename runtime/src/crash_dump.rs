@@ -0,0 +1,47 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    crash_dump.rs
+
+Abstract:
+
+    File contains GetCrashDump mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{GetCrashDumpResp, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::CaliptraResult;
+
+pub struct GetCrashDumpCmd;
+impl GetCrashDumpCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        // The record is consumed so that a later crash is not mistaken for
+        // this one
+        let record = core::mem::take(&mut drivers.persistent_data.get_mut().crash_record);
+        if !record.is_valid() {
+            return Ok(MailboxResp::GetCrashDump(GetCrashDumpResp::default()));
+        }
+
+        Ok(MailboxResp::GetCrashDump(GetCrashDumpResp {
+            hdr: MailboxRespHeader::default(),
+            valid: 1,
+            error_code: record.error_code,
+            last_cmd: record.last_cmd,
+            fht_validity: record.fht_validity,
+            stack_high_water: record.stack_high_water,
+            has_trap: record.has_trap,
+            mcause: record.mcause,
+            mscause: record.mscause,
+            mepc: record.mepc,
+            mtval: record.mtval,
+            mstatus: record.mstatus,
+            ra: record.ra,
+            sp: record.sp,
+        }))
+    }
+}
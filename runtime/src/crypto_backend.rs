@@ -0,0 +1,373 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    crypto_backend.rs
+
+Abstract:
+
+    File contains the crypto backend used by mailbox command handlers, with
+    the hardware drivers as the default implementation and a pure-software
+    implementation for host builds.
+
+--*/
+
+use caliptra_drivers::{
+    Array4x12, CaliptraResult, Ecc384, Ecc384PrivKeyIn, Ecc384PubKey, Ecc384Result,
+    Ecc384Signature, Hmac384, Hmac384Data, Hmac384Key, Hmac384Tag, KeyId, KeyReadArgs, Sha384,
+    Trng,
+};
+
+/// SHA-384, HMAC-384 and ECC-384 operations needed by the runtime handlers
+///
+/// Handlers written against this trait instead of the drivers can be unit
+/// tested on the host with [`SwCryptoBackend`].
+pub trait CryptoBackend {
+    /// Calculate the SHA-384 digest of `data`
+    fn sha384_digest(&mut self, data: &[u8]) -> CaliptraResult<Array4x12>;
+
+    /// Calculate the HMAC-384 tag of `data` keyed with `key`
+    fn hmac384(&mut self, key: &Array4x12, data: &[u8]) -> CaliptraResult<Array4x12>;
+
+    /// Sign `digest` with the ECC-384 private key held in `priv_key`
+    fn ecc384_sign(
+        &mut self,
+        priv_key: KeyId,
+        pub_key: &Ecc384PubKey,
+        digest: &Array4x12,
+    ) -> CaliptraResult<Ecc384Signature>;
+
+    /// Verify an ECC-384 signature over `digest`
+    fn ecc384_verify(
+        &mut self,
+        pub_key: &Ecc384PubKey,
+        digest: &Array4x12,
+        sig: &Ecc384Signature,
+    ) -> CaliptraResult<Ecc384Result>;
+}
+
+/// Crypto backend using the Caliptra hardware engines
+pub struct HwCryptoBackend<'a> {
+    pub sha384: &'a mut Sha384,
+    pub hmac384: &'a mut Hmac384,
+    pub ecc384: &'a mut Ecc384,
+    pub trng: &'a mut Trng,
+}
+
+impl<'a> HwCryptoBackend<'a> {
+    pub fn new(
+        sha384: &'a mut Sha384,
+        hmac384: &'a mut Hmac384,
+        ecc384: &'a mut Ecc384,
+        trng: &'a mut Trng,
+    ) -> Self {
+        Self {
+            sha384,
+            hmac384,
+            ecc384,
+            trng,
+        }
+    }
+}
+
+impl CryptoBackend for HwCryptoBackend<'_> {
+    fn sha384_digest(&mut self, data: &[u8]) -> CaliptraResult<Array4x12> {
+        self.sha384.digest(data)
+    }
+
+    fn hmac384(&mut self, key: &Array4x12, data: &[u8]) -> CaliptraResult<Array4x12> {
+        let mut tag = Array4x12::default();
+        self.hmac384.hmac(
+            &Hmac384Key::Array4x12(key),
+            &Hmac384Data::Slice(data),
+            self.trng,
+            Hmac384Tag::Array4x12(&mut tag),
+        )?;
+        Ok(tag)
+    }
+
+    fn ecc384_sign(
+        &mut self,
+        priv_key: KeyId,
+        pub_key: &Ecc384PubKey,
+        digest: &Array4x12,
+    ) -> CaliptraResult<Ecc384Signature> {
+        let priv_key = Ecc384PrivKeyIn::Key(KeyReadArgs::new(priv_key));
+        self.ecc384.sign(&priv_key, pub_key, digest, self.trng)
+    }
+
+    fn ecc384_verify(
+        &mut self,
+        pub_key: &Ecc384PubKey,
+        digest: &Array4x12,
+        sig: &Ecc384Signature,
+    ) -> CaliptraResult<Ecc384Result> {
+        self.ecc384.verify(pub_key, digest, sig)
+    }
+}
+
+#[cfg(feature = "std")]
+pub use sw::SwCryptoBackend;
+
+#[cfg(feature = "std")]
+mod sw {
+    use super::CryptoBackend;
+    use caliptra_drivers::{
+        Array4x12, CaliptraError, CaliptraResult, Ecc384PubKey, Ecc384Result, Ecc384Signature,
+        KeyId,
+    };
+    use p384::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+    use p384::ecdsa::{Signature, SigningKey, VerifyingKey};
+    use p384::EncodedPoint;
+    use sha2::digest::generic_array::GenericArray;
+    use sha2::{Digest, Sha384};
+    use zeroize::Zeroize;
+
+    const SHA384_BLOCK_SIZE: usize = 128;
+    const KEY_SLOT_COUNT: usize = 32;
+
+    fn array4x12(bytes: &[u8]) -> Array4x12 {
+        let mut array = [0u8; 48];
+        array.copy_from_slice(bytes);
+        Array4x12::from(array)
+    }
+
+    /// Crypto backend implemented in software, for host builds and emulation
+    ///
+    /// Key vault slots are modelled as an in-memory table of ECC-384 private
+    /// keys, populated with [`SwCryptoBackend::import_ecc384_priv_key`].
+    /// Signatures are deterministic (RFC 6979).
+    pub struct SwCryptoBackend {
+        keys: [Option<[u8; 48]>; KEY_SLOT_COUNT],
+    }
+
+    impl Default for SwCryptoBackend {
+        fn default() -> Self {
+            Self {
+                keys: [None; KEY_SLOT_COUNT],
+            }
+        }
+    }
+
+    impl Drop for SwCryptoBackend {
+        fn drop(&mut self) {
+            for key in self.keys.iter_mut().flatten() {
+                key.zeroize();
+            }
+        }
+    }
+
+    impl SwCryptoBackend {
+        /// Store `priv_key` in key slot `key_id`, returning its public key
+        ///
+        /// # Arguments
+        ///
+        /// * `key_id` - Key slot to store the private key in
+        /// * `priv_key` - ECC-384 private key; must be in `[1, n)`
+        pub fn import_ecc384_priv_key(
+            &mut self,
+            key_id: KeyId,
+            priv_key: &Array4x12,
+        ) -> CaliptraResult<Ecc384PubKey> {
+            let priv_key = <[u8; 48]>::from(priv_key);
+            let signing_key = SigningKey::from_slice(&priv_key)
+                .map_err(|_| CaliptraError::DRIVER_ECC384_SCALAR_RANGE_CHECK_FAILED)?;
+            let point = signing_key.verifying_key().to_encoded_point(false);
+            let (Some(x), Some(y)) = (point.x(), point.y()) else {
+                return Err(CaliptraError::DRIVER_ECC384_KEYGEN_PAIRWISE_CONSISTENCY_FAILURE);
+            };
+            let pub_key = Ecc384PubKey {
+                x: array4x12(x),
+                y: array4x12(y),
+            };
+            self.keys[usize::from(key_id)] = Some(priv_key);
+            Ok(pub_key)
+        }
+    }
+
+    impl CryptoBackend for SwCryptoBackend {
+        fn sha384_digest(&mut self, data: &[u8]) -> CaliptraResult<Array4x12> {
+            Ok(array4x12(&Sha384::digest(data)))
+        }
+
+        fn hmac384(&mut self, key: &Array4x12, data: &[u8]) -> CaliptraResult<Array4x12> {
+            let mut ipad = [0x36u8; SHA384_BLOCK_SIZE];
+            let mut opad = [0x5cu8; SHA384_BLOCK_SIZE];
+            for (i, byte) in <[u8; 48]>::from(key).iter().enumerate() {
+                ipad[i] ^= byte;
+                opad[i] ^= byte;
+            }
+            let inner = Sha384::new()
+                .chain_update(ipad)
+                .chain_update(data)
+                .finalize();
+            let tag = Sha384::new()
+                .chain_update(opad)
+                .chain_update(inner)
+                .finalize();
+            ipad.zeroize();
+            opad.zeroize();
+            Ok(array4x12(&tag))
+        }
+
+        fn ecc384_sign(
+            &mut self,
+            priv_key: KeyId,
+            pub_key: &Ecc384PubKey,
+            digest: &Array4x12,
+        ) -> CaliptraResult<Ecc384Signature> {
+            let Some(priv_key) = &self.keys[usize::from(priv_key)] else {
+                return Err(CaliptraError::DRIVER_ECC384_READ_PRIV_KEY_KV_UNKNOWN);
+            };
+            let signing_key = SigningKey::from_slice(priv_key)
+                .map_err(|_| CaliptraError::DRIVER_ECC384_SCALAR_RANGE_CHECK_FAILED)?;
+            let sig: Signature = signing_key
+                .sign_prehash(&<[u8; 48]>::from(digest))
+                .map_err(|_| CaliptraError::DRIVER_ECC384_SIGN_VALIDATION_FAILED)?;
+            let (r, s) = sig.split_bytes();
+            let sig = Ecc384Signature {
+                r: array4x12(&r),
+                s: array4x12(&s),
+            };
+
+            // Match the hardware flow, which verifies each signature it makes
+            if self.ecc384_verify(pub_key, digest, &sig)? != Ecc384Result::Success {
+                return Err(CaliptraError::DRIVER_ECC384_SIGN_VALIDATION_FAILED);
+            }
+            Ok(sig)
+        }
+
+        fn ecc384_verify(
+            &mut self,
+            pub_key: &Ecc384PubKey,
+            digest: &Array4x12,
+            sig: &Ecc384Signature,
+        ) -> CaliptraResult<Ecc384Result> {
+            let point = EncodedPoint::from_affine_coordinates(
+                GenericArray::from_slice(&<[u8; 48]>::from(pub_key.x)),
+                GenericArray::from_slice(&<[u8; 48]>::from(pub_key.y)),
+                false,
+            );
+            let Ok(verifying_key) = VerifyingKey::from_encoded_point(&point) else {
+                return Ok(Ecc384Result::SigVerifyFailed);
+            };
+            let Ok(sig) = Signature::from_scalars(
+                GenericArray::clone_from_slice(&<[u8; 48]>::from(sig.r)),
+                GenericArray::clone_from_slice(&<[u8; 48]>::from(sig.s)),
+            ) else {
+                return Ok(Ecc384Result::SigVerifyFailed);
+            };
+            match verifying_key.verify_prehash(&<[u8; 48]>::from(digest), &sig) {
+                Ok(()) => Ok(Ecc384Result::Success),
+                Err(_) => Ok(Ecc384Result::SigVerifyFailed),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use caliptra_drivers::CaliptraError;
+
+    // RFC 6979 A.2.6 P-384 private key
+    const PRIV_KEY: [u8; 48] = [
+        0x6b, 0x9d, 0x3d, 0xad, 0x2e, 0x1b, 0x8c, 0x1c, 0x05, 0xb1, 0x98, 0x75, 0xb6, 0x65, 0x9f,
+        0x4d, 0xe2, 0x3c, 0x3b, 0x66, 0x7b, 0xf2, 0x97, 0xba, 0x9a, 0xa4, 0x77, 0x40, 0x78, 0x71,
+        0x37, 0xd8, 0x96, 0xd5, 0x72, 0x4e, 0x4c, 0x70, 0xa8, 0x25, 0xf8, 0x72, 0xc9, 0xea, 0x60,
+        0xd2, 0xed, 0xf5,
+    ];
+
+    #[test]
+    fn test_sha384_digest() {
+        // FIPS 180-2 "abc"
+        let expected: [u8; 48] = [
+            0xcb, 0x00, 0x75, 0x3f, 0x45, 0xa3, 0x5e, 0x8b, 0xb5, 0xa0, 0x3d, 0x69, 0x9a, 0xc6,
+            0x50, 0x07, 0x27, 0x2c, 0x32, 0xab, 0x0e, 0xde, 0xd1, 0x63, 0x1a, 0x8b, 0x60, 0x5a,
+            0x43, 0xff, 0x5b, 0xed, 0x80, 0x86, 0x07, 0x2b, 0xa1, 0xe7, 0xcc, 0x23, 0x58, 0xba,
+            0xec, 0xa1, 0x34, 0xc8, 0x25, 0xa7,
+        ];
+        let digest = SwCryptoBackend::default().sha384_digest(b"abc").unwrap();
+        assert_eq!(<[u8; 48]>::from(digest), expected);
+    }
+
+    #[test]
+    fn test_hmac384() {
+        // RFC 4231 test case 2, with the key zero-padded to 48 bytes
+        let mut key = [0u8; 48];
+        key[..4].copy_from_slice(b"Jefe");
+        let expected: [u8; 48] = [
+            0xaf, 0x45, 0xd2, 0xe3, 0x76, 0x48, 0x40, 0x31, 0x61, 0x7f, 0x78, 0xd2, 0xb5, 0x8a,
+            0x6b, 0x1b, 0x9c, 0x7e, 0xf4, 0x64, 0xf5, 0xa0, 0x1b, 0x47, 0xe4, 0x2e, 0xc3, 0x73,
+            0x63, 0x22, 0x44, 0x5e, 0x8e, 0x22, 0x40, 0xca, 0x5e, 0x69, 0xe2, 0xc7, 0x8b, 0x32,
+            0x39, 0xec, 0xfa, 0xb2, 0x16, 0x49,
+        ];
+        let tag = SwCryptoBackend::default()
+            .hmac384(&Array4x12::from(key), b"what do ya want for nothing?")
+            .unwrap();
+        assert_eq!(<[u8; 48]>::from(tag), expected);
+    }
+
+    #[test]
+    fn test_ecc384_sign_verify() {
+        let mut crypto = SwCryptoBackend::default();
+        let pub_key = crypto
+            .import_ecc384_priv_key(KeyId::KeyId7, &Array4x12::from(PRIV_KEY))
+            .unwrap();
+        let digest = crypto.sha384_digest(b"sample").unwrap();
+        let sig = crypto
+            .ecc384_sign(KeyId::KeyId7, &pub_key, &digest)
+            .unwrap();
+
+        // RFC 6979 A.2.6, SHA-384, message "sample"
+        let expected_r: [u8; 48] = [
+            0x94, 0xed, 0xbb, 0x92, 0xa5, 0xec, 0xb8, 0xaa, 0xd4, 0x73, 0x6e, 0x56, 0xc6, 0x91,
+            0x91, 0x6b, 0x3f, 0x88, 0x14, 0x06, 0x66, 0xce, 0x9f, 0xa7, 0x3d, 0x64, 0xc4, 0xea,
+            0x95, 0xad, 0x13, 0x3c, 0x81, 0xa6, 0x48, 0x15, 0x2e, 0x44, 0xac, 0xf9, 0x6e, 0x36,
+            0xdd, 0x1e, 0x80, 0xfa, 0xbe, 0x46,
+        ];
+        let expected_s: [u8; 48] = [
+            0x99, 0xef, 0x4a, 0xeb, 0x15, 0xf1, 0x78, 0xce, 0xa1, 0xfe, 0x40, 0xdb, 0x26, 0x03,
+            0x13, 0x8f, 0x13, 0x0e, 0x74, 0x0a, 0x19, 0x62, 0x45, 0x26, 0x20, 0x3b, 0x63, 0x51,
+            0xd0, 0xa3, 0xa9, 0x4f, 0xa3, 0x29, 0xc1, 0x45, 0x78, 0x6e, 0x67, 0x9e, 0x7b, 0x82,
+            0xc7, 0x1a, 0x38, 0x62, 0x8a, 0xc8,
+        ];
+        assert_eq!(<[u8; 48]>::from(sig.r), expected_r);
+        assert_eq!(<[u8; 48]>::from(sig.s), expected_s);
+        assert_eq!(
+            crypto.ecc384_verify(&pub_key, &digest, &sig).unwrap(),
+            Ecc384Result::Success
+        );
+
+        let other = crypto.sha384_digest(b"test").unwrap();
+        assert_eq!(
+            crypto.ecc384_verify(&pub_key, &other, &sig).unwrap(),
+            Ecc384Result::SigVerifyFailed
+        );
+    }
+
+    #[test]
+    fn test_ecc384_sign_missing_key() {
+        let mut crypto = SwCryptoBackend::default();
+        let pub_key = crypto
+            .import_ecc384_priv_key(KeyId::KeyId7, &Array4x12::from(PRIV_KEY))
+            .unwrap();
+        let digest = crypto.sha384_digest(b"sample").unwrap();
+        assert_eq!(
+            crypto.ecc384_sign(KeyId::KeyId8, &pub_key, &digest),
+            Err(CaliptraError::DRIVER_ECC384_READ_PRIV_KEY_KV_UNKNOWN)
+        );
+    }
+
+    #[test]
+    fn test_import_zero_key() {
+        assert_eq!(
+            SwCryptoBackend::default()
+                .import_ecc384_priv_key(KeyId::KeyId7, &Array4x12::default())
+                .map(|_| ()),
+            Err(CaliptraError::DRIVER_ECC384_SCALAR_RANGE_CHECK_FAILED)
+        );
+    }
+}
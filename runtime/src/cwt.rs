@@ -0,0 +1,228 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    cwt.rs
+
+Abstract:
+
+    File contains CBOR Web Token (RFC 8392) encoding of runtime evidence.
+
+--*/
+
+use crate::Drivers;
+use caliptra_drivers::{Array4x12, CaliptraError, CaliptraResult, Ecc384PrivKeyIn, KeyReadArgs};
+use zeroize::Zeroize;
+
+const CBOR_UINT: u8 = 0;
+const CBOR_NINT: u8 = 1;
+const CBOR_BSTR: u8 = 2;
+const CBOR_TSTR: u8 = 3;
+const CBOR_ARRAY: u8 = 4;
+const CBOR_MAP: u8 = 5;
+const CBOR_TAG: u8 = 6;
+
+/// COSE_Sign1_Tagged (RFC 9052)
+const COSE_SIGN1_TAG: u64 = 18;
+
+/// Protected header of the token: {alg (1): ES384 (-35)}
+const PROTECTED_HEADER: [u8; 4] = [0xA1, 0x01, 0x38, 0x22];
+
+/// Size of the token before the payload, with the largest payload header
+const TOKEN_PREFIX_MAX_SIZE: usize = 2 + 1 + PROTECTED_HEADER.len() + 1 + 5;
+
+const SIGNATURE_SIZE: usize = 96;
+
+/// Minimal CBOR (RFC 8949) encoder writing definite-length items to a buffer
+pub struct CborWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> CborWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Number of bytes written
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    fn write_raw(&mut self, data: &[u8]) -> CaliptraResult<()> {
+        let end = self
+            .len
+            .checked_add(data.len())
+            .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?;
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?
+            .copy_from_slice(data);
+        self.len = end;
+        Ok(())
+    }
+
+    fn write_head(&mut self, major: u8, value: u64) -> CaliptraResult<()> {
+        let major = major << 5;
+        if value < 24 {
+            self.write_raw(&[major | value as u8])
+        } else if value <= u8::MAX as u64 {
+            self.write_raw(&[major | 24, value as u8])
+        } else if value <= u16::MAX as u64 {
+            self.write_raw(&[major | 25])?;
+            self.write_raw(&(value as u16).to_be_bytes())
+        } else if value <= u32::MAX as u64 {
+            self.write_raw(&[major | 26])?;
+            self.write_raw(&(value as u32).to_be_bytes())
+        } else {
+            self.write_raw(&[major | 27])?;
+            self.write_raw(&value.to_be_bytes())
+        }
+    }
+
+    pub fn write_uint(&mut self, value: u64) -> CaliptraResult<()> {
+        self.write_head(CBOR_UINT, value)
+    }
+
+    pub fn write_int(&mut self, value: i64) -> CaliptraResult<()> {
+        if value >= 0 {
+            self.write_head(CBOR_UINT, value as u64)
+        } else {
+            self.write_head(CBOR_NINT, !value as u64)
+        }
+    }
+
+    pub fn write_bstr(&mut self, data: &[u8]) -> CaliptraResult<()> {
+        self.write_head(CBOR_BSTR, data.len() as u64)?;
+        self.write_raw(data)
+    }
+
+    pub fn write_tstr(&mut self, text: &str) -> CaliptraResult<()> {
+        self.write_head(CBOR_TSTR, text.len() as u64)?;
+        self.write_raw(text.as_bytes())
+    }
+
+    pub fn write_array(&mut self, len: usize) -> CaliptraResult<()> {
+        self.write_head(CBOR_ARRAY, len as u64)
+    }
+
+    pub fn write_map(&mut self, len: usize) -> CaliptraResult<()> {
+        self.write_head(CBOR_MAP, len as u64)
+    }
+
+    pub fn write_tag(&mut self, tag: u64) -> CaliptraResult<()> {
+        self.write_head(CBOR_TAG, tag)
+    }
+}
+
+pub struct Cwt;
+impl Cwt {
+    /// Encode a CWT signed by the RT Alias key, as a COSE_Sign1_Tagged
+    /// structure with an ES384 signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `buf` - Buffer to write the token to
+    /// * `write_claims` - Writes the claims map of the token
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - Size of the token
+    pub fn sign(
+        drivers: &mut Drivers,
+        buf: &mut [u8],
+        write_claims: impl FnOnce(&mut CborWriter) -> CaliptraResult<()>,
+    ) -> CaliptraResult<usize> {
+        // Encode the claims after room for the token prefix, whose size
+        // depends on the size of the claims
+        let claims_buf = buf
+            .get_mut(TOKEN_PREFIX_MAX_SIZE..)
+            .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?;
+        let mut claims = CborWriter::new(claims_buf);
+        write_claims(&mut claims)?;
+        let claims_len = claims.len();
+
+        let mut prefix_buf = [0u8; TOKEN_PREFIX_MAX_SIZE];
+        let mut prefix = CborWriter::new(&mut prefix_buf);
+        prefix.write_tag(COSE_SIGN1_TAG)?;
+        prefix.write_array(4)?;
+        prefix.write_bstr(&PROTECTED_HEADER)?;
+        prefix.write_map(0)?;
+        let payload_head_start = prefix.len();
+        prefix.write_head(CBOR_BSTR, claims_len as u64)?;
+        let prefix_len = prefix.len();
+
+        buf.copy_within(
+            TOKEN_PREFIX_MAX_SIZE..TOKEN_PREFIX_MAX_SIZE + claims_len,
+            prefix_len,
+        );
+        buf.get_mut(..prefix_len)
+            .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?
+            .copy_from_slice(&prefix_buf[..prefix_len]);
+        let payload_len = prefix_len + claims_len;
+
+        let signature = Self::sign_payload(
+            drivers,
+            prefix_buf
+                .get(payload_head_start..prefix_len)
+                .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?,
+            buf.get(prefix_len..payload_len)
+                .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?,
+        )?;
+
+        let mut token = CborWriter::new(
+            buf.get_mut(payload_len..)
+                .ok_or(CaliptraError::RUNTIME_CWT_ENCODING_FAILED)?,
+        );
+        token.write_bstr(&signature)?;
+        Ok(payload_len + token.len())
+    }
+
+    /// Sign the Sig_structure of a COSE_Sign1 with the RT Alias key
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `payload_head` - CBOR head of the payload byte string
+    /// * `claims` - Encoded claims map
+    fn sign_payload(
+        drivers: &mut Drivers,
+        payload_head: &[u8],
+        claims: &[u8],
+    ) -> CaliptraResult<[u8; SIGNATURE_SIZE]> {
+        // ["Signature1", protected, external_aad, payload]
+        let mut sig_structure_buf = [0u8; 32];
+        let mut sig_structure = CborWriter::new(&mut sig_structure_buf);
+        sig_structure.write_array(4)?;
+        sig_structure.write_tstr("Signature1")?;
+        sig_structure.write_bstr(&PROTECTED_HEADER)?;
+        sig_structure.write_bstr(&[])?;
+        let sig_structure_len = sig_structure.len();
+
+        let mut digest = Array4x12::default();
+        let mut op = drivers.sha384.digest_init()?;
+        op.update(&sig_structure_buf[..sig_structure_len])?;
+        op.update(payload_head)?;
+        op.update(claims)?;
+        op.finalize(&mut digest)?;
+
+        let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+        let pub_key = drivers.persistent_data.get().fht.rt_dice_pub_key;
+        let priv_key = Ecc384PrivKeyIn::Key(KeyReadArgs::new(key_id_rt_priv_key));
+        let result = drivers
+            .ecc384
+            .sign(&priv_key, &pub_key, &digest, &mut drivers.trng);
+        digest.0.zeroize();
+        let sig = result?;
+
+        let r: [u8; 48] = sig.r.into();
+        let s: [u8; 48] = sig.s.into();
+        let mut signature = [0u8; SIGNATURE_SIZE];
+        signature[..48].copy_from_slice(&r);
+        signature[48..].copy_from_slice(&s);
+        Ok(signature)
+    }
+}
@@ -20,8 +20,8 @@ use caliptra_common::mailbox_api::{
 use crate::Drivers;
 
 use caliptra_drivers::{
-    hand_off::DataStore, CaliptraError, CaliptraResult, DataVault, Ecc384Scalar, Ecc384Signature,
-    PersistentData,
+    hand_off::DataStore, CaliptraError, CaliptraResult, DataVault, DccmSlice, Ecc384Scalar,
+    Ecc384Signature, PersistentData,
 };
 use caliptra_x509::{Ecdsa384CertBuilder, Ecdsa384Signature};
 use zerocopy::IntoBytes;
@@ -44,7 +44,8 @@ impl IDevIdCertCmd {
                 s: cmd.signature_s,
             };
 
-            let Some(builder) = Ecdsa384CertBuilder::new(&cmd.tbs[..cmd.tbs_size as usize], &sig) else {
+            let Some(builder) = Ecdsa384CertBuilder::new(&cmd.tbs[..cmd.tbs_size as usize], &sig)
+            else {
                 return Err(CaliptraError::RUNTIME_GET_IDEVID_CERT_FAILED);
             };
 
@@ -201,9 +202,10 @@ pub fn copy_ldevid_cert(
     persistent_data: &PersistentData,
     cert: &mut [u8],
 ) -> CaliptraResult<usize> {
-    let tbs = persistent_data
-        .ldevid_tbs
-        .get(..persistent_data.fht.ldevid_tbs_size.into());
+    let tbs = handoff_tbs(
+        persistent_data.fht.ldevid_tbs_addr,
+        persistent_data.fht.ldevid_tbs_size,
+    );
     let sig = ldevid_dice_sign(persistent_data, dv)?;
     cert_from_tbs_and_sig(tbs, &sig, cert)
         .map_err(|_| CaliptraError::RUNTIME_GET_LDEVID_CERT_FAILED)
@@ -302,9 +304,10 @@ pub fn copy_fmc_alias_cert(
     persistent_data: &PersistentData,
     cert: &mut [u8],
 ) -> CaliptraResult<usize> {
-    let tbs = persistent_data
-        .fmcalias_tbs
-        .get(..persistent_data.fht.fmcalias_tbs_size.into());
+    let tbs = handoff_tbs(
+        persistent_data.fht.fmcalias_tbs_addr,
+        persistent_data.fht.fmcalias_tbs_size,
+    );
     let sig = fmc_dice_sign(persistent_data, dv)?;
     cert_from_tbs_and_sig(tbs, &sig, cert)
         .map_err(|_| CaliptraError::RUNTIME_GET_FMC_ALIAS_CERT_FAILED)
@@ -332,6 +335,23 @@ pub fn copy_rt_alias_cert(
         .map_err(|_| CaliptraError::RUNTIME_GET_RT_ALIAS_CERT_FAILED)
 }
 
+/// Retrieve a TBS buffer handed off by ROM in the FHT
+///
+/// # Arguments
+///
+/// * `addr` - Address of the TBS, which must lie within DCCM
+/// * `size` - Size of the TBS in bytes
+///
+/// # Returns
+///
+/// * `Option<&[u8]>` - The TBS, or None if the range is not within DCCM
+fn handoff_tbs(addr: u32, size: u16) -> Option<&'static [u8]> {
+    let tbs = DccmSlice::<u8>::new(addr, size.into()).ok()?;
+    // Safety: ROM places the TBS buffers in persistent data, which Runtime
+    // Firmware never writes.
+    Some(unsafe { tbs.as_slice() })
+}
+
 /// Create a certificate from a tbs and a signature and write the output to `cert`
 ///
 /// # Arguments
@@ -367,3 +387,83 @@ fn cert_from_tbs_and_sig(
 
     Ok(size)
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{CryptoBackend, SwCryptoBackend};
+    use caliptra_drivers::{Array4x12, KeyId};
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNumContext;
+    use openssl::ec::{EcGroup, EcKey, EcPoint};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::x509::{X509NameBuilder, X509};
+    use x509_parser::prelude::{FromDer, X509Certificate};
+
+    #[test]
+    fn test_idevid_cert() {
+        // Use openssl to lay out a TBS, then sign it with the software backend
+        let group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+        let tmp_key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "Test IDevID").unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&tmp_key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(&tmp_key, MessageDigest::sha384()).unwrap();
+        let der = builder.build().to_der().unwrap();
+        let (_, parsed) = X509Certificate::from_der(&der).unwrap();
+        let tbs = parsed.tbs_certificate.as_ref();
+
+        let mut crypto = SwCryptoBackend::default();
+        let pub_key = crypto
+            .import_ecc384_priv_key(KeyId::KeyId7, &Array4x12::from([0x22; 48]))
+            .unwrap();
+        let digest = crypto.sha384_digest(tbs).unwrap();
+        let sig = crypto
+            .ecc384_sign(KeyId::KeyId7, &pub_key, &digest)
+            .unwrap();
+
+        let mut cmd = GetIdevCertReq {
+            tbs_size: tbs.len() as u32,
+            signature_r: sig.r.into(),
+            signature_s: sig.s.into(),
+            ..Default::default()
+        };
+        cmd.tbs[..tbs.len()].copy_from_slice(tbs);
+        let Ok(MailboxResp::GetIdevCert(resp)) = IDevIdCertCmd::execute(cmd.as_bytes()) else {
+            panic!("IDevIdCertCmd failed");
+        };
+
+        let cert = X509::from_der(&resp.cert[..resp.cert_size as usize]).unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let point = EcPoint::from_bytes(&group, &pub_key.to_der(), &mut ctx).unwrap();
+        let idevid_key =
+            PKey::from_ec_key(EcKey::from_public_key(&group, &point).unwrap()).unwrap();
+        assert!(cert.verify(&idevid_key).unwrap());
+        assert!(!cert.verify(&tmp_key).unwrap());
+    }
+
+    #[test]
+    fn test_idevid_cert_bad_tbs_size() {
+        let cmd = GetIdevCertReq {
+            tbs_size: GetIdevCertReq::DATA_MAX_SIZE as u32 + 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            IDevIdCertCmd::execute(cmd.as_bytes()).map(|_| ()),
+            Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)
+        );
+    }
+}
@@ -14,10 +14,11 @@ Abstract:
 
 use crate::Drivers;
 use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::crypto::lms_alias_key_gen;
 use caliptra_common::mailbox_api::MailboxResp;
 use caliptra_drivers::{
-    hmac384_kdf, Array4x12, CaliptraError, CaliptraResult, Ecc384Seed, Hmac384Key, KeyReadArgs,
-    KeyUsage, KeyWriteArgs,
+    Array4x12, CaliptraError, CaliptraResult, Hmac384Key, Kdf, KeyUsage, KeyWriteArgs,
+    LmsAlgorithmType, KDF_LABEL_RT_ALIAS_LMS_KEYGEN, KDF_LABEL_ZERO_RT_CDI,
 };
 use dpe::U8Bool;
 
@@ -54,12 +55,10 @@ impl DisableAttestationCmd {
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn zero_rt_cdi(drivers: &mut Drivers) -> CaliptraResult<()> {
         let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
-        hmac384_kdf(
-            &mut drivers.hmac384,
+        Kdf::new(&mut drivers.hmac384, &mut drivers.trng).derive(
             Hmac384Key::Array4x12(&Array4x12::default()),
-            b"zero_rt_cdi",
+            KDF_LABEL_ZERO_RT_CDI,
             None,
-            &mut drivers.trng,
             KeyWriteArgs::new(
                 key_id_rt_cdi,
                 KeyUsage::default()
@@ -83,18 +82,26 @@ impl DisableAttestationCmd {
     fn generate_dice_key(drivers: &mut Drivers) -> CaliptraResult<()> {
         let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
         let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
-        let pub_key = drivers.ecc384.key_pair(
-            &Ecc384Seed::Key(KeyReadArgs::new(key_id_rt_cdi)),
-            &Array4x12::default(),
-            &mut drivers.trng,
-            KeyWriteArgs::new(
-                key_id_rt_priv_key,
-                KeyUsage::default().set_ecc_private_key_en(),
-            )
-            .into(),
-        )?;
+        let pub_key =
+            drivers
+                .ecc384
+                .keygen_to_kv(key_id_rt_cdi, &mut drivers.trng, key_id_rt_priv_key)?;
         drivers.persistent_data.get_mut().fht.rt_dice_pub_key = pub_key;
 
+        // Replace the LMS alias key too if FMC generated one
+        let fht = &drivers.persistent_data.get().fht;
+        if fht.rt_dice_lms_pub_key.tree_type != LmsAlgorithmType::LmsReserved {
+            let lms_pub_key = lms_alias_key_gen(
+                &mut drivers.hmac384,
+                &mut drivers.trng,
+                &mut drivers.sha256,
+                &drivers.lms,
+                key_id_rt_cdi,
+                KDF_LABEL_RT_ALIAS_LMS_KEYGEN,
+            )?;
+            drivers.persistent_data.get_mut().fht.rt_dice_lms_pub_key = lms_pub_key;
+        }
+
         Ok(())
     }
 }
@@ -20,9 +20,10 @@ use caliptra_common::keyids::{
     KEY_ID_DPE_CDI, KEY_ID_DPE_PRIV_KEY, KEY_ID_EXPORTED_DPE_CDI, KEY_ID_TMP,
 };
 use caliptra_drivers::{
-    cprintln, hmac384_kdf, Array4x12, Ecc384, Ecc384PrivKeyIn, Ecc384PubKey, Ecc384Scalar,
-    Ecc384Seed, Hmac384, Hmac384Data, Hmac384Key, Hmac384Tag, KeyId, KeyReadArgs, KeyUsage,
-    KeyVault, KeyWriteArgs, Sha384, Sha384DigestOp, Trng,
+    cprintln, Array4x12, Array4x8, Ecc256PrivKey, Ecc256PubKey, Ecc256Scalar, Ecc256Sw, Ecc384,
+    Ecc384PrivKeyIn, Ecc384PubKey, Ecc384Scalar, Ecc384Seed, Hmac384, Hmac384Data, Hmac384Key,
+    Hmac384Tag, Kdf, KeyId, KeyReadArgs, KeyUsage, KeyVault, KeyWriteArgs, Sha256, Sha256Alg,
+    Sha256DigestOp, Sha3, Sha384, Sha384DigestOp, Sha3Mode, Trng, KDF_LABEL_DPE_CDI,
 };
 use crypto::{AlgLen, Crypto, CryptoBuf, CryptoError, Digest, EcdsaPub, EcdsaSig, Hasher};
 use dpe::{
@@ -36,7 +37,55 @@ use zeroize::Zeroize;
 pub const EXPORTED_HANDLES_NUM: usize = 1;
 pub type ExportedCdiHandles = [Option<(KeyId, ExportedCdiHandle)>; EXPORTED_HANDLES_NUM];
 
+/// Hash function used for DPE measurements and KDF contexts
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DpeHashAlgorithm {
+    /// SHA2-256 on the hardware accelerator
+    Sha256,
+
+    /// SHA2-384 on the hardware accelerator
+    Sha384,
+
+    /// SHA3-384 in software, for profiles that require it
+    Sha3_384,
+}
+
+/// Implementation choices that vary between DPE profiles
+#[derive(Debug, Copy, Clone)]
+pub struct DpeProfileDescriptor {
+    pub hash_alg: DpeHashAlgorithm,
+}
+
+#[cfg(all(feature = "dpe-profile-p384", feature = "dpe-profile-p256"))]
+compile_error!("The dpe-profile-p384 and dpe-profile-p256 features are mutually exclusive");
+
+#[cfg(not(any(feature = "dpe-profile-p384", feature = "dpe-profile-p256")))]
+compile_error!("Either the dpe-profile-p384 or the dpe-profile-p256 feature must be enabled");
+
+/// Descriptor for the DPE profile this firmware implements
+/// (`DPE_PROFILE_IROT_P384_SHA384`).
+#[cfg(feature = "dpe-profile-p384")]
+pub const DPE_PROFILE: DpeProfileDescriptor = DpeProfileDescriptor {
+    hash_alg: DpeHashAlgorithm::Sha384,
+};
+
+/// Descriptor for the DPE profile this firmware implements
+/// (`DPE_PROFILE_IROT_P256_SHA256`).
+#[cfg(feature = "dpe-profile-p256")]
+pub const DPE_PROFILE: DpeProfileDescriptor = DpeProfileDescriptor {
+    hash_alg: DpeHashAlgorithm::Sha256,
+};
+
+/// Fit a SHA2-384 measurement to the TCI size of the DPE profile by keeping
+/// its leftmost bytes.
+pub fn tci_from_measurement(measurement: &[u8; 48]) -> [u8; dpe::DPE_PROFILE.get_tci_size()] {
+    let mut tci = [0; dpe::DPE_PROFILE.get_tci_size()];
+    tci.copy_from_slice(&measurement[..tci.len()]);
+    tci
+}
+
 pub struct DpeCrypto<'a> {
+    sha256: &'a mut Sha256,
     sha384: &'a mut Sha384,
     trng: &'a mut Trng,
     ecc384: &'a mut Ecc384,
@@ -46,11 +95,16 @@ pub struct DpeCrypto<'a> {
     key_id_rt_cdi: KeyId,
     key_id_rt_priv_key: KeyId,
     exported_cdi_slots: &'a mut ExportedCdiHandles,
+    /// Private key of the last P-256 key pair derived, and the key vault
+    /// slot that stands for it in `PrivKey`
+    p256_priv_key: Option<(KeyId, Ecc256PrivKey)>,
+    wdt_pet: Option<fn()>,
 }
 
 impl<'a> DpeCrypto<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        sha256: &'a mut Sha256,
         sha384: &'a mut Sha384,
         trng: &'a mut Trng,
         ecc384: &'a mut Ecc384,
@@ -62,6 +116,7 @@ impl<'a> DpeCrypto<'a> {
         exported_cdi_slots: &'a mut ExportedCdiHandles,
     ) -> Self {
         Self {
+            sha256,
             sha384,
             trng,
             ecc384,
@@ -71,6 +126,21 @@ impl<'a> DpeCrypto<'a> {
             key_id_rt_cdi,
             key_id_rt_priv_key,
             exported_cdi_slots,
+            p256_priv_key: None,
+            wdt_pet: None,
+        }
+    }
+
+    /// Restart the watchdog with `wdt_pet` after each key generation and
+    /// signature, so that commands issuing certificates do not trip it.
+    pub fn with_wdt_pet(mut self, wdt_pet: fn()) -> Self {
+        self.wdt_pet = Some(wdt_pet);
+        self
+    }
+
+    fn pet_wdt(&self) {
+        if let Some(wdt_pet) = self.wdt_pet {
+            wdt_pet();
         }
     }
 
@@ -81,32 +151,20 @@ impl<'a> DpeCrypto<'a> {
         info: &[u8],
         key_id: KeyId,
     ) -> Result<<DpeCrypto<'a> as crypto::Crypto>::Cdi, CryptoError> {
-        match algs {
-            AlgLen::Bit256 => Err(CryptoError::Size),
-            AlgLen::Bit384 => {
-                let mut hasher = self.hash_initialize(algs)?;
-                hasher.update(measurement.bytes())?;
-                hasher.update(info)?;
-                let context = hasher.finish()?;
-
-                hmac384_kdf(
-                    self.hmac384,
-                    KeyReadArgs::new(self.key_id_rt_cdi).into(),
-                    b"derive_cdi",
-                    Some(context.bytes()),
-                    self.trng,
-                    KeyWriteArgs::new(
-                        key_id,
-                        KeyUsage::default()
-                            .set_hmac_key_en()
-                            .set_ecc_key_gen_seed_en(),
-                    )
-                    .into(),
-                )
-                .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
-                Ok(key_id)
-            }
-        }
+        let mut hasher = self.hash_initialize(algs)?;
+        hasher.update(measurement.bytes())?;
+        hasher.update(info)?;
+        let context = hasher.finish()?;
+
+        Kdf::new(self.hmac384, self.trng)
+            .derive_cdi(
+                self.key_id_rt_cdi,
+                KDF_LABEL_DPE_CDI,
+                Some(context.bytes()),
+                key_id,
+            )
+            .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+        Ok(key_id)
     }
 
     fn derive_key_pair_inner(
@@ -118,18 +176,47 @@ impl<'a> DpeCrypto<'a> {
         key_id: KeyId,
     ) -> Result<(<DpeCrypto<'a> as crypto::Crypto>::PrivKey, EcdsaPub), CryptoError> {
         match algs {
-            AlgLen::Bit256 => Err(CryptoError::Size),
+            AlgLen::Bit256 => {
+                // The P-256 key is generated in software, so its seed is
+                // derived from the CDI in the key vault like the P-384 seed,
+                // but returned to firmware instead of a key vault slot.
+                let mut seed = Array4x12::default();
+                Kdf::new(self.hmac384, self.trng)
+                    .derive(
+                        KeyReadArgs::new(*cdi).into(),
+                        label,
+                        Some(info),
+                        Hmac384Tag::Array4x12(&mut seed),
+                    )
+                    .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+                let key_pair = Ecc256Sw::key_pair(&seed);
+                seed.zeroize();
+                let (priv_key, pub_key) =
+                    key_pair.map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+                self.p256_priv_key = Some((key_id, priv_key));
+                self.pet_wdt();
+
+                let pub_key = EcdsaPub {
+                    x: CryptoBuf::new(&<[u8; AlgLen::Bit256.size()]>::from(pub_key.x))
+                        .map_err(|_| CryptoError::Size)?,
+                    y: CryptoBuf::new(&<[u8; AlgLen::Bit256.size()]>::from(pub_key.y))
+                        .map_err(|_| CryptoError::Size)?,
+                };
+                Ok((key_id, pub_key))
+            }
             AlgLen::Bit384 => {
-                hmac384_kdf(
-                    self.hmac384,
-                    KeyReadArgs::new(*cdi).into(),
-                    label,
-                    Some(info),
-                    self.trng,
-                    KeyWriteArgs::new(KEY_ID_TMP, KeyUsage::default().set_ecc_key_gen_seed_en())
+                Kdf::new(self.hmac384, self.trng)
+                    .derive(
+                        KeyReadArgs::new(*cdi).into(),
+                        label,
+                        Some(info),
+                        KeyWriteArgs::new(
+                            KEY_ID_TMP,
+                            KeyUsage::default().set_ecc_key_gen_seed_en(),
+                        )
                         .into(),
-                )
-                .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+                    )
+                    .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
 
                 let pub_key = self
                     .ecc384
@@ -141,6 +228,8 @@ impl<'a> DpeCrypto<'a> {
                             .into(),
                     )
                     .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+                self.pet_wdt();
+
                 let pub_key = EcdsaPub {
                     x: CryptoBuf::new(&<[u8; AlgLen::Bit384.size()]>::from(pub_key.x))
                         .map_err(|_| CryptoError::Size)?,
@@ -164,6 +253,66 @@ impl<'a> DpeCrypto<'a> {
         }
         None
     }
+
+    /// Sign `digest` with the P-384 key `priv_key`. Digests shorter than
+    /// 48 bytes are zero-extended on the left, as ECDSA reads them.
+    fn ecdsa384_sign(
+        &mut self,
+        digest: &[u8],
+        priv_key: KeyId,
+        pub_key: &EcdsaPub,
+    ) -> Result<EcdsaSig, CryptoError> {
+        let priv_key_args = KeyReadArgs::new(priv_key);
+        let ecc_priv_key = Ecc384PrivKeyIn::Key(priv_key_args);
+
+        const SIZE: usize = AlgLen::Bit384.size();
+        let mut x = [0u8; SIZE];
+        let mut y = [0u8; SIZE];
+        x.get_mut(..SIZE)
+            .ok_or(CryptoError::CryptoLibError(0))?
+            .copy_from_slice(
+                pub_key
+                    .x
+                    .bytes()
+                    .get(..SIZE)
+                    .ok_or(CryptoError::CryptoLibError(0))?,
+            );
+        y.get_mut(..SIZE)
+            .ok_or(CryptoError::CryptoLibError(0))?
+            .copy_from_slice(
+                pub_key
+                    .y
+                    .bytes()
+                    .get(..SIZE)
+                    .ok_or(CryptoError::CryptoLibError(0))?,
+            );
+        let ecc_pub_key = Ecc384PubKey {
+            x: Ecc384Scalar::from(x),
+            y: Ecc384Scalar::from(y),
+        };
+
+        let mut digest_arr = [0u8; SIZE];
+        digest_arr
+            .get_mut(SIZE.checked_sub(digest.len()).ok_or(CryptoError::Size)?..)
+            .ok_or(CryptoError::CryptoLibError(0))?
+            .copy_from_slice(digest);
+
+        let sig = self
+            .ecc384
+            .sign(
+                &ecc_priv_key,
+                &ecc_pub_key,
+                &Ecc384Scalar::from(digest_arr),
+                self.trng,
+            )
+            .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+        self.pet_wdt();
+
+        let r = CryptoBuf::new(&<[u8; SIZE]>::from(sig.r))?;
+        let s = CryptoBuf::new(&<[u8; SIZE]>::from(sig.s))?;
+
+        Ok(EcdsaSig { r, s })
+    }
 }
 
 impl Drop for DpeCrypto<'_> {
@@ -174,29 +323,57 @@ impl Drop for DpeCrypto<'_> {
     }
 }
 
-pub struct DpeHasher<'a> {
-    op: Sha384DigestOp<'a>,
+// The software SHA3 state is only used by profiles that select it; keep it
+// inline rather than requiring an allocator.
+#[allow(clippy::large_enum_variant)]
+pub enum DpeHasher<'a> {
+    Sha256(<Sha256 as Sha256Alg>::DigestOp<'a>),
+    Sha384(Sha384DigestOp<'a>),
+    Sha3_384(Sha3),
 }
 
 impl<'a> DpeHasher<'a> {
     pub fn new(op: Sha384DigestOp<'a>) -> Self {
-        Self { op }
+        Self::Sha384(op)
     }
 }
 
 impl<'a> Hasher for DpeHasher<'a> {
     fn update(&mut self, bytes: &[u8]) -> Result<(), CryptoError> {
-        self.op
-            .update(bytes)
-            .map_err(|e| CryptoError::HashError(u32::from(e)))
+        match self {
+            Self::Sha256(op) => op
+                .update(bytes)
+                .map_err(|e| CryptoError::HashError(u32::from(e))),
+            Self::Sha384(op) => op
+                .update(bytes)
+                .map_err(|e| CryptoError::HashError(u32::from(e))),
+            Self::Sha3_384(op) => {
+                op.update(bytes);
+                Ok(())
+            }
+        }
     }
 
     fn finish(self) -> Result<Digest, CryptoError> {
-        let mut digest = Array4x12::default();
-        self.op
-            .finalize(&mut digest)
-            .map_err(|e| CryptoError::HashError(u32::from(e)))?;
-        Digest::new(<[u8; AlgLen::Bit384.size()]>::from(digest).as_ref())
+        match self {
+            Self::Sha256(op) => {
+                let mut array = Array4x8::default();
+                op.finalize(&mut array)
+                    .map_err(|e| CryptoError::HashError(u32::from(e)))?;
+                Digest::new(&<[u8; AlgLen::Bit256.size()]>::from(array))
+            }
+            Self::Sha384(op) => {
+                let mut array = Array4x12::default();
+                op.finalize(&mut array)
+                    .map_err(|e| CryptoError::HashError(u32::from(e)))?;
+                Digest::new(&<[u8; AlgLen::Bit384.size()]>::from(array))
+            }
+            Self::Sha3_384(op) => {
+                let mut digest = [0u8; AlgLen::Bit384.size()];
+                op.finalize(&mut digest);
+                Digest::new(&digest)
+            }
+        }
     }
 }
 
@@ -218,15 +395,25 @@ impl<'a> Crypto for DpeCrypto<'a> {
     }
 
     fn hash_initialize(&mut self, algs: AlgLen) -> Result<Self::Hasher<'_>, CryptoError> {
-        match algs {
-            AlgLen::Bit256 => Err(CryptoError::Size),
-            AlgLen::Bit384 => {
+        match (algs, DPE_PROFILE.hash_alg) {
+            (AlgLen::Bit256, DpeHashAlgorithm::Sha256) => {
+                let op = self
+                    .sha256
+                    .digest_init()
+                    .map_err(|e| CryptoError::HashError(u32::from(e)))?;
+                Ok(DpeHasher::Sha256(op))
+            }
+            (AlgLen::Bit384, DpeHashAlgorithm::Sha384) => {
                 let op = self
                     .sha384
                     .digest_init()
                     .map_err(|e| CryptoError::HashError(u32::from(e)))?;
                 Ok(DpeHasher::new(op))
             }
+            (AlgLen::Bit384, DpeHashAlgorithm::Sha3_384) => {
+                Ok(DpeHasher::Sha3_384(Sha3::new(Sha3Mode::Sha3_384)))
+            }
+            _ => Err(CryptoError::Size),
         }
     }
 
@@ -306,7 +493,7 @@ impl<'a> Crypto for DpeCrypto<'a> {
 
     fn ecdsa_sign_with_alias(
         &mut self,
-        algs: AlgLen,
+        _algs: AlgLen,
         digest: &Digest,
     ) -> Result<EcdsaSig, CryptoError> {
         let pub_key = EcdsaPub {
@@ -315,7 +502,9 @@ impl<'a> Crypto for DpeCrypto<'a> {
             y: CryptoBuf::new(&<[u8; AlgLen::Bit384.size()]>::from(self.rt_pub_key.y))
                 .map_err(|_| CryptoError::Size)?,
         };
-        self.ecdsa_sign_with_derived(algs, digest, &self.key_id_rt_priv_key.clone(), &pub_key)
+        // The RT alias key is a P-384 key whatever the profile; ECDSA takes
+        // the SHA2-256 digests of the P-256 profile as they are.
+        self.ecdsa384_sign(digest.bytes(), self.key_id_rt_priv_key, &pub_key)
     }
 
     fn ecdsa_sign_with_derived(
@@ -326,63 +515,35 @@ impl<'a> Crypto for DpeCrypto<'a> {
         pub_key: &EcdsaPub,
     ) -> Result<EcdsaSig, CryptoError> {
         match algs {
-            AlgLen::Bit256 => Err(CryptoError::Size),
-            AlgLen::Bit384 => {
-                let priv_key_args = KeyReadArgs::new(*priv_key);
-                let ecc_priv_key = Ecc384PrivKeyIn::Key(priv_key_args);
-
-                const SIZE: usize = AlgLen::Bit384.size();
-                let mut x = [0u8; SIZE];
-                let mut y = [0u8; SIZE];
-                x.get_mut(..SIZE)
-                    .ok_or(CryptoError::CryptoLibError(0))?
-                    .copy_from_slice(
-                        pub_key
-                            .x
-                            .bytes()
-                            .get(..SIZE)
-                            .ok_or(CryptoError::CryptoLibError(0))?,
-                    );
-                y.get_mut(..SIZE)
-                    .ok_or(CryptoError::CryptoLibError(0))?
-                    .copy_from_slice(
-                        pub_key
-                            .y
-                            .bytes()
-                            .get(..SIZE)
-                            .ok_or(CryptoError::CryptoLibError(0))?,
-                    );
-                let ecc_pub_key = Ecc384PubKey {
-                    x: Ecc384Scalar::from(x),
-                    y: Ecc384Scalar::from(y),
+            AlgLen::Bit256 => {
+                const SIZE: usize = AlgLen::Bit256.size();
+                let priv_key = match &self.p256_priv_key {
+                    Some((key_id, priv_key_256)) if key_id == priv_key => priv_key_256,
+                    _ => Err(CryptoError::CryptoLibError(0))?,
+                };
+                let ecc_pub_key = Ecc256PubKey {
+                    x: Ecc256Scalar::from(
+                        <[u8; SIZE]>::try_from(pub_key.x.bytes()).map_err(|_| CryptoError::Size)?,
+                    ),
+                    y: Ecc256Scalar::from(
+                        <[u8; SIZE]>::try_from(pub_key.y.bytes()).map_err(|_| CryptoError::Size)?,
+                    ),
                 };
+                let digest = Ecc256Scalar::from(
+                    <[u8; SIZE]>::try_from(digest.bytes().get(..SIZE).ok_or(CryptoError::Size)?)
+                        .map_err(|_| CryptoError::Size)?,
+                );
 
-                let mut digest_arr = [0u8; SIZE];
-                digest_arr
-                    .get_mut(..SIZE)
-                    .ok_or(CryptoError::CryptoLibError(0))?
-                    .copy_from_slice(
-                        digest
-                            .bytes()
-                            .get(..SIZE)
-                            .ok_or(CryptoError::CryptoLibError(0))?,
-                    );
-
-                let sig = self
-                    .ecc384
-                    .sign(
-                        &ecc_priv_key,
-                        &ecc_pub_key,
-                        &Ecc384Scalar::from(digest_arr),
-                        self.trng,
-                    )
+                let sig = Ecc256Sw::sign(priv_key, &ecc_pub_key, &digest, self.trng)
                     .map_err(|e| CryptoError::CryptoLibError(u32::from(e)))?;
+                self.pet_wdt();
 
                 let r = CryptoBuf::new(&<[u8; SIZE]>::from(sig.r))?;
                 let s = CryptoBuf::new(&<[u8; SIZE]>::from(sig.s))?;
 
                 Ok(EcdsaSig { r, s })
             }
+            AlgLen::Bit384 => self.ecdsa384_sign(digest.bytes(), *priv_key, pub_key),
         }
     }
 }
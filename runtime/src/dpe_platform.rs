@@ -15,7 +15,7 @@ Abstract:
 use core::cmp::min;
 
 use arrayvec::ArrayVec;
-use caliptra_drivers::cprintln;
+use caliptra_common::log_info;
 use caliptra_image_types::{ImageHeader, ImageManifest};
 use caliptra_x509::{NotAfter, NotBefore};
 use crypto::Digest;
@@ -30,7 +30,7 @@ use platform::{
 };
 use zerocopy::IntoBytes;
 
-use crate::{subject_alt_name::AddSubjectAltNameCmd, MAX_CERT_CHAIN_SIZE};
+use crate::{cert_policy::CertPolicy, subject_alt_name::AddSubjectAltNameCmd, MAX_CERT_CHAIN_SIZE};
 
 pub struct DpePlatform<'a> {
     auto_init_locality: u32,
@@ -40,6 +40,7 @@ pub struct DpePlatform<'a> {
     not_after: &'a NotAfter,
     dmtf_device_info: Option<&'a [u8]>,
     ueid: Option<&'a [u8; 17]>,
+    cert_policy: Option<CertPolicy>,
 }
 
 pub const VENDOR_ID: u32 = u32::from_be_bytes(*b"CTRA");
@@ -54,6 +55,7 @@ impl<'a> DpePlatform<'a> {
         not_after: &'a NotAfter,
         dmtf_device_info: Option<&'a [u8]>,
         ueid: Option<&'a [u8; 17]>,
+        cert_policy: Option<CertPolicy>,
     ) -> Self {
         Self {
             auto_init_locality,
@@ -63,6 +65,7 @@ impl<'a> DpePlatform<'a> {
             not_after,
             dmtf_device_info,
             ueid,
+            cert_policy,
         }
     }
 }
@@ -159,7 +162,7 @@ impl Platform for DpePlatform<'_> {
     }
 
     fn write_str(&mut self, str: &str) -> Result<(), PlatformError> {
-        cprintln!("{}", str);
+        log_info!("{}", str);
         Ok(())
     }
 
@@ -168,9 +171,20 @@ impl Platform for DpePlatform<'_> {
         not_before
             .try_extend_from_slice(&self.not_before.value)
             .map_err(|_| PlatformError::CertValidityError(0))?;
+        // Clamp notAfter to the owner policy. GeneralizedTime values are
+        // fixed width, so they order the same as their bytes.
+        let mut not_after_value = &self.not_after.value;
+        if let Some(max_not_after) = self.cert_policy.as_ref().and_then(|p| p.max_not_after()) {
+            if *max_not_after <= self.not_before.value {
+                return Err(PlatformError::CertValidityError(0));
+            }
+            if max_not_after < not_after_value {
+                not_after_value = max_not_after;
+            }
+        }
         let mut not_after = ArrayVec::new();
         not_after
-            .try_extend_from_slice(&self.not_after.value)
+            .try_extend_from_slice(not_after_value)
             .map_err(|_| PlatformError::CertValidityError(0))?;
         Ok(CertValidity {
             not_before,
@@ -179,6 +193,13 @@ impl Platform for DpePlatform<'_> {
     }
 
     fn get_subject_alternative_name(&mut self) -> Result<SubjectAltName, PlatformError> {
+        if self
+            .cert_policy
+            .as_ref()
+            .is_some_and(|p| p.denies_subject_alt_name())
+        {
+            return Err(PlatformError::NotImplemented);
+        }
         match &self.dmtf_device_info {
             None => Err(PlatformError::NotImplemented),
             Some(dmtf_device_info) => {
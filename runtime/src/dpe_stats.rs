@@ -0,0 +1,61 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    dpe_stats.rs
+
+Abstract:
+
+    File contains GetDpeStats mailbox command, available in test builds only.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{GetDpeStatsResp, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use dpe::{
+    context::{Context, ContextState},
+    MAX_HANDLES,
+};
+
+pub struct GetDpeStatsCmd;
+impl GetDpeStatsCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &Drivers) -> CaliptraResult<MailboxResp> {
+        let pdata = drivers.persistent_data.get();
+        let pl0_pauser = pdata.manifest1.header.pl0_pauser;
+
+        let used_contexts = pdata
+            .dpe
+            .count_contexts(|c: &Context| c.state != ContextState::Inactive)
+            .map_err(|_| CaliptraError::RUNTIME_INTERNAL)?;
+        let pl0_used_contexts = pdata
+            .dpe
+            .count_contexts(|c: &Context| {
+                c.state != ContextState::Inactive && c.locality == pl0_pauser
+            })
+            .map_err(|_| CaliptraError::RUNTIME_INTERNAL)?;
+        let tagged_contexts = pdata
+            .context_has_tag
+            .iter()
+            .filter(|has_tag| has_tag.get())
+            .count();
+        let exported_cdi_handles = drivers
+            .exported_cdi_slots
+            .iter()
+            .filter(|slot| slot.is_some())
+            .count();
+
+        Ok(MailboxResp::GetDpeStats(GetDpeStatsResp {
+            hdr: MailboxRespHeader::default(),
+            used_contexts: used_contexts as u32,
+            free_contexts: (MAX_HANDLES - used_contexts) as u32,
+            pl0_used_contexts: pl0_used_contexts as u32,
+            pl1_used_contexts: (used_contexts - pl0_used_contexts) as u32,
+            tagged_contexts: tagged_contexts as u32,
+            exported_cdi_handles: exported_cdi_handles as u32,
+        }))
+    }
+}
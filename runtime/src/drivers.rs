@@ -18,20 +18,28 @@ Abstract:
 pub use crate::fips::{fips_self_test_cmd, fips_self_test_cmd::SelfTestStatus};
 
 use crate::{
-    dice, CptraDpeTypes, DisableAttestationCmd, DpeCrypto, DpePlatform, Mailbox, DPE_SUPPORT,
-    MAX_CERT_CHAIN_SIZE, PL0_DPE_ACTIVE_CONTEXT_THRESHOLD, PL0_PAUSER_FLAG,
-    PL1_DPE_ACTIVE_CONTEXT_THRESHOLD,
+    dice, suspend, timestamp, CptraDpeTypes, DisableAttestationCmd, DpeCrypto, DpePlatform,
+    Heartbeat, HwCryptoBackend, IdleStats, JobQueue, Mailbox, MailboxAuthKey, PldmFwUpdateState,
+    PrivilegedOpLog, SpdmState, StreamVerify, DPE_SUPPORT, MAX_CERT_CHAIN_SIZE,
+    PL0_DPE_ACTIVE_CONTEXT_THRESHOLD, PL0_PAUSER_FLAG, PL1_DPE_ACTIVE_CONTEXT_THRESHOLD,
 };
+use caliptra_common::{log_error, log_info, log_warn};
 
-use crate::dpe_crypto::{ExportedCdiHandles, EXPORTED_HANDLES_NUM};
+use crate::cert_policy::CertPolicy;
+use crate::dpe_crypto::{tci_from_measurement, ExportedCdiHandles, EXPORTED_HANDLES_NUM};
+use crate::handoff::RtHandoff;
+use crate::migrate::migrate_persistent_data;
+use crate::verify_object::ObjectSchemas;
 use arrayvec::ArrayVec;
 use caliptra_cfi_derive_git::{cfi_impl_fn, cfi_mod_fn};
 use caliptra_cfi_lib_git::{cfi_assert, cfi_assert_eq, cfi_assert_eq_12_words, cfi_launder};
+use caliptra_common::keyids::KEY_ID_FHT_MAC;
 use caliptra_common::mailbox_api::AddSubjectAltNameReq;
+use caliptra_common::WdtTimeout;
 use caliptra_drivers::KeyId;
 use caliptra_drivers::{
-    cprint, cprintln, pcr_log::RT_FW_JOURNEY_PCR, Array4x12, CaliptraError, CaliptraResult,
-    DataVault, Ecc384, KeyVault, Lms, PersistentDataAccessor, Pic, ResetReason, Sha1, SocIfc,
+    cprint, pcr_log::RT_FW_JOURNEY_PCR, Array4x12, CaliptraError, CaliptraResult, DataVault,
+    Ecc384, KeyVault, Lms, MemEcc, PersistentDataAccessor, Pic, ResetReason, Sha1, SocIfc,
 };
 use caliptra_drivers::{
     hand_off::DataStore, Ecc384PubKey, Hmac384, PcrBank, PcrId, Sha256, Sha256Alg, Sha2_512_384Acc,
@@ -55,12 +63,13 @@ use dpe::{
     context::ContextHandle,
     dpe_instance::{DpeEnv, DpeInstance, DpeTypes},
     support::Support,
-    DPE_PROFILE,
+    U8Bool,
 };
 
 use core::cmp::Ordering::{Equal, Greater};
 use crypto::{AlgLen, Crypto, CryptoBuf, Hasher, MAX_EXPORTED_CDI_SIZE};
 use zerocopy::IntoBytes;
+use zeroize::Zeroize;
 
 #[derive(PartialEq, Clone)]
 pub enum PauserPrivileges {
@@ -108,8 +117,63 @@ pub struct Drivers {
 
     pub is_shutdown: bool,
 
+    /// Set by PREPARE_FOR_SLEEP; the mailbox is no longer serviced once its
+    /// response is sent
+    pub is_sleeping: bool,
+
+    /// Set when a FIPS self test fails; blocks crypto commands until reset
+    pub fips_error: Option<CaliptraError>,
+
     pub dmtf_device_info: Option<ArrayVec<u8, { AddSubjectAltNameReq::MAX_DEVICE_INFO_LEN }>>,
     pub exported_cdi_slots: ExportedCdiHandles,
+
+    /// Certificate issuance policy set by SET_CERT_POLICY since the last reset
+    pub cert_policy: Option<CertPolicy>,
+
+    /// Key established by PAIR_MAILBOX since the last reset
+    pub mbox_auth_key: Option<MailboxAuthKey>,
+
+    /// Object schemas approved by SET_OBJECT_SCHEMAS since the last reset
+    pub object_schemas: Option<ObjectSchemas>,
+
+    /// Sequence number of the next GET_RAW_ENTROPY page, or None if no
+    /// capture has been started by CAPTURE_RAW_ENTROPY
+    pub raw_entropy_seq: Option<u32>,
+
+    /// Set while a mailbox command handler is executing
+    pub handling_command: bool,
+
+    /// Audit log of privileged SoC interface operations
+    pub privileged_op_log: PrivilegedOpLog,
+
+    /// Asynchronous mailbox command jobs
+    pub jobs: JobQueue,
+
+    /// Size of the mailbox SRAM to scrub once the SoC releases the mailbox,
+    /// set after a confidential command
+    pub mbox_scrub_len: Option<u32>,
+
+    /// Time spent asleep waiting for mailbox commands
+    pub idle_stats: IdleStats,
+
+    /// DCCM scrubber run while idle, and ECC error counts reported in
+    /// FW_INFO
+    pub mem_ecc: MemEcc,
+
+    /// Mailbox command counters reported in FW_INFO
+    pub heartbeat: Heartbeat,
+
+    /// Watchdog timeout armed around each command, set by CONFIGURE_WDT
+    pub wdt_timeout: WdtTimeout,
+
+    /// SPDM connection negotiated through the SPDM command
+    pub spdm: SpdmState,
+
+    /// PLDM firmware update in progress through the MCTP command
+    pub pldm_fw_update: PldmFwUpdateState,
+
+    /// SoC image stream started by START_STREAM_VERIFY
+    pub stream_verify: Option<StreamVerify>,
 }
 
 impl Drivers {
@@ -126,13 +190,26 @@ impl Drivers {
             &SocIfcReg::new(),
         )?;
 
+        // Prefer the capabilities recorded by ROM, probing the hardware only
+        // when running on a ROM that predates them.
+        let mut persistent_data = PersistentDataAccessor::new();
+        let soc_ifc = SocIfc::new(SocIfcReg::new());
+        migrate_persistent_data(persistent_data.get_mut(), soc_ifc.reset_reason());
+        let hw_caps = persistent_data
+            .get()
+            .fht
+            .hw_capabilities()
+            .unwrap_or_else(|| soc_ifc.hw_capabilities());
+        let mut sha256 = Sha256::new(Sha256Reg::new());
+        sha256.set_hw_capabilities(hw_caps);
+
         Ok(Self {
             mbox: Mailbox::new(MboxCsr::new()),
             sha_acc: Sha512AccCsr::new(),
             data_vault: DataVault::new(DvReg::new()),
             key_vault: KeyVault::new(KvReg::new()),
-            soc_ifc: SocIfc::new(SocIfcReg::new()),
-            sha256: Sha256::new(Sha256Reg::new()),
+            soc_ifc,
+            sha256,
             sha384: Sha384::new(Sha512Reg::new()),
             sha2_512_384_acc: Sha2_512_384Acc::new(Sha512AccCsr::new()),
             hmac384: Hmac384::new(HmacReg::new()),
@@ -140,21 +217,58 @@ impl Drivers {
             sha1: Sha1::default(),
             lms: Lms::default(),
             trng,
-            persistent_data: PersistentDataAccessor::new(),
+            persistent_data,
             pcr_bank: PcrBank::new(PvReg::new()),
             pic: Pic::new(El2PicCtrl::new()),
             #[cfg(feature = "fips_self_test")]
             self_test_status: SelfTestStatus::Idle,
             cert_chain: ArrayVec::new(),
             is_shutdown: false,
+            is_sleeping: false,
+            fips_error: None,
             dmtf_device_info: None,
             exported_cdi_slots: [None; EXPORTED_HANDLES_NUM],
+            cert_policy: None,
+            mbox_auth_key: None,
+            object_schemas: None,
+            raw_entropy_seq: None,
+            handling_command: false,
+            privileged_op_log: PrivilegedOpLog::default(),
+            jobs: JobQueue::default(),
+            mbox_scrub_len: None,
+            idle_stats: IdleStats::default(),
+            mem_ecc: MemEcc::default(),
+            heartbeat: Heartbeat::default(),
+            wdt_timeout: WdtTimeout::default(),
+            spdm: SpdmState::default(),
+            pldm_fw_update: PldmFwUpdateState::default(),
+            stream_verify: None,
         })
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn run_reset_flow(&mut self) -> CaliptraResult<()> {
+        // Nothing in the FHT can be trusted until its MAC is checked
+        Self::verify_fht_mac(self)?;
+
+        // Certificates populated by the SoC only persist across update and
+        // warm resets.
+        if self.soc_ifc.reset_reason() == ResetReason::ColdReset {
+            self.persistent_data.get_mut().idevid_cert_chain.zeroize();
+        }
         Self::create_cert_chain(self)?;
+
+        // Break-glass recovery images always run with attestation disabled
+        if self
+            .persistent_data
+            .get()
+            .manifest1
+            .header
+            .owner_data
+            .is_break_glass()
+        {
+            self.persistent_data.get_mut().attestation_disabled = U8Bool::new(true);
+        }
         if self.persistent_data.get().attestation_disabled.get() {
             DisableAttestationCmd::execute(self)
                 .map_err(|_| CaliptraError::RUNTIME_GLOBAL_EXCEPTION)?;
@@ -165,18 +279,38 @@ impl Drivers {
             ResetReason::ColdReset => {
                 cfi_assert_eq(self.soc_ifc.reset_reason(), ResetReason::ColdReset);
                 Self::initialize_dpe(self)?;
+                self.persistent_data.get_mut().fw_update_journal.zeroize();
+                self.persistent_data.get_mut().suspend_checkpoint.zeroize();
+                self.persistent_data.get_mut().crash_record.zeroize();
+                self.persistent_data.get_mut().log_level = 0;
+                self.persistent_data.get_mut().revoked_localities.clear();
+                timestamp::start_boot_session(self)?;
             }
             ResetReason::UpdateReset => {
                 cfi_assert_eq(self.soc_ifc.reset_reason(), ResetReason::UpdateReset);
                 Self::validate_dpe_structure(self)?;
                 Self::validate_context_tags(self)?;
                 Self::update_dpe_rt_journey(self)?;
+                Self::complete_fw_update_journal_entry(self)?;
+                // The checkpoint is bound to the RT CDI of the previous firmware
+                self.persistent_data.get_mut().suspend_checkpoint.zeroize();
             }
             ResetReason::WarmReset => {
                 cfi_assert_eq(self.soc_ifc.reset_reason(), ResetReason::WarmReset);
                 Self::validate_dpe_structure(self)?;
                 Self::validate_context_tags(self)?;
                 Self::check_dpe_rt_journey_unchanged(self)?;
+                // An update interrupted by a warm reset never took effect
+                self.persistent_data
+                    .get_mut()
+                    .fw_update_journal
+                    .discard_pending();
+                // The reset ends the low-power state entered with
+                // PREPARE_FOR_SLEEP
+                if self.persistent_data.get().suspend_checkpoint.is_sleeping() {
+                    suspend::resume_from_sleep(self);
+                }
+                timestamp::start_boot_session(self)?;
             }
             ResetReason::Unknown => {
                 cfi_assert_eq(self.soc_ifc.reset_reason(), ResetReason::Unknown);
@@ -232,7 +366,7 @@ impl Drivers {
             }
             match result {
                 Ok(_) => {
-                    cprintln!("Disabled attest : DPE valid fail");
+                    log_warn!("Disabled attest : DPE valid fail");
                     // store specific validation error in CPTRA_FW_EXTENDED_ERROR_INFO
                     drivers.soc_ifc.set_fw_extended_error(e.get_error_code());
                     caliptra_drivers::report_fw_error_non_fatal(
@@ -240,7 +374,7 @@ impl Drivers {
                     );
                 }
                 Err(e) => {
-                    cprintln!("{}", e.0);
+                    log_error!("{}", e.0);
                     return Err(CaliptraError::RUNTIME_GLOBAL_EXCEPTION);
                 }
             }
@@ -262,11 +396,11 @@ impl Drivers {
                 }
                 match result {
                     Ok(_) => {
-                        cprintln!("Disable attest DPE used context limit breach");
+                        log_warn!("Disable attest DPE used context limit breach");
                         caliptra_drivers::report_fw_error_non_fatal(e.into());
                     }
                     Err(e) => {
-                        cprintln!("{}", e.0);
+                        log_error!("{}", e.0);
                         return Err(CaliptraError::RUNTIME_GLOBAL_EXCEPTION);
                     }
                 }
@@ -281,19 +415,41 @@ impl Drivers {
     fn update_dpe_rt_journey(drivers: &mut Drivers) -> CaliptraResult<()> {
         let dpe = &mut drivers.persistent_data.get_mut().dpe;
         let root_idx = Self::get_dpe_root_context_idx(dpe)?;
-        let latest_pcr = <[u8; 48]>::from(drivers.pcr_bank.read_pcr(RT_FW_JOURNEY_PCR));
+        let latest_pcr = tci_from_measurement(&drivers.pcr_bank.read_pcr(RT_FW_JOURNEY_PCR).into());
         dpe.contexts[root_idx].tci.tci_current = TciMeasurement(latest_pcr);
         dpe.contexts[root_idx].tci.tci_cumulative = TciMeasurement(latest_pcr);
 
         Ok(())
     }
 
+    /// Record the update that led to this update reset in the firmware
+    /// update journal
+    fn complete_fw_update_journal_entry(drivers: &mut Drivers) -> CaliptraResult<()> {
+        let pdata = drivers.persistent_data.get();
+        let new_svn = RtHandoff {
+            data_vault: &drivers.data_vault,
+            fht: &pdata.fht,
+        }
+        .rt_svn()?;
+        let digest = pdata.manifest1.runtime.digest;
+
+        if !drivers
+            .persistent_data
+            .get_mut()
+            .fw_update_journal
+            .complete(new_svn, digest)
+        {
+            log_info!("[rt] No pending update in journal");
+        }
+        Ok(())
+    }
+
     /// Check that RT_FW_JOURNEY_PCR == DPE Root Context's TCI measurement
     fn check_dpe_rt_journey_unchanged(mut drivers: &mut Drivers) -> CaliptraResult<()> {
         let dpe = &drivers.persistent_data.get().dpe;
         let root_idx = Self::get_dpe_root_context_idx(dpe)?;
-        let latest_tci = Array4x12::from(&dpe.contexts[root_idx].tci.tci_current.0);
-        let latest_pcr = drivers.pcr_bank.read_pcr(RT_FW_JOURNEY_PCR);
+        let latest_tci = dpe.contexts[root_idx].tci.tci_current.0;
+        let latest_pcr = tci_from_measurement(&drivers.pcr_bank.read_pcr(RT_FW_JOURNEY_PCR).into());
 
         // Ensure TCI from SRAM == RT_FW_JOURNEY_PCR
         if latest_pcr != latest_tci {
@@ -306,13 +462,13 @@ impl Drivers {
             }
             match result {
                 Ok(_) => {
-                    cprintln!("Disabled attestation due to latest TCI of the node containing the runtime journey PCR not matching the runtime PCR");
+                    log_warn!("Disabled attestation due to latest TCI of the node containing the runtime journey PCR not matching the runtime PCR");
                     caliptra_drivers::report_fw_error_non_fatal(
                         CaliptraError::RUNTIME_RT_JOURNEY_PCR_VALIDATION_FAILED.into(),
                     );
                 }
                 Err(e) => {
-                    cprintln!("{}", e.0);
+                    log_error!("{}", e.0);
                     return Err(CaliptraError::RUNTIME_GLOBAL_EXCEPTION);
                 }
             }
@@ -326,6 +482,19 @@ impl Drivers {
         Ok(())
     }
 
+    /// Check the MAC FMC computed over the FHT before handing off
+    fn verify_fht_mac(drivers: &mut Drivers) -> CaliptraResult<()> {
+        let fht = &drivers.persistent_data.get().fht;
+        let mac = fht.compute_mac(&mut drivers.hmac384, &mut drivers.trng, KEY_ID_FHT_MAC)?;
+        if cfi_launder(mac) != fht.fht_mac {
+            log_error!("[rt] FHT MAC mismatch");
+            return Err(CaliptraError::RUNTIME_HANDOFF_FHT_MAC_MISMATCH);
+        } else {
+            cfi_assert_eq_12_words(&mac, &fht.fht_mac);
+        }
+        Ok(())
+    }
+
     /// Check that inactive DPE contexts do not have context tags set
     fn validate_context_tags(mut drivers: &mut Drivers) -> CaliptraResult<()> {
         let pdata = drivers.persistent_data.get();
@@ -345,6 +514,16 @@ impl Drivers {
         Ok(())
     }
 
+    /// Crypto backend using the hardware engines owned by `self`
+    pub fn crypto_backend(&mut self) -> HwCryptoBackend<'_> {
+        HwCryptoBackend::new(
+            &mut self.sha384,
+            &mut self.hmac384,
+            &mut self.ecc384,
+            &mut self.trng,
+        )
+    }
+
     /// Compute the Caliptra Name SerialNumber by Sha256 hashing the RT Alias public key
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn compute_rt_alias_sn(&mut self) -> CaliptraResult<CryptoBuf> {
@@ -382,6 +561,7 @@ impl Drivers {
         let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
         let pdata = drivers.persistent_data.get_mut();
         let mut crypto = DpeCrypto::new(
+            &mut drivers.sha256,
             &mut drivers.sha384,
             &mut drivers.trng,
             &mut drivers.ecc384,
@@ -404,13 +584,13 @@ impl Drivers {
                 &nf,
                 None,
                 None,
+                None,
             ),
         };
 
         // Initialize DPE with the RT journey PCR
-        let rt_journey_measurement = <[u8; DPE_PROFILE.get_hash_size()]>::from(
-            &drivers.pcr_bank.read_pcr(RT_FW_JOURNEY_PCR),
-        );
+        let rt_journey_measurement =
+            tci_from_measurement(&drivers.pcr_bank.read_pcr(RT_FW_JOURNEY_PCR).into());
         let mut dpe = DpeInstance::new_auto_init(
             &mut env,
             DPE_SUPPORT,
@@ -422,10 +602,7 @@ impl Drivers {
         // Call DeriveContext to create a measurement for the mailbox valid pausers and change locality to the pl0 pauser locality
         let derive_context_resp = DeriveContextCmd {
             handle: ContextHandle::default(),
-            data: valid_pauser_hash
-                .as_bytes()
-                .try_into()
-                .map_err(|_| CaliptraError::RUNTIME_ADD_VALID_PAUSER_MEASUREMENT_TO_DPE_FAILED)?,
+            data: tci_from_measurement(&valid_pauser_hash.into()),
             flags: DeriveContextFlags::MAKE_DEFAULT
                 | DeriveContextFlags::CHANGE_LOCALITY
                 | DeriveContextFlags::INPUT_ALLOW_CA
@@ -460,9 +637,11 @@ impl Drivers {
             let tci_type = u32::from_ne_bytes(measurement_log_entry.metadata);
             let derive_context_resp = DeriveContextCmd {
                 handle: ContextHandle::default(),
-                data: measurement_data
-                    .try_into()
-                    .map_err(|_| CaliptraError::RUNTIME_ADD_ROM_MEASUREMENTS_TO_DPE_FAILED)?,
+                data: tci_from_measurement(
+                    measurement_data
+                        .try_into()
+                        .map_err(|_| CaliptraError::RUNTIME_ADD_ROM_MEASUREMENTS_TO_DPE_FAILED)?,
+                ),
                 flags: DeriveContextFlags::MAKE_DEFAULT
                     | DeriveContextFlags::CHANGE_LOCALITY
                     | DeriveContextFlags::INPUT_ALLOW_CA
@@ -492,10 +671,25 @@ impl Drivers {
         let persistent_data = &drivers.persistent_data;
         let mut cert = [0u8; MAX_CERT_CHAIN_SIZE];
 
+        // Write the certs populated by the SoC to the cert chain.
+        let idevid_chain = persistent_data
+            .get()
+            .idevid_cert_chain
+            .as_slice()
+            .ok_or(CaliptraError::RUNTIME_CERT_CHAIN_CREATION_FAILED)?;
+        let idevid_chain_size = idevid_chain.len();
+        cert.get_mut(..idevid_chain_size)
+            .ok_or(CaliptraError::RUNTIME_CERT_CHAIN_CREATION_FAILED)?
+            .copy_from_slice(idevid_chain);
+
         // Write ldev_id cert to cert chain.
-        let ldevid_cert_size =
-            dice::copy_ldevid_cert(data_vault, persistent_data.get(), &mut cert)?;
-        if ldevid_cert_size > cert.len() {
+        let ldevid_cert_size = dice::copy_ldevid_cert(
+            data_vault,
+            persistent_data.get(),
+            &mut cert[idevid_chain_size..],
+        )?;
+        let ldevid_cert_end = idevid_chain_size + ldevid_cert_size;
+        if ldevid_cert_end > cert.len() {
             return Err(CaliptraError::RUNTIME_LDEV_ID_CERT_TOO_BIG);
         }
 
@@ -503,18 +697,18 @@ impl Drivers {
         let fmcalias_cert_size = dice::copy_fmc_alias_cert(
             data_vault,
             persistent_data.get(),
-            &mut cert[ldevid_cert_size..],
+            &mut cert[ldevid_cert_end..],
         )?;
-        if ldevid_cert_size + fmcalias_cert_size > cert.len() {
+        if ldevid_cert_end + fmcalias_cert_size > cert.len() {
             return Err(CaliptraError::RUNTIME_FMC_ALIAS_CERT_TOO_BIG);
         }
 
         // Write rt alias cert to cert chain.
         let rtalias_cert_size = dice::copy_rt_alias_cert(
             persistent_data.get(),
-            &mut cert[ldevid_cert_size + fmcalias_cert_size..],
+            &mut cert[ldevid_cert_end + fmcalias_cert_size..],
         )?;
-        let cert_chain_size = ldevid_cert_size + fmcalias_cert_size + rtalias_cert_size;
+        let cert_chain_size = ldevid_cert_end + fmcalias_cert_size + rtalias_cert_size;
         if cert_chain_size > cert.len() {
             return Err(CaliptraError::RUNTIME_RT_ALIAS_CERT_TOO_BIG);
         }
@@ -585,6 +779,14 @@ impl Drivers {
         }
     }
 
+    /// Returns true if `locality` was revoked by REVOKE_LOCALITY since the last cold reset
+    pub fn is_locality_revoked(&self, locality: u32) -> bool {
+        self.persistent_data
+            .get()
+            .revoked_localities
+            .contains(locality)
+    }
+
     /// Retrieves the caller permission level
     pub fn caller_privilege_level(&self) -> PauserPrivileges {
         let manifest_header = self.persistent_data.get().manifest1.header;
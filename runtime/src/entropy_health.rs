@@ -0,0 +1,49 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    entropy_health.rs
+
+Abstract:
+
+    File contains GetEntropyHealth mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{
+    GetEntropyHealthReq, GetEntropyHealthResp, MailboxResp, MailboxRespHeader,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::FromBytes;
+
+pub struct GetEntropyHealthCmd;
+impl GetEntropyHealthCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = GetEntropyHealthReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        if cmd.flags & GetEntropyHealthReq::RESEED != 0 {
+            drivers.trng.reseed()?;
+        }
+
+        let stats = drivers.trng.health_test_stats()?;
+        Ok(MailboxResp::GetEntropyHealth(GetEntropyHealthResp {
+            hdr: MailboxRespHeader::default(),
+            repcnt_threshold: stats.repcnt_threshold,
+            adaptp_hi_threshold: stats.adaptp_hi_threshold,
+            adaptp_lo_threshold: stats.adaptp_lo_threshold,
+            repcnt_hi_watermark: stats.repcnt_hi_watermark,
+            adaptp_hi_watermark: stats.adaptp_hi_watermark,
+            adaptp_lo_watermark: stats.adaptp_lo_watermark,
+            repcnt_total_fails: stats.repcnt_total_fails,
+            adaptp_hi_total_fails: stats.adaptp_hi_total_fails,
+            adaptp_lo_total_fails: stats.adaptp_lo_total_fails,
+        }))
+    }
+}
@@ -12,8 +12,8 @@ Abstract:
 
 --*/
 use caliptra_cfi_derive_git::{cfi_impl_fn, cfi_mod_fn};
-use caliptra_common::cprintln;
-use caliptra_common::mailbox_api::{MailboxResp, MailboxRespHeader};
+use caliptra_common::mailbox_api::{MailboxResp, MailboxRespHeader, ZeroizeResp, ZeroizeStatus};
+use caliptra_common::{log_error, log_info, log_trace, log_warn};
 use caliptra_drivers::CaliptraError;
 use caliptra_drivers::CaliptraResult;
 use caliptra_drivers::Ecc384;
@@ -25,16 +25,15 @@ use caliptra_drivers::Sha384;
 use caliptra_registers::mbox::enums::MboxStatusE;
 use zeroize::Zeroize;
 
+use crate::dpe_crypto::EXPORTED_HANDLES_NUM;
 use crate::Drivers;
 
 pub struct FipsModule;
 
 /// Fips command handler.
 impl FipsModule {
-    /// Clear data structures in DCCM.
-    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
-    #[inline(never)]
-    fn zeroize(env: &mut Drivers) {
+    /// Clear the crypto blocks and lock the SHA accelerator.
+    fn zeroize_engines() {
         unsafe {
             // Zeroize the crypto blocks.
             Ecc384::zeroize();
@@ -43,12 +42,19 @@ impl FipsModule {
             Sha384::zeroize();
             Sha2_512_384Acc::zeroize();
 
-            // Zeroize the key vault.
-            KeyVault::zeroize();
-
             // Lock the SHA Accelerator.
             Sha2_512_384Acc::lock();
         }
+    }
+
+    /// Clear data structures in DCCM.
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    fn zeroize(env: &mut Drivers) {
+        Self::zeroize_engines();
+
+        // Zeroize the key vault.
+        unsafe { KeyVault::zeroize() };
 
         #[cfg(feature = "fips-test-hooks")]
         unsafe {
@@ -64,13 +70,17 @@ impl FipsModule {
 #[cfg(feature = "fips_self_test")]
 pub mod fips_self_test_cmd {
     use super::*;
+    use crate::stream_verify;
+    use crate::PrivilegedSocIfc;
     use crate::RtBootStatus::{RtFipSelfTestComplete, RtFipSelfTestStarted};
     use caliptra_cfi_lib_git::cfi_assert_eq_8_words;
+    use caliptra_common::mailbox_api::{SelfTestAlgorithms, SelfTestGetResultsResp};
     use caliptra_common::HexBytes;
     use caliptra_common::{verifier::FirmwareImageVerificationEnv, FMC_SIZE, RUNTIME_SIZE};
     use caliptra_drivers::{ResetReason, ShaAccLockState};
-    use caliptra_image_types::{ImageTocEntry, RomInfo};
+    use caliptra_image_types::{ImageManifestFormat, ImageTocEntry, RomInfo};
     use caliptra_image_verify::ImageVerifier;
+    use caliptra_kat::{Ecc256SwKat, Kat, KatsEnv, Sha3Kat};
     use zerocopy::IntoBytes;
 
     // Helper function to create a slice from a memory region
@@ -80,36 +90,84 @@ pub mod fips_self_test_cmd {
     }
     pub enum SelfTestStatus {
         Idle,
-        InProgress(fn(&mut Drivers) -> CaliptraResult<()>),
-        Done,
+        InProgress(fn(&mut Drivers) -> SelfTestResults),
+        Done(SelfTestResults),
+    }
+
+    /// Per-algorithm outcome of a self test run.
+    #[derive(Default, Copy, Clone)]
+    pub struct SelfTestResults {
+        pub passed: SelfTestAlgorithms,
+        pub failed: SelfTestAlgorithms,
+
+        /// Error reported by the first failing test, if any
+        pub first_error: Option<CaliptraError>,
+    }
+
+    impl SelfTestResults {
+        fn record(&mut self, algorithm: SelfTestAlgorithms, result: CaliptraResult<()>) {
+            match result {
+                Ok(()) => self.passed |= algorithm,
+                Err(e) => {
+                    log_error!("[rt] Self test failed 0x{:08X}", u32::from(e));
+                    self.failed |= algorithm;
+                    self.first_error.get_or_insert(e);
+                }
+            }
+        }
+    }
+
+    impl From<SelfTestResults> for MailboxResp {
+        fn from(results: SelfTestResults) -> Self {
+            MailboxResp::SelfTestGetResults(SelfTestGetResultsResp {
+                hdr: MailboxRespHeader::default(),
+                passed: results.passed.bits(),
+                failed: results.failed.bits(),
+            })
+        }
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
-    fn copy_and_verify_image(env: &mut Drivers) -> CaliptraResult<()> {
+    fn copy_and_verify_image(env: &mut Drivers, wdt_pet: fn()) -> CaliptraResult<()> {
+        // The image is rebuilt with a version 1 manifest. An image built with a
+        // larger version 2 manifest has its FMC further in, so pad up to the
+        // FMC offset of the TOC.
+        let fmc_offset = env.persistent_data.get().manifest1.fmc.offset;
+        let img_bundle_sz = fmc_offset
+            + env.persistent_data.get().manifest1.fmc.size
+            + env.persistent_data.get().manifest1.runtime_size();
         env.mbox.write_cmd(0)?;
-        env.mbox.set_dlen(
-            env.persistent_data.get().manifest1.size
-                + env.persistent_data.get().manifest1.fmc.size
-                + env.persistent_data.get().manifest1.runtime.size,
-        );
+        env.mbox.set_dlen(img_bundle_sz);
         env.mbox
             .copy_bytes_to_mbox(env.persistent_data.get().manifest1.as_bytes())?;
+        env.mbox.copy_bytes_to_mbox(
+            env.persistent_data
+                .get()
+                .vendor_intermediate_keys
+                .as_bytes(),
+        )?;
+        let padding = fmc_offset.saturating_sub(ImageManifestFormat::V1.size() as u32);
+        for _ in 0..padding / 4 {
+            env.mbox.copy_bytes_to_mbox(&[0; 4])?;
+        }
 
-        let fmc_toc = &env.persistent_data.get().manifest1.fmc;
-        let rt_toc = &env.persistent_data.get().manifest1.runtime;
+        let manifest = &env.persistent_data.get().manifest1;
+        let fmc_toc = &manifest.fmc;
 
         if fmc_toc.size > FMC_SIZE {
             return Err(CaliptraError::RUNTIME_INVALID_FMC_SIZE);
         }
-        if rt_toc.size > RUNTIME_SIZE {
+        if manifest.runtime_size() > RUNTIME_SIZE {
             return Err(CaliptraError::RUNTIME_INVALID_RUNTIME_SIZE);
         }
 
         let fmc = unsafe { create_slice(&fmc_toc) };
-        let rt = unsafe { create_slice(&rt_toc) };
-
         env.mbox.copy_bytes_to_mbox(fmc.as_bytes())?;
-        env.mbox.copy_bytes_to_mbox(rt.as_bytes())?;
+
+        for rt_toc in manifest.runtime_segments() {
+            let rt = unsafe { create_slice(rt_toc) };
+            env.mbox.copy_bytes_to_mbox(rt.as_bytes())?;
+        }
 
         let mut venv = FirmwareImageVerificationEnv {
             sha256: &mut env.sha256,
@@ -119,64 +177,98 @@ pub mod fips_self_test_cmd {
             data_vault: &mut env.data_vault,
             pcr_bank: &mut env.pcr_bank,
             image: env.mbox.raw_mailbox_contents(),
+            load_sha_acc: None,
+            wdt_pet: Some(wdt_pet),
         };
 
-        let mut verifier = ImageVerifier::new(&mut venv);
+        let mut verifier = ImageVerifier::new(&mut venv)
+            .with_vendor_intermediate_keys(&env.persistent_data.get().vendor_intermediate_keys);
         let _info = verifier.verify(
             &env.persistent_data.get().manifest1,
-            env.persistent_data.get().manifest1.size
-                + env.persistent_data.get().manifest1.fmc.size
-                + env.persistent_data.get().manifest1.runtime.size,
+            img_bundle_sz,
             ResetReason::UpdateReset,
         )?;
-        cprintln!("[rt] Verify complete");
+        log_info!("[rt] Verify complete");
         Ok(())
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
-    pub(crate) fn execute(env: &mut Drivers) -> CaliptraResult<()> {
+    pub(crate) fn execute(env: &mut Drivers) -> SelfTestResults {
         caliptra_drivers::report_boot_status(RtFipSelfTestStarted.into());
-        cprintln!("[rt] FIPS self test");
-        execute_kats(env)?;
-        rom_integrity_test(env)?;
-        copy_and_verify_image(env)?;
+        log_info!("[rt] FIPS self test");
+        let mut results = SelfTestResults::default();
+        let wdt_pet = PrivilegedSocIfc::wdt_pet(env);
+        // The SHA accelerator KAT needs the accelerator lock
+        stream_verify::abort(env);
+        execute_kats(env, &mut results, wdt_pet);
+        wdt_pet();
+        results.record(SelfTestAlgorithms::ROM_INTEGRITY, rom_integrity_test(env));
+        // Compressed images can't be reassembled from the loaded image, so
+        // their integrity test is neither passed nor failed.
+        let manifest = &env.persistent_data.get().manifest1;
+        if manifest.fmc.is_compressed() || manifest.runtime_segments().any(|s| s.is_compressed()) {
+            log_warn!("[rt] Skipping FW integrity test of compressed image");
+        } else {
+            results.record(
+                SelfTestAlgorithms::FW_INTEGRITY,
+                copy_and_verify_image(env, wdt_pet),
+            );
+        }
         caliptra_drivers::report_boot_status(RtFipSelfTestComplete.into());
-        Ok(())
+        results
     }
 
-    /// Execute KAT for cryptographic algorithms implemented in H/W.
-    fn execute_kats(env: &mut Drivers) -> CaliptraResult<()> {
-        let mut kats_env = caliptra_kat::KatsEnv {
-            // SHA1 Engine
-            sha1: &mut env.sha1,
+    /// Self test result bit for `kat`
+    fn kat_algorithm(kat: Kat) -> SelfTestAlgorithms {
+        match kat {
+            Kat::Sha1 => SelfTestAlgorithms::SHA1,
+            Kat::Sha256 => SelfTestAlgorithms::SHA256,
+            Kat::Sha384 => SelfTestAlgorithms::SHA384,
+            Kat::Sha2_512_384Acc => SelfTestAlgorithms::SHA2_512_384_ACC,
+            Kat::Ecc384 => SelfTestAlgorithms::ECC384,
+            Kat::Hmac384Kdf => SelfTestAlgorithms::HMAC384_KDF,
+            Kat::Lms => SelfTestAlgorithms::LMS,
+        }
+    }
 
-            // sha256
-            sha256: &mut env.sha256,
+    /// Execute KAT for cryptographic algorithms implemented in H/W, followed
+    /// by those the Runtime Firmware implements in software.
+    ///
+    /// Each KAT is run even if a previous one failed so that the caller
+    /// gets a result for every algorithm. `wdt_pet` is called after each of
+    /// them.
+    fn execute_kats(env: &mut Drivers, results: &mut SelfTestResults, wdt_pet: fn()) {
+        log_info!("[kat] ++");
 
-            // SHA2-384 Engine
+        let mut kats_env = KatsEnv {
+            sha1: &mut env.sha1,
+            sha256: &mut env.sha256,
             sha384: &mut env.sha384,
-
-            // SHA2-512/384 Accelerator
             sha2_512_384_acc: &mut env.sha2_512_384_acc,
-
-            // Hmac384 Engine
             hmac384: &mut env.hmac384,
-
-            /// Cryptographically Secure Random Number Generator
             trng: &mut env.trng,
-
-            // LMS Engine
             lms: &mut env.lms,
-
-            /// Ecc384 Engine
             ecc384: &mut env.ecc384,
-
-            /// SHA Acc Lock State
             sha_acc_lock_state: ShaAccLockState::NotAcquired,
         };
+        for kat in Kat::ALL {
+            log_trace!("[kat] {}", kat.name());
+            results.record(kat_algorithm(kat), kat.execute(&mut kats_env));
+            wdt_pet();
+        }
 
-        caliptra_kat::execute_kat(&mut kats_env)?;
-        Ok(())
+        log_trace!("[kat] SHA3-384/SHAKE256");
+        results.record(SelfTestAlgorithms::SHA3, Sha3Kat::default().execute());
+        wdt_pet();
+
+        log_trace!("[kat] ECC-256");
+        results.record(
+            SelfTestAlgorithms::ECC256,
+            Ecc256SwKat::default().execute(&mut env.trng),
+        );
+        wdt_pet();
+
+        log_info!("[kat] --");
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
@@ -195,10 +287,10 @@ pub mod fips_self_test_cmd {
             env.persistent_data.get().fht.rom_info_addr.get()? as *const RomInfo as usize / 64;
 
         let mut digest = unsafe { env.sha256.digest_blocks_raw(rom_start, n_blocks)? };
-        cprintln!("ROM Digest: {}", HexBytes(&<[u8; 32]>::from(digest)));
+        log_info!("ROM Digest: {}", HexBytes(&<[u8; 32]>::from(digest)));
         if digest.0 != rom_info.sha256_digest {
             digest.zeroize();
-            cprintln!("ROM integrity test failed");
+            log_error!("ROM integrity test failed");
             return Err(CaliptraError::ROM_INTEGRITY_FAILURE);
         } else {
             cfi_assert_eq_8_words(&digest.0, &rom_info.sha256_digest);
@@ -209,6 +301,68 @@ pub mod fips_self_test_cmd {
         Ok(())
     }
 }
+/// Latch the FIPS error state after a failed self test.
+///
+/// The crypto engines and the key vault are cleared and the SHA accelerator
+/// is locked. Persistent data is left intact so that informational commands
+/// keep working until the next reset.
+///
+/// # Arguments
+///
+/// * `env` - Drivers
+/// * `err` - Error reported by the failing self test
+#[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
+pub(crate) fn enter_fips_error_state(env: &mut Drivers, err: CaliptraError) {
+    log_error!("[rt] Entering FIPS error state");
+    FipsModule::zeroize_engines();
+
+    // Zeroize the key vault.
+    unsafe { KeyVault::zeroize() };
+
+    caliptra_drivers::report_fw_error_non_fatal(err.into());
+    env.fips_error = Some(err);
+}
+
+pub struct FipsZeroizeCmd;
+impl FipsZeroizeCmd {
+    /// Scrub every secret held by the runtime and enter the terminal
+    /// shutdown state.
+    ///
+    /// Key vault slots that are use or write locked cannot be cleared by
+    /// firmware; they are reported back to the caller in `kv_locked_slots`
+    /// and stay unusable until the next reset.
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(env: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        log_info!("[rt] FIPS zeroize");
+        let mut status = ZeroizeStatus::empty();
+
+        FipsModule::zeroize_engines();
+        status |= ZeroizeStatus::CRYPTO_ENGINES_CLEARED;
+
+        let kv_locked_slots = env.key_vault.erase_all_keys();
+        status |= ZeroizeStatus::KEY_VAULT_ERASED;
+
+        // Persistent data holds the DPE state, the cert TBS buffers and the
+        // measurement logs.
+        env.persistent_data.get_mut().zeroize();
+        env.exported_cdi_slots = [None; EXPORTED_HANDLES_NUM];
+        status |= ZeroizeStatus::PERSISTENT_DATA_CLEARED;
+
+        env.data_vault.lock_all();
+        status |= ZeroizeStatus::DATA_VAULT_LOCKED;
+
+        // Every subsequent command is rejected with RUNTIME_SHUTDOWN.
+        env.is_shutdown = true;
+
+        Ok(MailboxResp::Zeroize(ZeroizeResp {
+            hdr: MailboxRespHeader::default(),
+            status: status.bits(),
+            kv_locked_slots,
+        }))
+    }
+}
+
 pub struct FipsShutdownCmd;
 impl FipsShutdownCmd {
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
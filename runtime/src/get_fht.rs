@@ -0,0 +1,70 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    get_fht.rs
+
+Abstract:
+
+    File contains GetFht mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{GetFhtResp, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::{CaliptraError, CaliptraResult, CrashRecord, Lifecycle};
+
+pub struct GetFhtCmd;
+impl GetFhtCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        // The table is a debugging aid; it is not available on production
+        // parts unless debug is unlocked.
+        if drivers.soc_ifc.lifecycle() == Lifecycle::Production && drivers.soc_ifc.debug_locked() {
+            return Err(CaliptraError::RUNTIME_GET_FHT_NOT_ALLOWED);
+        }
+
+        let fht = &drivers.persistent_data.get().fht;
+        Ok(MailboxResp::GetFht(GetFhtResp {
+            hdr: MailboxRespHeader::default(),
+            format_version: GetFhtResp::FORMAT_VERSION,
+            fht_validity: CrashRecord::fht_validity(fht),
+            fht_marker: fht.fht_marker,
+            fht_major_ver: fht.fht_major_ver.into(),
+            fht_minor_ver: fht.fht_minor_ver.into(),
+            manifest_load_addr: fht.manifest_load_addr,
+            fips_fw_load_addr_hdl: fht.fips_fw_load_addr_hdl.0,
+            rt_fw_entry_point_hdl: fht.rt_fw_entry_point_hdl.0,
+            fmc_tci_dv_hdl: fht.fmc_tci_dv_hdl.0,
+            fmc_cdi_kv_hdl: fht.fmc_cdi_kv_hdl.0,
+            fmc_priv_key_kv_hdl: fht.fmc_priv_key_kv_hdl.0,
+            fmc_pub_key_x_dv_hdl: fht.fmc_pub_key_x_dv_hdl.0,
+            fmc_pub_key_y_dv_hdl: fht.fmc_pub_key_y_dv_hdl.0,
+            fmc_cert_sig_r_dv_hdl: fht.fmc_cert_sig_r_dv_hdl.0,
+            fmc_cert_sig_s_dv_hdl: fht.fmc_cert_sig_s_dv_hdl.0,
+            fmc_svn_dv_hdl: fht.fmc_svn_dv_hdl.0,
+            rt_tci_dv_hdl: fht.rt_tci_dv_hdl.0,
+            rt_cdi_kv_hdl: fht.rt_cdi_kv_hdl.0,
+            rt_priv_key_kv_hdl: fht.rt_priv_key_kv_hdl.0,
+            rt_svn_dv_hdl: fht.rt_svn_dv_hdl.0,
+            rt_min_svn_dv_hdl: fht.rt_min_svn_dv_hdl.0,
+            ldevid_cert_sig_r_dv_hdl: fht.ldevid_cert_sig_r_dv_hdl.0,
+            ldevid_cert_sig_s_dv_hdl: fht.ldevid_cert_sig_s_dv_hdl.0,
+            rt_hash_chain_kv_hdl: fht.rt_hash_chain_kv_hdl.0,
+            rt_hash_chain_max_svn: fht.rt_hash_chain_max_svn.into(),
+            ldevid_tbs_addr: fht.ldevid_tbs_addr,
+            ldevid_tbs_size: fht.ldevid_tbs_size.into(),
+            fmcalias_tbs_addr: fht.fmcalias_tbs_addr,
+            fmcalias_tbs_size: fht.fmcalias_tbs_size.into(),
+            rtalias_tbs_size: fht.rtalias_tbs_size.into(),
+            pcr_log_addr: fht.pcr_log_addr,
+            pcr_log_index: fht.pcr_log_index,
+            meas_log_addr: fht.meas_log_addr,
+            meas_log_index: fht.meas_log_index,
+            fuse_log_addr: fht.fuse_log_addr,
+            hw_caps: fht.hw_caps,
+        }))
+    }
+}
@@ -0,0 +1,151 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    get_rt_alias_csr.rs
+
+Abstract:
+
+    File contains GetRtAliasCsr mailbox command.
+
+--*/
+
+use crate::{CryptoBackend, Drivers};
+
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{GetRtAliasCsrResp, MailboxResp};
+use caliptra_drivers::{CaliptraError, CaliptraResult, Ecc384PubKey, KeyId};
+use caliptra_x509::{Ecdsa384CsrBuilder, Ecdsa384Signature, RtAliasCsrTbs, RtAliasCsrTbsParams};
+use crypto::Digest;
+use zeroize::Zeroize;
+
+pub struct GetRtAliasCsrCmd;
+impl GetRtAliasCsrCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+        let pub_key = drivers.persistent_data.get().fht.rt_dice_pub_key;
+
+        // The subject serial number matches the one in the RT Alias certificate
+        let hashed_rt_pub_key = drivers.compute_rt_alias_sn()?;
+        let mut subject_sn = [0u8; RtAliasCsrTbsParams::SUBJECT_SN_LEN];
+        Digest::write_hex_str(&hashed_rt_pub_key, &mut subject_sn)
+            .map_err(|_| CaliptraError::RUNTIME_GET_RT_ALIAS_CSR_FAILED)?;
+
+        let ueid = drivers.soc_ifc.fuse_bank().ueid();
+        let resp = Self::build_csr(
+            &mut drivers.crypto_backend(),
+            key_id_rt_priv_key,
+            &pub_key,
+            &ueid,
+            &subject_sn,
+        )?;
+
+        Ok(MailboxResp::GetRtAliasCsr(resp))
+    }
+
+    /// Build and sign the RT Alias CSR
+    ///
+    /// # Arguments
+    ///
+    /// * `crypto` - Crypto backend
+    /// * `priv_key` - Key slot holding the RT Alias private key
+    /// * `pub_key` - RT Alias public key
+    /// * `ueid` - Unique endpoint identifier
+    /// * `subject_sn` - Hex encoded subject serial number
+    fn build_csr(
+        crypto: &mut impl CryptoBackend,
+        priv_key: KeyId,
+        pub_key: &Ecc384PubKey,
+        ueid: &[u8; RtAliasCsrTbsParams::UEID_LEN],
+        subject_sn: &[u8; RtAliasCsrTbsParams::SUBJECT_SN_LEN],
+    ) -> CaliptraResult<GetRtAliasCsrResp> {
+        let params = RtAliasCsrTbsParams {
+            ueid,
+            subject_sn,
+            public_key: &pub_key.to_der(),
+        };
+        let tbs = RtAliasCsrTbs::new(&params);
+
+        let mut digest = crypto.sha384_digest(tbs.tbs())?;
+        let result = crypto.ecc384_sign(priv_key, pub_key, &digest);
+        digest.0.zeroize();
+        let sig = result?;
+
+        let sig = Ecdsa384Signature {
+            r: sig.r.into(),
+            s: sig.s.into(),
+        };
+        let Some(builder) = Ecdsa384CsrBuilder::new(tbs.tbs(), &sig) else {
+            return Err(CaliptraError::RUNTIME_GET_RT_ALIAS_CSR_FAILED);
+        };
+
+        let mut resp = GetRtAliasCsrResp::default();
+        let Some(csr_size) = builder.build(&mut resp.data) else {
+            return Err(CaliptraError::RUNTIME_GET_RT_ALIAS_CSR_FAILED);
+        };
+        resp.data_size = csr_size as u32;
+
+        Ok(resp)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::SwCryptoBackend;
+    use caliptra_drivers::Array4x12;
+    use openssl::bn::BigNumContext;
+    use openssl::ec::PointConversionForm;
+    use openssl::x509::X509Req;
+
+    #[test]
+    fn test_build_csr() {
+        let mut crypto = SwCryptoBackend::default();
+        let pub_key = crypto
+            .import_ecc384_priv_key(KeyId::KeyId5, &Array4x12::from([0x11; 48]))
+            .unwrap();
+        let resp = GetRtAliasCsrCmd::build_csr(
+            &mut crypto,
+            KeyId::KeyId5,
+            &pub_key,
+            &[0xAB; RtAliasCsrTbsParams::UEID_LEN],
+            &[b'0'; RtAliasCsrTbsParams::SUBJECT_SN_LEN],
+        )
+        .unwrap();
+
+        let req = X509Req::from_der(&resp.data[..resp.data_size as usize]).unwrap();
+        let req_key = req.public_key().unwrap();
+        assert!(req.verify(&req_key).unwrap());
+
+        let ec_key = req_key.ec_key().unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let point = ec_key
+            .public_key()
+            .to_bytes(ec_key.group(), PointConversionForm::UNCOMPRESSED, &mut ctx)
+            .unwrap();
+        assert_eq!(point, pub_key.to_der());
+    }
+
+    #[test]
+    fn test_build_csr_missing_key() {
+        let mut crypto = SwCryptoBackend::default();
+        let pub_key = crypto
+            .import_ecc384_priv_key(KeyId::KeyId5, &Array4x12::from([0x11; 48]))
+            .unwrap();
+        assert_eq!(
+            GetRtAliasCsrCmd::build_csr(
+                &mut crypto,
+                KeyId::KeyId6,
+                &pub_key,
+                &[0xAB; RtAliasCsrTbsParams::UEID_LEN],
+                &[b'0'; RtAliasCsrTbsParams::SUBJECT_SN_LEN],
+            )
+            .map(|_| ()),
+            Err(CaliptraError::DRIVER_ECC384_READ_PRIV_KEY_KV_UNKNOWN)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    heartbeat.rs
+
+Abstract:
+
+    File contains the command counters and uptime reported in FW_INFO.
+
+--*/
+
+/// Counters that let the SoC tell a wedged or restarted runtime from a
+/// healthy one
+#[derive(Default)]
+pub struct Heartbeat {
+    /// Mailbox commands handled since the runtime started
+    pub cmd_count: u32,
+
+    /// Mailbox commands that completed successfully
+    pub cmd_success_count: u32,
+
+    /// Mailbox commands that failed
+    pub cmd_failure_count: u32,
+}
+
+impl Heartbeat {
+    /// Account for a handled mailbox command. The counters wrap so that
+    /// they keep moving on a long-lived instance.
+    pub fn record(&mut self, success: bool) {
+        self.cmd_count = self.cmd_count.wrapping_add(1);
+        if success {
+            self.cmd_success_count = self.cmd_success_count.wrapping_add(1);
+        } else {
+            self.cmd_failure_count = self.cmd_failure_count.wrapping_add(1);
+        }
+    }
+}
+
+/// Cycles since the core last left reset
+#[cfg(feature = "riscv")]
+pub fn uptime_cycles() -> u64 {
+    caliptra_cpu::csr::mcycle()
+}
+
+#[cfg(not(feature = "riscv"))]
+pub fn uptime_cycles() -> u64 {
+    0
+}
@@ -16,8 +16,8 @@ use caliptra_cfi_derive_git::{cfi_impl_fn, cfi_mod_fn};
 use caliptra_cfi_lib_git::{cfi_assert, cfi_assert_eq, cfi_launder};
 use caliptra_common::{crypto::Ecc384KeyPair, keyids::KEY_ID_TMP};
 use caliptra_drivers::{
-    hmac384_kdf, Array4x12, Ecc384PrivKeyOut, Ecc384PubKey, Hmac384Data, Hmac384Key, Hmac384Tag,
-    KeyId, KeyReadArgs, KeyUsage, KeyWriteArgs,
+    Array4x12, Ecc384PubKey, Hmac384Data, Hmac384Key, Hmac384Tag, Kdf, KeyId, KeyReadArgs,
+    KeyUsage, KeyWriteArgs,
 };
 use caliptra_error::CaliptraResult;
 use zerocopy::IntoBytes;
@@ -44,27 +44,11 @@ fn ecc384_key_gen(
     label: &[u8],
     priv_key: KeyId,
 ) -> CaliptraResult<Ecc384KeyPair> {
-    hmac384_kdf(
-        &mut drivers.hmac384,
-        KeyReadArgs::new(input).into(),
-        label,
-        None,
-        &mut drivers.trng,
-        KeyWriteArgs::new(
-            KEY_ID_TMP,
-            KeyUsage::default()
-                .set_hmac_key_en()
-                .set_ecc_key_gen_seed_en(),
-        )
-        .into(),
-    )?;
-
-    let pub_key = drivers.ecc384.key_pair(
-        &KeyReadArgs::new(KEY_ID_TMP).into(),
-        &Array4x12::default(),
-        &mut drivers.trng,
-        KeyWriteArgs::new(priv_key, KeyUsage::default().set_ecc_private_key_en()).into(),
-    );
+    Kdf::new(&mut drivers.hmac384, &mut drivers.trng).derive_cdi(input, label, None, KEY_ID_TMP)?;
+
+    let pub_key = drivers
+        .ecc384
+        .keygen_to_kv(KEY_ID_TMP, &mut drivers.trng, priv_key);
 
     if KEY_ID_TMP != priv_key {
         drivers.key_vault.erase_key(KEY_ID_TMP)?;
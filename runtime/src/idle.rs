@@ -0,0 +1,41 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    idle.rs
+
+Abstract:
+
+    File contains the low power idle state of the runtime and its statistics.
+
+--*/
+
+use crate::heartbeat::uptime_cycles;
+
+/// Statistics of the time the runtime spent asleep waiting for the mailbox
+/// notification interrupt
+#[derive(Default)]
+pub struct IdleStats {
+    /// Number of times the core was halted
+    pub count: u32,
+
+    /// Cycles spent halted
+    pub cycles: u64,
+}
+
+impl IdleStats {
+    /// Halt the core until an interrupt wakes it up, and account for the time
+    /// spent asleep
+    pub fn sleep(&mut self) {
+        let start = uptime_cycles();
+        #[cfg(feature = "riscv")]
+        caliptra_cpu::csr::mpmc_halt_and_enable_interrupts();
+
+        self.count = self.count.saturating_add(1);
+        self.cycles = self
+            .cycles
+            .saturating_add(uptime_cycles().saturating_sub(start));
+    }
+}
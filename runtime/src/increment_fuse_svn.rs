@@ -0,0 +1,60 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    increment_fuse_svn.rs
+
+Abstract:
+
+    File contains IncrementFuseSvn mailbox command.
+
+--*/
+
+use crate::{handoff::RtHandoff, Drivers, PrivilegedSocIfc};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{IncrementFuseSvnResp, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::IntoBytes;
+
+pub struct IncrementFuseSvnCmd;
+impl IncrementFuseSvnCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let fuse_bank = drivers.soc_ifc.fuse_bank();
+        if fuse_bank.anti_rollback_disable() {
+            return Err(CaliptraError::RUNTIME_FUSE_SVN_ANTI_ROLLBACK_DISABLED);
+        }
+        let fuse_svn = fuse_bank.runtime_fuse_svn();
+
+        let rt_svn = RtHandoff {
+            data_vault: &drivers.data_vault,
+            fht: &drivers.persistent_data.get().fht,
+        }
+        .rt_svn()?;
+
+        // Burning a lower or equal SVN would be a no-op at best
+        if rt_svn <= fuse_svn {
+            return Err(CaliptraError::RUNTIME_FUSE_SVN_NOT_INCREASED);
+        }
+
+        PrivilegedSocIfc::request_runtime_svn_fuse_update(drivers, rt_svn)?;
+
+        // The fuse holds the SVN as the number of bits set, starting from bit 0.
+        // rt_svn is in 1..=MAX_RUNTIME_SVN here, so the shift cannot overflow.
+        let fuse_value = u128::MAX >> (u128::BITS - rt_svn);
+        let mut resp = IncrementFuseSvnResp {
+            hdr: MailboxRespHeader::default(),
+            fuse_svn,
+            new_fuse_svn: rt_svn,
+            ..Default::default()
+        };
+        resp.fuse_runtime_svn
+            .as_mut_bytes()
+            .copy_from_slice(&fuse_value.to_le_bytes());
+
+        Ok(MailboxResp::IncrementFuseSvn(resp))
+    }
+}
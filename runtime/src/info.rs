@@ -12,15 +12,18 @@ Abstract:
 
 --*/
 
-use crate::{handoff::RtHandoff, Drivers};
-use caliptra_common::mailbox_api::{FwInfoResp, GetIdevInfoResp, MailboxResp, MailboxRespHeader};
-use caliptra_drivers::CaliptraResult;
+use crate::{handoff::RtHandoff, heartbeat::uptime_cycles, Drivers};
+use caliptra_common::mailbox_api::{
+    FwInfoResp, FwInfoV1Resp, FwInfoVersionedReq, GetIdevInfoResp, MailboxResp, MailboxRespHeader,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
 use caliptra_image_types::RomInfo;
+use zerocopy::{FromBytes, IntoBytes};
 
 pub struct FwInfoCmd;
 impl FwInfoCmd {
     #[inline(never)]
-    pub(crate) fn execute(drivers: &Drivers) -> CaliptraResult<MailboxResp> {
+    pub(crate) fn execute(drivers: &Drivers, version: u8) -> CaliptraResult<MailboxResp> {
         let pdata = drivers.persistent_data.get();
 
         let handoff = RtHandoff {
@@ -32,8 +35,10 @@ impl FwInfoCmd {
         let min_runtime_svn = handoff.rt_min_svn()?;
         let fmc_manifest_svn = handoff.fmc_svn()?;
         let rom_info = handoff.fht.rom_info_addr.get()?;
+        let uptime = uptime_cycles();
+        let ecc = drivers.mem_ecc.counts(&drivers.soc_ifc);
 
-        Ok(MailboxResp::FwInfo(FwInfoResp {
+        let resp = FwInfoResp {
             hdr: MailboxRespHeader::default(),
             pl0_pauser: pdata.manifest1.header.pl0_pauser,
             runtime_svn,
@@ -47,7 +52,32 @@ impl FwInfoCmd {
             fmc_sha384_digest: pdata.manifest1.fmc.digest,
             runtime_sha384_digest: pdata.manifest1.runtime.digest,
             owner_pub_key_hash: drivers.data_vault.owner_pk_hash().into(),
-        }))
+            rom_sha_acc_lock_contention: pdata.fht.rom_sha_acc_lock_contention,
+            fmc_wdt_budget_cycles: pdata.fht.fmc_wdt_budget_cycles,
+            fmc_wdt_max_phase_cycles: pdata.fht.fmc_wdt_max_phase_cycles,
+            idle_count: drivers.idle_stats.count,
+            idle_cycles_low: drivers.idle_stats.cycles as u32,
+            idle_cycles_high: (drivers.idle_stats.cycles >> 32) as u32,
+            cmd_count: drivers.heartbeat.cmd_count,
+            cmd_success_count: drivers.heartbeat.cmd_success_count,
+            cmd_failure_count: drivers.heartbeat.cmd_failure_count,
+            uptime_cycles_low: uptime as u32,
+            uptime_cycles_high: (uptime >> 32) as u32,
+            iccm_ecc_cor_count: ecc.iccm_correctable,
+            dccm_ecc_cor_count: ecc.dccm_correctable,
+            mbox_ecc_cor_count: ecc.mbox_correctable,
+            mbox_ecc_unc_count: ecc.mbox_uncorrectable,
+            ccm_ecc_unc: u32::from(ecc.iccm_uncorrectable) | u32::from(ecc.dccm_uncorrectable) << 1,
+            dccm_scrub_passes: drivers.mem_ecc.scrub_passes(),
+        };
+
+        // Callers that predate version 2 expect the original response size
+        if version < FwInfoVersionedReq::VERSION {
+            let (resp, _) = FwInfoV1Resp::read_from_prefix(resp.as_bytes())
+                .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+            return Ok(MailboxResp::FwInfoV1(resp));
+        }
+        Ok(MailboxResp::FwInfo(resp))
     }
 }
 
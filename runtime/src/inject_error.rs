@@ -0,0 +1,69 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    inject_error.rs
+
+Abstract:
+
+    File contains InjectError mailbox command.
+
+--*/
+
+use crate::{Drivers, PrivilegedSocIfc};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{InjectErrorReq, InjectErrorType, MailboxResp};
+use caliptra_drivers::{CaliptraError, CaliptraResult, Lifecycle};
+use zerocopy::FromBytes;
+
+/// WDT1 and WDT2 periods used when injecting a watchdog expiry.
+const INJECT_WDT_TIMEOUT_CYCLES: u64 = 1000;
+
+pub struct InjectErrorCmd;
+impl InjectErrorCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    #[allow(clippy::empty_loop)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = InjectErrorReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // Error injection is a bring-up aid; it is not available on
+        // production parts unless debug is unlocked.
+        if drivers.soc_ifc.lifecycle() == Lifecycle::Production && drivers.soc_ifc.debug_locked() {
+            return Err(CaliptraError::RUNTIME_INJECT_ERROR_NOT_ALLOWED);
+        }
+
+        match InjectErrorType::from(cmd.error_type) {
+            // Failing the command latches the error code in
+            // CPTRA_FW_ERROR_NON_FATAL just like any other command failure.
+            InjectErrorType::NonFatal => Err(CaliptraError::RUNTIME_INJECTED_NON_FATAL_ERROR),
+            InjectErrorType::Fatal => caliptra_common::handle_fatal_error(
+                CaliptraError::RUNTIME_INJECTED_FATAL_ERROR.into(),
+            ),
+            InjectErrorType::Nmi => {
+                // VeeR raises an NMI when an external interrupt is taken while
+                // meivt points outside of DCCM.
+                #[cfg(feature = "riscv")]
+                {
+                    caliptra_cpu::csr::meivt_write(0);
+                    drivers.soc_ifc.trigger_mbox_notif_interrupt();
+                    caliptra_cpu::csr::mpmc_halt_and_enable_interrupts();
+                }
+
+                // The NMI handler never returns
+                loop {}
+            }
+            InjectErrorType::Wdt => {
+                // Let the watchdog expire as if the firmware had hung.
+                PrivilegedSocIfc::inject_wdt_expiry(drivers, INJECT_WDT_TIMEOUT_CYCLES)?;
+
+                // The NMI handler never returns
+                loop {}
+            }
+            InjectErrorType::Invalid => Err(CaliptraError::RUNTIME_INJECT_ERROR_INVALID_TYPE),
+        }
+    }
+}
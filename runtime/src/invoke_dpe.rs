@@ -13,7 +13,8 @@ Abstract:
 --*/
 
 use crate::{
-    CptraDpeTypes, DpeCrypto, DpeEnv, DpePlatform, Drivers, PauserPrivileges, PL0_PAUSER_FLAG,
+    CptraDpeTypes, DpeCrypto, DpeEnv, DpePlatform, Drivers, PauserPrivileges, PrivilegedSocIfc,
+    PL0_PAUSER_FLAG,
 };
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_common::mailbox_api::{InvokeDpeReq, InvokeDpeResp, MailboxResp, MailboxRespHeader};
@@ -25,7 +26,7 @@ use dpe::{
     },
     context::{Context, ContextState},
     response::{Response, ResponseHdr},
-    DpeInstance, U8Bool, MAX_HANDLES,
+    DpeInstance, U8Bool, DPE_PROFILE, MAX_HANDLES,
 };
 use zerocopy::{FromBytes, IntoBytes};
 
@@ -49,9 +50,12 @@ impl InvokeDpeCmd {
 
             let caller_privilege_level = drivers.caller_privilege_level();
             let dpe_context_threshold_err = drivers.is_dpe_context_threshold_exceeded();
+            let cert_policy = drivers.cert_policy;
+            let wdt_pet = PrivilegedSocIfc::wdt_pet(drivers);
 
             let pdata = drivers.persistent_data.get_mut();
             let crypto = DpeCrypto::new(
+                &mut drivers.sha256,
                 &mut drivers.sha384,
                 &mut drivers.trng,
                 &mut drivers.ecc384,
@@ -61,7 +65,8 @@ impl InvokeDpeCmd {
                 key_id_rt_cdi,
                 key_id_rt_priv_key,
                 &mut drivers.exported_cdi_slots,
-            );
+            )
+            .with_wdt_pet(wdt_pet);
             let pl0_pauser = pdata.manifest1.header.pl0_pauser;
             let (nb, nf) = Drivers::get_cert_validity_info(&pdata.manifest1);
             let ueid = &drivers.soc_ifc.fuse_bank().ueid();
@@ -75,6 +80,7 @@ impl InvokeDpeCmd {
                     &nf,
                     None,
                     Some(ueid),
+                    cert_policy,
                 ),
             };
 
@@ -97,6 +103,9 @@ impl InvokeDpeCmd {
                         .map_err(|_| CaliptraError::RUNTIME_COULD_NOT_GET_DPE_PROFILE)?,
                 )),
                 Command::InitCtx(cmd) => {
+                    if pdata.revoked_localities.contains(locality) {
+                        return Err(CaliptraError::RUNTIME_LOCALITY_REVOKED);
+                    }
                     // InitCtx can only create new contexts if they are simulation contexts.
                     if InitCtxCmd::flag_is_simulation(cmd) {
                         dpe_context_threshold_err?;
@@ -104,6 +113,14 @@ impl InvokeDpeCmd {
                     cmd.execute(dpe, &mut env, locality)
                 }
                 Command::DeriveContext(cmd) => {
+                    // Revoked localities may neither derive new contexts nor
+                    // receive them through a locality change.
+                    if pdata.revoked_localities.contains(locality)
+                        || (DeriveContextCmd::changes_locality(cmd)
+                            && pdata.revoked_localities.contains(cmd.target_locality))
+                    {
+                        return Err(CaliptraError::RUNTIME_LOCALITY_REVOKED);
+                    }
                     // If the recursive flag is not set, DeriveContext will generate a new context.
                     // If recursive _is_ set, it will extend the existing one, which will not count
                     // against the context threshold.
@@ -132,12 +149,16 @@ impl InvokeDpeCmd {
                     {
                         return Err(CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL);
                     }
+                    if let Some(cert_policy) = &cert_policy {
+                        cert_policy.check_certify_key(cmd)?;
+                    }
                     cmd.execute(dpe, &mut env, locality)
                 }
                 Command::DestroyCtx(cmd) => {
                     let destroy_ctx_resp = cmd.execute(dpe, &mut env, locality);
                     // clear tags for destroyed contexts
                     Self::clear_tags_for_inactive_contexts(dpe, context_has_tag, context_tags);
+                    Self::verify_contexts_reclaimed(dpe, context_has_tag, context_tags)?;
                     destroy_ctx_resp
                 }
                 Command::Sign(cmd) => cmd.execute(dpe, &mut env, locality),
@@ -197,4 +218,48 @@ impl InvokeDpeCmd {
             }
         });
     }
+
+    /// Check that destroyed DPE contexts were fully reclaimed: no live
+    /// context may reference an inactive parent or child, and inactive
+    /// contexts must hold no measurements or tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `dpe` - DpeInstance
+    /// * `context_has_tag` - Bool slice indicating if a DPE context has a tag
+    /// * `context_tags` - Tags for each DPE context
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn verify_contexts_reclaimed(
+        dpe: &DpeInstance,
+        context_has_tag: &[U8Bool; MAX_HANDLES],
+        context_tags: &[u32; MAX_HANDLES],
+    ) -> CaliptraResult<()> {
+        let is_inactive = |idx: usize| {
+            dpe.contexts
+                .get(idx)
+                .map_or(true, |c| c.state == ContextState::Inactive)
+        };
+
+        for (i, context) in dpe.contexts.iter().enumerate() {
+            let reclaimed = if context.state == ContextState::Inactive {
+                !context_has_tag[i].get()
+                    && context_tags[i] == 0
+                    && context.tci.tci_current.0 == [0; DPE_PROFILE.get_hash_size()]
+                    && context.tci.tci_cumulative.0 == [0; DPE_PROFILE.get_hash_size()]
+            } else {
+                let parent_live = context.parent_idx == Context::ROOT_INDEX
+                    || !is_inactive(context.parent_idx as usize);
+                let children_live = (0..MAX_HANDLES).all(|j| {
+                    context.children & (1 << j) == 0
+                        || (!is_inactive(j) && dpe.contexts[j].parent_idx as usize == i)
+                });
+                parent_live && children_live
+            };
+            if !reclaimed {
+                return Err(CaliptraError::RUNTIME_DPE_CONTEXT_NOT_RECLAIMED);
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,225 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    jobs.rs
+
+Abstract:
+
+    File contains the queue of asynchronous mailbox command jobs and the
+    StartJob and GetJobStatus mailbox commands.
+
+--*/
+
+use crate::{CmdPolicy, Drivers, EcdsaVerifyCmd, LmsVerifyCmd, PrivilegedSocIfc};
+use arrayvec::ArrayVec;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::cfi_assert;
+use caliptra_common::mailbox_api::{
+    CommandId, GetJobStatusReq, GetJobStatusResp, MailboxResp, MailboxRespHeader, StartJobReq,
+    StartJobResp,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::{FromBytes, IntoBytes};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JobState {
+    /// No job is queued
+    Empty,
+
+    /// The job is waiting for the mailbox to be idle
+    Pending,
+
+    /// The job ran and its result waits to be collected
+    Done(CaliptraResult<()>),
+}
+
+/// Queue of mailbox commands run in the background, so that commands that
+/// take longer than the SoC mailbox timeout can be polled for instead.
+///
+/// The queue holds a single job. A job runs the next time the mailbox is idle,
+/// and its result is kept until the PAUSER that started it collects it.
+pub struct JobQueue {
+    next_id: u32,
+    id: u32,
+    pauser: u32,
+    cmd: u32,
+    state: JobState,
+    req: ArrayVec<u8, { StartJobReq::DATA_MAX_SIZE }>,
+    resp: ArrayVec<u8, { GetJobStatusResp::DATA_MAX_SIZE }>,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            id: 0,
+            pauser: 0,
+            cmd: 0,
+            state: JobState::Empty,
+            req: ArrayVec::new(),
+            resp: ArrayVec::new(),
+        }
+    }
+}
+
+impl JobQueue {
+    /// Returns true if a job waits to run
+    pub(crate) fn is_pending(&self) -> bool {
+        self.state == JobState::Pending
+    }
+
+    /// Commands that can run as a job
+    fn is_job_cmd(cmd: &CommandId) -> bool {
+        matches!(*cmd, CommandId::LMS_VERIFY | CommandId::ECDSA384_VERIFY)
+    }
+
+    /// Run the queued job, if any. The mailbox is locked while the job runs
+    /// so that it does not race with a new command.
+    ///
+    /// Jobs are held back while in the FIPS error state, or once
+    /// PREPARE_FOR_SLEEP has saved its checkpoint, like the commands they
+    /// wrap.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether a job is still waiting for the mailbox
+    pub(crate) fn run_pending(drivers: &mut Drivers) -> CaliptraResult<bool> {
+        if drivers.jobs.state != JobState::Pending {
+            return Ok(false);
+        }
+
+        if drivers.fips_error.is_some()
+            || drivers
+                .persistent_data
+                .get()
+                .suspend_checkpoint
+                .is_sleeping()
+        {
+            return Ok(false);
+        }
+
+        let lock = drivers.mbox.lock();
+        if lock {
+            cfi_assert!(lock);
+            return Ok(true);
+        }
+
+        let cmd = CommandId::from(drivers.jobs.cmd);
+        let req = core::mem::take(&mut drivers.jobs.req);
+        // The job runs outside of a command, so it is not covered by the
+        // watchdog the mailbox loop arms around each command.
+        PrivilegedSocIfc::start_wdt(drivers)?;
+        let result = Self::execute(drivers, cmd, &req);
+        PrivilegedSocIfc::stop_wdt(drivers)?;
+        drivers.mbox.unlock();
+
+        let result = result.and_then(|mut resp| drivers.jobs.store_resp(&mut resp));
+        drivers.jobs.state = JobState::Done(result);
+        Ok(false)
+    }
+
+    fn store_resp(&mut self, resp: &mut MailboxResp) -> CaliptraResult<()> {
+        resp.populate_chksum()?;
+        self.resp.clear();
+        self.resp
+            .try_extend_from_slice(resp.as_bytes()?)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_API_RESPONSE_DATA_LEN_TOO_LARGE)
+    }
+
+    fn execute(drivers: &mut Drivers, cmd: CommandId, req: &[u8]) -> CaliptraResult<MailboxResp> {
+        match cmd {
+            CommandId::LMS_VERIFY => LmsVerifyCmd::execute(drivers, req),
+            CommandId::ECDSA384_VERIFY => EcdsaVerifyCmd::execute(drivers, req),
+            _ => Err(CaliptraError::RUNTIME_JOB_CMD_NOT_SUPPORTED),
+        }
+    }
+}
+
+pub struct StartJobCmd;
+impl StartJobCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        if cmd_args.len() > core::mem::size_of::<StartJobReq>() {
+            return Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY);
+        }
+        let mut cmd = StartJobReq::default();
+        cmd.as_mut_bytes()[..cmd_args.len()].copy_from_slice(cmd_args);
+        let req = cmd
+            .data
+            .get(..cmd.data_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        if !JobQueue::is_job_cmd(&CommandId::from(cmd.cmd)) {
+            return Err(CaliptraError::RUNTIME_JOB_CMD_NOT_SUPPORTED);
+        }
+        CmdPolicy::authorize(drivers, CommandId::from(cmd.cmd))?;
+
+        let jobs = &mut drivers.jobs;
+        if jobs.state != JobState::Empty {
+            return Err(CaliptraError::RUNTIME_JOB_QUEUE_FULL);
+        }
+        jobs.req.clear();
+        jobs.req
+            .try_extend_from_slice(req)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        jobs.id = jobs.next_id;
+        jobs.next_id = jobs.next_id.checked_add(1).unwrap_or(1);
+        jobs.pauser = drivers.mbox.user();
+        jobs.cmd = cmd.cmd;
+        jobs.state = JobState::Pending;
+
+        Ok(MailboxResp::StartJob(StartJobResp {
+            hdr: MailboxRespHeader::default(),
+            job_id: jobs.id,
+        }))
+    }
+}
+
+pub struct GetJobStatusCmd;
+impl GetJobStatusCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = GetJobStatusReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // Jobs are only visible to the PAUSER that started them
+        let jobs = &mut drivers.jobs;
+        if jobs.state == JobState::Empty
+            || jobs.id != cmd.job_id
+            || jobs.pauser != drivers.mbox.user()
+        {
+            return Err(CaliptraError::RUNTIME_JOB_NOT_FOUND);
+        }
+
+        let mut resp = GetJobStatusResp::default();
+        match jobs.state {
+            JobState::Pending => resp.status = GetJobStatusResp::STATUS_IN_PROGRESS,
+            JobState::Done(Ok(())) => {
+                resp.status = GetJobStatusResp::STATUS_COMPLETE;
+                resp.data_size = jobs.resp.len() as u32;
+                resp.data
+                    .get_mut(..jobs.resp.len())
+                    .ok_or(CaliptraError::RUNTIME_MAILBOX_API_RESPONSE_DATA_LEN_TOO_LARGE)?
+                    .copy_from_slice(&jobs.resp);
+                jobs.state = JobState::Empty;
+            }
+            JobState::Done(Err(e)) => {
+                resp.status = GetJobStatusResp::STATUS_FAILED;
+                resp.error = e.into();
+                jobs.state = JobState::Empty;
+            }
+            JobState::Empty => return Err(CaliptraError::RUNTIME_JOB_NOT_FOUND),
+        }
+
+        Ok(MailboxResp::GetJobStatus(resp))
+    }
+}
@@ -0,0 +1,47 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    kv_status.rs
+
+Abstract:
+
+    File contains GetKvStatus mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{GetKvStatusResp, KvSlotInfo, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::CaliptraResult;
+
+pub struct GetKvStatusCmd;
+impl GetKvStatusCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let mut resp = GetKvStatusResp {
+            hdr: MailboxRespHeader::default(),
+            ..Default::default()
+        };
+
+        let slot_map = drivers.key_vault.slot_map();
+        for (i, (status, info)) in slot_map.iter().zip(resp.slots.iter_mut()).enumerate() {
+            if status.occupied() {
+                resp.occupied_slots |= 1 << i;
+            }
+            if status.use_lock {
+                resp.use_locked_slots |= 1 << i;
+            }
+            if status.write_lock {
+                resp.write_locked_slots |= 1 << i;
+            }
+            *info = KvSlotInfo {
+                usage: status.usage.0,
+                kind: status.kind() as u32,
+            };
+        }
+
+        Ok(MailboxResp::GetKvStatus(resp))
+    }
+}
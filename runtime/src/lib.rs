@@ -14,28 +14,71 @@ Abstract:
 #![cfg_attr(not(feature = "fip-self-test"), allow(unused))]
 #![no_std]
 mod authorize_and_stash;
+mod batch;
+mod boot_profile;
+mod cancel;
 mod capabilities;
+mod cert_dist_points;
+mod cert_policy;
 mod certify_key_extended;
+mod cmd_policy;
+mod cmd_version;
+mod commit_owner_key_rotation;
+mod configure_wdt;
+mod crash_dump;
+mod crypto_backend;
+mod cwt;
 pub mod dice;
 mod disable;
 mod dpe_crypto;
 mod dpe_platform;
+#[cfg(feature = "dpe-test-hooks")]
+mod dpe_stats;
 mod drivers;
+mod entropy_health;
 pub mod fips;
+mod get_fht;
 mod get_fmc_alias_csr;
 mod get_idev_csr;
+mod get_rt_alias_csr;
 pub mod handoff;
+mod heartbeat;
 mod hmac;
+mod idle;
+mod increment_fuse_svn;
 pub mod info;
+mod inject_error;
 mod invoke_dpe;
+mod jobs;
+mod kv_status;
+mod log_level;
+mod mailbox_auth;
+mod mctp;
+mod migrate;
+mod monotonic_counter;
+pub mod nmi;
 mod pcr;
+mod pldm;
+mod pldm_fw_update;
 mod populate_idev;
+mod privileged;
+mod raw_entropy;
+mod reseed_field_entropy;
+mod revoke_locality;
+mod sealed_key;
 mod set_auth_manifest;
+mod set_measurement_manifest;
 mod sign_with_exported_ecdsa;
+mod spdm;
 mod stash_measurement;
+mod stream_verify;
 mod subject_alt_name;
+mod suspend;
+mod timestamp;
 mod update;
+mod update_history;
 mod verify;
+mod verify_object;
 
 // Used by runtime tests
 pub mod mailbox;
@@ -45,37 +88,81 @@ use caliptra_registers::soc_ifc::SocIfcReg;
 pub use drivers::{Drivers, PauserPrivileges};
 use mailbox::Mailbox;
 
+pub use crate::batch::BatchCmd;
+pub use crate::boot_profile::GetBootProfileCmd;
 use crate::capabilities::CapabilitiesCmd;
+pub use crate::cert_dist_points::SetCertDistPointsCmd;
+pub use crate::cert_policy::{CertPolicy, SetCertPolicyCmd};
 pub use crate::certify_key_extended::CertifyKeyExtendedCmd;
+pub use crate::cmd_policy::{CmdAccess, CmdPolicy};
+pub use crate::heartbeat::Heartbeat;
 pub use crate::hmac::Hmac;
+pub use crate::idle::IdleStats;
 use crate::sign_with_exported_ecdsa::SignWithExportedEcdsaCmd;
 pub use crate::subject_alt_name::AddSubjectAltNameCmd;
+pub use crate::suspend::{PrepareForSleepCmd, ResumeNotifyCmd, SuspendPrepareCmd};
 pub use authorize_and_stash::{IMAGE_AUTHORIZED, IMAGE_HASH_MISMATCH, IMAGE_NOT_AUTHORIZED};
 pub use caliptra_common::fips::FipsVersionCmd;
+pub use cmd_version::CmdVersion;
+pub use commit_owner_key_rotation::CommitOwnerKeyRotationCmd;
+pub use configure_wdt::ConfigureWdtCmd;
+pub use crash_dump::GetCrashDumpCmd;
+#[cfg(feature = "std")]
+pub use crypto_backend::SwCryptoBackend;
+pub use crypto_backend::{CryptoBackend, HwCryptoBackend};
+pub use cwt::{CborWriter, Cwt};
 pub use dice::{GetFmcAliasCertCmd, GetLdevCertCmd, IDevIdCertCmd};
 pub use disable::DisableAttestationCmd;
 use dpe_crypto::DpeCrypto;
 pub use dpe_platform::{DpePlatform, VENDOR_ID, VENDOR_SKU};
-pub use fips::FipsShutdownCmd;
+#[cfg(feature = "dpe-test-hooks")]
+pub use dpe_stats::GetDpeStatsCmd;
+pub use entropy_health::GetEntropyHealthCmd;
 #[cfg(feature = "fips_self_test")]
 pub use fips::{fips_self_test_cmd, fips_self_test_cmd::SelfTestStatus};
+pub use fips::{FipsShutdownCmd, FipsZeroizeCmd};
+pub use kv_status::GetKvStatusCmd;
+pub use log_level::SetLogLevelCmd;
+pub use mailbox_auth::{MailboxAuthKey, PairMailboxCmd};
+pub use mctp::MctpCmd;
+pub use pldm_fw_update::PldmFwUpdateState;
 pub use populate_idev::PopulateIDevIdCertCmd;
+pub use privileged::{PrivilegedOp, PrivilegedOpLog, PrivilegedOpRecord, PrivilegedSocIfc};
+pub use raw_entropy::{CaptureRawEntropyCmd, GetRawEntropyCmd};
+pub use reseed_field_entropy::ReseedFieldEntropyCmd;
+pub use revoke_locality::RevokeLocalityCmd;
 
+pub use get_fht::GetFhtCmd;
 pub use get_fmc_alias_csr::GetFmcAliasCsrCmd;
 pub use get_idev_csr::GetIdevCsrCmd;
+pub use get_rt_alias_csr::GetRtAliasCsrCmd;
+pub use increment_fuse_svn::IncrementFuseSvnCmd;
 pub use info::{FwInfoCmd, IDevIdInfoCmd};
+pub use inject_error::InjectErrorCmd;
 pub use invoke_dpe::InvokeDpeCmd;
+pub use jobs::{GetJobStatusCmd, JobQueue, StartJobCmd};
+pub use monotonic_counter::{CreateCounterCmd, IncrementCounterCmd, ReadCounterCmd};
 pub use pcr::IncrementPcrResetCounterCmd;
+pub use sealed_key::{SealKeyCmd, UnsealKeyCmd};
 pub use set_auth_manifest::SetAuthManifestCmd;
+pub use set_measurement_manifest::SetMeasurementManifestCmd;
+pub use spdm::{SpdmCmd, SpdmState};
 pub use stash_measurement::StashMeasurementCmd;
+pub use stream_verify::{FinishStreamVerifyCmd, StartStreamVerifyCmd, StreamDataCmd, StreamVerify};
+pub use timestamp::TimestampCmd;
+pub use update_history::GetUpdateHistoryCmd;
 pub use verify::{EcdsaVerifyCmd, LmsVerifyCmd};
+pub use verify_object::{SetObjectSchemasCmd, VerifyObjectCmd};
 pub mod packet;
+use caliptra_common::boot_profile::boot_checkpoint;
 use caliptra_common::mailbox_api::{CommandId, MailboxResp};
+use caliptra_drivers::BootCheckpoint;
 use packet::Packet;
 pub mod tagging;
 use tagging::{GetTaggedTciCmd, TagTciCmd};
 
-use caliptra_common::cprintln;
+use caliptra_common::stack_guard;
+use caliptra_common::{log_error, log_trace, log_warn};
 
 use caliptra_drivers::{okmutref, CaliptraError, CaliptraResult, ResetReason};
 use caliptra_registers::el2_pic_ctrl::El2PicCtrl;
@@ -103,6 +190,7 @@ pub enum RtBootStatus {
     RtReadyForCommands = RUNTIME_BOOT_STATUS_BASE,
     RtFipSelfTestStarted = RUNTIME_BOOT_STATUS_BASE + 1,
     RtFipSelfTestComplete = RUNTIME_BOOT_STATUS_BASE + 2,
+    RtReadyForSleep = RUNTIME_BOOT_STATUS_BASE + 3,
 }
 
 impl From<RtBootStatus> for u32 {
@@ -129,27 +217,68 @@ impl DpeTypes for CptraDpeTypes {
 }
 
 /// Run pending jobs and enter low power mode.
-fn enter_idle(drivers: &mut Drivers) {
+fn enter_idle(drivers: &mut Drivers) -> CaliptraResult<()> {
     // Run pending jobs before entering low power mode.
     #[cfg(feature = "fips_self_test")]
     if let SelfTestStatus::InProgress(execute) = drivers.self_test_status {
         let lock = drivers.mbox.lock();
         if lock == false {
-            let result = execute(drivers);
+            // The self test runs outside of a command, so it is not covered
+            // by the watchdog the mailbox loop arms around each command.
+            PrivilegedSocIfc::start_wdt(drivers)?;
+            let results = execute(drivers);
+            PrivilegedSocIfc::stop_wdt(drivers)?;
             drivers.mbox.unlock();
-            match result {
-                Ok(_) => drivers.self_test_status = SelfTestStatus::Done,
-                Err(e) => caliptra_common::handle_fatal_error(e.into()),
+            if let Some(err) = results.first_error {
+                fips::enter_fips_error_state(drivers, err);
             }
+            drivers.self_test_status = SelfTestStatus::Done(results);
         } else {
             cfi_assert!(lock);
             // Don't enter low power mode when in progress
-            return;
+            return Ok(());
         }
     }
 
-    #[cfg(feature = "riscv")]
-    caliptra_cpu::csr::mpmc_halt_and_enable_interrupts();
+    // Scrub the payload of a confidential command once the SoC has released
+    // the mailbox, before anyone else can lock it.
+    if let Some(len) = drivers.mbox_scrub_len {
+        let lock = drivers.mbox.lock();
+        if lock {
+            cfi_assert!(lock);
+            // Don't enter low power mode until the mailbox is scrubbed
+            return Ok(());
+        }
+        drivers.mbox.zeroize(len as usize);
+        drivers.mbox.unlock();
+        drivers.mbox_scrub_len = None;
+    }
+
+    // Don't enter low power mode while a job waits for the mailbox
+    if JobQueue::run_pending(drivers)? {
+        return Ok(());
+    }
+
+    drivers.mem_ecc.scrub();
+    drivers.idle_stats.sleep();
+    Ok(())
+}
+
+/// Commands that do not use the crypto engines and remain available after a
+/// FIPS self test failure.
+fn allowed_in_fips_error_state(cmd: CommandId) -> bool {
+    matches!(
+        cmd,
+        CommandId::VERSION
+            | CommandId::FW_INFO
+            | CommandId::CAPABILITIES
+            | CommandId::SELF_TEST_GET_RESULTS
+            | CommandId::GET_UPDATE_HISTORY
+            | CommandId::GET_CRASH_DUMP
+            | CommandId::GET_BOOT_PROFILE
+            | CommandId::SHUTDOWN
+            | CommandId::ZEROIZE
+    )
 }
 
 /// Handles the pending mailbox command and writes the repsonse back to the mailbox
@@ -163,10 +292,29 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
         return Err(CaliptraError::RUNTIME_CMD_RESERVED_PAUSER);
     }
 
+    // Only informational commands are serviced in the FIPS error state
+    if drivers.fips_error.is_some() && !allowed_in_fips_error_state(drivers.mbox.cmd()) {
+        return Err(CaliptraError::RUNTIME_FIPS_ERROR_STATE);
+    }
+
+    // Drop commands the caller is not authorized to invoke
+    CmdPolicy::authorize(drivers, drivers.mbox.cmd())?;
+
+    // Only informational commands are serviced until the SoC resumes
+    if drivers
+        .persistent_data
+        .get()
+        .suspend_checkpoint
+        .is_suspended()
+        && !suspend::allowed_while_suspended(drivers.mbox.cmd())
+    {
+        return Err(CaliptraError::RUNTIME_SUSPENDED);
+    }
+
     // For firmware update, don't read data from the mailbox
     if drivers.mbox.cmd() == CommandId::FIRMWARE_LOAD {
         cfi_assert_eq(drivers.mbox.cmd(), CommandId::FIRMWARE_LOAD);
-        update::handle_impactless_update(drivers)?;
+        update::handle_impactless_update(drivers, None)?;
 
         // If the handler succeeds but does not invoke reset that is
         // unexpected. Denote that the update failed.
@@ -178,8 +326,15 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
     // Get the command bytes
     let req_packet = Packet::copy_from_mbox(drivers)?;
     let cmd_bytes = req_packet.as_bytes()?;
+    cancel::checkpoint(&mut drivers.soc_ifc, &drivers.mbox)?;
+
+    log_trace!("[rt]cmd =0x{:x}, len={}", req_packet.cmd, req_packet.len);
+
+    // Privileged commands must be authenticated once the mailbox is paired
+    let cmd_bytes =
+        mailbox_auth::authenticate(drivers, CommandId::from(req_packet.cmd), cmd_bytes)?;
 
-    cprintln!("[rt]cmd =0x{:x}, len={}", req_packet.cmd, req_packet.len);
+    let cmd_version = CmdVersion::check(&CommandId::from(req_packet.cmd), cmd_bytes)?;
 
     // Handle the request and generate the response
     let mut resp = match CommandId::from(req_packet.cmd) {
@@ -193,7 +348,7 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
         CommandId::EXTEND_PCR => ExtendPcrCmd::execute(drivers, cmd_bytes),
         CommandId::STASH_MEASUREMENT => StashMeasurementCmd::execute(drivers, cmd_bytes),
         CommandId::DISABLE_ATTESTATION => DisableAttestationCmd::execute(drivers),
-        CommandId::FW_INFO => FwInfoCmd::execute(drivers),
+        CommandId::FW_INFO => FwInfoCmd::execute(drivers, cmd_version),
         CommandId::DPE_TAG_TCI => TagTciCmd::execute(drivers, cmd_bytes),
         CommandId::DPE_GET_TAGGED_TCI => GetTaggedTciCmd::execute(drivers, cmd_bytes),
         CommandId::POPULATE_IDEV_CERT => PopulateIDevIdCertCmd::execute(drivers, cmd_bytes),
@@ -204,11 +359,11 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
         CommandId::INCREMENT_PCR_RESET_COUNTER => {
             IncrementPcrResetCounterCmd::execute(drivers, cmd_bytes)
         }
-        CommandId::QUOTE_PCRS => GetPcrQuoteCmd::execute(drivers, cmd_bytes),
+        CommandId::QUOTE_PCRS => GetPcrQuoteCmd::execute(drivers, cmd_bytes, cmd_version),
         CommandId::VERSION => {
             FipsVersionCmd::execute(&drivers.soc_ifc).map(MailboxResp::FipsVersion)
         }
-        CommandId::CAPABILITIES => CapabilitiesCmd::execute(),
+        CommandId::CAPABILITIES => CapabilitiesCmd::execute(cmd_bytes, cmd_version),
         #[cfg(feature = "fips_self_test")]
         CommandId::SELF_TEST_START => match drivers.self_test_status {
             SelfTestStatus::Idle => {
@@ -219,24 +374,75 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
         },
         #[cfg(feature = "fips_self_test")]
         CommandId::SELF_TEST_GET_RESULTS => match drivers.self_test_status {
-            SelfTestStatus::Done => {
+            SelfTestStatus::Done(results) => {
                 drivers.self_test_status = SelfTestStatus::Idle;
-                Ok(MailboxResp::default())
+                Ok(results.into())
             }
             _ => Err(CaliptraError::RUNTIME_SELF_TEST_NOT_STARTED),
         },
         CommandId::SHUTDOWN => FipsShutdownCmd::execute(drivers),
+        CommandId::ZEROIZE => FipsZeroizeCmd::execute(drivers),
         CommandId::SET_AUTH_MANIFEST => SetAuthManifestCmd::execute(drivers, cmd_bytes),
         CommandId::AUTHORIZE_AND_STASH => AuthorizeAndStashCmd::execute(drivers, cmd_bytes),
         CommandId::GET_IDEV_CSR => GetIdevCsrCmd::execute(drivers, cmd_bytes),
         CommandId::GET_FMC_ALIAS_CSR => GetFmcAliasCsrCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_RT_ALIAS_CSR => GetRtAliasCsrCmd::execute(drivers),
         CommandId::SIGN_WITH_EXPORTED_ECDSA => {
             SignWithExportedEcdsaCmd::execute(drivers, cmd_bytes)
         }
+        CommandId::REVOKE_LOCALITY => RevokeLocalityCmd::execute(drivers, cmd_bytes),
+        CommandId::INJECT_ERROR => InjectErrorCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_UPDATE_HISTORY => GetUpdateHistoryCmd::execute(drivers),
+        CommandId::INCREMENT_FUSE_SVN => IncrementFuseSvnCmd::execute(drivers),
+        CommandId::COMMIT_OWNER_KEY_ROTATION => {
+            CommitOwnerKeyRotationCmd::execute(drivers, cmd_bytes)
+        }
+        CommandId::SET_CERT_POLICY => SetCertPolicyCmd::execute(drivers, cmd_bytes),
+        CommandId::SET_CERT_DIST_POINTS => SetCertDistPointsCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_KV_STATUS => GetKvStatusCmd::execute(drivers),
+        CommandId::CAPTURE_RAW_ENTROPY => CaptureRawEntropyCmd::execute(drivers),
+        CommandId::GET_RAW_ENTROPY => GetRawEntropyCmd::execute(drivers),
+        CommandId::START_JOB => StartJobCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_JOB_STATUS => GetJobStatusCmd::execute(drivers, cmd_bytes),
+        CommandId::BATCH => BatchCmd::execute(drivers, cmd_bytes),
+        CommandId::SUSPEND_PREPARE => SuspendPrepareCmd::execute(drivers),
+        CommandId::RESUME_NOTIFY => ResumeNotifyCmd::execute(drivers),
+        CommandId::PREPARE_FOR_SLEEP => PrepareForSleepCmd::execute(drivers),
+        CommandId::CONFIGURE_WDT => ConfigureWdtCmd::execute(drivers, cmd_bytes),
+        CommandId::SET_OBJECT_SCHEMAS => SetObjectSchemasCmd::execute(drivers, cmd_bytes),
+        CommandId::VERIFY_OBJECT => VerifyObjectCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_CRASH_DUMP => GetCrashDumpCmd::execute(drivers),
+        CommandId::SET_LOG_LEVEL => SetLogLevelCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_BOOT_PROFILE => GetBootProfileCmd::execute(drivers),
+        CommandId::RESEED_FIELD_ENTROPY => ReseedFieldEntropyCmd::execute(drivers),
+        CommandId::GET_ENTROPY_HEALTH => GetEntropyHealthCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_FHT => GetFhtCmd::execute(drivers),
+        CommandId::SPDM => SpdmCmd::execute(drivers, cmd_bytes),
+        CommandId::MCTP => MctpCmd::execute(drivers, cmd_bytes),
+        CommandId::SET_MEASUREMENT_MANIFEST => {
+            SetMeasurementManifestCmd::execute(drivers, cmd_bytes)
+        }
+        CommandId::START_STREAM_VERIFY => StartStreamVerifyCmd::execute(drivers, cmd_bytes),
+        CommandId::STREAM_DATA => StreamDataCmd::execute(drivers, cmd_bytes),
+        CommandId::FINISH_STREAM_VERIFY => FinishStreamVerifyCmd::execute(drivers, cmd_bytes),
+        CommandId::SEAL_KEY => SealKeyCmd::execute(drivers, cmd_bytes),
+        CommandId::UNSEAL_KEY => UnsealKeyCmd::execute(drivers, cmd_bytes),
+        CommandId::CREATE_COUNTER => CreateCounterCmd::execute(drivers),
+        CommandId::INCREMENT_COUNTER => IncrementCounterCmd::execute(drivers, cmd_bytes),
+        CommandId::READ_COUNTER => ReadCounterCmd::execute(drivers, cmd_bytes),
+        CommandId::TIMESTAMP => TimestampCmd::execute(drivers, cmd_bytes),
+        CommandId::PAIR_MAILBOX => PairMailboxCmd::execute(drivers, cmd_bytes),
+        #[cfg(feature = "dpe-test-hooks")]
+        CommandId::GET_DPE_STATS => GetDpeStatsCmd::execute(drivers),
         _ => Err(CaliptraError::RUNTIME_UNIMPLEMENTED_COMMAND),
     };
     let resp = okmutref(&mut resp)?;
 
+    // The SoC is no longer waiting for the response
+    if drivers.mbox.is_aborted() {
+        return Err(CaliptraError::RUNTIME_MAILBOX_CMD_ABORTED);
+    }
+
     // Send the response
     Packet::copy_to_mbox(drivers, resp)?;
 
@@ -262,6 +468,7 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
     // Indicator to SOC that RT firmware is ready
     drivers.soc_ifc.assert_ready_for_runtime();
     caliptra_drivers::report_boot_status(RtBootStatus::RtReadyForCommands.into());
+    boot_checkpoint(BootCheckpoint::RtReady);
     // Disable attestation if in the middle of executing an mbox cmd during warm reset
     let cmd_busy = drivers.mbox.cmd_busy();
     if cmd_busy {
@@ -276,13 +483,13 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
             }
             match result {
                 Ok(_) => {
-                    cprintln!("Disabled attest - cmd busy + warm rst");
+                    log_warn!("Disabled attest - cmd busy + warm rst");
                     caliptra_drivers::report_fw_error_non_fatal(
                         CaliptraError::RUNTIME_CMD_BUSY_DURING_WARM_RESET.into(),
                     );
                 }
                 Err(e) => {
-                    cprintln!("{}", e.0);
+                    log_error!("{}", e.0);
                     return Err(CaliptraError::RUNTIME_GLOBAL_EXCEPTION);
                 }
             }
@@ -292,9 +499,10 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
     }
     #[cfg(feature = "riscv")]
     setup_mailbox_wfi(drivers);
-    caliptra_common::wdt::stop_wdt(&mut drivers.soc_ifc);
+    PrivilegedSocIfc::stop_wdt(drivers)?;
+    drivers.soc_ifc.clear_mbox_cancel_request();
     loop {
-        enter_idle(drivers);
+        enter_idle(drivers)?;
 
         // Random delay for CFI glitch protection.
         CfiCounter::delay();
@@ -303,6 +511,15 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
             return Err(CaliptraError::RUNTIME_SHUTDOWN);
         }
 
+        // The guard region at the bottom of the stack is only written if the
+        // stack overflowed or came close to it.
+        if !stack_guard::stack_guard_intact() {
+            drivers
+                .soc_ifc
+                .set_fw_extended_error(stack_guard::stack_high_water());
+            return Err(CaliptraError::RUNTIME_STACK_OVERFLOW);
+        }
+
         // The hardware will set this interrupt high when the mbox_fsm_ps
         // transitions to state MBOX_EXECUTE_UC (same state as mbox.is_cmd_ready()),
         // but once cleared will not set it high again until the state
@@ -322,27 +539,47 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
             }
 
             // TODO : Move start/stop WDT to wait_for_cmd when NMI is implemented.
-            caliptra_common::wdt::start_wdt(
-                &mut drivers.soc_ifc,
-                caliptra_common::WdtTimeout::default(),
-            );
+            PrivilegedSocIfc::start_wdt(drivers)?;
             caliptra_drivers::report_fw_error_non_fatal(0);
+            let confidential = CmdPolicy::confidential(&drivers.mbox.cmd());
+            let req_len = drivers.mbox.dlen();
+            drivers.handling_command = true;
             let commmand_result = handle_command(drivers);
+            drivers.handling_command = false;
+            // A cancel that arrived after the last checkpoint must not carry
+            // over to the next command.
+            drivers.soc_ifc.clear_mbox_cancel_request();
             if cfi_launder(commmand_result.is_ok()) {
                 cfi_assert!(commmand_result.is_ok());
             } else {
                 cfi_assert!(commmand_result.is_err());
             }
+            drivers.heartbeat.record(commmand_result.is_ok());
             match commmand_result {
                 Ok(status) => {
-                    drivers.mbox.set_status(status);
+                    if !drivers.mbox.is_aborted() {
+                        drivers.mbox.set_status(status);
+                    }
                 }
                 Err(e) => {
+                    if cancel::is_canceled(e) {
+                        cancel::scrub_canceled_command();
+                    }
                     caliptra_drivers::report_fw_error_non_fatal(e.into());
-                    drivers.mbox.set_status(MboxStatusE::CmdFailure);
+                    // There is no one to report the failure to once the SoC
+                    // has released the mailbox
+                    if !drivers.mbox.is_aborted() {
+                        drivers.mbox.set_status(MboxStatusE::CmdFailure);
+                    }
                 }
             }
-            caliptra_common::wdt::stop_wdt(&mut drivers.soc_ifc);
+            if confidential {
+                drivers.mbox_scrub_len = Some(req_len.max(drivers.mbox.dlen()));
+            }
+            PrivilegedSocIfc::stop_wdt(drivers)?;
+            if drivers.is_sleeping {
+                suspend::enter_sleep(drivers);
+            }
         } else {
             cfi_assert!(!cmd_ready);
         }
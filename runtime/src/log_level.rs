@@ -0,0 +1,38 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    log_level.rs
+
+Abstract:
+
+    File contains the SetLogLevel mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::log::LogLevel;
+use caliptra_common::mailbox_api::{MailboxResp, SetLogLevelReq};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::FromBytes;
+
+pub struct SetLogLevelCmd;
+impl SetLogLevelCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SetLogLevelReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // 0 restores the level the firmware was built with. Levels above it
+        // are accepted but print nothing more, as those messages were
+        // compiled out.
+        LogLevel::try_from(cmd.log_level).map_err(|_| CaliptraError::RUNTIME_LOG_LEVEL_INVALID)?;
+        drivers.persistent_data.get_mut().log_level = cmd.log_level;
+
+        Ok(MailboxResp::default())
+    }
+}
@@ -41,6 +41,12 @@ impl Mailbox {
         mbox.status().read().mbox_fsm_ps().mbox_execute_uc()
     }
 
+    /// Check if the SoC released the mailbox before the command in progress
+    /// completed. Nothing may be written to the mailbox once it has.
+    pub fn is_aborted(&self) -> bool {
+        !self.is_cmd_ready()
+    }
+
     /// Check if we are currently executing a mailbox command
     pub fn cmd_busy(&self) -> bool {
         let mbox = self.mbox.regs();
@@ -158,6 +164,56 @@ impl Mailbox {
         mbox.status().write(|w| w.status(|_| status));
     }
 
+    /// Zeroize the first `len` bytes of the mailbox SRAM. The mailbox must
+    /// be locked by the uC.
+    pub fn zeroize(&mut self, len: usize) {
+        let len = len.min(memory_layout::MBOX_SIZE as usize);
+        let count = (len + size_of::<u32>() - 1) / size_of::<u32>();
+        let sram = memory_layout::MBOX_ORG as *mut u32;
+        for i in 0..count {
+            unsafe { core::ptr::write_volatile(sram.add(i), 0) };
+        }
+    }
+
+    /// Write `buf` to the mailbox SRAM at `offset`, which must be
+    /// word-aligned. A trailing partial word is padded with zeros.
+    pub fn write_sram(&mut self, offset: usize, buf: &[u8]) -> CaliptraResult<()> {
+        if offset % size_of::<u32>() != 0
+            || offset.saturating_add(buf.len()) > memory_layout::MBOX_SIZE as usize
+        {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+        let sram = memory_layout::MBOX_ORG as *mut u32;
+        let start = offset / size_of::<u32>();
+        for (i, chunk) in buf.chunks(size_of::<u32>()).enumerate() {
+            let mut word = 0_u32;
+            word.as_mut_bytes()[..chunk.len()].copy_from_slice(chunk);
+            unsafe { core::ptr::write_volatile(sram.add(start + i), word) };
+        }
+        Ok(())
+    }
+
+    /// Move `len` bytes of the mailbox SRAM at `offset`, which must be
+    /// word-aligned, to the start of the mailbox and make them the data of
+    /// the command in progress.
+    pub fn move_to_start(&mut self, offset: usize, len: usize) -> CaliptraResult<()> {
+        if offset % size_of::<u32>() != 0
+            || offset.saturating_add(len) > memory_layout::MBOX_SIZE as usize
+        {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+        let sram = memory_layout::MBOX_ORG as *mut u32;
+        let start = offset / size_of::<u32>();
+        // Copying forward is safe as the destination precedes the source
+        for i in 0..(len + size_of::<u32>() - 1) / size_of::<u32>() {
+            unsafe {
+                let word = core::ptr::read_volatile(sram.add(start + i));
+                core::ptr::write_volatile(sram.add(i), word);
+            }
+        }
+        self.set_dlen(len as u32)
+    }
+
     /// Retrieve a slice with the contents of the mailbox
     pub fn raw_mailbox_contents(&self) -> &[u8] {
         unsafe {
@@ -0,0 +1,175 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    mailbox_auth.rs
+
+Abstract:
+
+    File contains the PAIR_MAILBOX mailbox command and the authentication of
+    privileged commands once the mailbox is paired.
+
+--*/
+
+use crate::{CmdAccess, CmdPolicy, Drivers};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert_eq_12_words, cfi_launder};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxAuthTrailer, MailboxReqHeader, MailboxResp, PairMailboxReq, PairMailboxResp,
+};
+use caliptra_drivers::{
+    Array4x12, Array4x8, CaliptraError, CaliptraResult, Ecc256PubKey, Ecc256Sw, Hmac384Data,
+    Hmac384Key, Hmac384Tag,
+};
+use core::mem::size_of;
+use zerocopy::FromBytes;
+use zeroize::Zeroize;
+
+/// Key established by PAIR_MAILBOX, held until the next reset
+#[derive(Zeroize)]
+pub struct MailboxAuthKey {
+    key: Array4x12,
+
+    /// Sequence number expected in the next authenticated command
+    seq: u32,
+}
+
+impl Drop for MailboxAuthKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Whether `cmd` must carry a `MailboxAuthTrailer` once the mailbox is
+/// paired: DISABLE_ATTESTATION and every command restricted to the PL0 or
+/// owner command PAUSER, including PAIR_MAILBOX itself
+///
+/// # Arguments
+///
+/// * `cmd` - Mailbox command
+pub(crate) fn requires_auth(cmd: CommandId) -> bool {
+    cmd == CommandId::DISABLE_ATTESTATION || CmdPolicy::access(cmd) != CmdAccess::Any
+}
+
+/// Check the trailer of a privileged command against the paired key and
+/// strip it from the request. Requests are returned unchanged while the
+/// mailbox is not paired.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `cmd` - Mailbox command
+/// * `cmd_bytes` - Request, including the trailer
+///
+/// # Returns
+///
+/// * `&[u8]` - Request without the trailer
+#[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+pub(crate) fn authenticate<'a>(
+    drivers: &mut Drivers,
+    cmd: CommandId,
+    cmd_bytes: &'a [u8],
+) -> CaliptraResult<&'a [u8]> {
+    let Some(auth_key) = &drivers.mbox_auth_key else {
+        return Ok(cmd_bytes);
+    };
+    if !requires_auth(cmd) {
+        return Ok(cmd_bytes);
+    }
+
+    let (req, trailer) = MailboxAuthTrailer::read_from_suffix(cmd_bytes)
+        .map_err(|_| CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED)?;
+    // The checksum covers the trailer, so the tag cannot cover the checksum
+    let body = req
+        .get(size_of::<MailboxReqHeader>()..)
+        .ok_or(CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED)?;
+    if trailer.seq != auth_key.seq {
+        return Err(CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED);
+    }
+    let mut key = auth_key.key;
+
+    let mut digest = Array4x12::default();
+    let mut hasher = drivers.sha384.digest_init()?;
+    hasher.update(&cmd.0.to_le_bytes())?;
+    hasher.update(&trailer.seq.to_le_bytes())?;
+    hasher.update(body)?;
+    hasher.finalize(&mut digest)?;
+
+    let mut tag = Array4x12::default();
+    let result = drivers.hmac384.hmac(
+        &Hmac384Key::Array4x12(&key),
+        &Hmac384Data::Slice(&<[u8; 48]>::from(digest)),
+        &mut drivers.trng,
+        Hmac384Tag::Array4x12(&mut tag),
+    );
+    key.zeroize();
+    result?;
+
+    let tag: [u32; 12] = tag.into();
+    let expected: [u32; 12] = Array4x12::from(trailer.tag).into();
+    if cfi_launder(tag) != expected {
+        return Err(CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED);
+    } else {
+        cfi_assert_eq_12_words(&tag, &expected);
+    }
+
+    // Consume the sequence number before the command runs, so that it can
+    // never be replayed, even if it fails
+    if let Some(auth_key) = &mut drivers.mbox_auth_key {
+        auth_key.seq = auth_key.seq.wrapping_add(1);
+    }
+    Ok(req)
+}
+
+pub struct PairMailboxCmd;
+impl PairMailboxCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = PairMailboxReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let soc_pub_key = Ecc256PubKey {
+            x: Array4x8::from(cmd.pub_key_x),
+            y: Array4x8::from(cmd.pub_key_y),
+        };
+
+        let mut seed = drivers.trng.generate()?;
+        let key_pair = Ecc256Sw::key_pair(&seed);
+        seed.zeroize();
+        let (priv_key, pub_key) = key_pair?;
+        let mut shared_secret = Ecc256Sw::ecdh(&priv_key, &soc_pub_key)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_PAIRING_FAILED)?;
+
+        let resp = PairMailboxResp {
+            pub_key_x: pub_key.x.into(),
+            pub_key_y: pub_key.y.into(),
+            ..Default::default()
+        };
+
+        // SP 800-56C one-step key derivation with SHA-384. Done in a closure
+        // to ensure the shared secret is always zeroized.
+        let mut key = Array4x12::default();
+        let mut kdf = || -> CaliptraResult<()> {
+            let mut hasher = drivers.sha384.digest_init()?;
+            hasher.update(&1u32.to_be_bytes())?;
+            hasher.update(&<[u8; 32]>::from(shared_secret))?;
+            hasher.update(PairMailboxReq::KDF_LABEL)?;
+            hasher.update(&cmd.pub_key_x)?;
+            hasher.update(&cmd.pub_key_y)?;
+            hasher.update(&resp.pub_key_x)?;
+            hasher.update(&resp.pub_key_y)?;
+            hasher.finalize(&mut key)
+        };
+        let result = kdf();
+        shared_secret.zeroize();
+        result?;
+
+        // Pairing again replaces the key and restarts the sequence numbers
+        drivers.mbox_auth_key = Some(MailboxAuthKey { key, seq: 0 });
+        key.zeroize();
+
+        Ok(MailboxResp::PairMailbox(resp))
+    }
+}
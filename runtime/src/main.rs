@@ -18,10 +18,14 @@ Abstract:
 core::arch::global_asm!(include_str!("ext_intr.S"));
 
 use caliptra_cfi_lib_git::CfiCounter;
-use caliptra_common::{cprintln, handle_fatal_error};
+use caliptra_common::boot_profile::boot_checkpoint;
+use caliptra_common::{handle_fatal_error, handle_fatal_trap, PanicSite};
+use caliptra_common::{log_error, log_info};
 use caliptra_cpu::{log_trap_record, TrapRecord};
+use caliptra_drivers::BootCheckpoint;
 use caliptra_error::CaliptraError;
 use caliptra_registers::soc_ifc::SocIfcReg;
+use caliptra_runtime::nmi::{decode_nmi, reset_mailbox, NmiPolicy};
 use caliptra_runtime::Drivers;
 use core::hint::black_box;
 
@@ -40,7 +44,8 @@ const BANNER: &str = r#"
 #[no_mangle]
 #[allow(clippy::empty_loop)]
 pub extern "C" fn entry_point() -> ! {
-    cprintln!("{}", BANNER);
+    boot_checkpoint(BootCheckpoint::RtEntry);
+    log_info!("{}", BANNER);
 
     #[cfg(target_arch = "riscv32")]
     unsafe {
@@ -57,13 +62,13 @@ pub extern "C" fn entry_point() -> ! {
 
     let mut drivers = unsafe {
         Drivers::new_from_registers().unwrap_or_else(|e| {
-            cprintln!("[rt] Runtime can't load drivers");
+            log_error!("[rt] Runtime can't load drivers");
             handle_fatal_error(e.into());
         })
     };
 
     if !cfg!(feature = "no-cfi") {
-        cprintln!("[state] CFI Enabled");
+        log_info!("[state] CFI Enabled");
         let mut entropy_gen = || {
             drivers
                 .trng
@@ -75,19 +80,19 @@ pub extern "C" fn entry_point() -> ! {
         CfiCounter::reset(&mut entropy_gen);
         CfiCounter::reset(&mut entropy_gen);
     } else {
-        cprintln!("[state] CFI Disabled");
+        log_info!("[state] CFI Disabled");
     }
 
     drivers.run_reset_flow().unwrap_or_else(|e| {
-        cprintln!("[rt] Runtime failed reset flow");
+        log_error!("[rt] Runtime failed reset flow");
         handle_fatal_error(e.into());
     });
 
     if !drivers.persistent_data.get().fht.is_valid() {
-        cprintln!("[rt] Runtime can't load FHT");
+        log_error!("[rt] Runtime can't load FHT");
         handle_fatal_error(caliptra_drivers::CaliptraError::RUNTIME_HANDOFF_FHT_NOT_LOADED.into());
     }
-    cprintln!("[rt] Runtime listening for mailbox commands...");
+    log_info!("[rt] Runtime listening for mailbox commands...");
     if let Err(e) = caliptra_runtime::handle_mailbox_commands(&mut drivers) {
         handle_fatal_error(e.into());
     }
@@ -98,7 +103,7 @@ pub extern "C" fn entry_point() -> ! {
 #[inline(never)]
 #[allow(clippy::empty_loop)]
 extern "C" fn exception_handler(trap_record: &TrapRecord) {
-    cprintln!(
+    log_error!(
         "RT EXCEPTION mcause=0x{:08X} mscause=0x{:08X} mepc=0x{:08X} ra=0x{:08X}",
         trap_record.mcause,
         trap_record.mscause,
@@ -108,7 +113,10 @@ extern "C" fn exception_handler(trap_record: &TrapRecord) {
     log_trap_record(trap_record, None);
 
     // Signal non-fatal error to SOC
-    handle_fatal_error(caliptra_drivers::CaliptraError::RUNTIME_GLOBAL_EXCEPTION.into());
+    handle_fatal_trap(
+        caliptra_drivers::CaliptraError::RUNTIME_GLOBAL_EXCEPTION.into(),
+        trap_record,
+    );
 }
 
 #[no_mangle]
@@ -127,7 +135,7 @@ extern "C" fn nmi_handler(trap_record: &TrapRecord) {
             .read(),
     );
     log_trap_record(trap_record, Some(err_interrupt_status));
-    cprintln!(
+    log_error!(
         "RT NMI mcause=0x{:08X} mscause=0x{:08X} mepc=0x{:08X} ra=0x{:08X} error_internal_intr_r={:08X}",
         trap_record.mcause,
         trap_record.mscause,
@@ -137,23 +145,36 @@ extern "C" fn nmi_handler(trap_record: &TrapRecord) {
     );
 
     let wdt_status = soc_ifc.regs().cptra_wdt_status().read();
-    let error = if wdt_status.t1_timeout() || wdt_status.t2_timeout() {
-        cprintln!("[rt] WDT Expired");
-        CaliptraError::RUNTIME_GLOBAL_WDT_EXPIRED
+    let (error, policy) = if wdt_status.t1_timeout() || wdt_status.t2_timeout() {
+        log_error!("[rt] WDT Expired");
+        (CaliptraError::RUNTIME_GLOBAL_WDT_EXPIRED, NmiPolicy::Fatal)
     } else {
-        CaliptraError::RUNTIME_GLOBAL_NMI
+        decode_nmi(err_interrupt_status)
     };
 
-    handle_fatal_error(error.into());
+    if policy == NmiPolicy::ResetMailbox {
+        log_error!("[rt] Recovering from NMI 0x{:08X}", u32::from(error));
+        reset_mailbox(error, err_interrupt_status);
+        return;
+    }
+
+    handle_fatal_trap(error.into(), trap_record);
 }
 
 #[panic_handler]
 #[inline(never)]
 #[cfg(not(feature = "std"))]
 #[allow(clippy::empty_loop)]
-fn runtime_panic(_: &core::panic::PanicInfo) -> ! {
-    cprintln!("RT Panic!!");
+fn runtime_panic(info: &core::panic::PanicInfo) -> ! {
+    let site = PanicSite::from_panic_info(info);
+    log_error!(
+        "RT Panic!! file_hash=0x{:08X} line={} column={}",
+        site.file_hash,
+        site.line,
+        site.column
+    );
     panic_is_possible();
+    site.log();
 
     // TODO: Signal non-fatal error to SOC
     handle_fatal_error(caliptra_drivers::CaliptraError::RUNTIME_GLOBAL_PANIC.into());
@@ -161,7 +182,7 @@ fn runtime_panic(_: &core::panic::PanicInfo) -> ! {
 
 #[no_mangle]
 extern "C" fn cfi_panic_handler(code: u32) -> ! {
-    cprintln!("RT CFI Panic code=0x{:08X}", code);
+    log_error!("RT CFI Panic code=0x{:08X}", code);
 
     handle_fatal_error(code);
 }
@@ -0,0 +1,198 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    mctp.rs
+
+Abstract:
+
+    File contains the MCTP mailbox command, which unwraps MCTP messages
+    carried in the mailbox and routes them to the handler of their message
+    type.
+
+--*/
+
+use crate::{pldm, spdm, Drivers};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{MailboxResp, MctpReq, MctpResp};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use zerocopy::IntoBytes;
+
+const MCTP_HDR_VERSION: u8 = 0x01;
+const MCTP_HDR_SIZE: usize = 4;
+
+// Flags of the last transport header byte
+const MCTP_SOM: u8 = 1 << 7;
+const MCTP_EOM: u8 = 1 << 6;
+const MCTP_TO: u8 = 1 << 3;
+const MCTP_TAG_MASK: u8 = 0x7;
+
+/// Integrity check bit of the message type byte
+const MCTP_MSG_IC: u8 = 1 << 7;
+
+const MCTP_MSG_TYPE_CONTROL: u8 = 0x00;
+const MCTP_MSG_TYPE_PLDM: u8 = 0x01;
+const MCTP_MSG_TYPE_SPDM: u8 = 0x05;
+
+// Control message fields
+const CONTROL_RQ: u8 = 1 << 7;
+const CONTROL_INSTANCE_MASK: u8 = 0x1F;
+const CONTROL_GET_VERSION_SUPPORT: u8 = 0x04;
+const CONTROL_GET_MSG_TYPE_SUPPORT: u8 = 0x05;
+const CONTROL_ERROR_INVALID_LENGTH: u8 = 0x03;
+const CONTROL_ERROR_UNSUPPORTED_CMD: u8 = 0x05;
+const CONTROL_MSG_TYPE_NOT_SUPPORTED: u8 = 0x80;
+
+/// Message type of Get MCTP Version Support that selects the base
+/// specification
+const CONTROL_VERSION_BASE_SPEC: u8 = 0xFF;
+
+/// MCTP 1.3.1, as reported by Get MCTP Version Support
+const MCTP_VERSION: [u8; 4] = [0xF1, 0xF3, 0xF1, 0x00];
+
+struct MctpHandler {
+    msg_type: u8,
+
+    /// Versions reported by Get MCTP Version Support
+    versions: &'static [[u8; 4]],
+
+    /// Handles the message body following the message type, writing the
+    /// body of the response to the output buffer and returning its length
+    handle: fn(&mut Drivers, &[u8], &mut [u8]) -> CaliptraResult<usize>,
+}
+
+const HANDLERS: [MctpHandler; 3] = [
+    MctpHandler {
+        msg_type: MCTP_MSG_TYPE_CONTROL,
+        versions: &[MCTP_VERSION],
+        handle: handle_control,
+    },
+    MctpHandler {
+        msg_type: MCTP_MSG_TYPE_PLDM,
+        versions: &[pldm::PLDM_BASE_VERSION],
+        handle: pldm::handle_message,
+    },
+    MctpHandler {
+        msg_type: MCTP_MSG_TYPE_SPDM,
+        versions: &[[0xF1, 0xF2, 0xF0, 0x00], [0xF1, 0xF3, 0xF0, 0x00]],
+        handle: spdm::handle_message,
+    },
+];
+
+pub struct MctpCmd;
+impl MctpCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        if cmd_args.len() > core::mem::size_of::<MctpReq>() {
+            return Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY);
+        }
+        let mut cmd = MctpReq::default();
+        cmd.as_mut_bytes()[..cmd_args.len()].copy_from_slice(cmd_args);
+        let packet = cmd
+            .data
+            .get(..cmd.data_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        // Messages are not fragmented, so every packet is a whole request
+        let (hdr_version, dest, src, flags, msg_type, body) = match packet {
+            [hdr_version, dest, src, flags, msg_type, body @ ..] => {
+                (*hdr_version, *dest, *src, *flags, *msg_type, body)
+            }
+            _ => return Err(CaliptraError::RUNTIME_MCTP_INVALID_PACKET),
+        };
+        if hdr_version & 0xF != MCTP_HDR_VERSION
+            || flags & (MCTP_SOM | MCTP_EOM | MCTP_TO) != MCTP_SOM | MCTP_EOM | MCTP_TO
+            || msg_type & MCTP_MSG_IC != 0
+        {
+            return Err(CaliptraError::RUNTIME_MCTP_INVALID_PACKET);
+        }
+        let handler = HANDLERS
+            .iter()
+            .find(|handler| msg_type == handler.msg_type)
+            .ok_or(CaliptraError::RUNTIME_MCTP_UNSUPPORTED_MSG_TYPE)?;
+
+        let mut resp = MctpResp::default();
+        let (resp_hdr, resp_body) = resp.data.split_at_mut(MCTP_HDR_SIZE + 1);
+        let len = (handler.handle)(drivers, body, resp_body)?;
+        resp_hdr.copy_from_slice(&[
+            MCTP_HDR_VERSION,
+            src,
+            dest,
+            MCTP_SOM | MCTP_EOM | (flags & MCTP_TAG_MASK),
+            msg_type,
+        ]);
+        resp.data_size = (MCTP_HDR_SIZE + 1 + len) as u32;
+
+        Ok(MailboxResp::Mctp(resp))
+    }
+}
+
+/// Handles the MCTP control messages that describe the supported message
+/// types
+fn handle_control(_drivers: &mut Drivers, req: &[u8], out: &mut [u8]) -> CaliptraResult<usize> {
+    let (rq, cmd, data) = match req {
+        [rq, cmd, data @ ..] => (*rq, *cmd, data),
+        _ => return Err(CaliptraError::RUNTIME_MCTP_INVALID_PACKET),
+    };
+    // Responses and datagrams are not answered
+    if rq & !CONTROL_INSTANCE_MASK != CONTROL_RQ {
+        return Err(CaliptraError::RUNTIME_MCTP_INVALID_PACKET);
+    }
+
+    // Completion code 0 is success
+    let mut resp = [0u8; 3 + 4 * HANDLERS.len()];
+    resp[0] = rq & CONTROL_INSTANCE_MASK;
+    resp[1] = cmd;
+    let len = match (cmd, data) {
+        (CONTROL_GET_VERSION_SUPPORT, [msg_type]) => {
+            let versions = match *msg_type {
+                CONTROL_VERSION_BASE_SPEC => Some(&[MCTP_VERSION][..]),
+                msg_type => HANDLERS
+                    .iter()
+                    .find(|handler| msg_type == handler.msg_type)
+                    .map(|handler| handler.versions),
+            };
+            match versions {
+                Some(versions) => {
+                    resp[3] = versions.len() as u8;
+                    for (entry, version) in resp[4..].chunks_exact_mut(4).zip(versions) {
+                        entry.copy_from_slice(version);
+                    }
+                    4 + 4 * versions.len()
+                }
+                None => {
+                    resp[2] = CONTROL_MSG_TYPE_NOT_SUPPORTED;
+                    3
+                }
+            }
+        }
+        (CONTROL_GET_MSG_TYPE_SUPPORT, []) => {
+            // The control message type is implied
+            let msg_types = HANDLERS
+                .iter()
+                .filter(|handler| handler.msg_type != MCTP_MSG_TYPE_CONTROL);
+            let mut count = 0;
+            for (entry, handler) in resp[4..].iter_mut().zip(msg_types) {
+                *entry = handler.msg_type;
+                count += 1;
+            }
+            resp[3] = count as u8;
+            4 + count
+        }
+        (CONTROL_GET_VERSION_SUPPORT | CONTROL_GET_MSG_TYPE_SUPPORT, _) => {
+            resp[2] = CONTROL_ERROR_INVALID_LENGTH;
+            3
+        }
+        _ => {
+            resp[2] = CONTROL_ERROR_UNSUPPORTED_CMD;
+            3
+        }
+    };
+    out.get_mut(..len)
+        .ok_or(CaliptraError::RUNTIME_MCTP_INVALID_PACKET)?
+        .copy_from_slice(&resp[..len]);
+    Ok(len)
+}
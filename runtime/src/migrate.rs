@@ -0,0 +1,92 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    migrate.rs
+
+Abstract:
+
+    File contains the checks run on persistent data left behind by the
+    previous Runtime Firmware.
+
+--*/
+
+use caliptra_common::log_warn;
+use caliptra_drivers::{
+    CertDistPoints, PersistentData, PersistentDataSection, ResetReason, PERSISTENT_DATA_VERSION,
+};
+use dpe::U8Bool;
+use zeroize::Zeroize;
+
+/// Bring persistent data up to `PERSISTENT_DATA_VERSION`
+///
+/// Sections that cannot be interpreted by this firmware are discarded rather
+/// than read with the wrong layout. A discarded DPE section disables
+/// attestation.
+///
+/// # Arguments
+///
+/// * `persistent_data` - Persistent data
+/// * `reset_reason` - Reason of the current reset
+pub(crate) fn migrate_persistent_data(
+    persistent_data: &mut PersistentData,
+    reset_reason: ResetReason,
+) {
+    if reset_reason != ResetReason::ColdReset {
+        let stale = stale_sections(persistent_data);
+        for (section, stale) in PersistentDataSection::ALL.iter().zip(stale) {
+            if stale {
+                log_warn!(
+                    "Discarding stale persistent data section {}",
+                    *section as usize
+                );
+                discard(persistent_data, *section);
+            }
+        }
+    }
+    persistent_data.header.reset();
+}
+
+/// Returns which sections were not left in a layout this firmware
+/// understands
+///
+/// Add an arm here when bumping `PERSISTENT_DATA_VERSION`. It must check the
+/// recorded checksums against the previous layout and convert the sections
+/// it can.
+fn stale_sections(persistent_data: &PersistentData) -> [bool; PersistentDataSection::COUNT] {
+    let header = persistent_data.header;
+    match header.version {
+        PERSISTENT_DATA_VERSION => {
+            let mut stale = [false; PersistentDataSection::COUNT];
+            if header.is_sealed() {
+                for (section, stale) in PersistentDataSection::ALL.iter().zip(stale.iter_mut()) {
+                    *stale =
+                        persistent_data.section_checksum(*section) != header.checksum(*section);
+                }
+            }
+            stale
+        }
+        // Written by firmware that predates versioning. Its layout matches
+        // version 1, but it never recorded checksums.
+        0 => [false; PersistentDataSection::COUNT],
+        // Written by newer firmware
+        _ => [true; PersistentDataSection::COUNT],
+    }
+}
+
+fn discard(persistent_data: &mut PersistentData, section: PersistentDataSection) {
+    match section {
+        PersistentDataSection::Dpe => persistent_data.attestation_disabled = U8Bool::new(true),
+        PersistentDataSection::PcrReset => persistent_data.pcr_reset.zeroize(),
+        PersistentDataSection::AuthManifestImageMetadata => {
+            persistent_data.auth_manifest_image_metadata_col.zeroize()
+        }
+        PersistentDataSection::FwUpdateJournal => persistent_data.fw_update_journal.zeroize(),
+        PersistentDataSection::IdevIdCertChain => persistent_data.idevid_cert_chain.zeroize(),
+        PersistentDataSection::CertDistPoints => {
+            persistent_data.cert_dist_points = CertDistPoints::default()
+        }
+    }
+}
@@ -0,0 +1,130 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    monotonic_counter.rs
+
+Abstract:
+
+    File contains the CREATE_COUNTER, INCREMENT_COUNTER and READ_COUNTER
+    mailbox commands, which expose monotonic counters kept in the data
+    vault.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{
+    CreateCounterResp, IncrementCounterReq, IncrementCounterResp, MailboxResp, MailboxRespHeader,
+    ReadCounterReq, ReadCounterResp,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult, ColdResetEntry4, MONOTONIC_COUNTER_ENTRIES};
+use zerocopy::FromBytes;
+
+const _: () = assert!(MONOTONIC_COUNTER_ENTRIES.len() == CreateCounterResp::MAX_COUNTERS);
+
+/// Value of the data vault entry of a counter that was not created. The
+/// entry of a created counter holds the counter value plus one.
+const COUNTER_FREE: u32 = 0;
+
+/// Read the counter `counter_id`
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `counter_id` - Counter ID
+///
+/// # Returns
+///
+/// * `(ColdResetEntry4, u32)` - Data vault entry and value of the counter
+fn read_counter(drivers: &Drivers, counter_id: u32) -> CaliptraResult<(ColdResetEntry4, u32)> {
+    let entry = *MONOTONIC_COUNTER_ENTRIES
+        .get(counter_id as usize)
+        .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+    match drivers.data_vault.read_cold_reset_entry4(entry) {
+        COUNTER_FREE => Err(CaliptraError::RUNTIME_COUNTER_NOT_CREATED),
+        raw => Ok((entry, raw - 1)),
+    }
+}
+
+/// Increment the counter `counter_id`
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `counter_id` - Counter ID
+///
+/// # Returns
+///
+/// * `u32` - Value of the counter after the increment
+pub(crate) fn increment_counter(drivers: &mut Drivers, counter_id: u32) -> CaliptraResult<u32> {
+    let (entry, value) = read_counter(drivers, counter_id)?;
+
+    // Counters never wrap. An exhausted counter is locked, so that no
+    // firmware can roll it back before the next cold reset.
+    if value >= CreateCounterResp::MAX_VALUE {
+        return Err(CaliptraError::RUNTIME_COUNTER_EXHAUSTED);
+    }
+    let value = value + 1;
+    drivers.data_vault.write_cold_reset_entry4(entry, value + 1);
+    if value == CreateCounterResp::MAX_VALUE {
+        drivers.data_vault.lock_cold_reset_entry4(entry);
+    }
+    Ok(value)
+}
+
+pub struct CreateCounterCmd;
+impl CreateCounterCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let Some((counter_id, entry)) = MONOTONIC_COUNTER_ENTRIES
+            .iter()
+            .enumerate()
+            .find(|(_, entry)| drivers.data_vault.read_cold_reset_entry4(**entry) == COUNTER_FREE)
+        else {
+            return Err(CaliptraError::RUNTIME_COUNTER_UNAVAILABLE);
+        };
+        drivers.data_vault.write_cold_reset_entry4(*entry, 1);
+
+        Ok(MailboxResp::CreateCounter(CreateCounterResp {
+            hdr: MailboxRespHeader::default(),
+            counter_id: counter_id as u32,
+            value: 0,
+        }))
+    }
+}
+
+pub struct IncrementCounterCmd;
+impl IncrementCounterCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = IncrementCounterReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let value = increment_counter(drivers, cmd.counter_id)?;
+
+        Ok(MailboxResp::IncrementCounter(IncrementCounterResp {
+            hdr: MailboxRespHeader::default(),
+            value,
+        }))
+    }
+}
+
+pub struct ReadCounterCmd;
+impl ReadCounterCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = ReadCounterReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let (_, value) = read_counter(drivers, cmd.counter_id)?;
+
+        Ok(MailboxResp::ReadCounter(ReadCounterResp {
+            hdr: MailboxRespHeader::default(),
+            value,
+        }))
+    }
+}
@@ -0,0 +1,127 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    nmi.rs
+
+Abstract:
+
+    File contains the decoding of NMI causes and the recovery from the
+    causes that do not require a reset.
+
+--*/
+
+use caliptra_drivers::{report_fw_error_non_fatal, CaliptraError};
+use caliptra_registers::mbox::{enums::MboxFsmE, MboxCsr};
+use caliptra_registers::soc_ifc::SocIfcReg;
+
+/// What the NMI handler does once the cause has been decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NmiPolicy {
+    /// Report a fatal error and halt
+    Fatal,
+
+    /// Report a non-fatal error, return the mailbox to idle and resume
+    ResetMailbox,
+}
+
+struct NmiCause {
+    /// Bit in error_internal_intr_r
+    mask: u32,
+    error: CaliptraError,
+    policy: NmiPolicy,
+}
+
+/// error_internal_intr_r bits, most severe first
+const NMI_CAUSES: [NmiCause; 8] = [
+    NmiCause {
+        mask: 1 << 7,
+        error: CaliptraError::RUNTIME_GLOBAL_WDT_EXPIRED,
+        policy: NmiPolicy::Fatal,
+    },
+    NmiCause {
+        mask: 1 << 6,
+        error: CaliptraError::RUNTIME_GLOBAL_WDT_EXPIRED,
+        policy: NmiPolicy::Fatal,
+    },
+    NmiCause {
+        mask: 1 << 0,
+        error: CaliptraError::RUNTIME_NMI_INTERNAL_ERROR,
+        policy: NmiPolicy::Fatal,
+    },
+    NmiCause {
+        mask: 1 << 4,
+        error: CaliptraError::RUNTIME_NMI_ICCM_BLOCKED,
+        policy: NmiPolicy::Fatal,
+    },
+    NmiCause {
+        mask: 1 << 3,
+        error: CaliptraError::RUNTIME_NMI_BAD_FUSE,
+        policy: NmiPolicy::Fatal,
+    },
+    // The contents of the mailbox are lost, but nothing the firmware holds
+    // is affected.
+    NmiCause {
+        mask: 1 << 5,
+        error: CaliptraError::RUNTIME_NMI_MBOX_ECC_UNC,
+        policy: NmiPolicy::ResetMailbox,
+    },
+    // The SoC broke the mailbox protocol.
+    NmiCause {
+        mask: 1 << 2,
+        error: CaliptraError::RUNTIME_NMI_MBOX_PROTOCOL_ERROR,
+        policy: NmiPolicy::ResetMailbox,
+    },
+    NmiCause {
+        mask: 1 << 1,
+        error: CaliptraError::RUNTIME_NMI_INVALID_DEVICE,
+        policy: NmiPolicy::ResetMailbox,
+    },
+];
+
+/// Decode the cause of an NMI
+///
+/// # Arguments
+///
+/// * `err_interrupt_status` - Value of error_internal_intr_r
+///
+/// # Returns
+///
+/// * `(CaliptraError, NmiPolicy)` - Error for the most severe cause and how
+///   to handle it. An NMI with no cause is fatal.
+pub fn decode_nmi(err_interrupt_status: u32) -> (CaliptraError, NmiPolicy) {
+    NMI_CAUSES
+        .iter()
+        .find(|cause| err_interrupt_status & cause.mask != 0)
+        .map(|cause| (cause.error, cause.policy))
+        .unwrap_or((CaliptraError::RUNTIME_GLOBAL_NMI, NmiPolicy::Fatal))
+}
+
+/// Recover from an NMI whose policy is `NmiPolicy::ResetMailbox`
+///
+/// Clears the causes, forces the mailbox back to idle and reports `error`
+/// as non-fatal. A command interrupted by the NMI still runs to completion,
+/// but the SoC no longer waits for its response.
+///
+/// # Arguments
+///
+/// * `error` - Error returned by `decode_nmi`
+/// * `err_interrupt_status` - Value of error_internal_intr_r
+pub fn reset_mailbox(error: CaliptraError, err_interrupt_status: u32) {
+    let mut soc_ifc = unsafe { SocIfcReg::new() };
+    soc_ifc
+        .regs_mut()
+        .intr_block_rf()
+        .error_internal_intr_r()
+        .write(|_| err_interrupt_status.into());
+
+    let mut mbox = unsafe { MboxCsr::new() };
+    let mbox = mbox.regs_mut();
+    if mbox.status().read().mbox_fsm_ps() != MboxFsmE::MboxIdle {
+        mbox.unlock().write(|w| w.unlock(true));
+    }
+
+    report_fw_error_non_fatal(error.into());
+}
@@ -12,11 +12,11 @@ Abstract:
 
 --*/
 
-use crate::Drivers;
+use crate::{Cwt, Drivers};
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_common::mailbox_api::{
-    ExtendPcrReq, IncrementPcrResetCounterReq, MailboxResp, MailboxRespHeader, QuotePcrsReq,
-    QuotePcrsResp,
+    ExtendPcrReq, IncrementPcrResetCounterReq, MailboxResp, MailboxRespHeader, QuotePcrsCwtReq,
+    QuotePcrsCwtResp, QuotePcrsReq, QuotePcrsResp,
 };
 use caliptra_drivers::{hand_off::DataStore, CaliptraError, CaliptraResult, PcrBank, PcrId};
 use zerocopy::FromBytes;
@@ -47,7 +47,15 @@ pub struct GetPcrQuoteCmd;
 impl GetPcrQuoteCmd {
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     #[inline(never)]
-    pub(crate) fn execute(drivers: &mut Drivers, cmd_bytes: &[u8]) -> CaliptraResult<MailboxResp> {
+    pub(crate) fn execute(
+        drivers: &mut Drivers,
+        cmd_bytes: &[u8],
+        version: u8,
+    ) -> CaliptraResult<MailboxResp> {
+        if version >= QuotePcrsCwtReq::VERSION {
+            return Self::execute_cwt(drivers, cmd_bytes);
+        }
+
         let args: &QuotePcrsReq = QuotePcrsReq::ref_from_bytes(cmd_bytes)
             .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
 
@@ -70,6 +78,41 @@ impl GetPcrQuoteCmd {
             signature_s: signature.s.into(),
         }))
     }
+
+    /// Quote the PCRs as a CWT signed by the RT Alias key, with the nonce,
+    /// UEID, PCR values and PCR reset counters as claims.
+    fn execute_cwt(drivers: &mut Drivers, cmd_bytes: &[u8]) -> CaliptraResult<MailboxResp> {
+        let args = QuotePcrsCwtReq::ref_from_bytes(cmd_bytes)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        let ueid = drivers.soc_ifc.fuse_bank().ueid();
+        let pcrs = drivers.pcr_bank.read_all_pcrs();
+        let reset_ctrs = drivers.persistent_data.get().pcr_reset.all_counters();
+
+        let mut resp = QuotePcrsCwtResp::default();
+        let token_size = Cwt::sign(drivers, &mut resp.data, |claims| {
+            claims.write_map(4)?;
+            claims.write_int(QuotePcrsCwtResp::CLAIM_NONCE)?;
+            claims.write_bstr(&args.nonce)?;
+            claims.write_int(QuotePcrsCwtResp::CLAIM_UEID)?;
+            claims.write_bstr(&ueid)?;
+            claims.write_int(QuotePcrsCwtResp::CLAIM_PCRS)?;
+            claims.write_array(pcrs.len())?;
+            for pcr in pcrs.iter() {
+                let pcr: [u8; 48] = pcr.into();
+                claims.write_bstr(&pcr)?;
+            }
+            claims.write_int(QuotePcrsCwtResp::CLAIM_PCR_RESET_COUNTERS)?;
+            claims.write_array(reset_ctrs.len())?;
+            for ctr in reset_ctrs.iter() {
+                claims.write_uint(*ctr as u64)?;
+            }
+            Ok(())
+        })?;
+        resp.data_size = token_size as u32;
+
+        Ok(MailboxResp::QuotePcrsCwt(resp))
+    }
 }
 
 pub struct ExtendPcrCmd;
@@ -0,0 +1,127 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    pldm.rs
+
+Abstract:
+
+    File contains the PLDM responder reached through the MCTP mailbox
+    command. Only the discovery commands of the PLDM base type, and the
+    firmware update type, are supported.
+
+--*/
+
+use crate::pldm_fw_update::{
+    self, ACTIVATE_FIRMWARE, CANCEL_UPDATE, PASS_COMPONENT_TABLE, REQUEST_FIRMWARE_DATA,
+    REQUEST_UPDATE, UPDATE_COMPONENT,
+};
+use crate::Drivers;
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+
+/// PLDM base 1.1.0, as reported by Get MCTP Version Support
+pub const PLDM_BASE_VERSION: [u8; 4] = [0xF1, 0xF1, 0xF0, 0x00];
+
+const PLDM_RQ: u8 = 1 << 7;
+const PLDM_DATAGRAM: u8 = 1 << 6;
+const PLDM_INSTANCE_MASK: u8 = 0x1F;
+const PLDM_HDR_VERSION_MASK: u8 = 0xC0;
+const PLDM_TYPE_MASK: u8 = 0x3F;
+
+const PLDM_TYPE_BASE: u8 = 0x00;
+const PLDM_TYPE_FW_UPDATE: u8 = 0x05;
+
+// Base type commands
+const GET_PLDM_TYPES: u8 = 0x04;
+const GET_PLDM_COMMANDS: u8 = 0x05;
+
+// Completion codes
+const PLDM_SUCCESS: u8 = 0x00;
+const PLDM_ERROR_INVALID_LENGTH: u8 = 0x03;
+const PLDM_ERROR_UNSUPPORTED_CMD: u8 = 0x05;
+const PLDM_ERROR_INVALID_PLDM_TYPE: u8 = 0x20;
+const PLDM_INVALID_PLDM_TYPE_IN_REQUEST_DATA: u8 = 0x83;
+
+/// Size of the response header, including the completion code
+const PLDM_RESP_HDR_SIZE: usize = 4;
+
+/// Handles the PLDM request `req`, writing the response to `out`
+///
+/// # Returns
+///
+/// * `usize` - Length of the response, whose completion code reports
+///             unsupported requests
+pub(crate) fn handle_message(
+    drivers: &mut Drivers,
+    req: &[u8],
+    out: &mut [u8],
+) -> CaliptraResult<usize> {
+    let (rq, pldm_type, cmd, data) = match req {
+        [rq, pldm_type, cmd, data @ ..] => (*rq, *pldm_type, *cmd, data),
+        _ => return Err(CaliptraError::RUNTIME_MCTP_INVALID_PACKET),
+    };
+    // Responses and datagrams are not answered
+    if rq & (PLDM_RQ | PLDM_DATAGRAM) != PLDM_RQ {
+        return Err(CaliptraError::RUNTIME_MCTP_INVALID_PACKET);
+    }
+
+    let mut resp = [0u8; PLDM_RESP_HDR_SIZE + 32];
+    resp[0] = rq & PLDM_INSTANCE_MASK;
+    resp[1] = pldm_type;
+    resp[2] = cmd;
+    let resp_data = &mut resp[PLDM_RESP_HDR_SIZE..];
+    let (completion_code, data_len) = if pldm_type & PLDM_HDR_VERSION_MASK != 0 {
+        (PLDM_ERROR_INVALID_PLDM_TYPE, 0)
+    } else {
+        match pldm_type & PLDM_TYPE_MASK {
+            PLDM_TYPE_BASE => handle_base(cmd, data, resp_data),
+            PLDM_TYPE_FW_UPDATE => pldm_fw_update::handle_command(drivers, cmd, data, resp_data)?,
+            _ => (PLDM_ERROR_INVALID_PLDM_TYPE, 0),
+        }
+    };
+    resp[3] = completion_code;
+
+    let len = PLDM_RESP_HDR_SIZE + data_len;
+    out.get_mut(..len)
+        .ok_or(CaliptraError::RUNTIME_MCTP_INVALID_PACKET)?
+        .copy_from_slice(&resp[..len]);
+    Ok(len)
+}
+
+/// Handles the discovery commands of the base type
+///
+/// # Returns
+///
+/// * `(u8, usize)` - Completion code and length of the response data
+fn handle_base(cmd: u8, data: &[u8], out: &mut [u8]) -> (u8, usize) {
+    match (cmd, data) {
+        (GET_PLDM_TYPES, []) => {
+            out[0] = (1 << PLDM_TYPE_BASE) | (1 << PLDM_TYPE_FW_UPDATE);
+            (PLDM_SUCCESS, 8)
+        }
+        // The version of the type is not checked, as each type has a single
+        // version
+        (GET_PLDM_COMMANDS, [PLDM_TYPE_BASE, _, _, _, _]) => {
+            out[0] = (1 << GET_PLDM_TYPES) | (1 << GET_PLDM_COMMANDS);
+            (PLDM_SUCCESS, 32)
+        }
+        (GET_PLDM_COMMANDS, [PLDM_TYPE_FW_UPDATE, _, _, _, _]) => {
+            for cmd in [
+                REQUEST_UPDATE,
+                PASS_COMPONENT_TABLE,
+                UPDATE_COMPONENT,
+                REQUEST_FIRMWARE_DATA,
+                ACTIVATE_FIRMWARE,
+                CANCEL_UPDATE,
+            ] {
+                out[usize::from(cmd / 8)] |= 1 << (cmd % 8);
+            }
+            (PLDM_SUCCESS, 32)
+        }
+        (GET_PLDM_COMMANDS, [_, _, _, _, _]) => (PLDM_INVALID_PLDM_TYPE_IN_REQUEST_DATA, 0),
+        (GET_PLDM_TYPES | GET_PLDM_COMMANDS, _) => (PLDM_ERROR_INVALID_LENGTH, 0),
+        _ => (PLDM_ERROR_UNSUPPORTED_CMD, 0),
+    }
+}
@@ -0,0 +1,224 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    pldm_fw_update.rs
+
+Abstract:
+
+    File contains the PLDM for Firmware Update (type 5) responder, which
+    stages a firmware image in the mailbox and activates it with the
+    impactless update flow.
+
+--*/
+
+use crate::{update, Drivers};
+use caliptra_common::mailbox_api::{CommandId, MctpResp};
+use caliptra_drivers::{memory_layout, CaliptraError, CaliptraResult};
+
+// Commands
+pub const REQUEST_UPDATE: u8 = 0x10;
+pub const PASS_COMPONENT_TABLE: u8 = 0x13;
+pub const UPDATE_COMPONENT: u8 = 0x14;
+pub const REQUEST_FIRMWARE_DATA: u8 = 0x15;
+pub const ACTIVATE_FIRMWARE: u8 = 0x1A;
+pub const CANCEL_UPDATE: u8 = 0x1D;
+
+// Completion codes
+const PLDM_SUCCESS: u8 = 0x00;
+const PLDM_ERROR_INVALID_DATA: u8 = 0x02;
+const PLDM_ERROR_INVALID_LENGTH: u8 = 0x03;
+const PLDM_ERROR_UNSUPPORTED_CMD: u8 = 0x05;
+const NOT_IN_UPDATE_MODE: u8 = 0x80;
+const ALREADY_IN_UPDATE_MODE: u8 = 0x81;
+const DATA_OUT_OF_RANGE: u8 = 0x82;
+const INVALID_TRANSFER_LENGTH: u8 = 0x83;
+const INVALID_STATE_FOR_COMMAND: u8 = 0x84;
+const INCOMPLETE_UPDATE: u8 = 0x85;
+
+// Transfer flags of PassComponentTable
+const TRANSFER_FLAG_END: u8 = 0x04;
+
+/// Offset of the image in the mailbox SRAM. The MCTP commands of the
+/// transfer, and their responses, fit below it.
+const STAGING_OFFSET: usize = 4096;
+
+/// Largest image that can be staged
+const STAGING_MAX_SIZE: usize = memory_layout::MBOX_SIZE as usize - STAGING_OFFSET;
+
+const _: () = assert!(core::mem::size_of::<MctpResp>() <= STAGING_OFFSET);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum FdState {
+    #[default]
+    Idle,
+    LearnComponents,
+    ReadyXfer,
+    Download,
+}
+
+/// State of the firmware device, kept between MCTP mailbox commands
+#[derive(Default)]
+pub struct PldmFwUpdateState {
+    state: FdState,
+
+    /// Size of the component image announced by UpdateComponent
+    image_size: u32,
+
+    /// Number of bytes of the image received so far
+    received: u32,
+}
+
+/// Handles the firmware update command `cmd`, writing the response data
+/// that follows the completion code to `out`
+///
+/// # Returns
+///
+/// * `(u8, usize)` - Completion code and length of the response data
+pub(crate) fn handle_command(
+    drivers: &mut Drivers,
+    cmd: u8,
+    data: &[u8],
+    out: &mut [u8],
+) -> CaliptraResult<(u8, usize)> {
+    let fw_update = &mut drivers.pldm_fw_update;
+    match (cmd, fw_update.state) {
+        (REQUEST_UPDATE, FdState::Idle) => {}
+        (REQUEST_UPDATE, _) => return Ok((ALREADY_IN_UPDATE_MODE, 0)),
+        (
+            PASS_COMPONENT_TABLE
+            | UPDATE_COMPONENT
+            | REQUEST_FIRMWARE_DATA
+            | ACTIVATE_FIRMWARE
+            | CANCEL_UPDATE,
+            FdState::Idle,
+        ) => return Ok((NOT_IN_UPDATE_MODE, 0)),
+        (PASS_COMPONENT_TABLE, FdState::LearnComponents)
+        | (UPDATE_COMPONENT, FdState::ReadyXfer)
+        | (REQUEST_FIRMWARE_DATA | ACTIVATE_FIRMWARE, FdState::Download)
+        | (CANCEL_UPDATE, _) => {}
+        (
+            PASS_COMPONENT_TABLE | UPDATE_COMPONENT | REQUEST_FIRMWARE_DATA | ACTIVATE_FIRMWARE,
+            _,
+        ) => return Ok((INVALID_STATE_FOR_COMMAND, 0)),
+        _ => return Ok((PLDM_ERROR_UNSUPPORTED_CMD, 0)),
+    }
+
+    match cmd {
+        REQUEST_UPDATE => {
+            // The image set version string follows the fixed fields
+            let (Some(num_components), Some(&str_len)) = (read_u16(data, 4), data.get(10)) else {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            };
+            if data.len() != 11 + usize::from(str_len) {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            }
+            // The runtime image is the only component
+            if num_components != 1 {
+                return Ok((PLDM_ERROR_INVALID_DATA, 0));
+            }
+            fw_update.state = FdState::LearnComponents;
+            // No device metadata, and no package data is requested
+            out[..3].fill(0);
+            Ok((PLDM_SUCCESS, 3))
+        }
+        PASS_COMPONENT_TABLE => {
+            let (Some(&transfer_flag), Some(&str_len)) = (data.first(), data.get(11)) else {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            };
+            if data.len() != 12 + usize::from(str_len) {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            }
+            if transfer_flag & TRANSFER_FLAG_END != 0 {
+                fw_update.state = FdState::ReadyXfer;
+            }
+            // The component can be updated
+            out[..2].fill(0);
+            Ok((PLDM_SUCCESS, 2))
+        }
+        UPDATE_COMPONENT => {
+            let (Some(image_size), Some(&str_len)) = (read_u32(data, 9), data.get(18)) else {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            };
+            if data.len() != 19 + usize::from(str_len) {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            }
+            if image_size == 0 || image_size as usize > STAGING_MAX_SIZE {
+                return Ok((PLDM_ERROR_INVALID_DATA, 0));
+            }
+            fw_update.state = FdState::Download;
+            fw_update.image_size = image_size;
+            fw_update.received = 0;
+            // The component is compatible, no update options are enabled
+            // and the data can be sent right away
+            out[..8].fill(0);
+            Ok((PLDM_SUCCESS, 8))
+        }
+        REQUEST_FIRMWARE_DATA => {
+            let (Some(offset), Some(length)) = (read_u32(data, 0), read_u32(data, 4)) else {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            };
+            let chunk = &data[8..];
+            if chunk.len() != length as usize {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            }
+            if length == 0 {
+                return Ok((INVALID_TRANSFER_LENGTH, 0));
+            }
+            // Chunks are word-aligned and may be sent again, but must not
+            // leave gaps
+            let end = offset.saturating_add(length);
+            if offset % 4 != 0 || offset > fw_update.received || end > fw_update.image_size {
+                return Ok((DATA_OUT_OF_RANGE, 0));
+            }
+            fw_update.received = fw_update.received.max(end);
+            drivers
+                .mbox
+                .write_sram(STAGING_OFFSET + offset as usize, chunk)?;
+            Ok((PLDM_SUCCESS, 0))
+        }
+        ACTIVATE_FIRMWARE => {
+            if data.len() != 1 {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            }
+            if fw_update.received != fw_update.image_size {
+                return Ok((INCOMPLETE_UPDATE, 0));
+            }
+            // Moving the image overwrites it, so the update ends here
+            let image_size = fw_update.image_size as usize;
+            *fw_update = PldmFwUpdateState::default();
+            drivers.mbox.move_to_start(STAGING_OFFSET, image_size)?;
+            update::handle_impactless_update(drivers, Some(CommandId::MCTP))?;
+
+            // The update reset does not return, and ROM completes the
+            // command without a response
+            Err(CaliptraError::RUNTIME_UNEXPECTED_UPDATE_RETURN)
+        }
+        CANCEL_UPDATE => {
+            if !data.is_empty() {
+                return Ok((PLDM_ERROR_INVALID_LENGTH, 0));
+            }
+            *fw_update = PldmFwUpdateState::default();
+            // The running image is untouched
+            out[..9].fill(0);
+            Ok((PLDM_SUCCESS, 9))
+        }
+        _ => Ok((PLDM_ERROR_UNSUPPORTED_CMD, 0)),
+    }
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Option<u16> {
+    match buf.get(offset..offset + 2) {
+        Some(&[a, b]) => Some(u16::from_le_bytes([a, b])),
+        _ => None,
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    match buf.get(offset..offset + 4) {
+        Some(&[a, b, c, d]) => Some(u32::from_le_bytes([a, b, c, d])),
+        _ => None,
+    }
+}
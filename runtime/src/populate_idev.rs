@@ -19,6 +19,9 @@ use zerocopy::IntoBytes;
 
 use crate::{Drivers, MAX_CERT_CHAIN_SIZE, PL0_PAUSER_FLAG};
 
+const DER_SEQUENCE: u8 = 0x30;
+const DER_CONTEXT_0: u8 = 0xA0;
+
 pub struct PopulateIDevIdCertCmd;
 impl PopulateIDevIdCertCmd {
     #[inline(never)]
@@ -38,9 +41,24 @@ impl PopulateIDevIdCertCmd {
                 return Err(CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL);
             }
 
+            let cert = &cmd.cert[..cert_size];
+            if cert.is_empty() {
+                return Ok(MailboxResp::default());
+            }
+            Self::validate_order(drivers, cert)?;
+
+            if !drivers
+                .persistent_data
+                .get_mut()
+                .idevid_cert_chain
+                .prepend(cert)
+            {
+                return Err(CaliptraError::RUNTIME_IDEV_CERT_CHAIN_TOO_LONG);
+            }
+
             let mut tmp_chain = ArrayVec::<u8, MAX_CERT_CHAIN_SIZE>::new();
             tmp_chain
-                .try_extend_from_slice(&cmd.cert[..cert_size])
+                .try_extend_from_slice(cert)
                 .map_err(|_| CaliptraError::RUNTIME_IDEV_CERT_POPULATION_FAILED)?;
             tmp_chain
                 .try_extend_from_slice(drivers.cert_chain.as_slice())
@@ -52,4 +70,103 @@ impl PopulateIDevIdCertCmd {
             Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)
         }
     }
+
+    /// Check that `cert` issued the certificate currently at the start of
+    /// the chain: the first populated certificate must issue the LDevID
+    /// certificate, and each following one the previously populated
+    /// certificate.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `cert` - DER-encoded certificate to add to the chain
+    fn validate_order(drivers: &Drivers, cert: &[u8]) -> CaliptraResult<()> {
+        let (_, subject) =
+            cert_names(cert).ok_or(CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_CERT)?;
+
+        let persistent_data = drivers.persistent_data.get();
+        let expected_subject = match persistent_data.idevid_cert_chain.first() {
+            Some(head) => {
+                let (issuer, head_subject) =
+                    cert_names(head).ok_or(CaliptraError::RUNTIME_IDEV_CERT_POPULATION_FAILED)?;
+                // Nothing can be added above a self-issued root
+                if issuer == head_subject {
+                    return Err(CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_ORDER);
+                }
+                issuer
+            }
+            None => {
+                let ldevid_tbs = persistent_data
+                    .ldevid_tbs
+                    .get(..persistent_data.fht.ldevid_tbs_size as usize)
+                    .ok_or(CaliptraError::RUNTIME_IDEV_CERT_POPULATION_FAILED)?;
+                let (issuer, _) = der_next(ldevid_tbs)
+                    .and_then(|tbs| tbs_names(tbs.value))
+                    .ok_or(CaliptraError::RUNTIME_IDEV_CERT_POPULATION_FAILED)?;
+                issuer
+            }
+        };
+
+        if subject != expected_subject {
+            return Err(CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_ORDER);
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct DerTlv<'a> {
+    pub tag: u8,
+    pub tlv: &'a [u8],
+    pub value: &'a [u8],
+    pub rest: &'a [u8],
+}
+
+/// Split the next DER TLV off the start of `buf`
+pub(crate) fn der_next(buf: &[u8]) -> Option<DerTlv<'_>> {
+    let tag = *buf.first()?;
+    let (hdr_len, len) = match *buf.get(1)? {
+        len @ 0..=0x7f => (2, len as usize),
+        0x81 => (3, *buf.get(2)? as usize),
+        0x82 => (4, u16::from_be_bytes([*buf.get(2)?, *buf.get(3)?]) as usize),
+        _ => return None,
+    };
+    let tlv = buf.get(..hdr_len.checked_add(len)?)?;
+    Some(DerTlv {
+        tag,
+        tlv,
+        value: tlv.get(hdr_len..)?,
+        rest: buf.get(tlv.len()..)?,
+    })
+}
+
+/// Get the DER-encoded issuer and subject names from the contents of a
+/// TBSCertificate
+fn tbs_names(tbs: &[u8]) -> Option<(&[u8], &[u8])> {
+    let mut field = der_next(tbs)?;
+    if field.tag == DER_CONTEXT_0 {
+        // Skip the version
+        field = der_next(field.rest)?;
+    }
+    let signature = der_next(field.rest)?;
+    let issuer = der_next(signature.rest)?;
+    let validity = der_next(issuer.rest)?;
+    let subject = der_next(validity.rest)?;
+    if issuer.tag != DER_SEQUENCE || subject.tag != DER_SEQUENCE {
+        return None;
+    }
+    Some((issuer.tlv, subject.tlv))
+}
+
+/// Get the DER-encoded issuer and subject names of a certificate. The
+/// certificate must span the whole of `cert`.
+fn cert_names(cert: &[u8]) -> Option<(&[u8], &[u8])> {
+    let cert = der_next(cert)?;
+    if cert.tag != DER_SEQUENCE || !cert.rest.is_empty() {
+        return None;
+    }
+    let tbs = der_next(cert.value)?;
+    if tbs.tag != DER_SEQUENCE {
+        return None;
+    }
+    tbs_names(tbs.value)
 }
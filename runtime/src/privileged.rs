@@ -0,0 +1,186 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    privileged.rs
+
+Abstract:
+
+    File contains the audited facade for privileged SoC interface operations.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::CommandId;
+use caliptra_common::WdtTimeout;
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+
+/// Number of privileged operations retained in the audit log
+pub const PRIVILEGED_OP_LOG_SIZE: usize = 16;
+
+/// SoC interface operations that can stall or reset the firmware and must
+/// go through [`PrivilegedSocIfc`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrivilegedOp {
+    StartWdt,
+    StopWdt,
+    PetWdt,
+    InjectWdtExpiry,
+    FwUpdateReset,
+    FuseSvnUpdateRequest,
+    FieldEntropyReseedRequest,
+}
+
+impl PrivilegedOp {
+    /// Check whether the operation is allowed in the current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `active_cmd` - Command whose handler is executing, `None` when
+    ///   called from the mailbox loop
+    fn allowed(self, active_cmd: Option<CommandId>) -> bool {
+        match self {
+            // The watchdog is owned by the mailbox loop, which arms it around
+            // each command. Handlers must never stop it.
+            PrivilegedOp::StartWdt | PrivilegedOp::StopWdt => active_cmd.is_none(),
+            // Restarting the watchdog cannot disarm it.
+            PrivilegedOp::PetWdt => true,
+            PrivilegedOp::InjectWdtExpiry => active_cmd == Some(CommandId::INJECT_ERROR),
+            // MCTP activates images staged by PLDM firmware update
+            PrivilegedOp::FwUpdateReset => {
+                matches!(active_cmd, Some(CommandId::FIRMWARE_LOAD | CommandId::MCTP))
+            }
+            PrivilegedOp::FuseSvnUpdateRequest => active_cmd == Some(CommandId::INCREMENT_FUSE_SVN),
+            PrivilegedOp::FieldEntropyReseedRequest => {
+                active_cmd == Some(CommandId::RESEED_FIELD_ENTROPY)
+            }
+        }
+    }
+}
+
+/// Audit log entry for a privileged operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrivilegedOpRecord {
+    pub op: PrivilegedOp,
+
+    /// Mailbox command the operation was performed on behalf of
+    pub cmd_id: u32,
+
+    /// PAUSER of the mailbox command
+    pub pauser: u32,
+
+    /// Whether the operation was performed or refused
+    pub allowed: bool,
+}
+
+/// Ring buffer of the most recent privileged operations.
+#[derive(Default)]
+pub struct PrivilegedOpLog {
+    entries: [Option<PrivilegedOpRecord>; PRIVILEGED_OP_LOG_SIZE],
+    next: usize,
+}
+
+impl PrivilegedOpLog {
+    fn record(&mut self, record: PrivilegedOpRecord) {
+        self.entries[self.next] = Some(record);
+        self.next = (self.next + 1) % PRIVILEGED_OP_LOG_SIZE;
+    }
+
+    /// Iterate over the recorded operations, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &PrivilegedOpRecord> {
+        self.entries[self.next..]
+            .iter()
+            .chain(self.entries[..self.next].iter())
+            .flatten()
+    }
+}
+
+pub struct PrivilegedSocIfc;
+impl PrivilegedSocIfc {
+    /// Record `op` in the audit log and check that it is allowed.
+    fn authorize(drivers: &mut Drivers, op: PrivilegedOp) -> CaliptraResult<()> {
+        let active_cmd = drivers.handling_command.then(|| drivers.mbox.cmd());
+        let allowed = op.allowed(active_cmd);
+        Self::record(drivers, op, allowed);
+
+        if !allowed {
+            return Err(CaliptraError::RUNTIME_PRIVILEGED_OP_NOT_ALLOWED);
+        }
+        Ok(())
+    }
+
+    fn record(drivers: &mut Drivers, op: PrivilegedOp, allowed: bool) {
+        drivers.privileged_op_log.record(PrivilegedOpRecord {
+            op,
+            cmd_id: drivers.mbox.cmd().into(),
+            pauser: drivers.mbox.user(),
+            allowed,
+        });
+    }
+
+    /// Arm the watchdog with the timeout set by CONFIGURE_WDT.
+    pub fn start_wdt(drivers: &mut Drivers) -> CaliptraResult<()> {
+        Self::authorize(drivers, PrivilegedOp::StartWdt)?;
+        caliptra_common::wdt::start_wdt(&mut drivers.soc_ifc, WdtTimeout(drivers.wdt_timeout.0));
+        Ok(())
+    }
+
+    /// Callback restarting the watchdog, for long-running operations that
+    /// hold borrows of the drivers.
+    ///
+    /// `PrivilegedOp::PetWdt` is always allowed, so it is recorded once here
+    /// rather than on every restart.
+    pub fn wdt_pet(drivers: &mut Drivers) -> fn() {
+        Self::record(drivers, PrivilegedOp::PetWdt, true);
+        caliptra_drivers::restart_wdt1
+    }
+
+    /// Stop the watchdog.
+    pub fn stop_wdt(drivers: &mut Drivers) -> CaliptraResult<()> {
+        Self::authorize(drivers, PrivilegedOp::StopWdt)?;
+        caliptra_common::wdt::stop_wdt(&mut drivers.soc_ifc);
+        Ok(())
+    }
+
+    /// Arm WDT1 with WDT2 disabled so that WDT1 expiry cascades into WDT2
+    /// and an NMI.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_cycles` - WDT1 and WDT2 periods
+    pub fn inject_wdt_expiry(drivers: &mut Drivers, timeout_cycles: u64) -> CaliptraResult<()> {
+        Self::authorize(drivers, PrivilegedOp::InjectWdtExpiry)?;
+        drivers.soc_ifc.set_wdt1_timeout(timeout_cycles);
+        drivers.soc_ifc.set_wdt2_timeout(timeout_cycles);
+        drivers.soc_ifc.configure_wdt1(true);
+        drivers.soc_ifc.reset_wdt1();
+        Ok(())
+    }
+
+    /// Hold the impactless firmware update reset for the number of cycles
+    /// requested by the SoC.
+    pub fn assert_fw_update_reset(drivers: &mut Drivers) -> CaliptraResult<()> {
+        Self::authorize(drivers, PrivilegedOp::FwUpdateReset)?;
+        let cycles = drivers.soc_ifc.internal_fw_update_reset_wait_cycles();
+        for _ in 0..cycles {
+            drivers.soc_ifc.assert_fw_update_reset();
+        }
+        Ok(())
+    }
+
+    /// Signal the SoC to burn the runtime SVN fuse up to `svn`.
+    pub fn request_runtime_svn_fuse_update(drivers: &mut Drivers, svn: u32) -> CaliptraResult<()> {
+        Self::authorize(drivers, PrivilegedOp::FuseSvnUpdateRequest)?;
+        drivers.soc_ifc.request_runtime_svn_fuse_update(svn);
+        Ok(())
+    }
+
+    /// Signal the SoC to program new field entropy.
+    pub fn request_field_entropy_reseed(drivers: &mut Drivers) -> CaliptraResult<()> {
+        Self::authorize(drivers, PrivilegedOp::FieldEntropyReseedRequest)?;
+        drivers.soc_ifc.request_field_entropy_reseed();
+        Ok(())
+    }
+}
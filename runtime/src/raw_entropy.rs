@@ -0,0 +1,69 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    raw_entropy.rs
+
+Abstract:
+
+    File contains CaptureRawEntropy and GetRawEntropy mailbox commands.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{GetRawEntropyResp, MailboxResp, MailboxRespHeader};
+use caliptra_drivers::{CaliptraError, CaliptraResult, Lifecycle};
+
+/// Raw entropy is only handed out for SP 800-90B assessment of parts that
+/// have not left manufacturing.
+fn check_manufacturing(drivers: &Drivers) -> CaliptraResult<()> {
+    if drivers.soc_ifc.lifecycle() != Lifecycle::Manufacturing {
+        return Err(CaliptraError::RUNTIME_RAW_ENTROPY_NOT_MANUFACTURING);
+    }
+    Ok(())
+}
+
+pub struct CaptureRawEntropyCmd;
+impl CaptureRawEntropyCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        check_manufacturing(drivers)?;
+
+        drivers.trng.start_raw_entropy_capture()?;
+
+        // Restart the sequence so the host can tell a new capture apart
+        // from a continuation of an earlier one.
+        drivers.raw_entropy_seq = Some(0);
+
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct GetRawEntropyCmd;
+impl GetRawEntropyCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        check_manufacturing(drivers)?;
+
+        let seq_num = drivers
+            .raw_entropy_seq
+            .ok_or(CaliptraError::RUNTIME_RAW_ENTROPY_CAPTURE_NOT_STARTED)?;
+
+        let mut resp = GetRawEntropyResp {
+            hdr: MailboxRespHeader::default(),
+            seq_num,
+            ..Default::default()
+        };
+
+        if drivers.trng.read_raw_entropy(&mut resp.samples)? {
+            resp.flags |= GetRawEntropyResp::FLAG_OVERFLOW;
+        }
+        resp.sample_count = GetRawEntropyResp::MAX_SAMPLES as u32;
+
+        drivers.raw_entropy_seq = Some(seq_num.wrapping_add(1));
+
+        Ok(MailboxResp::GetRawEntropy(resp))
+    }
+}
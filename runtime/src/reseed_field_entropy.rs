@@ -0,0 +1,32 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    reseed_field_entropy.rs
+
+Abstract:
+
+    File contains ReseedFieldEntropy mailbox command.
+
+--*/
+
+use crate::{DisableAttestationCmd, Drivers, PrivilegedSocIfc};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::MailboxResp;
+use caliptra_drivers::CaliptraResult;
+
+pub struct ReseedFieldEntropyCmd;
+impl ReseedFieldEntropyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        PrivilegedSocIfc::request_field_entropy_reseed(drivers)?;
+
+        // The LDevID and the identities derived from it belong to the previous
+        // owner's field entropy. Retire them until the cold reset that
+        // re-derives the LDevID from the new field entropy.
+        DisableAttestationCmd::execute(drivers)
+    }
+}
@@ -0,0 +1,127 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    revoke_locality.rs
+
+Abstract:
+
+    File contains RevokeLocality mailbox command.
+
+--*/
+
+use crate::{invoke_dpe::InvokeDpeCmd, CptraDpeTypes, DpeCrypto, DpeEnv, DpePlatform, Drivers};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{MailboxResp, RevokeLocalityReq};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use dpe::{
+    commands::{CommandExecution, DestroyCtxCmd},
+    context::ContextState,
+    MAX_HANDLES,
+};
+use zerocopy::FromBytes;
+
+pub struct RevokeLocalityCmd;
+impl RevokeLocalityCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = RevokeLocalityReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let target_locality = cmd.locality;
+
+        // The PL0 locality owns the default context and Caliptra's own locality
+        // owns the root; neither can be torn down.
+        let pl0_pauser = drivers.persistent_data.get().manifest1.header.pl0_pauser;
+        if target_locality == pl0_pauser || target_locality == crate::RESERVED_PAUSER {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+
+        drivers
+            .persistent_data
+            .get_mut()
+            .revoked_localities
+            .insert(target_locality)
+            .map_err(|_| CaliptraError::RUNTIME_REVOKED_LOCALITY_LIMIT_REACHED)?;
+
+        Self::destroy_contexts(drivers, target_locality)?;
+
+        Ok(MailboxResp::default())
+    }
+
+    /// Destroy every DPE context owned by `locality`, along with its
+    /// descendants, and clear the tags of the destroyed contexts.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `locality` - Locality whose contexts are destroyed
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    fn destroy_contexts(drivers: &mut Drivers, locality: u32) -> CaliptraResult<()> {
+        let hashed_rt_pub_key = drivers.compute_rt_alias_sn()?;
+        let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
+        let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+        let pdata = drivers.persistent_data.get_mut();
+        let crypto = DpeCrypto::new(
+            &mut drivers.sha256,
+            &mut drivers.sha384,
+            &mut drivers.trng,
+            &mut drivers.ecc384,
+            &mut drivers.hmac384,
+            &mut drivers.key_vault,
+            &mut pdata.fht.rt_dice_pub_key,
+            key_id_rt_cdi,
+            key_id_rt_priv_key,
+            &mut drivers.exported_cdi_slots,
+        );
+        let (nb, nf) = Drivers::get_cert_validity_info(&pdata.manifest1);
+        let mut env = DpeEnv::<CptraDpeTypes> {
+            crypto,
+            platform: DpePlatform::new(
+                pdata.manifest1.header.pl0_pauser,
+                &hashed_rt_pub_key,
+                &drivers.cert_chain,
+                &nb,
+                &nf,
+                None,
+                None,
+                None,
+            ),
+        };
+
+        let dpe = &mut pdata.dpe;
+        // Destroying a context also destroys its children, so the state of
+        // each slot must be re-checked as we go.
+        for i in 0..MAX_HANDLES {
+            if i >= dpe.contexts.len() {
+                break;
+            }
+            let context = dpe.contexts[i];
+            if context.state == ContextState::Inactive || context.locality != locality {
+                continue;
+            }
+            DestroyCtxCmd {
+                handle: context.handle,
+            }
+            .execute(dpe, &mut env, locality)
+            .map_err(|e| {
+                // If there is extended error info, populate CPTRA_FW_EXTENDED_ERROR_INFO
+                if let Some(ext_err) = e.get_error_detail() {
+                    drivers.soc_ifc.set_fw_extended_error(ext_err);
+                }
+                CaliptraError::RUNTIME_REVOKE_LOCALITY_FAILED
+            })?;
+        }
+
+        InvokeDpeCmd::clear_tags_for_inactive_contexts(
+            dpe,
+            &mut pdata.context_has_tag,
+            &mut pdata.context_tags,
+        );
+        InvokeDpeCmd::verify_contexts_reclaimed(dpe, &pdata.context_has_tag, &pdata.context_tags)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    sealed_key.rs
+
+Abstract:
+
+    File contains the SEAL_KEY and UNSEAL_KEY mailbox commands, which bind a
+    key to a PCR and SVN policy and release it only while the policy holds.
+
+--*/
+
+use crate::{handoff::RtHandoff, CryptoBackend, Drivers, Hmac};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert_eq_12_words, cfi_launder};
+use caliptra_common::keyids::KEY_ID_FHT_MAC;
+use caliptra_common::mailbox_api::{
+    MailboxResp, MailboxRespHeader, SealKeyReq, SealKeyResp, SealedKeyBlob, UnsealKeyReq,
+    UnsealKeyResp,
+};
+use caliptra_drivers::{Array4x12, CaliptraError, CaliptraResult};
+use core::mem::size_of;
+use zerocopy::{FromBytes, IntoBytes};
+use zeroize::Zeroize;
+
+const SEALED_KEY_LABEL: &[u8] = b"sealed_key";
+const SEALED_KEY_ENC_LABEL: &[u8] = b"enc";
+const SEALED_KEY_MAC_LABEL: &[u8] = b"mac";
+
+/// Keys protecting a sealed key blob
+struct SealingKeys {
+    /// Key of the keystream the key is encrypted with
+    enc: Array4x12,
+
+    /// Key of the tag covering the policy and the encrypted key
+    mac: Array4x12,
+}
+
+impl SealingKeys {
+    /// Derive the keys protecting the blob with nonce `nonce`. They derive
+    /// from the FHT MAC key, which FMC derives from the FMC CDI on every
+    /// boot, so blobs remain valid across runtime updates and the SVN
+    /// policy is what keeps older runtimes from unsealing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `nonce` - Nonce of the blob
+    fn derive(drivers: &mut Drivers, nonce: &[u8; 48]) -> CaliptraResult<Self> {
+        let mut seed = Hmac::ecc384_hmac(drivers, KEY_ID_FHT_MAC, SEALED_KEY_LABEL, nonce)?;
+        let enc = hmac(drivers, &seed, SEALED_KEY_ENC_LABEL);
+        let mac = hmac(drivers, &seed, SEALED_KEY_MAC_LABEL);
+        seed.zeroize();
+        Ok(Self {
+            enc: enc?,
+            mac: mac?,
+        })
+    }
+
+    /// XOR `data` with the keystream, whose block `i` is HMAC(enc, i)
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `data` - Key to encrypt or decrypt in place
+    fn apply_keystream(&self, drivers: &mut Drivers, data: &mut [u8]) -> CaliptraResult<()> {
+        for (i, chunk) in data.chunks_mut(48).enumerate() {
+            let mut block: [u8; 48] = hmac(drivers, &self.enc, &[i as u8])?.into();
+            for (byte, key_byte) in chunk.iter_mut().zip(block.iter()) {
+                *byte ^= key_byte;
+            }
+            block.zeroize();
+        }
+        Ok(())
+    }
+
+    /// Compute the tag of `sealed`, which covers every field but the tag
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `sealed` - Sealed key blob
+    fn tag(&self, drivers: &mut Drivers, sealed: &SealedKeyBlob) -> CaliptraResult<Array4x12> {
+        let data = &sealed.as_bytes()[..size_of::<SealedKeyBlob>() - sealed.tag.len()];
+        hmac(drivers, &self.mac, data)
+    }
+}
+
+impl Drop for SealingKeys {
+    fn drop(&mut self) {
+        self.enc.zeroize();
+        self.mac.zeroize();
+    }
+}
+
+fn hmac(drivers: &mut Drivers, key: &Array4x12, data: &[u8]) -> CaliptraResult<Array4x12> {
+    drivers.crypto_backend().hmac384(key, data)
+}
+
+/// Sealed keys are not released once attestation is disabled, as the
+/// runtime is then no longer vouched for
+fn check_available(drivers: &Drivers) -> CaliptraResult<()> {
+    if drivers.persistent_data.get().attestation_disabled.get() {
+        return Err(CaliptraError::RUNTIME_SEALED_KEY_UNAVAILABLE);
+    }
+    Ok(())
+}
+
+/// Hash the PCRs selected by `pcr_mask`, in ascending index order
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `pcr_mask` - Bit `i` selects PCR `i`
+fn pcr_digest(drivers: &mut Drivers, pcr_mask: u32) -> CaliptraResult<Array4x12> {
+    let pcrs = drivers.pcr_bank.read_all_pcrs();
+    let mut digest = Array4x12::default();
+    let mut hasher = drivers.sha384.digest_init()?;
+    for (i, pcr) in pcrs.iter().enumerate() {
+        if pcr_mask & (1 << i) != 0 {
+            let pcr: [u8; 48] = pcr.into();
+            hasher.update(&pcr)?;
+        }
+    }
+    hasher.finalize(&mut digest)?;
+    Ok(digest)
+}
+
+pub struct SealKeyCmd;
+impl SealKeyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SealKeyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let key = cmd
+            .key
+            .get(..cmd.key_size as usize)
+            .filter(|key| !key.is_empty())
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        check_available(drivers)?;
+
+        let mut sealed = SealedKeyBlob {
+            pcr_mask: cmd.pcr_mask,
+            pcr_digest: cmd.pcr_digest,
+            min_svn: cmd.min_svn,
+            nonce: drivers.trng.generate()?.into(),
+            key_size: cmd.key_size,
+            ..Default::default()
+        };
+        let keys = SealingKeys::derive(drivers, &sealed.nonce)?;
+        let ciphertext = &mut sealed.ciphertext[..key.len()];
+        ciphertext.copy_from_slice(key);
+        if let Err(e) = keys.apply_keystream(drivers, ciphertext) {
+            ciphertext.zeroize();
+            return Err(e);
+        }
+        sealed.tag = keys.tag(drivers, &sealed)?.into();
+
+        Ok(MailboxResp::SealKey(SealKeyResp {
+            hdr: MailboxRespHeader::default(),
+            sealed,
+        }))
+    }
+}
+
+pub struct UnsealKeyCmd;
+impl UnsealKeyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = UnsealKeyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let sealed = &cmd.sealed;
+        let ciphertext = sealed
+            .ciphertext
+            .get(..sealed.key_size as usize)
+            .filter(|ciphertext| !ciphertext.is_empty())
+            .ok_or(CaliptraError::RUNTIME_SEALED_KEY_INVALID)?;
+        check_available(drivers)?;
+
+        let keys = SealingKeys::derive(drivers, &sealed.nonce)?;
+        let tag = keys.tag(drivers, sealed)?;
+        let expected_tag = Array4x12::from(sealed.tag);
+        if cfi_launder(tag) != expected_tag {
+            return Err(CaliptraError::RUNTIME_SEALED_KEY_INVALID);
+        } else {
+            cfi_assert_eq_12_words(&tag.0, &expected_tag.0);
+        }
+        Self::check_policy(drivers, sealed)?;
+
+        let mut resp = UnsealKeyResp {
+            hdr: MailboxRespHeader::default(),
+            key_size: sealed.key_size,
+            ..Default::default()
+        };
+        let key = &mut resp.key[..ciphertext.len()];
+        key.copy_from_slice(ciphertext);
+        if let Err(e) = keys.apply_keystream(drivers, key) {
+            key.zeroize();
+            return Err(e);
+        }
+
+        Ok(MailboxResp::UnsealKey(resp))
+    }
+
+    /// Check that the runtime SVN and the PCRs satisfy the policy of the
+    /// blob. A PCR mask of 0 does not bind the key to any PCR.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `sealed` - Sealed key blob
+    fn check_policy(drivers: &mut Drivers, sealed: &SealedKeyBlob) -> CaliptraResult<()> {
+        let rt_svn = RtHandoff {
+            data_vault: &drivers.data_vault,
+            fht: &drivers.persistent_data.get().fht,
+        }
+        .rt_svn()?;
+        if rt_svn < sealed.min_svn {
+            return Err(CaliptraError::RUNTIME_SEALED_KEY_POLICY_MISMATCH);
+        }
+
+        if sealed.pcr_mask != 0 {
+            let digest = pcr_digest(drivers, sealed.pcr_mask)?;
+            let expected_digest = Array4x12::from(sealed.pcr_digest);
+            if cfi_launder(digest) != expected_digest {
+                return Err(CaliptraError::RUNTIME_SEALED_KEY_POLICY_MISMATCH);
+            } else {
+                cfi_assert_eq_12_words(&digest.0, &expected_digest.0);
+            }
+        }
+        Ok(())
+    }
+}
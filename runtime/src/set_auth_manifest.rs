@@ -16,7 +16,7 @@ use core::cmp::min;
 use core::mem::size_of;
 
 use crate::verify;
-use crate::{dpe_crypto::DpeCrypto, CptraDpeTypes, DpePlatform, Drivers};
+use crate::{cancel, dpe_crypto::DpeCrypto, CptraDpeTypes, DpePlatform, Drivers};
 use caliptra_auth_man_types::{
     AuthManifestFlags, AuthManifestImageMetadata, AuthManifestImageMetadataCollection,
     AuthManifestPreamble, AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT, AUTH_MANIFEST_MARKER,
@@ -513,6 +513,7 @@ impl SetAuthManifestCmd {
             &mut drivers.sha256,
             &drivers.soc_ifc,
         )?;
+        cancel::checkpoint(&mut drivers.soc_ifc, &drivers.mbox)?;
 
         // Verify the owner public keys.
         Self::verify_owner_pub_keys(
@@ -523,6 +524,7 @@ impl SetAuthManifestCmd {
             &mut drivers.sha256,
             &drivers.soc_ifc,
         )?;
+        cancel::checkpoint(&mut drivers.soc_ifc, &drivers.mbox)?;
 
         Self::process_image_metadata_col(
             manifest_buf
@@ -0,0 +1,181 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    set_measurement_manifest.rs
+
+Abstract:
+
+    File contains SetMeasurementManifest mailbox command.
+
+--*/
+
+use crate::{Drivers, PauserPrivileges, StashMeasurementCmd};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert_eq_12_words, cfi_launder};
+use caliptra_common::mailbox_api::{
+    MailboxResp, MailboxRespHeader, MeasurementManifestEntry, SetMeasurementManifestReq,
+    SetMeasurementManifestResp,
+};
+use caliptra_drivers::{
+    pcr_log::{MeasurementLogEntry, PcrLogEntry, PcrLogEntryId, PCR_ID_STASH_MEASUREMENT},
+    Array4x12, CaliptraError, CaliptraResult, Ecc384PubKey, Ecc384Scalar, Ecc384Signature,
+    PersistentData, MEASUREMENT_MAX_COUNT,
+};
+use dpe::response::DpeErrorCode;
+use zerocopy::{FromBytes, IntoBytes};
+
+pub struct SetMeasurementManifestCmd;
+impl SetMeasurementManifestCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SetMeasurementManifestReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let entries = cmd
+            .entries
+            .get(..cmd.entry_count as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        // ID 0 marks measurements that were not carried by a manifest
+        if cmd.manifest_id == 0 {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+
+        // Only PL0 can stash measurements
+        match drivers.caller_privilege_level() {
+            PauserPrivileges::PL0 => (),
+            PauserPrivileges::PL1 => {
+                return Err(CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL);
+            }
+        }
+
+        if !Self::verify_signature(drivers, cmd, entries)? {
+            return Err(CaliptraError::RUNTIME_MEASUREMENT_MANIFEST_SIGNATURE_INVALID);
+        }
+
+        // A manifest is ingested once, and only if the measurement log has
+        // room for all of its entries
+        let pdata = drivers.persistent_data.get();
+        let log_len = (pdata.fht.meas_log_index as usize).min(MEASUREMENT_MAX_COUNT);
+        if pdata.measurement_log[..log_len]
+            .iter()
+            .any(|entry| entry.manifest_id == cmd.manifest_id)
+        {
+            return Err(CaliptraError::RUNTIME_MEASUREMENT_MANIFEST_ALREADY_SET);
+        }
+        if log_len + entries.len() > MEASUREMENT_MAX_COUNT {
+            return Err(CaliptraError::RUNTIME_MEASUREMENT_LOG_FULL);
+        }
+
+        let mut dpe_result = DpeErrorCode::NoError;
+        let mut applied_count = 0;
+        for entry in entries {
+            dpe_result = StashMeasurementCmd::stash_measurement(
+                drivers,
+                &entry.metadata,
+                &entry.measurement,
+            )?;
+            if !matches!(dpe_result, DpeErrorCode::NoError) {
+                break;
+            }
+            Self::log_measurement(drivers.persistent_data.get_mut(), cmd.manifest_id, entry)?;
+            applied_count += 1;
+        }
+
+        Ok(MailboxResp::SetMeasurementManifest(
+            SetMeasurementManifestResp {
+                hdr: MailboxRespHeader::default(),
+                dpe_result: dpe_result.get_error_code(),
+                applied_count,
+            },
+        ))
+    }
+
+    /// Verify that the manifest is signed by the owner key of the running
+    /// firmware. The signature covers the manifest ID, the entry count and
+    /// the entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `cmd` - SetMeasurementManifest request
+    /// * `entries` - Entries of the manifest
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the signature is valid
+    fn verify_signature(
+        drivers: &mut Drivers,
+        cmd: &SetMeasurementManifestReq,
+        entries: &[MeasurementManifestEntry],
+    ) -> CaliptraResult<bool> {
+        let mut digest = Array4x12::default();
+        let mut hasher = drivers.sha384.digest_init()?;
+        hasher.update(cmd.manifest_id.as_bytes())?;
+        hasher.update(cmd.entry_count.as_bytes())?;
+        hasher.update(entries.as_bytes())?;
+        hasher.finalize(&mut digest)?;
+
+        let owner_pub_key = &drivers
+            .persistent_data
+            .get()
+            .manifest1
+            .preamble
+            .owner_pub_keys
+            .ecc_pub_key;
+        let pub_key = Ecc384PubKey {
+            x: owner_pub_key.x.into(),
+            y: owner_pub_key.y.into(),
+        };
+        let sig = Ecc384Signature {
+            r: Ecc384Scalar::from(cmd.signature_r),
+            s: Ecc384Scalar::from(cmd.signature_s),
+        };
+
+        let verify_r = drivers.ecc384.verify_r(&pub_key, &digest, &sig)?;
+        if cfi_launder(verify_r) != sig.r {
+            return Ok(false);
+        }
+        cfi_assert_eq_12_words(&verify_r.0, &sig.r.0);
+        Ok(true)
+    }
+
+    /// Append a manifest entry to the measurement log
+    ///
+    /// # Arguments
+    ///
+    /// * `persistent_data` - Persistent data
+    /// * `manifest_id` - ID of the manifest carrying the entry
+    /// * `entry` - Manifest entry
+    fn log_measurement(
+        persistent_data: &mut PersistentData,
+        manifest_id: u32,
+        entry: &MeasurementManifestEntry,
+    ) -> CaliptraResult<()> {
+        let fht = &mut persistent_data.fht;
+        let Some(dst) = persistent_data
+            .measurement_log
+            .get_mut(fht.meas_log_index as usize)
+        else {
+            return Err(CaliptraError::RUNTIME_MEASUREMENT_LOG_FULL);
+        };
+
+        *dst = MeasurementLogEntry {
+            pcr_entry: PcrLogEntry {
+                id: PcrLogEntryId::MeasurementManifest as u16,
+                reserved0: [0u8; 2],
+                pcr_ids: 1 << (PCR_ID_STASH_MEASUREMENT as u8),
+                pcr_data: zerocopy::transmute!(entry.measurement),
+            },
+            metadata: entry.metadata,
+            context: [0u32; 12],
+            svn: entry.svn,
+            manifest_id,
+        };
+        fht.meas_log_index += 1;
+
+        Ok(())
+    }
+}
@@ -73,6 +73,7 @@ impl SignWithExportedEcdsaCmd {
         let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
 
         let mut crypto = DpeCrypto::new(
+            &mut drivers.sha256,
             &mut drivers.sha384,
             &mut drivers.trng,
             &mut drivers.ecc384,
@@ -0,0 +1,588 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    spdm.rs
+
+Abstract:
+
+    File contains the SPDM mailbox command, which carries the messages of an
+    SPDM 1.2/1.3 responder.
+
+--*/
+
+use crate::Drivers;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{MailboxResp, SpdmReq, SpdmResp};
+use caliptra_drivers::{
+    Array4x12, CaliptraError, CaliptraResult, Ecc384PrivKeyIn, KeyReadArgs, Sha384Sw,
+};
+use zerocopy::IntoBytes;
+use zeroize::Zeroize;
+
+const SPDM_VERSION_10: u8 = 0x10;
+const SPDM_VERSION_12: u8 = 0x12;
+const SPDM_VERSION_13: u8 = 0x13;
+
+// Request codes
+const GET_DIGESTS: u8 = 0x81;
+const GET_CERTIFICATE: u8 = 0x82;
+const CHALLENGE: u8 = 0x83;
+const GET_VERSION: u8 = 0x84;
+const GET_MEASUREMENTS: u8 = 0xE0;
+const GET_CAPABILITIES: u8 = 0xE1;
+const NEGOTIATE_ALGORITHMS: u8 = 0xE3;
+
+// Response codes
+const DIGESTS: u8 = 0x01;
+const CERTIFICATE: u8 = 0x02;
+const CHALLENGE_AUTH: u8 = 0x03;
+const VERSION: u8 = 0x04;
+const MEASUREMENTS: u8 = 0x60;
+const CAPABILITIES: u8 = 0x61;
+const ALGORITHMS: u8 = 0x63;
+const ERROR: u8 = 0x7F;
+
+// Error codes
+const ERROR_INVALID_REQUEST: u8 = 0x01;
+const ERROR_UNEXPECTED_REQUEST: u8 = 0x04;
+const ERROR_UNSUPPORTED_REQUEST: u8 = 0x07;
+const ERROR_RESPONSE_TOO_LARGE: u8 = 0x0D;
+const ERROR_VERSION_MISMATCH: u8 = 0x41;
+
+/// CERT_CAP, CHAL_CAP and MEAS_CAP with signatures
+const CAPABILITY_FLAGS: u32 = (1 << 1) | (1 << 2) | (2 << 3);
+
+/// Timeout for cryptographic operations, 2^20 us
+const CT_EXPONENT: u8 = 20;
+
+/// Smallest DataTransferSize a 1.2 requester may report
+const MIN_DATA_TRANSFER_SIZE: u32 = 42;
+
+const BASE_ASYM_ECDSA_P384: u32 = 1 << 7;
+const BASE_HASH_SHA384: u32 = 1 << 1;
+const MEAS_HASH_SHA384: u32 = 1 << 2;
+const MEAS_SPEC_DMTF: u8 = 1 << 0;
+
+/// DMTF measurement value type of a PCR: a digest of mutable firmware
+const MEAS_VALUE_MUTABLE_FW: u8 = 0x01;
+
+const HASH_SIZE: usize = 48;
+const NONCE_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 96;
+const REQUESTER_CONTEXT_SIZE: usize = 8;
+const PCR_COUNT: usize = 32;
+const MEAS_BLOCK_SIZE: usize = 7 + HASH_SIZE;
+
+/// Length, reserved bytes and root hash preceding the certificates of a
+/// certificate chain
+const CERT_CHAIN_HEADER_SIZE: usize = 4 + HASH_SIZE;
+
+const CHALLENGE_AUTH_CONTEXT: &[u8] = b"responder-challenge_auth signing";
+const MEASUREMENTS_CONTEXT: &[u8] = b"responder-measurements signing";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum ConnectionState {
+    #[default]
+    NotStarted,
+    AfterVersion,
+    AfterCapabilities,
+    Negotiated,
+}
+
+/// SPDM connection state, kept between SPDM mailbox commands
+#[derive(Default)]
+pub struct SpdmState {
+    state: ConnectionState,
+
+    /// Version selected by GET_CAPABILITIES
+    version: u8,
+
+    /// DataTransferSize of the requester, or 0 before GET_CAPABILITIES
+    max_response_size: u32,
+
+    /// Transcript of GET_VERSION through ALGORITHMS
+    vca: Sha384Sw,
+
+    /// Transcript signed by the next CHALLENGE_AUTH
+    m1: Sha384Sw,
+
+    /// Transcript signed by the next signed MEASUREMENTS
+    l1: Sha384Sw,
+}
+
+enum SpdmError {
+    /// Reported to the requester in an ERROR message
+    Response(u8),
+
+    /// Fails the mailbox command
+    Fatal(CaliptraError),
+}
+
+impl From<CaliptraError> for SpdmError {
+    fn from(e: CaliptraError) -> Self {
+        SpdmError::Fatal(e)
+    }
+}
+
+type SpdmResult<T> = Result<T, SpdmError>;
+
+struct SpdmWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SpdmWriter<'a> {
+    fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    fn put(&mut self, data: &[u8]) -> SpdmResult<()> {
+        let end = self.len + data.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(SpdmError::Response(ERROR_RESPONSE_TOO_LARGE))?
+            .copy_from_slice(data);
+        self.len = end;
+        Ok(())
+    }
+
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.len
+    }
+
+    fn written(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+pub struct SpdmCmd;
+impl SpdmCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        if cmd_args.len() > core::mem::size_of::<SpdmReq>() {
+            return Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY);
+        }
+        let mut cmd = SpdmReq::default();
+        cmd.as_mut_bytes()[..cmd_args.len()].copy_from_slice(cmd_args);
+        let request = cmd
+            .data
+            .get(..cmd.data_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        let mut resp = SpdmResp::default();
+        resp.data_size = handle_message(drivers, request, &mut resp.data)? as u32;
+
+        Ok(MailboxResp::Spdm(resp))
+    }
+}
+
+/// Handle the SPDM request `request`, writing the response to `out`
+///
+/// # Returns
+///
+/// * `usize` - Length of the response, which is an SPDM ERROR message if the
+///             request failed
+pub(crate) fn handle_message(
+    drivers: &mut Drivers,
+    request: &[u8],
+    out: &mut [u8],
+) -> CaliptraResult<usize> {
+    let limit = match drivers.spdm.max_response_size as usize {
+        0 => out.len(),
+        size => size.min(out.len()),
+    };
+    let mut writer = SpdmWriter::new(&mut out[..limit]);
+    match respond(drivers, request, &mut writer) {
+        Ok(()) => Ok(writer.len),
+        Err(SpdmError::Response(code)) => {
+            let version = match request.get(1) {
+                Some(&GET_VERSION) => SPDM_VERSION_10,
+                _ if drivers.spdm.state < ConnectionState::AfterCapabilities => SPDM_VERSION_10,
+                _ => drivers.spdm.version,
+            };
+            let data = match code {
+                ERROR_UNSUPPORTED_REQUEST => request.get(1).copied().unwrap_or(0),
+                _ => 0,
+            };
+            out.get_mut(..4)
+                .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?
+                .copy_from_slice(&[version, ERROR, code, data]);
+            Ok(4)
+        }
+        Err(SpdmError::Fatal(e)) => Err(e),
+    }
+}
+
+fn respond(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    let (version, code) = match req {
+        [version, code, _, _, ..] => (*version, *code),
+        _ => return Err(SpdmError::Response(ERROR_INVALID_REQUEST)),
+    };
+
+    match code {
+        GET_VERSION => {
+            if version != SPDM_VERSION_10 {
+                return Err(SpdmError::Response(ERROR_VERSION_MISMATCH));
+            }
+            return get_version(drivers, req, w);
+        }
+        GET_CAPABILITIES => {
+            if drivers.spdm.state != ConnectionState::AfterVersion {
+                return Err(SpdmError::Response(ERROR_UNEXPECTED_REQUEST));
+            }
+            if version != SPDM_VERSION_12 && version != SPDM_VERSION_13 {
+                return Err(SpdmError::Response(ERROR_VERSION_MISMATCH));
+            }
+            return get_capabilities(drivers, req, w);
+        }
+        NEGOTIATE_ALGORITHMS | GET_DIGESTS | GET_CERTIFICATE | CHALLENGE | GET_MEASUREMENTS => {}
+        _ => return Err(SpdmError::Response(ERROR_UNSUPPORTED_REQUEST)),
+    }
+
+    if drivers.spdm.state < ConnectionState::AfterCapabilities {
+        return Err(SpdmError::Response(ERROR_UNEXPECTED_REQUEST));
+    }
+    if version != drivers.spdm.version {
+        return Err(SpdmError::Response(ERROR_VERSION_MISMATCH));
+    }
+    if code == NEGOTIATE_ALGORITHMS {
+        if drivers.spdm.state != ConnectionState::AfterCapabilities {
+            return Err(SpdmError::Response(ERROR_UNEXPECTED_REQUEST));
+        }
+        return negotiate_algorithms(drivers, req, w);
+    }
+    if drivers.spdm.state != ConnectionState::Negotiated {
+        return Err(SpdmError::Response(ERROR_UNEXPECTED_REQUEST));
+    }
+
+    match code {
+        GET_DIGESTS => get_digests(drivers, req, w),
+        GET_CERTIFICATE => get_certificate(drivers, req, w),
+        CHALLENGE => challenge(drivers, req, w),
+        _ => get_measurements(drivers, req, w),
+    }
+}
+
+/// Resets the connection and reports versions 1.2 and 1.3
+fn get_version(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    if req.len() != 4 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    drivers.spdm = SpdmState::default();
+
+    w.put(&[SPDM_VERSION_10, VERSION, 0, 0, 0, 2])?;
+    w.put(&(u16::from(SPDM_VERSION_12) << 8).to_le_bytes())?;
+    w.put(&(u16::from(SPDM_VERSION_13) << 8).to_le_bytes())?;
+
+    let spdm = &mut drivers.spdm;
+    spdm.vca.update(req);
+    spdm.vca.update(w.written());
+    spdm.state = ConnectionState::AfterVersion;
+    Ok(())
+}
+
+fn get_capabilities(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    if req.len() != 20 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let data_transfer_size = read_u32(req, 12)?;
+    if data_transfer_size < MIN_DATA_TRANSFER_SIZE {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+
+    let version = req[0];
+    w.put(&[version, CAPABILITIES, 0, 0, 0, CT_EXPONENT, 0, 0])?;
+    w.put(&CAPABILITY_FLAGS.to_le_bytes())?;
+    // Requests are not chunked, so a request must fit in a single mailbox
+    // command.
+    w.put(&(SpdmReq::DATA_MAX_SIZE as u32).to_le_bytes())?;
+    w.put(&(SpdmReq::DATA_MAX_SIZE as u32).to_le_bytes())?;
+
+    let spdm = &mut drivers.spdm;
+    spdm.vca.update(req);
+    spdm.vca.update(w.written());
+    spdm.version = version;
+    spdm.max_response_size = data_transfer_size;
+    spdm.state = ConnectionState::AfterCapabilities;
+    Ok(())
+}
+
+/// Selects ECDSA P-384 with SHA-384, the only algorithms of the DICE keys
+fn negotiate_algorithms(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    if req.len() < 32 || usize::from(read_u16(req, 4)?) != req.len() {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let meas_spec = req[6] & MEAS_SPEC_DMTF;
+    let base_asym = read_u32(req, 8)? & BASE_ASYM_ECDSA_P384;
+    let base_hash = read_u32(req, 12)? & BASE_HASH_SHA384;
+    if base_asym == 0 || base_hash == 0 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let meas_hash = if meas_spec != 0 { MEAS_HASH_SHA384 } else { 0 };
+
+    w.put(&[drivers.spdm.version, ALGORITHMS, 0, 0])?;
+    w.put(&36u16.to_le_bytes())?;
+    w.put(&[meas_spec, 0])?;
+    w.put(&meas_hash.to_le_bytes())?;
+    w.put(&base_asym.to_le_bytes())?;
+    w.put(&base_hash.to_le_bytes())?;
+    w.put(&[0; 16])?;
+
+    let spdm = &mut drivers.spdm;
+    spdm.vca.update(req);
+    spdm.vca.update(w.written());
+    spdm.m1 = spdm.vca.clone();
+    spdm.l1 = spdm.vca.clone();
+    spdm.state = ConnectionState::Negotiated;
+    Ok(())
+}
+
+/// Reports the digest of the chain in slot 0, the only slot
+fn get_digests(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    if req.len() != 4 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let version = drivers.spdm.version;
+    // 1.3 reports the supported slots in Param1; 1.2 reserves it
+    let supported_slots = if version >= SPDM_VERSION_13 { 1 } else { 0 };
+    let digest = cert_chain_digest(drivers)?;
+
+    w.put(&[version, DIGESTS, supported_slots, 1])?;
+    w.put(&digest)?;
+
+    let spdm = &mut drivers.spdm;
+    spdm.m1.update(req);
+    spdm.m1.update(w.written());
+    Ok(())
+}
+
+fn get_certificate(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    if req.len() != 8 || req[2] & 0xF != 0 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let offset = usize::from(read_u16(req, 4)?);
+    let length = usize::from(read_u16(req, 6)?);
+
+    let header = cert_chain_header(drivers)?;
+    let certs = drivers.cert_chain.as_slice();
+    let total = header.len() + certs.len();
+    if offset >= total {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let portion = length
+        .min(total - offset)
+        .min(w.remaining().saturating_sub(8));
+    let remainder = total - offset - portion;
+
+    w.put(&[drivers.spdm.version, CERTIFICATE, 0, 0])?;
+    w.put(&(portion as u16).to_le_bytes())?;
+    w.put(&(remainder as u16).to_le_bytes())?;
+    // The chain is the header followed by the certificates
+    let end = offset + portion;
+    if offset < header.len() {
+        w.put(&header[offset..end.min(header.len())])?;
+    }
+    if end > header.len() {
+        w.put(&certs[offset.max(header.len()) - header.len()..end - header.len()])?;
+    }
+
+    let spdm = &mut drivers.spdm;
+    spdm.m1.update(req);
+    spdm.m1.update(w.written());
+    Ok(())
+}
+
+/// Signs M1 with the RT Alias key, which the chain in slot 0 certifies
+fn challenge(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    let version = drivers.spdm.version;
+    if req.len() != 4 + NONCE_SIZE + requester_context_size(version) || req[2] != 0 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let summary_type = req[3];
+    if !matches!(summary_type, 0 | 1 | 0xFF) {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+
+    let chain_digest = cert_chain_digest(drivers)?;
+    let nonce = responder_nonce(drivers)?;
+
+    w.put(&[version, CHALLENGE_AUTH, 0, 1])?;
+    w.put(&chain_digest)?;
+    w.put(&nonce)?;
+    if summary_type != 0 {
+        w.put(&measurement_summary_hash(drivers))?;
+    }
+    // No opaque data
+    w.put(&[0, 0])?;
+    w.put(&req[4 + NONCE_SIZE..])?;
+
+    let spdm = &mut drivers.spdm;
+    spdm.m1.update(req);
+    spdm.m1.update(w.written());
+    let transcript = core::mem::replace(&mut spdm.m1, spdm.vca.clone()).finalize();
+    w.put(&sign(drivers, CHALLENGE_AUTH_CONTEXT, &transcript)?)
+}
+
+/// Reports PCR N as measurement index N + 1, signed with the RT Alias key if
+/// requested
+fn get_measurements(drivers: &mut Drivers, req: &[u8], w: &mut SpdmWriter) -> SpdmResult<()> {
+    let version = drivers.spdm.version;
+    let signed = req[2] & 1 != 0;
+    let sig_fields = if signed { NONCE_SIZE + 1 } else { 0 };
+    if req.len() != 4 + sig_fields + requester_context_size(version) {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    if signed && req[4 + NONCE_SIZE] & 0xF != 0 {
+        return Err(SpdmError::Response(ERROR_INVALID_REQUEST));
+    }
+    let operation = usize::from(req[3]);
+    let (indices, total) = match operation {
+        0 => (0..0, PCR_COUNT as u8),
+        0xFF => (0..PCR_COUNT, 0),
+        index @ 1..=PCR_COUNT => (index - 1..index, 0),
+        _ => return Err(SpdmError::Response(ERROR_INVALID_REQUEST)),
+    };
+
+    let record_len = (indices.len() * MEAS_BLOCK_SIZE) as u32;
+    w.put(&[version, MEASUREMENTS, total, 0])?;
+    w.put(&[indices.len() as u8])?;
+    w.put(&record_len.to_le_bytes()[..3])?;
+    let pcrs = drivers.pcr_bank.read_all_pcrs();
+    for pcr in indices {
+        w.put(&measurement_block(pcr, &pcrs[pcr]))?;
+    }
+    w.put(&responder_nonce(drivers)?)?;
+    // No opaque data
+    w.put(&[0, 0])?;
+    w.put(&req[4 + sig_fields..])?;
+
+    let spdm = &mut drivers.spdm;
+    spdm.l1.update(req);
+    spdm.l1.update(w.written());
+    if signed {
+        let transcript = core::mem::replace(&mut spdm.l1, spdm.vca.clone()).finalize();
+        w.put(&sign(drivers, MEASUREMENTS_CONTEXT, &transcript)?)?;
+    }
+    Ok(())
+}
+
+fn requester_context_size(version: u8) -> usize {
+    if version >= SPDM_VERSION_13 {
+        REQUESTER_CONTEXT_SIZE
+    } else {
+        0
+    }
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> SpdmResult<u16> {
+    match buf.get(offset..offset + 2) {
+        Some(&[a, b]) => Ok(u16::from_le_bytes([a, b])),
+        _ => Err(SpdmError::Response(ERROR_INVALID_REQUEST)),
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> SpdmResult<u32> {
+    match buf.get(offset..offset + 4) {
+        Some(&[a, b, c, d]) => Ok(u32::from_le_bytes([a, b, c, d])),
+        _ => Err(SpdmError::Response(ERROR_INVALID_REQUEST)),
+    }
+}
+
+/// Header of the chain in slot 0. The root hash is the digest of the first
+/// certificate of the chain, which is the root once the SoC has populated
+/// the IDevID chain up to it.
+fn cert_chain_header(drivers: &mut Drivers) -> CaliptraResult<[u8; CERT_CHAIN_HEADER_SIZE]> {
+    let certs = drivers.cert_chain.as_slice();
+    let root = crate::populate_idev::der_next(certs)
+        .ok_or(CaliptraError::RUNTIME_CERT_CHAIN_CREATION_FAILED)?
+        .tlv;
+    let root_hash: [u8; HASH_SIZE] = drivers.sha384.digest(root)?.into();
+
+    let len = u16::try_from(CERT_CHAIN_HEADER_SIZE + certs.len())
+        .map_err(|_| CaliptraError::RUNTIME_CERT_CHAIN_CREATION_FAILED)?;
+    let mut header = [0; CERT_CHAIN_HEADER_SIZE];
+    header[..2].copy_from_slice(&len.to_le_bytes());
+    header[4..].copy_from_slice(&root_hash);
+    Ok(header)
+}
+
+fn cert_chain_digest(drivers: &mut Drivers) -> CaliptraResult<[u8; HASH_SIZE]> {
+    let header = cert_chain_header(drivers)?;
+    let mut digest = Array4x12::default();
+    let mut op = drivers.sha384.digest_init()?;
+    op.update(&header)?;
+    op.update(drivers.cert_chain.as_slice())?;
+    op.finalize(&mut digest)?;
+    Ok(digest.into())
+}
+
+fn measurement_block(pcr: usize, value: &Array4x12) -> [u8; MEAS_BLOCK_SIZE] {
+    let value: [u8; HASH_SIZE] = value.into();
+    let mut block = [0; MEAS_BLOCK_SIZE];
+    block[0] = pcr as u8 + 1;
+    block[1] = MEAS_SPEC_DMTF;
+    block[2..4].copy_from_slice(&((3 + HASH_SIZE) as u16).to_le_bytes());
+    block[4] = MEAS_VALUE_MUTABLE_FW;
+    block[5..7].copy_from_slice(&(HASH_SIZE as u16).to_le_bytes());
+    block[7..].copy_from_slice(&value);
+    block
+}
+
+/// Every PCR is part of the TCB, so both summary types hash all blocks
+fn measurement_summary_hash(drivers: &Drivers) -> [u8; HASH_SIZE] {
+    let mut op = Sha384Sw::new();
+    for (pcr, value) in drivers.pcr_bank.read_all_pcrs().iter().enumerate() {
+        op.update(&measurement_block(pcr, value));
+    }
+    op.finalize()
+}
+
+fn responder_nonce(drivers: &mut Drivers) -> CaliptraResult<[u8; NONCE_SIZE]> {
+    let random: [u8; 48] = drivers.trng.generate()?.into();
+    let mut nonce = [0; NONCE_SIZE];
+    nonce.copy_from_slice(&random[..NONCE_SIZE]);
+    Ok(nonce)
+}
+
+/// Sign `transcript` as an SPDM 1.2+ signature: the hash of the combined
+/// prefix for `context` followed by the transcript hash, signed with the RT
+/// Alias key
+fn sign(
+    drivers: &mut Drivers,
+    context: &[u8],
+    transcript: &[u8; HASH_SIZE],
+) -> CaliptraResult<[u8; SIGNATURE_SIZE]> {
+    let version = drivers.spdm.version;
+    let mut prefix = *b"dmtf-spdm-v1.2.*";
+    prefix[14] = b'0' + (version & 0xF);
+
+    let mut digest = Array4x12::default();
+    let mut op = drivers.sha384.digest_init()?;
+    for _ in 0..4 {
+        op.update(&prefix)?;
+    }
+    op.update(&[0; 36][..36 - context.len()])?;
+    op.update(context)?;
+    op.update(transcript)?;
+    op.finalize(&mut digest)?;
+
+    let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+    let pub_key = drivers.persistent_data.get().fht.rt_dice_pub_key;
+    let priv_key = Ecc384PrivKeyIn::Key(KeyReadArgs::new(key_id_rt_priv_key));
+    let result = drivers
+        .ecc384
+        .sign(&priv_key, &pub_key, &digest, &mut drivers.trng);
+    digest.0.zeroize();
+    let sig = result?;
+
+    let r: [u8; 48] = sig.r.into();
+    let s: [u8; 48] = sig.s.into();
+    let mut signature = [0u8; SIGNATURE_SIZE];
+    signature[..48].copy_from_slice(&r);
+    signature[48..].copy_from_slice(&s);
+    Ok(signature)
+}
@@ -12,7 +12,10 @@ Abstract:
 
 --*/
 
-use crate::{dpe_crypto::DpeCrypto, CptraDpeTypes, DpePlatform, Drivers, PauserPrivileges};
+use crate::{
+    dpe_crypto::{tci_from_measurement, DpeCrypto},
+    CptraDpeTypes, DpePlatform, Drivers, PauserPrivileges,
+};
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_common::mailbox_api::{
     MailboxResp, MailboxRespHeader, StashMeasurementReq, StashMeasurementResp,
@@ -54,6 +57,7 @@ impl StashMeasurementCmd {
             let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
             let pdata = drivers.persistent_data.get_mut();
             let mut crypto = DpeCrypto::new(
+                &mut drivers.sha256,
                 &mut drivers.sha384,
                 &mut drivers.trng,
                 &mut drivers.ecc384,
@@ -75,6 +79,7 @@ impl StashMeasurementCmd {
                     &nf,
                     None,
                     None,
+                    None,
                 ),
             };
 
@@ -82,7 +87,7 @@ impl StashMeasurementCmd {
 
             let derive_context_resp = DeriveContextCmd {
                 handle: ContextHandle::default(),
-                data: *measurement,
+                data: tci_from_measurement(measurement),
                 flags: DeriveContextFlags::MAKE_DEFAULT
                     | DeriveContextFlags::CHANGE_LOCALITY
                     | DeriveContextFlags::INPUT_ALLOW_CA
@@ -0,0 +1,138 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    stream_verify.rs
+
+Abstract:
+
+    File contains the START_STREAM_VERIFY, STREAM_DATA and
+    FINISH_STREAM_VERIFY mailbox commands, which hash a SoC image streamed
+    through the mailbox with the SHA accelerator and authorize it against
+    the image metadata collection.
+
+--*/
+
+use crate::{AuthorizeAndStashCmd, Drivers};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{
+    FinishStreamVerifyReq, FinishStreamVerifyResp, MailboxReqHeader, MailboxResp,
+    MailboxRespHeader, StartStreamVerifyReq, StreamDataReq,
+};
+use caliptra_drivers::{Array4x12, CaliptraError, CaliptraResult, Sha2_512_384AccDetachedStream};
+use core::mem::size_of;
+use zerocopy::FromBytes;
+
+/// Number of polls to wait for the SoC to release the SHA accelerator
+const SHA_ACC_LOCK_TIMEOUT_POLLS: u32 = 100_000;
+
+/// Image stream started by START_STREAM_VERIFY
+pub struct StreamVerify {
+    /// Firmware ID of the image, in little-endian format
+    fw_id: [u8; 4],
+
+    /// Digest of the image, which holds the SHA accelerator lock
+    stream: Sha2_512_384AccDetachedStream,
+}
+
+/// Discards the image stream in progress, if any, releasing the SHA
+/// accelerator
+pub(crate) fn abort(drivers: &mut Drivers) {
+    if let Some(verify) = drivers.stream_verify.take() {
+        verify.stream.abort(&mut drivers.sha2_512_384_acc);
+    }
+}
+
+pub struct StartStreamVerifyCmd;
+impl StartStreamVerifyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = StartStreamVerifyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        if drivers.stream_verify.is_some() {
+            return Err(CaliptraError::RUNTIME_STREAM_VERIFY_IN_PROGRESS);
+        }
+        if cmd.image_size == 0 {
+            return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+        }
+
+        let stream = drivers
+            .sha2_512_384_acc
+            .acquire_with_timeout(SHA_ACC_LOCK_TIMEOUT_POLLS)?
+            .detach_stream_384(cmd.image_size)?;
+        drivers.stream_verify = Some(StreamVerify {
+            fw_id: cmd.fw_id,
+            stream,
+        });
+
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct StreamDataCmd;
+impl StreamDataCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        const DATA_OFFSET: usize = size_of::<StreamDataReq>() - StreamDataReq::DATA_MAX_SIZE;
+
+        // The data is streamed from the request in place, as the request
+        // is too large to copy to the stack
+        if cmd_args.len() > size_of::<StreamDataReq>() {
+            return Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY);
+        }
+        let data_size = cmd_args
+            .get(size_of::<MailboxReqHeader>()..DATA_OFFSET)
+            .and_then(|data_size| u32::read_from_bytes(data_size).ok())
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let data = cmd_args
+            .get(DATA_OFFSET..)
+            .and_then(|data| data.get(..data_size as usize))
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        let verify = drivers
+            .stream_verify
+            .as_mut()
+            .ok_or(CaliptraError::RUNTIME_STREAM_VERIFY_NOT_STARTED)?;
+        verify.stream.update(&mut drivers.sha2_512_384_acc, data)?;
+
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct FinishStreamVerifyCmd;
+impl FinishStreamVerifyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = FinishStreamVerifyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+        let verify = drivers
+            .stream_verify
+            .take()
+            .ok_or(CaliptraError::RUNTIME_STREAM_VERIFY_NOT_STARTED)?;
+
+        // An incomplete stream is discarded
+        let mut digest = Array4x12::default();
+        verify
+            .stream
+            .finalize(&mut drivers.sha2_512_384_acc, &mut digest)?;
+        let digest: [u8; 48] = digest.into();
+
+        let auth_req_result = AuthorizeAndStashCmd::authorize_and_stash(
+            drivers,
+            &verify.fw_id,
+            &digest,
+            cmd.flags.into(),
+        )?;
+
+        Ok(MailboxResp::FinishStreamVerify(FinishStreamVerifyResp {
+            hdr: MailboxRespHeader::default(),
+            auth_req_result,
+            digest,
+        }))
+    }
+}
@@ -0,0 +1,209 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    suspend.rs
+
+Abstract:
+
+    File contains the SuspendPrepare, ResumeNotify and PrepareForSleep
+    mailbox commands.
+
+--*/
+
+use crate::{CertPolicy, DisableAttestationCmd, Drivers, Hmac, RtBootStatus};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert_eq_12_words, cfi_launder};
+use caliptra_common::log_warn;
+use caliptra_common::mailbox_api::{CommandId, MailboxResp};
+use caliptra_drivers::{
+    report_boot_status, report_fw_error_non_fatal, Array4x12, CaliptraError, CaliptraResult,
+    SuspendCheckpoint,
+};
+use zerocopy::IntoBytes;
+use zeroize::Zeroize;
+
+const SUSPEND_CHECKPOINT_LABEL: &[u8] = b"suspend_checkpoint";
+
+/// Compute the MAC of the suspend checkpoint and of the persistent
+/// attestation state
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+///
+/// # Returns
+///
+/// * `[u32; 12]` - MAC keyed by the RT CDI
+fn checkpoint_mac(drivers: &mut Drivers) -> CaliptraResult<[u32; 12]> {
+    let mut digest = Array4x12::default();
+    let pdata = drivers.persistent_data.get();
+    let mut hasher = drivers.sha384.digest_init()?;
+    hasher.update(pdata.suspend_checkpoint.mac_data())?;
+    hasher.update(pdata.dpe.as_bytes())?;
+    hasher.update(pdata.context_tags.as_bytes())?;
+    hasher.update(pdata.context_has_tag.as_bytes())?;
+    hasher.update(pdata.attestation_disabled.as_bytes())?;
+    hasher.update(pdata.revoked_localities.as_bytes())?;
+    hasher.update(pdata.pcr_reset.as_bytes())?;
+    hasher.finalize(&mut digest)?;
+
+    let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
+    let mac = Hmac::ecc384_hmac(
+        drivers,
+        key_id_rt_cdi,
+        SUSPEND_CHECKPOINT_LABEL,
+        digest.as_bytes(),
+    )?;
+    Ok(mac.into())
+}
+
+/// Commands serviced between SUSPEND_PREPARE and RESUME_NOTIFY
+pub(crate) fn allowed_while_suspended(cmd: CommandId) -> bool {
+    matches!(
+        cmd,
+        CommandId::VERSION
+            | CommandId::FW_INFO
+            | CommandId::CAPABILITIES
+            | CommandId::SUSPEND_PREPARE
+            | CommandId::RESUME_NOTIFY
+            | CommandId::PREPARE_FOR_SLEEP
+    )
+}
+
+/// Save the volatile attestation state to the suspend checkpoint
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `suspended` - `SuspendCheckpoint::SUSPENDED` or `SuspendCheckpoint::SLEEPING`
+fn save_checkpoint(drivers: &mut Drivers, suspended: u32) -> CaliptraResult<()> {
+    let mut checkpoint = SuspendCheckpoint {
+        suspended,
+        ..Default::default()
+    };
+    if let Some(cert_policy) = &drivers.cert_policy {
+        checkpoint.has_cert_policy = 1;
+        checkpoint.cert_policy_flags = cert_policy.flags();
+        checkpoint.cert_policy_max_not_after[..15].copy_from_slice(cert_policy.raw_max_not_after());
+    }
+    drivers.persistent_data.get_mut().suspend_checkpoint = checkpoint;
+
+    let mac = checkpoint_mac(drivers);
+    let pdata = drivers.persistent_data.get_mut();
+    match mac {
+        Ok(mac) => pdata.suspend_checkpoint.mac = mac,
+        Err(e) => {
+            pdata.suspend_checkpoint.zeroize();
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Check the suspend checkpoint against the attestation state and restore
+/// the volatile state it holds. The checkpoint is consumed.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+fn restore_checkpoint(drivers: &mut Drivers) -> CaliptraResult<()> {
+    let mac = checkpoint_mac(drivers)?;
+    let checkpoint = core::mem::take(&mut drivers.persistent_data.get_mut().suspend_checkpoint);
+    if cfi_launder(mac) != checkpoint.mac {
+        // The attestation state cannot be trusted anymore
+        DisableAttestationCmd::execute(drivers)
+            .map_err(|_| CaliptraError::RUNTIME_GLOBAL_EXCEPTION)?;
+        return Err(CaliptraError::RUNTIME_SUSPEND_CHECKPOINT_INVALID);
+    } else {
+        cfi_assert_eq_12_words(&mac, &checkpoint.mac);
+    }
+
+    // Restore the volatile state lost if Caliptra was reset while the
+    // SoC was in a low-power state
+    if checkpoint.has_cert_policy != 0 {
+        let mut max_not_after = [0; 15];
+        max_not_after.copy_from_slice(&checkpoint.cert_policy_max_not_after[..15]);
+        drivers.cert_policy = Some(
+            CertPolicy::from_raw(checkpoint.cert_policy_flags, max_not_after)
+                .ok_or(CaliptraError::RUNTIME_SUSPEND_CHECKPOINT_INVALID)?,
+        );
+    }
+    Ok(())
+}
+
+/// Restore the state saved by PREPARE_FOR_SLEEP on the warm reset that ends
+/// the low-power state. A checkpoint that fails to restore is reported as a
+/// non-fatal error, with attestation disabled if it no longer matches.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+pub(crate) fn resume_from_sleep(drivers: &mut Drivers) {
+    if let Err(e) = restore_checkpoint(drivers) {
+        log_warn!("[rt] Failed to resume from sleep 0x{:08X}", u32::from(e));
+        report_fw_error_non_fatal(e.into());
+    }
+}
+
+/// Stop servicing the mailbox until the warm reset that ends the low-power
+/// state. Called once the response of PREPARE_FOR_SLEEP has been sent.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+pub(crate) fn enter_sleep(drivers: &mut Drivers) -> ! {
+    drivers.soc_ifc.disable_mbox_notif_interrupts();
+    report_boot_status(RtBootStatus::RtReadyForSleep.into());
+    loop {
+        drivers.idle_stats.sleep();
+    }
+}
+
+pub struct SuspendPrepareCmd;
+impl SuspendPrepareCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        save_checkpoint(drivers, SuspendCheckpoint::SUSPENDED)?;
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct ResumeNotifyCmd;
+impl ResumeNotifyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        if !drivers
+            .persistent_data
+            .get()
+            .suspend_checkpoint
+            .is_suspended()
+        {
+            return Err(CaliptraError::RUNTIME_NOT_SUSPENDED);
+        }
+
+        restore_checkpoint(drivers)?;
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct PrepareForSleepCmd;
+impl PrepareForSleepCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        // A queued job would be lost by the warm reset
+        if drivers.jobs.is_pending() {
+            return Err(CaliptraError::RUNTIME_SLEEP_JOB_PENDING);
+        }
+
+        save_checkpoint(drivers, SuspendCheckpoint::SLEEPING)?;
+        drivers.persistent_data.get_mut().seal();
+        drivers.is_sleeping = true;
+        Ok(MailboxResp::default())
+    }
+}
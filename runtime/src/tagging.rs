@@ -90,10 +90,15 @@ impl GetTaggedTciCmd {
         }
         let context = persistent_data.dpe.contexts[idx];
 
-        Ok(MailboxResp::GetTaggedTci(GetTaggedTciResp {
+        // TCIs of profiles with shorter digests fill the start of the fields
+        let mut resp = GetTaggedTciResp {
             hdr: MailboxRespHeader::default(),
-            tci_cumulative: context.tci.tci_cumulative.0,
-            tci_current: context.tci.tci_current.0,
-        }))
+            tci_cumulative: [0; 48],
+            tci_current: [0; 48],
+        };
+        let tci_size = context.tci.tci_current.0.len();
+        resp.tci_cumulative[..tci_size].copy_from_slice(&context.tci.tci_cumulative.0);
+        resp.tci_current[..tci_size].copy_from_slice(&context.tci.tci_current.0);
+        Ok(MailboxResp::GetTaggedTci(resp))
     }
 }
@@ -0,0 +1,91 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    timestamp.rs
+
+Abstract:
+
+    File contains the TIMESTAMP mailbox command, which binds a nonce to a
+    monotonic counter and the boot session in a signed token, so that SoC
+    components can prove the order of events without a real-time clock.
+
+--*/
+
+use crate::{monotonic_counter::increment_counter, Cwt, Drivers};
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{MailboxResp, TimestampReq, TimestampResp};
+use caliptra_drivers::{dv, CaliptraError, CaliptraResult};
+use zerocopy::FromBytes;
+
+/// Start a new boot session, whose random ID is held in the data vault
+/// until the next warm or cold reset. Update resets continue the session.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+pub(crate) fn start_boot_session(drivers: &mut Drivers) -> CaliptraResult<()> {
+    let id = drivers.trng.generate()?.0;
+    drivers.data_vault.write_lock::<dv::BootSessionId0>(&id[0]);
+    drivers.data_vault.write_lock::<dv::BootSessionId1>(&id[1]);
+    Ok(())
+}
+
+/// Read the ID of the current boot session
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+fn boot_session_id(drivers: &Drivers) -> [u8; 8] {
+    let mut id = [0u8; 8];
+    id[..4].copy_from_slice(
+        &drivers
+            .data_vault
+            .read::<dv::BootSessionId0>()
+            .to_be_bytes(),
+    );
+    id[4..].copy_from_slice(
+        &drivers
+            .data_vault
+            .read::<dv::BootSessionId1>()
+            .to_be_bytes(),
+    );
+    id
+}
+
+pub struct TimestampCmd;
+impl TimestampCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = TimestampReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // Every token consumes a counter value, so no two tokens of a
+        // counter carry the same value
+        let value = increment_counter(drivers, cmd.counter_id)?;
+        let ueid = drivers.soc_ifc.fuse_bank().ueid();
+        let session_id = boot_session_id(drivers);
+
+        let mut resp = TimestampResp::default();
+        let token_size = Cwt::sign(drivers, &mut resp.data, |claims| {
+            claims.write_map(5)?;
+            claims.write_int(TimestampResp::CLAIM_NONCE)?;
+            claims.write_bstr(&cmd.nonce)?;
+            claims.write_int(TimestampResp::CLAIM_UEID)?;
+            claims.write_bstr(&ueid)?;
+            claims.write_int(TimestampResp::CLAIM_BOOT_SESSION_ID)?;
+            claims.write_bstr(&session_id)?;
+            claims.write_int(TimestampResp::CLAIM_COUNTER_ID)?;
+            claims.write_uint(cmd.counter_id as u64)?;
+            claims.write_int(TimestampResp::CLAIM_COUNTER_VALUE)?;
+            claims.write_uint(value as u64)?;
+            Ok(())
+        })?;
+        resp.data_size = token_size as u32;
+
+        Ok(MailboxResp::Timestamp(resp))
+    }
+}
@@ -12,16 +12,53 @@ Abstract:
 
 --*/
 
-use crate::Drivers;
+use crate::{cancel, handoff::RtHandoff, stream_verify, Drivers, PrivilegedSocIfc};
 use caliptra_cfi_derive_git::cfi_mod_fn;
+use caliptra_common::mailbox_api::CommandId;
 use caliptra_drivers::{CaliptraError, CaliptraResult};
 
+/// Trigger the update reset that lets ROM verify and load the new image.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `staged_cmd` - Command being executed if the image was staged in the
+///                  mailbox by the runtime, `None` for FIRMWARE_LOAD
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
-pub(crate) fn handle_impactless_update(drivers: &mut Drivers) -> CaliptraResult<()> {
-    let cycles = drivers.soc_ifc.internal_fw_update_reset_wait_cycles();
-    for _ in 0..cycles {
-        drivers.soc_ifc.assert_fw_update_reset();
+pub(crate) fn handle_impactless_update(
+    drivers: &mut Drivers,
+    staged_cmd: Option<CommandId>,
+) -> CaliptraResult<()> {
+    cancel::checkpoint(&mut drivers.soc_ifc, &drivers.mbox)?;
+
+    // ROM needs the SHA accelerator to verify the image
+    stream_verify::abort(drivers);
+
+    // Start a journal entry; the updated runtime completes it after the
+    // update reset.
+    let old_svn = RtHandoff {
+        data_vault: &drivers.data_vault,
+        fht: &drivers.persistent_data.get().fht,
+    }
+    .rt_svn()?;
+    let timestamp = drivers.soc_ifc.generic_input_wires();
+    drivers
+        .persistent_data
+        .get_mut()
+        .fw_update_journal
+        .begin(old_svn, timestamp);
+    if let Some(cmd) = staged_cmd {
+        drivers
+            .persistent_data
+            .get_mut()
+            .fw_update_journal
+            .set_staged_cmd(cmd.into());
     }
 
+    // Let the updated runtime detect sections it cannot interpret
+    drivers.persistent_data.get_mut().seal();
+
+    PrivilegedSocIfc::assert_fw_update_reset(drivers)?;
+
     Err(CaliptraError::RUNTIME_UNEXPECTED_UPDATE_RETURN)
 }
@@ -0,0 +1,47 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    update_history.rs
+
+Abstract:
+
+    File contains GetUpdateHistory mailbox command.
+
+--*/
+
+use crate::Drivers;
+use caliptra_common::mailbox_api::{
+    FwUpdateHistoryEntry, GetUpdateHistoryResp, MailboxResp, MailboxRespHeader,
+};
+use caliptra_drivers::{CaliptraResult, FW_UPDATE_JOURNAL_MAX_COUNT};
+
+const _: () = assert!(FW_UPDATE_JOURNAL_MAX_COUNT == GetUpdateHistoryResp::MAX_ENTRIES);
+
+pub struct GetUpdateHistoryCmd;
+impl GetUpdateHistoryCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &Drivers) -> CaliptraResult<MailboxResp> {
+        let journal = &drivers.persistent_data.get().fw_update_journal;
+
+        let mut resp = GetUpdateHistoryResp {
+            hdr: MailboxRespHeader::default(),
+            total_updates: journal.total(),
+            ..Default::default()
+        };
+        for (dest, entry) in resp.entries.iter_mut().zip(journal.iter()) {
+            *dest = FwUpdateHistoryEntry {
+                old_svn: entry.old_svn,
+                new_svn: entry.new_svn,
+                runtime_sha384_digest: entry.digest,
+                timestamp_low: entry.timestamp as u32,
+                timestamp_high: (entry.timestamp >> 32) as u32,
+            };
+            resp.entry_count += 1;
+        }
+
+        Ok(MailboxResp::GetUpdateHistory(resp))
+    }
+}
@@ -12,7 +12,7 @@ Abstract:
 
 --*/
 
-use crate::Drivers;
+use crate::{CryptoBackend, Drivers, PrivilegedSocIfc};
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_common::mailbox_api::{EcdsaVerifyReq, LmsVerifyReq, MailboxResp};
 use caliptra_drivers::{
@@ -49,7 +49,9 @@ impl EcdsaVerifyCmd {
             s: Ecc384Scalar::from(cmd.signature_s),
         };
 
-        let success = drivers.ecc384.verify(&pubkey, &digest, &sig)?;
+        let success = drivers
+            .crypto_backend()
+            .ecc384_verify(&pubkey, &digest, &sig)?;
         if success != Ecc384Result::Success {
             return Err(CaliptraError::RUNTIME_ECDSA_VERIFY_FAILED);
         }
@@ -118,11 +120,13 @@ impl LmsVerifyCmd {
             return Err(CaliptraError::RUNTIME_LMS_VERIFY_INVALID_LMS_ALGORITHM);
         }
 
-        let success = drivers.lms.verify_lms_signature(
+        let wdt_pet = PrivilegedSocIfc::wdt_pet(drivers);
+        let success = drivers.lms.verify_lms_signature_with_wdt_pet(
             &mut drivers.sha256,
             &msg_digest,
             &lms_pub_key,
             &lms_sig,
+            wdt_pet,
         )?;
         if success != LmsResult::Success {
             return Err(CaliptraError::RUNTIME_LMS_VERIFY_FAILED);
@@ -0,0 +1,136 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    verify_object.rs
+
+Abstract:
+
+    File contains the SetObjectSchemas and VerifyObject mailbox commands.
+
+--*/
+
+use crate::Drivers;
+use arrayvec::ArrayVec;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert_eq_12_words, cfi_launder};
+use caliptra_common::mailbox_api::{
+    MailboxResp, MailboxRespHeader, SetObjectSchemasReq, VerifyObjectReq, VerifyObjectResp,
+};
+use caliptra_drivers::{
+    Array4x12, CaliptraError, CaliptraResult, Ecc384PubKey, Ecc384Scalar, Ecc384Signature,
+};
+use zerocopy::{FromBytes, IntoBytes};
+
+pub const MAX_OBJECT_SCHEMAS: usize = SetObjectSchemasReq::MAX_SCHEMAS;
+
+/// Digests of the object schemas approved by the owner
+pub type ObjectSchemas = ArrayVec<[u8; 48], MAX_OBJECT_SCHEMAS>;
+
+pub struct SetObjectSchemasCmd;
+impl SetObjectSchemasCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SetObjectSchemasReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        // The schemas are latched until the next reset so the set of
+        // approved formats cannot grow behind the owner's back
+        if drivers.object_schemas.is_some() {
+            return Err(CaliptraError::RUNTIME_OBJECT_SCHEMAS_ALREADY_SET);
+        }
+
+        let schemas = cmd
+            .schema_digests
+            .get(..cmd.schema_count as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        drivers.object_schemas = Some(schemas.iter().copied().collect());
+
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct VerifyObjectCmd;
+impl VerifyObjectCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        if cmd_args.len() > core::mem::size_of::<VerifyObjectReq>() {
+            return Err(CaliptraError::RUNTIME_INSUFFICIENT_MEMORY);
+        }
+        let mut cmd = VerifyObjectReq::default();
+        cmd.as_mut_bytes()[..cmd_args.len()].copy_from_slice(cmd_args);
+        let data = cmd
+            .data
+            .get(..cmd.data_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        let schemas = drivers
+            .object_schemas
+            .as_ref()
+            .ok_or(CaliptraError::RUNTIME_OBJECT_SCHEMAS_NOT_SET)?;
+        let verdict = if !schemas.contains(&cmd.schema_digest) {
+            VerifyObjectResp::OBJECT_SCHEMA_NOT_APPROVED
+        } else if Self::verify_signature(drivers, &cmd, data)? {
+            VerifyObjectResp::OBJECT_AUTHORIZED
+        } else {
+            VerifyObjectResp::OBJECT_SIGNATURE_INVALID
+        };
+
+        Ok(MailboxResp::VerifyObject(VerifyObjectResp {
+            hdr: MailboxRespHeader::default(),
+            verdict,
+        }))
+    }
+
+    /// Verify that the object is signed by the owner key of the running
+    /// firmware. The signature covers the schema digest followed by the
+    /// object, so an object cannot be replayed under another schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `cmd` - VerifyObject request
+    /// * `data` - Object carried by the request
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the signature is valid
+    fn verify_signature(
+        drivers: &mut Drivers,
+        cmd: &VerifyObjectReq,
+        data: &[u8],
+    ) -> CaliptraResult<bool> {
+        let mut digest = Array4x12::default();
+        let mut hasher = drivers.sha384.digest_init()?;
+        hasher.update(&cmd.schema_digest)?;
+        hasher.update(data)?;
+        hasher.finalize(&mut digest)?;
+
+        let owner_pub_key = &drivers
+            .persistent_data
+            .get()
+            .manifest1
+            .preamble
+            .owner_pub_keys
+            .ecc_pub_key;
+        let pub_key = Ecc384PubKey {
+            x: owner_pub_key.x.into(),
+            y: owner_pub_key.y.into(),
+        };
+        let sig = Ecc384Signature {
+            r: Ecc384Scalar::from(cmd.signature_r),
+            s: Ecc384Scalar::from(cmd.signature_s),
+        };
+
+        let verify_r = drivers.ecc384.verify_r(&pub_key, &digest, &sig)?;
+        if cfi_launder(verify_r) != sig.r {
+            return Ok(false);
+        }
+        cfi_assert_eq_12_words(&verify_r.0, &sig.r.0);
+        Ok(true)
+    }
+}
@@ -8,8 +8,8 @@ use caliptra_builder::{
 };
 use caliptra_common::{
     mailbox_api::{
-        CommandId, GetFmcAliasCertResp, GetRtAliasCertResp, InvokeDpeReq, InvokeDpeResp,
-        MailboxReq, MailboxReqHeader,
+        CommandId, GetFmcAliasCertResp, GetLdevCertResp, GetRtAliasCertResp, InvokeDpeReq,
+        InvokeDpeResp, MailboxReq, MailboxReqHeader,
     },
     memory_layout::{ROM_ORG, ROM_SIZE, ROM_STACK_ORG, ROM_STACK_SIZE, STACK_ORG, STACK_SIZE},
     FMC_ORG, FMC_SIZE, RUNTIME_ORG, RUNTIME_SIZE,
@@ -49,6 +49,9 @@ pub const TEST_DIGEST: [u8; 48] = [
 pub const DEFAULT_FMC_VERSION: u16 = 0xaaaa;
 pub const DEFAULT_APP_VERSION: u32 = 0xbbbbbbbb;
 
+/// Bytes at the end of each stack that the firmware must never reach
+const STACK_GUARD_BYTES: u32 = 64;
+
 #[derive(Default)]
 pub struct RuntimeTestArgs<'a> {
     pub test_fwid: Option<&'static FwId<'static>>,
@@ -77,22 +80,25 @@ pub fn run_rt_test_lms(args: RuntimeTestArgs, lms_verify: bool) -> DefaultHwMode
         ImageInfo::new(
             StackRange::new(ROM_STACK_ORG + ROM_STACK_SIZE, ROM_STACK_ORG),
             CodeRange::new(ROM_ORG, ROM_ORG + ROM_SIZE),
-        ),
+        )
+        .with_name("ROM"),
         ImageInfo::new(
             StackRange::new(STACK_ORG + STACK_SIZE, STACK_ORG),
             CodeRange::new(FMC_ORG, FMC_ORG + FMC_SIZE),
-        ),
+        )
+        .with_name("FMC"),
         ImageInfo::new(
             StackRange::new(STACK_ORG + STACK_SIZE, STACK_ORG),
             CodeRange::new(RUNTIME_ORG, RUNTIME_ORG + RUNTIME_SIZE),
-        ),
+        )
+        .with_name("RT"),
     ];
     let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
     let init_params = match args.init_params {
         Some(init_params) => init_params,
         None => InitParams {
             rom: &rom,
-            stack_info: Some(StackInfo::new(image_info)),
+            stack_info: Some(StackInfo::new(image_info).with_guard_bytes(STACK_GUARD_BYTES)),
             ..Default::default()
         },
     };
@@ -298,6 +304,20 @@ pub fn get_certs<R: Request>(model: &mut DefaultHwModel) -> R::Resp {
     resp
 }
 
+pub fn get_ldev_cert(model: &mut DefaultHwModel) -> GetLdevCertResp {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_LDEV_CERT), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_LDEV_CERT), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    assert!(resp.len() <= std::mem::size_of::<GetLdevCertResp>());
+    let mut ldev_resp = GetLdevCertResp::default();
+    ldev_resp.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
+    ldev_resp
+}
+
 pub fn get_fmc_alias_cert(model: &mut DefaultHwModel) -> GetFmcAliasCertResp {
     let payload = MailboxReqHeader {
         chksum: caliptra_common::checksum::calc_checksum(
@@ -2,25 +2,55 @@
 
 mod common;
 mod test_authorize_and_stash;
+mod test_batch;
 mod test_boot;
+mod test_boot_profile;
+mod test_cert_dist_points;
+mod test_cert_policy;
 mod test_certify_key_extended;
 mod test_certs;
+mod test_cmd_policy;
+mod test_configure_wdt;
+mod test_crash_dump;
 mod test_disable;
 mod test_ecdsa;
+mod test_entropy_health;
 mod test_fips;
+mod test_get_fht;
 mod test_get_fmc_alias_csr;
 mod test_get_idev_csr;
+mod test_get_rt_alias_csr;
+mod test_increment_fuse_svn;
 mod test_info;
+mod test_inject_error;
 mod test_invoke_dpe;
+mod test_jobs;
+mod test_kv_status;
 mod test_lms;
+mod test_log_level;
 mod test_mailbox;
+mod test_mailbox_auth;
+mod test_mbox_fuzz;
+mod test_mctp;
+mod test_monotonic_counter;
+mod test_owner_key_rotation;
 mod test_panic_missing;
 mod test_pauser_privilege_levels;
 mod test_pcr;
 mod test_populate_idev;
+mod test_raw_entropy;
+mod test_reseed_field_entropy;
+mod test_revoke_locality;
+mod test_sealed_key;
 mod test_set_auth_manifest;
+mod test_set_measurement_manifest;
 mod test_sign_with_export_ecdsa;
+mod test_spdm;
 mod test_stash_measurement;
+mod test_stream_verify;
+mod test_suspend;
 mod test_tagging;
+mod test_timestamp;
 mod test_update_reset;
+mod test_verify_object;
 mod test_warm_reset;
@@ -0,0 +1,136 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    BatchReq, BatchRespEntryHdr, CommandId, MailboxReq, MailboxReqHeader, MailboxRespHeaderVarSize,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::{FromBytes, IntoBytes};
+
+/// Request of a command whose request only holds a header
+fn header_only_req(cmd: CommandId) -> MailboxReqHeader {
+    MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(cmd), &[]),
+    }
+}
+
+fn batch(
+    model: &mut DefaultHwModel,
+    cmds: &[(CommandId, &[u8])],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut req = BatchReq::default();
+    for (cmd, cmd_req) in cmds {
+        req.push(CommandId(cmd.0), cmd_req).unwrap();
+    }
+    let mut cmd = MailboxReq::Batch(req);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::BATCH), cmd.as_bytes().unwrap())
+}
+
+/// Split a BATCH response into its entries
+fn batch_entries(resp: &[u8]) -> Vec<(BatchRespEntryHdr, Vec<u8>)> {
+    let (hdr, mut data) = MailboxRespHeaderVarSize::read_from_prefix(resp).unwrap();
+    assert_eq!(data.len(), hdr.data_len as usize);
+
+    let mut entries = vec![];
+    while !data.is_empty() {
+        let (entry_hdr, rest) = BatchRespEntryHdr::read_from_prefix(data).unwrap();
+        let entry_len = entry_hdr.data_size as usize;
+        let entry_resp = rest[..entry_len].to_vec();
+        data = &rest[(entry_len + 3) & !3..];
+        entries.push((entry_hdr, entry_resp));
+    }
+    entries
+}
+
+#[test]
+fn test_batch_attestation_cmds() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let cmds = [
+        CommandId::VERSION,
+        CommandId::FW_INFO,
+        CommandId::GET_LDEV_CERT,
+        CommandId::GET_RT_ALIAS_CERT,
+    ];
+    let reqs: Vec<_> = cmds
+        .iter()
+        .map(|cmd| header_only_req(CommandId(cmd.0)))
+        .collect();
+    let batch_cmds: Vec<_> = cmds
+        .iter()
+        .zip(&reqs)
+        .map(|(cmd, req)| (CommandId(cmd.0), req.as_bytes()))
+        .collect();
+
+    let resp = batch(&mut model, &batch_cmds)
+        .unwrap()
+        .expect("We expected a response");
+    let entries = batch_entries(&resp);
+    assert_eq!(entries.len(), cmds.len());
+
+    // Each response matches the one of the command called directly
+    for ((cmd, req), (entry_hdr, entry_resp)) in cmds.iter().zip(&reqs).zip(&entries) {
+        assert_eq!(entry_hdr.cmd, cmd.0);
+        assert_eq!(entry_hdr.error, 0);
+        let direct_resp = model
+            .mailbox_execute(cmd.0, req.as_bytes())
+            .unwrap()
+            .expect("We expected a response");
+        assert_eq!(entry_resp, &direct_resp);
+    }
+}
+
+#[test]
+fn test_batch_cmd_failure() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // CAPABILITIES fails without a request header, and the batch carries on
+    let fw_info_req = header_only_req(CommandId::FW_INFO);
+    let resp = batch(
+        &mut model,
+        &[
+            (CommandId::CAPABILITIES, &[]),
+            (CommandId::FW_INFO, fw_info_req.as_bytes()),
+        ],
+    )
+    .unwrap()
+    .expect("We expected a response");
+    let entries = batch_entries(&resp);
+    assert_eq!(entries.len(), 2);
+
+    let (entry_hdr, entry_resp) = &entries[0];
+    assert_eq!(entry_hdr.cmd, u32::from(CommandId::CAPABILITIES));
+    assert_eq!(
+        entry_hdr.error,
+        u32::from(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)
+    );
+    assert!(entry_resp.is_empty());
+
+    let (entry_hdr, entry_resp) = &entries[1];
+    assert_eq!(entry_hdr.cmd, u32::from(CommandId::FW_INFO));
+    assert_eq!(entry_hdr.error, 0);
+    assert!(!entry_resp.is_empty());
+}
+
+#[test]
+fn test_batch_cmd_not_supported() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let version_req = header_only_req(CommandId::VERSION);
+    let disable_req = header_only_req(CommandId::DISABLE_ATTESTATION);
+    let resp = batch(
+        &mut model,
+        &[
+            (CommandId::VERSION, version_req.as_bytes()),
+            (CommandId::DISABLE_ATTESTATION, disable_req.as_bytes()),
+        ],
+    )
+    .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_BATCH_CMD_NOT_SUPPORTED,
+        resp,
+    );
+}
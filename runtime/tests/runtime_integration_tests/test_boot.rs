@@ -88,7 +88,7 @@ fn test_update() {
     // via the mailbox.
     let mut model = run_rt_test(RuntimeTestArgs::default());
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     model
         .mailbox_execute(u32::from(CommandId::FIRMWARE_LOAD), &image)
@@ -130,7 +130,7 @@ fn test_stress_update() {
     let stress_num = if cfg!(feature = "slow_tests") { 500 } else { 1 };
     let mut image_select = 0;
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     for _ in 0..stress_num {
         if image_select == 0 {
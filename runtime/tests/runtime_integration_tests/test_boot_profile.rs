@@ -0,0 +1,57 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{CommandId, GetBootProfileResp, MailboxReqHeader};
+use caliptra_hw_model::{DefaultHwModel, Fuses, HwModel};
+use zerocopy::{FromBytes, IntoBytes};
+
+fn get_boot_profile(model: &mut DefaultHwModel) -> GetBootProfileResp {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::GET_BOOT_PROFILE),
+            &[],
+        ),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_BOOT_PROFILE), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    GetBootProfileResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_boot_profile_cold_reset() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let profile = get_boot_profile(&mut model);
+    let milestones = [
+        profile.rom_entry,
+        profile.rom_kats_complete,
+        profile.rom_fw_received,
+        profile.rom_fw_verified,
+        profile.rom_exit,
+        profile.fmc_entry,
+        profile.fmc_exit,
+        profile.rt_entry,
+        profile.rt_ready,
+    ];
+    assert!(milestones.iter().all(|&cycles| cycles != 0));
+    assert!(milestones.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_boot_profile_warm_reset() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.warm_reset_flow(&Fuses::default());
+    model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+
+    // No image is loaded on a warm reset
+    let profile = get_boot_profile(&mut model);
+    assert_eq!(profile.rom_fw_received, 0);
+    assert_eq!(profile.rom_fw_verified, 0);
+    assert!(profile.rom_entry < profile.rom_exit);
+    assert!(profile.rom_exit < profile.fmc_entry);
+    assert!(profile.rt_entry < profile.rt_ready);
+}
@@ -0,0 +1,123 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{
+    assert_error, get_fmc_alias_cert, get_rt_alias_cert, run_rt_test, RuntimeTestArgs,
+};
+use crate::test_update_reset::update_fw;
+use caliptra_builder::{firmware::APP_WITH_UART, ImageOptions};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxRespHeader, SetCertDistPointsReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use openssl::{nid::Nid, x509::X509};
+use zerocopy::FromBytes;
+
+const CRL_URI: &str = "http://crl.example.com/caliptra-fmc-alias.crl";
+const AIA_URI: &str = "http://pki.example.com/caliptra-fmc-alias.der";
+
+fn set_cert_dist_points(
+    model: &mut DefaultHwModel,
+    crl_uri: &[u8],
+    aia_uri: &[u8],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut req = SetCertDistPointsReq {
+        crl_uri_size: crl_uri.len() as u32,
+        aia_uri_size: aia_uri.len() as u32,
+        ..Default::default()
+    };
+    req.crl_uri[..crl_uri.len()].copy_from_slice(crl_uri);
+    req.aia_uri[..aia_uri.len()].copy_from_slice(aia_uri);
+
+    let mut cmd = MailboxReq::SetCertDistPoints(req);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::SET_CERT_DIST_POINTS),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+fn rt_alias_cert(model: &mut DefaultHwModel) -> X509 {
+    let resp = get_rt_alias_cert(model);
+    X509::from_der(&resp.data[..resp.data_size as usize]).unwrap()
+}
+
+#[test]
+fn test_cert_dist_points() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = set_cert_dist_points(&mut model, CRL_URI.as_bytes(), AIA_URI.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    // The RT Alias cert is only regenerated by the FMC on the next update reset
+    let rt_cert = rt_alias_cert(&mut model);
+    assert!(rt_cert.crl_distribution_points().is_none());
+    assert!(rt_cert.authority_info().is_none());
+
+    update_fw(&mut model, &APP_WITH_UART, ImageOptions::default());
+
+    let rt_cert = rt_alias_cert(&mut model);
+    let points = rt_cert.crl_distribution_points().unwrap();
+    assert_eq!(points.len(), 1);
+    let names = points
+        .get(0)
+        .unwrap()
+        .distpoint()
+        .unwrap()
+        .fullname()
+        .unwrap();
+    assert_eq!(names.len(), 1);
+    assert_eq!(names.get(0).unwrap().uri(), Some(CRL_URI));
+
+    let access = rt_cert.authority_info().unwrap();
+    assert_eq!(access.len(), 1);
+    let access = access.get(0).unwrap();
+    assert_eq!(access.method().nid(), Nid::AD_CA_ISSUERS);
+    assert_eq!(access.location().uri(), Some(AIA_URI));
+
+    // The extended cert is still signed by the FMC Alias key
+    let fmc_resp = get_fmc_alias_cert(&mut model);
+    let fmc_cert = X509::from_der(&fmc_resp.data[..fmc_resp.data_size as usize]).unwrap();
+    assert!(rt_cert.verify(&fmc_cert.public_key().unwrap()).unwrap());
+
+    // Clearing the URIs removes the extensions on the next update reset
+    set_cert_dist_points(&mut model, &[], &[]).unwrap();
+    update_fw(&mut model, &APP_WITH_UART, ImageOptions::default());
+    let rt_cert = rt_alias_cert(&mut model);
+    assert!(rt_cert.crl_distribution_points().is_none());
+    assert!(rt_cert.authority_info().is_none());
+}
+
+#[test]
+fn test_cert_dist_points_invalid_uri() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = set_cert_dist_points(&mut model, b"http://example.com/ca crl", &[]);
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_CERT_DIST_POINT_INVALID_URI,
+        resp.unwrap_err(),
+    );
+
+    let mut req = SetCertDistPointsReq {
+        aia_uri_size: SetCertDistPointsReq::MAX_URI_SIZE as u32 + 1,
+        ..Default::default()
+    };
+    let mut cmd = MailboxReq::SetCertDistPoints(req);
+    cmd.populate_chksum().unwrap();
+    let resp = model.mailbox_execute(
+        u32::from(CommandId::SET_CERT_DIST_POINTS),
+        cmd.as_bytes().unwrap(),
+    );
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_CERT_DIST_POINT_INVALID_URI,
+        resp.unwrap_err(),
+    );
+}
@@ -0,0 +1,129 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{
+    assert_error, execute_dpe_cmd, run_rt_test, DpeResult, RuntimeTestArgs, TEST_LABEL,
+};
+use caliptra_common::mailbox_api::{
+    CertPolicyFlags, CommandId, MailboxReq, MailboxReqHeader, MailboxRespHeader, SetCertPolicyReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::HwModel;
+use dpe::{
+    commands::{CertifyKeyCmd, CertifyKeyFlags, Command},
+    context::ContextHandle,
+    response::Response,
+};
+use openssl::{asn1::Asn1Time, x509::X509};
+use zerocopy::FromBytes;
+
+const MAX_NOT_AFTER: [u8; 15] = *b"20300101000000Z";
+
+fn set_cert_policy(
+    model: &mut caliptra_hw_model::DefaultHwModel,
+    flags: CertPolicyFlags,
+    max_not_after: [u8; 15],
+) -> Result<Option<Vec<u8>>, caliptra_hw_model::ModelError> {
+    let mut cmd = MailboxReq::SetCertPolicy(SetCertPolicyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        flags,
+        max_not_after,
+        reserved: 0,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::SET_CERT_POLICY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+fn certify_key_cmd(format: u32) -> CertifyKeyCmd {
+    CertifyKeyCmd {
+        handle: ContextHandle::default(),
+        label: TEST_LABEL,
+        flags: CertifyKeyFlags::empty(),
+        format,
+    }
+}
+
+#[test]
+fn test_cert_policy_deny_x509() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = set_cert_policy(&mut model, CertPolicyFlags::DENY_X509, [0; 15])
+        .unwrap()
+        .expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::CertifyKey(&certify_key_cmd(CertifyKeyCmd::FORMAT_X509)),
+        DpeResult::MboxCmdFailure(CaliptraError::RUNTIME_CERT_POLICY_VIOLATION),
+    );
+    assert!(resp.is_none());
+
+    // CSRs are still allowed
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::CertifyKey(&certify_key_cmd(CertifyKeyCmd::FORMAT_CSR)),
+        DpeResult::Success,
+    );
+    let Some(Response::CertifyKey(_)) = resp else {
+        panic!("Wrong response type!");
+    };
+}
+
+#[test]
+fn test_cert_policy_cap_validity() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    set_cert_policy(&mut model, CertPolicyFlags::CAP_VALIDITY, MAX_NOT_AFTER)
+        .unwrap()
+        .expect("We expected a response");
+
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::CertifyKey(&certify_key_cmd(CertifyKeyCmd::FORMAT_X509)),
+        DpeResult::Success,
+    );
+    let Some(Response::CertifyKey(certify_key_resp)) = resp else {
+        panic!("Wrong response type!");
+    };
+
+    let cert =
+        X509::from_der(&certify_key_resp.cert[..certify_key_resp.cert_size as usize]).unwrap();
+    let max_not_after: Asn1Time = Asn1Time::from_str("20300101000000Z").unwrap();
+    assert!(cert.not_after() == max_not_after);
+}
+
+#[test]
+fn test_cert_policy_already_set() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    set_cert_policy(&mut model, CertPolicyFlags::DENY_CSR, [0; 15])
+        .unwrap()
+        .expect("We expected a response");
+
+    // The policy cannot be relaxed once set
+    let resp = set_cert_policy(&mut model, CertPolicyFlags::empty(), [0; 15]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_CERT_POLICY_ALREADY_SET,
+        resp,
+    );
+}
+
+#[test]
+fn test_cert_policy_invalid_max_not_after() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = set_cert_policy(&mut model, CertPolicyFlags::CAP_VALIDITY, [0; 15]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+        resp,
+    );
+}
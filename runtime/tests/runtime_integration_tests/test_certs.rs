@@ -1,21 +1,24 @@
 // Licensed under the Apache-2.0 license
 
 use crate::common::{
-    execute_dpe_cmd, generate_test_x509_cert, get_fmc_alias_cert, get_rt_alias_cert, run_rt_test,
-    DpeResult, RuntimeTestArgs, TEST_LABEL,
+    execute_dpe_cmd, generate_test_x509_cert, get_fmc_alias_cert, get_ldev_cert, get_rt_alias_cert,
+    run_rt_test, DpeResult, RuntimeTestArgs, TEST_LABEL,
 };
+use caliptra_api::evidence::collect_evidence;
 use caliptra_builder::firmware::{APP_WITH_UART, FMC_WITH_UART};
 use caliptra_builder::ImageOptions;
 use caliptra_common::mailbox_api::{
-    CommandId, GetIdevCertReq, GetIdevCertResp, GetIdevInfoResp, GetLdevCertResp,
-    GetRtAliasCertResp, MailboxReq, MailboxReqHeader, StashMeasurementReq,
+    CommandId, FwInfoV1Resp, GetIdevCertReq, GetIdevCertResp, GetIdevInfoResp, GetRtAliasCertResp,
+    MailboxReq, MailboxReqHeader, StashMeasurementReq,
 };
 use caliptra_error::CaliptraError;
 use caliptra_hw_model::{BootParams, DefaultHwModel, HwModel, InitParams};
+use caliptra_verifier::{verify, Component, Failure, Policy};
 use dpe::{
     commands::{CertifyKeyCmd, CertifyKeyFlags, Command, DeriveContextCmd, DeriveContextFlags},
     context::ContextHandle,
     response::{CertifyKeyResp, Response},
+    DPE_PROFILE,
 };
 use openssl::{
     asn1::Asn1Time,
@@ -160,20 +163,6 @@ fn test_idev_id_cert_size_too_big() {
     );
 }
 
-fn get_ldev_cert(model: &mut DefaultHwModel) -> GetLdevCertResp {
-    let payload = MailboxReqHeader {
-        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_LDEV_CERT), &[]),
-    };
-    let resp = model
-        .mailbox_execute(u32::from(CommandId::GET_LDEV_CERT), payload.as_bytes())
-        .unwrap()
-        .unwrap();
-    assert!(resp.len() <= std::mem::size_of::<GetLdevCertResp>());
-    let mut ldev_resp = GetLdevCertResp::default();
-    ldev_resp.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
-    ldev_resp
-}
-
 #[test]
 fn test_ldev_cert() {
     let mut model = run_rt_test(RuntimeTestArgs::default());
@@ -473,3 +462,120 @@ pub fn test_all_measurement_apis() {
     assert_eq!(rom_stash_dpe_cert, rt_stash_dpe_cert);
     assert_eq!(rom_stash_dpe_cert, derive_context_dpe_cert);
 }
+
+#[test]
+fn test_collect_evidence() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let evidence = collect_evidence(&mut model, &TEST_LABEL).unwrap();
+    assert_eq!(evidence.nonce, TEST_LABEL);
+    assert_eq!(evidence.dpe_profile, DPE_PROFILE as u32);
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    let fw_info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(evidence.fw.runtime_svn, fw_info.runtime_svn);
+    assert_eq!(
+        evidence.fw.runtime_sha384_digest,
+        fw_info.runtime_sha384_digest
+    );
+
+    // The DPE leaf key depends only on the label
+    let certify_key_resp = get_dpe_leaf_cert(&mut model);
+    assert_eq!(evidence.dpe_pub_key_x, certify_key_resp.derived_pubkey_x);
+    assert_eq!(evidence.dpe_pub_key_y, certify_key_resp.derived_pubkey_y);
+
+    let ldev_cert = X509::from_der(&evidence.ldev_cert).unwrap();
+    let fmc_cert = X509::from_der(&evidence.fmc_alias_cert).unwrap();
+    let rt_cert = X509::from_der(&evidence.rt_alias_cert).unwrap();
+    let dpe_leaf_cert = X509::from_der(&evidence.dpe_cert).unwrap();
+
+    // Verify the chain from the LDevID cert to the DPE leaf cert
+    let mut roots_bldr = X509StoreBuilder::new().unwrap();
+    roots_bldr.add_cert(ldev_cert).unwrap();
+    roots_bldr
+        .set_flags(X509VerifyFlags::X509_STRICT | X509VerifyFlags::PARTIAL_CHAIN)
+        .unwrap();
+    let roots = roots_bldr.build();
+    let mut cert_store = X509StoreContext::new().unwrap();
+    let mut chain = Stack::new().unwrap();
+    chain.push(fmc_cert).unwrap();
+    chain.push(rt_cert).unwrap();
+    cert_store
+        .init(&roots, &dpe_leaf_cert, &chain, |c| {
+            let success = c.verify_cert().unwrap();
+            assert_eq!(c.error(), X509VerifyResult::OK);
+            assert!(success);
+
+            Ok(())
+        })
+        .unwrap();
+
+    // A new nonce certifies a new key
+    let mut nonce = TEST_LABEL;
+    nonce[0] ^= 1;
+    let fresh = collect_evidence(&mut model, &nonce).unwrap();
+    assert_eq!(fresh.ldev_cert, evidence.ldev_cert);
+    assert_ne!(fresh.dpe_pub_key_x, evidence.dpe_pub_key_x);
+}
+
+#[test]
+fn test_verify_evidence() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let evidence = collect_evidence(&mut model, &TEST_LABEL).unwrap();
+    let policy = Policy {
+        trust_anchors: vec![evidence.ldev_cert.clone()],
+        nonce: TEST_LABEL.to_vec(),
+        ..Default::default()
+    };
+
+    let verdict = verify(&evidence, &policy);
+    assert_eq!(verdict.failures, vec![]);
+    assert!(verdict.is_trusted());
+    assert_eq!(verdict.tcb.runtime_svn, Some(evidence.fw.runtime_svn));
+    assert!(verdict.tcb.fmc_digest.is_some());
+
+    // A stale nonce is rejected
+    let mut stale = policy.clone();
+    stale.nonce[0] ^= 1;
+    assert_eq!(
+        verify(&evidence, &stale).failures,
+        vec![Failure::NonceMismatch]
+    );
+
+    // So is firmware older than the policy allows
+    let mut newer = policy.clone();
+    newer.min_runtime_svn = evidence.fw.runtime_svn + 1;
+    assert_eq!(
+        verify(&evidence, &newer).failures,
+        vec![Failure::SvnBelowMinimum {
+            component: Component::Runtime,
+            svn: evidence.fw.runtime_svn,
+            min: evidence.fw.runtime_svn + 1,
+        }]
+    );
+
+    // And a PCR the quote does not cover
+    let mut tampered = evidence.clone();
+    tampered.pcr_quote.pcrs[0][0] ^= 1;
+    assert_eq!(
+        verify(&tampered, &policy).failures,
+        vec![Failure::QuoteDigestMismatch]
+    );
+
+    // Without a trust anchor the chain does not validate
+    let untrusted = Policy {
+        trust_anchors: vec![],
+        ..policy
+    };
+    assert!(matches!(
+        verify(&evidence, &untrusted).failures.as_slice(),
+        [Failure::CertChain(_)]
+    ));
+}
@@ -0,0 +1,113 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_builder::ImageOptions;
+use caliptra_common::mailbox_api::{
+    CertPolicyFlags, CommandId, MailboxReq, MailboxReqHeader, MailboxRespHeader, RevokeLocalityReq,
+    SetCertPolicyReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_image_fake_keys::OWNER_CONFIG;
+use caliptra_image_gen::ImageGeneratorOwnerConfig;
+use zerocopy::{FromBytes, IntoBytes};
+
+/// PAUSER of the mailbox caller in the test models
+const CALLER_PAUSER: u32 = 1;
+
+fn run_with_pausers(pl0_pauser: u32, owner_cmd_pauser: Option<u32>) -> DefaultHwModel {
+    let mut image_opts = ImageOptions {
+        owner_config: Some(ImageGeneratorOwnerConfig {
+            cmd_pauser: owner_cmd_pauser,
+            ..OWNER_CONFIG
+        }),
+        ..Default::default()
+    };
+    image_opts.vendor_config.pl0_pauser = Some(pl0_pauser);
+
+    run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_opts),
+        ..Default::default()
+    })
+}
+
+fn set_cert_policy(model: &mut DefaultHwModel) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::SetCertPolicy(SetCertPolicyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        flags: CertPolicyFlags::DENY_X509,
+        max_not_after: [0; 15],
+        reserved: 0,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::SET_CERT_POLICY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+fn revoke_locality(
+    model: &mut DefaultHwModel,
+    locality: u32,
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::RevokeLocality(RevokeLocalityReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        locality,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::REVOKE_LOCALITY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+#[test]
+fn test_owner_cmd_pauser_denies_pl0() {
+    let mut model = run_with_pausers(CALLER_PAUSER, Some(CALLER_PAUSER + 1));
+
+    // The owner designated another PAUSER, so PL0 is not enough
+    let resp = set_cert_policy(&mut model).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_CMD_NOT_AUTHORIZED, resp);
+
+    // Commands that are not owner management commands are unaffected
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+}
+
+#[test]
+fn test_owner_cmd_pauser_allows_pl1() {
+    let mut model = run_with_pausers(CALLER_PAUSER + 1, Some(CALLER_PAUSER));
+
+    let resp = set_cert_policy(&mut model)
+        .unwrap()
+        .expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    // The designation only covers the owner management commands
+    let resp = revoke_locality(&mut model, CALLER_PAUSER + 2).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL,
+        resp,
+    );
+}
+
+#[test]
+fn test_owner_cmds_default_to_pl0() {
+    let mut model = run_with_pausers(CALLER_PAUSER + 1, None);
+
+    let resp = set_cert_policy(&mut model).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL,
+        resp,
+    );
+}
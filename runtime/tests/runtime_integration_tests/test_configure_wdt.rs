@@ -0,0 +1,61 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, ConfigureWdtReq, MailboxReq, MailboxReqHeader, MailboxRespHeader,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::{FromBytes, IntoBytes};
+
+const DEFAULT_WDT_TIMEOUT_CYCLES: u64 = 20_000_000;
+
+fn configure_wdt(
+    model: &mut DefaultHwModel,
+    timeout_cycles: u64,
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::ConfigureWdt(ConfigureWdtReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        timeout_cycles_low: timeout_cycles as u32,
+        timeout_cycles_high: (timeout_cycles >> 32) as u32,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::CONFIGURE_WDT), cmd.as_bytes().unwrap())
+}
+
+fn assert_success(resp: Result<Option<Vec<u8>>, ModelError>) {
+    let resp = resp.unwrap().expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+}
+
+#[test]
+fn test_configure_wdt() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_success(configure_wdt(&mut model, 2 * DEFAULT_WDT_TIMEOUT_CYCLES));
+    assert_success(configure_wdt(&mut model, 1 << 40));
+
+    // Commands keep running with the new timeout
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_LDEV_CERT), &[]),
+    };
+    model
+        .mailbox_execute(u32::from(CommandId::GET_LDEV_CERT), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+
+    // 0 restores the default
+    assert_success(configure_wdt(&mut model, 0));
+}
+
+#[test]
+fn test_configure_wdt_timeout_too_short() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = configure_wdt(&mut model, DEFAULT_WDT_TIMEOUT_CYCLES - 1).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_WDT_TIMEOUT_INVALID, resp);
+}
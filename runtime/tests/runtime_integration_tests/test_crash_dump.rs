@@ -0,0 +1,84 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    CommandId, GetCrashDumpResp, InjectErrorReq, InjectErrorType, MailboxReq, MailboxReqHeader,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, Fuses, HwModel};
+use zerocopy::{FromBytes, IntoBytes};
+
+/// Trigger a fatal error of `error_type`, then reset Caliptra back to the
+/// runtime
+fn crash_and_reboot(model: &mut DefaultHwModel, error_type: InjectErrorType) {
+    let mut cmd = MailboxReq::InjectError(InjectErrorReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        error_type: error_type as u32,
+    });
+    cmd.populate_chksum().unwrap();
+    model
+        .start_mailbox_execute(u32::from(CommandId::INJECT_ERROR), cmd.as_bytes().unwrap())
+        .unwrap();
+    model.step_until(|m| m.soc_ifc().cptra_fw_error_fatal().read() != 0);
+
+    model.warm_reset_flow(&Fuses::default());
+    model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+}
+
+fn get_crash_dump(model: &mut DefaultHwModel) -> GetCrashDumpResp {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_CRASH_DUMP), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_CRASH_DUMP), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    GetCrashDumpResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_crash_dump_empty() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_eq!(get_crash_dump(&mut model).valid, 0);
+}
+
+#[test]
+fn test_crash_dump_fatal_error() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    crash_and_reboot(&mut model, InjectErrorType::Fatal);
+
+    let dump = get_crash_dump(&mut model);
+    assert_eq!(dump.valid, 1);
+    assert_eq!(
+        dump.error_code,
+        u32::from(CaliptraError::RUNTIME_INJECTED_FATAL_ERROR)
+    );
+    assert_eq!(dump.last_cmd, u32::from(CommandId::INJECT_ERROR));
+    // Marker present, table valid, RT CDI and alias key handles valid
+    assert_eq!(dump.fht_validity, 0xf);
+    assert!(dump.stack_high_water > 0);
+    assert_eq!(dump.has_trap, 0);
+
+    // The record is consumed by the first read
+    assert_eq!(get_crash_dump(&mut model).valid, 0);
+}
+
+#[test]
+fn test_crash_dump_nmi() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    crash_and_reboot(&mut model, InjectErrorType::Nmi);
+
+    let dump = get_crash_dump(&mut model);
+    assert_eq!(dump.valid, 1);
+    assert_eq!(
+        dump.error_code,
+        u32::from(CaliptraError::RUNTIME_GLOBAL_NMI)
+    );
+    assert_eq!(dump.has_trap, 1);
+    assert_ne!(dump.mepc, 0);
+    assert_ne!(dump.sp, 0);
+}
@@ -1,11 +1,15 @@
 // Licensed under the Apache-2.0 license
 
+use caliptra_api::{evidence::collect_evidence, CaliptraApiError};
 use caliptra_builder::{
     firmware::{APP_WITH_UART, FMC_WITH_UART},
     ImageOptions,
 };
-use caliptra_common::mailbox_api::{CommandId, FwInfoResp, MailboxReqHeader, MailboxRespHeader};
-use caliptra_hw_model::HwModel;
+use caliptra_common::mailbox_api::{CommandId, FwInfoV1Resp, MailboxReqHeader, MailboxRespHeader};
+use caliptra_hw_model::{BootParams, HwModel, InitParams};
+use caliptra_image_crypto::OsslCrypto as Crypto;
+use caliptra_image_fake_keys::OWNER_CONFIG;
+use caliptra_image_gen::{ImageGenerator, ImageGeneratorOwnerConfig};
 use dpe::{
     commands::{CertifyKeyCmd, CertifyKeyFlags, Command, SignCmd, SignFlags},
     context::ContextHandle,
@@ -21,8 +25,8 @@ use openssl::{
 use zerocopy::{FromBytes, IntoBytes};
 
 use crate::common::{
-    execute_dpe_cmd, get_rt_alias_cert, run_rt_test, DpeResult, RuntimeTestArgs, TEST_DIGEST,
-    TEST_LABEL,
+    execute_dpe_cmd, get_fmc_alias_cert, get_ldev_cert, get_rt_alias_cert, run_rt_test, DpeResult,
+    RuntimeTestArgs, TEST_DIGEST, TEST_LABEL,
 };
 
 #[test]
@@ -93,6 +97,12 @@ fn test_disable_attestation_cmd() {
     .unwrap();
     // check that signature is unable to be verified by the pub key
     assert!(!sig.verify(&TEST_DIGEST, &ecc_pub_key).unwrap());
+
+    // evidence is no longer collected
+    assert_eq!(
+        collect_evidence(&mut model, &TEST_LABEL),
+        Err(CaliptraApiError::AttestationDisabled)
+    );
 }
 
 #[test]
@@ -140,7 +150,7 @@ fn test_attestation_disabled_flag_after_update_reset() {
         .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
         .unwrap()
         .unwrap();
-    let info = FwInfoResp::read_from_bytes(resp.as_slice()).unwrap();
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
     assert_eq!(info.attestation_disabled, 1);
 
     // test that attestation is really disabled by checking that
@@ -169,3 +179,57 @@ fn test_attestation_disabled_flag_after_update_reset() {
         .verify(&rt_cert.public_key().unwrap())
         .unwrap());
 }
+
+#[test]
+fn test_attestation_disabled_for_break_glass_image() {
+    let mut image_opts = ImageOptions::default();
+    image_opts.vendor_config.pl0_pauser = Some(0x1);
+    image_opts.owner_config = Some(ImageGeneratorOwnerConfig {
+        break_glass: true,
+        ..OWNER_CONFIG
+    });
+
+    // Cannot use run_rt_test since the owner key must be in the fuses
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    let image =
+        caliptra_builder::build_and_sign_image(&FMC_WITH_UART, &APP_WITH_UART, image_opts).unwrap();
+
+    let owner_pub_key_hash = ImageGenerator::new(Crypto::default())
+        .owner_pubkey_digest(&image.manifest.preamble)
+        .unwrap();
+
+    let mut model = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            ..Default::default()
+        },
+        BootParams {
+            fw_image: Some(&image.to_bytes().unwrap()),
+            fuses: caliptra_hw_model::Fuses {
+                owner_pk_hash: owner_pub_key_hash,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // check attestation disabled via FW_INFO
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(info.attestation_disabled, 1);
+
+    // ROM keeps every key the image can reach out of the device chain, so
+    // firmware that does not disable attestation cannot attest either
+    let ldev_resp = get_ldev_cert(&mut model);
+    let ldev_cert: X509 = X509::from_der(&ldev_resp.data[..ldev_resp.data_size as usize]).unwrap();
+    let fmc_resp = get_fmc_alias_cert(&mut model);
+    let fmc_cert: X509 = X509::from_der(&fmc_resp.data[..fmc_resp.data_size as usize]).unwrap();
+    assert!(!fmc_cert.verify(&ldev_cert.public_key().unwrap()).unwrap());
+}
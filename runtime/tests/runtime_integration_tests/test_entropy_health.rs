@@ -0,0 +1,84 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    GetEntropyHealthReq, GetEntropyHealthResp, MailboxReq, MailboxReqHeader,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, InitParams, ModelError, TrngMode};
+use zerocopy::FromBytes;
+
+fn get_entropy_health(
+    model: &mut DefaultHwModel,
+    flags: u32,
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::GetEntropyHealth(GetEntropyHealthReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        flags,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(cmd.cmd_code()), cmd.as_bytes().unwrap())
+}
+
+fn run_rt_test_with_trng(trng_mode: TrngMode) -> DefaultHwModel {
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    run_rt_test(RuntimeTestArgs {
+        init_params: Some(InitParams {
+            rom: &rom,
+            trng_mode: Some(trng_mode),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+#[test]
+#[cfg_attr(
+    all(
+        any(feature = "verilator", feature = "fpga_realtime"),
+        not(feature = "itrng")
+    ),
+    ignore
+)]
+fn test_get_entropy_health() {
+    let mut model = run_rt_test_with_trng(TrngMode::Internal);
+
+    let resp = get_entropy_health(&mut model, 0).unwrap().unwrap();
+    let health = GetEntropyHealthResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(health.repcnt_total_fails, 0);
+    assert_eq!(health.adaptp_hi_total_fails, 0);
+    assert_eq!(health.adaptp_lo_total_fails, 0);
+    assert!(health.repcnt_hi_watermark > 0);
+    assert!(health.repcnt_hi_watermark < health.repcnt_threshold);
+    assert!(health.adaptp_hi_watermark <= health.adaptp_hi_threshold);
+    assert!(health.adaptp_lo_watermark >= health.adaptp_lo_threshold);
+
+    // Reseeding runs more entropy through the health tests
+    let resp = get_entropy_health(&mut model, GetEntropyHealthReq::RESEED)
+        .unwrap()
+        .unwrap();
+    let reseeded = GetEntropyHealthResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(reseeded.repcnt_total_fails, 0);
+    assert!(reseeded.repcnt_hi_watermark >= health.repcnt_hi_watermark);
+    assert!(reseeded.adaptp_hi_watermark >= health.adaptp_hi_watermark);
+    assert!(reseeded.adaptp_lo_watermark <= health.adaptp_lo_watermark);
+}
+
+#[test]
+#[cfg_attr(
+    all(
+        any(feature = "verilator", feature = "fpga_realtime"),
+        feature = "itrng"
+    ),
+    ignore
+)]
+fn test_get_entropy_health_external_trng() {
+    let mut model = run_rt_test_with_trng(TrngMode::External);
+
+    let resp = get_entropy_health(&mut model, 0).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::DRIVER_TRNG_HEALTH_TEST_STATS_UNAVAILABLE,
+        resp,
+    );
+}
@@ -4,7 +4,7 @@ use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
 use caliptra_api::SocManager;
 use caliptra_builder::{version, ImageOptions};
 use caliptra_common::mailbox_api::{
-    CommandId, FipsVersionResp, MailboxReqHeader, MailboxRespHeader,
+    CommandId, FipsVersionResp, MailboxReqHeader, MailboxRespHeader, ZeroizeResp, ZeroizeStatus,
 };
 use caliptra_hw_model::HwModel;
 use caliptra_runtime::FipsVersionCmd;
@@ -24,7 +24,7 @@ fn test_fips_version() {
     };
     let mut model = run_rt_test(args);
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     // VERSION
     let payload = MailboxReqHeader {
@@ -74,7 +74,7 @@ fn test_fips_version() {
 fn test_fips_shutdown() {
     let mut model = run_rt_test(RuntimeTestArgs::default());
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     // SHUTDOWN
     let payload = MailboxReqHeader {
@@ -108,3 +108,49 @@ fn test_fips_shutdown() {
         resp,
     );
 }
+
+#[test]
+fn test_fips_zeroize() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until_mailbox_idle().unwrap();
+
+    // ZEROIZE
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::ZEROIZE), &[]),
+    };
+
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::ZEROIZE), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+
+    let resp = ZeroizeResp::read_from_bytes(resp.as_slice()).unwrap();
+    // Verify checksum and FIPS status
+    assert!(caliptra_common::checksum::verify_checksum(
+        resp.hdr.chksum,
+        0x0,
+        &resp.as_bytes()[core::mem::size_of_val(&resp.hdr.chksum)..],
+    ));
+    assert_eq!(
+        resp.hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+    assert_eq!(
+        ZeroizeStatus::from_bits_retain(resp.status),
+        ZeroizeStatus::all()
+    );
+
+    // Check we are rejecting additional commands with the shutdown error code.
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::VERSION), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::VERSION), payload.as_bytes())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        caliptra_drivers::CaliptraError::RUNTIME_SHUTDOWN,
+        resp,
+    );
+}
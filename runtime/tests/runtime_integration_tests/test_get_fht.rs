@@ -0,0 +1,42 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{CommandId, GetFhtResp, MailboxReqHeader};
+use caliptra_drivers::{hand_off::FHT_MARKER, CrashRecord};
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use zerocopy::{FromBytes, IntoBytes};
+
+fn get_fht(model: &mut DefaultHwModel) -> GetFhtResp {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_FHT), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_FHT), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    GetFhtResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_get_fht() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let fht = get_fht(&mut model);
+    assert_eq!(fht.format_version, GetFhtResp::FORMAT_VERSION);
+    assert_eq!(fht.fht_marker, FHT_MARKER);
+    assert_eq!(
+        fht.fht_validity,
+        CrashRecord::FHT_MARKER_VALID
+            | CrashRecord::FHT_VALID
+            | CrashRecord::FHT_RT_CDI_VALID
+            | CrashRecord::FHT_RT_PRIV_KEY_VALID
+    );
+    assert_eq!(fht.fht_major_ver, 1);
+
+    // The RT alias CDI and private key are held in key vault slots 4 and 5
+    assert_eq!(fht.rt_cdi_kv_hdl, 0x0000_1004);
+    assert_eq!(fht.rt_priv_key_kv_hdl, 0x0000_1005);
+    assert_ne!(fht.ldevid_tbs_size, 0);
+    assert_ne!(fht.fmcalias_tbs_size, 0);
+    assert_ne!(fht.rtalias_tbs_size, 0);
+}
@@ -0,0 +1,42 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{get_certs, run_rt_test, RuntimeTestArgs};
+use caliptra_api::mailbox::GetRtAliasCsrReq;
+use caliptra_common::mailbox_api::GetRtAliasCertReq;
+use openssl::{
+    nid::Nid,
+    x509::{X509Req, X509},
+};
+
+#[test]
+fn test_get_rt_alias_csr() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let csr_resp = get_certs::<GetRtAliasCsrReq>(&mut model);
+    assert_ne!(0, csr_resp.data_size);
+    let csr = X509Req::from_der(&csr_resp.data[..csr_resp.data_size as usize]).unwrap();
+
+    let pub_key = csr.public_key().unwrap();
+    assert!(
+        csr.verify(&pub_key).unwrap(),
+        "Invalid public key. Unable to verify RT Alias CSR",
+    );
+
+    // The CSR must be for the same key and subject as the RT Alias cert
+    let cert_resp = get_certs::<GetRtAliasCertReq>(&mut model);
+    let cert = X509::from_der(&cert_resp.data[..cert_resp.data_size as usize]).unwrap();
+    assert!(cert.public_key().unwrap().public_eq(&pub_key));
+    assert_eq!(
+        csr.subject_name().to_der().unwrap(),
+        cert.subject_name().to_der().unwrap()
+    );
+    assert_eq!(
+        csr.subject_name()
+            .entries_by_nid(Nid::COMMONNAME)
+            .next()
+            .unwrap()
+            .data()
+            .as_slice(),
+        b"Caliptra 1.0 Rt Alias"
+    );
+}
@@ -0,0 +1,69 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_builder::ImageOptions;
+use caliptra_common::mailbox_api::{CommandId, IncrementFuseSvnResp, MailboxReqHeader};
+use caliptra_drivers::{RUNTIME_SVN_FUSE_REQUEST_VALID, RUNTIME_SVN_FUSE_REQUEST_WIRE};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::{FromBytes, IntoBytes};
+
+fn increment_fuse_svn(model: &mut DefaultHwModel) -> Result<Option<Vec<u8>>, ModelError> {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::INCREMENT_FUSE_SVN),
+            &[],
+        ),
+    };
+    model.mailbox_execute(u32::from(CommandId::INCREMENT_FUSE_SVN), payload.as_bytes())
+}
+
+fn svn_fuse_request(model: &mut DefaultHwModel) -> u32 {
+    model
+        .soc_ifc()
+        .cptra_generic_output_wires()
+        .at(RUNTIME_SVN_FUSE_REQUEST_WIRE)
+        .read()
+}
+
+#[test]
+fn test_increment_fuse_svn() {
+    let mut image_options = ImageOptions::default();
+    image_options.vendor_config.pl0_pauser = Some(0x1);
+    image_options.app_svn = 3;
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_options),
+        ..Default::default()
+    });
+
+    model.step_until_mailbox_idle().unwrap();
+    assert_eq!(svn_fuse_request(&mut model), 0);
+
+    let resp = increment_fuse_svn(&mut model).unwrap().unwrap();
+    let resp = IncrementFuseSvnResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(resp.fuse_svn, 0);
+    assert_eq!(resp.new_fuse_svn, 3);
+    assert_eq!(resp.fuse_runtime_svn, [0b111, 0, 0, 0]);
+
+    assert_eq!(
+        svn_fuse_request(&mut model),
+        RUNTIME_SVN_FUSE_REQUEST_VALID | 3
+    );
+}
+
+#[test]
+fn test_increment_fuse_svn_not_increased() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until_mailbox_idle().unwrap();
+
+    // The default image has runtime SVN 0, which matches the fuses
+    let resp = increment_fuse_svn(&mut model).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_FUSE_SVN_NOT_INCREASED,
+        resp,
+    );
+    assert_eq!(svn_fuse_request(&mut model), 0);
+}
@@ -1,6 +1,6 @@
 // Licensed under the Apache-2.0 license.
 
-use crate::common::{run_rt_test, RuntimeTestArgs};
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
 use caliptra_builder::{
     firmware::{APP_WITH_UART, FMC_WITH_UART},
     ImageOptions,
@@ -8,10 +8,14 @@ use caliptra_builder::{
 use caliptra_common::{
     capabilities::Capabilities,
     mailbox_api::{
-        CapabilitiesResp, CommandId, FwInfoResp, GetIdevInfoResp, MailboxReqHeader,
-        MailboxRespHeader,
+        CapabilitiesCmdVersionReq, CapabilitiesCmdVersionResp, CapabilitiesNegotiateReq,
+        CapabilitiesNegotiateResp, CapabilitiesResp, CommandId, FwInfoResp, FwInfoV1Resp,
+        FwInfoVersionedReq, GetIdevInfoResp, MailboxReq, MailboxReqHeader,
+        MailboxReqVersionedHeader, MailboxRespHeader,
     },
 };
+use caliptra_drivers::{memory_layout::DCCM_SIZE, MEM_ECC_SCRUB_CHUNK_SIZE};
+use caliptra_error::CaliptraError;
 use caliptra_hw_model::{BootParams, DefaultHwModel, HwModel, InitParams};
 use caliptra_image_crypto::OsslCrypto as Crypto;
 use caliptra_image_gen::ImageGenerator;
@@ -86,12 +90,16 @@ fn test_fw_info() {
     let rom_info = find_rom_info(&rom).unwrap();
 
     let get_fwinfo = |model: &mut DefaultHwModel| {
-        let payload = MailboxReqHeader {
-            chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
-        };
+        let mut req = MailboxReq::FwInfoVersioned(FwInfoVersionedReq {
+            hdr: MailboxReqVersionedHeader {
+                version: FwInfoVersionedReq::VERSION,
+                ..Default::default()
+            },
+        });
+        req.populate_chksum().unwrap();
 
         let resp = model
-            .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+            .mailbox_execute(u32::from(CommandId::FW_INFO), req.as_bytes().unwrap())
             .unwrap()
             .unwrap();
 
@@ -122,6 +130,12 @@ fn test_fw_info() {
     let info = get_fwinfo(&mut model);
     // Verify FW info
     assert_eq!(info.pl0_pauser, 0x1);
+    // The SoC never holds the SHA accelerator while ROM loads the firmware
+    assert_eq!(info.rom_sha_acc_lock_contention, 0);
+    // FMC ran its phases within its own watchdog budget
+    assert_eq!(info.fmc_wdt_budget_cycles, 10_000_000);
+    assert!(info.fmc_wdt_max_phase_cycles > 0);
+    assert!(info.fmc_wdt_max_phase_cycles < info.fmc_wdt_budget_cycles);
     assert_eq!(info.fmc_manifest_svn, 5);
     assert_eq!(info.runtime_svn, 10);
     assert_eq!(info.min_runtime_svn, 10);
@@ -134,6 +148,53 @@ fn test_fw_info() {
     assert_eq!(info.runtime_sha384_digest, image.manifest.runtime.digest);
     // Check owner public key hash
     assert_eq!(info.owner_pub_key_hash, owner_pub_key_hash);
+    // The runtime slept between commands
+    assert!(info.idle_count > 0);
+    let next_info = get_fwinfo(&mut model);
+    assert!(next_info.idle_count > info.idle_count);
+    // Each command is counted and the uptime keeps moving
+    assert_eq!(next_info.cmd_count, info.cmd_count + 1);
+    assert_eq!(next_info.cmd_success_count, info.cmd_success_count + 1);
+    assert_eq!(next_info.cmd_failure_count, info.cmd_failure_count);
+    let uptime = |info: &FwInfoResp| {
+        (u64::from(info.uptime_cycles_high) << 32) | u64::from(info.uptime_cycles_low)
+    };
+    assert!(uptime(&next_info) > uptime(&info));
+
+    // No memory errors are injected, and DCCM is scrubbed while idle
+    assert_eq!(next_info.iccm_ecc_cor_count, 0);
+    assert_eq!(next_info.dccm_ecc_cor_count, 0);
+    assert_eq!(next_info.mbox_ecc_cor_count, 0);
+    assert_eq!(next_info.mbox_ecc_unc_count, 0);
+    assert_eq!(next_info.ccm_ecc_unc, 0);
+    let mut scrub_info = get_fwinfo(&mut model);
+    for _ in 0..DCCM_SIZE / MEM_ECC_SCRUB_CHUNK_SIZE {
+        if scrub_info.dccm_scrub_passes > 0 {
+            break;
+        }
+        scrub_info = get_fwinfo(&mut model);
+    }
+    assert!(scrub_info.dccm_scrub_passes > 0);
+    let next_info = get_fwinfo(&mut model);
+
+    // A failed command is counted as such
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::FW_INFO),
+            MailboxReqHeader { chksum: 0 }.as_bytes(),
+        )
+        .unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_INVALID_CHECKSUM, resp);
+    let failed_info = get_fwinfo(&mut model);
+    assert_eq!(failed_info.cmd_count, next_info.cmd_count + 2);
+    assert_eq!(
+        failed_info.cmd_success_count,
+        next_info.cmd_success_count + 1
+    );
+    assert_eq!(
+        failed_info.cmd_failure_count,
+        next_info.cmd_failure_count + 1
+    );
 
     // Make image with newer SVN.
     let mut image_opts20 = image_opts.clone();
@@ -201,3 +262,133 @@ fn test_capabilities() {
     let capabilities = Capabilities::try_from(capabilities_resp.capabilities.as_bytes()).unwrap();
     assert!(capabilities.contains(Capabilities::RT_BASE));
 }
+
+fn capabilities_cmd_version(
+    model: &mut DefaultHwModel,
+    version: u8,
+    cmd: CommandId,
+) -> Result<Option<Vec<u8>>, caliptra_hw_model::ModelError> {
+    let mut req = MailboxReq::CapabilitiesCmdVersion(CapabilitiesCmdVersionReq {
+        hdr: MailboxReqVersionedHeader {
+            version,
+            ..Default::default()
+        },
+        cmd: cmd.into(),
+    });
+    req.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::CAPABILITIES), req.as_bytes().unwrap())
+}
+
+#[test]
+fn test_capabilities_cmd_version() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = capabilities_cmd_version(
+        &mut model,
+        CapabilitiesCmdVersionReq::VERSION,
+        CommandId::CAPABILITIES,
+    )
+    .unwrap()
+    .unwrap();
+    let resp = CapabilitiesCmdVersionResp::read_from_bytes(resp.as_slice()).unwrap();
+    let capabilities = Capabilities::try_from(resp.capabilities.as_bytes()).unwrap();
+    assert!(capabilities.contains(Capabilities::RT_CMD_VERSIONING));
+    assert_eq!(resp.min_version, 1);
+    assert_eq!(resp.max_version, CapabilitiesCmdVersionReq::VERSION);
+
+    let resp = capabilities_cmd_version(
+        &mut model,
+        CapabilitiesCmdVersionReq::VERSION,
+        CommandId::FW_INFO,
+    )
+    .unwrap()
+    .unwrap();
+    let resp = CapabilitiesCmdVersionResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        (resp.min_version, resp.max_version),
+        (1, FwInfoVersionedReq::VERSION)
+    );
+
+    let resp = capabilities_cmd_version(
+        &mut model,
+        CapabilitiesCmdVersionReq::VERSION,
+        CommandId::GET_IDEV_INFO,
+    )
+    .unwrap()
+    .unwrap();
+    let resp = CapabilitiesCmdVersionResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!((resp.min_version, resp.max_version), (1, 1));
+}
+
+#[test]
+fn test_capabilities_negotiate() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let mut req = MailboxReq::CapabilitiesNegotiate(CapabilitiesNegotiateReq {
+        hdr: MailboxReqVersionedHeader {
+            version: CapabilitiesNegotiateReq::VERSION,
+            ..Default::default()
+        },
+    });
+    req.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::CAPABILITIES), req.as_bytes().unwrap())
+        .unwrap()
+        .unwrap();
+    let resp = CapabilitiesNegotiateResp::read_from_bytes(resp.as_slice()).unwrap();
+    let capabilities = Capabilities::try_from(resp.capabilities.as_bytes()).unwrap();
+    assert!(capabilities.contains(Capabilities::RT_CMD_NEGOTIATION));
+
+    assert!(resp.is_supported(&CommandId::GET_IDEV_INFO));
+    assert_eq!(resp.max_version(&CommandId::GET_IDEV_INFO), 1);
+    assert_eq!(
+        resp.max_version(&CommandId::CAPABILITIES),
+        CapabilitiesNegotiateReq::VERSION
+    );
+    assert_eq!(
+        resp.max_version(&CommandId::FW_INFO),
+        FwInfoVersionedReq::VERSION
+    );
+
+    // ROM-only and unknown commands are not supported
+    assert!(!resp.is_supported(&CommandId::GET_PROVISIONING_STATUS));
+    assert!(!resp.is_supported(&CommandId(0x5858_5858)));
+
+    // Bits beyond the assigned commands are clear
+    let assigned = CommandId::CAPABILITY_BITS.len();
+    assert!(resp.max_versions[assigned..].iter().all(|v| *v == 0));
+}
+
+#[test]
+fn test_fw_info_v1() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // Callers that send an unversioned header get the original response
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    assert_eq!(resp.len(), core::mem::size_of::<FwInfoV1Resp>());
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(info.attestation_disabled, 0);
+}
+
+#[test]
+fn test_capabilities_unsupported_version() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = capabilities_cmd_version(
+        &mut model,
+        CapabilitiesNegotiateReq::VERSION + 1,
+        CommandId::CAPABILITIES,
+    )
+    .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_CMD_UNSUPPORTED_VERSION,
+        resp,
+    );
+}
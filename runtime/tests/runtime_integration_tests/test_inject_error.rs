@@ -0,0 +1,119 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_builder::ImageOptions;
+use caliptra_common::mailbox_api::{
+    CommandId, InjectErrorReq, InjectErrorType, MailboxReq, MailboxReqHeader,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use zerocopy::IntoBytes;
+
+fn inject_error_req(error_type: u32) -> MailboxReq {
+    let mut cmd = MailboxReq::InjectError(InjectErrorReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        error_type,
+    });
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+fn inject_fatal_error(model: &mut DefaultHwModel, error_type: InjectErrorType) -> u32 {
+    let cmd = inject_error_req(error_type as u32);
+    model
+        .start_mailbox_execute(u32::from(CommandId::INJECT_ERROR), cmd.as_bytes().unwrap())
+        .unwrap();
+
+    model.step_until(|m| m.soc_ifc().cptra_fw_error_fatal().read() != 0);
+    model.soc_ifc().cptra_fw_error_fatal().read()
+}
+
+#[test]
+fn test_inject_non_fatal_error() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let cmd = inject_error_req(InjectErrorType::NonFatal as u32);
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::INJECT_ERROR), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INJECTED_NON_FATAL_ERROR,
+        resp,
+    );
+
+    // The firmware keeps servicing commands after a non-fatal error
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+}
+
+#[test]
+fn test_inject_fatal_error() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_eq!(
+        inject_fatal_error(&mut model, InjectErrorType::Fatal),
+        u32::from(CaliptraError::RUNTIME_INJECTED_FATAL_ERROR)
+    );
+}
+
+#[test]
+fn test_inject_nmi() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_eq!(
+        inject_fatal_error(&mut model, InjectErrorType::Nmi),
+        u32::from(CaliptraError::RUNTIME_GLOBAL_NMI)
+    );
+}
+
+#[test]
+fn test_inject_wdt_expiry() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_eq!(
+        inject_fatal_error(&mut model, InjectErrorType::Wdt),
+        u32::from(CaliptraError::RUNTIME_GLOBAL_WDT_EXPIRED)
+    );
+}
+
+#[test]
+fn test_inject_invalid_error_type() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let cmd = inject_error_req(InjectErrorType::Invalid as u32);
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::INJECT_ERROR), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INJECT_ERROR_INVALID_TYPE,
+        resp,
+    );
+}
+
+#[test]
+fn test_inject_error_cannot_be_called_from_pl1() {
+    let mut image_opts = ImageOptions::default();
+    image_opts.vendor_config.pl0_pauser = None;
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_opts),
+        ..Default::default()
+    });
+
+    let cmd = inject_error_req(InjectErrorType::Fatal as u32);
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::INJECT_ERROR), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL,
+        resp,
+    );
+    assert_eq!(model.soc_ifc().cptra_fw_error_fatal().read(), 0);
+}
@@ -4,10 +4,13 @@ use crate::common::{
     execute_dpe_cmd, get_rt_alias_cert, run_rt_test, DpeResult, RuntimeTestArgs, TEST_DIGEST,
     TEST_LABEL,
 };
-use caliptra_api::SocManager;
-use caliptra_common::mailbox_api::{InvokeDpeReq, MailboxReq, MailboxReqHeader};
+use caliptra_api::{evidence::collect_evidence, SocManager};
+use caliptra_builder::firmware::{APP_WITH_UART_DPE_P256, APP_WITH_UART_DPE_TEST_HOOKS};
+use caliptra_common::mailbox_api::{
+    CommandId, GetDpeStatsResp, InvokeDpeReq, MailboxReq, MailboxReqHeader,
+};
 use caliptra_drivers::CaliptraError;
-use caliptra_hw_model::HwModel;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
 use caliptra_runtime::{RtBootStatus, DPE_SUPPORT, VENDOR_ID, VENDOR_SKU};
 use cms::{
     cert::x509::der::{Decode, Encode},
@@ -16,21 +19,24 @@ use cms::{
 };
 use dpe::{
     commands::{
-        CertifyKeyCmd, CertifyKeyFlags, Command, GetCertificateChainCmd, InitCtxCmd, RotateCtxCmd,
-        RotateCtxFlags, SignCmd, SignFlags,
+        CertifyKeyCmd, CertifyKeyFlags, Command, DeriveContextCmd, DeriveContextFlags,
+        DestroyCtxCmd, GetCertificateChainCmd, InitCtxCmd, RotateCtxCmd, RotateCtxFlags, SignCmd,
+        SignFlags,
     },
     context::ContextHandle,
     response::{DpeErrorCode, Response},
     DPE_PROFILE,
 };
 use openssl::{
-    bn::BigNum,
+    bn::{BigNum, BigNumContext},
     ec::{EcGroup, EcKey},
     ecdsa::EcdsaSig,
     nid::Nid,
-    x509::X509,
+    pkey::PKey,
+    x509::{X509Req, X509},
 };
 use sha2::{Digest, Sha384};
+use zerocopy::{FromBytes, IntoBytes};
 
 #[test]
 fn test_invoke_dpe_get_profile_cmd() {
@@ -50,6 +56,52 @@ fn test_invoke_dpe_get_profile_cmd() {
     assert_eq!(profile.flags, DPE_SUPPORT.bits());
 }
 
+#[test]
+fn test_invoke_dpe_p256_profile() {
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_fwid: Some(&APP_WITH_UART_DPE_P256),
+        ..Default::default()
+    });
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    // collect_evidence builds CertifyKey for the profile GetProfile reports
+    let evidence = collect_evidence(&mut model, &TEST_LABEL).unwrap();
+    const DPE_PROFILE_IROT_P256_SHA256: u32 = 3;
+    assert_eq!(evidence.dpe_profile, DPE_PROFILE_IROT_P256_SHA256);
+    assert_eq!(evidence.dpe_pub_key_x.len(), 32);
+    assert_eq!(evidence.dpe_pub_key_y.len(), 32);
+
+    // The P-256 leaf certificate is issued by the P-384 RT alias key
+    let rt_alias_cert = X509::from_der(&evidence.rt_alias_cert).unwrap();
+    let dpe_cert = X509::from_der(&evidence.dpe_cert).unwrap();
+    assert_eq!(
+        dpe_cert.signature_algorithm().object().nid(),
+        Nid::ECDSA_WITH_SHA256
+    );
+    assert!(dpe_cert
+        .verify(&rt_alias_cert.public_key().unwrap())
+        .unwrap());
+
+    let dpe_key = dpe_cert.public_key().unwrap().ec_key().unwrap();
+    assert_eq!(dpe_key.group().curve_name(), Some(Nid::X9_62_PRIME256V1));
+    let mut x = BigNum::new().unwrap();
+    let mut y = BigNum::new().unwrap();
+    let mut ctx = BigNumContext::new().unwrap();
+    dpe_key
+        .public_key()
+        .affine_coordinates(dpe_key.group(), &mut x, &mut y, &mut ctx)
+        .unwrap();
+    assert_eq!(x.to_vec_padded(32).unwrap(), evidence.dpe_pub_key_x);
+    assert_eq!(y.to_vec_padded(32).unwrap(), evidence.dpe_pub_key_y);
+
+    // The same label derives the same key
+    let again = collect_evidence(&mut model, &TEST_LABEL).unwrap();
+    assert_eq!(again.dpe_pub_key_x, evidence.dpe_pub_key_x);
+}
+
 #[test]
 fn test_invoke_dpe_size_too_big() {
     // Test with data_size too big.
@@ -255,6 +307,18 @@ fn test_invoke_dpe_certify_key_csr() {
     let alias_key = rt_cert.public_key().unwrap().ec_key().unwrap();
     let csr_sig = EcdsaSig::from_der(signer_info.signature.as_bytes()).unwrap();
     assert!(csr_sig.verify(&csr_digest, &alias_key).unwrap());
+
+    // validate the PKCS#10 CSR, which is signed by the context key
+    let csr = X509Req::from_der(econtent).unwrap();
+    let derived_key = EcKey::from_public_key_affine_coordinates(
+        &EcGroup::from_curve_name(Nid::SECP384R1).unwrap(),
+        &BigNum::from_slice(&certify_key_resp.derived_pubkey_x).unwrap(),
+        &BigNum::from_slice(&certify_key_resp.derived_pubkey_y).unwrap(),
+    )
+    .unwrap();
+    let derived_key = PKey::from_ec_key(derived_key).unwrap();
+    assert!(csr.public_key().unwrap().public_eq(&derived_key));
+    assert!(csr.verify(&derived_key).unwrap());
 }
 
 #[test]
@@ -297,3 +361,80 @@ fn test_invoke_dpe_rotate_context() {
 
     assert!(rotate_ctx_resp.handle.is_default());
 }
+
+fn get_dpe_stats(model: &mut DefaultHwModel) -> GetDpeStatsResp {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_DPE_STATS), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_DPE_STATS), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    GetDpeStatsResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_invoke_dpe_destroy_context_reclaims_children() {
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_fwid: Some(&APP_WITH_UART_DPE_TEST_HOOKS),
+        ..Default::default()
+    });
+
+    // Rotate the default context so that it can be retained in derive context.
+    let rotate_ctx_cmd = RotateCtxCmd {
+        handle: ContextHandle::default(),
+        flags: RotateCtxFlags::empty(),
+    };
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::RotateCtx(&rotate_ctx_cmd),
+        DpeResult::Success,
+    );
+    let Some(Response::RotateCtx(rotate_ctx_resp)) = resp else {
+        panic!("Wrong response type!");
+    };
+
+    let initial_stats = get_dpe_stats(&mut model);
+
+    // Derive a child and a grandchild context
+    let derive_context = |model: &mut DefaultHwModel, handle| {
+        let derive_context_cmd = DeriveContextCmd {
+            handle,
+            data: [0u8; DPE_PROFILE.get_hash_size()],
+            flags: DeriveContextFlags::RETAIN_PARENT_CONTEXT,
+            tci_type: 0,
+            target_locality: 0,
+        };
+        let resp = execute_dpe_cmd(
+            model,
+            &mut Command::DeriveContext(&derive_context_cmd),
+            DpeResult::Success,
+        );
+        let Some(Response::DeriveContext(derive_context_resp)) = resp else {
+            panic!("Wrong response type!");
+        };
+        derive_context_resp
+    };
+    let child = derive_context(&mut model, rotate_ctx_resp.handle);
+    // Deriving from the child rotates the child's handle
+    let grandchild = derive_context(&mut model, child.handle);
+
+    let stats = get_dpe_stats(&mut model);
+    assert_eq!(stats.used_contexts, initial_stats.used_contexts + 2);
+    assert_eq!(stats.free_contexts, initial_stats.free_contexts - 2);
+
+    // Destroying the child also destroys the grandchild
+    let destroy_ctx_cmd = DestroyCtxCmd {
+        handle: grandchild.parent_handle,
+    };
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::DestroyCtx(&destroy_ctx_cmd),
+        DpeResult::Success,
+    );
+    let Some(Response::DestroyCtx(_)) = resp else {
+        panic!("Wrong response type!");
+    };
+
+    assert_eq!(get_dpe_stats(&mut model), initial_stats);
+}
@@ -0,0 +1,190 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    CommandId, EcdsaVerifyReq, GetJobStatusReq, GetJobStatusResp, MailboxReq, MailboxReqHeader,
+    MailboxRespHeader, StartJobReq, StartJobResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError, ShaAccMode};
+use openssl::{
+    bn::BigNumContext,
+    ec::{EcGroup, EcKey, PointConversionForm},
+    ecdsa::EcdsaSig,
+    nid::Nid,
+    sha::sha384,
+};
+use zerocopy::{FromBytes, IntoBytes};
+
+const MSG: &[u8] = b"Caliptra asynchronous job";
+
+/// Build an ECDSA384_VERIFY request over `MSG`, and stream `MSG` to the SHA
+/// accelerator
+fn ecdsa_verify_req(model: &mut DefaultHwModel) -> EcdsaVerifyReq {
+    let group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+    let key = EcKey::generate(&group).unwrap();
+    let sig = EcdsaSig::sign(&sha384(MSG), &key).unwrap();
+
+    let mut ctx = BigNumContext::new().unwrap();
+    let pub_key = key
+        .public_key()
+        .to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut ctx)
+        .unwrap();
+
+    model
+        .compute_sha512_acc_digest(MSG, ShaAccMode::Sha384Stream)
+        .unwrap();
+
+    EcdsaVerifyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        pub_key_x: pub_key[1..49].try_into().unwrap(),
+        pub_key_y: pub_key[49..].try_into().unwrap(),
+        signature_r: sig.r().to_vec_padded(48).unwrap().try_into().unwrap(),
+        signature_s: sig.s().to_vec_padded(48).unwrap().try_into().unwrap(),
+    }
+}
+
+fn start_job(
+    model: &mut DefaultHwModel,
+    cmd: CommandId,
+    req: &[u8],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut start_job = StartJobReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        cmd: cmd.into(),
+        data_size: req.len() as u32,
+        ..Default::default()
+    };
+    start_job.data[..req.len()].copy_from_slice(req);
+    let mut cmd = MailboxReq::StartJob(start_job);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::START_JOB), cmd.as_bytes().unwrap())
+}
+
+fn start_ecdsa_verify_job(model: &mut DefaultHwModel, req: &EcdsaVerifyReq) -> u32 {
+    let resp = start_job(model, CommandId::ECDSA384_VERIFY, req.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    StartJobResp::read_from_bytes(resp.as_slice())
+        .unwrap()
+        .job_id
+}
+
+fn get_job_status(model: &mut DefaultHwModel, job_id: u32) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::GetJobStatus(GetJobStatusReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        job_id,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::GET_JOB_STATUS),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+/// Poll the status of a job until it is no longer in progress
+fn wait_for_job(model: &mut DefaultHwModel, job_id: u32) -> GetJobStatusResp {
+    for _ in 0..100 {
+        // The runtime holds the mailbox lock while the job runs
+        model.step_until_mailbox_idle().unwrap();
+        let resp = get_job_status(model, job_id)
+            .unwrap()
+            .expect("We expected a response");
+        let resp = GetJobStatusResp::read_from_bytes(resp.as_slice()).unwrap();
+        if resp.status != GetJobStatusResp::STATUS_IN_PROGRESS {
+            return resp;
+        }
+        model.step();
+    }
+    panic!("Job {job_id} did not complete");
+}
+
+#[test]
+fn test_job_ecdsa_verify() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let req = ecdsa_verify_req(&mut model);
+    let job_id = start_ecdsa_verify_job(&mut model, &req);
+
+    let resp = wait_for_job(&mut model, job_id);
+    assert_eq!(resp.status, GetJobStatusResp::STATUS_COMPLETE);
+    assert_eq!(resp.error, 0);
+    assert_eq!(
+        resp.data_size as usize,
+        core::mem::size_of::<MailboxRespHeader>()
+    );
+    let job_resp_hdr =
+        MailboxRespHeader::read_from_bytes(&resp.data[..resp.data_size as usize]).unwrap();
+    assert_eq!(
+        job_resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    // The job was removed once collected
+    let resp = get_job_status(&mut model, job_id).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_NOT_FOUND, resp);
+}
+
+#[test]
+fn test_job_ecdsa_verify_failure() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let mut req = ecdsa_verify_req(&mut model);
+    req.signature_s[47] ^= 1;
+    let job_id = start_ecdsa_verify_job(&mut model, &req);
+
+    let resp = wait_for_job(&mut model, job_id);
+    assert_eq!(resp.status, GetJobStatusResp::STATUS_FAILED);
+    assert_eq!(
+        resp.error,
+        u32::from(CaliptraError::RUNTIME_ECDSA_VERIFY_FAILED)
+    );
+    assert_eq!(resp.data_size, 0);
+}
+
+#[test]
+fn test_job_queue_full() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let req = ecdsa_verify_req(&mut model);
+    let job_id = start_ecdsa_verify_job(&mut model, &req);
+
+    // The first job holds the queue until it is collected
+    let resp = start_job(&mut model, CommandId::ECDSA384_VERIFY, req.as_bytes()).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_QUEUE_FULL, resp);
+
+    let resp = wait_for_job(&mut model, job_id);
+    assert_eq!(resp.status, GetJobStatusResp::STATUS_COMPLETE);
+
+    let next_job_id = start_ecdsa_verify_job(&mut model, &req);
+    assert_ne!(next_job_id, job_id);
+}
+
+#[test]
+fn test_job_not_found() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = get_job_status(&mut model, 1).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_NOT_FOUND, resp);
+
+    let req = ecdsa_verify_req(&mut model);
+    let job_id = start_ecdsa_verify_job(&mut model, &req);
+    let resp = get_job_status(&mut model, job_id + 1).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_NOT_FOUND, resp);
+}
+
+#[test]
+fn test_job_cmd_not_supported() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    let resp = start_job(&mut model, CommandId::FW_INFO, payload.as_bytes()).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_JOB_CMD_NOT_SUPPORTED,
+        resp,
+    );
+}
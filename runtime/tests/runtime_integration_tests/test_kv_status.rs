@@ -0,0 +1,51 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, GetKvStatusResp, KvSlotInfo, MailboxReqHeader, MailboxRespHeader,
+};
+use caliptra_hw_model::HwModel;
+use zerocopy::{FromBytes, IntoBytes};
+
+// Key slots used by the runtime; see common/src/keyids.rs
+const KEY_ID_TMP: usize = 3;
+const KEY_ID_RT_CDI: usize = 4;
+const KEY_ID_RT_PRIV_KEY: usize = 5;
+
+#[test]
+fn test_get_kv_status() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_KV_STATUS), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_KV_STATUS), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    let kv_status = GetKvStatusResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        kv_status.hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    // The runtime alias CDI and key stay in the key vault
+    assert_ne!(kv_status.occupied_slots & (1 << KEY_ID_RT_CDI), 0);
+    assert_eq!(kv_status.slots[KEY_ID_RT_CDI].kind, KvSlotInfo::KIND_CDI);
+    assert_ne!(kv_status.occupied_slots & (1 << KEY_ID_RT_PRIV_KEY), 0);
+    assert_eq!(
+        kv_status.slots[KEY_ID_RT_PRIV_KEY].kind,
+        KvSlotInfo::KIND_PRIVATE_KEY
+    );
+
+    // The temporary slot is erased after every use
+    assert_eq!(kv_status.occupied_slots & (1 << KEY_ID_TMP), 0);
+    assert_eq!(kv_status.slots[KEY_ID_TMP], KvSlotInfo::default());
+
+    // Every occupied slot reports its usage bits
+    for (i, slot) in kv_status.slots.iter().enumerate() {
+        let occupied = kv_status.occupied_slots & (1 << i) != 0;
+        assert_eq!(occupied, slot.usage != 0);
+        assert_eq!(occupied, slot.kind != KvSlotInfo::KIND_EMPTY);
+    }
+}
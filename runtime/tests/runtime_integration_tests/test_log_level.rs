@@ -0,0 +1,63 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, MailboxRespHeader, SetLogLevelReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::{FromBytes, IntoBytes};
+
+fn set_log_level(
+    model: &mut DefaultHwModel,
+    log_level: u32,
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::SetLogLevel(SetLogLevelReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        log_level,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::SET_LOG_LEVEL), cmd.as_bytes().unwrap())
+}
+
+fn assert_success(resp: Result<Option<Vec<u8>>, ModelError>) {
+    let resp = resp.unwrap().expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+}
+
+/// Run VERSION, which logs at info level, and return the UART output
+fn version_output(model: &mut DefaultHwModel) -> String {
+    model.output().take(usize::MAX);
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::VERSION), &[]),
+    };
+    model
+        .mailbox_execute(u32::from(CommandId::VERSION), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+    model.output().take(usize::MAX)
+}
+
+#[test]
+fn test_set_log_level() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    assert!(version_output(&mut model).contains("[rt] FIPS Version"));
+
+    assert_success(set_log_level(&mut model, SetLogLevelReq::LOG_LEVEL_ERROR));
+    assert!(!version_output(&mut model).contains("[rt] FIPS Version"));
+
+    assert_success(set_log_level(&mut model, SetLogLevelReq::LOG_LEVEL_DEFAULT));
+    assert!(version_output(&mut model).contains("[rt] FIPS Version"));
+}
+
+#[test]
+fn test_set_log_level_invalid() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = set_log_level(&mut model, SetLogLevelReq::LOG_LEVEL_TRACE + 1).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_LOG_LEVEL_INVALID, resp);
+}
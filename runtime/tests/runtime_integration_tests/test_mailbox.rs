@@ -2,10 +2,12 @@
 
 use caliptra_api::SocManager;
 use caliptra_common::mailbox_api::{CommandId, MailboxReqHeader};
-use caliptra_hw_model::HwModel;
+use caliptra_drivers::{MBOX_CANCEL_REQUEST, MBOX_CANCEL_REQUEST_REG};
+use caliptra_hw_model::{DefaultHwModel, HwModel, MAILBOX_TIMEOUT_CYCLES};
+use dpe::commands::Command;
 use zerocopy::IntoBytes;
 
-use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use crate::common::{assert_error, execute_dpe_cmd, run_rt_test, DpeResult, RuntimeTestArgs};
 
 /// When a successful command runs after a failed command, ensure the error
 /// register is cleared.
@@ -13,7 +15,7 @@ use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
 fn test_error_cleared() {
     let mut model = run_rt_test(RuntimeTestArgs::default());
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     // Send invalid command to cause failure
     let resp = model.mailbox_execute(0xffffffff, &[]).unwrap_err();
@@ -39,7 +41,7 @@ fn test_error_cleared() {
 fn test_unimplemented_cmds() {
     let mut model = run_rt_test(RuntimeTestArgs::default());
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     // Send something that is not a valid RT command.
     const INVALID_CMD: u32 = 0xAABBCCDD;
@@ -63,7 +65,7 @@ fn test_unimplemented_cmds() {
 fn test_reserved_pauser() {
     let mut model = run_rt_test(RuntimeTestArgs::default());
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     // Set pauser to the reserved value
     model.set_apb_pauser(0xffffffff);
@@ -81,3 +83,136 @@ fn test_reserved_pauser() {
         resp,
     );
 }
+
+#[test]
+fn test_cancel_in_flight_cmd() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until_mailbox_idle().unwrap();
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::VERSION), &[]),
+    };
+
+    // Request a cancel before the firmware picks up the command
+    model
+        .start_mailbox_execute(u32::from(CommandId::VERSION), payload.as_bytes())
+        .unwrap();
+    model
+        .soc_ifc()
+        .cptra_rsvd_reg()
+        .at(MBOX_CANCEL_REQUEST_REG)
+        .write(|_| MBOX_CANCEL_REQUEST);
+    let resp = model.finish_mailbox_execute().unwrap_err();
+    assert_error(
+        &mut model,
+        caliptra_drivers::CaliptraError::RUNTIME_MAILBOX_CMD_CANCELED,
+        resp,
+    );
+
+    // The request is acknowledged and does not affect the next command
+    assert_eq!(
+        model
+            .soc_ifc()
+            .cptra_rsvd_reg()
+            .at(MBOX_CANCEL_REQUEST_REG)
+            .read(),
+        0
+    );
+    let _ = model
+        .mailbox_execute(u32::from(CommandId::VERSION), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+fn test_abort_in_flight_cmd() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until_mailbox_idle().unwrap();
+
+    // Fail a command so that the firmware clearing the error register shows
+    // it has started on the next one
+    let resp = model.mailbox_execute(0xffffffff, &[]).unwrap_err();
+    assert_error(
+        &mut model,
+        caliptra_drivers::CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+        resp,
+    );
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::VERSION), &[]),
+    };
+    model
+        .start_mailbox_execute(u32::from(CommandId::VERSION), payload.as_bytes())
+        .unwrap();
+    model.step_until(|m| m.soc_ifc().cptra_fw_error_non_fatal().read() == 0);
+
+    // Release the mailbox while the command is in progress
+    model.soc_mbox().execute().write(|w| w.execute(false));
+    model.step_until(|m| m.soc_ifc().cptra_fw_error_non_fatal().read() != 0);
+    assert_eq!(
+        model.soc_ifc().cptra_fw_error_non_fatal().read(),
+        u32::from(caliptra_drivers::CaliptraError::RUNTIME_MAILBOX_CMD_ABORTED)
+    );
+    assert_eq!(model.soc_ifc().cptra_fw_error_fatal().read(), 0);
+
+    // The firmware goes back to listening for commands
+    assert!(model.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    let _ = model
+        .mailbox_execute(u32::from(CommandId::VERSION), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+}
+
+/// Run CAPABILITIES, whose response is 24 bytes, then read `len` bytes of the
+/// mailbox SRAM back by growing dlen, as a malicious SoC would.
+fn read_mbox_sram(model: &mut DefaultHwModel, len: usize) -> Vec<u8> {
+    assert!(!model.soc_mbox().lock().read().lock());
+    let chksum = caliptra_common::checksum::calc_checksum(u32::from(CommandId::CAPABILITIES), &[]);
+    model
+        .soc_mbox()
+        .cmd()
+        .write(|_| u32::from(CommandId::CAPABILITIES));
+    model.soc_mbox().dlen().write(|_| 4);
+    model.soc_mbox().datain().write(|_| chksum);
+    model.soc_mbox().execute().write(|w| w.execute(true));
+    model
+        .step_until_timeout(MAILBOX_TIMEOUT_CYCLES, |m| {
+            !m.soc_mbox().status().read().status().cmd_busy()
+        })
+        .unwrap();
+
+    model.soc_mbox().dlen().write(|_| len as u32);
+    let mut data = vec![];
+    for _ in 0..len / 4 {
+        data.extend_from_slice(&model.soc_mbox().dataout().read().to_le_bytes());
+    }
+    model.soc_mbox().execute().write(|w| w.execute(false));
+    data
+}
+
+#[test]
+fn test_confidential_cmd_scrubs_mbox_sram() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until_mailbox_idle().unwrap();
+
+    // The response of GET_IDEV_INFO is public and stays in the mailbox SRAM
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::GET_IDEV_INFO), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_IDEV_INFO), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    let sram = read_mbox_sram(&mut model, 48);
+    assert_eq!(sram[24..], resp[24..48]);
+
+    // The response of INVOKE_DPE is scrubbed once the mailbox is released
+    execute_dpe_cmd(&mut model, &mut Command::GetProfile, DpeResult::Success).unwrap();
+    model.step_until(|m| !m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
+    let sram = read_mbox_sram(&mut model, 48);
+    assert_eq!(sram[24..], [0u8; 24]);
+}
@@ -0,0 +1,189 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxAuthTrailer, MailboxReq, MailboxReqHeader, MailboxRespHeader, PairMailboxReq,
+    PairMailboxResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    derive::Deriver,
+    ec::{EcGroup, EcKey},
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Private},
+    sha::{sha384, Sha384},
+    sign::Signer,
+};
+use zerocopy::{FromBytes, IntoBytes};
+
+/// Pair the mailbox with a fresh SoC key, returning the paired key
+fn pair(model: &mut DefaultHwModel) -> [u8; 48] {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+    let soc_key = EcKey::generate(&group).unwrap();
+    let mut ctx = BigNumContext::new().unwrap();
+    let mut x = BigNum::new().unwrap();
+    let mut y = BigNum::new().unwrap();
+    soc_key
+        .public_key()
+        .affine_coordinates(&group, &mut x, &mut y, &mut ctx)
+        .unwrap();
+
+    let mut cmd = MailboxReq::PairMailbox(PairMailboxReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        pub_key_x: x.to_vec_padded(32).unwrap().try_into().unwrap(),
+        pub_key_y: y.to_vec_padded(32).unwrap().try_into().unwrap(),
+    });
+    cmd.populate_chksum().unwrap();
+    let MailboxReq::PairMailbox(req) = &cmd else {
+        unreachable!()
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::PAIR_MAILBOX), cmd.as_bytes().unwrap())
+        .unwrap()
+        .expect("We expected a response");
+    let resp = PairMailboxResp::read_from_bytes(resp.as_slice()).unwrap();
+
+    let rt_x = BigNum::from_slice(&resp.pub_key_x).unwrap();
+    let rt_y = BigNum::from_slice(&resp.pub_key_y).unwrap();
+    let rt_key = EcKey::from_public_key_affine_coordinates(&group, &rt_x, &rt_y).unwrap();
+    let soc_pkey: PKey<Private> = PKey::from_ec_key(soc_key).unwrap();
+    let mut deriver = Deriver::new(&soc_pkey).unwrap();
+    deriver
+        .set_peer(&PKey::from_ec_key(rt_key).unwrap())
+        .unwrap();
+    let shared_secret = deriver.derive_to_vec().unwrap();
+
+    let mut kdf = Sha384::new();
+    kdf.update(&1u32.to_be_bytes());
+    kdf.update(&shared_secret);
+    kdf.update(PairMailboxReq::KDF_LABEL);
+    kdf.update(&req.pub_key_x);
+    kdf.update(&req.pub_key_y);
+    kdf.update(&resp.pub_key_x);
+    kdf.update(&resp.pub_key_y);
+    kdf.finish()
+}
+
+/// Build the request of `cmd` with `body` and an authentication trailer
+fn authenticated_req(cmd: CommandId, body: &[u8], seq: u32, key: &[u8; 48]) -> Vec<u8> {
+    let mut msg = u32::from(cmd).to_le_bytes().to_vec();
+    msg.extend_from_slice(&seq.to_le_bytes());
+    msg.extend_from_slice(body);
+    let pkey = PKey::hmac(key).unwrap();
+    let mut signer = Signer::new(MessageDigest::sha384(), &pkey).unwrap();
+    signer.update(&sha384(&msg)).unwrap();
+    let mut trailer = MailboxAuthTrailer {
+        seq,
+        ..Default::default()
+    };
+    signer.sign(&mut trailer.tag).unwrap();
+
+    let mut data = body.to_vec();
+    data.extend_from_slice(trailer.as_bytes());
+    let chksum = caliptra_common::checksum::calc_checksum(u32::from(cmd), &data);
+    let mut req = chksum.to_le_bytes().to_vec();
+    req.extend_from_slice(&data);
+    req
+}
+
+fn disable_attestation(
+    model: &mut DefaultHwModel,
+    req: &[u8],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    model.mailbox_execute(u32::from(CommandId::DISABLE_ATTESTATION), req)
+}
+
+#[test]
+fn test_unpaired_mailbox_accepts_unauthenticated_commands() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::DISABLE_ATTESTATION),
+            &[],
+        ),
+    };
+    disable_attestation(&mut model, payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+}
+
+#[test]
+fn test_paired_mailbox_authenticates_privileged_commands() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    let key = pair(&mut model);
+
+    // A privileged command without a trailer is rejected
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::DISABLE_ATTESTATION),
+            &[],
+        ),
+    };
+    let resp = disable_attestation(&mut model, payload.as_bytes()).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED, resp);
+
+    // So is one authenticated with the wrong key
+    let req = authenticated_req(CommandId::DISABLE_ATTESTATION, &[], 0, &[0xaa; 48]);
+    let resp = disable_attestation(&mut model, &req).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED, resp);
+
+    // Unprivileged commands need no trailer
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+
+    let req = authenticated_req(CommandId::DISABLE_ATTESTATION, &[], 0, &key);
+    let resp = disable_attestation(&mut model, &req)
+        .unwrap()
+        .expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    // The same request cannot be replayed
+    let resp = disable_attestation(&mut model, &req).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_MAILBOX_AUTH_FAILED, resp);
+
+    let req = authenticated_req(CommandId::DISABLE_ATTESTATION, &[], 1, &key);
+    disable_attestation(&mut model, &req)
+        .unwrap()
+        .expect("We expected a response");
+}
+
+#[test]
+fn test_pair_mailbox_invalid_pub_key() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // (0, 0) is not on the curve
+    let mut cmd = MailboxReq::PairMailbox(PairMailboxReq::default());
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::PAIR_MAILBOX), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MAILBOX_PAIRING_FAILED,
+        resp,
+    );
+
+    // The mailbox stays unpaired
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::DISABLE_ATTESTATION),
+            &[],
+        ),
+    };
+    disable_attestation(&mut model, payload.as_bytes())
+        .unwrap()
+        .expect("We expected a response");
+}
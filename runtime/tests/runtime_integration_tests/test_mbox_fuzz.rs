@@ -0,0 +1,244 @@
+// Licensed under the Apache-2.0 license
+
+use caliptra_api::SocManager;
+use caliptra_builder::{
+    firmware::{APP_WITH_UART, FMC_WITH_UART},
+    ImageOptions,
+};
+use caliptra_common::mailbox_api::CommandId;
+use caliptra_hw_model::{
+    mbox_fuzz::{MboxFuzzAction, MboxFuzzParams, MboxFuzzer},
+    BootParams, DefaultHwModel, DeviceLifecycle, Fuses, HwModel, InitParams, ModelError,
+    SecurityState, VaultEvent,
+};
+use openssl::sha::sha384;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use zerocopy::IntoBytes;
+
+/// Seconds to fuzz each security state for. Without it, a fixed number of
+/// actions is run.
+const FUZZ_SECS_ENV: &str = "CPTRA_RT_FUZZ_SECS";
+
+/// Seed of the run, printed at the start of every run to reproduce failures
+const FUZZ_SEED_ENV: &str = "CPTRA_RT_FUZZ_SEED";
+
+/// KeyVault slots holding the UDS, field entropy, FMC CDI and FMC Alias key,
+/// which the runtime must never write
+const ROM_KEY_SLOTS: [u32; 4] = [0, 1, 6, 7];
+
+/// Commands to generate well-formed requests for. FIRMWARE_LOAD, SHUTDOWN,
+/// ZEROIZE, INJECT_ERROR, INCREMENT_FUSE_SVN, RESEED_FIELD_ENTROPY and
+/// PREPARE_FOR_SLEEP are left out: they end the runtime session by design.
+const COMMANDS: [CommandId; 62] = [
+    CommandId::GET_IDEV_CERT,
+    CommandId::GET_IDEV_INFO,
+    CommandId::POPULATE_IDEV_CERT,
+    CommandId::GET_LDEV_CERT,
+    CommandId::GET_FMC_ALIAS_CERT,
+    CommandId::GET_RT_ALIAS_CERT,
+    CommandId::ECDSA384_VERIFY,
+    CommandId::LMS_VERIFY,
+    CommandId::STASH_MEASUREMENT,
+    CommandId::INVOKE_DPE,
+    CommandId::DISABLE_ATTESTATION,
+    CommandId::FW_INFO,
+    CommandId::DPE_TAG_TCI,
+    CommandId::DPE_GET_TAGGED_TCI,
+    CommandId::INCREMENT_PCR_RESET_COUNTER,
+    CommandId::QUOTE_PCRS,
+    CommandId::EXTEND_PCR,
+    CommandId::ADD_SUBJECT_ALT_NAME,
+    CommandId::CERTIFY_KEY_EXTENDED,
+    CommandId::VERSION,
+    CommandId::SELF_TEST_START,
+    CommandId::SELF_TEST_GET_RESULTS,
+    CommandId::CAPABILITIES,
+    CommandId::SET_AUTH_MANIFEST,
+    CommandId::AUTHORIZE_AND_STASH,
+    CommandId::GET_IDEV_CSR,
+    CommandId::GET_FMC_ALIAS_CSR,
+    CommandId::GET_RT_ALIAS_CSR,
+    CommandId::SIGN_WITH_EXPORTED_ECDSA,
+    CommandId::REVOKE_LOCALITY,
+    CommandId::GET_UPDATE_HISTORY,
+    CommandId::COMMIT_OWNER_KEY_ROTATION,
+    CommandId::SET_CERT_POLICY,
+    CommandId::SET_CERT_DIST_POINTS,
+    CommandId::GET_KV_STATUS,
+    CommandId::GET_RAW_ENTROPY,
+    CommandId::START_JOB,
+    CommandId::GET_JOB_STATUS,
+    CommandId::BATCH,
+    CommandId::SUSPEND_PREPARE,
+    CommandId::RESUME_NOTIFY,
+    CommandId::CONFIGURE_WDT,
+    CommandId::SET_OBJECT_SCHEMAS,
+    CommandId::VERIFY_OBJECT,
+    CommandId::GET_CRASH_DUMP,
+    CommandId::SET_LOG_LEVEL,
+    CommandId::GET_BOOT_PROFILE,
+    CommandId::GET_ENTROPY_HEALTH,
+    CommandId::GET_FHT,
+    CommandId::SPDM,
+    CommandId::MCTP,
+    CommandId::SET_MEASUREMENT_MANIFEST,
+    CommandId::START_STREAM_VERIFY,
+    CommandId::STREAM_DATA,
+    CommandId::FINISH_STREAM_VERIFY,
+    CommandId::SEAL_KEY,
+    CommandId::UNSEAL_KEY,
+    CommandId::CREATE_COUNTER,
+    CommandId::INCREMENT_COUNTER,
+    CommandId::READ_COUNTER,
+    CommandId::TIMESTAMP,
+    CommandId::PAIR_MAILBOX,
+];
+
+fn swap_word_bytes_inplace(words: &mut [u32]) {
+    for word in words.iter_mut() {
+        *word = word.swap_bytes()
+    }
+}
+
+fn bytes_to_be_words_48(buf: &[u8; 48]) -> [u32; 12] {
+    let mut result: [u32; 12] = zerocopy::transmute!(*buf);
+    swap_word_bytes_inplace(&mut result);
+    result
+}
+
+fn boot(security_state: SecurityState) -> (DefaultHwModel, Fuses) {
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    let mut image_options = ImageOptions::default();
+    image_options.vendor_config.pl0_pauser = Some(0x1);
+    let image =
+        caliptra_builder::build_and_sign_image(&FMC_WITH_UART, &APP_WITH_UART, image_options)
+            .unwrap();
+
+    let fuses = Fuses {
+        key_manifest_pk_hash: bytes_to_be_words_48(&sha384(
+            image.manifest.preamble.vendor_pub_keys.as_bytes(),
+        )),
+        owner_pk_hash: bytes_to_be_words_48(&sha384(
+            image.manifest.preamble.owner_pub_keys.as_bytes(),
+        )),
+        ..Default::default()
+    };
+    let mut model = caliptra_hw_model::new(
+        InitParams {
+            rom: &rom,
+            security_state,
+            ..Default::default()
+        },
+        BootParams {
+            fuses: fuses.clone(),
+            fw_image: Some(&image.to_bytes().unwrap()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+    (model, fuses)
+}
+
+/// Check that the runtime did not raise a fatal error (which is how a
+/// runtime panic is reported) and did not overwrite the ROM's keys.
+fn check_invariants(model: &mut DefaultHwModel, context: &dyn Fn() -> String) {
+    let fatal = model.soc_ifc().cptra_fw_error_fatal().read();
+    assert_eq!(fatal, 0, "Fatal error 0x{fatal:08x} after {}", context());
+
+    for event in model.take_vault_events() {
+        if let VaultEvent::KeyWrite { slot, .. } = event {
+            assert!(
+                !ROM_KEY_SLOTS.contains(&slot),
+                "Runtime wrote KeyVault slot {slot} after {}",
+                context()
+            );
+        }
+    }
+}
+
+fn fuzz(security_state: SecurityState, seed: u64, duration: Option<Duration>) {
+    let (mut model, fuses) = boot(security_state);
+    // Keys written by ROM and FMC are not the runtime's doing
+    model.take_vault_events();
+
+    let commands = COMMANDS.iter().map(|cmd| cmd.0).collect();
+    let mut fuzzer = MboxFuzzer::new(MboxFuzzParams {
+        seed,
+        commands,
+        ..Default::default()
+    });
+
+    let action_count = if cfg!(feature = "slow_tests") {
+        1000
+    } else {
+        25
+    };
+    let start = Instant::now();
+    let mut count = 0;
+    loop {
+        match duration {
+            Some(duration) if start.elapsed() >= duration => break,
+            None if count >= action_count => break,
+            _ => {}
+        }
+        count += 1;
+
+        let action = fuzzer.next_action();
+        let context = || format!("action #{count} {action:x?} (seed {seed:#x})");
+        match &action {
+            MboxFuzzAction::Execute { cmd, payload } => {
+                match model.mailbox_execute(*cmd, payload) {
+                    Ok(_) | Err(ModelError::MailboxCmdFailed(_)) => {}
+                    Err(e) => panic!("Unexpected error {e} for {}", context()),
+                }
+                check_invariants(&mut model, &context);
+            }
+            MboxFuzzAction::SetApbPauser(pauser) => model.set_apb_pauser(*pauser),
+            MboxFuzzAction::WarmReset => {
+                model.warm_reset_flow(&fuses);
+                model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+                let fatal = model.soc_ifc().cptra_fw_error_fatal().read();
+                assert_eq!(fatal, 0, "Fatal error 0x{fatal:08x} after {}", context());
+                model.take_vault_events();
+            }
+        }
+    }
+    println!("Ran {count} fuzz actions with {security_state:?}");
+}
+
+/// Run a random stream of well-formed and garbage mailbox commands and warm
+/// resets against the runtime in each security state.
+///
+/// By default this runs a short smoke test, or 1000 actions per security state
+/// with the "slow_tests" feature. Set `CPTRA_RT_FUZZ_SECS` to fuzz for a fixed
+/// time instead, and `CPTRA_RT_FUZZ_SEED` to replay a failing run.
+#[test]
+fn test_mbox_fuzz() {
+    // Only the emulator can observe vault mutations
+    #![cfg_attr(any(feature = "verilator", feature = "fpga_realtime"), ignore)]
+
+    let seed = match std::env::var(FUZZ_SEED_ENV) {
+        Ok(seed) => u64::from_str_radix(seed.trim_start_matches("0x"), 16).unwrap(),
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64,
+    };
+    let duration = std::env::var(FUZZ_SECS_ENV)
+        .ok()
+        .map(|secs| Duration::from_secs(secs.parse().unwrap()));
+    println!("Fuzzing the runtime mailbox with {FUZZ_SEED_ENV}={seed:#x}");
+
+    let security_states = [
+        *SecurityState::default()
+            .set_debug_locked(false)
+            .set_device_lifecycle(DeviceLifecycle::Manufacturing),
+        *SecurityState::default()
+            .set_debug_locked(true)
+            .set_device_lifecycle(DeviceLifecycle::Production),
+    ];
+    for security_state in security_states {
+        fuzz(security_state, seed, duration);
+    }
+}
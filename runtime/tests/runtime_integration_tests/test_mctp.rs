@@ -0,0 +1,325 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_builder::{
+    firmware::{APP_WITH_UART, FMC_WITH_UART},
+    ImageOptions,
+};
+use caliptra_common::mailbox_api::{
+    CommandId, GetUpdateHistoryResp, MailboxReq, MailboxReqHeader, MctpReq, MctpResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::{FromBytes, IntoBytes};
+
+/// Transport header from EID 0x08 to EID 0x1d with SOM, EOM, TO and tag 3
+const REQ_HDR: [u8; 4] = [0x01, 0x1d, 0x08, 0xcb];
+
+/// Transport header of the response to `REQ_HDR`
+const RESP_HDR: [u8; 4] = [0x01, 0x08, 0x1d, 0xc3];
+
+fn mctp_req(packet: &[u8]) -> MailboxReq {
+    let mut req = MctpReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        data_size: packet.len() as u32,
+        ..Default::default()
+    };
+    req.data[..packet.len()].copy_from_slice(packet);
+    let mut cmd = MailboxReq::Mctp(req);
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+fn mctp(model: &mut DefaultHwModel, packet: &[u8]) -> Result<Vec<u8>, ModelError> {
+    let cmd = mctp_req(packet);
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::MCTP), cmd.as_bytes().unwrap())?
+        .unwrap();
+    let mut mctp_resp = MctpResp::default();
+    mctp_resp.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
+    Ok(mctp_resp.data[..mctp_resp.data_size as usize].to_vec())
+}
+
+/// Send `msg` from `REQ_HDR`, returning the response message
+fn message(model: &mut DefaultHwModel, msg: &[u8]) -> Vec<u8> {
+    let resp = mctp(model, &[&REQ_HDR[..], msg].concat()).unwrap();
+    assert_eq!(resp[..4], RESP_HDR);
+    resp[4..].to_vec()
+}
+
+#[test]
+fn test_mctp_control() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // Get Message Type Support
+    assert_eq!(
+        message(&mut model, &[0x00, 0x81, 0x05]),
+        [0x00, 0x01, 0x05, 0x00, 2, 0x01, 0x05]
+    );
+
+    // Get MCTP Version Support of the base specification and of SPDM
+    assert_eq!(
+        message(&mut model, &[0x00, 0x82, 0x04, 0xFF]),
+        [0x00, 0x02, 0x04, 0x00, 1, 0xF1, 0xF3, 0xF1, 0x00]
+    );
+    assert_eq!(
+        message(&mut model, &[0x00, 0x83, 0x04, 0x05]),
+        [0x00, 0x03, 0x04, 0x00, 2, 0xF1, 0xF2, 0xF0, 0x00, 0xF1, 0xF3, 0xF0, 0x00]
+    );
+    assert_eq!(
+        message(&mut model, &[0x00, 0x84, 0x04, 0x7E]),
+        [0x00, 0x04, 0x04, 0x80]
+    );
+
+    // Set Endpoint ID is not supported
+    assert_eq!(
+        message(&mut model, &[0x00, 0x85, 0x01, 0x00, 0x1d]),
+        [0x00, 0x05, 0x01, 0x05]
+    );
+}
+
+#[test]
+fn test_mctp_pldm() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // GetPLDMTypes
+    let resp = message(&mut model, &[0x01, 0x81, 0x00, 0x04]);
+    assert_eq!(resp[..5], [0x01, 0x01, 0x00, 0x04, 0x00]);
+    assert_eq!(resp[5..], [0x21, 0, 0, 0, 0, 0, 0, 0]);
+
+    // GetPLDMCommands of the base type, then of the platform monitoring type
+    let resp = message(
+        &mut model,
+        &[0x01, 0x82, 0x00, 0x05, 0x00, 0xF1, 0xF1, 0xF0, 0x00],
+    );
+    assert_eq!(resp[..5], [0x01, 0x02, 0x00, 0x05, 0x00]);
+    assert_eq!(resp.len(), 5 + 32);
+    assert_eq!(resp[5], 0x30);
+    assert_eq!(
+        message(
+            &mut model,
+            &[0x01, 0x83, 0x00, 0x05, 0x02, 0xF1, 0xF1, 0xF0, 0x00]
+        ),
+        [0x01, 0x03, 0x00, 0x05, 0x83]
+    );
+
+    // GetTID is not supported, nor is any type but base
+    assert_eq!(
+        message(&mut model, &[0x01, 0x84, 0x00, 0x02]),
+        [0x01, 0x04, 0x00, 0x02, 0x05]
+    );
+    assert_eq!(
+        message(&mut model, &[0x01, 0x85, 0x02, 0x01]),
+        [0x01, 0x05, 0x02, 0x01, 0x20]
+    );
+}
+
+/// Send the PLDM firmware update command `cmd`, returning the completion
+/// code followed by the response data
+fn fw_update(model: &mut DefaultHwModel, cmd: u8, data: &[u8]) -> Vec<u8> {
+    let resp = message(model, &[&[0x01, 0x80, 0x05, cmd][..], data].concat());
+    assert_eq!(resp[..4], [0x01, 0x00, 0x05, cmd]);
+    resp[4..].to_vec()
+}
+
+#[test]
+fn test_mctp_pldm_fw_update() {
+    const REQUEST_UPDATE: u8 = 0x10;
+    const PASS_COMPONENT_TABLE: u8 = 0x13;
+    const UPDATE_COMPONENT: u8 = 0x14;
+    const REQUEST_FIRMWARE_DATA: u8 = 0x15;
+    const ACTIVATE_FIRMWARE: u8 = 0x1A;
+    const CANCEL_UPDATE: u8 = 0x1D;
+    const CHUNK_SIZE: usize = 248;
+
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    let image = caliptra_builder::build_and_sign_image(
+        &FMC_WITH_UART,
+        &APP_WITH_UART,
+        ImageOptions {
+            app_svn: 2,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .to_bytes()
+    .unwrap();
+
+    // GetPLDMCommands of the firmware update type
+    let resp = message(
+        &mut model,
+        &[0x01, 0x81, 0x00, 0x05, 0x05, 0xF1, 0xF1, 0xF0, 0x00],
+    );
+    assert_eq!(resp[..5], [0x01, 0x01, 0x00, 0x05, 0x00]);
+    assert_eq!(resp[5 + 2..5 + 4], [0x39, 0x24]);
+
+    let request_update = [
+        &0x100_u32.to_le_bytes()[..],
+        &1_u16.to_le_bytes(),
+        &[1],
+        &0_u16.to_le_bytes(),
+        &[1, 4],
+        b"v2.0",
+    ]
+    .concat();
+    let component = [&0x000A_u16.to_le_bytes()[..], &0_u16.to_le_bytes(), &[0]].concat();
+    let pass_component_table = [&[0x05][..], &component, &[0; 4], &[1, 4], b"v2.0"].concat();
+    let update_component = [
+        &component[..],
+        &[0; 4],
+        &(image.len() as u32).to_le_bytes(),
+        &[0; 4],
+        &[1, 4],
+        b"v2.0",
+    ]
+    .concat();
+
+    // Out of order and malformed commands
+    assert_eq!(fw_update(&mut model, ACTIVATE_FIRMWARE, &[0]), [0x80]);
+    assert_eq!(
+        fw_update(&mut model, REQUEST_UPDATE, &request_update[1..]),
+        [0x03]
+    );
+    assert_eq!(
+        fw_update(&mut model, REQUEST_UPDATE, &request_update),
+        [0x00, 0, 0, 0]
+    );
+    assert_eq!(
+        fw_update(&mut model, REQUEST_UPDATE, &request_update),
+        [0x81]
+    );
+    assert_eq!(
+        fw_update(&mut model, UPDATE_COMPONENT, &update_component),
+        [0x84]
+    );
+
+    // A canceled update starts over
+    assert_eq!(fw_update(&mut model, CANCEL_UPDATE, &[]), [0x00; 10]);
+    assert_eq!(
+        fw_update(&mut model, PASS_COMPONENT_TABLE, &pass_component_table),
+        [0x80]
+    );
+    assert_eq!(
+        fw_update(&mut model, REQUEST_UPDATE, &request_update),
+        [0x00, 0, 0, 0]
+    );
+
+    assert_eq!(
+        fw_update(&mut model, PASS_COMPONENT_TABLE, &pass_component_table),
+        [0x00, 0, 0]
+    );
+    assert_eq!(
+        fw_update(&mut model, UPDATE_COMPONENT, &update_component),
+        [0x00; 9]
+    );
+
+    let request_data = |offset: usize, chunk: &[u8]| {
+        [
+            &(offset as u32).to_le_bytes()[..],
+            &(chunk.len() as u32).to_le_bytes(),
+            chunk,
+        ]
+        .concat()
+    };
+    let mut chunks = image.chunks(CHUNK_SIZE).enumerate();
+    let (_, first) = chunks.next().unwrap();
+    assert_eq!(
+        fw_update(&mut model, REQUEST_FIRMWARE_DATA, &request_data(0, first)),
+        [0x00]
+    );
+    // Gaps, unaligned chunks and data past the image are refused
+    for (offset, chunk) in [
+        (2 * CHUNK_SIZE, &image[..4]),
+        (2, &image[2..6]),
+        (image.len() - 4, &image[..8]),
+    ] {
+        assert_eq!(
+            fw_update(
+                &mut model,
+                REQUEST_FIRMWARE_DATA,
+                &request_data(offset, chunk)
+            ),
+            [0x82]
+        );
+    }
+    assert_eq!(fw_update(&mut model, ACTIVATE_FIRMWARE, &[0]), [0x85]);
+
+    for (i, chunk) in chunks {
+        assert_eq!(
+            fw_update(
+                &mut model,
+                REQUEST_FIRMWARE_DATA,
+                &request_data(i * CHUNK_SIZE, chunk)
+            ),
+            [0x00]
+        );
+    }
+
+    // ROM completes the activation without a response
+    let cmd = mctp_req(&[&REQ_HDR[..], &[0x01, 0x80, 0x05, ACTIVATE_FIRMWARE, 0]].concat());
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::MCTP), cmd.as_bytes().unwrap())
+        .unwrap();
+    assert_eq!(resp, None);
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::GET_UPDATE_HISTORY),
+            &[],
+        ),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_UPDATE_HISTORY), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    let history = GetUpdateHistoryResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(history.total_updates, 1);
+    assert_eq!(history.entries[0].new_svn, 2);
+}
+
+#[test]
+fn test_mctp_spdm() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_eq!(
+        message(&mut model, &[0x05, 0x10, 0x84, 0, 0]),
+        [0x05, 0x10, 0x04, 0, 0, 0, 2, 0x00, 0x12, 0x00, 0x13]
+    );
+    // The connection is shared with the SPDM command
+    assert_eq!(
+        message(&mut model, &[0x05, 0x13, 0x81, 0, 0]),
+        [0x05, 0x10, 0x7F, 0x04, 0]
+    );
+}
+
+#[test]
+fn test_mctp_invalid_packets() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let invalid = [
+        // Too short
+        &[0x01, 0x1d, 0x08, 0xcb][..],
+        // Wrong header version
+        &[0x02, 0x1d, 0x08, 0xcb, 0x00, 0x81, 0x05],
+        // Fragmented
+        &[0x01, 0x1d, 0x08, 0x8b, 0x00, 0x81, 0x05],
+        // Not a request
+        &[0x01, 0x1d, 0x08, 0xc3, 0x00, 0x81, 0x05],
+        // Message integrity check
+        &[0x01, 0x1d, 0x08, 0xcb, 0x85, 0x10, 0x84, 0, 0],
+        // A control response
+        &[0x01, 0x1d, 0x08, 0xcb, 0x00, 0x01, 0x05, 0x00],
+    ];
+    for packet in invalid {
+        let err = mctp(&mut model, packet).unwrap_err();
+        assert_error(&mut model, CaliptraError::RUNTIME_MCTP_INVALID_PACKET, err);
+    }
+
+    // Vendor defined messages have no handler
+    let err = mctp(&mut model, &[0x01, 0x1d, 0x08, 0xcb, 0x7E, 0x00]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MCTP_UNSUPPORTED_MSG_TYPE,
+        err,
+    );
+}
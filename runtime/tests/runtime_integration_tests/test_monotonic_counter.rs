@@ -0,0 +1,115 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use crate::test_update_reset::update_fw;
+use caliptra_builder::{firmware::APP_WITH_UART, ImageOptions};
+use caliptra_common::mailbox_api::{
+    CommandId, CreateCounterResp, IncrementCounterReq, IncrementCounterResp, MailboxReq,
+    MailboxReqHeader, ReadCounterReq, ReadCounterResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::{FromBytes, IntoBytes};
+
+pub fn create_counter(model: &mut DefaultHwModel) -> Result<CreateCounterResp, ModelError> {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::CREATE_COUNTER), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::CREATE_COUNTER), payload.as_bytes())?
+        .expect("We expected a response");
+    Ok(CreateCounterResp::read_from_bytes(resp.as_slice()).unwrap())
+}
+
+fn increment_counter(model: &mut DefaultHwModel, counter_id: u32) -> Result<u32, ModelError> {
+    let mut cmd = MailboxReq::IncrementCounter(IncrementCounterReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        counter_id,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::INCREMENT_COUNTER),
+            cmd.as_bytes().unwrap(),
+        )?
+        .expect("We expected a response");
+    Ok(IncrementCounterResp::read_from_bytes(resp.as_slice())
+        .unwrap()
+        .value)
+}
+
+fn read_counter(model: &mut DefaultHwModel, counter_id: u32) -> Result<u32, ModelError> {
+    let mut cmd = MailboxReq::ReadCounter(ReadCounterReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        counter_id,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::READ_COUNTER), cmd.as_bytes().unwrap())?
+        .expect("We expected a response");
+    Ok(ReadCounterResp::read_from_bytes(resp.as_slice())
+        .unwrap()
+        .value)
+}
+
+#[test]
+fn test_monotonic_counter() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = create_counter(&mut model).unwrap();
+    assert_eq!(resp.counter_id, 0);
+    assert_eq!(resp.value, 0);
+    assert_eq!(read_counter(&mut model, 0).unwrap(), 0);
+
+    assert_eq!(increment_counter(&mut model, 0).unwrap(), 1);
+    assert_eq!(increment_counter(&mut model, 0).unwrap(), 2);
+    assert_eq!(read_counter(&mut model, 0).unwrap(), 2);
+
+    // Counters are independent
+    let resp = create_counter(&mut model).unwrap();
+    assert_eq!(resp.counter_id, 1);
+    assert_eq!(increment_counter(&mut model, 1).unwrap(), 1);
+    assert_eq!(read_counter(&mut model, 0).unwrap(), 2);
+}
+
+#[test]
+fn test_monotonic_counter_unavailable() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    for counter_id in 0..CreateCounterResp::MAX_COUNTERS as u32 {
+        assert_eq!(create_counter(&mut model).unwrap().counter_id, counter_id);
+    }
+    let resp = create_counter(&mut model).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_COUNTER_UNAVAILABLE, resp);
+}
+
+#[test]
+fn test_monotonic_counter_not_created() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = read_counter(&mut model, 0).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_COUNTER_NOT_CREATED, resp);
+    let resp = increment_counter(&mut model, 0).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_COUNTER_NOT_CREATED, resp);
+
+    let resp = read_counter(&mut model, CreateCounterResp::MAX_COUNTERS as u32).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+        resp,
+    );
+}
+
+#[test]
+fn test_monotonic_counter_update_reset() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    create_counter(&mut model).unwrap();
+    increment_counter(&mut model, 0).unwrap();
+
+    update_fw(&mut model, &APP_WITH_UART, ImageOptions::default());
+
+    assert_eq!(read_counter(&mut model, 0).unwrap(), 1);
+    assert_eq!(increment_counter(&mut model, 0).unwrap(), 2);
+    assert_eq!(create_counter(&mut model).unwrap().counter_id, 1);
+}
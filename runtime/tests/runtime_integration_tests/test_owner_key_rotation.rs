@@ -0,0 +1,142 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use crate::test_update_reset::update_fw;
+use caliptra_api::SocManager;
+use caliptra_builder::{
+    firmware::{APP_WITH_UART, FMC_WITH_UART},
+    ImageOptions,
+};
+use caliptra_common::mailbox_api::{
+    CommandId, CommitOwnerKeyRotationReq, MailboxReq, MailboxReqHeader,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_image_fake_keys::{
+    OWNER_CONFIG, VENDOR_ECC_KEY_1_PRIVATE, VENDOR_ECC_KEY_1_PUBLIC, VENDOR_LMS_KEY_1_PRIVATE,
+    VENDOR_LMS_KEY_1_PUBLIC,
+};
+use caliptra_image_gen::ImageGeneratorOwnerConfig;
+use caliptra_image_types::{ImageOwnerPrivKeys, ImageOwnerPubKeys};
+
+const NEXT_OWNER_PUB_KEYS: ImageOwnerPubKeys = ImageOwnerPubKeys {
+    ecc_pub_key: VENDOR_ECC_KEY_1_PUBLIC,
+    lms_pub_key: VENDOR_LMS_KEY_1_PUBLIC,
+};
+
+fn image_options(owner_config: ImageGeneratorOwnerConfig) -> ImageOptions {
+    let mut opts = ImageOptions::default();
+    opts.vendor_config.pl0_pauser = Some(0x1);
+    opts.owner_config = Some(owner_config);
+    opts
+}
+
+/// Image signed by the current owner key that authorizes rotating to the
+/// next owner key
+fn rotating_image_options() -> ImageOptions {
+    image_options(ImageGeneratorOwnerConfig {
+        next_owner_pub_keys: Some(NEXT_OWNER_PUB_KEYS),
+        ..OWNER_CONFIG
+    })
+}
+
+/// Image signed by the next owner key
+fn next_owner_image_options() -> ImageOptions {
+    image_options(ImageGeneratorOwnerConfig {
+        pub_keys: NEXT_OWNER_PUB_KEYS,
+        priv_keys: Some(ImageOwnerPrivKeys {
+            ecc_priv_key: VENDOR_ECC_KEY_1_PRIVATE,
+            lms_priv_key: VENDOR_LMS_KEY_1_PRIVATE,
+        }),
+        ..OWNER_CONFIG
+    })
+}
+
+fn next_owner_pub_keys_digest() -> [u32; 12] {
+    caliptra_builder::build_and_sign_image(&FMC_WITH_UART, &APP_WITH_UART, rotating_image_options())
+        .unwrap()
+        .manifest
+        .header
+        .owner_data
+        .next_owner_pub_keys_digest
+}
+
+fn commit_owner_key_rotation(
+    model: &mut DefaultHwModel,
+    owner_pub_keys_digest: [u32; 12],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::CommitOwnerKeyRotation(CommitOwnerKeyRotationReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        owner_pub_keys_digest,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::COMMIT_OWNER_KEY_ROTATION),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+#[test]
+fn test_owner_key_rotation() {
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(rotating_image_options()),
+        ..Default::default()
+    });
+    model.step_until_mailbox_idle().unwrap();
+
+    let digest = next_owner_pub_keys_digest();
+    assert_ne!(digest, [0; 12]);
+
+    let resp = commit_owner_key_rotation(&mut model, [0xa5; 12]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_OWNER_KEY_ROTATION_DIGEST_MISMATCH,
+        resp,
+    );
+
+    // The running image is still signed by the current owner key
+    let resp = commit_owner_key_rotation(&mut model, digest).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_OWNER_KEY_ROTATION_KEY_NOT_IN_USE,
+        resp,
+    );
+
+    // An image signed by the next owner key is accepted on update reset
+    update_fw(&mut model, &APP_WITH_UART, next_owner_image_options());
+    commit_owner_key_rotation(&mut model, digest)
+        .unwrap()
+        .expect("We expected a response");
+
+    // ROM commits the rotation on the next update reset, before verifying the
+    // new image, so an image signed by the previous owner key is rejected
+    let image = caliptra_builder::build_and_sign_image(
+        &FMC_WITH_UART,
+        &APP_WITH_UART,
+        rotating_image_options(),
+    )
+    .unwrap()
+    .to_bytes()
+    .unwrap();
+    model
+        .start_mailbox_execute(u32::from(CommandId::FIRMWARE_LOAD), &image)
+        .unwrap();
+    model.step_until(|m| m.soc_ifc().cptra_fw_error_non_fatal().read() != 0);
+    assert_eq!(
+        model.soc_ifc().cptra_fw_error_non_fatal().read(),
+        u32::from(CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_OWNER_DIGEST_FAILURE)
+    );
+}
+
+#[test]
+fn test_owner_key_rotation_not_pending() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until_mailbox_idle().unwrap();
+
+    let resp = commit_owner_key_rotation(&mut model, next_owner_pub_keys_digest()).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_OWNER_KEY_ROTATION_NOT_PENDING,
+        resp,
+    );
+}
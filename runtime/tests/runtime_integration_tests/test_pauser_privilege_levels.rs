@@ -629,6 +629,7 @@ fn test_pl0_unset_in_header() {
                 .unwrap(),
                 vendor_config: opts.vendor_config,
                 owner_config: opts.owner_config,
+                manifest_format: opts.manifest_format,
             },
             ecc_index,
             lms_index,
@@ -1,11 +1,11 @@
 // Licensed under the Apache-2.0 license
 
-use crate::common::{get_fmc_alias_cert, run_rt_test, RuntimeTestArgs};
+use crate::common::{get_fmc_alias_cert, get_rt_alias_cert, run_rt_test, RuntimeTestArgs};
 use caliptra_api::SocManager;
 
 use caliptra_common::mailbox_api::{
     CommandId, ExtendPcrReq, IncrementPcrResetCounterReq, MailboxReq, MailboxReqHeader,
-    QuotePcrsReq, QuotePcrsResp,
+    MailboxReqVersionedHeader, QuotePcrsCwtReq, QuotePcrsCwtResp, QuotePcrsReq, QuotePcrsResp,
 };
 use caliptra_drivers::PcrId;
 use caliptra_error::CaliptraError;
@@ -24,7 +24,7 @@ fn test_pcr_quote() {
 
     const RESET_PCR: u32 = 7;
 
-    model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
+    model.step_until_mailbox_idle().unwrap();
 
     let mut cmd = MailboxReq::IncrementPcrResetCounter(IncrementPcrResetCounterReq {
         hdr: MailboxReqHeader { chksum: 0 },
@@ -77,6 +77,146 @@ fn test_pcr_quote() {
     assert!(sig.verify(&resp.digest, &pkey).unwrap());
 }
 
+/// Minimal CBOR reader for the items of the CWTs returned by the runtime
+pub struct CborReader<'a> {
+    pub buf: &'a [u8],
+}
+
+impl<'a> CborReader<'a> {
+    pub fn head(&mut self) -> (u8, u64) {
+        let initial = self.buf[0];
+        let (value, len) = match initial & 0x1f {
+            info @ 0..=23 => (info as u64, 1),
+            24 => (self.buf[1] as u64, 2),
+            25 => (
+                u16::from_be_bytes(self.buf[1..3].try_into().unwrap()) as u64,
+                3,
+            ),
+            26 => (
+                u32::from_be_bytes(self.buf[1..5].try_into().unwrap()) as u64,
+                5,
+            ),
+            27 => (u64::from_be_bytes(self.buf[1..9].try_into().unwrap()), 9),
+            _ => panic!("Unsupported CBOR item {initial:#x}"),
+        };
+        self.buf = &self.buf[len..];
+        (initial >> 5, value)
+    }
+
+    pub fn expect(&mut self, major: u8) -> u64 {
+        let (actual, value) = self.head();
+        assert_eq!(actual, major);
+        value
+    }
+
+    pub fn int(&mut self) -> i64 {
+        match self.head() {
+            (0, value) => value as i64,
+            (1, value) => -1 - value as i64,
+            (major, _) => panic!("Expected an integer, got major type {major}"),
+        }
+    }
+
+    pub fn bstr(&mut self) -> &'a [u8] {
+        let len = self.expect(2) as usize;
+        let (data, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        data
+    }
+}
+
+/// Check that `token` is a COSE_Sign1_Tagged CWT signed by the RT Alias key
+/// and return its payload
+pub fn verify_cwt<'a>(model: &mut DefaultHwModel, token: &'a [u8]) -> &'a [u8] {
+    // COSE_Sign1_Tagged: 18([protected, unprotected, payload, signature])
+    let mut reader = CborReader { buf: token };
+    assert_eq!(reader.head(), (6, 18));
+    assert_eq!(reader.expect(4), 4);
+    let protected = reader.bstr();
+    assert_eq!(protected, [0xa1, 0x01, 0x38, 0x22]);
+    assert_eq!(reader.expect(5), 0);
+    let payload_item = reader.buf;
+    let payload = reader.bstr();
+    let payload_item = &payload_item[..payload_item.len() - reader.buf.len()];
+    let signature = reader.bstr();
+    assert_eq!(signature.len(), 96);
+    assert!(reader.buf.is_empty());
+
+    // Sig_structure: ["Signature1", protected, external_aad, payload]
+    let mut h = Hasher::new(MessageDigest::sha384()).unwrap();
+    h.update(&[0x84, 0x6a]).unwrap();
+    h.update(b"Signature1").unwrap();
+    h.update(&[0x44]).unwrap();
+    h.update(protected).unwrap();
+    h.update(&[0x40]).unwrap();
+    h.update(payload_item).unwrap();
+    let digest = h.finish().unwrap();
+
+    let sig = EcdsaSig::from_private_components(
+        BigNum::from_slice(&signature[..48]).unwrap(),
+        BigNum::from_slice(&signature[48..]).unwrap(),
+    )
+    .unwrap();
+    let rt_resp = get_rt_alias_cert(model);
+    let rt_cert = X509::from_der(&rt_resp.data[..rt_resp.data_size as usize]).unwrap();
+    let pkey = rt_cert.public_key().unwrap().ec_key().unwrap();
+    assert!(sig.verify(&digest, &pkey).unwrap());
+
+    payload
+}
+
+#[test]
+fn test_pcr_quote_cwt() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    const NONCE: [u8; 32] = [0xa5; 32];
+
+    let mut cmd = MailboxReq::QuotePcrs(QuotePcrsReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        nonce: NONCE,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::QUOTE_PCRS), cmd.as_bytes().unwrap())
+        .unwrap()
+        .unwrap();
+    let quote = QuotePcrsResp::read_from_bytes(resp.as_slice()).unwrap();
+
+    let mut cmd = MailboxReq::QuotePcrsCwt(QuotePcrsCwtReq {
+        hdr: MailboxReqVersionedHeader {
+            version: QuotePcrsCwtReq::VERSION,
+            ..Default::default()
+        },
+        nonce: NONCE,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::QUOTE_PCRS), cmd.as_bytes().unwrap())
+        .unwrap()
+        .unwrap();
+    let mut cwt_resp = QuotePcrsCwtResp::default();
+    cwt_resp.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
+    let token = &cwt_resp.data[..cwt_resp.data_size as usize];
+    let payload = verify_cwt(&mut model, token);
+
+    let mut claims = CborReader { buf: payload };
+    assert_eq!(claims.expect(5), 4);
+    assert_eq!(claims.int(), QuotePcrsCwtResp::CLAIM_NONCE);
+    assert_eq!(claims.bstr(), NONCE);
+    assert_eq!(claims.int(), QuotePcrsCwtResp::CLAIM_UEID);
+    assert_eq!(claims.bstr().len(), 17);
+    assert_eq!(claims.int(), QuotePcrsCwtResp::CLAIM_PCRS);
+    assert_eq!(claims.expect(4), 32);
+    for pcr in quote.pcrs.iter() {
+        assert_eq!(claims.bstr(), *pcr);
+    }
+    assert_eq!(claims.int(), QuotePcrsCwtResp::CLAIM_PCR_RESET_COUNTERS);
+    assert_eq!(claims.expect(4), 32);
+    for ctr in quote.reset_ctrs.iter() {
+        assert_eq!(claims.expect(0), *ctr as u64);
+    }
+    assert!(claims.buf.is_empty());
+}
+
 fn generate_mailbox_extend_pcr_req(idx: u32, pcr_extension_data: [u8; 48]) -> MailboxReq {
     let mut cmd = MailboxReq::ExtendPcr(ExtendPcrReq {
         hdr: MailboxReqHeader { chksum: 0 },
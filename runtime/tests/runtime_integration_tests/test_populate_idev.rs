@@ -1,22 +1,29 @@
 // Licensed under the Apache-2.0 license
 
 use crate::common::{
-    execute_dpe_cmd, generate_test_x509_cert, run_rt_test, DpeResult, RuntimeTestArgs,
+    assert_error, execute_dpe_cmd, get_certs, run_rt_test, DpeResult, RuntimeTestArgs,
 };
+use crate::test_update_reset::update_fw;
 use caliptra_api::SocManager;
-use caliptra_common::mailbox_api::{CommandId, MailboxReq, MailboxReqHeader, PopulateIdevCertReq};
+use caliptra_builder::{firmware::APP_WITH_UART, ImageOptions};
+use caliptra_common::mailbox_api::{
+    CommandId, GetLdevCertReq, MailboxReq, MailboxReqHeader, PopulateIdevCertReq,
+};
 use caliptra_error::CaliptraError;
-use caliptra_hw_model::{DefaultHwModel, HwModel};
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
 use caliptra_runtime::RtBootStatus;
 use dpe::{
     commands::{Command, GetCertificateChainCmd},
     response::Response,
 };
 use openssl::{
+    asn1::{Asn1Integer, Asn1Time},
+    bn::BigNum,
     ec::{EcGroup, EcKey},
+    hash::MessageDigest,
     nid::Nid,
-    pkey::PKey,
-    x509::X509,
+    pkey::{PKey, Private},
+    x509::{X509Builder, X509Name, X509NameRef, X509},
 };
 
 fn get_full_cert_chain(model: &mut DefaultHwModel, out: &mut [u8; 4096]) -> usize {
@@ -56,6 +63,69 @@ fn get_full_cert_chain(model: &mut DefaultHwModel, out: &mut [u8; 4096]) -> usiz
     cert_chunk_1.certificate_size as usize + cert_chunk_2.certificate_size as usize
 }
 
+fn generate_key() -> PKey<Private> {
+    let ec_group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+    PKey::from_ec_key(EcKey::generate(&ec_group).unwrap()).unwrap()
+}
+
+fn cn_name(cn: &str) -> X509Name {
+    let mut builder = X509Name::builder().unwrap();
+    builder.append_entry_by_text("CN", cn).unwrap();
+    builder.build()
+}
+
+fn generate_ca_cert(
+    key: &PKey<Private>,
+    subject: &X509NameRef,
+    issuer: &X509NameRef,
+    issuer_key: &PKey<Private>,
+) -> X509 {
+    let mut cert_builder = X509Builder::new().unwrap();
+    cert_builder.set_version(2).unwrap();
+    cert_builder
+        .set_serial_number(&Asn1Integer::from_bn(&BigNum::from_u32(1).unwrap()).unwrap())
+        .unwrap();
+    cert_builder.set_subject_name(subject).unwrap();
+    cert_builder.set_issuer_name(issuer).unwrap();
+    cert_builder.set_pubkey(key).unwrap();
+    cert_builder
+        .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    cert_builder
+        .set_not_after(&Asn1Time::days_from_now(365).unwrap())
+        .unwrap();
+    cert_builder
+        .sign(issuer_key, MessageDigest::sha384())
+        .unwrap();
+    cert_builder.build()
+}
+
+fn ldevid_issuer(model: &mut DefaultHwModel) -> X509Name {
+    let resp = get_certs::<GetLdevCertReq>(model);
+    let ldev_cert = X509::from_der(&resp.data[..resp.data_size as usize]).unwrap();
+    ldev_cert.issuer_name().to_owned().unwrap()
+}
+
+fn populate_idev_cert(
+    model: &mut DefaultHwModel,
+    cert_bytes: &[u8],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cert_slice = [0u8; PopulateIdevCertReq::MAX_CERT_SIZE];
+    cert_slice[..cert_bytes.len()].copy_from_slice(cert_bytes);
+
+    let mut pop_idev_cmd = MailboxReq::PopulateIdevCert(PopulateIdevCertReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        cert_size: cert_bytes.len() as u32,
+        cert: cert_slice,
+    });
+    pop_idev_cmd.populate_chksum().unwrap();
+
+    model.mailbox_execute(
+        u32::from(CommandId::POPULATE_IDEV_CERT),
+        pop_idev_cmd.as_bytes().unwrap(),
+    )
+}
+
 // Will panic if any of the cert chain chunks is not a valid X.509 cert
 fn parse_cert_chain(cert_chain: &[u8], cert_chain_size: usize, expected_num_certs: u32) {
     let mut i = 0;
@@ -80,30 +150,13 @@ fn test_populate_idev_cert_cmd() {
     let cert_chain_len_without_idev_cert =
         get_full_cert_chain(&mut model, &mut cert_chain_without_idev_cert);
 
-    // generate test idev cert
-    let ec_group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
-    let ec_key = PKey::from_ec_key(EcKey::generate(&ec_group).unwrap()).unwrap();
-
-    let cert = generate_test_x509_cert(ec_key);
-
-    // copy der encoded idev cert
-    let cert_bytes = cert.to_der().unwrap();
-    let mut cert_slice = [0u8; PopulateIdevCertReq::MAX_CERT_SIZE];
-    cert_slice[..cert_bytes.len()].copy_from_slice(&cert_bytes);
-
-    let mut pop_idev_cmd = MailboxReq::PopulateIdevCert(PopulateIdevCertReq {
-        hdr: MailboxReqHeader { chksum: 0 },
-        cert_size: cert_bytes.len() as u32,
-        cert: cert_slice,
-    });
-    pop_idev_cmd.populate_chksum().unwrap();
+    // generate test idev cert issued to the issuer of the ldev cert
+    let ec_key = generate_key();
+    let subject = ldevid_issuer(&mut model);
+    let cert = generate_ca_cert(&ec_key, &subject, &subject, &ec_key);
 
     // call populate idev cert so that the idev cert is added to the certificate chain
-    model
-        .mailbox_execute(
-            u32::from(CommandId::POPULATE_IDEV_CERT),
-            pop_idev_cmd.as_bytes().unwrap(),
-        )
+    populate_idev_cert(&mut model, &cert.to_der().unwrap())
         .unwrap()
         .expect("We should have received a response");
 
@@ -144,3 +197,127 @@ fn test_populate_idev_cert_size_too_big() {
         Err(CaliptraError::RUNTIME_MAILBOX_API_REQUEST_DATA_LEN_TOO_LARGE)
     );
 }
+
+#[test]
+fn test_populate_idev_cert_chain() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let mut cert_chain_without_idev_certs = [0u8; 4096];
+    let cert_chain_len_without_idev_certs =
+        get_full_cert_chain(&mut model, &mut cert_chain_without_idev_certs);
+
+    // root -> intermediate -> idevid
+    let root_key = generate_key();
+    let root_name = cn_name("Test Root CA");
+    let root_cert = generate_ca_cert(&root_key, &root_name, &root_name, &root_key);
+
+    let intermediate_key = generate_key();
+    let intermediate_name = cn_name("Test Intermediate CA");
+    let intermediate_cert =
+        generate_ca_cert(&intermediate_key, &intermediate_name, &root_name, &root_key);
+
+    let idev_cert = generate_ca_cert(
+        &generate_key(),
+        &ldevid_issuer(&mut model),
+        &intermediate_name,
+        &intermediate_key,
+    );
+
+    // Certs are populated starting from the one closest to the ldev cert
+    let populated_certs: Vec<Vec<u8>> = [&idev_cert, &intermediate_cert, &root_cert]
+        .iter()
+        .map(|cert| cert.to_der().unwrap())
+        .collect();
+    for cert in populated_certs.iter() {
+        populate_idev_cert(&mut model, cert).unwrap();
+    }
+
+    // Nothing can be added above the root
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_ORDER,
+        populate_idev_cert(&mut model, &root_cert.to_der().unwrap()).unwrap_err(),
+    );
+
+    let expected_chain: Vec<u8> = populated_certs
+        .iter()
+        .rev()
+        .flatten()
+        .chain(&cert_chain_without_idev_certs[..cert_chain_len_without_idev_certs])
+        .copied()
+        .collect();
+
+    let mut cert_chain = [0u8; 4096];
+    let cert_chain_len = get_full_cert_chain(&mut model, &mut cert_chain);
+    assert_eq!(&cert_chain[..cert_chain_len], &expected_chain[..]);
+    parse_cert_chain(&cert_chain, cert_chain_len, 6);
+
+    // The populated certs are kept across an update reset
+    update_fw(&mut model, &APP_WITH_UART, ImageOptions::default());
+    let cert_chain_len = get_full_cert_chain(&mut model, &mut cert_chain);
+    assert_eq!(&cert_chain[..cert_chain_len], &expected_chain[..]);
+}
+
+#[test]
+fn test_populate_idev_cert_invalid_order() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    // The first cert must be issued to the issuer of the ldev cert
+    let key = generate_key();
+    let name = cn_name("example.com");
+    let cert = generate_ca_cert(&key, &name, &name, &key);
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_ORDER,
+        populate_idev_cert(&mut model, &cert.to_der().unwrap()).unwrap_err(),
+    );
+
+    // The next cert must be issued to the issuer of the previous cert
+    let idev_cert = generate_ca_cert(&key, &ldevid_issuer(&mut model), &name, &key);
+    populate_idev_cert(&mut model, &idev_cert.to_der().unwrap()).unwrap();
+
+    let other_name = cn_name("Other CA");
+    let cert = generate_ca_cert(&key, &other_name, &other_name, &key);
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_ORDER,
+        populate_idev_cert(&mut model, &cert.to_der().unwrap()).unwrap_err(),
+    );
+}
+
+#[test]
+fn test_populate_idev_cert_invalid_cert() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let key = generate_key();
+    let cert = generate_ca_cert(&key, &ldevid_issuer(&mut model), &cn_name("CA"), &key);
+    let mut cert_bytes = cert.to_der().unwrap();
+
+    // Trailing data after the cert
+    cert_bytes.push(0);
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_CERT,
+        populate_idev_cert(&mut model, &cert_bytes).unwrap_err(),
+    );
+
+    // Truncated cert
+    cert_bytes.truncate(cert_bytes.len() - 2);
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_IDEV_CERT_CHAIN_INVALID_CERT,
+        populate_idev_cert(&mut model, &cert_bytes).unwrap_err(),
+    );
+}
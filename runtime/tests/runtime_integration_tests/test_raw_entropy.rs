@@ -0,0 +1,58 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{CommandId, MailboxReqHeader};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, DeviceLifecycle, HwModel, InitParams, SecurityState};
+use zerocopy::IntoBytes;
+
+fn execute_header_only_cmd(
+    model: &mut DefaultHwModel,
+    cmd: CommandId,
+) -> Result<Option<Vec<u8>>, caliptra_hw_model::ModelError> {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(cmd), &[]),
+    };
+    model.mailbox_execute(u32::from(cmd), payload.as_bytes())
+}
+
+#[test]
+fn test_raw_entropy_not_manufacturing() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = execute_header_only_cmd(&mut model, CommandId::CAPTURE_RAW_ENTROPY).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_RAW_ENTROPY_NOT_MANUFACTURING,
+        resp,
+    );
+
+    let resp = execute_header_only_cmd(&mut model, CommandId::GET_RAW_ENTROPY).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_RAW_ENTROPY_NOT_MANUFACTURING,
+        resp,
+    );
+}
+
+#[test]
+fn test_raw_entropy_capture_not_started() {
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    let security_state =
+        *SecurityState::default().set_device_lifecycle(DeviceLifecycle::Manufacturing);
+    let mut model = run_rt_test(RuntimeTestArgs {
+        init_params: Some(InitParams {
+            rom: &rom,
+            security_state,
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let resp = execute_header_only_cmd(&mut model, CommandId::GET_RAW_ENTROPY).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_RAW_ENTROPY_CAPTURE_NOT_STARTED,
+        resp,
+    );
+}
@@ -0,0 +1,73 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_builder::ImageOptions;
+use caliptra_common::mailbox_api::{
+    CommandId, FwInfoV1Resp, IncrementFuseSvnResp, MailboxReqHeader, MailboxRespHeader,
+};
+use caliptra_drivers::{
+    FIELD_ENTROPY_RESEED_REQUEST, FIELD_ENTROPY_RESEED_REQUEST_WIRE, RUNTIME_SVN_FUSE_REQUEST_VALID,
+};
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use zerocopy::{FromBytes, IntoBytes};
+
+fn execute_no_payload(model: &mut DefaultHwModel, cmd: CommandId) -> Vec<u8> {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(cmd), &[]),
+    };
+    model
+        .mailbox_execute(u32::from(cmd), payload.as_bytes())
+        .unwrap()
+        .unwrap()
+}
+
+fn output_wire(model: &mut DefaultHwModel) -> u32 {
+    model
+        .soc_ifc()
+        .cptra_generic_output_wires()
+        .at(FIELD_ENTROPY_RESEED_REQUEST_WIRE)
+        .read()
+}
+
+#[test]
+fn test_reseed_field_entropy() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until_mailbox_idle().unwrap();
+    assert_eq!(output_wire(&mut model), 0);
+
+    let resp = execute_no_payload(&mut model, CommandId::RESEED_FIELD_ENTROPY);
+    MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(output_wire(&mut model), FIELD_ENTROPY_RESEED_REQUEST);
+
+    // The identities derived from the old field entropy are retired until the
+    // next cold reset
+    let resp = execute_no_payload(&mut model, CommandId::FW_INFO);
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(info.attestation_disabled, 1);
+}
+
+#[test]
+fn test_reseed_field_entropy_with_svn_request() {
+    let mut image_options = ImageOptions::default();
+    image_options.vendor_config.pl0_pauser = Some(0x1);
+    image_options.app_svn = 3;
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_options),
+        ..Default::default()
+    });
+
+    model.step_until_mailbox_idle().unwrap();
+
+    execute_no_payload(&mut model, CommandId::RESEED_FIELD_ENTROPY);
+    let resp = execute_no_payload(&mut model, CommandId::INCREMENT_FUSE_SVN);
+    let resp = IncrementFuseSvnResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(resp.new_fuse_svn, 3);
+
+    // Both requests share the output wire
+    assert_eq!(
+        output_wire(&mut model),
+        FIELD_ENTROPY_RESEED_REQUEST | RUNTIME_SVN_FUSE_REQUEST_VALID | 3
+    );
+}
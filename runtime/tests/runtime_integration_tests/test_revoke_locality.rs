@@ -0,0 +1,161 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, execute_dpe_cmd, run_rt_test, DpeResult, RuntimeTestArgs};
+use crate::test_update_reset::update_fw;
+use caliptra_builder::{
+    firmware::{runtime_tests::MBOX, APP_WITH_UART},
+    ImageOptions,
+};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, MailboxRespHeader, RevokeLocalityReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use caliptra_runtime::ContextState;
+use dpe::{
+    commands::{Command, DeriveContextCmd, DeriveContextFlags, RotateCtxCmd, RotateCtxFlags},
+    context::ContextHandle,
+    response::Response,
+    DpeInstance, DPE_PROFILE,
+};
+use zerocopy::{FromBytes, IntoBytes, TryFromBytes};
+
+const DATA: [u8; DPE_PROFILE.get_hash_size()] = [0u8; 48];
+const TENANT_LOCALITY: u32 = 2;
+
+fn revoke_locality(
+    model: &mut caliptra_hw_model::DefaultHwModel,
+    locality: u32,
+) -> Result<Option<Vec<u8>>, caliptra_hw_model::ModelError> {
+    let mut cmd = MailboxReq::RevokeLocality(RevokeLocalityReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        locality,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::REVOKE_LOCALITY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+/// Returns the number of active DPE contexts owned by `locality`. The model
+/// must be running the MBOX test firmware.
+fn active_contexts(model: &mut DefaultHwModel, locality: u32) -> usize {
+    let dpe_resp = model.mailbox_execute(0xA000_0000, &[]).unwrap().unwrap();
+    let dpe = DpeInstance::try_read_from_bytes(dpe_resp.as_bytes()).unwrap();
+    dpe.contexts
+        .iter()
+        .filter(|context| context.state != ContextState::Inactive && context.locality == locality)
+        .count()
+}
+
+#[test]
+fn test_revoke_locality() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // Rotate the default context so that it can be retained in derive context.
+    let rotate_ctx_cmd = RotateCtxCmd {
+        handle: ContextHandle::default(),
+        flags: RotateCtxFlags::empty(),
+    };
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::RotateCtx(&rotate_ctx_cmd),
+        DpeResult::Success,
+    );
+    let Some(Response::RotateCtx(rotate_ctx_resp)) = resp else {
+        panic!("Wrong response type!");
+    };
+
+    // Hand a new context off to the tenant locality
+    let derive_context_cmd = DeriveContextCmd {
+        handle: rotate_ctx_resp.handle,
+        data: DATA,
+        flags: DeriveContextFlags::RETAIN_PARENT_CONTEXT | DeriveContextFlags::CHANGE_LOCALITY,
+        tci_type: 0,
+        target_locality: TENANT_LOCALITY,
+    };
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::DeriveContext(&derive_context_cmd),
+        DpeResult::Success,
+    );
+    let Some(Response::DeriveContext(derive_context_resp)) = resp else {
+        panic!("Wrong response type!");
+    };
+
+    let resp = revoke_locality(&mut model, TENANT_LOCALITY)
+        .unwrap()
+        .expect("We expected a response");
+    let resp_hdr = MailboxRespHeader::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+
+    // Handing a context to the revoked locality must now fail
+    let derive_context_cmd = DeriveContextCmd {
+        handle: derive_context_resp.parent_handle,
+        data: DATA,
+        flags: DeriveContextFlags::RETAIN_PARENT_CONTEXT | DeriveContextFlags::CHANGE_LOCALITY,
+        tci_type: 0,
+        target_locality: TENANT_LOCALITY,
+    };
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::DeriveContext(&derive_context_cmd),
+        DpeResult::MboxCmdFailure(CaliptraError::RUNTIME_LOCALITY_REVOKED),
+    );
+    assert!(resp.is_none());
+
+    // Revoking the same locality twice is allowed
+    revoke_locality(&mut model, TENANT_LOCALITY)
+        .unwrap()
+        .expect("We expected a response");
+
+    // The contexts of the revoked locality were destroyed, and an update reset
+    // does not bring them back
+    update_fw(&mut model, &MBOX, ImageOptions::default());
+    assert_eq!(active_contexts(&mut model, TENANT_LOCALITY), 0);
+    assert_ne!(active_contexts(&mut model, 1), 0);
+
+    // The revocation survives the update reset
+    update_fw(&mut model, &APP_WITH_UART, ImageOptions::default());
+    let resp = execute_dpe_cmd(
+        &mut model,
+        &mut Command::DeriveContext(&derive_context_cmd),
+        DpeResult::MboxCmdFailure(CaliptraError::RUNTIME_LOCALITY_REVOKED),
+    );
+    assert!(resp.is_none());
+}
+
+#[test]
+fn test_revoke_pl0_locality_fails() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // The default test image uses PAUSER 1 as the PL0 PAUSER
+    let resp = revoke_locality(&mut model, 1).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+        resp,
+    );
+}
+
+#[test]
+fn test_revoke_locality_pl1_caller_fails() {
+    let mut image_opts = ImageOptions::default();
+    image_opts.vendor_config.pl0_pauser = None;
+
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_opts),
+        ..Default::default()
+    });
+
+    let resp = revoke_locality(&mut model, TENANT_LOCALITY).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL,
+        resp,
+    );
+}
@@ -0,0 +1,216 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use crate::test_pcr::get_model_pcrs;
+use caliptra_common::mailbox_api::{
+    CommandId, ExtendPcrReq, MailboxReq, MailboxReqHeader, SealKeyReq, SealKeyResp, SealedKeyBlob,
+    UnsealKeyReq, UnsealKeyResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use sha2::{Digest, Sha384};
+use zerocopy::{FromBytes, IntoBytes};
+
+const KEY: [u8; 40] = [0xa5; 40];
+
+/// PCR 0 holds the firmware measurements and PCR 4 is extended by the SoC
+const PCR_MASK: u32 = 1 | 1 << 4;
+
+fn pcr_digest(model: &mut DefaultHwModel, pcr_mask: u32) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    for (i, pcr) in get_model_pcrs(model).iter().enumerate() {
+        if pcr_mask & (1 << i) != 0 {
+            hasher.update(pcr);
+        }
+    }
+    hasher.finalize().as_slice().try_into().unwrap()
+}
+
+fn seal_key(
+    model: &mut DefaultHwModel,
+    pcr_mask: u32,
+    pcr_digest: [u8; 48],
+    min_svn: u32,
+) -> Result<SealedKeyBlob, ModelError> {
+    let mut req = SealKeyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        pcr_mask,
+        pcr_digest,
+        min_svn,
+        key_size: KEY.len() as u32,
+        ..Default::default()
+    };
+    req.key[..KEY.len()].copy_from_slice(&KEY);
+    let mut cmd = MailboxReq::SealKey(req);
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::SEAL_KEY), cmd.as_bytes().unwrap())?
+        .expect("We expected a response");
+    Ok(SealKeyResp::read_from_bytes(resp.as_slice())
+        .unwrap()
+        .sealed)
+}
+
+fn unseal_key(
+    model: &mut DefaultHwModel,
+    sealed: SealedKeyBlob,
+) -> Result<UnsealKeyResp, ModelError> {
+    let mut cmd = MailboxReq::UnsealKey(UnsealKeyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        sealed,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::UNSEAL_KEY), cmd.as_bytes().unwrap())?
+        .expect("We expected a response");
+    Ok(UnsealKeyResp::read_from_bytes(resp.as_slice()).unwrap())
+}
+
+fn extend_pcr(model: &mut DefaultHwModel, pcr_idx: u32) {
+    let mut cmd = MailboxReq::ExtendPcr(ExtendPcrReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        pcr_idx,
+        data: [0x11; 48],
+    });
+    cmd.populate_chksum().unwrap();
+    model
+        .mailbox_execute(u32::from(CommandId::EXTEND_PCR), cmd.as_bytes().unwrap())
+        .unwrap()
+        .expect("We expected a response");
+}
+
+#[test]
+fn test_seal_unseal_key() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let digest = pcr_digest(&mut model, PCR_MASK);
+    let sealed = seal_key(&mut model, PCR_MASK, digest, 0).unwrap();
+    assert_eq!(sealed.key_size, KEY.len() as u32);
+    assert_ne!(&sealed.ciphertext[..KEY.len()], &KEY);
+    assert_eq!(sealed.ciphertext[KEY.len()..], [0u8; 24]);
+
+    let resp = unseal_key(&mut model, sealed).unwrap();
+    assert_eq!(resp.key_size, KEY.len() as u32);
+    assert_eq!(&resp.key[..KEY.len()], &KEY);
+    assert_eq!(resp.key[KEY.len()..], [0u8; 24]);
+
+    // Every blob has its own nonce
+    let resealed = seal_key(&mut model, PCR_MASK, digest, 0).unwrap();
+    assert_ne!(resealed.nonce, sealed.nonce);
+    assert_ne!(resealed.ciphertext, sealed.ciphertext);
+}
+
+#[test]
+fn test_unseal_key_pcr_mismatch() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let digest = pcr_digest(&mut model, PCR_MASK);
+    let sealed = seal_key(&mut model, PCR_MASK, digest, 0).unwrap();
+
+    // PCRs outside of the mask do not matter
+    extend_pcr(&mut model, 6);
+    unseal_key(&mut model, sealed).unwrap();
+
+    extend_pcr(&mut model, 4);
+    let resp = unseal_key(&mut model, sealed).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_SEALED_KEY_POLICY_MISMATCH,
+        resp,
+    );
+
+    // A policy may be sealed for measurements that are yet to be taken
+    let sealed = seal_key(&mut model, 1 << 5, [0x22; 48], 0).unwrap();
+    let resp = unseal_key(&mut model, sealed).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_SEALED_KEY_POLICY_MISMATCH,
+        resp,
+    );
+}
+
+#[test]
+fn test_unseal_key_svn_mismatch() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let sealed = seal_key(&mut model, 0, [0u8; 48], u32::MAX).unwrap();
+    let resp = unseal_key(&mut model, sealed).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_SEALED_KEY_POLICY_MISMATCH,
+        resp,
+    );
+}
+
+#[test]
+fn test_unseal_key_tampered() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let sealed = seal_key(&mut model, 0, [0u8; 48], u32::MAX).unwrap();
+
+    // The policy is covered by the tag
+    let mut tampered = sealed;
+    tampered.min_svn = 0;
+    let resp = unseal_key(&mut model, tampered).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_SEALED_KEY_INVALID, resp);
+
+    let mut tampered = sealed;
+    tampered.ciphertext[0] ^= 1;
+    let resp = unseal_key(&mut model, tampered).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_SEALED_KEY_INVALID, resp);
+
+    let mut tampered = sealed;
+    tampered.key_size = SealedKeyBlob::KEY_MAX_SIZE as u32 + 1;
+    let resp = unseal_key(&mut model, tampered).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_SEALED_KEY_INVALID, resp);
+}
+
+#[test]
+fn test_seal_key_invalid_size() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    for key_size in [0, SealedKeyBlob::KEY_MAX_SIZE as u32 + 1] {
+        let mut cmd = MailboxReq::SealKey(SealKeyReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            key_size,
+            ..Default::default()
+        });
+        cmd.populate_chksum().unwrap();
+        let resp = model
+            .mailbox_execute(u32::from(CommandId::SEAL_KEY), cmd.as_bytes().unwrap())
+            .unwrap_err();
+        assert_error(
+            &mut model,
+            CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+            resp,
+        );
+    }
+}
+
+#[test]
+fn test_unseal_key_attestation_disabled() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let sealed = seal_key(&mut model, 0, [0u8; 48], 0).unwrap();
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::DISABLE_ATTESTATION),
+            &[],
+        ),
+    };
+    model
+        .mailbox_execute(
+            u32::from(CommandId::DISABLE_ATTESTATION),
+            payload.as_bytes(),
+        )
+        .unwrap()
+        .expect("We expected a response");
+
+    let resp = unseal_key(&mut model, sealed).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_SEALED_KEY_UNAVAILABLE,
+        resp,
+    );
+}
@@ -0,0 +1,156 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, MeasurementManifestEntry, SetMeasurementManifestReq,
+    SetMeasurementManifestResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_image_crypto::OsslCrypto as Crypto;
+use caliptra_image_fake_keys::{OWNER_PRIVATE_KEYS, OWNER_PUBLIC_KEYS};
+use caliptra_image_gen::ImageGeneratorCrypto;
+use zerocopy::{FromBytes, IntoBytes};
+
+fn manifest_entries(count: usize) -> Vec<MeasurementManifestEntry> {
+    (0..count)
+        .map(|i| MeasurementManifestEntry {
+            metadata: [i as u8; 4],
+            measurement: [0x10 + i as u8; 48],
+            svn: i as u32,
+        })
+        .collect()
+}
+
+/// Build a manifest signed with the owner key
+fn signed_manifest(
+    manifest_id: u32,
+    entries: &[MeasurementManifestEntry],
+) -> SetMeasurementManifestReq {
+    let mut req = SetMeasurementManifestReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        manifest_id,
+        entry_count: entries.len() as u32,
+        ..Default::default()
+    };
+    req.entries[..entries.len()].copy_from_slice(entries);
+
+    let crypto = Crypto::default();
+    let digest = crypto
+        .sha384_digest(
+            &[
+                manifest_id.as_bytes(),
+                req.entry_count.as_bytes(),
+                entries.as_bytes(),
+            ]
+            .concat(),
+        )
+        .unwrap();
+    let sig = crypto
+        .ecdsa384_sign(
+            &digest,
+            &OWNER_PRIVATE_KEYS.ecc_priv_key,
+            &OWNER_PUBLIC_KEYS.ecc_pub_key,
+        )
+        .unwrap();
+    let to_bytes = |words: [u32; 12]| {
+        let mut bytes = [0u8; 48];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        bytes
+    };
+    req.signature_r = to_bytes(sig.r);
+    req.signature_s = to_bytes(sig.s);
+    req
+}
+
+fn set_measurement_manifest(
+    model: &mut DefaultHwModel,
+    req: SetMeasurementManifestReq,
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::SetMeasurementManifest(req);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::SET_MEASUREMENT_MANIFEST),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+#[test]
+fn test_set_measurement_manifest() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let entries = manifest_entries(2);
+    let resp = set_measurement_manifest(&mut model, signed_manifest(1, &entries))
+        .unwrap()
+        .expect("We expected a response");
+    let resp = SetMeasurementManifestResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(resp.dpe_result, 0);
+    assert_eq!(resp.applied_count, 2);
+
+    // A manifest is only ingested once
+    let resp = set_measurement_manifest(&mut model, signed_manifest(1, &entries)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MEASUREMENT_MANIFEST_ALREADY_SET,
+        resp,
+    );
+}
+
+#[test]
+fn test_set_measurement_manifest_invalid_signature() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // Tampered entry
+    let mut req = signed_manifest(1, &manifest_entries(2));
+    req.entries[1].measurement[0] ^= 1;
+    let resp = set_measurement_manifest(&mut model, req).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MEASUREMENT_MANIFEST_SIGNATURE_INVALID,
+        resp,
+    );
+
+    // The manifest ID is covered by the signature
+    let mut req = signed_manifest(1, &manifest_entries(2));
+    req.manifest_id = 2;
+    let resp = set_measurement_manifest(&mut model, req).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MEASUREMENT_MANIFEST_SIGNATURE_INVALID,
+        resp,
+    );
+}
+
+#[test]
+fn test_set_measurement_manifest_invalid_id() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let req = signed_manifest(0, &manifest_entries(2));
+    let resp = set_measurement_manifest(&mut model, req).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+        resp,
+    );
+}
+
+#[test]
+fn test_set_measurement_manifest_log_full() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let req = signed_manifest(1, &manifest_entries(SetMeasurementManifestReq::MAX_ENTRIES));
+    set_measurement_manifest(&mut model, req)
+        .unwrap()
+        .expect("We expected a response");
+
+    // No entry of a manifest is applied if the log cannot hold all of them
+    let req = signed_manifest(2, &manifest_entries(1));
+    let resp = set_measurement_manifest(&mut model, req).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MEASUREMENT_LOG_FULL,
+        resp,
+    );
+}
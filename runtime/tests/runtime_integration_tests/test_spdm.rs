@@ -0,0 +1,266 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{get_rt_alias_cert, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, QuotePcrsReq, QuotePcrsResp, SpdmReq, SpdmResp,
+};
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use openssl::{bn::BigNum, ecdsa::EcdsaSig, sha::sha384, x509::X509};
+use zerocopy::{FromBytes, IntoBytes};
+
+const SPDM_VERSION_13: u8 = 0x13;
+const CHAIN_HEADER_SIZE: usize = 52;
+
+fn spdm(model: &mut DefaultHwModel, msg: &[u8]) -> Vec<u8> {
+    let mut req = SpdmReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        data_size: msg.len() as u32,
+        ..Default::default()
+    };
+    req.data[..msg.len()].copy_from_slice(msg);
+    let mut cmd = MailboxReq::Spdm(req);
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::SPDM), cmd.as_bytes().unwrap())
+        .unwrap()
+        .unwrap();
+    let mut spdm_resp = SpdmResp::default();
+    spdm_resp.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
+    spdm_resp.data[..spdm_resp.data_size as usize].to_vec()
+}
+
+/// Run GET_VERSION through NEGOTIATE_ALGORITHMS for version 1.3, returning
+/// the messages exchanged
+fn negotiate(model: &mut DefaultHwModel) -> Vec<u8> {
+    let mut transcript = vec![];
+    let mut exchange = |model: &mut DefaultHwModel, req: &[u8]| {
+        let resp = spdm(model, req);
+        transcript.extend_from_slice(req);
+        transcript.extend_from_slice(&resp);
+        resp
+    };
+
+    let version = exchange(model, &[0x10, 0x84, 0, 0]);
+    assert_eq!(version, [0x10, 0x04, 0, 0, 0, 2, 0x00, 0x12, 0x00, 0x13]);
+
+    let mut get_capabilities = vec![SPDM_VERSION_13, 0xE1, 0, 0, 0, 0, 0, 0];
+    get_capabilities.extend_from_slice(&0u32.to_le_bytes());
+    get_capabilities.extend_from_slice(&4096u32.to_le_bytes());
+    get_capabilities.extend_from_slice(&4096u32.to_le_bytes());
+    let capabilities = exchange(model, &get_capabilities);
+    assert_eq!(capabilities[1], 0x61);
+    assert_eq!(&capabilities[8..12], &0x16u32.to_le_bytes());
+
+    let mut negotiate_algorithms = vec![SPDM_VERSION_13, 0xE3, 0, 0];
+    negotiate_algorithms.extend_from_slice(&32u16.to_le_bytes());
+    negotiate_algorithms.extend_from_slice(&[1, 0]);
+    // ECDSA P-256 and P-384, SHA-256 and SHA-384
+    negotiate_algorithms.extend_from_slice(&((1u32 << 4) | (1 << 7)).to_le_bytes());
+    negotiate_algorithms.extend_from_slice(&((1u32 << 0) | (1 << 1)).to_le_bytes());
+    negotiate_algorithms.extend_from_slice(&[0; 16]);
+    let algorithms = exchange(model, &negotiate_algorithms);
+    assert_eq!(algorithms[1], 0x63);
+    assert_eq!(algorithms.len(), 36);
+    assert_eq!(algorithms[6], 1);
+    assert_eq!(&algorithms[8..12], &(1u32 << 2).to_le_bytes());
+    assert_eq!(&algorithms[12..16], &(1u32 << 7).to_le_bytes());
+    assert_eq!(&algorithms[16..20], &(1u32 << 1).to_le_bytes());
+
+    transcript
+}
+
+/// Check an SPDM 1.3 signature by `cert` over `transcript`
+fn verify_signature(cert: &X509, context: &[u8], transcript: &[u8], signature: &[u8]) -> bool {
+    let mut msg = b"dmtf-spdm-v1.3.*".repeat(4);
+    msg.resize(msg.len() + 36 - context.len(), 0);
+    msg.extend_from_slice(context);
+    msg.extend_from_slice(&sha384(transcript));
+
+    let sig = EcdsaSig::from_private_components(
+        BigNum::from_slice(&signature[..48]).unwrap(),
+        BigNum::from_slice(&signature[48..]).unwrap(),
+    )
+    .unwrap();
+    sig.verify(&sha384(&msg), &cert.public_key().unwrap().ec_key().unwrap())
+        .unwrap()
+}
+
+fn rt_alias_cert(model: &mut DefaultHwModel) -> X509 {
+    let resp = get_rt_alias_cert(model);
+    X509::from_der(&resp.data[..resp.data_size as usize]).unwrap()
+}
+
+#[test]
+fn test_spdm_challenge() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    let rt_cert = rt_alias_cert(&mut model);
+
+    let mut m1 = negotiate(&mut model);
+
+    let get_digests = [SPDM_VERSION_13, 0x81, 0, 0];
+    let digests = spdm(&mut model, &get_digests);
+    assert_eq!(&digests[..4], &[SPDM_VERSION_13, 0x01, 1, 1]);
+    assert_eq!(digests.len(), 4 + 48);
+    m1.extend_from_slice(&get_digests);
+    m1.extend_from_slice(&digests);
+
+    // Read the chain in portions smaller than the chain
+    let mut chain = vec![];
+    loop {
+        let mut get_certificate = vec![SPDM_VERSION_13, 0x82, 0, 0];
+        get_certificate.extend_from_slice(&(chain.len() as u16).to_le_bytes());
+        get_certificate.extend_from_slice(&0x200u16.to_le_bytes());
+        let certificate = spdm(&mut model, &get_certificate);
+        assert_eq!(certificate[1], 0x02);
+        let portion = u16::from_le_bytes([certificate[4], certificate[5]]) as usize;
+        let remainder = u16::from_le_bytes([certificate[6], certificate[7]]);
+        chain.extend_from_slice(&certificate[8..8 + portion]);
+        m1.extend_from_slice(&get_certificate);
+        m1.extend_from_slice(&certificate);
+        if remainder == 0 {
+            break;
+        }
+    }
+    assert_eq!(
+        u16::from_le_bytes([chain[0], chain[1]]) as usize,
+        chain.len()
+    );
+    assert_eq!(sha384(&chain), digests[4..]);
+
+    // The chain ends with the RT Alias certificate, and the root hash is the
+    // digest of its first certificate
+    let rt_der = rt_cert.to_der().unwrap();
+    assert!(chain.ends_with(&rt_der));
+    let first = X509::from_der(&chain[CHAIN_HEADER_SIZE..]).unwrap();
+    assert_eq!(
+        sha384(&first.to_der().unwrap()),
+        chain[4..CHAIN_HEADER_SIZE]
+    );
+
+    let mut challenge = vec![SPDM_VERSION_13, 0x83, 0, 0xFF];
+    challenge.extend_from_slice(&[0x5a; 32]);
+    challenge.extend_from_slice(&[0xc3; 8]);
+    let challenge_auth = spdm(&mut model, &challenge);
+    assert_eq!(&challenge_auth[..4], &[SPDM_VERSION_13, 0x03, 0, 1]);
+    assert_eq!(challenge_auth[4..52], digests[4..]);
+    // Chain hash, nonce, summary hash, opaque length, context, signature
+    assert_eq!(challenge_auth.len(), 4 + 48 + 32 + 48 + 2 + 8 + 96);
+    let (body, signature) = challenge_auth.split_at(challenge_auth.len() - 96);
+    assert_eq!(&body[body.len() - 8..], &[0xc3; 8]);
+    m1.extend_from_slice(&challenge);
+    m1.extend_from_slice(body);
+    assert!(verify_signature(
+        &rt_cert,
+        b"responder-challenge_auth signing",
+        &m1,
+        signature
+    ));
+
+    // A second challenge without the digest and certificate exchange signs
+    // only the negotiation and the challenge itself
+    let mut m1 = negotiate(&mut model);
+    let challenge_auth = spdm(&mut model, &challenge);
+    let (body, signature) = challenge_auth.split_at(challenge_auth.len() - 96);
+    m1.extend_from_slice(&challenge);
+    m1.extend_from_slice(body);
+    assert!(verify_signature(
+        &rt_cert,
+        b"responder-challenge_auth signing",
+        &m1,
+        signature
+    ));
+}
+
+#[test]
+fn test_spdm_measurements() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    let rt_cert = rt_alias_cert(&mut model);
+
+    let mut l1 = negotiate(&mut model);
+
+    // Number of indices
+    let get_count = [SPDM_VERSION_13, 0xE0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let count = spdm(&mut model, &get_count);
+    assert_eq!(&count[..8], &[SPDM_VERSION_13, 0x60, 32, 0, 0, 0, 0, 0]);
+    l1.extend_from_slice(&get_count);
+    l1.extend_from_slice(&count);
+
+    let mut get_measurements = vec![SPDM_VERSION_13, 0xE0, 1, 0xFF];
+    get_measurements.extend_from_slice(&[0x11; 32]);
+    get_measurements.push(0);
+    get_measurements.extend_from_slice(&[0x22; 8]);
+    let measurements = spdm(&mut model, &get_measurements);
+    assert_eq!(&measurements[..5], &[SPDM_VERSION_13, 0x60, 0, 0, 32]);
+    let record_len = u32::from_le_bytes([measurements[5], measurements[6], measurements[7], 0]);
+    assert_eq!(record_len, 32 * 55);
+
+    let quote_req = QuotePcrsReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        nonce: [0; 32],
+    };
+    let mut cmd = MailboxReq::QuotePcrs(quote_req);
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::QUOTE_PCRS), cmd.as_bytes().unwrap())
+        .unwrap()
+        .unwrap();
+    let quote = QuotePcrsResp::read_from_bytes(resp.as_slice()).unwrap();
+    for (i, block) in measurements[8..8 + record_len as usize]
+        .chunks_exact(55)
+        .enumerate()
+    {
+        assert_eq!(&block[..7], &[i as u8 + 1, 1, 51, 0, 1, 48, 0]);
+        assert_eq!(block[7..], quote.pcrs[i]);
+    }
+
+    let (body, signature) = measurements.split_at(measurements.len() - 96);
+    assert_eq!(&body[body.len() - 8..], &[0x22; 8]);
+    l1.extend_from_slice(&get_measurements);
+    l1.extend_from_slice(body);
+    assert!(verify_signature(
+        &rt_cert,
+        b"responder-measurements signing",
+        &l1,
+        signature
+    ));
+
+    // A single index
+    let get_one = [SPDM_VERSION_13, 0xE0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0];
+    let one = spdm(&mut model, &get_one);
+    assert_eq!(&one[4..8], &[1, 55, 0, 0]);
+    assert_eq!(one[8], 3);
+    assert_eq!(one[15..15 + 48], quote.pcrs[2]);
+}
+
+#[test]
+fn test_spdm_errors() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // Nothing but GET_VERSION before negotiation
+    assert_eq!(
+        spdm(&mut model, &[SPDM_VERSION_13, 0x81, 0, 0]),
+        [0x10, 0x7F, 0x04, 0]
+    );
+    assert_eq!(spdm(&mut model, &[0x12, 0x84, 0, 0]), [0x10, 0x7F, 0x41, 0]);
+
+    negotiate(&mut model);
+
+    // KEY_EXCHANGE is not supported
+    assert_eq!(
+        spdm(&mut model, &[SPDM_VERSION_13, 0xE4, 0, 0]),
+        [SPDM_VERSION_13, 0x7F, 0x07, 0xE4]
+    );
+    assert_eq!(
+        spdm(&mut model, &[0x12, 0x81, 0, 0]),
+        [SPDM_VERSION_13, 0x7F, 0x41, 0]
+    );
+    // Only slot 0 holds a chain
+    assert_eq!(
+        spdm(&mut model, &[SPDM_VERSION_13, 0x82, 1, 0, 0, 0, 0, 4]),
+        [SPDM_VERSION_13, 0x7F, 0x01, 0]
+    );
+    assert_eq!(
+        spdm(&mut model, &[SPDM_VERSION_13, 0xE3, 0, 0]),
+        [SPDM_VERSION_13, 0x7F, 0x04, 0]
+    );
+}
@@ -0,0 +1,176 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use crate::test_authorize_and_stash::set_auth_manifest;
+use crate::test_set_auth_manifest::create_auth_manifest_with_metadata;
+use caliptra_auth_man_types::{AuthManifestImageMetadata, ImageMetadataFlags};
+use caliptra_common::mailbox_api::{
+    AuthAndStashFlags, CommandId, FinishStreamVerifyReq, FinishStreamVerifyResp, ImageHashSource,
+    MailboxReq, MailboxReqHeader, StartStreamVerifyReq, StreamDataReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_runtime::{IMAGE_AUTHORIZED, IMAGE_HASH_MISMATCH, IMAGE_NOT_AUTHORIZED};
+use sha2::{Digest, Sha384};
+use zerocopy::FromBytes;
+
+const FW_ID: u32 = 1;
+
+fn image() -> Vec<u8> {
+    (0..300u32).map(|i| i as u8).collect()
+}
+
+fn image_digest(image: &[u8]) -> [u8; 48] {
+    Sha384::digest(image).as_slice().try_into().unwrap()
+}
+
+fn start(model: &mut DefaultHwModel, image_size: u32) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::StartStreamVerify(StartStreamVerifyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        fw_id: FW_ID.to_le_bytes(),
+        image_size,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::START_STREAM_VERIFY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+fn stream_data(model: &mut DefaultHwModel, data: &[u8]) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut req = StreamDataReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        data_size: data.len() as u32,
+        ..Default::default()
+    };
+    req.data[..data.len()].copy_from_slice(data);
+    let mut cmd = MailboxReq::StreamData(req);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::STREAM_DATA), cmd.as_bytes().unwrap())
+}
+
+fn finish(model: &mut DefaultHwModel) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::FinishStreamVerify(FinishStreamVerifyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        flags: AuthAndStashFlags::SKIP_STASH.bits(),
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::FINISH_STREAM_VERIFY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+/// Stream `image` in 256-byte chunks and finish the verification
+fn stream_verify(model: &mut DefaultHwModel, image: &[u8]) -> FinishStreamVerifyResp {
+    start(model, image.len() as u32)
+        .unwrap()
+        .expect("We expected a response");
+    for chunk in image.chunks(256) {
+        stream_data(model, chunk)
+            .unwrap()
+            .expect("We expected a response");
+    }
+    let resp = finish(model).unwrap().expect("We expected a response");
+    FinishStreamVerifyResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+fn set_image_digest(digest: [u8; 48]) -> DefaultHwModel {
+    let mut flags = ImageMetadataFlags(0);
+    flags.set_image_source(ImageHashSource::InRequest as u32);
+    let image_metadata = vec![AuthManifestImageMetadata {
+        fw_id: FW_ID,
+        flags: flags.0,
+        digest,
+    }];
+    set_auth_manifest(Some(create_auth_manifest_with_metadata(image_metadata)))
+}
+
+#[test]
+fn test_stream_verify() {
+    let image = image();
+    let mut model = set_image_digest(image_digest(&image));
+
+    let resp = stream_verify(&mut model, &image);
+    assert_eq!(resp.auth_req_result, IMAGE_AUTHORIZED);
+    assert_eq!(resp.digest, image_digest(&image));
+}
+
+#[test]
+fn test_stream_verify_hash_mismatch() {
+    let image = image();
+    let mut model = set_image_digest(image_digest(&image));
+
+    let mut tampered = image.clone();
+    tampered[299] ^= 1;
+    let resp = stream_verify(&mut model, &tampered);
+    assert_eq!(resp.auth_req_result, IMAGE_HASH_MISMATCH);
+    assert_eq!(resp.digest, image_digest(&tampered));
+}
+
+#[test]
+fn test_stream_verify_no_manifest() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = stream_verify(&mut model, &image());
+    assert_eq!(resp.auth_req_result, IMAGE_NOT_AUTHORIZED);
+}
+
+#[test]
+fn test_stream_verify_incomplete() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    let image = image();
+
+    start(&mut model, image.len() as u32)
+        .unwrap()
+        .expect("We expected a response");
+    stream_data(&mut model, &image[..256])
+        .unwrap()
+        .expect("We expected a response");
+    let resp = finish(&mut model).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH,
+        resp,
+    );
+
+    // The stream was discarded and the accelerator released
+    let resp = finish(&mut model).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_STREAM_VERIFY_NOT_STARTED,
+        resp,
+    );
+    let resp = stream_verify(&mut model, &image);
+    assert_eq!(resp.digest, image_digest(&image));
+}
+
+#[test]
+fn test_stream_verify_sequence() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = stream_data(&mut model, &[0u8; 4]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_STREAM_VERIFY_NOT_STARTED,
+        resp,
+    );
+
+    start(&mut model, 8)
+        .unwrap()
+        .expect("We expected a response");
+    let resp = start(&mut model, 8).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_STREAM_VERIFY_IN_PROGRESS,
+        resp,
+    );
+
+    // No more than the announced image size can be streamed
+    let resp = stream_data(&mut model, &[0u8; 12]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::DRIVER_SHA2_512_384ACC_STREAM_LENGTH_MISMATCH,
+        resp,
+    );
+}
@@ -0,0 +1,119 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    CertPolicyFlags, CommandId, MailboxReq, MailboxReqHeader, MailboxRespHeader, SetCertPolicyReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, Fuses, HwModel, ModelError};
+use caliptra_runtime::RtBootStatus;
+use zerocopy::{FromBytes, IntoBytes};
+
+fn header_only_cmd(
+    model: &mut DefaultHwModel,
+    cmd: CommandId,
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(cmd.0, &[]),
+    };
+    model.mailbox_execute(u32::from(cmd), payload.as_bytes())
+}
+
+fn assert_success(resp: Result<Option<Vec<u8>>, ModelError>) {
+    let resp = resp.unwrap().expect("We expected a response");
+    let (resp_hdr, _) = MailboxRespHeader::read_from_prefix(resp.as_slice()).unwrap();
+    assert_eq!(
+        resp_hdr.fips_status,
+        MailboxRespHeader::FIPS_STATUS_APPROVED
+    );
+}
+
+fn set_cert_policy(model: &mut DefaultHwModel) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::SetCertPolicy(SetCertPolicyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        flags: CertPolicyFlags::DENY_X509,
+        max_not_after: [0; 15],
+        reserved: 0,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::SET_CERT_POLICY),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+#[test]
+fn test_suspend_resume() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_success(header_only_cmd(&mut model, CommandId::SUSPEND_PREPARE));
+
+    // Only informational commands are serviced while suspended
+    assert_success(header_only_cmd(&mut model, CommandId::FW_INFO));
+    let resp = header_only_cmd(&mut model, CommandId::GET_LDEV_CERT).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_SUSPENDED, resp);
+
+    assert_success(header_only_cmd(&mut model, CommandId::RESUME_NOTIFY));
+    header_only_cmd(&mut model, CommandId::GET_LDEV_CERT)
+        .unwrap()
+        .expect("We expected a response");
+
+    let resp = header_only_cmd(&mut model, CommandId::RESUME_NOTIFY).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_NOT_SUSPENDED, resp);
+}
+
+#[test]
+fn test_suspend_resume_warm_reset() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_success(set_cert_policy(&mut model));
+    assert_success(header_only_cmd(&mut model, CommandId::SUSPEND_PREPARE));
+
+    model.warm_reset_flow(&Fuses::default());
+    model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+
+    // The runtime is still suspended after the reset
+    let resp = set_cert_policy(&mut model).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_SUSPENDED, resp);
+
+    assert_success(header_only_cmd(&mut model, CommandId::RESUME_NOTIFY));
+
+    // The certificate policy latched before the reset was restored
+    let resp = set_cert_policy(&mut model).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_CERT_POLICY_ALREADY_SET,
+        resp,
+    );
+}
+
+#[test]
+fn test_prepare_for_sleep_warm_reset() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    assert_success(set_cert_policy(&mut model));
+    assert_success(header_only_cmd(&mut model, CommandId::PREPARE_FOR_SLEEP));
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForSleep)
+    });
+
+    model.warm_reset_flow(&Fuses::default());
+    model.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+    assert_eq!(model.soc_ifc().cptra_fw_error_non_fatal().read(), 0);
+
+    // The runtime resumed without RESUME_NOTIFY
+    header_only_cmd(&mut model, CommandId::GET_LDEV_CERT)
+        .unwrap()
+        .expect("We expected a response");
+    let resp = header_only_cmd(&mut model, CommandId::RESUME_NOTIFY).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_NOT_SUSPENDED, resp);
+
+    // The certificate policy latched before the reset was restored
+    let resp = set_cert_policy(&mut model).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_CERT_POLICY_ALREADY_SET,
+        resp,
+    );
+}
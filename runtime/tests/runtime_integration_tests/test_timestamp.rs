@@ -0,0 +1,102 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use crate::test_monotonic_counter::create_counter;
+use crate::test_pcr::{verify_cwt, CborReader};
+use crate::test_update_reset::update_fw;
+use caliptra_builder::{firmware::APP_WITH_UART, ImageOptions};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, TimestampReq, TimestampResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use zerocopy::IntoBytes;
+
+/// Claims of a TIMESTAMP token
+struct Timestamp {
+    nonce: Vec<u8>,
+    boot_session_id: Vec<u8>,
+    counter_id: u64,
+    counter_value: u64,
+}
+
+fn timestamp(
+    model: &mut DefaultHwModel,
+    counter_id: u32,
+    nonce: [u8; 32],
+) -> Result<Timestamp, ModelError> {
+    let mut cmd = MailboxReq::Timestamp(TimestampReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        counter_id,
+        nonce,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::TIMESTAMP), cmd.as_bytes().unwrap())?
+        .expect("We expected a response");
+    let mut ts_resp = TimestampResp::default();
+    ts_resp.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
+    let token = &ts_resp.data[..ts_resp.data_size as usize];
+    let payload = verify_cwt(model, token);
+
+    let mut claims = CborReader { buf: payload };
+    assert_eq!(claims.expect(5), 5);
+    assert_eq!(claims.int(), TimestampResp::CLAIM_NONCE);
+    let nonce = claims.bstr().to_vec();
+    assert_eq!(claims.int(), TimestampResp::CLAIM_UEID);
+    assert_eq!(claims.bstr().len(), 17);
+    assert_eq!(claims.int(), TimestampResp::CLAIM_BOOT_SESSION_ID);
+    let boot_session_id = claims.bstr().to_vec();
+    assert_eq!(claims.int(), TimestampResp::CLAIM_COUNTER_ID);
+    let counter_id = claims.expect(0);
+    assert_eq!(claims.int(), TimestampResp::CLAIM_COUNTER_VALUE);
+    let counter_value = claims.expect(0);
+    assert!(claims.buf.is_empty());
+
+    Ok(Timestamp {
+        nonce,
+        boot_session_id,
+        counter_id,
+        counter_value,
+    })
+}
+
+#[test]
+fn test_timestamp() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    create_counter(&mut model).unwrap();
+    let counter_id = create_counter(&mut model).unwrap().counter_id;
+
+    let first = timestamp(&mut model, counter_id, [0x11; 32]).unwrap();
+    assert_eq!(first.nonce, [0x11; 32]);
+    assert_eq!(first.boot_session_id.len(), 8);
+    assert_eq!(first.counter_id, counter_id as u64);
+    assert_eq!(first.counter_value, 1);
+
+    let second = timestamp(&mut model, counter_id, [0x22; 32]).unwrap();
+    assert_eq!(second.nonce, [0x22; 32]);
+    assert_eq!(second.boot_session_id, first.boot_session_id);
+    assert_eq!(second.counter_value, 2);
+}
+
+#[test]
+fn test_timestamp_update_reset() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    create_counter(&mut model).unwrap();
+    let before = timestamp(&mut model, 0, [0x11; 32]).unwrap();
+
+    update_fw(&mut model, &APP_WITH_UART, ImageOptions::default());
+
+    // Firmware updates continue the boot session
+    let after = timestamp(&mut model, 0, [0x11; 32]).unwrap();
+    assert_eq!(after.boot_session_id, before.boot_session_id);
+    assert_eq!(after.counter_value, 2);
+}
+
+#[test]
+fn test_timestamp_counter_not_created() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let resp = timestamp(&mut model, 0, [0x11; 32]).err().unwrap();
+    assert_error(&mut model, CaliptraError::RUNTIME_COUNTER_NOT_CREATED, resp);
+}
@@ -8,7 +8,8 @@ use caliptra_builder::{
     FwId, ImageOptions,
 };
 use caliptra_common::mailbox_api::{
-    CommandId, FwInfoResp, IncrementPcrResetCounterReq, MailboxReq, MailboxReqHeader, TagTciReq,
+    CommandId, FwInfoV1Resp, GetUpdateHistoryResp, IncrementPcrResetCounterReq, MailboxReq,
+    MailboxReqHeader, TagTciReq,
 };
 use caliptra_drivers::PcrResetCounter;
 use caliptra_error::CaliptraError;
@@ -193,7 +194,7 @@ fn test_dpe_validation_deformed_structure() {
         .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
         .unwrap()
         .unwrap();
-    let info = FwInfoResp::read_from_bytes(resp.as_slice()).unwrap();
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
     assert_eq!(info.attestation_disabled, 1);
 }
 
@@ -241,7 +242,7 @@ fn test_dpe_validation_illegal_state() {
         .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
         .unwrap()
         .unwrap();
-    let info = FwInfoResp::read_from_bytes(resp.as_slice()).unwrap();
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
     assert_eq!(info.attestation_disabled, 1);
 }
 
@@ -295,7 +296,7 @@ fn test_dpe_validation_used_context_threshold_exceeded() {
         .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
         .unwrap()
         .unwrap();
-    let info = FwInfoResp::read_from_bytes(resp.as_slice()).unwrap();
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
     assert_eq!(info.attestation_disabled, 1);
 }
 
@@ -340,3 +341,59 @@ fn test_pcr_reset_counter_persistence() {
     // check that the pcr reset counters are not default
     assert_ne!(pcr_reset_counter_1, [0u8; size_of::<PcrResetCounter>()]);
 }
+
+#[test]
+fn test_update_history() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let get_history = |model: &mut DefaultHwModel| {
+        let payload = MailboxReqHeader {
+            chksum: caliptra_common::checksum::calc_checksum(
+                u32::from(CommandId::GET_UPDATE_HISTORY),
+                &[],
+            ),
+        };
+        let resp = model
+            .mailbox_execute(u32::from(CommandId::GET_UPDATE_HISTORY), payload.as_bytes())
+            .unwrap()
+            .unwrap();
+        GetUpdateHistoryResp::read_from_bytes(resp.as_slice()).unwrap()
+    };
+
+    // No updates have been applied since cold reset
+    let history = get_history(&mut model);
+    assert_eq!(history.total_updates, 0);
+    assert_eq!(history.entry_count, 0);
+
+    update_fw(
+        &mut model,
+        &APP_WITH_UART,
+        ImageOptions {
+            app_svn: 2,
+            ..Default::default()
+        },
+    );
+
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
+    };
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::FW_INFO), payload.as_bytes())
+        .unwrap()
+        .unwrap();
+    let info = FwInfoV1Resp::read_from_bytes(resp.as_slice()).unwrap();
+
+    let history = get_history(&mut model);
+    assert_eq!(history.total_updates, 1);
+    assert_eq!(history.entry_count, 1);
+    assert_eq!(history.entries[0].old_svn, 0);
+    assert_eq!(history.entries[0].new_svn, 2);
+    assert_eq!(
+        history.entries[0].runtime_sha384_digest,
+        info.runtime_sha384_digest
+    );
+}
@@ -0,0 +1,159 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, SetObjectSchemasReq, VerifyObjectReq, VerifyObjectResp,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_image_crypto::OsslCrypto as Crypto;
+use caliptra_image_fake_keys::{OWNER_PRIVATE_KEYS, OWNER_PUBLIC_KEYS};
+use caliptra_image_gen::ImageGeneratorCrypto;
+use zerocopy::{FromBytes, IntoBytes};
+
+const SCHEMA_A: [u8; 48] = [0xa5; 48];
+const SCHEMA_B: [u8; 48] = [0x5a; 48];
+
+fn set_object_schemas(
+    model: &mut DefaultHwModel,
+    schemas: &[[u8; 48]],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut req = SetObjectSchemasReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        schema_count: schemas.len() as u32,
+        ..Default::default()
+    };
+    req.schema_digests[..schemas.len()].copy_from_slice(schemas);
+    let mut cmd = MailboxReq::SetObjectSchemas(req);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        u32::from(CommandId::SET_OBJECT_SCHEMAS),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+/// Sign `data` of schema `schema` with the owner key
+fn sign_object(schema: &[u8; 48], data: &[u8]) -> ([u8; 48], [u8; 48]) {
+    let crypto = Crypto::default();
+    let digest = crypto.sha384_digest(&[&schema[..], data].concat()).unwrap();
+    let sig = crypto
+        .ecdsa384_sign(
+            &digest,
+            &OWNER_PRIVATE_KEYS.ecc_priv_key,
+            &OWNER_PUBLIC_KEYS.ecc_pub_key,
+        )
+        .unwrap();
+    let to_bytes = |words: [u32; 12]| {
+        let mut bytes = [0u8; 48];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        bytes
+    };
+    (to_bytes(sig.r), to_bytes(sig.s))
+}
+
+fn verify_object(
+    model: &mut DefaultHwModel,
+    schema: &[u8; 48],
+    signature: &([u8; 48], [u8; 48]),
+    data: &[u8],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut req = VerifyObjectReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        schema_digest: *schema,
+        signature_r: signature.0,
+        signature_s: signature.1,
+        data_size: data.len() as u32,
+        ..Default::default()
+    };
+    req.data[..data.len()].copy_from_slice(data);
+    let mut cmd = MailboxReq::VerifyObject(req);
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(u32::from(CommandId::VERIFY_OBJECT), cmd.as_bytes().unwrap())
+}
+
+fn verdict(resp: Result<Option<Vec<u8>>, ModelError>) -> u32 {
+    let resp = resp.unwrap().expect("We expected a response");
+    VerifyObjectResp::read_from_bytes(resp.as_slice())
+        .unwrap()
+        .verdict
+}
+
+#[test]
+fn test_verify_object() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    set_object_schemas(&mut model, &[SCHEMA_A])
+        .unwrap()
+        .expect("We expected a response");
+
+    let data = b"owner-defined object";
+    let sig = sign_object(&SCHEMA_A, data);
+    assert_eq!(
+        verdict(verify_object(&mut model, &SCHEMA_A, &sig, data)),
+        VerifyObjectResp::OBJECT_AUTHORIZED
+    );
+
+    // Tampered object
+    assert_eq!(
+        verdict(verify_object(
+            &mut model,
+            &SCHEMA_A,
+            &sig,
+            b"owner-defined objecT"
+        )),
+        VerifyObjectResp::OBJECT_SIGNATURE_INVALID
+    );
+
+    // Unregistered schema, even if the object is signed for it
+    let sig = sign_object(&SCHEMA_B, data);
+    assert_eq!(
+        verdict(verify_object(&mut model, &SCHEMA_B, &sig, data)),
+        VerifyObjectResp::OBJECT_SCHEMA_NOT_APPROVED
+    );
+}
+
+#[test]
+fn test_verify_object_schema_replay() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    set_object_schemas(&mut model, &[SCHEMA_A, SCHEMA_B])
+        .unwrap()
+        .expect("We expected a response");
+
+    // An object signed under one schema is not valid under another
+    let data = b"owner-defined object";
+    let sig = sign_object(&SCHEMA_A, data);
+    assert_eq!(
+        verdict(verify_object(&mut model, &SCHEMA_B, &sig, data)),
+        VerifyObjectResp::OBJECT_SIGNATURE_INVALID
+    );
+}
+
+#[test]
+fn test_verify_object_schemas_not_set() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let data = b"owner-defined object";
+    let sig = sign_object(&SCHEMA_A, data);
+    let resp = verify_object(&mut model, &SCHEMA_A, &sig, data).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_OBJECT_SCHEMAS_NOT_SET,
+        resp,
+    );
+}
+
+#[test]
+fn test_set_object_schemas_latched() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    set_object_schemas(&mut model, &[SCHEMA_A])
+        .unwrap()
+        .expect("We expected a response");
+
+    let resp = set_object_schemas(&mut model, &[SCHEMA_A, SCHEMA_B]).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_OBJECT_SCHEMAS_ALREADY_SET,
+        resp,
+    );
+}
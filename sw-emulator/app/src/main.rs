@@ -31,6 +31,7 @@ use std::rc::Rc;
 use tock_registers::interfaces::{ReadWriteable, Readable, Writeable};
 use tock_registers::registers::InMemoryRegister;
 mod gdb;
+mod rom;
 use crate::gdb::gdb_target::GdbTarget;
 use gdb::gdb_state;
 
@@ -79,9 +80,14 @@ fn main() -> io::Result<()> {
     let args = clap::Command::new("caliptra-emu")
         .about("Caliptra emulator")
         .arg(
-            arg!(--"rom" <FILE> "ROM binary path")
+            arg!(--"rom" <FILE> "ROM binary path, or ROM hex image path if the file has a .hex extension")
                 .value_parser(value_parser!(PathBuf))
         )
+        .arg(
+            arg!(--"rom-elf" <FILE> "ROM ELF to cross-check the ROM image against")
+                .required(false)
+                .value_parser(value_parser!(PathBuf)),
+        )
         .arg(
             arg!(--"gdb-port" <VALUE> "Gdb Debugger")
                 .required(false)
@@ -155,6 +161,7 @@ fn main() -> io::Result<()> {
         .get_matches();
 
     let args_rom = args.get_one::<PathBuf>("rom").unwrap();
+    let args_rom_elf = args.get_one::<PathBuf>("rom-elf");
     let args_current_fw = args.get_one::<PathBuf>("firmware");
     let args_update_fw = args.get_one::<PathBuf>("update-firmware");
     let args_log_dir = args.get_one::<PathBuf>("log-dir").unwrap();
@@ -199,6 +206,20 @@ fn main() -> io::Result<()> {
     let mut rom_buffer = Vec::new();
     rom.read_to_end(&mut rom_buffer)?;
 
+    if rom::is_hex_path(args_rom) {
+        let Ok(text) = String::from_utf8(rom_buffer) else {
+            println!("ROM hex file {:?} is not valid text", args_rom);
+            exit(-1);
+        };
+        rom_buffer = match rom::rom_from_hex(&text) {
+            Ok(rom_buffer) => rom_buffer,
+            Err(e) => {
+                println!("Failed to parse ROM hex file {:?}: {}", args_rom, e);
+                exit(-1);
+            }
+        };
+    }
+
     if rom_buffer.len() > CaliptraRootBus::ROM_SIZE {
         println!(
             "ROM File Size must not exceed {} bytes",
@@ -207,6 +228,24 @@ fn main() -> io::Result<()> {
         exit(-1);
     }
 
+    if let Some(path) = args_rom_elf {
+        let elf_bytes = std::fs::read(path)?;
+        match rom::cross_check_rom_elf(&rom_buffer, &elf_bytes) {
+            Ok(None) => {}
+            Ok(Some(offset)) => {
+                println!(
+                    "ROM image {:?} does not match ROM ELF {:?} at offset 0x{:x}",
+                    args_rom, path, offset
+                );
+                exit(-1);
+            }
+            Err(e) => {
+                println!("Failed to parse ROM ELF {:?}: {}", path, e);
+                exit(-1);
+            }
+        }
+    }
+
     let mut current_fw_buf = Vec::new();
     if let Some(path) = args_current_fw {
         if !Path::new(&path).exists() {
@@ -0,0 +1,160 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    rom.rs
+
+Abstract:
+
+    File contains routines for loading ROM images in the formats used by RTL.
+
+--*/
+
+use caliptra_emu_periph::CaliptraRootBus;
+use elf::endian::LittleEndian;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+/// Symbol holding the ROM info that is patched in after linking
+const ROM_INFO_SYMBOL: &str = "CALIPTRA_ROM_INFO";
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Returns true if `path` names a ROM hex image rather than a raw binary.
+pub fn is_hex_path(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("hex"))
+}
+
+/// Parse a ROM image in the Verilog hex format consumed by `$readmemh` in
+/// the RTL testbench (as produced by `objcopy -O verilog`).
+///
+/// `@<addr>` records set the byte address of the following data; every other
+/// token is a single data byte. Gaps between records are filled with zeros.
+pub fn rom_from_hex(text: &str) -> io::Result<Vec<u8>> {
+    let mut rom = vec![];
+    let mut addr = 0usize;
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.split("//").next().unwrap_or_default();
+        for token in line.split_whitespace() {
+            let err = |msg: &str| invalid_data(format!("line {}: {msg} {token:?}", line_no + 1));
+            if let Some(new_addr) = token.strip_prefix('@') {
+                addr = usize::from_str_radix(new_addr, 16)
+                    .map_err(|_| err("invalid address record"))?;
+                continue;
+            }
+            if token.len() != 2 {
+                return Err(err("expected a single byte, found"));
+            }
+            let byte = u8::from_str_radix(token, 16).map_err(|_| err("invalid byte"))?;
+            if addr >= CaliptraRootBus::ROM_SIZE {
+                return Err(invalid_data(format!(
+                    "line {}: data at 0x{addr:x} exceeds the ROM size of 0x{:x} bytes",
+                    line_no + 1,
+                    CaliptraRootBus::ROM_SIZE
+                )));
+            }
+            if rom.len() <= addr {
+                rom.resize(addr + 1, 0);
+            }
+            rom[addr] = byte;
+            addr += 1;
+        }
+    }
+    Ok(rom)
+}
+
+/// Compare a ROM image against the loadable segments of the ROM ELF it was
+/// built from.
+///
+/// The `CALIPTRA_ROM_INFO` structure is filled in after linking and is
+/// excluded from the comparison.
+///
+/// # Returns
+///
+/// The offset of the first mismatching byte, if any.
+pub fn cross_check_rom_elf(rom: &[u8], elf_bytes: &[u8]) -> io::Result<Option<usize>> {
+    let elf = elf::ElfBytes::<LittleEndian>::minimal_parse(elf_bytes)
+        .map_err(|e| invalid_data(e.to_string()))?;
+    let Some(segments) = elf.segments() else {
+        return Err(invalid_data("ELF file has no segments"));
+    };
+
+    let rom_info = rom_info_range(&elf)?;
+    let mut expected = vec![0u8; rom.len()];
+    for segment in segments {
+        if segment.p_type != elf::abi::PT_LOAD || segment.p_filesz == 0 {
+            continue;
+        }
+        let data = elf
+            .segment_data(&segment)
+            .map_err(|e| invalid_data(e.to_string()))?;
+        let start = segment.p_paddr as usize;
+        let end = start + data.len();
+        if end > CaliptraRootBus::ROM_SIZE {
+            return Err(invalid_data(format!(
+                "segment at 0x{start:04x}..0x{end:04x} exceeds the ROM region"
+            )));
+        }
+        if expected.len() < end {
+            expected.resize(end, 0);
+        }
+        expected[start..end].copy_from_slice(data);
+    }
+
+    let len = expected.len().max(rom.len());
+    Ok((0..len).find(|&i| {
+        !rom_info.as_ref().map_or(false, |r| r.contains(&i))
+            && rom.get(i).copied().unwrap_or(0) != expected.get(i).copied().unwrap_or(0)
+    }))
+}
+
+fn rom_info_range(elf: &elf::ElfBytes<LittleEndian>) -> io::Result<Option<Range<usize>>> {
+    let Some((symbols, strings)) = elf
+        .symbol_table()
+        .map_err(|e| invalid_data(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+    Ok(symbols
+        .iter()
+        .find(|sym| strings.get(sym.st_name as usize).ok() == Some(ROM_INFO_SYMBOL))
+        .map(|sym| sym.st_value as usize..(sym.st_value + sym.st_size) as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rom_from_hex() {
+        let rom = rom_from_hex("@00000000\n6F 00 40 0a\n// comment\n@00000008\n13 05\n").unwrap();
+        assert_eq!(rom, [0x6f, 0x00, 0x40, 0x0a, 0, 0, 0, 0, 0x13, 0x05]);
+    }
+
+    #[test]
+    fn test_rom_from_hex_without_address() {
+        let rom = rom_from_hex("01 02\n03\n").unwrap();
+        assert_eq!(rom, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rom_from_hex_invalid() {
+        assert!(rom_from_hex("0102\n").is_err());
+        assert!(rom_from_hex("zz\n").is_err());
+        assert!(rom_from_hex("@zz\n").is_err());
+        assert!(rom_from_hex(&format!("@{:x}\n00\n", CaliptraRootBus::ROM_SIZE)).is_err());
+    }
+
+    #[test]
+    fn test_is_hex_path() {
+        assert!(is_hex_path(Path::new("caliptra-rom.hex")));
+        assert!(is_hex_path(Path::new("caliptra-rom.HEX")));
+        assert!(!is_hex_path(Path::new("caliptra-rom.bin")));
+    }
+}
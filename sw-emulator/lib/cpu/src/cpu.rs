@@ -50,6 +50,8 @@ impl CodeRange {
 pub struct ImageInfo {
     stack_range: StackRange,
     code_range: CodeRange,
+    name: &'static str,
+    peak_stack_usage: u32,
 }
 
 impl ImageInfo {
@@ -57,9 +59,26 @@ impl ImageInfo {
         Self {
             stack_range,
             code_range,
+            name: "image",
+            peak_stack_usage: 0,
         }
     }
 
+    /// Name of the image in stack usage reports
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Largest number of bytes of the stack used by the image so far
+    pub fn peak_stack_usage(&self) -> u32 {
+        self.peak_stack_usage
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
     /// Checks if the program counter is contained in `self`
     ///
     /// returns `true` if the pc is in the image. `false` otherwise.
@@ -93,6 +112,7 @@ pub struct StackInfo {
     images: Vec<ImageInfo>,
     max_stack_overflow: u32,
     has_overflowed: bool,
+    guard_bytes: u32,
 }
 
 impl StackInfo {
@@ -103,8 +123,22 @@ impl StackInfo {
             images,
             max_stack_overflow: 0,
             has_overflowed: false,
+            guard_bytes: 0,
         }
     }
+
+    /// Also fail when the stack pointer comes within `guard_bytes` of the
+    /// end of the stack. Code running this close to the end of its stack is
+    /// likely to overflow it on a slightly different path.
+    pub fn with_guard_bytes(mut self, guard_bytes: u32) -> Self {
+        self.guard_bytes = guard_bytes;
+        self
+    }
+
+    /// The images being monitored, with their peak stack usage
+    pub fn images(&self) -> &[ImageInfo] {
+        &self.images
+    }
 }
 
 impl StackInfo {
@@ -119,7 +153,8 @@ impl StackInfo {
         }
     }
 
-    /// Checks if the stack will overflow when pushed to `stack_address`.
+    /// Checks if the stack will overflow when pushed to `stack_address`,
+    /// and records the stack usage of the image executing at `pc`.
     ///
     /// Returns `Some(u32)` if the stack will overflow and by how much, `None` if it will not overflow.
     fn check_overflow(&mut self, pc: u32, stack_address: u32) -> Option<u32> {
@@ -128,8 +163,10 @@ impl StackInfo {
             return None;
         }
 
-        for image in self.images.iter() {
+        for image in self.images.iter_mut() {
             if image.contains_pc(pc) {
+                let usage = image.stack_range.0.saturating_sub(stack_address);
+                image.peak_stack_usage = image.peak_stack_usage.max(usage);
                 if let Some(overflow_amount) = image.check_overflow(stack_address) {
                     self.max_stack_overflow = self.max_stack_overflow.max(overflow_amount);
                     self.has_overflowed = true;
@@ -140,6 +177,34 @@ impl StackInfo {
 
         None
     }
+
+    /// Checks if `stack_address` is within the guard bytes at the end of the
+    /// stack of the image executing at `pc`.
+    ///
+    /// Returns the number of bytes left on the stack if it is.
+    fn check_guard(&self, pc: u32, stack_address: u32) -> Option<u32> {
+        if stack_address == 0 {
+            return None;
+        }
+
+        self.images
+            .iter()
+            .filter(|image| image.contains_pc(pc))
+            .map(|image| stack_address.saturating_sub(image.stack_range.1))
+            .find(|&remaining| remaining < self.guard_bytes)
+    }
+
+    /// Print the peak stack usage of each image
+    fn report_usage(&self) {
+        for image in self.images.iter() {
+            eprintln!(
+                "[EMU] Peak stack usage of {}: {} of {} bytes",
+                image.name,
+                image.peak_stack_usage,
+                image.stack_range.0 - image.stack_range.1
+            );
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -285,7 +350,9 @@ pub struct Cpu<TBus: Bus> {
 impl<TBus: Bus> Drop for Cpu<TBus> {
     fn drop(&mut self) {
         if let Some(stack_info) = &self.stack_info {
-            if stack_info.has_overflowed {
+            stack_info.report_usage();
+            // An overflow already failed the test when it happened
+            if stack_info.has_overflowed && !std::thread::panicking() {
                 panic!(
                     "[EMU] Fatal: Caliptra's stack overflowed by {} bytes!",
                     stack_info.max_stack_overflow().unwrap()
@@ -339,6 +406,11 @@ impl<TBus: Bus> Cpu<TBus> {
         self.stack_info = Some(stack_info);
     }
 
+    /// Stack usage of each image, if stack monitoring is enabled
+    pub fn stack_info(&self) -> Option<&StackInfo> {
+        self.stack_info.as_ref()
+    }
+
     /// Read the RISCV CPU Program counter
     ///
     ///  # Return
@@ -410,15 +482,23 @@ impl<TBus: Bus> Cpu<TBus> {
         Ok(())
     }
 
-    // Check if the stack overflows at the requested address.
+    // Check if the stack overflows at the requested address. Overflows
+    // corrupt the data below the stack, so fail right away rather than
+    // letting the firmware run on with a corrupted DCCM.
     fn check_stack(&mut self, val: RvData) {
         if let Some(stack_info) = &mut self.stack_info {
             if let Some(overflow_amount) = stack_info.check_overflow(self.pc, val) {
-                eprintln!(
-                    "[EMU] Caliptra's stack overflowed by {} bytes at pc 0x{:x}.",
+                panic!(
+                    "[EMU] Fatal: Caliptra's stack overflowed by {} bytes at pc 0x{:x}.",
                     overflow_amount, self.pc
                 );
             }
+            if let Some(remaining) = stack_info.check_guard(self.pc, val) {
+                panic!(
+                    "[EMU] Fatal: Caliptra's stack came within {} bytes of overflowing at pc 0x{:x}.",
+                    remaining, self.pc
+                );
+            }
         }
     }
 
@@ -685,7 +765,9 @@ impl<TBus: Bus> Cpu<TBus> {
             Ok(_) => (),
             Err(_) => return StepAction::Fatal,
         };
-        let Ok(next_pc) = self.read_bus(RvSize::Word, next_pc_ptr) else { return StepAction::Fatal; };
+        let Ok(next_pc) = self.read_bus(RvSize::Word, next_pc_ptr) else {
+            return StepAction::Fatal;
+        };
         const MACHINE_EXTERNAL_INT: u32 = 0x8000_000B;
         let ret = self.handle_trap(self.read_pc(), MACHINE_EXTERNAL_INT, 0, next_pc);
         match ret {
@@ -784,6 +866,48 @@ mod tests {
         assert_eq!(cpu.read_pc(), 31 * 4);
     }
 
+    fn cpu_with_stack_info(guard_bytes: u32) -> Cpu<DynamicBus> {
+        let mut cpu = Cpu::new(DynamicBus::new(), Clock::new());
+        cpu.with_stack_info(
+            StackInfo::new(vec![ImageInfo::new(
+                StackRange::new(0x2000, 0x1000),
+                CodeRange::new(0, 0x100),
+            )
+            .with_name("test")])
+            .with_guard_bytes(guard_bytes),
+        );
+        cpu
+    }
+
+    #[test]
+    #[cfg(not(feature = "sw_emu_stack_check_disable"))]
+    fn test_peak_stack_usage() {
+        let mut cpu = cpu_with_stack_info(0);
+        cpu.write_xreg(XReg::X2, 0x1800).unwrap();
+        cpu.write_xreg(XReg::X2, 0x1c00).unwrap();
+
+        let images = cpu.stack_info().unwrap().images();
+        assert_eq!(images[0].name(), "test");
+        assert_eq!(images[0].peak_stack_usage(), 0x800);
+    }
+
+    #[test]
+    #[cfg(not(feature = "sw_emu_stack_check_disable"))]
+    #[should_panic(expected = "stack overflowed by 16 bytes")]
+    fn test_stack_overflow() {
+        let mut cpu = cpu_with_stack_info(0);
+        cpu.write_xreg(XReg::X2, 0xff0).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(feature = "sw_emu_stack_check_disable"))]
+    #[should_panic(expected = "came within 16 bytes")]
+    fn test_stack_guard() {
+        let mut cpu = cpu_with_stack_info(0x20);
+        cpu.write_xreg(XReg::X2, 0x1040).unwrap();
+        cpu.write_xreg(XReg::X2, 0x1010).unwrap();
+    }
+
     pub fn count_executed(coverage: &CodeCoverage) -> usize {
         coverage
             .rom_bit_vec
@@ -161,6 +161,13 @@ impl CsrFile {
     ///
     /// * `RvException` - Exception with cause `RvExceptionCause::IllegalRegister``
     pub fn read(&self, addr: RvAddr) -> Result<RvData, RvException> {
+        // The cycle counter follows the simulation clock
+        match addr {
+            Csr::MCYCLE => return Ok(self.timer.now() as u32),
+            Csr::MCYCLEH => return Ok((self.timer.now() >> 32) as u32),
+            _ => {}
+        }
+
         let addr = addr as usize;
         const CSR_MAX: usize = CsrFile::CSR_COUNT - 1;
         match addr {
@@ -269,4 +276,15 @@ mod tests {
         assert_eq!(csrs.write(Csr::MCOUNTINHIBIT, u32::MAX).ok(), Some(()));
         assert_eq!(csrs.read(Csr::MCOUNTINHIBIT).ok(), Some(0x0000_007D));
     }
+
+    #[test]
+    fn test_read_mcycle() {
+        let clock = Clock::new();
+        let csrs = CsrFile::new(&clock);
+        assert_eq!(csrs.read(Csr::MCYCLE).ok(), Some(0));
+
+        clock.increment(0x1_0000_0010);
+        assert_eq!(csrs.read(Csr::MCYCLE).ok(), Some(0x10));
+        assert_eq!(csrs.read(Csr::MCYCLEH).ok(), Some(1));
+    }
 }
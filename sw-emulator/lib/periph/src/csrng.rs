@@ -62,6 +62,24 @@ pub struct Csrng {
     #[register(offset = 0x1040, write_fn = adaptp_lo_thresholds_write)]
     adaptp_lo_thresholds: u32,
 
+    #[register(offset = 0x1058, read_fn = repcnt_hi_watermarks_read)]
+    repcnt_hi_watermarks: ReadOnlyRegister<u32>,
+
+    #[register(offset = 0x1060, read_fn = adaptp_hi_watermarks_read)]
+    adaptp_hi_watermarks: ReadOnlyRegister<u32>,
+
+    #[register(offset = 0x1064, read_fn = adaptp_lo_watermarks_read)]
+    adaptp_lo_watermarks: ReadOnlyRegister<u32>,
+
+    #[register(offset = 0x107c, read_fn = repcnt_total_fails_read)]
+    repcnt_total_fails: ReadOnlyRegister<u32>,
+
+    #[register(offset = 0x1084, read_fn = adaptp_hi_total_fails_read)]
+    adaptp_hi_total_fails: ReadOnlyRegister<u32>,
+
+    #[register(offset = 0x1088, read_fn = adaptp_lo_total_fails_read)]
+    adaptp_lo_total_fails: ReadOnlyRegister<u32>,
+
     #[register(offset = 0x10a4, read_fn = alert_summary_fail_counts_read)]
     alert_summary_fail_counts: ReadOnlyRegister<u32>,
 
@@ -94,6 +112,12 @@ impl Csrng {
             repcnt_thresholds: 0xffffffff,
             adaptp_hi_thresholds: 0xffffffff,
             adaptp_lo_thresholds: 0,
+            repcnt_hi_watermarks: ReadOnlyRegister::new(0),
+            adaptp_hi_watermarks: ReadOnlyRegister::new(0),
+            adaptp_lo_watermarks: ReadOnlyRegister::new(0xffff),
+            repcnt_total_fails: ReadOnlyRegister::new(0),
+            adaptp_hi_total_fails: ReadOnlyRegister::new(0),
+            adaptp_lo_total_fails: ReadOnlyRegister::new(0),
             alert_summary_fail_counts: ReadOnlyRegister::new(0),
             alert_fail_counts: ReadOnlyRegister::new(0),
             main_sm_state: ReadOnlyRegister::new(0x2c), // StartupHTStart, entropy_src_main_sm_pkg.sv
@@ -160,6 +184,7 @@ impl Csrng {
     }
 
     fn repcnt_thresholds_write(&mut self, _: RvSize, data: RvData) -> Result<(), BusError> {
+        self.repcnt_thresholds = data;
         self.health_tester
             .repcnt
             .set_threshold(RepcntThresholdsReadVal::from(data));
@@ -167,6 +192,7 @@ impl Csrng {
     }
 
     fn adaptp_hi_thresholds_write(&mut self, _: RvSize, data: RvData) -> Result<(), BusError> {
+        self.adaptp_hi_thresholds = data;
         self.health_tester
             .adaptp
             .set_hi_threshold(AdaptpHiThresholdsReadVal::from(data));
@@ -174,12 +200,37 @@ impl Csrng {
     }
 
     fn adaptp_lo_thresholds_write(&mut self, _: RvSize, data: RvData) -> Result<(), BusError> {
+        self.adaptp_lo_thresholds = data;
         self.health_tester
             .adaptp
             .set_lo_threshold(AdaptpLoThresholdsReadVal::from(data));
         Ok(())
     }
 
+    fn repcnt_hi_watermarks_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
+        Ok(self.health_tester.repcnt.hi_watermark().min(0xffff))
+    }
+
+    fn adaptp_hi_watermarks_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
+        Ok(self.health_tester.adaptp.hi_watermark().min(0xffff))
+    }
+
+    fn adaptp_lo_watermarks_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
+        Ok(self.health_tester.adaptp.lo_watermark().min(0xffff))
+    }
+
+    fn repcnt_total_fails_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
+        Ok(self.health_tester.repcnt.failures())
+    }
+
+    fn adaptp_hi_total_fails_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
+        Ok(self.health_tester.adaptp.hi_failures())
+    }
+
+    fn adaptp_lo_total_fails_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
+        Ok(self.health_tester.adaptp.lo_failures())
+    }
+
     fn alert_summary_fail_counts_read(&mut self, _: RvSize) -> Result<RvData, BusError> {
         let failures = self.health_tester.failures();
         self.alert_summary_fail_counts = ReadOnlyRegister::new(failures);
@@ -215,6 +266,7 @@ impl Csrng {
 
     fn process_new_cmd(&mut self, data: RvData) {
         const INSTANTIATE: u32 = 1;
+        const RESEED: u32 = 2;
         const GENERATE: u32 = 3;
         const UNINSTANTIATE: u32 = 5;
 
@@ -253,6 +305,20 @@ impl Csrng {
                 }
             }
 
+            RESEED => {
+                const FALSE: u32 = MultiBitBool::False as u32;
+
+                match [flag0, clen] {
+                    [FALSE, 0] => {
+                        // Reseed from entropy_src.
+                        let seed = self.get_conditioned_seed();
+                        self.ctr_drbg.reseed(&seed);
+                    }
+
+                    _ => unimplemented!("reseed: flag0={flag0}, clen={clen}"),
+                }
+            }
+
             GENERATE => {
                 self.ctr_drbg.generate(glen as usize);
             }
@@ -72,6 +72,11 @@ impl CtrDrbg {
         self.update(seed_material);
     }
 
+    pub fn reseed(&mut self, seed: &Seed) {
+        // Section 10.2.1.4 (page 54).
+        self.update(*seed);
+    }
+
     pub fn generate(&mut self, num_128_bit_blocks: usize) {
         // Section 10.2.1.5 (page 55).
 
@@ -77,6 +77,7 @@ pub struct RepetitionCountTester {
     prev_nibble: [Option<Bit>; BITS_PER_NIBBLE],
     repetition_count: [u32; BITS_PER_NIBBLE],
     failures: u32,
+    hi_watermark: u32,
 }
 
 impl RepetitionCountTester {
@@ -86,6 +87,7 @@ impl RepetitionCountTester {
             prev_nibble: [None; BITS_PER_NIBBLE],
             repetition_count: [1; BITS_PER_NIBBLE], // the hardware starts the counter at 1
             failures: 0,
+            hi_watermark: 0,
         }
     }
 
@@ -97,6 +99,10 @@ impl RepetitionCountTester {
         self.failures
     }
 
+    pub fn hi_watermark(&self) -> u32 {
+        self.hi_watermark
+    }
+
     pub fn feed(&mut self, nibble: u8) {
         // Replicate the logic in caliptra-rtl/src/entropy_src/rtl/entropy_src_repcnt_ht.sv.
         // If any of the four RNG wires repeats a bit, increment a wire-specific repetition counter.
@@ -114,6 +120,7 @@ impl RepetitionCountTester {
 
             if is_repeat {
                 self.repetition_count[i] += 1;
+                self.hi_watermark = self.hi_watermark.max(self.repetition_count[i]);
 
                 if self.repetition_count[i] >= self.threshold {
                     self.failures += 1;
@@ -131,6 +138,8 @@ pub struct AdaptiveProportionTester {
     hi_threshold: u32,
     lo_failures: u32,
     hi_failures: u32,
+    lo_watermark: u32,
+    hi_watermark: u32,
     num_ones_seen: u32,
     num_bits_seen: usize,
 }
@@ -142,6 +151,8 @@ impl AdaptiveProportionTester {
             hi_threshold: 0xffff,
             lo_failures: 0,
             hi_failures: 0,
+            lo_watermark: 0xffff,
+            hi_watermark: 0,
             num_ones_seen: 0,
             num_bits_seen: 0,
         }
@@ -163,6 +174,14 @@ impl AdaptiveProportionTester {
         self.hi_failures
     }
 
+    pub fn lo_watermark(&self) -> u32 {
+        self.lo_watermark
+    }
+
+    pub fn hi_watermark(&self) -> u32 {
+        self.hi_watermark
+    }
+
     pub fn feed(&mut self, nibble: u8) {
         // Replicate the logic in caliptra-rtl/src/entropy_src/rtl/entropy_src_adaptp_ht.sv.
         assert!(
@@ -173,6 +192,9 @@ impl AdaptiveProportionTester {
         self.num_bits_seen += BITS_PER_NIBBLE;
 
         if self.num_bits_seen >= HEALTH_TEST_WINDOW_BITS {
+            self.lo_watermark = self.lo_watermark.min(self.num_ones_seen);
+            self.hi_watermark = self.hi_watermark.max(self.num_ones_seen);
+
             if self.num_ones_seen < self.lo_threshold {
                 self.lo_failures += 1;
             }
@@ -144,9 +144,18 @@ impl Doe {
         if self.timer.fired(&mut self.op_complete_action) {
             let key_id = self.control.reg.read(Control::DEST);
             match self.control.reg.read_as_enum(Control::CMD) {
-                Some(Control::CMD::Value::DEOBFUSCATE_UDS) => self.unscramble_uds(key_id),
-                Some(Control::CMD::Value::DEOBFUSCATE_FE) => self.unscramble_fe(key_id),
-                Some(Control::CMD::Value::CLEAR_SECRETS) => self.clear_secrets(),
+                Some(Control::CMD::Value::DEOBFUSCATE_UDS) => {
+                    self.unscramble_uds(key_id);
+                    self.status.reg.modify(Status::UDS_FLOW_DONE::SET);
+                }
+                Some(Control::CMD::Value::DEOBFUSCATE_FE) => {
+                    self.unscramble_fe(key_id);
+                    self.status.reg.modify(Status::FE_FLOW_DONE::SET);
+                }
+                Some(Control::CMD::Value::CLEAR_SECRETS) => {
+                    self.clear_secrets();
+                    self.status.reg.modify(Status::DEOBF_SECRETS_CLEARED::SET);
+                }
                 _ => {}
             }
             self.status
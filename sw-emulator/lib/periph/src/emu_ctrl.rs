@@ -17,28 +17,35 @@ use caliptra_emu_types::{RvAddr, RvData, RvSize};
 use std::process::exit;
 
 /// Emulation Control
-pub struct EmuCtrl {}
+pub struct EmuCtrl {
+    /// Seed for firmware built with the deterministic DRBG
+    drbg_seed: [u32; 12],
+}
 
 impl EmuCtrl {
     // Exit emulator address
     const ADDR_EXIT: RvAddr = 0x0000_0000;
 
+    // Deterministic DRBG seed address range
+    const ADDR_DRBG_SEED_START: RvAddr = 0x0000_0010;
+    const ADDR_DRBG_SEED_END: RvAddr = 0x0000_003c;
+
     /// Create an new instance of emulator control
     ///
     /// # Arguments
     ///
-    /// * `name` - Name of the device
-    pub fn new() -> Self {
-        Self {}
+    /// * `drbg_seed` - Seed for firmware built with the deterministic DRBG
+    pub fn new(drbg_seed: [u32; 12]) -> Self {
+        Self { drbg_seed }
     }
     /// Memory map size.
     pub fn mmap_size(&self) -> RvAddr {
-        4
+        Self::ADDR_DRBG_SEED_END + 4
     }
 }
 impl Default for EmuCtrl {
     fn default() -> Self {
-        Self::new()
+        Self::new([0; 12])
     }
 }
 
@@ -57,6 +64,11 @@ impl Bus for EmuCtrl {
     fn read(&mut self, size: RvSize, addr: RvAddr) -> Result<RvData, BusError> {
         match (size, addr) {
             (RvSize::Word, EmuCtrl::ADDR_EXIT) => Ok(0),
+            (RvSize::Word, EmuCtrl::ADDR_DRBG_SEED_START..=EmuCtrl::ADDR_DRBG_SEED_END)
+                if addr % 4 == 0 =>
+            {
+                Ok(self.drbg_seed[((addr - EmuCtrl::ADDR_DRBG_SEED_START) / 4) as usize])
+            }
             _ => Err(BusError::LoadAccessFault),
         }
     }
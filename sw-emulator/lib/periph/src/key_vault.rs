@@ -16,6 +16,7 @@ use bitfield::bitfield;
 use caliptra_emu_bus::{Bus, BusError, ReadWriteMemory, ReadWriteRegisterArray};
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
+use caliptra_hw_model_types::{DataVaultRegion, VaultEvent};
 use std::cell::RefCell;
 use std::rc::Rc;
 use tock_registers::{register_bitfields, LocalRegisterCopy};
@@ -207,6 +208,12 @@ impl KeyVault {
             .borrow_mut()
             .clear_with_debug_values(sel_debug_value);
     }
+
+    /// Internal emulator interface to drain the vault mutations recorded
+    /// since the last call
+    pub fn take_events(&mut self) -> Vec<VaultEvent> {
+        std::mem::take(&mut self.regs.borrow_mut().events)
+    }
 }
 impl Default for KeyVault {
     fn default() -> Self {
@@ -375,6 +382,9 @@ pub struct KeyVaultRegs {
     #[register_array(offset = 0x0000_44a0, write_fn = write_sticky_lockable_scratch)]
     sticky_lockable_scratch:
         ReadWriteRegisterArray<u32, { STICKY_LOCKABLE_SCRATCH_REG_COUNT as usize }>,
+
+    /// Key, PCR, and DataVault mutations, oldest first
+    events: Vec<VaultEvent>,
 }
 
 impl KeyVaultRegs {
@@ -400,6 +410,7 @@ impl KeyVaultRegs {
                 STICKY_LOCKABLE_SCRATCH_CTRL_REG_RESET_VAL,
             ),
             sticky_lockable_scratch: ReadWriteRegisterArray::new(0),
+            events: Vec::new(),
         }
     }
 
@@ -423,6 +434,22 @@ impl KeyVaultRegs {
         for lockable_scratch_ctrl_reg in self.lockable_scratch_control.iter_mut() {
             lockable_scratch_ctrl_reg.modify(DV_CONTROL::LOCK_ENTRY::CLEAR);
         }
+
+        self.events.push(VaultEvent::LocksReleased);
+    }
+
+    fn record_dv_lock(
+        &mut self,
+        region: DataVaultRegion,
+        entry: usize,
+        val: LocalRegisterCopy<u32, DV_CONTROL::Register>,
+    ) {
+        if val.is_set(DV_CONTROL::LOCK_ENTRY) {
+            self.events.push(VaultEvent::DataVaultLock {
+                region,
+                entry: entry as u32,
+            });
+        }
     }
 
     /// Called by Bus::warm_reset() to indicate a warm reset
@@ -443,9 +470,14 @@ impl KeyVaultRegs {
             PV_CONTROL::LOCK.val(pcr_ctrl_reg.read(PV_CONTROL::LOCK) | val.read(PV_CONTROL::LOCK)),
         );
 
+        if val.is_set(PV_CONTROL::LOCK) {
+            self.events.push(VaultEvent::PcrLock { pcr: index as u32 });
+        }
+
         if pcr_ctrl_reg.read(PV_CONTROL::LOCK) == 0 && val.is_set(PV_CONTROL::CLEAR) {
             let pcr_start = index * constants::PCR_SIZE_WORDS;
             self.pcrs[pcr_start..(pcr_start + PCR_SIZE_WORDS)].fill(0);
+            self.events.push(VaultEvent::PcrClear { pcr: index as u32 });
         }
         Ok(())
     }
@@ -466,10 +498,23 @@ impl KeyVaultRegs {
                 .val(key_ctrl_reg.read(KV_CONTROL::USE_LOCK) | val.read(KV_CONTROL::USE_LOCK)),
         );
 
-        if key_ctrl_reg.read(KV_CONTROL::WRITE_LOCK) == 0 && val.is_set(KV_CONTROL::CLEAR) {
+        let cleared =
+            key_ctrl_reg.read(KV_CONTROL::WRITE_LOCK) == 0 && val.is_set(KV_CONTROL::CLEAR);
+
+        if val.is_set(KV_CONTROL::WRITE_LOCK) || val.is_set(KV_CONTROL::USE_LOCK) {
+            self.events.push(VaultEvent::KeyLock {
+                slot: index as u32,
+                write_lock: val.is_set(KV_CONTROL::WRITE_LOCK),
+                use_lock: val.is_set(KV_CONTROL::USE_LOCK),
+            });
+        }
+
+        if cleared {
             let key_min = index * KeyVault::KEY_SIZE;
             let key_max = key_min + KeyVault::KEY_SIZE;
             self.keys.data_mut()[key_min..key_max].fill(0);
+            self.events
+                .push(VaultEvent::KeyClear { slot: index as u32 });
         }
         Ok(())
     }
@@ -541,6 +586,10 @@ impl KeyVaultRegs {
         // Update the last dword in the key
         key_ctrl_reg.modify(KV_CONTROL::LAST_DWORD.val(key_wordlen as u32 - 1));
 
+        self.events.push(VaultEvent::KeyWrite {
+            slot: key_id,
+            usage: key_usage,
+        });
         Ok(())
     }
 
@@ -581,6 +630,7 @@ impl KeyVaultRegs {
             DV_CONTROL::LOCK_ENTRY
                 .val(ctrl_reg.read(DV_CONTROL::LOCK_ENTRY) | val.read(DV_CONTROL::LOCK_ENTRY)),
         );
+        self.record_dv_lock(DataVaultRegion::StickyDataVault, index, val);
         Ok(())
     }
 
@@ -613,6 +663,7 @@ impl KeyVaultRegs {
             DV_CONTROL::LOCK_ENTRY
                 .val(ctrl_reg.read(DV_CONTROL::LOCK_ENTRY) | val.read(DV_CONTROL::LOCK_ENTRY)),
         );
+        self.record_dv_lock(DataVaultRegion::NonStickyDataVault, index, val);
         Ok(())
     }
 
@@ -644,6 +695,7 @@ impl KeyVaultRegs {
             DV_CONTROL::LOCK_ENTRY
                 .val(ctrl_reg.read(DV_CONTROL::LOCK_ENTRY) | val_reg.read(DV_CONTROL::LOCK_ENTRY)),
         );
+        self.record_dv_lock(DataVaultRegion::NonStickyLockableScratch, index, val_reg);
         Ok(())
     }
 
@@ -675,6 +727,7 @@ impl KeyVaultRegs {
             DV_CONTROL::LOCK_ENTRY
                 .val(ctrl_reg.read(DV_CONTROL::LOCK_ENTRY) | val.read(DV_CONTROL::LOCK_ENTRY)),
         );
+        self.record_dv_lock(DataVaultRegion::StickyLockableScratch, index, val);
         Ok(())
     }
 
@@ -1025,6 +1078,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_events() {
+        let mut vault = KeyVault::new();
+        let key_ctrl_addr = |key_id: u32| {
+            KeyVault::KEY_CONTROL_REG_OFFSET + (key_id * KeyVault::KEY_CONTROL_REG_WIDTH)
+        };
+        let mut key_usage = KeyUsage::default();
+        key_usage.set_hmac_data(true); // dummy usage.
+
+        vault
+            .write_key(2, &[0x5a; KeyVault::KEY_SIZE], u32::from(key_usage))
+            .unwrap();
+        vault.write(RvSize::Word, key_ctrl_addr(2), 0x1).unwrap();
+        // Clearing a write-locked key has no effect.
+        vault.write(RvSize::Word, key_ctrl_addr(2), 0x4).unwrap();
+        vault.write(RvSize::Word, key_ctrl_addr(4), 0x4).unwrap();
+        vault
+            .write(
+                RvSize::Word,
+                PCR_CONTROL_REG_START_OFFSET + PCR_CONTROL_REG_WIDTH,
+                0x1,
+            )
+            .unwrap();
+        vault
+            .write(
+                RvSize::Word,
+                STICKY_DATAVAULT_CTRL_REG_START_OFFSET + 3 * STICKY_DATAVAULT_CTRL_REG_WIDTH,
+                0x1,
+            )
+            .unwrap();
+        vault.warm_reset();
+
+        assert_eq!(
+            vault.take_events(),
+            vec![
+                VaultEvent::KeyWrite {
+                    slot: 2,
+                    usage: u32::from(key_usage)
+                },
+                VaultEvent::KeyLock {
+                    slot: 2,
+                    write_lock: true,
+                    use_lock: false
+                },
+                VaultEvent::KeyClear { slot: 4 },
+                VaultEvent::PcrLock { pcr: 1 },
+                VaultEvent::DataVaultLock {
+                    region: DataVaultRegion::StickyDataVault,
+                    entry: 3
+                },
+                VaultEvent::LocksReleased,
+            ]
+        );
+        assert!(vault.take_events().is_empty());
+    }
+
     #[test]
     fn test_sticky_dv_entry_ctrl_reset_state() {
         let mut vault = KeyVault::new();
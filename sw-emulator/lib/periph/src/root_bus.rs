@@ -225,6 +225,9 @@ pub struct CaliptraRootBusArgs {
 
     pub itrng_nibbles: Option<Box<dyn Iterator<Item = u8>>>,
     pub etrng_responses: Box<dyn Iterator<Item = EtrngResponse>>,
+
+    // Seed read by firmware built with the deterministic DRBG
+    pub drbg_seed: [u32; 12],
 }
 impl Default for CaliptraRootBusArgs {
     fn default() -> Self {
@@ -240,6 +243,7 @@ impl Default for CaliptraRootBusArgs {
             cptra_obf_key: words_from_bytes_be(&DEFAULT_DOE_KEY),
             itrng_nibbles: Some(Box::new(RandomNibbles::new_from_thread_rng())),
             etrng_responses: Box::new(RandomEtrngResponses::new_from_stdrng()),
+            drbg_seed: [0; 12],
         }
     }
 }
@@ -314,6 +318,7 @@ impl CaliptraRootBus {
         let iccm = Iccm::new(clock);
         let pic = Pic::new();
         let itrng_nibbles = args.itrng_nibbles.take();
+        let drbg_seed = args.drbg_seed;
         let soc_reg = SocRegistersInternal::new(clock, mailbox.clone(), iccm.clone(), &pic, args);
         if !soc_reg.is_debug_locked() {
             // When debug is possible, the key-vault is initialized with a debug value...
@@ -335,7 +340,7 @@ impl CaliptraRootBus {
             iccm,
             dccm: Ram::new(vec![0; Self::DCCM_SIZE]),
             uart: Uart::new(),
-            ctrl: EmuCtrl::new(),
+            ctrl: EmuCtrl::new(drbg_seed),
             soc_reg,
             mailbox_sram: mailbox_ram.clone(),
             mailbox,
@@ -104,12 +104,22 @@ mod constants {
     pub const INTERNAL_FW_UPDATE_RESET_START: u32 = 0x624;
     pub const INTERNAL_FW_UPDATE_RESET_WAIT_CYCLES_START: u32 = 0x628;
     pub const INTERNAL_NMI_VECTOR_START: u32 = 0x62c;
+    pub const ERROR_INTERNAL_INTR_START: u32 = 0x814;
 }
 use constants::*;
 
 register_bitfields! [
     u32,
 
+    /// Fatal Hardware Errors
+    HwErrorFatal [
+        ICCM_ECC_UNC OFFSET(0) NUMBITS(1) [],
+        DCCM_ECC_UNC OFFSET(1) NUMBITS(1) [],
+        NMI_PIN OFFSET(2) NUMBITS(1) [],
+        CRYPTO_ERR OFFSET(3) NUMBITS(1) [],
+        RSVD OFFSET(4) NUMBITS(28) [],
+    ],
+
     /// Flow Status
     FlowStatus [
         STATUS OFFSET(0) NUMBITS(23) [],
@@ -489,7 +499,7 @@ impl Bus for SocRegistersExternal {
 #[poll_fn(bus_poll)]
 struct SocRegistersImpl {
     #[register(offset = 0x0000)]
-    cptra_hw_error_fatal: ReadWriteRegister<u32>,
+    cptra_hw_error_fatal: ReadWriteRegister<u32, HwErrorFatal::Register>,
 
     #[register(offset = 0x0004)]
     cptra_hw_error_non_fatal: ReadWriteRegister<u32>,
@@ -695,7 +705,7 @@ struct SocRegistersImpl {
     notif_global_intr_r: ReadWriteRegister<u32, NotifGlobalIntr::Register>,
 
     /// ERROR_INTERNAL_INTR_R Register
-    #[register(offset = 0x0814)]
+    #[register(offset = 0x0814, write_fn = on_write_error_internal_intr)]
     error_internal_intr_r: ReadWriteRegister<u32, ErrorIntrT::Register>,
 
     /// NOTIF_INTERNAL_INTR_R Register
@@ -1058,6 +1068,7 @@ impl SocRegistersImpl {
         self.cptra_wdt_status
             .reg
             .modify(WdtStatus::T1_TIMEOUT::CLEAR);
+        self.stop_wdt_cascade();
 
         // If timer is enabled, schedule a callback on expiry.
         if self.cptra_wdt_timer1_en.reg.is_set(WdtEnable::TIMER_EN) {
@@ -1083,6 +1094,7 @@ impl SocRegistersImpl {
             self.cptra_wdt_status
                 .reg
                 .modify(WdtStatus::T1_TIMEOUT::CLEAR);
+            self.stop_wdt_cascade();
 
             let timer_period: u64 = (self.cptra_wdt_timer1_timeout_period[1] as u64) << 32
                 | self.cptra_wdt_timer1_timeout_period[0] as u64;
@@ -1132,6 +1144,29 @@ impl SocRegistersImpl {
         Ok(())
     }
 
+    /// In cascade mode (WDT2 disabled) WDT2 only runs while a WDT1 timeout is
+    /// pending. Servicing WDT1 stops it before it can raise an NMI.
+    fn stop_wdt_cascade(&mut self) {
+        if !self.cptra_wdt_timer2_en.reg.is_set(WdtEnable::TIMER_EN) {
+            self.op_wdt_timer2_expired_action = None;
+        }
+    }
+
+    // Clear bits on writing 1
+    fn on_write_error_internal_intr(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
+        let reg = self.error_internal_intr_r.reg.get();
+        let clear_bits = reg & val;
+        self.error_internal_intr_r.reg.set(reg ^ clear_bits);
+
+        // Clearing the WDT1 timeout interrupt services the watchdog.
+        if InMemoryRegister::<u32, ErrorIntrT::Register>::new(clear_bits)
+            .is_set(ErrorIntrT::ERROR_WDT_TIMER1_TIMEOUT_STS)
+        {
+            self.stop_wdt_cascade();
+        }
+        Ok(())
+    }
+
     fn on_write_trng_status(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
         let val = CptraTrngStatusReadVal::from(val);
         if val.data_req() && self.pending_etrng_response.is_none() {
@@ -1242,21 +1277,26 @@ impl SocRegistersImpl {
 
         if self.timer.fired(&mut self.op_wdt_timer2_expired_action) {
             self.cptra_wdt_status.reg.modify(WdtStatus::T2_TIMEOUT::SET);
+            self.error_internal_intr_r
+                .reg
+                .modify(ErrorIntrT::ERROR_WDT_TIMER2_TIMEOUT_STS::SET);
 
             // If WDT2 was not scheduled due to WDT1 expiry (i.e WDT2 is disabled), schedule an NMI.
             // Else, do nothing.
             if self.cptra_wdt_timer2_en.reg.is_set(WdtEnable::TIMER_EN) {
-                self.error_internal_intr_r
-                    .reg
-                    .modify(ErrorIntrT::ERROR_WDT_TIMER2_TIMEOUT_STS::SET);
                 return;
             }
 
+            // The cascaded timeout drives the NMI pin, which is reported as a fatal HW error.
+            self.cptra_hw_error_fatal
+                .reg
+                .modify(HwErrorFatal::NMI_PIN::SET);
+
             // Raise an NMI. NMIs don't fire immediately; a couple instructions is a fairly typicaly delay on VeeR.
             const NMI_DELAY: u64 = 2;
 
-            // From RISC-V_VeeR_EL2_PRM.pdf
-            const NMI_CAUSE_WDT_TIMEOUT: u32 = 0x0000_0000; // [TODO] Need correct mcause value.
+            // From RISC-V_VeeR_EL2_PRM.pdf: an NMI signalled on the nmi_int pin reports mcause 0.
+            const NMI_CAUSE_WDT_TIMEOUT: u32 = 0x0000_0000;
 
             self.timer.schedule_action_in(
                 NMI_DELAY,
@@ -1639,4 +1679,172 @@ mod tests {
             })
         );
     }
+
+    fn wdt_soc_reg(
+        clock: &Clock,
+        pic: &Pic,
+        t1_period: u32,
+        t2_period: u32,
+    ) -> SocRegistersInternal {
+        let mut soc_reg = SocRegistersInternal::new(
+            clock,
+            MailboxInternal::new(clock, MailboxRam::new()),
+            Iccm::new(clock),
+            pic,
+            CaliptraRootBusArgs::default(),
+        );
+        soc_reg
+            .write(
+                RvSize::Word,
+                CPTRA_WDT_TIMER1_TIMEOUT_PERIOD_START,
+                t1_period,
+            )
+            .unwrap();
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER1_TIMEOUT_PERIOD_START + 4, 0)
+            .unwrap();
+        soc_reg
+            .write(
+                RvSize::Word,
+                CPTRA_WDT_TIMER2_TIMEOUT_PERIOD_START,
+                t2_period,
+            )
+            .unwrap();
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER2_TIMEOUT_PERIOD_START + 4, 0)
+            .unwrap();
+        soc_reg
+    }
+
+    fn wdt_status(
+        soc_reg: &mut SocRegistersInternal,
+    ) -> InMemoryRegister<u32, WdtStatus::Register> {
+        InMemoryRegister::new(soc_reg.read(RvSize::Word, CPTRA_WDT_STATUS_START).unwrap())
+    }
+
+    #[test]
+    fn test_wdt_cascade_status() {
+        let pic = Pic::new();
+        let clock = Clock::new();
+        let mut soc_reg = wdt_soc_reg(&clock, &pic, 4, 1);
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER1_EN_START, 1)
+            .unwrap();
+
+        let mut nmi = false;
+        for _ in 0..100 {
+            let actions = clock.increment_and_process_timer_actions(1, &mut soc_reg);
+            nmi |= actions.contains(&TimerAction::Nmi { mcause: 0 });
+        }
+        assert!(nmi);
+
+        let status = wdt_status(&mut soc_reg);
+        assert!(status.is_set(WdtStatus::T1_TIMEOUT));
+        assert!(status.is_set(WdtStatus::T2_TIMEOUT));
+
+        let intr = InMemoryRegister::<u32, ErrorIntrT::Register>::new(
+            soc_reg
+                .read(RvSize::Word, ERROR_INTERNAL_INTR_START)
+                .unwrap(),
+        );
+        assert!(intr.is_set(ErrorIntrT::ERROR_WDT_TIMER1_TIMEOUT_STS));
+        assert!(intr.is_set(ErrorIntrT::ERROR_WDT_TIMER2_TIMEOUT_STS));
+
+        let hw_error_fatal = InMemoryRegister::<u32, HwErrorFatal::Register>::new(
+            soc_reg
+                .read(RvSize::Word, CPTRA_HW_ERROR_FATAL_START)
+                .unwrap(),
+        );
+        assert!(hw_error_fatal.is_set(HwErrorFatal::NMI_PIN));
+    }
+
+    #[test]
+    fn test_wdt_cascade_serviced() {
+        let pic = Pic::new();
+        let clock = Clock::new();
+        let mut soc_reg = wdt_soc_reg(&clock, &pic, 4, 50);
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER1_EN_START, 1)
+            .unwrap();
+
+        while !wdt_status(&mut soc_reg).is_set(WdtStatus::T1_TIMEOUT) {
+            clock.increment_and_process_timer_actions(1, &mut soc_reg);
+        }
+
+        // Servicing the WDT1 interrupt stops the cascaded WDT2
+        soc_reg
+            .write(
+                RvSize::Word,
+                ERROR_INTERNAL_INTR_START,
+                ErrorIntrT::ERROR_WDT_TIMER1_TIMEOUT_STS::SET.value,
+            )
+            .unwrap();
+
+        for _ in 0..100 {
+            let actions = clock.increment_and_process_timer_actions(1, &mut soc_reg);
+            assert!(!actions.contains(&TimerAction::Nmi { mcause: 0 }));
+        }
+        assert!(!wdt_status(&mut soc_reg).is_set(WdtStatus::T2_TIMEOUT));
+        assert_eq!(
+            soc_reg
+                .read(RvSize::Word, CPTRA_HW_ERROR_FATAL_START)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_wdt_restart_stops_cascade() {
+        let pic = Pic::new();
+        let clock = Clock::new();
+        let mut soc_reg = wdt_soc_reg(&clock, &pic, 4, 50);
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER1_EN_START, 1)
+            .unwrap();
+
+        while !wdt_status(&mut soc_reg).is_set(WdtStatus::T1_TIMEOUT) {
+            clock.increment_and_process_timer_actions(1, &mut soc_reg);
+        }
+
+        // Disabling WDT1 also stops the cascaded WDT2
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER1_EN_START, 0)
+            .unwrap();
+
+        for _ in 0..100 {
+            let actions = clock.increment_and_process_timer_actions(1, &mut soc_reg);
+            assert!(!actions.contains(&TimerAction::Nmi { mcause: 0 }));
+        }
+        assert!(!wdt_status(&mut soc_reg).is_set(WdtStatus::T2_TIMEOUT));
+    }
+
+    #[test]
+    fn test_wdt_independent_t2() {
+        let pic = Pic::new();
+        let clock = Clock::new();
+        let mut soc_reg = wdt_soc_reg(&clock, &pic, 0xffff_ffff, 4);
+        soc_reg
+            .write(RvSize::Word, CPTRA_WDT_TIMER2_EN_START, 1)
+            .unwrap();
+
+        // An independent WDT2 timeout only raises an error interrupt
+        for _ in 0..100 {
+            let actions = clock.increment_and_process_timer_actions(1, &mut soc_reg);
+            assert!(!actions.contains(&TimerAction::Nmi { mcause: 0 }));
+        }
+        assert!(wdt_status(&mut soc_reg).is_set(WdtStatus::T2_TIMEOUT));
+
+        let intr = InMemoryRegister::<u32, ErrorIntrT::Register>::new(
+            soc_reg
+                .read(RvSize::Word, ERROR_INTERNAL_INTR_START)
+                .unwrap(),
+        );
+        assert!(intr.is_set(ErrorIntrT::ERROR_WDT_TIMER2_TIMEOUT_STS));
+        assert_eq!(
+            soc_reg
+                .read(RvSize::Word, CPTRA_HW_ERROR_FATAL_START)
+                .unwrap(),
+            0
+        );
+    }
 }
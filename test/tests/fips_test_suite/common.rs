@@ -421,7 +421,7 @@ pub fn verify_mbox_cmds_fail<T: HwModel>(hw: &mut T, exp_error_code: u32) {
     };
 
     // Make sure we get the right failure
-    match mbx_send_and_check_resp_hdr::<_, FwInfoResp>(
+    match mbx_send_and_check_resp_hdr::<_, FwInfoV1Resp>(
         hw,
         u32::from(CommandId::FW_INFO),
         payload.as_bytes(),
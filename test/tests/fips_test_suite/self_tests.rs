@@ -1,6 +1,7 @@
 // Licensed under the Apache-2.0 license
 
 use crate::common;
+use crate::services;
 use caliptra_api::SocManager;
 
 use caliptra_builder::firmware::{
@@ -12,6 +13,7 @@ use caliptra_drivers::CaliptraError;
 use caliptra_drivers::FipsTestHook;
 use caliptra_hw_model::{BootParams, HwModel, InitParams, ModelError, ShaAccMode};
 use common::*;
+use services::exec_cmd_self_test_get_results;
 use zerocopy::IntoBytes;
 
 #[test]
@@ -142,7 +144,7 @@ fn self_test_failure_flow_rom(hook_code: u8, exp_error_code: u32) {
     hw.upload_firmware(&fw_image).unwrap();
 }
 
-fn self_test_failure_flow_rt(hook_code: u8, exp_error_code: u32) {
+fn self_test_failure_flow_rt(hook_code: u8, exp_error_code: u32, exp_failed: SelfTestAlgorithms) {
     // Build FW with test hooks and init to runtime
     let fw_image = caliptra_builder::build_and_sign_image(
         &FMC_WITH_UART,
@@ -183,22 +185,27 @@ fn self_test_failure_flow_rt(hook_code: u8, exp_error_code: u32) {
     )
     .unwrap();
 
-    // Wait for error
-    hw.step_until(|m| m.soc_ifc().cptra_fw_error_fatal().read() != 0);
-    // Wait for the remaining operations and cleanup from a fatal error to complete
-    // (This is mainly for the SW emulator which only runs when we step)
-    for _ in 0..1000 {
-        hw.step();
-    }
+    // Wait for the self test to fail and latch the FIPS error state
+    hw.step_until(|m| m.soc_ifc().cptra_fw_error_non_fatal().read() != 0);
 
     // Verify error code is correct
-    assert_eq!(hw.soc_ifc().cptra_fw_error_fatal().read(), exp_error_code);
+    assert_eq!(
+        hw.soc_ifc().cptra_fw_error_non_fatal().read(),
+        exp_error_code
+    );
+
+    // Verify the failing algorithm is reported in the results
+    let results = exec_cmd_self_test_get_results(&mut hw);
+    assert!(SelfTestAlgorithms::from_bits_truncate(results.failed).contains(exp_failed));
+    assert_eq!(results.passed & results.failed, 0);
 
     // Verify we cannot use the algorithm
     match hw.upload_firmware(&fw_image) {
         Ok(_) => panic!("FW Load should fail at this point"),
         Err(act_error) => {
-            if act_error != ModelError::MailboxCmdFailed(exp_error_code) {
+            if act_error
+                != ModelError::MailboxCmdFailed(u32::from(CaliptraError::RUNTIME_FIPS_ERROR_STATE))
+            {
                 panic!("FW Load received unexpected error {}", act_error)
             }
         }
@@ -206,19 +213,6 @@ fn self_test_failure_flow_rt(hook_code: u8, exp_error_code: u32) {
     // Check that the SHA engine is not usable
     verify_sha_engine_output_inhibited(&mut hw);
 
-    // Attempt to clear the error in an undocumented way
-    // Clear the error reg and attempt output again
-    // Now that we have cleared the error, we expect an error code of 0 because
-    // The fatal error loop that marks all mbox messages as failed does not update the error code
-    hw.soc_ifc().cptra_fw_error_fatal().write(|_| 0);
-    hw.soc_ifc().cptra_fw_error_non_fatal().write(|_| 0);
-    match hw.upload_firmware(&fw_image) {
-        Ok(_) => panic!("FW Load should fail at this point"),
-        Err(ModelError::MailboxCmdFailed(0x0)) => (),
-        Err(e) => panic!("FW Load received unexpected error {}", e),
-    }
-    verify_sha_engine_output_inhibited(&mut hw);
-
     // Restart Caliptra
     if cfg!(any(feature = "verilator", feature = "fpga_realtime")) {
         hw.cold_reset();
@@ -252,6 +246,7 @@ pub fn kat_sha1_digest_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA1_DIGEST_FAILURE,
         u32::from(CaliptraError::KAT_SHA1_DIGEST_FAILURE),
+        SelfTestAlgorithms::SHA1,
     );
 }
 
@@ -269,6 +264,7 @@ pub fn kat_sha1_digest_mismatch_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA1_CORRUPT_DIGEST,
         u32::from(CaliptraError::KAT_SHA1_DIGEST_MISMATCH),
+        SelfTestAlgorithms::SHA1,
     );
 }
 
@@ -286,6 +282,7 @@ pub fn kat_sha256_digest_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA256_DIGEST_FAILURE,
         u32::from(CaliptraError::KAT_SHA256_DIGEST_FAILURE),
+        SelfTestAlgorithms::SHA256,
     );
 }
 
@@ -303,6 +300,7 @@ pub fn kat_sha256_digest_mismatch_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA256_CORRUPT_DIGEST,
         u32::from(CaliptraError::KAT_SHA256_DIGEST_MISMATCH),
+        SelfTestAlgorithms::SHA256,
     );
 }
 
@@ -320,6 +318,7 @@ pub fn kat_sha384_digest_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA384_DIGEST_FAILURE,
         u32::from(CaliptraError::KAT_SHA384_DIGEST_FAILURE),
+        SelfTestAlgorithms::SHA384,
     );
 }
 
@@ -337,6 +336,7 @@ pub fn kat_sha384_digest_mismatch_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA384_CORRUPT_DIGEST,
         u32::from(CaliptraError::KAT_SHA384_DIGEST_MISMATCH),
+        SelfTestAlgorithms::SHA384,
     );
 }
 
@@ -354,6 +354,7 @@ pub fn kat_sha2_512_384acc_digest_start_op_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA2_512_384_ACC_START_OP_FAILURE,
         u32::from(CaliptraError::KAT_SHA2_512_384_ACC_DIGEST_START_OP_FAILURE),
+        SelfTestAlgorithms::SHA2_512_384_ACC,
     );
 }
 
@@ -371,6 +372,7 @@ pub fn kat_sha2_512_384acc_digest_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA2_512_384_ACC_DIGEST_512_FAILURE,
         u32::from(CaliptraError::KAT_SHA2_512_384_ACC_DIGEST_FAILURE),
+        SelfTestAlgorithms::SHA2_512_384_ACC,
     );
 }
 
@@ -388,6 +390,7 @@ pub fn kat_sha2_512_384acc_digest_mismatch_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::SHA2_512_384_ACC_CORRUPT_DIGEST_512,
         u32::from(CaliptraError::KAT_SHA2_512_384_ACC_DIGEST_MISMATCH),
+        SelfTestAlgorithms::SHA2_512_384_ACC,
     );
 }
 
@@ -405,6 +408,7 @@ pub fn kat_ecc384_signature_generate_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::ECC384_SIGNATURE_GENERATE_FAILURE,
         u32::from(CaliptraError::KAT_ECC384_KEY_PAIR_GENERATE_FAILURE),
+        SelfTestAlgorithms::ECC384,
     );
 }
 
@@ -422,6 +426,7 @@ pub fn kat_ecc384_signature_verify_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::ECC384_CORRUPT_SIGNATURE,
         u32::from(CaliptraError::KAT_ECC384_SIGNATURE_MISMATCH),
+        SelfTestAlgorithms::ECC384,
     );
 }
 
@@ -439,6 +444,7 @@ pub fn kat_ecc384_deterministic_key_gen_generate_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::ECC384_KEY_PAIR_GENERATE_FAILURE,
         u32::from(CaliptraError::KAT_ECC384_KEY_PAIR_GENERATE_FAILURE),
+        SelfTestAlgorithms::ECC384,
     );
 }
 
@@ -456,6 +462,7 @@ pub fn kat_ecc384_deterministic_key_gen_verify_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::ECC384_CORRUPT_KEY_PAIR,
         u32::from(CaliptraError::KAT_ECC384_KEY_PAIR_VERIFY_FAILURE),
+        SelfTestAlgorithms::ECC384,
     );
 }
 
@@ -473,6 +480,7 @@ pub fn kat_hmac384_failure_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::HMAC384_FAILURE,
         u32::from(CaliptraError::KAT_HMAC384_FAILURE),
+        SelfTestAlgorithms::HMAC384_KDF,
     );
 }
 
@@ -490,6 +498,7 @@ pub fn kat_hmac384_tag_mismatch_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::HMAC384_CORRUPT_TAG,
         u32::from(CaliptraError::KAT_HMAC384_TAG_MISMATCH),
+        SelfTestAlgorithms::HMAC384_KDF,
     );
 }
 
@@ -507,6 +516,7 @@ pub fn kat_lms_digest_mismatch_rt() {
     self_test_failure_flow_rt(
         FipsTestHook::LMS_CORRUPT_INPUT,
         u32::from(CaliptraError::KAT_LMS_DIGEST_MISMATCH),
+        SelfTestAlgorithms::LMS,
     );
 }
 
@@ -77,7 +77,7 @@ pub fn exec_cmd_self_test_start<T: HwModel>(hw: &mut T) {
     .unwrap();
 }
 
-pub fn exec_cmd_self_test_get_results<T: HwModel>(hw: &mut T) {
+pub fn exec_cmd_self_test_get_results<T: HwModel>(hw: &mut T) -> SelfTestGetResultsResp {
     let payload = MailboxReqHeader {
         chksum: caliptra_common::checksum::calc_checksum(
             u32::from(CommandId::SELF_TEST_GET_RESULTS),
@@ -88,13 +88,12 @@ pub fn exec_cmd_self_test_get_results<T: HwModel>(hw: &mut T) {
     // Attempt get_results in a loop until we get a response
     loop {
         // Get self test results
-        match mbx_send_and_check_resp_hdr::<_, MailboxRespHeader>(
+        match mbx_send_and_check_resp_hdr::<_, SelfTestGetResultsResp>(
             hw,
             u32::from(CommandId::SELF_TEST_GET_RESULTS),
             payload.as_bytes(),
         ) {
-            // Nothing extra to do once we see success
-            Ok(_resp) => break,
+            Ok(resp) => return resp,
             Err(ModelError::MailboxCmdFailed(code)) => {
                 if code != u32::from(CaliptraError::RUNTIME_SELF_TEST_NOT_STARTED) {
                     panic!("Unexpected caliptra error code {:#x}", code);
@@ -333,7 +332,7 @@ pub fn exec_fw_info<T: HwModel>(hw: &mut T, fw_image: &[u8]) {
     let payload = MailboxReqHeader {
         chksum: caliptra_common::checksum::calc_checksum(u32::from(CommandId::FW_INFO), &[]),
     };
-    let fw_info_resp = mbx_send_and_check_resp_hdr::<_, FwInfoResp>(
+    let fw_info_resp = mbx_send_and_check_resp_hdr::<_, FwInfoV1Resp>(
         hw,
         u32::from(CommandId::FW_INFO),
         payload.as_bytes(),
@@ -811,5 +810,7 @@ pub fn fips_self_test_rt() {
     exec_cmd_self_test_start(&mut hw);
 
     // SELF TEST GET RESULTS
-    exec_cmd_self_test_get_results(&mut hw);
+    let results = exec_cmd_self_test_get_results(&mut hw);
+    assert_eq!(results.passed, SelfTestAlgorithms::all().bits());
+    assert_eq!(results.failed, 0);
 }
@@ -0,0 +1,333 @@
+// Licensed under the Apache-2.0 license
+
+//! Reference verifier for the evidence returned by
+//! `caliptra_api::evidence::collect_evidence`.
+//!
+//! [`verify`] runs every check against a [`Policy`] and returns a [`Verdict`]
+//! listing the checks that failed, along with the TCB claims read from the
+//! certificates. The claims are taken from signed certificates; FW_INFO is
+//! unsigned and only cross-checked against them.
+//!
+//! The PCR quote is the only signature over the nonce. The DPE leaf key is
+//! derived from the nonce, but a verifier cannot check the derivation.
+
+pub mod x509;
+
+use caliptra_api::evidence::{Evidence, PcrQuote};
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::PointConversionForm,
+    ecdsa::EcdsaSig,
+    error::ErrorStack,
+    hash::{DigestBytes, Hasher, MessageDigest},
+    stack::Stack,
+    x509::{store::X509StoreBuilder, verify::X509VerifyFlags, X509StoreContext, X509},
+};
+use serde_derive::Serialize;
+use x509::DiceTcbInfo;
+
+/// What a relying party requires of the evidence
+#[derive(Clone, Debug, Default)]
+pub struct Policy {
+    /// DER certificates trusted to issue the LDevID certificate, usually the
+    /// IDevID certificate of the device or the CA that issued it
+    pub trust_anchors: Vec<Vec<u8>>,
+
+    /// Nonce the verifier sent to the host
+    pub nonce: Vec<u8>,
+
+    pub min_fuse_svn: u32,
+    pub min_fmc_svn: u32,
+    pub min_runtime_svn: u32,
+}
+
+/// Certificates in the evidence
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Cert {
+    LdevId,
+    FmcAlias,
+    RtAlias,
+    DpeLeaf,
+}
+
+/// Components whose SVN is checked against the policy
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Component {
+    Fuses,
+    Fmc,
+    Runtime,
+}
+
+/// A check that failed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum Failure {
+    MalformedCert(Cert),
+
+    /// The chain from a trust anchor to the DPE leaf certificate did not
+    /// validate
+    CertChain(String),
+
+    MissingTcbInfo(Cert),
+    SvnBelowMinimum {
+        component: Component,
+        svn: u32,
+        min: u32,
+    },
+
+    /// FW_INFO disagrees with the certificates about this field
+    FwInfoMismatch(&'static str),
+
+    /// The nonce of the evidence or of the PCR quote is not the policy nonce
+    NonceMismatch,
+
+    /// The DPE leaf certificate does not certify the reported DPE key
+    DpeKeyMismatch,
+
+    QuoteDigestMismatch,
+    QuoteSignatureInvalid,
+}
+
+/// TCB claims read from the TcbInfo extensions
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TcbClaims {
+    pub fuse_svn: Option<u32>,
+    pub device_flags: Option<u32>,
+    pub fmc_svn: Option<u32>,
+    pub fmc_digest: Option<Vec<u8>>,
+    pub runtime_svn: Option<u32>,
+    pub runtime_digest: Option<Vec<u8>>,
+}
+
+/// Outcome of `verify`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Verdict {
+    pub failures: Vec<Failure>,
+    pub tcb: TcbClaims,
+}
+
+impl Verdict {
+    /// Whether every check passed
+    pub fn is_trusted(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+struct Certs {
+    ldev: X509,
+    fmc_alias: X509,
+    rt_alias: X509,
+    dpe_leaf: X509,
+}
+
+/// Check `evidence` against `policy`
+pub fn verify(evidence: &Evidence, policy: &Policy) -> Verdict {
+    let mut verdict = Verdict::default();
+
+    let parse = |der: &[u8], cert| X509::from_der(der).map_err(|_| Failure::MalformedCert(cert));
+    let certs = match (
+        parse(&evidence.ldev_cert, Cert::LdevId),
+        parse(&evidence.fmc_alias_cert, Cert::FmcAlias),
+        parse(&evidence.rt_alias_cert, Cert::RtAlias),
+        parse(&evidence.dpe_cert, Cert::DpeLeaf),
+    ) {
+        (Ok(ldev), Ok(fmc_alias), Ok(rt_alias), Ok(dpe_leaf)) => Certs {
+            ldev,
+            fmc_alias,
+            rt_alias,
+            dpe_leaf,
+        },
+        (ldev, fmc_alias, rt_alias, dpe_leaf) => {
+            verdict.failures.extend(
+                [ldev.err(), fmc_alias.err(), rt_alias.err(), dpe_leaf.err()]
+                    .into_iter()
+                    .flatten(),
+            );
+            return verdict;
+        }
+    };
+
+    match verify_chain(&certs, &policy.trust_anchors) {
+        Ok(None) => {}
+        Ok(Some(error)) => verdict.failures.push(Failure::CertChain(error)),
+        Err(e) => verdict.failures.push(Failure::CertChain(e.to_string())),
+    }
+
+    read_tcb_claims(evidence, &mut verdict);
+    check_svns(policy, &mut verdict);
+    check_fw_info(evidence, &mut verdict);
+
+    if evidence.nonce != policy.nonce
+        || policy.nonce.get(..32) != Some(&evidence.pcr_quote.nonce[..])
+    {
+        verdict.failures.push(Failure::NonceMismatch);
+    }
+    if !matches!(dpe_key_matches(evidence, &certs.dpe_leaf), Ok(true)) {
+        verdict.failures.push(Failure::DpeKeyMismatch);
+    }
+    if let Err(failure) = check_quote(evidence, &certs.fmc_alias) {
+        verdict.failures.push(failure);
+    }
+
+    verdict
+}
+
+/// Returns the reason the chain did not validate, if it did not
+fn verify_chain(certs: &Certs, trust_anchors: &[Vec<u8>]) -> Result<Option<String>, ErrorStack> {
+    let mut roots = X509StoreBuilder::new()?;
+    for anchor in trust_anchors {
+        roots.add_cert(X509::from_der(anchor)?)?;
+    }
+    // The anchor need not be self-signed
+    roots.set_flags(X509VerifyFlags::X509_STRICT | X509VerifyFlags::PARTIAL_CHAIN)?;
+    let roots = roots.build();
+
+    let mut chain = Stack::new()?;
+    chain.push(certs.ldev.clone())?;
+    chain.push(certs.fmc_alias.clone())?;
+    chain.push(certs.rt_alias.clone())?;
+
+    let mut context = X509StoreContext::new()?;
+    context.init(&roots, &certs.dpe_leaf, &chain, |c| {
+        Ok((!c.verify_cert()?).then(|| c.error().error_string().to_string()))
+    })
+}
+
+/// Caliptra encodes SVNs as `0x100 | svn` so that the DER integer has a fixed
+/// width.
+fn decode_svn(svn: Option<u32>) -> Option<u32> {
+    svn.map(|svn| svn & 0xff)
+}
+
+fn first_fwid(tcb_info: &DiceTcbInfo) -> Option<Vec<u8>> {
+    tcb_info.fwids.first().map(|fwid| fwid.digest.clone())
+}
+
+fn read_tcb_claims(evidence: &Evidence, verdict: &mut Verdict) {
+    // The FMC Alias certificate holds the device entry followed by the FMC
+    // entry.
+    match DiceTcbInfo::find_multiple_in_cert(&evidence.fmc_alias_cert) {
+        Ok(tcb_infos) if tcb_infos.len() == 2 => {
+            verdict.tcb.fuse_svn = decode_svn(tcb_infos[0].svn);
+            verdict.tcb.device_flags = tcb_infos[0].flags;
+            verdict.tcb.fmc_svn = decode_svn(tcb_infos[1].svn);
+            verdict.tcb.fmc_digest = first_fwid(&tcb_infos[1]);
+        }
+        Ok(_) => verdict
+            .failures
+            .push(Failure::MissingTcbInfo(Cert::FmcAlias)),
+        Err(_) => verdict
+            .failures
+            .push(Failure::MalformedCert(Cert::FmcAlias)),
+    }
+
+    match DiceTcbInfo::find_single_in_cert(&evidence.rt_alias_cert) {
+        Ok(Some(tcb_info)) => {
+            verdict.tcb.runtime_svn = decode_svn(tcb_info.svn);
+            verdict.tcb.runtime_digest = first_fwid(&tcb_info);
+        }
+        Ok(None) => verdict
+            .failures
+            .push(Failure::MissingTcbInfo(Cert::RtAlias)),
+        Err(_) => verdict.failures.push(Failure::MalformedCert(Cert::RtAlias)),
+    }
+}
+
+fn check_svns(policy: &Policy, verdict: &mut Verdict) {
+    let svns = [
+        (Component::Fuses, verdict.tcb.fuse_svn, policy.min_fuse_svn),
+        (Component::Fmc, verdict.tcb.fmc_svn, policy.min_fmc_svn),
+        (
+            Component::Runtime,
+            verdict.tcb.runtime_svn,
+            policy.min_runtime_svn,
+        ),
+    ];
+    for (component, svn, min) in svns {
+        // A missing SVN is already reported as missing TcbInfo
+        if let Some(svn) = svn {
+            if svn < min {
+                verdict.failures.push(Failure::SvnBelowMinimum {
+                    component,
+                    svn,
+                    min,
+                });
+            }
+        }
+    }
+}
+
+/// Digests in FW_INFO are words; the certificates hold their big-endian
+/// bytes.
+fn digest_bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_be_bytes()).collect()
+}
+
+fn check_fw_info(evidence: &Evidence, verdict: &mut Verdict) {
+    let fw = &evidence.fw;
+    if let Some(digest) = &verdict.tcb.fmc_digest {
+        if *digest != digest_bytes(&fw.fmc_sha384_digest) {
+            verdict
+                .failures
+                .push(Failure::FwInfoMismatch("fmc_sha384_digest"));
+        }
+    }
+    if let Some(digest) = &verdict.tcb.runtime_digest {
+        if *digest != digest_bytes(&fw.runtime_sha384_digest) {
+            verdict
+                .failures
+                .push(Failure::FwInfoMismatch("runtime_sha384_digest"));
+        }
+    }
+    if let Some(svn) = verdict.tcb.runtime_svn {
+        if svn != fw.runtime_svn {
+            verdict
+                .failures
+                .push(Failure::FwInfoMismatch("runtime_svn"));
+        }
+    }
+}
+
+fn dpe_key_matches(evidence: &Evidence, dpe_leaf: &X509) -> Result<bool, ErrorStack> {
+    let key = dpe_leaf.public_key()?.ec_key()?;
+    let mut ctx = BigNumContext::new()?;
+    let point =
+        key.public_key()
+            .to_bytes(key.group(), PointConversionForm::UNCOMPRESSED, &mut ctx)?;
+    let expected: Vec<u8> = [
+        &[0x04][..],
+        &evidence.dpe_pub_key_x,
+        &evidence.dpe_pub_key_y,
+    ]
+    .concat();
+    Ok(point == expected)
+}
+
+/// The quote signs the SHA-384 hash of the PCRs followed by the nonce
+fn quote_digest(quote: &PcrQuote) -> Result<DigestBytes, ErrorStack> {
+    let mut h = Hasher::new(MessageDigest::sha384())?;
+    for pcr in &quote.pcrs {
+        h.update(pcr)?;
+    }
+    h.update(&quote.nonce)?;
+    h.finish()
+}
+
+fn quote_signature_valid(quote: &PcrQuote, fmc_alias: &X509) -> Result<bool, ErrorStack> {
+    let sig = EcdsaSig::from_private_components(
+        BigNum::from_slice(&quote.signature_r)?,
+        BigNum::from_slice(&quote.signature_s)?,
+    )?;
+    sig.verify(&quote.digest, &fmc_alias.public_key()?.ec_key()?)
+}
+
+fn check_quote(evidence: &Evidence, fmc_alias: &X509) -> Result<(), Failure> {
+    let quote = &evidence.pcr_quote;
+    match quote_digest(quote) {
+        Ok(digest) if *digest == *quote.digest => {}
+        _ => return Err(Failure::QuoteDigestMismatch),
+    }
+    if !matches!(quote_signature_valid(quote, fmc_alias), Ok(true)) {
+        return Err(Failure::QuoteSignatureInvalid);
+    }
+    Ok(())
+}
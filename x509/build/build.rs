@@ -43,6 +43,7 @@ fn main() {
         gen_local_devid_cert(out_dir);
         gen_fmc_alias_cert(out_dir);
         gen_rt_alias_cert(out_dir);
+        gen_rt_alias_csr(out_dir);
     }
 }
 
@@ -138,3 +139,17 @@ fn gen_rt_alias_cert(out_dir: &str) {
     let template = bldr.tbs_template("Caliptra 1.0 Rt Alias", "Caliptra 1.0 FMC Alias");
     CodeGen::gen_code("RtAliasCertTbs", template, out_dir);
 }
+
+/// Generate RT Alias Cert Signing request Template
+#[cfg(feature = "generate_templates")]
+fn gen_rt_alias_csr(out_dir: &str) {
+    let mut usage = KeyUsage::default();
+    usage.set_key_cert_sign(true);
+    usage.set_digital_signature(true);
+    let bldr = csr::CsrTemplateBuilder::<EcdsaSha384Algo>::new()
+        .add_basic_constraints_ext(true, 2)
+        .add_key_usage_ext(usage)
+        .add_ueid_ext(&[0xFF; 17]);
+    let template = bldr.tbs_template("Caliptra 1.0 Rt Alias");
+    CodeGen::gen_code("RtAliasCsrTbs", template, out_dir);
+}
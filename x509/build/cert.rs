@@ -157,6 +157,28 @@ impl<Algo: SigningAlgorithm> CertTemplateBuilder<Algo> {
         self
     }
 
+    /// Add subjectAltPublicKeyInfo extension carrying the LMS alias key
+    ///
+    /// None of the checked-in templates use this yet; the alias certificate
+    /// templates must be regenerated to carry the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `lms_pub_key` - Placeholder LMS public key
+    #[allow(dead_code)]
+    pub fn add_subj_alt_public_key_info_ext(mut self, lms_pub_key: &[u8]) -> Self {
+        self.exts
+            .push(x509::make_subj_alt_public_key_info_ext(lms_pub_key))
+            .unwrap();
+
+        self.params.push(CertTemplateParam {
+            tbs_param: TbsParam::new("SUBJECT_ALT_PUBLIC_KEY", 0, lms_pub_key.len()),
+            needle: lms_pub_key.to_vec(),
+        });
+
+        self
+    }
+
     /// Add Subject Key Id Extension
     ///
     /// # Arguments
@@ -44,6 +44,9 @@ const AUTH_KEY_ID_OID: &str = "2.5.29.35";
 const TCG_UEID_OID: &str = "2.23.133.5.4.4";
 const TCG_TCB_INFO_OID: &str = "2.23.133.5.4.1";
 const TCG_MULTI_TCB_INFO_OID: &str = "2.23.133.5.4.5";
+const SUBJ_ALT_PUBLIC_KEY_INFO_OID: &str = "2.5.29.72";
+const HSS_LMS_HASH_SIG_OID: asn1::ObjectIdentifier =
+    asn1::oid!(/*id-alg-hss-lms-hashsig*/ 1, 2, 840, 113549, 1, 9, 16, 3, 17);
 
 #[derive(asn1::Asn1Write)]
 struct TcbInfo<'a> {
@@ -381,6 +384,36 @@ pub fn make_rt_dice_tcb_info_ext(svn: u8, fwids: &[FwidParam]) -> X509Extension
     X509Extension::new_from_der(&oid, false, &der).unwrap()
 }
 
+/// Make subjectAltPublicKeyInfo extension carrying an HSS/LMS public key
+///
+/// The key is encoded as a single-level HSS public key (RFC 8708), i.e. the
+/// LMS public key prefixed by the big-endian number of levels.
+pub fn make_subj_alt_public_key_info_ext(lms_pub_key: &[u8]) -> X509Extension {
+    #[derive(asn1::Asn1Write)]
+    struct AlgorithmIdentifier {
+        algorithm: asn1::ObjectIdentifier,
+    }
+
+    #[derive(asn1::Asn1Write)]
+    struct SubjectPublicKeyInfo<'a> {
+        algorithm: AlgorithmIdentifier,
+        subject_public_key: asn1::BitString<'a>,
+    }
+
+    let hss_pub_key = [&1u32.to_be_bytes()[..], lms_pub_key].concat();
+    let spki = SubjectPublicKeyInfo {
+        algorithm: AlgorithmIdentifier {
+            algorithm: HSS_LMS_HASH_SIG_OID,
+        },
+        subject_public_key: asn1::BitString::new(&hss_pub_key, 0).unwrap(),
+    };
+
+    let der = asn1::write_single(&spki).unwrap();
+    let der = Asn1OctetString::new_from_bytes(&der).unwrap();
+    let oid = Asn1Object::from_str(SUBJ_ALT_PUBLIC_KEY_INFO_OID).unwrap();
+    X509Extension::new_from_der(&oid, false, &der).unwrap()
+}
+
 /// Retrieve the TBS from DER encoded vector
 ///
 /// Note: Rust OpenSSL binding is missing the extensions to retrieve TBS portion of the X509
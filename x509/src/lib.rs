@@ -20,6 +20,8 @@ mod fmc_alias_csr;
 mod idevid_csr;
 mod ldevid_cert;
 mod rt_alias_cert;
+mod rt_alias_csr;
+mod tbs_ext;
 mod test_util;
 
 pub use cert_bldr::{Ecdsa384CertBuilder, Ecdsa384CsrBuilder, Ecdsa384Signature};
@@ -28,6 +30,11 @@ pub use fmc_alias_csr::{FmcAliasCsrTbs, FmcAliasCsrTbsParams};
 pub use idevid_csr::{InitDevIdCsrTbs, InitDevIdCsrTbsParams};
 pub use ldevid_cert::{LocalDevIdCertTbs, LocalDevIdCertTbsParams};
 pub use rt_alias_cert::{RtAliasCertTbs, RtAliasCertTbsParams};
+pub use rt_alias_csr::{RtAliasCsrTbs, RtAliasCsrTbsParams};
+pub use tbs_ext::{
+    add_extensions, encode_authority_info_access, encode_crl_distribution_point, X509Extension,
+    AUTHORITY_INFO_ACCESS_OID, CRL_DISTRIBUTION_POINTS_OID,
+};
 use zeroize::Zeroize;
 
 pub const NOT_BEFORE: &str = "20230101000000Z";
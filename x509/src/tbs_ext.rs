@@ -0,0 +1,517 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    tbs_ext.rs
+
+Abstract:
+
+    X509 API to add caller-specified extensions to a certificate
+    "To Be Signed" blob generated from a template.
+
+--*/
+
+/// DER Boolean Tag
+const DER_BOOL_TAG: u8 = 0x01;
+
+/// DER Octet String Tag
+const DER_OCTET_STR_TAG: u8 = 0x04;
+
+/// DER Object Identifier Tag
+const DER_OID_TAG: u8 = 0x06;
+
+/// DER Sequence Tag
+const DER_SEQ_TAG: u8 = 0x30;
+
+/// Explicit [3] tag wrapping the TBS certificate extensions
+const DER_TBS_EXTENSIONS_TAG: u8 = 0xA3;
+
+/// Context-specific constructed [0] tag
+const DER_CONTEXT_0_TAG: u8 = 0xA0;
+
+/// GeneralName uniformResourceIdentifier [6] tag
+const DER_GENERAL_NAME_URI_TAG: u8 = 0x86;
+
+/// id-ce-cRLDistributionPoints (2.5.29.31)
+pub const CRL_DISTRIBUTION_POINTS_OID: &[u8] = &[0x55, 0x1D, 0x1F];
+
+/// id-pe-authorityInfoAccess (1.3.6.1.5.5.7.1.1)
+pub const AUTHORITY_INFO_ACCESS_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x01, 0x01];
+
+/// id-ad-caIssuers (1.3.6.1.5.5.7.48.2)
+const CA_ISSUERS_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x02];
+
+/// Caller-specified X509 extension
+#[derive(Debug)]
+pub struct X509Extension<'a> {
+    /// Contents of the DER encoded OBJECT IDENTIFIER, without tag and length
+    pub oid: &'a [u8],
+
+    /// Flag indicating if the extension is critical
+    pub critical: bool,
+
+    /// DER encoded extension value, wrapped in the extnValue OCTET STRING
+    pub value: &'a [u8],
+}
+
+impl X509Extension<'_> {
+    /// Length of the DER encoded Extension sequence contents
+    fn content_len(&self) -> Option<usize> {
+        let critical_len = if self.critical { 3 } else { 0 };
+        Some(der_tlv_len(self.oid.len())? + critical_len + der_tlv_len(self.value.len())?)
+    }
+
+    /// DER encode the extension
+    fn encode(&self, buf: &mut [u8]) -> Option<usize> {
+        let mut pos = der_encode_header(DER_SEQ_TAG, self.content_len()?, buf)?;
+
+        pos += der_encode_header(DER_OID_TAG, self.oid.len(), buf.get_mut(pos..)?)?;
+        buf.get_mut(pos..pos + self.oid.len())?
+            .copy_from_slice(self.oid);
+        pos += self.oid.len();
+
+        if self.critical {
+            buf.get_mut(pos..pos + 3)?
+                .copy_from_slice(&[DER_BOOL_TAG, 1, 0xFF]);
+            pos += 3;
+        }
+
+        pos += der_encode_header(DER_OCTET_STR_TAG, self.value.len(), buf.get_mut(pos..)?)?;
+        buf.get_mut(pos..pos + self.value.len())?
+            .copy_from_slice(self.value);
+        pos += self.value.len();
+
+        Some(pos)
+    }
+}
+
+/// Add extensions to a DER encoded TBS certificate
+///
+/// The extensions are appended after any extensions already in the TBS, and
+/// the lengths of the enclosing structures are re-encoded. The result can be
+/// signed and passed to `Ecdsa384CertBuilder` like a TBS built from a
+/// template.
+///
+/// # Arguments
+///
+/// * `tbs` - DER encoded TBS certificate
+/// * `exts` - Extensions to add
+/// * `buf` - Buffer to construct the new TBS certificate in
+///
+/// # Returns
+///
+/// * `usize` - Length of the new TBS certificate
+pub fn add_extensions(tbs: &[u8], exts: &[X509Extension], buf: &mut [u8]) -> Option<usize> {
+    let (tag, hdr_len, len) = der_decode_header(tbs)?;
+    if tag != DER_SEQ_TAG || hdr_len + len != tbs.len() {
+        return None;
+    }
+
+    // Find the existing extensions, which are always the last TBS field
+    let mut pos = hdr_len;
+    let mut fields_end = tbs.len();
+    let mut existing_exts: &[u8] = &[];
+    while pos < tbs.len() {
+        let (tag, hdr_len, len) = der_decode_header(tbs.get(pos..)?)?;
+        let field_end = pos + hdr_len + len;
+        if tag == DER_TBS_EXTENSIONS_TAG {
+            if field_end != tbs.len() {
+                return None;
+            }
+            let ext_seq = tbs.get(pos + hdr_len..field_end)?;
+            let (tag, seq_hdr_len, seq_len) = der_decode_header(ext_seq)?;
+            if tag != DER_SEQ_TAG || seq_hdr_len + seq_len != ext_seq.len() {
+                return None;
+            }
+            fields_end = pos;
+            existing_exts = ext_seq.get(seq_hdr_len..)?;
+        }
+        pos = field_end;
+    }
+    if pos != tbs.len() {
+        return None;
+    }
+
+    let mut exts_len = existing_exts.len();
+    for ext in exts {
+        exts_len += der_tlv_len(ext.content_len()?)?;
+    }
+    let exts_field_len = der_tlv_len(der_tlv_len(exts_len)?)?;
+    let fields = tbs.get(hdr_len..fields_end)?;
+
+    // Re-encode the TBS sequence
+    let mut pos = der_encode_header(DER_SEQ_TAG, fields.len() + exts_field_len, buf)?;
+    buf.get_mut(pos..pos + fields.len())?
+        .copy_from_slice(fields);
+    pos += fields.len();
+
+    // Re-encode the extensions
+    pos += der_encode_header(
+        DER_TBS_EXTENSIONS_TAG,
+        der_tlv_len(exts_len)?,
+        buf.get_mut(pos..)?,
+    )?;
+    pos += der_encode_header(DER_SEQ_TAG, exts_len, buf.get_mut(pos..)?)?;
+    buf.get_mut(pos..pos + existing_exts.len())?
+        .copy_from_slice(existing_exts);
+    pos += existing_exts.len();
+    for ext in exts {
+        pos += ext.encode(buf.get_mut(pos..)?)?;
+    }
+
+    Some(pos)
+}
+
+/// Encode a CRL Distribution Points extension value with a single
+/// distribution point identified by a URI
+///
+/// # Arguments
+///
+/// * `uri` - IA5String URI of the CRL
+/// * `buf` - Buffer to encode the extension value in
+///
+/// # Returns
+///
+/// * `usize` - Length of the extension value
+pub fn encode_crl_distribution_point(uri: &[u8], buf: &mut [u8]) -> Option<usize> {
+    // SEQUENCE OF DistributionPoint { distributionPoint [0] { fullName [0] { uri } } }
+    der_encode_nested(
+        &[
+            DER_SEQ_TAG,
+            DER_SEQ_TAG,
+            DER_CONTEXT_0_TAG,
+            DER_CONTEXT_0_TAG,
+        ],
+        &[(DER_GENERAL_NAME_URI_TAG, uri)],
+        buf,
+    )
+}
+
+/// Encode an Authority Information Access extension value with a single
+/// caIssuers access description identified by a URI
+///
+/// # Arguments
+///
+/// * `uri` - IA5String URI of the issuer certificate
+/// * `buf` - Buffer to encode the extension value in
+///
+/// # Returns
+///
+/// * `usize` - Length of the extension value
+pub fn encode_authority_info_access(uri: &[u8], buf: &mut [u8]) -> Option<usize> {
+    // SEQUENCE OF AccessDescription { caIssuers, uri }
+    der_encode_nested(
+        &[DER_SEQ_TAG, DER_SEQ_TAG],
+        &[
+            (DER_OID_TAG, CA_ISSUERS_OID),
+            (DER_GENERAL_NAME_URI_TAG, uri),
+        ],
+        buf,
+    )
+}
+
+/// DER encode `tlvs` nested inside the constructed `tags`, outermost first
+fn der_encode_nested(tags: &[u8], tlvs: &[(u8, &[u8])], buf: &mut [u8]) -> Option<usize> {
+    let mut contents_len = 0;
+    for (_, value) in tlvs {
+        contents_len += der_tlv_len(value.len())?;
+    }
+
+    let mut pos = 0;
+    for (i, &tag) in tags.iter().enumerate() {
+        let mut len = contents_len;
+        for _ in tags.get(i + 1..)? {
+            len = der_tlv_len(len)?;
+        }
+        pos += der_encode_header(tag, len, buf.get_mut(pos..)?)?;
+    }
+    for &(tag, value) in tlvs {
+        pos += der_encode_header(tag, value.len(), buf.get_mut(pos..)?)?;
+        buf.get_mut(pos..pos + value.len())?.copy_from_slice(value);
+        pos += value.len();
+    }
+
+    Some(pos)
+}
+
+/// Length of a DER TLV with `len` bytes of contents
+fn der_tlv_len(len: usize) -> Option<usize> {
+    let len_bytes = match len {
+        0..=127 => 1,
+        128..=255 => 2,
+        256..=0xFFFF => 3,
+        _ => return None,
+    };
+    Some(1 + len_bytes + len)
+}
+
+/// DER encode a tag and length, returning the size of the header
+fn der_encode_header(tag: u8, len: usize, buf: &mut [u8]) -> Option<usize> {
+    *buf.get_mut(0)? = tag;
+    match len {
+        0..=127 => {
+            *buf.get_mut(1)? = len as u8;
+            Some(2)
+        }
+        128..=255 => {
+            buf.get_mut(1..3)?.copy_from_slice(&[0x81, len as u8]);
+            Some(3)
+        }
+        256..=0xFFFF => {
+            buf.get_mut(1..4)?
+                .copy_from_slice(&[0x82, (len >> 8) as u8, len as u8]);
+            Some(4)
+        }
+        _ => None,
+    }
+}
+
+/// Decode a DER tag and length, returning the tag, the size of the header
+/// and the length of the contents
+fn der_decode_header(buf: &[u8]) -> Option<(u8, usize, usize)> {
+    let tag = *buf.first()?;
+    let (hdr_len, len) = match *buf.get(1)? {
+        len @ 0..=127 => (2, len as usize),
+        0x81 => (3, *buf.get(2)? as usize),
+        0x82 => (4, (*buf.get(2)? as usize) << 8 | *buf.get(3)? as usize),
+        _ => return None,
+    };
+    if buf.len() < hdr_len + len {
+        return None;
+    }
+    Some((tag, hdr_len, len))
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use openssl::ecdsa::EcdsaSig;
+    use openssl::sha::Sha384;
+    use openssl::x509::X509;
+
+    use x509_parser::nom::Parser;
+    use x509_parser::oid_registry::asn1_rs::oid;
+    use x509_parser::oid_registry::Oid;
+    use x509_parser::prelude::X509CertificateParser;
+
+    use super::*;
+    use crate::test_util::tests::*;
+    use crate::{NotAfter, NotBefore, RtAliasCertTbs, RtAliasCertTbsParams};
+
+    // tcg-dice-TcbInfo
+    const TCB_INFO_OID: &[u8] = &[0x67, 0x81, 0x05, 0x05, 0x04, 0x01];
+
+    // TcbInfo { vendor: "Caliptra" }
+    const TCB_INFO_VALUE: &[u8] = &[
+        0x30, 0x0A, 0x80, 0x08, b'C', b'a', b'l', b'i', b'p', b't', b'r', b'a',
+    ];
+
+    // A value long enough to need a multi-byte length
+    const LONG_VALUE: [u8; 300] = {
+        let mut value = [0xAB; 300];
+        value[0] = DER_OCTET_STR_TAG;
+        value[1] = 0x82;
+        value[2] = 0x01;
+        value[3] = 0x28;
+        value
+    };
+
+    #[test]
+    fn test_add_extensions() {
+        let subject_key = Ecc384AsymKey::default();
+        let issuer_key = Ecc384AsymKey::default();
+        let ec_key = issuer_key.priv_key().ec_key().unwrap();
+
+        let params = RtAliasCertTbsParams {
+            serial_number: &[0xABu8; RtAliasCertTbsParams::SERIAL_NUMBER_LEN],
+            public_key: subject_key.pub_key().try_into().unwrap(),
+            subject_sn: &subject_key.hex_str().into_bytes().try_into().unwrap(),
+            issuer_sn: &issuer_key.hex_str().into_bytes().try_into().unwrap(),
+            ueid: &[0xAB; RtAliasCertTbsParams::UEID_LEN],
+            subject_key_id: &subject_key.sha1(),
+            authority_key_id: &issuer_key.sha1(),
+            tcb_info_rt_svn: &[0xE3],
+            tcb_info_rt_tci: &[0xEFu8; RtAliasCertTbsParams::TCB_INFO_RT_TCI_LEN],
+            not_before: &NotBefore::default().value,
+            not_after: &NotAfter::default().value,
+        };
+        let cert = RtAliasCertTbs::new(&params);
+
+        let exts = [
+            X509Extension {
+                oid: TCB_INFO_OID,
+                critical: true,
+                value: TCB_INFO_VALUE,
+            },
+            X509Extension {
+                // 1.3.6.1.4.1.311.1
+                oid: &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x01],
+                critical: false,
+                value: &LONG_VALUE,
+            },
+        ];
+        let mut tbs = [0u8; 2048];
+        let tbs_len = add_extensions(cert.tbs(), &exts, &mut tbs).unwrap();
+        let tbs = &tbs[..tbs_len];
+
+        let mut sha = Sha384::new();
+        sha.update(tbs);
+        let sig = EcdsaSig::sign(&sha.finish(), &ec_key).unwrap();
+        let ecdsa_sig = crate::Ecdsa384Signature {
+            r: sig.r().to_vec_padded(48).unwrap().try_into().unwrap(),
+            s: sig.s().to_vec_padded(48).unwrap().try_into().unwrap(),
+        };
+
+        let builder = crate::Ecdsa384CertBuilder::new(tbs, &ecdsa_sig).unwrap();
+        let mut buf = vec![0u8; builder.len()];
+        builder.build(&mut buf).unwrap();
+
+        let cert: X509 = X509::from_der(&buf).unwrap();
+        assert!(cert.verify(issuer_key.priv_key()).unwrap());
+
+        let mut parser = X509CertificateParser::new().with_deep_parse_extensions(false);
+        let (_, parsed_cert) = parser.parse(&buf).unwrap();
+        let ext_map = parsed_cert.extensions_map().unwrap();
+
+        // The template's extensions are kept
+        const UEID_OID: Oid = oid!(2.23.133 .5 .4 .4);
+        assert!(ext_map.contains_key(&UEID_OID));
+
+        const TCB_INFO: Oid = oid!(2.23.133 .5 .4 .1);
+        assert!(ext_map[&TCB_INFO].critical);
+        assert_eq!(ext_map[&TCB_INFO].value, TCB_INFO_VALUE);
+
+        const LONG: Oid = oid!(1.3.6 .1 .4 .1 .311 .1);
+        assert!(!ext_map[&LONG].critical);
+        assert_eq!(ext_map[&LONG].value, LONG_VALUE);
+    }
+
+    #[test]
+    fn test_distribution_point_extensions() {
+        use x509_parser::extensions::{DistributionPointName, GeneralName, ParsedExtension};
+
+        const CRL_URI: &[u8] = b"http://crl.example.com/caliptra.crl";
+        const AIA_URI: &[u8] = b"http://pki.example.com/caliptra-fmc-alias.der";
+
+        let subject_key = Ecc384AsymKey::default();
+        let issuer_key = Ecc384AsymKey::default();
+        let ec_key = issuer_key.priv_key().ec_key().unwrap();
+
+        let params = RtAliasCertTbsParams {
+            serial_number: &[0xABu8; RtAliasCertTbsParams::SERIAL_NUMBER_LEN],
+            public_key: subject_key.pub_key().try_into().unwrap(),
+            subject_sn: &subject_key.hex_str().into_bytes().try_into().unwrap(),
+            issuer_sn: &issuer_key.hex_str().into_bytes().try_into().unwrap(),
+            ueid: &[0xAB; RtAliasCertTbsParams::UEID_LEN],
+            subject_key_id: &subject_key.sha1(),
+            authority_key_id: &issuer_key.sha1(),
+            tcb_info_rt_svn: &[0xE3],
+            tcb_info_rt_tci: &[0xEFu8; RtAliasCertTbsParams::TCB_INFO_RT_TCI_LEN],
+            not_before: &NotBefore::default().value,
+            not_after: &NotAfter::default().value,
+        };
+        let cert = RtAliasCertTbs::new(&params);
+
+        let mut crl_value = [0u8; 128];
+        let crl_len = encode_crl_distribution_point(CRL_URI, &mut crl_value).unwrap();
+        let mut aia_value = [0u8; 128];
+        let aia_len = encode_authority_info_access(AIA_URI, &mut aia_value).unwrap();
+        assert_eq!(encode_authority_info_access(AIA_URI, &mut [0u8; 16]), None);
+
+        let exts = [
+            X509Extension {
+                oid: CRL_DISTRIBUTION_POINTS_OID,
+                critical: false,
+                value: &crl_value[..crl_len],
+            },
+            X509Extension {
+                oid: AUTHORITY_INFO_ACCESS_OID,
+                critical: false,
+                value: &aia_value[..aia_len],
+            },
+        ];
+        let mut tbs = [0u8; 2048];
+        let tbs_len = add_extensions(cert.tbs(), &exts, &mut tbs).unwrap();
+        let tbs = &tbs[..tbs_len];
+
+        let mut sha = Sha384::new();
+        sha.update(tbs);
+        let sig = EcdsaSig::sign(&sha.finish(), &ec_key).unwrap();
+        let ecdsa_sig = crate::Ecdsa384Signature {
+            r: sig.r().to_vec_padded(48).unwrap().try_into().unwrap(),
+            s: sig.s().to_vec_padded(48).unwrap().try_into().unwrap(),
+        };
+
+        let builder = crate::Ecdsa384CertBuilder::new(tbs, &ecdsa_sig).unwrap();
+        let mut buf = vec![0u8; builder.len()];
+        builder.build(&mut buf).unwrap();
+
+        let cert: X509 = X509::from_der(&buf).unwrap();
+        assert!(cert.verify(issuer_key.priv_key()).unwrap());
+
+        let mut parser = X509CertificateParser::new();
+        let (_, parsed_cert) = parser.parse(&buf).unwrap();
+
+        let mut found_crl = false;
+        let mut found_aia = false;
+        for ext in parsed_cert.extensions() {
+            match ext.parsed_extension() {
+                ParsedExtension::CRLDistributionPoints(points) => {
+                    assert_eq!(points.points.len(), 1);
+                    let Some(DistributionPointName::FullName(names)) =
+                        &points.points[0].distribution_point
+                    else {
+                        panic!("Missing CRL distribution point name");
+                    };
+                    assert_eq!(names.len(), 1);
+                    assert!(matches!(names[0], GeneralName::URI(uri) if uri.as_bytes() == CRL_URI));
+                    found_crl = true;
+                }
+                ParsedExtension::AuthorityInfoAccess(aia) => {
+                    assert_eq!(aia.accessdescs.len(), 1);
+                    const CA_ISSUERS: Oid = oid!(1.3.6 .1 .5 .5 .7 .48 .2);
+                    assert_eq!(aia.accessdescs[0].access_method, CA_ISSUERS);
+                    assert!(matches!(
+                        aia.accessdescs[0].access_location,
+                        GeneralName::URI(uri) if uri.as_bytes() == AIA_URI
+                    ));
+                    found_aia = true;
+                }
+                _ => {}
+            }
+        }
+        assert!(found_crl && found_aia);
+    }
+
+    #[test]
+    fn test_add_extensions_errors() {
+        let exts = [X509Extension {
+            oid: TCB_INFO_OID,
+            critical: false,
+            value: TCB_INFO_VALUE,
+        }];
+        let mut buf = [0u8; 64];
+
+        // Not a sequence
+        assert_eq!(add_extensions(&[0x31, 0x00], &exts, &mut buf), None);
+
+        // Truncated
+        assert_eq!(
+            add_extensions(&[0x30, 0x05, 0x02, 0x01], &exts, &mut buf),
+            None
+        );
+
+        // Buffer too small
+        let tbs = [0x30, 0x03, 0x02, 0x01, 0x01];
+        assert_eq!(add_extensions(&tbs, &exts, &mut buf[..20]), None);
+
+        // A TBS without extensions gets a new extensions field
+        let len = add_extensions(&tbs, &exts, &mut buf).unwrap();
+        assert_eq!(
+            &buf[..9],
+            &[0x30, 0x1F, 0x02, 0x01, 0x01, 0xA3, 0x1A, 0x30, 0x18]
+        );
+        assert_eq!(len, 33);
+    }
+}